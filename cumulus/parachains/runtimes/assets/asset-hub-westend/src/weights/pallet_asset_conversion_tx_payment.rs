@@ -70,6 +70,12 @@ impl<T: frame_system::Config> pallet_asset_conversion_tx_payment::WeightInfo for
 			.saturating_add(T::DbWeight::get().reads(2))
 			.saturating_add(T::DbWeight::get().writes(1))
 	}
+	/// Storage: `AssetConversion::PoolCurves` (r:1 w:0)
+	/// Proof: `AssetConversion::PoolCurves` (`max_values`: None, `max_size`: Some(1237), added: 3712, mode: `MaxEncodedLen`)
+	/// Storage: `AssetConversion::PriceAccumulators` (r:1 w:1)
+	/// Proof: `AssetConversion::PriceAccumulators` (`max_values`: None, `max_size`: Some(1296), added: 3771, mode: `MaxEncodedLen`)
+	/// Storage: `AssetConversion::PriceObservations` (r:0 w:1)
+	/// Proof: `AssetConversion::PriceObservations` (`max_values`: None, `max_size`: Some(1268), added: 3743, mode: `MaxEncodedLen`)
 	/// Storage: `ForeignAssets::Asset` (r:1 w:1)
 	/// Proof: `ForeignAssets::Asset` (`max_values`: None, `max_size`: Some(808), added: 3283, mode: `MaxEncodedLen`)
 	/// Storage: `ForeignAssets::Account` (r:2 w:2)
@@ -87,5 +93,9 @@ impl<T: frame_system::Config> pallet_asset_conversion_tx_payment::WeightInfo for
 			.saturating_add(Weight::from_parts(0, 7404))
 			.saturating_add(T::DbWeight::get().reads(7))
 			.saturating_add(T::DbWeight::get().writes(4))
+			// Not benchmarked yet: the curve and the price accumulator of the pool of the fee swap.
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().writes(2))
+			.saturating_add(Weight::from_parts(0, 7483))
 	}
 }
@@ -19,7 +19,7 @@
 
 use alloy_core::{
 	primitives::U256,
-	sol_types::{sol_data, SolType},
+	sol_types::{sol_data, SolCall, SolType},
 };
 use asset_hub_westend_runtime::{
 	xcm_config,
@@ -29,7 +29,7 @@ use asset_hub_westend_runtime::{
 	},
 	AllPalletsWithoutSystem, Assets, Balances, Block, ExistentialDeposit, ForeignAssets,
 	ForeignAssetsInstance, MetadataDepositBase, MetadataDepositPerByte, ParachainSystem,
-	PolkadotXcm, Revive, Runtime, RuntimeCall, RuntimeEvent, RuntimeOrigin, SessionKeys,
+	PolkadotXcm, ProxyType, Revive, Runtime, RuntimeCall, RuntimeEvent, RuntimeOrigin, SessionKeys,
 	ToRococoXcmRouterInstance, TrustBackedAssetsInstance, XcmpQueue,
 };
 pub use asset_hub_westend_runtime::{AssetConversion, AssetDeposit, CollatorSelection, System};
@@ -50,9 +50,10 @@ use frame_support::{
 	weights::{Weight, WeightToFee as WeightToFeeT},
 };
 use hex_literal::hex;
+use pallet_proxy::{precompiles::IProxy, Proxies, ProxyDefinition};
 use pallet_revive::{
-	test_utils::builder::{BareInstantiateBuilder, Contract},
-	Code, DepositLimit,
+	test_utils::builder::{BareCallBuilder, BareInstantiateBuilder, Contract},
+	AddressMapper, Code, DepositLimit,
 };
 use pallet_revive_fixtures::compile_module;
 use parachains_common::{AccountId, AssetIdForTrustBackedAssets, AuraId, Balance};
@@ -1759,3 +1760,47 @@ fn expensive_erc20_runs_out_of_gas() {
 		.is_err());
 	});
 }
+
+#[test]
+fn contract_can_add_proxy_through_precompile() {
+	let sender: AccountId = ALICE.into();
+	let delegate: AccountId = BOB.into();
+	let initial_wnd_amount = 10_000_000_000_000u128;
+
+	ExtBuilder::<Runtime>::default().build().execute_with(|| {
+		assert_ok!(Balances::mint_into(&sender, initial_wnd_amount));
+		assert_ok!(Balances::mint_into(&delegate, initial_wnd_amount));
+		assert_ok!(Revive::map_account(RuntimeOrigin::signed(sender.clone())));
+		assert_ok!(Revive::map_account(RuntimeOrigin::signed(delegate.clone())));
+
+		// This contract forwards its input to the address passed in front of it.
+		let (code, _) = compile_module("call_and_return").unwrap();
+		let Contract { addr, account_id } = bare_instantiate(&sender, code)
+			.gas_limit(Weight::from_parts(2_000_000_000, 200_000))
+			.storage_deposit_limit(DepositLimit::Balance(Balance::MAX))
+			.build_and_unwrap_contract();
+		// The contract pays the proxy deposit.
+		assert_ok!(Balances::mint_into(&account_id, initial_wnd_amount));
+
+		let precompile = hex!("00000000000000000000000000000000000B0000");
+		let add_proxy = IProxy::addProxyCall {
+			delegate: <Runtime as pallet_revive::Config>::AddressMapper::to_address(&delegate)
+				.0
+				.into(),
+			proxyType: ProxyType::Any.encode().into(),
+			delay: 0,
+		}
+		.abi_encode();
+		let result = BareCallBuilder::<Runtime>::bare_call(RuntimeOrigin::signed(sender), addr)
+			.gas_limit(Weight::from_parts(2_000_000_000, 200_000))
+			.storage_deposit_limit(DepositLimit::Balance(Balance::MAX))
+			.data([&precompile[..], &0u64.to_le_bytes(), &add_proxy].concat())
+			.build_and_unwrap_result();
+		assert!(!result.did_revert());
+
+		assert_eq!(
+			Proxies::<Runtime>::get(&account_id).0.into_inner(),
+			vec![ProxyDefinition { delegate, proxy_type: ProxyType::Any, delay: 0 }]
+		);
+	});
+}
@@ -287,4 +287,68 @@ impl<T: frame_system::Config> pallet_scheduler::WeightInfo for WeightInfo<T> {
 			.saturating_add(T::DbWeight::get().reads(2))
 			.saturating_add(T::DbWeight::get().writes(1))
 	}
+	/// Storage: `Scheduler::TimeIncompleteSince` (r:1 w:1)
+	/// Proof: `Scheduler::TimeIncompleteSince` (`max_values`: Some(1), `max_size`: Some(8), added: 503, mode: `MaxEncodedLen`)
+	/// Storage: `Timestamp::Now` (r:1 w:0)
+	/// Proof: `Timestamp::Now` (`max_values`: Some(1), `max_size`: Some(8), added: 503, mode: `MaxEncodedLen`)
+	fn service_time_agendas_base() -> Weight {
+		// Not benchmarked yet: like `service_agendas_base`, with the larger
+		// `Scheduler::TimeIncompleteSince` and reading the time from `Timestamp::Now`.
+		<Self as pallet_scheduler::WeightInfo>::service_agendas_base()
+			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(Weight::from_parts(0, 507))
+	}
+	fn next_cron_occurrence() -> Weight {
+		// Not benchmarked yet: no measured weight covers searching the calendar, which walks at
+		// most eight years of days. It is charged like servicing a full agenda, a generous bound
+		// until the benchmark is run.
+		<Self as pallet_scheduler::WeightInfo>::service_agenda_base(200)
+	}
+	/// Storage: `Timestamp::Now` (r:1 w:0)
+	/// Proof: `Timestamp::Now` (`max_values`: Some(1), `max_size`: Some(8), added: 503, mode: `MaxEncodedLen`)
+	/// Storage: `Scheduler::TimeAgenda` (r:1 w:1)
+	/// Proof: `Scheduler::TimeAgenda` (`max_values`: None, `max_size`: Some(159614), added: 162093, mode: `MaxEncodedLen`)
+	/// The range of component `s` is `[0, 199]`.
+	fn schedule_at_time(s: u32, ) -> Weight {
+		// Not benchmarked yet: like `schedule`, with the larger `Scheduler::TimeAgenda` and
+		// reading the time from `Timestamp::Now`.
+		<Self as pallet_scheduler::WeightInfo>::schedule(s)
+			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(Weight::from_parts(0, 4307))
+	}
+	/// Storage: `Scheduler::TimeAgenda` (r:1 w:1)
+	/// Proof: `Scheduler::TimeAgenda` (`max_values`: None, `max_size`: Some(159614), added: 162093, mode: `MaxEncodedLen`)
+	/// Storage: `Scheduler::TimeLookup` (r:0 w:1)
+	/// Proof: `Scheduler::TimeLookup` (`max_values`: None, `max_size`: Some(52), added: 2527, mode: `MaxEncodedLen`)
+	/// The range of component `s` is `[1, 200]`.
+	fn cancel_at_time(s: u32, ) -> Weight {
+		// Not benchmarked yet: like `cancel`, with the larger `Scheduler::TimeAgenda`.
+		<Self as pallet_scheduler::WeightInfo>::cancel(s)
+			.saturating_add(Weight::from_parts(0, 3804))
+	}
+	/// Storage: `Scheduler::TimeLookup` (r:1 w:1)
+	/// Proof: `Scheduler::TimeLookup` (`max_values`: None, `max_size`: Some(52), added: 2527, mode: `MaxEncodedLen`)
+	/// Storage: `Timestamp::Now` (r:1 w:0)
+	/// Proof: `Timestamp::Now` (`max_values`: Some(1), `max_size`: Some(8), added: 503, mode: `MaxEncodedLen`)
+	/// Storage: `Scheduler::TimeAgenda` (r:1 w:1)
+	/// Proof: `Scheduler::TimeAgenda` (`max_values`: None, `max_size`: Some(159614), added: 162093, mode: `MaxEncodedLen`)
+	/// The range of component `s` is `[0, 199]`.
+	fn schedule_named_at_time(s: u32, ) -> Weight {
+		// Not benchmarked yet: like `schedule_named`, with the larger `Scheduler::TimeAgenda` and
+		// `Scheduler::TimeLookup`, and reading the time from `Timestamp::Now`.
+		<Self as pallet_scheduler::WeightInfo>::schedule_named(s)
+			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(Weight::from_parts(0, 4311))
+	}
+	/// Storage: `Scheduler::TimeLookup` (r:1 w:1)
+	/// Proof: `Scheduler::TimeLookup` (`max_values`: None, `max_size`: Some(52), added: 2527, mode: `MaxEncodedLen`)
+	/// Storage: `Scheduler::TimeAgenda` (r:1 w:1)
+	/// Proof: `Scheduler::TimeAgenda` (`max_values`: None, `max_size`: Some(159614), added: 162093, mode: `MaxEncodedLen`)
+	/// The range of component `s` is `[1, 200]`.
+	fn cancel_named_at_time(s: u32, ) -> Weight {
+		// Not benchmarked yet: like `cancel_named`, with the larger `Scheduler::TimeAgenda` and
+		// `Scheduler::TimeLookup`.
+		<Self as pallet_scheduler::WeightInfo>::cancel_named(s)
+			.saturating_add(Weight::from_parts(0, 3808))
+	}
 }
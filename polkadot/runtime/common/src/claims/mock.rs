@@ -68,6 +68,8 @@ impl pallet_vesting::Config for Test {
 	type WeightInfo = ();
 	type UnvestedFundsAllowedWithdrawReasons = UnvestedFundsAllowedWithdrawReasons;
 	type BlockNumberProvider = System;
+	type RevokeOrigin = frame_system::EnsureRoot<u64>;
+	type RuntimeHoldReason = RuntimeHoldReason;
 	const MAX_VESTING_SCHEDULES: u32 = 28;
 }
 
@@ -108,4 +108,17 @@ pub enum Subcommand {
 
 	/// Db meta columns information.
 	ChainInfo(sc_cli::ChainInfoCmd),
+
+	/// Write a snapshot of the last finalized block.
+	Snapshot(sc_cli::SnapshotCmd),
+
+	/// Restore a snapshot into an empty database.
+	RestoreSnapshot(sc_cli::RestoreSnapshotCmd),
+
+	/// Check the integrity of the database.
+	CheckDb(sc_cli::CheckDbCmd),
+
+	/// Database maintenance utilities.
+	#[command(subcommand)]
+	Db(sc_cli::DbSubcommand),
 }
@@ -26,22 +26,30 @@ use polkadot_sdk::{
 };
 
 use crate::Cli;
-use codec::Encode;
+use codec::{Decode, Encode};
 use frame_benchmarking_cli::SUBSTRATE_REFERENCE_HARDWARE;
 use frame_system_rpc_runtime_api::AccountNonceApi;
 use futures::prelude::*;
 use kitchensink_runtime::RuntimeApi;
 use node_primitives::Block;
+use pallet_revive::{
+	evm::{GenericTransaction, TransactionSigned},
+	ReviveApi,
+};
 use sc_client_api::{Backend, BlockBackend};
 use sc_consensus_babe::{self, SlotProportion};
 use sc_network::{
-	event::Event, service::traits::NetworkService, NetworkBackend, NetworkEventStream,
+	config::{BlockHistorySync, CheckpointSync},
+	event::Event,
+	service::traits::NetworkService,
+	NetworkBackend, NetworkEventStream,
 };
 use sc_network_sync::{strategy::warp::WarpSyncConfig, SyncingService};
+use sc_rpc::txpool::TxPoolApiServer;
 use sc_service::{config::Configuration, error::Error as ServiceError, RpcHandlers, TaskManager};
 use sc_statement_store::Store as StatementStore;
 use sc_telemetry::{Telemetry, TelemetryWorker};
-use sc_transaction_pool::TransactionPoolHandle;
+use sc_transaction_pool::{TransactionPoolHandle, TxMemPoolPolicy, TxSlot};
 use sc_transaction_pool_api::OffchainTransactionPoolFactory;
 use sp_api::ProvideRuntimeApi;
 use sp_core::crypto::Pair;
@@ -97,6 +105,40 @@ pub fn fetch_nonce(client: &FullClient, account: sp_core::sr25519::Pair) -> u32
 		.expect("Fetching account nonce works; qed")
 }
 
+/// The mempool policy of the Ethereum transactions dispatched through `pallet-revive`.
+///
+/// Ethereum transactions are identified by their signer and nonce, and compared by the maximum
+/// gas price they pay, so that they are replaced and evicted like in geth.
+struct EthTxMemPoolPolicy {
+	client: Arc<FullClient>,
+}
+
+impl TxMemPoolPolicy<Block> for EthTxMemPoolPolicy {
+	fn slot(&self, xt: &<Block as BlockT>::Extrinsic) -> Option<TxSlot> {
+		let xt = kitchensink_runtime::UncheckedExtrinsic::decode(&mut &xt.encode()[..]).ok()?;
+		let kitchensink_runtime::RuntimeCall::Revive(pallet_revive::Call::eth_transact { payload }) =
+			xt.0.function
+		else {
+			return None
+		};
+
+		let tx = TransactionSigned::decode(&payload).ok()?;
+		let signer = tx.recover_eth_address().ok()?;
+		let tx = GenericTransaction::from_signed(tx, Default::default(), Some(signer));
+		Some(TxSlot {
+			sender: signer.as_bytes().to_vec(),
+			nonce: tx.nonce?.try_into().ok()?,
+			fee: tx.max_fee_per_gas.or(tx.gas_price)?.try_into().unwrap_or(u128::MAX),
+		})
+	}
+
+	fn account_nonce(&self, sender: &[u8]) -> Option<u64> {
+		let best_hash = self.client.chain_info().best_hash;
+		let address = sp_core::H160::from_slice(sender);
+		self.client.runtime_api().nonce(best_hash, address).ok().map(Into::into)
+	}
+}
+
 /// Create a transaction using the given `call`.
 ///
 /// The transaction will be signed by `sender`. If `nonce` is `None` it will be fetched from the
@@ -239,6 +281,7 @@ pub fn new_partial(
 		)
 		.with_options(config.transaction_pool.clone())
 		.with_prometheus(config.prometheus_registry())
+		.with_mempool_policy(Arc::new(EthTxMemPoolPolicy { client: client.clone() }))
 		.build(),
 	);
 
@@ -357,7 +400,18 @@ pub fn new_partial(
 					mixnet_api: mixnet_api.as_ref().cloned(),
 				};
 
-				node_rpc::create_full(deps).map_err(Into::into)
+				let mut io = node_rpc::create_full(deps)?;
+				// Groups the transactions by signer, replacing the default `txpool` RPCs.
+				let txpool = sc_rpc::txpool::TxPool::new(pool.clone()).with_sender_of(
+					sc_rpc::txpool::signer_of::<
+						Block,
+						kitchensink_runtime::Address,
+						node_primitives::Signature,
+						kitchensink_runtime::TxExtension,
+					>(),
+				);
+				io.merge(txpool.into_rpc()).map_err(|e| ServiceError::Application(e.into()))?;
+				Ok::<_, ServiceError>(io)
 			};
 
 		(rpc_extensions_builder, shared_voter_state2)
@@ -402,6 +456,8 @@ pub struct NewFullBase {
 pub fn new_full_base<N: NetworkBackend<Block, <Block as BlockT>::Hash>>(
 	config: Configuration,
 	mixnet_config: Option<sc_mixnet::Config>,
+	checkpoint_sync: Option<CheckpointSync>,
+	block_history: BlockHistorySync,
 	disable_hardware_benchmarks: bool,
 	with_startup_data: impl FnOnce(
 		&sc_consensus_babe::BabeBlockImport<
@@ -454,6 +510,10 @@ pub fn new_full_base<N: NetworkBackend<Block, <Block as BlockT>::Hash>>(
 		&config.network,
 		config.prometheus_config.as_ref().map(|cfg| cfg.registry.clone()),
 	);
+	if let Some(checkpoint) = checkpoint_sync {
+		net_config.set_checkpoint_sync(checkpoint);
+	}
+	net_config.set_block_history(block_history);
 
 	let genesis_hash = client.block_hash(0).ok().flatten().expect("Genesis block exists; qed");
 	let peer_store_handle = net_config.peer_store_handle();
@@ -825,6 +885,8 @@ pub fn new_full(config: Configuration, cli: Cli) -> Result<TaskManager, ServiceE
 			let task_manager = new_full_base::<sc_network::NetworkWorker<_, _>>(
 				config,
 				mixnet_config,
+				cli.run.network_params.checkpoint_sync(),
+				cli.run.network_params.block_history(),
 				cli.no_hardware_benchmarks,
 				|_, _| (),
 			)
@@ -835,6 +897,8 @@ pub fn new_full(config: Configuration, cli: Cli) -> Result<TaskManager, ServiceE
 			let task_manager = new_full_base::<sc_network::Litep2pNetworkBackend>(
 				config,
 				mixnet_config,
+				cli.run.network_params.checkpoint_sync(),
+				cli.run.network_params.block_history(),
 				cli.no_hardware_benchmarks,
 				|_, _| (),
 			)
@@ -922,6 +986,8 @@ mod tests {
 					new_full_base::<sc_network::NetworkWorker<_, _>>(
 						config,
 						None,
+						None,
+						Default::default(),
 						false,
 						|block_import: &sc_consensus_babe::BabeBlockImport<Block, _, _>,
 						 babe_link: &sc_consensus_babe::BabeLink<Block>| {
@@ -1126,6 +1192,8 @@ mod tests {
 					new_full_base::<sc_network::NetworkWorker<_, _>>(
 						config,
 						None,
+						None,
+						Default::default(),
 						false,
 						|_, _| (),
 					)?;
@@ -668,6 +668,16 @@ pub trait Backend<Block: BlockT>: AuxStore + Send + Sync {
 
 	/// Tells whether the backend requires full-sync mode.
 	fn requires_full_sync(&self) -> bool;
+
+	/// Write a snapshot of the last finalized block to the `path` directory.
+	///
+	/// Returns the hash and number of the block the snapshot was taken at.
+	fn snapshot(
+		&self,
+		_path: &std::path::Path,
+	) -> sp_blockchain::Result<(Block::Hash, NumberFor<Block>)> {
+		Err(sp_blockchain::Error::Backend("Snapshots are not supported by this backend".into()))
+	}
 }
 
 /// Mark for all Backend implementations, that are making use of state data, stored locally.
@@ -0,0 +1,108 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{error, CliConfiguration, DatabaseParams, PruningParams, SharedParams};
+use log::{info, warn};
+use sc_client_db::check::{check_database, CheckOptions};
+use sp_runtime::traits::Block as BlockT;
+
+/// The `check-db` command used to verify the integrity of the database.
+///
+/// The node must not be running while the database is checked. The blocks pruning setting the
+/// node runs with must be given, so that the pruned bodies are told apart from the missing ones.
+#[derive(Debug, Clone, clap::Parser)]
+pub struct CheckDbCmd {
+	/// Repair the problems that can be fixed without resyncing.
+	///
+	/// Inconsistent state-db journals are rewritten, blocks that should have been pruned are
+	/// pruned and leaves without a header are removed.
+	#[arg(long)]
+	pub repair: bool,
+
+	/// Check the state of every block kept in the database, instead of the finalized and best
+	/// blocks only.
+	#[arg(long)]
+	pub all_states: bool,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub shared_params: SharedParams,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub pruning_params: PruningParams,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub database_params: DatabaseParams,
+}
+
+impl CheckDbCmd {
+	/// Run the `check-db` command
+	pub fn run<B>(&self, config: &sc_service::Configuration) -> error::Result<()>
+	where
+		B: BlockT,
+	{
+		let db_config = sc_client_db::DatabaseSettings {
+			trie_cache_maximum_size: config.trie_cache_maximum_size,
+			state_pruning: config.state_pruning.clone(),
+			source: config.database.clone(),
+			blocks_pruning: config.blocks_pruning,
+			metrics_registry: None,
+		};
+		let options = CheckOptions { repair: self.repair, all_states: self.all_states };
+		let report = check_database::<B>(&db_config, options)?;
+
+		for problem in &report.problems {
+			if report.repaired && problem.is_repairable() {
+				warn!("Repaired: {problem}");
+			} else {
+				log::error!("{problem}");
+			}
+		}
+		let remaining = report.remaining().count();
+		info!(
+			"Checked {} blocks and {} state nodes, {} problems found, {} left",
+			report.blocks,
+			report.state_nodes,
+			report.problems.len(),
+			remaining,
+		);
+		if remaining > 0 {
+			if !report.repaired && report.problems.iter().any(|problem| problem.is_repairable()) {
+				info!("Run the command again with `--repair` to fix the repairable problems");
+			}
+			return Err(error::Error::Input(format!("The database has {remaining} problems")))
+		}
+		Ok(())
+	}
+}
+
+impl CliConfiguration for CheckDbCmd {
+	fn shared_params(&self) -> &SharedParams {
+		&self.shared_params
+	}
+
+	fn pruning_params(&self) -> Option<&PruningParams> {
+		Some(&self.pruning_params)
+	}
+
+	fn database_params(&self) -> Option<&DatabaseParams> {
+		Some(&self.database_params)
+	}
+}
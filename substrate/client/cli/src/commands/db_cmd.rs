@@ -0,0 +1,140 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+use crate::{
+	CliConfiguration, DatabaseParams, Error, PruningParams, Result as CliResult, SharedParams,
+};
+use log::info;
+use sc_client_db::DatabaseSource;
+use sp_runtime::traits::Block as BlockT;
+use std::path::PathBuf;
+
+/// Database maintenance utilities for the cli.
+#[derive(Debug, clap::Subcommand)]
+pub enum DbSubcommand {
+	/// Migrate a RocksDB database to ParityDB, or back.
+	Migrate(MigrateDbCmd),
+}
+
+/// The `db migrate` command used to copy the database into another backend.
+///
+/// The node must not be running while the database is migrated. An interrupted migration is
+/// resumed when the command is started again with the same target.
+///
+/// Both migrations start the target database from the last finalized block of the source
+/// database, the unfinalized blocks are not migrated. A ParityDB database is migrated back to
+/// RocksDB with the state of the finalized block only, and without the auxiliary data and the
+/// offchain storage, which ParityDB can't enumerate. This has to be allowed with `--allow-lossy`.
+#[derive(Debug, Clone, clap::Parser)]
+pub struct MigrateDbCmd {
+	/// The directory of the database to create.
+	///
+	/// Defaults to the database directory of the chain for the other backend, which is the one
+	/// picked up when the node is started with `--database paritydb` or `--database rocksdb`.
+	#[arg(long, value_name = "PATH")]
+	pub target_path: Option<PathBuf>,
+
+	/// Allow a migration from ParityDB, which loses data.
+	///
+	/// The states of the blocks before the finalized one, the auxiliary data, e.g. the consensus
+	/// data of the node, and the offchain storage are not migrated back to RocksDB.
+	#[arg(long)]
+	pub allow_lossy: bool,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub shared_params: SharedParams,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub pruning_params: PruningParams,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub database_params: DatabaseParams,
+}
+
+impl MigrateDbCmd {
+	/// Run the `db migrate` command
+	pub fn run<B>(&self, config: &sc_service::Configuration) -> CliResult<()>
+	where
+		B: BlockT,
+	{
+		// `<base>/db/full` is migrated to `<base>/paritydb/full`, and back.
+		let sibling = |path: &PathBuf, name: &str| {
+			path.parent()
+				.and_then(|path| path.parent())
+				.map(|base| base.join(name).join("full"))
+				.ok_or_else(|| Error::Input(format!("Invalid database path {}", path.display())))
+		};
+		let (source, target) = match &config.database {
+			DatabaseSource::RocksDb { path, cache_size } => (
+				DatabaseSource::RocksDb { path: path.clone(), cache_size: *cache_size },
+				DatabaseSource::ParityDb {
+					path: self.target_path.clone().map_or_else(|| sibling(path, "paritydb"), Ok)?,
+				},
+			),
+			DatabaseSource::Auto { rocksdb_path, paritydb_path, cache_size } => (
+				DatabaseSource::RocksDb { path: rocksdb_path.clone(), cache_size: *cache_size },
+				DatabaseSource::ParityDb {
+					path: self.target_path.clone().unwrap_or_else(|| paritydb_path.clone()),
+				},
+			),
+			DatabaseSource::ParityDb { path } => (
+				DatabaseSource::ParityDb { path: path.clone() },
+				DatabaseSource::RocksDb {
+					path: self.target_path.clone().map_or_else(|| sibling(path, "db"), Ok)?,
+					cache_size: self.database_params.database_cache_size().unwrap_or(1024),
+				},
+			),
+			_ =>
+				return Err(Error::Input(
+					"Only RocksDB and ParityDB databases can be migrated".into(),
+				)),
+		};
+
+		let entries =
+			sc_client_db::migrate::migrate_database::<B>(&source, &target, self.allow_lossy)?;
+
+		let display = |db: &DatabaseSource| {
+			db.path().map_or_else(String::new, |path| path.display().to_string())
+		};
+		let backend =
+			if matches!(target, DatabaseSource::ParityDb { .. }) { "paritydb" } else { "rocksdb" };
+		info!("✅ Migrated {} entries into {}", entries, display(&target));
+		info!(
+			"Start the node with `--database {backend}` to use the new database. The database in \
+			 {} is left untouched and can be removed once the node runs fine.",
+			display(&source),
+		);
+		Ok(())
+	}
+}
+
+impl CliConfiguration for MigrateDbCmd {
+	fn shared_params(&self) -> &SharedParams {
+		&self.shared_params
+	}
+
+	fn pruning_params(&self) -> Option<&PruningParams> {
+		Some(&self.pruning_params)
+	}
+
+	fn database_params(&self) -> Option<&DatabaseParams> {
+		Some(&self.database_params)
+	}
+}
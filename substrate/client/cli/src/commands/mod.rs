@@ -21,6 +21,9 @@
 mod build_spec_cmd;
 mod chain_info_cmd;
 mod check_block_cmd;
+mod check_db_cmd;
+#[cfg(feature = "rocksdb")]
+mod db_cmd;
 mod export_blocks_cmd;
 mod export_chain_spec_cmd;
 mod export_state_cmd;
@@ -32,20 +35,25 @@ mod inspect_key;
 mod inspect_node_key;
 mod key;
 mod purge_chain_cmd;
+mod restore_snapshot_cmd;
 mod revert_cmd;
 mod run_cmd;
 mod sign;
+mod snapshot_cmd;
 mod test;
 pub mod utils;
 mod vanity;
 mod verify;
 
+#[cfg(feature = "rocksdb")]
+pub use self::db_cmd::{DbSubcommand, MigrateDbCmd};
 pub use self::{
 	build_spec_cmd::BuildSpecCmd, chain_info_cmd::ChainInfoCmd, check_block_cmd::CheckBlockCmd,
-	export_blocks_cmd::ExportBlocksCmd, export_chain_spec_cmd::ExportChainSpecCmd,
-	export_state_cmd::ExportStateCmd, generate::GenerateCmd,
-	generate_node_key::GenerateKeyCmdCommon, import_blocks_cmd::ImportBlocksCmd,
-	insert_key::InsertKeyCmd, inspect_key::InspectKeyCmd, inspect_node_key::InspectNodeKeyCmd,
-	key::KeySubcommand, purge_chain_cmd::PurgeChainCmd, revert_cmd::RevertCmd, run_cmd::RunCmd,
-	sign::SignCmd, vanity::VanityCmd, verify::VerifyCmd,
+	check_db_cmd::CheckDbCmd, export_blocks_cmd::ExportBlocksCmd,
+	export_chain_spec_cmd::ExportChainSpecCmd, export_state_cmd::ExportStateCmd,
+	generate::GenerateCmd, generate_node_key::GenerateKeyCmdCommon,
+	import_blocks_cmd::ImportBlocksCmd, insert_key::InsertKeyCmd, inspect_key::InspectKeyCmd,
+	inspect_node_key::InspectNodeKeyCmd, key::KeySubcommand, purge_chain_cmd::PurgeChainCmd,
+	restore_snapshot_cmd::RestoreSnapshotCmd, revert_cmd::RevertCmd, run_cmd::RunCmd,
+	sign::SignCmd, snapshot_cmd::SnapshotCmd, vanity::VanityCmd, verify::VerifyCmd,
 };
@@ -0,0 +1,116 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{
+	commands::snapshot_cmd::is_tarball, error, CliConfiguration, DatabaseParams, PruningParams,
+	SharedParams,
+};
+use sp_runtime::traits::Block as BlockT;
+use std::{
+	fmt::Debug,
+	fs::{self, File},
+	path::PathBuf,
+	str::FromStr,
+};
+
+/// The `restore-snapshot` command used to restore a snapshot into an empty database.
+#[derive(Debug, Clone, clap::Parser)]
+pub struct RestoreSnapshotCmd {
+	/// The directory or tarball holding the snapshot.
+	#[arg(value_name = "PATH")]
+	pub input: PathBuf,
+
+	/// Refuse to restore a snapshot of a chain with another genesis hash.
+	#[arg(long, value_name = "HASH")]
+	pub expected_genesis: Option<String>,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub shared_params: SharedParams,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub pruning_params: PruningParams,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub database_params: DatabaseParams,
+}
+
+impl RestoreSnapshotCmd {
+	/// Run the `restore-snapshot` command
+	pub fn run<B>(&self, config: &sc_service::Configuration) -> error::Result<()>
+	where
+		B: BlockT,
+		<B::Hash as FromStr>::Err: Debug,
+	{
+		let expected_genesis = self
+			.expected_genesis
+			.as_ref()
+			.map(|hash| {
+				B::Hash::from_str(hash.trim_start_matches("0x"))
+					.map_err(|e| error::Error::Input(format!("Invalid genesis hash: {e:?}")))
+			})
+			.transpose()?;
+		let db_config = sc_client_db::DatabaseSettings {
+			trie_cache_maximum_size: config.trie_cache_maximum_size,
+			state_pruning: config.state_pruning.clone(),
+			source: config.database.clone(),
+			blocks_pruning: config.blocks_pruning,
+			metrics_registry: None,
+		};
+
+		if !is_tarball(&self.input) {
+			sc_client_db::snapshot::restore_snapshot::<B>(
+				&self.input,
+				&db_config,
+				expected_genesis,
+			)?;
+			return Ok(())
+		}
+
+		let staging = self.input.with_extension("staging");
+		let result = tar::Archive::new(File::open(&self.input)?)
+			.unpack(&staging)
+			.map_err(Into::into)
+			.and_then(|_| -> error::Result<_> {
+				sc_client_db::snapshot::restore_snapshot::<B>(
+					&staging,
+					&db_config,
+					expected_genesis,
+				)
+				.map_err(Into::into)
+			});
+		let _ = fs::remove_dir_all(&staging);
+		result.map(|_| ())
+	}
+}
+
+impl CliConfiguration for RestoreSnapshotCmd {
+	fn shared_params(&self) -> &SharedParams {
+		&self.shared_params
+	}
+
+	fn pruning_params(&self) -> Option<&PruningParams> {
+		Some(&self.pruning_params)
+	}
+
+	fn database_params(&self) -> Option<&DatabaseParams> {
+		Some(&self.database_params)
+	}
+}
@@ -0,0 +1,105 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{CliConfiguration, DatabaseParams, PruningParams, Result as CliResult, SharedParams};
+use log::info;
+use sp_runtime::traits::Block as BlockT;
+use std::{
+	fs::{self, File},
+	path::{Path, PathBuf},
+};
+
+/// Returns `true` if the snapshot at `path` is packed in a tarball.
+pub(crate) fn is_tarball(path: &Path) -> bool {
+	path.extension().map_or(false, |extension| extension == "tar")
+}
+
+/// The `snapshot` command used to write a snapshot of the last finalized block.
+///
+/// The database is opened exclusively, so the node must be stopped. A running node writes
+/// snapshots with the unsafe `snapshot_create` RPC.
+#[derive(Debug, Clone, clap::Parser)]
+pub struct SnapshotCmd {
+	/// The directory to write the snapshot to.
+	///
+	/// The snapshot is packed in a tarball if the path ends with `.tar`.
+	#[arg(value_name = "PATH")]
+	pub output: PathBuf,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub shared_params: SharedParams,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub pruning_params: PruningParams,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub database_params: DatabaseParams,
+}
+
+impl SnapshotCmd {
+	/// Run the `snapshot` command
+	pub fn run<B>(&self, config: &sc_service::Configuration) -> CliResult<()>
+	where
+		B: BlockT,
+	{
+		let db_config = sc_client_db::DatabaseSettings {
+			trie_cache_maximum_size: config.trie_cache_maximum_size,
+			state_pruning: config.state_pruning.clone(),
+			source: config.database.clone(),
+			blocks_pruning: config.blocks_pruning,
+			metrics_registry: None,
+		};
+		let backend = sc_service::new_db_backend::<B>(db_config)?;
+
+		if !is_tarball(&self.output) {
+			backend.snapshot(&self.output)?;
+			return Ok(())
+		}
+
+		let staging = self.output.with_extension("staging");
+		let result =
+			backend.snapshot(&staging).map_err(Into::into).and_then(|_| -> CliResult<()> {
+				let mut builder = tar::Builder::new(File::create(&self.output)?);
+				builder.append_dir_all(".", &staging)?;
+				builder.into_inner()?.sync_all()?;
+				Ok(())
+			});
+		let _ = fs::remove_dir_all(&staging);
+		result?;
+
+		info!("Snapshot packed into {}", self.output.display());
+		Ok(())
+	}
+}
+
+impl CliConfiguration for SnapshotCmd {
+	fn shared_params(&self) -> &SharedParams {
+		&self.shared_params
+	}
+
+	fn pruning_params(&self) -> Option<&PruningParams> {
+		Some(&self.pruning_params)
+	}
+
+	fn database_params(&self) -> Option<&DatabaseParams> {
+		Some(&self.database_params)
+	}
+}
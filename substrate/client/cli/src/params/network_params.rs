@@ -22,8 +22,10 @@ use crate::{
 };
 use clap::Args;
 use sc_network::{
+	bandwidth::{InboundRequestLimits, RateLimit},
 	config::{
-		NetworkConfiguration, NodeKeyConfig, NonReservedPeerMode, SetConfig, TransportConfig,
+		BlockHistorySync, CheckpointSync, NetworkConfiguration, NodeKeyConfig, NonReservedPeerMode,
+		SetConfig, TransportConfig,
 	},
 	multiaddr::Protocol,
 };
@@ -31,7 +33,11 @@ use sc_service::{
 	config::{Multiaddr, MultiaddrWithPeerId},
 	ChainSpec, ChainType,
 };
-use std::{borrow::Cow, num::NonZeroUsize, path::PathBuf};
+use std::{
+	borrow::Cow,
+	num::{NonZeroU64, NonZeroUsize},
+	path::PathBuf,
+};
 
 /// Parameters used to create the network configuration.
 #[derive(Debug, Clone, Args)]
@@ -163,6 +169,29 @@ pub struct NetworkParams {
 	)]
 	pub sync: SyncMode,
 
+	/// Hash of a trusted finalized block to sync the state of.
+	///
+	/// Instead of proving finality from genesis, the node downloads the header of this block from
+	/// its peers, syncs the state at it and follows the chain from there. Works for chains without
+	/// finality proofs as well. Implies `--sync warp`.
+	#[arg(long, value_name = "HASH", value_parser = parse_checkpoint_hash)]
+	pub checkpoint_sync: Option<CheckpointSync>,
+
+	/// JSON file with a trusted finalized header to sync the state of.
+	///
+	/// Accepts the output of the `chain_getHeader` RPC, or the output of `chain_getBlock` in which
+	/// case the justifications of the block are imported as well. Implies `--sync warp`.
+	#[arg(long, value_name = "PATH", conflicts_with = "checkpoint_sync")]
+	pub checkpoint_sync_file: Option<PathBuf>,
+
+	/// Block history to download in the background after warp sync.
+	///
+	/// `full` downloads the whole history forward from genesis. A number downloads the history
+	/// backwards from the warp sync target, with the bodies of that many blocks below the last
+	/// finalized block and only the headers of older blocks.
+	#[arg(long, value_name = "full|DEPTH", default_value = "full", value_parser = parse_block_history)]
+	pub block_history: BlockHistorySync,
+
 	/// Maximum number of blocks per request.
 	///
 	/// Try reducing this number from the default value if you have a slow network connection
@@ -170,6 +199,23 @@ pub struct NetworkParams {
 	#[arg(long, value_name = "COUNT", default_value_t = 64)]
 	pub max_blocks_per_request: u32,
 
+	/// Maximum rate of inbound request-response traffic (e.g. block and state requests), in
+	/// KiB/s, shared by all peers.
+	///
+	/// The limit applies to each request-response protocol separately and both the received
+	/// requests and the responses sent back count towards it. Requests arriving while the limit
+	/// is exceeded are dropped. No limit is applied by default.
+	#[arg(long, value_name = "KIB_PER_SEC")]
+	pub inbound_requests_rate_limit: Option<NonZeroU64>,
+
+	/// Maximum rate of inbound request-response traffic (e.g. block and state requests), in
+	/// KiB/s, for each individual peer.
+	///
+	/// Works like `--inbound-requests-rate-limit` but is tracked separately for every peer.
+	/// No limit is applied by default.
+	#[arg(long, value_name = "KIB_PER_SEC")]
+	pub inbound_requests_peer_rate_limit: Option<NonZeroU64>,
+
 	/// Network backend used for P2P networking.
 	///
 	/// Litep2p is a lightweight alternative to libp2p, that is designed to be more
@@ -275,6 +321,7 @@ impl NetworkParams {
 			},
 			max_parallel_downloads: self.max_parallel_downloads,
 			max_blocks_per_request: self.max_blocks_per_request,
+			inbound_request_limits: self.inbound_request_limits(),
 			min_peers_to_start_warp_sync: None,
 			enable_dht_random_walk: !self.reserved_only,
 			allow_non_globals_in_dht,
@@ -285,6 +332,50 @@ impl NetworkParams {
 			network_backend: self.network_backend.into(),
 		}
 	}
+
+	/// Checkpoint sync configured on the command line.
+	///
+	/// Nodes supporting checkpoint sync pass it on to
+	/// [`FullNetworkConfiguration::set_checkpoint_sync`](sc_network::config::FullNetworkConfiguration::set_checkpoint_sync).
+	pub fn checkpoint_sync(&self) -> Option<CheckpointSync> {
+		self.checkpoint_sync
+			.clone()
+			.or_else(|| self.checkpoint_sync_file.clone().map(CheckpointSync::File))
+	}
+
+	/// Block history to download after warp sync, configured on the command line.
+	///
+	/// Nodes supporting it pass it on to
+	/// [`FullNetworkConfiguration::set_block_history`](sc_network::config::FullNetworkConfiguration::set_block_history).
+	pub fn block_history(&self) -> BlockHistorySync {
+		self.block_history
+	}
+
+	/// Inbound request rate limits configured on the command line.
+	fn inbound_request_limits(&self) -> InboundRequestLimits {
+		let limit =
+			|kib_per_sec: NonZeroU64| RateLimit::new(kib_per_sec.get().saturating_mul(1024));
+
+		InboundRequestLimits {
+			global: self.inbound_requests_rate_limit.map(limit),
+			per_peer: self.inbound_requests_peer_rate_limit.map(limit),
+		}
+	}
+}
+
+fn parse_checkpoint_hash(s: &str) -> Result<CheckpointSync, String> {
+	array_bytes::hex2bytes(s)
+		.map(CheckpointSync::Hash)
+		.map_err(|err| format!("Invalid block hash: {err:?}"))
+}
+
+fn parse_block_history(s: &str) -> Result<BlockHistorySync, String> {
+	match s {
+		"full" => Ok(BlockHistorySync::Full),
+		depth => depth.parse().map(BlockHistorySync::Depth).map_err(|_| {
+			format!("Invalid block history `{depth}`, expected `full` or a block count")
+		}),
+	}
 }
 
 #[cfg(test)]
@@ -337,4 +428,69 @@ mod tests {
 
 		assert_eq!(SyncMode::Warp, params.network_params.sync);
 	}
+
+	#[test]
+	fn inbound_request_rate_limits() {
+		let params = Cli::try_parse_from([""]).expect("Parses network params");
+		assert!(params.network_params.inbound_request_limits().is_unlimited());
+
+		let params = Cli::try_parse_from([
+			"",
+			"--inbound-requests-rate-limit",
+			"4096",
+			"--inbound-requests-peer-rate-limit",
+			"512",
+		])
+		.expect("Parses network params");
+
+		assert_eq!(
+			params.network_params.inbound_request_limits(),
+			InboundRequestLimits {
+				global: Some(RateLimit::new(4096 * 1024)),
+				per_peer: Some(RateLimit::new(512 * 1024)),
+			},
+		);
+	}
+
+	#[test]
+	fn checkpoint_sync() {
+		let params = Cli::try_parse_from([""]).expect("Parses network params");
+		assert_eq!(params.network_params.checkpoint_sync(), None);
+
+		let params = Cli::try_parse_from(["", "--checkpoint-sync", "0x0102"])
+			.expect("Parses network params");
+		assert_eq!(params.network_params.checkpoint_sync(), Some(CheckpointSync::Hash(vec![1, 2])));
+
+		let params = Cli::try_parse_from(["", "--checkpoint-sync-file", "header.json"])
+			.expect("Parses network params");
+		assert_eq!(
+			params.network_params.checkpoint_sync(),
+			Some(CheckpointSync::File("header.json".into())),
+		);
+
+		assert!(Cli::try_parse_from(["", "--checkpoint-sync", "0xzz"]).is_err());
+		assert!(Cli::try_parse_from([
+			"",
+			"--checkpoint-sync",
+			"0x0102",
+			"--checkpoint-sync-file",
+			"header.json",
+		])
+		.is_err());
+	}
+
+	#[test]
+	fn block_history() {
+		let parse = |value| {
+			Cli::try_parse_from(["", "--block-history", value])
+				.map(|cli| cli.network_params.block_history)
+		};
+
+		let params = Cli::try_parse_from([""]).expect("Parses network params");
+		assert_eq!(params.network_params.block_history, BlockHistorySync::Full);
+		assert_eq!(parse("full").unwrap(), BlockHistorySync::Full);
+		assert_eq!(parse("14400").unwrap(), BlockHistorySync::Depth(14400));
+		assert!(parse("none").is_err());
+		assert!(parse("all").is_err());
+	}
 }
@@ -18,7 +18,8 @@
 
 use crate::error;
 use clap::Args;
-use sc_service::{BlocksPruning, PruningMode};
+use sc_service::{BlocksPruning, PruningConstraints, PruningMode, PruningWindowLimits};
+use std::{num::NonZeroU32, time::Duration};
 
 /// Parameters to define the pruning mode
 #[derive(Debug, Clone, Args)]
@@ -45,6 +46,32 @@ pub struct PruningParams {
 	#[arg(alias = "pruning", long, value_name = "PRUNING_MODE")]
 	pub state_pruning: Option<DatabasePruningMode>,
 
+	/// Keep the state of the blocks finalized in the last DURATION.
+	///
+	/// The duration is a number followed by one of the `s`, `m`, `h` or `d` units, e.g. `7d`.
+	/// The age is measured from the time the blocks were finalized by this node, so the number of
+	/// kept blocks is still limited while syncing: by the `NUMBER` passed to `--state-pruning`, or
+	/// to 100800 blocks otherwise.
+	#[arg(long, value_name = "DURATION", value_parser = parse_duration)]
+	pub state_pruning_max_age: Option<Duration>,
+
+	/// Keep the state of the last finalized blocks as long as it takes less than SIZE MiB.
+	///
+	/// This is the size of the state written by the kept blocks, on top of the state of the last
+	/// finalized block. The number of kept blocks is limited by the `NUMBER` passed to
+	/// `--state-pruning`, or to 100800 blocks otherwise.
+	#[arg(long, value_name = "SIZE")]
+	pub state_pruning_max_size: Option<u64>,
+
+	/// Additionally keep the state of every finalized block whose number is a multiple of
+	/// INTERVAL.
+	///
+	/// This turns the node into a sparse archive, giving access to the historical state at these
+	/// checkpoints without the cost of a full archive. Like the pruning mode, this can only be
+	/// enabled on the first creation of the database, but the interval may change between runs.
+	#[arg(long, value_name = "INTERVAL")]
+	pub state_pruning_checkpoints: Option<NonZeroU32>,
+
 	/// Specify the blocks pruning mode.
 	///
 	/// This mode specifies when the block's body (including justifications)
@@ -69,7 +96,37 @@ pub struct PruningParams {
 impl PruningParams {
 	/// Get the pruning value from the parameters
 	pub fn state_pruning(&self) -> error::Result<Option<PruningMode>> {
-		Ok(self.state_pruning.map(|v| v.into()))
+		if self.state_pruning_max_age.is_none() &&
+			self.state_pruning_max_size.is_none() &&
+			self.state_pruning_checkpoints.is_none()
+		{
+			return Ok(self.state_pruning.map(|v| v.into()))
+		}
+
+		let max_blocks = match self.state_pruning {
+			Some(DatabasePruningMode::Custom(n)) => Some(n),
+			Some(DatabasePruningMode::Archive | DatabasePruningMode::ArchiveCanonical) =>
+				return Err(error::Error::Input(
+					"The state pruning constraints and checkpoints require a pruned state".into(),
+				)),
+			None => None,
+		};
+		let limits = PruningWindowLimits {
+			max_age: self.state_pruning_max_age,
+			max_size: self.state_pruning_max_size.map(|size| size.saturating_mul(1024 * 1024)),
+		};
+		let limited = limits.max_age.is_some() || limits.max_size.is_some();
+		let constraints = match max_blocks {
+			// Only the checkpoints were requested, keep the default pruning window.
+			None if !limited => PruningConstraints::default(),
+			max_blocks => PruningConstraints { max_blocks },
+		};
+
+		Ok(Some(match self.state_pruning_checkpoints {
+			Some(checkpoint_interval) =>
+				PruningMode::SparseArchive { checkpoint_interval, constraints, limits },
+			None => PruningMode::Limited { constraints, limits },
+		}))
 	}
 
 	/// Get the block pruning value from the parameters
@@ -78,6 +135,21 @@ impl PruningParams {
 	}
 }
 
+/// Parse a duration made of a number and a unit, e.g. `7d`.
+fn parse_duration(input: &str) -> Result<Duration, String> {
+	let (value, unit) =
+		input.split_at(input.find(|c: char| !c.is_ascii_digit()).unwrap_or(input.len()));
+	let value: u64 = value.parse().map_err(|_| format!("Invalid duration: {input}"))?;
+	let seconds = match unit {
+		"s" | "" => 1,
+		"m" => 60,
+		"h" => 60 * 60,
+		"d" => 24 * 60 * 60,
+		_ => return Err(format!("Invalid duration unit: {unit}, expected `s`, `m`, `h` or `d`")),
+	};
+	Ok(Duration::from_secs(value.saturating_mul(seconds)))
+}
+
 /// Specifies the pruning mode of the database.
 ///
 /// This specifies when the block's data (either state via `--state-pruning`
@@ -162,4 +234,38 @@ mod tests {
 		assert!(matches!(dbg!(pruning.state_pruning), Some(DatabasePruningMode::ArchiveCanonical)));
 		assert!(matches!(pruning.blocks_pruning, DatabasePruningMode::ArchiveCanonical));
 	}
+
+	#[test]
+	fn state_pruning_constraints_parse_works() {
+		let Cli { pruning } =
+			Cli::parse_from(["", "--state-pruning-max-age=7d", "--state-pruning-max-size=1024"]);
+		assert_eq!(
+			pruning.state_pruning().unwrap(),
+			Some(PruningMode::Limited {
+				constraints: PruningConstraints { max_blocks: None },
+				limits: PruningWindowLimits {
+					max_age: Some(Duration::from_secs(7 * 24 * 60 * 60)),
+					max_size: Some(1024 * 1024 * 1024),
+				},
+			})
+		);
+
+		let Cli { pruning } =
+			Cli::parse_from(["", "--state-pruning=1000", "--state-pruning-checkpoints=100"]);
+		assert_eq!(
+			pruning.state_pruning().unwrap(),
+			Some(PruningMode::SparseArchive {
+				checkpoint_interval: NonZeroU32::new(100).unwrap(),
+				constraints: PruningConstraints { max_blocks: Some(1000) },
+				limits: Default::default(),
+			})
+		);
+
+		let Cli { pruning } =
+			Cli::parse_from(["", "--state-pruning=archive", "--state-pruning-max-age=1h"]);
+		assert!(pruning.state_pruning().is_err());
+
+		assert!(Cli::try_parse_from(["", "--state-pruning-max-age=7w"]).is_err());
+		assert!(Cli::try_parse_from(["", "--state-pruning-checkpoints=0"]).is_err());
+	}
 }
@@ -17,7 +17,8 @@
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
 use clap::{Args, ValueEnum};
-use sc_transaction_pool::TransactionPoolOptions;
+use sc_transaction_pool::{JournalOptions, LocalLaneOptions, PoolLimit, TransactionPoolOptions};
+use std::{path::PathBuf, time::Duration};
 
 /// Type of transaction pool to be used
 #[derive(Debug, Clone, Copy, ValueEnum)]
@@ -59,17 +60,87 @@ pub struct TransactionPoolParams {
 	/// The type of transaction pool to be instantiated.
 	#[arg(long, value_enum, default_value_t = TransactionPoolType::ForkAware)]
 	pub pool_type: TransactionPoolType,
+
+	/// Path of the journal persisting the transactions of the pool across restarts.
+	///
+	/// The journal is disabled if not provided.
+	#[arg(long, value_name = "PATH")]
+	pub pool_journal: Option<PathBuf>,
+
+	/// Maximum number of kilobytes of all transactions stored in the journal.
+	#[arg(long, value_name = "COUNT", default_value_t = 10240, requires = "pool_journal")]
+	pub pool_journal_kbytes: usize,
+
+	/// Maximum age of a journaled transaction, in seconds.
+	#[arg(long, value_name = "SECONDS", default_value_t = 10800, requires = "pool_journal")]
+	pub pool_journal_lifetime: u64,
+
+	/// Minimum priority of the transactions accepted to the pool.
+	#[arg(long, value_name = "PRIORITY", default_value_t = 0)]
+	pub pool_min_priority: u64,
+
+	/// Never evict the transactions submitted by the node itself when the pool is full, as long
+	/// as they stay within `--pool-local-limit` and `--pool-local-kbytes`.
+	#[arg(long)]
+	pub pool_local_exempt_from_limits: bool,
+
+	/// Maximum number of transactions submitted by the node itself exempt from the limits of the
+	/// pool.
+	#[arg(long, value_name = "COUNT", default_value_t = 256)]
+	pub pool_local_limit: usize,
+
+	/// Maximum number of kilobytes of the transactions submitted by the node itself exempt from
+	/// the limits of the pool.
+	#[arg(long, value_name = "COUNT", default_value_t = 2048)]
+	pub pool_local_kbytes: usize,
+
+	/// Accept the transactions submitted by the node itself regardless of `--pool-min-priority`.
+	#[arg(long)]
+	pub pool_local_exempt_from_min_priority: bool,
+
+	/// Interval at which the ready transactions submitted by the node itself are propagated
+	/// again, in seconds.
+	///
+	/// Local transactions are only propagated once if not provided.
+	#[arg(long, value_name = "SECONDS")]
+	pub pool_local_rebroadcast: Option<u64>,
+
+	/// Treat the transactions submitted over RPC as transactions submitted by the node itself.
+	///
+	/// Only use it if the RPC server is exclusively used by the operator of the node.
+	#[arg(long)]
+	pub pool_local_rpc: bool,
 }
 
 impl TransactionPoolParams {
 	/// Fill the given `PoolConfiguration` by looking at the cli parameters.
 	pub fn transaction_pool(&self, is_dev: bool) -> TransactionPoolOptions {
-		TransactionPoolOptions::new_with_params(
+		let options = TransactionPoolOptions::new_with_params(
 			self.pool_limit,
 			self.pool_kbytes * 1024,
 			self.tx_ban_seconds,
 			self.pool_type.into(),
 			is_dev,
 		)
+		.with_min_priority(self.pool_min_priority)
+		.with_local_lane(LocalLaneOptions {
+			exempt_from_limits: self.pool_local_exempt_from_limits,
+			limit: PoolLimit {
+				count: self.pool_local_limit,
+				total_bytes: self.pool_local_kbytes * 1024,
+			},
+			exempt_from_min_priority: self.pool_local_exempt_from_min_priority,
+			rebroadcast_interval: self.pool_local_rebroadcast.map(Duration::from_secs),
+		})
+		.with_rpc_local_lane(self.pool_local_rpc);
+
+		match &self.pool_journal {
+			Some(path) => options.with_journal(JournalOptions {
+				path: path.clone(),
+				max_bytes: self.pool_journal_kbytes * 1024,
+				max_age: Duration::from_secs(self.pool_journal_lifetime),
+			}),
+			None => options,
+		}
 	}
 }
@@ -18,7 +18,11 @@
 
 //! Utilities for dealing with authorities, authority sets, and handoffs.
 
-use std::{cmp::Ord, fmt::Debug, ops::Add};
+use std::{
+	cmp::Ord,
+	fmt::Debug,
+	ops::{Add, Range},
+};
 
 use codec::{Decode, Encode};
 use finality_grandpa::voter_set::VoterSet;
@@ -756,6 +760,36 @@ impl<N: Ord + Clone> AuthoritySetChanges<N> {
 		self.0.insert(idx, (set_id, block_number));
 	}
 
+	/// Ids the set ending at `block_number` may have, given the known changes around it.
+	///
+	/// Used when the block history is imported backwards, where the set id can't be derived from
+	/// the change before. Changes above all known ones belong to a set before `current_set_id`.
+	pub(crate) fn possible_set_ids(&self, block_number: &N, current_set_id: u64) -> Range<u64> {
+		let idx = self.0.partition_point(|(_, n)| n < block_number);
+		let start = if idx == 0 { 0 } else { self.0[idx - 1].0 + 1 };
+		let end = self.0.get(idx).map_or(current_set_id, |(set_id, _)| *set_id);
+		start..end
+	}
+
+	/// Insert the last block of authority set `set_id`, imported after the set was finalized.
+	///
+	/// Returns `false` if the change is already known or if `set_id` doesn't fit between the
+	/// known changes around it.
+	pub(crate) fn insert_with_set_id(&mut self, set_id: u64, block_number: N) -> bool {
+		let Err(idx) = self.0.binary_search_by_key(&block_number, |(_, n)| n.clone()) else {
+			return false
+		};
+
+		if (idx > 0 && self.0[idx - 1].0 >= set_id) ||
+			self.0.get(idx).map_or(false, |(next_set_id, _)| *next_set_id <= set_id)
+		{
+			return false
+		}
+
+		self.0.insert(idx, (set_id, block_number));
+		true
+	}
+
 	/// Returns an iterator over all historical authority set changes starting at the given block
 	/// number (excluded). The iterator yields a tuple representing the set id and the block number
 	/// of the last block in that set.
@@ -1689,6 +1723,32 @@ mod tests {
 		assert_eq!(authority_set_changes.get_set_id(101), AuthoritySetChangeId::Set(2, 101));
 	}
 
+	#[test]
+	fn authority_set_changes_insert_backwards() {
+		// History downloaded backwards after warp sync to set 5.
+		let mut authority_set_changes = AuthoritySetChanges::empty();
+		assert_eq!(authority_set_changes.possible_set_ids(&121, 5), 0..5);
+		assert!(authority_set_changes.insert_with_set_id(4, 121));
+
+		// A forced change skipped set 3.
+		assert_eq!(authority_set_changes.possible_set_ids(&81, 5), 0..4);
+		assert!(authority_set_changes.insert_with_set_id(2, 81));
+		assert_eq!(authority_set_changes.get_set_id(100), AuthoritySetChangeId::Set(4, 121));
+		assert_eq!(authority_set_changes.get_set_id(81), AuthoritySetChangeId::Set(2, 81));
+		assert_eq!(authority_set_changes.get_set_id(60), AuthoritySetChangeId::Unknown);
+
+		// Known changes and set ids out of order are refused.
+		assert!(!authority_set_changes.insert_with_set_id(4, 121));
+		assert!(!authority_set_changes.insert_with_set_id(3, 41));
+		assert!(!authority_set_changes.insert_with_set_id(2, 101));
+
+		assert_eq!(authority_set_changes.possible_set_ids(&101, 5), 3..4);
+		assert_eq!(authority_set_changes.possible_set_ids(&41, 5), 0..2);
+		assert!(authority_set_changes.insert_with_set_id(0, 41));
+		assert_eq!(authority_set_changes.get_set_id(41), AuthoritySetChangeId::Set(0, 41));
+		assert_eq!(authority_set_changes.get_set_id(42), AuthoritySetChangeId::Set(2, 81));
+	}
+
 	#[test]
 	fn authority_set_changes_for_complete_data() {
 		let mut authority_set_changes = AuthoritySetChanges::empty();
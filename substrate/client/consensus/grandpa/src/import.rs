@@ -18,7 +18,7 @@
 
 use std::{collections::HashMap, marker::PhantomData, sync::Arc};
 
-use codec::Decode;
+use codec::{Decode, DecodeAll};
 use log::debug;
 use parking_lot::Mutex;
 
@@ -30,7 +30,7 @@ use sc_consensus::{
 use sc_telemetry::TelemetryHandle;
 use sc_utils::mpsc::TracingUnboundedSender;
 use sp_api::{Core, RuntimeApiInfo};
-use sp_blockchain::BlockStatus;
+use sp_blockchain::{BlockGapType, BlockStatus};
 use sp_consensus::{BlockOrigin, Error as ConsensusError, SelectChain};
 use sp_consensus_grandpa::{ConsensusLog, GrandpaApi, ScheduledChange, SetId, GRANDPA_ENGINE_ID};
 use sp_runtime::{
@@ -557,8 +557,53 @@ where
 							.into(),
 					))
 				}
+				// The block history after warp sync is imported backwards, from the current set.
+				let filling_history_backwards = self.inner.info().block_gap.map_or(false, |gap| {
+					gap.gap_type == BlockGapType::MissingHeaderAndBody && gap.end == number
+				});
 				let mut authority_set = self.authority_set.inner_locked();
-				authority_set.authority_set_changes.insert(number);
+				if filling_history_backwards {
+					// Forced changes skip set ids, so the id of the set is recovered from the
+					// signatures of the justification instead of the changes around it.
+					let justification = block
+						.justifications
+						.as_ref()
+						.and_then(|justifications| justifications.get(GRANDPA_ENGINE_ID))
+						.and_then(|encoded| {
+							GrandpaJustification::<Block>::decode_all(&mut &encoded[..]).ok()
+						})
+						.filter(|justification| {
+							let commit = &justification.justification.commit;
+							(commit.target_hash, commit.target_number) == (hash, number)
+						})
+						.ok_or_else(|| {
+							ConsensusError::ClientImport(
+								"Invalid GRANDPA justification of an old block with authority set \
+									change."
+									.into(),
+							)
+						})?;
+					let possible_set_ids = authority_set
+						.authority_set_changes
+						.possible_set_ids(&number, authority_set.set_id);
+					// Without forced changes, the set right below the next known one signed it.
+					let set_id =
+						justification.signer_set_id(possible_set_ids.rev()).ok_or_else(|| {
+							ConsensusError::ClientImport(
+								"GRANDPA justification of an old block with authority set change \
+									isn't signed by any possible set."
+									.into(),
+							)
+						})?;
+					if !authority_set.authority_set_changes.insert_with_set_id(set_id, number) {
+						debug!(
+							target: LOG_TARGET,
+							"Ignoring known or inconsistent change of set {set_id} at #{number}",
+						);
+					}
+				} else {
+					authority_set.authority_set_changes.insert(number);
+				}
 				crate::aux_schema::update_authority_set::<Block, _, _>(
 					&authority_set,
 					None,
@@ -162,6 +162,28 @@ impl<Block: BlockT> GrandpaJustification<Block> {
 		self.verify_with_voter_set(set_id, &voters)
 	}
 
+	/// Find the id of the authority set that signed this justification among `set_ids`.
+	///
+	/// Only the signature of one precommit is checked, against the key of its signer. This
+	/// doesn't prove finality, but tells which set finalized a block already known to be
+	/// canonical.
+	pub(crate) fn signer_set_id(&self, set_ids: impl IntoIterator<Item = u64>) -> Option<u64> {
+		let signed = self.justification.commit.precommits.first()?;
+		let message = finality_grandpa::Message::Precommit(signed.precommit.clone());
+		let mut buf = Vec::new();
+
+		set_ids.into_iter().find(|set_id| {
+			sp_consensus_grandpa::check_message_signature_with_buffer(
+				&message,
+				&signed.id,
+				&signed.signature,
+				self.justification.round,
+				*set_id,
+				&mut buf,
+			)
+		})
+	}
+
 	/// Validate the commit and the votes' ancestry proofs.
 	pub(crate) fn verify_with_voter_set(
 		&self,
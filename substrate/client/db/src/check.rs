@@ -0,0 +1,508 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Database integrity checks.
+//!
+//! [`check_database`] verifies that:
+//! - the state-db meta-data is consistent, see [`sc_state_db::check_meta`],
+//! - every canonical block up to the best one has a header linked to its parent,
+//! - the bodies kept according to the blocks pruning setting can be read, and the ones that should
+//!   have been pruned are gone,
+//! - the justifications can be decoded,
+//! - every leaf has a header,
+//! - every trie node of the finalized and best states, or of every kept state, can be read.
+//!
+//! The problems that can be fixed without resyncing are repaired on request: inconsistent
+//! state-db journals are rewritten, unpruned blocks are pruned and stale leaves are removed.
+
+use crate::{
+	apply_state_commit, columns,
+	utils::{self, meta_keys, DatabaseType},
+	Backend, BlocksPruning, Database, DatabaseSettings, DbHash, StateMetaDb, StorageDb,
+};
+use codec::Decode;
+use hash_db::Prefix;
+use log::info;
+use sc_state_db::MetaIssue;
+use sp_blockchain::{BlockGapType, HeaderBackend, Result as ClientResult};
+use sp_database::Transaction;
+use sp_runtime::{
+	generic::BlockId,
+	traits::{Block as BlockT, HashingFor, Header as HeaderT, NumberFor, One, Zero},
+};
+use sp_state_machine::{DBValue, TrieBackendStorage};
+use std::{
+	collections::HashSet,
+	fmt,
+	sync::atomic::{AtomicU64, Ordering},
+};
+
+/// A problem found by [`check_database`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Problem<Hash, Number> {
+	/// The database can't be opened, the checks needing an opened database were skipped.
+	Open(String),
+	/// The state-db meta-data is inconsistent.
+	StateMeta(MetaIssue<Hash>),
+	/// The canonical block with this number can't be found.
+	MissingBlock(Number),
+	/// The header of a canonical block is missing.
+	MissingHeader { number: Number, hash: Hash },
+	/// The header of a canonical block can't be decoded.
+	InvalidHeader { number: Number, hash: Hash },
+	/// The header of a canonical block is stored under another hash.
+	MismatchedHeader { number: Number, hash: Hash, found: Hash },
+	/// A canonical block is not a child of the previous canonical block.
+	BrokenLink { number: Number, hash: Hash, parent_hash: Hash },
+	/// The body of a block is missing while it should be kept.
+	MissingBody { number: Number, hash: Hash },
+	/// The body of a block can't be read, e.g. an indexed transaction is missing.
+	InvalidBody { number: Number, hash: Hash, error: String },
+	/// The justifications of a block can't be decoded.
+	InvalidJustifications { number: Number, hash: Hash },
+	/// The body or the justifications of a block are kept while they should have been pruned.
+	UnprunedBlock { number: Number, hash: Hash },
+	/// A leaf has no header.
+	StaleLeaf { number: Number, hash: Hash },
+	/// The state of a block can't be read, e.g. a trie node is missing.
+	InvalidState { number: Number, hash: Hash, error: String },
+}
+
+impl<Hash, Number> Problem<Hash, Number> {
+	/// Returns whether [`check_database`] can repair the problem.
+	pub fn is_repairable(&self) -> bool {
+		match self {
+			Problem::StateMeta(issue) => issue.is_repairable(),
+			Problem::UnprunedBlock { .. } | Problem::StaleLeaf { .. } => true,
+			_ => false,
+		}
+	}
+}
+
+impl<Hash: fmt::Debug, Number: fmt::Display> fmt::Display for Problem<Hash, Number> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Problem::Open(error) => write!(f, "Failed to open the database: {error}"),
+			Problem::StateMeta(issue) => issue.fmt(f),
+			Problem::MissingBlock(number) => write!(f, "Canonical block #{number} is missing"),
+			Problem::MissingHeader { number, hash } =>
+				write!(f, "Header of #{number} ({hash:?}) is missing"),
+			Problem::InvalidHeader { number, hash } =>
+				write!(f, "Header of #{number} ({hash:?}) can't be decoded"),
+			Problem::MismatchedHeader { number, hash, found } =>
+				write!(f, "Header of #{number} ({hash:?}) hashes to {found:?}"),
+			Problem::BrokenLink { number, hash, parent_hash } => write!(
+				f,
+				"Parent {parent_hash:?} of #{number} ({hash:?}) is not the previous canonical block"
+			),
+			Problem::MissingBody { number, hash } =>
+				write!(f, "Body of #{number} ({hash:?}) is missing"),
+			Problem::InvalidBody { number, hash, error } =>
+				write!(f, "Body of #{number} ({hash:?}) can't be read: {error}"),
+			Problem::InvalidJustifications { number, hash } =>
+				write!(f, "Justifications of #{number} ({hash:?}) can't be decoded"),
+			Problem::UnprunedBlock { number, hash } =>
+				write!(f, "Body or justifications of #{number} ({hash:?}) should have been pruned"),
+			Problem::StaleLeaf { number, hash } =>
+				write!(f, "Leaf #{number} ({hash:?}) has no header"),
+			Problem::InvalidState { number, hash, error } =>
+				write!(f, "State of #{number} ({hash:?}) can't be read: {error}"),
+		}
+	}
+}
+
+/// The options of [`check_database`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CheckOptions {
+	/// Repair the problems that can be repaired.
+	pub repair: bool,
+	/// Check every state kept in the database instead of the finalized and best ones only.
+	pub all_states: bool,
+}
+
+/// The result of [`check_database`].
+#[derive(Debug)]
+pub struct CheckReport<Hash, Number> {
+	/// The problems found.
+	pub problems: Vec<Problem<Hash, Number>>,
+	/// Whether the repairable problems were repaired.
+	pub repaired: bool,
+	/// The number of canonical blocks checked.
+	pub blocks: u64,
+	/// The number of trie nodes read while checking the states.
+	pub state_nodes: u64,
+}
+
+impl<Hash, Number> CheckReport<Hash, Number> {
+	/// Returns the problems left in the database.
+	pub fn remaining(&self) -> impl Iterator<Item = &Problem<Hash, Number>> {
+		self.problems
+			.iter()
+			.filter(|problem| !(self.repaired && problem.is_repairable()))
+	}
+}
+
+/// Counts the trie nodes read from the database.
+struct CountingStorage<'a, Block: BlockT> {
+	storage: &'a StorageDb<Block>,
+	nodes: AtomicU64,
+}
+
+impl<Block: BlockT> TrieBackendStorage<HashingFor<Block>> for CountingStorage<'_, Block> {
+	fn get(&self, key: &Block::Hash, prefix: Prefix) -> Result<Option<DBValue>, String> {
+		let value = sp_state_machine::Storage::get(self.storage, key, prefix)?;
+		if value.is_some() {
+			self.nodes.fetch_add(1, Ordering::Relaxed);
+		}
+		Ok(value)
+	}
+}
+
+/// Returns the hash of the canonical block with the given number, as stored in the lookup index.
+fn canonical_hash<Block: BlockT>(
+	db: &dyn Database<DbHash>,
+	number: impl TryInto<u32>,
+) -> Option<Block::Hash> {
+	let lookup_key = db.get(columns::KEY_LOOKUP, &utils::number_index_key(number).ok()?)?;
+	Block::Hash::decode(&mut lookup_key.get(4..)?).ok()
+}
+
+/// Check the integrity of the database described by `settings`, see the module documentation.
+///
+/// The database must not be used by a running node.
+pub fn check_database<Block: BlockT>(
+	settings: &DatabaseSettings,
+	options: CheckOptions,
+) -> ClientResult<CheckReport<Block::Hash, NumberFor<Block>>> {
+	let db = utils::open_database::<Block>(&settings.source, DatabaseType::Full, false)?;
+	let mut report =
+		CheckReport { problems: Vec::new(), repaired: options.repair, blocks: 0, state_nodes: 0 };
+
+	// The state-db is checked before opening the backend, which fails on inconsistent journals.
+	info!("Checking the state-db meta-data");
+	let meta_check =
+		sc_state_db::check_meta::<Block::Hash, Vec<u8>, _>(&StateMetaDb(db.clone()), |number| {
+			canonical_hash::<Block>(&*db, number)
+		})
+		.map_err(sp_blockchain::Error::from_state_db)?;
+	report.problems.extend(meta_check.issues.into_iter().map(Problem::StateMeta));
+	if options.repair {
+		let mut transaction = Transaction::new();
+		apply_state_commit(&mut transaction, meta_check.repair);
+		db.commit(transaction)?;
+	}
+
+	let backend = match Backend::<Block>::from_database(db, 0, settings, false) {
+		Ok(backend) => backend,
+		Err(e) => {
+			report.problems.push(Problem::Open(e.to_string()));
+			return Ok(report)
+		},
+	};
+	let mut transaction = Transaction::new();
+	check_chain(&backend, &mut report, &mut transaction)?;
+	check_leaves(&backend, &mut report, &mut transaction)?;
+	check_states(&backend, &mut report, options.all_states)?;
+	if options.repair {
+		backend.storage.db.commit(transaction)?;
+	}
+	Ok(report)
+}
+
+fn check_chain<Block: BlockT>(
+	backend: &Backend<Block>,
+	report: &mut CheckReport<Block::Hash, NumberFor<Block>>,
+	transaction: &mut Transaction<DbHash>,
+) -> ClientResult<()> {
+	let info = backend.blockchain.info();
+	let db = &*backend.storage.db;
+	let in_gap = |number: NumberFor<Block>, gap_type: BlockGapType| {
+		info.block_gap.map_or(false, |gap| {
+			gap.gap_type == gap_type && gap.start <= number && number <= gap.end
+		})
+	};
+	// The bodies of the finalized blocks below this one are pruned.
+	let first_kept = match backend.blocks_pruning {
+		BlocksPruning::Some(keep) => {
+			let keep: NumberFor<Block> = keep.max(1).into();
+			if info.finalized_number >= keep {
+				info.finalized_number - keep + One::one()
+			} else {
+				Zero::zero()
+			}
+		},
+		BlocksPruning::KeepAll | BlocksPruning::KeepFinalized => Zero::zero(),
+	};
+
+	info!("Checking the canonical chain up to #{}", info.best_number);
+	let mut parent = None;
+	let mut number = Zero::zero();
+	while number <= info.best_number {
+		let current = number;
+		number += One::one();
+		if in_gap(current, BlockGapType::MissingHeaderAndBody) {
+			parent = None;
+			continue
+		}
+		report.blocks += 1;
+		let Some(hash) = canonical_hash::<Block>(db, current) else {
+			report.problems.push(Problem::MissingBlock(current));
+			parent = None;
+			continue
+		};
+		parent = check_header(backend, current, hash, parent, report)?.then_some(hash);
+
+		let id = BlockId::<Block>::Number(current);
+		if current < first_kept {
+			let body = utils::read_db(db, columns::KEY_LOOKUP, columns::BODY, id)?;
+			let index = utils::read_db(db, columns::KEY_LOOKUP, columns::BODY_INDEX, id)?;
+			let justifications =
+				utils::read_db(db, columns::KEY_LOOKUP, columns::JUSTIFICATIONS, id)?;
+			if body.is_some() || index.is_some() || justifications.is_some() {
+				report.problems.push(Problem::UnprunedBlock { number: current, hash });
+				backend.prune_block(transaction, id)?;
+			}
+			continue
+		}
+
+		match backend.blockchain.body_uncached(hash) {
+			Ok(Some(_)) => {},
+			Ok(None) if in_gap(current, BlockGapType::MissingBody) => {},
+			Ok(None) => report.problems.push(Problem::MissingBody { number: current, hash }),
+			Err(e) => report.problems.push(Problem::InvalidBody {
+				number: current,
+				hash,
+				error: e.to_string(),
+			}),
+		}
+		if backend.blockchain.justifications_uncached(hash).is_err() {
+			report.problems.push(Problem::InvalidJustifications { number: current, hash });
+		}
+	}
+	Ok(())
+}
+
+/// Check the header of the canonical block `hash`, returns whether it is valid.
+fn check_header<Block: BlockT>(
+	backend: &Backend<Block>,
+	number: NumberFor<Block>,
+	hash: Block::Hash,
+	parent: Option<Block::Hash>,
+	report: &mut CheckReport<Block::Hash, NumberFor<Block>>,
+) -> ClientResult<bool> {
+	let id = BlockId::<Block>::Number(number);
+	let Some(header) =
+		utils::read_db(&*backend.storage.db, columns::KEY_LOOKUP, columns::HEADER, id)?
+	else {
+		report.problems.push(Problem::MissingHeader { number, hash });
+		return Ok(false)
+	};
+	let Ok(header) = Block::Header::decode(&mut &header[..]) else {
+		report.problems.push(Problem::InvalidHeader { number, hash });
+		return Ok(false)
+	};
+	if header.hash() != hash {
+		report
+			.problems
+			.push(Problem::MismatchedHeader { number, hash, found: header.hash() });
+		return Ok(false)
+	}
+	if parent.map_or(false, |parent| parent != *header.parent_hash()) {
+		let parent_hash = *header.parent_hash();
+		report.problems.push(Problem::BrokenLink { number, hash, parent_hash });
+	}
+	Ok(true)
+}
+
+fn check_leaves<Block: BlockT>(
+	backend: &Backend<Block>,
+	report: &mut CheckReport<Block::Hash, NumberFor<Block>>,
+	transaction: &mut Transaction<DbHash>,
+) -> ClientResult<()> {
+	info!("Checking the leaves");
+	// The leaf set keeps the leaves grouped by number, which it doesn't expose.
+	let leaves = backend
+		.storage
+		.db
+		.get(columns::META, meta_keys::LEAF_PREFIX)
+		.map(|leaves| {
+			Vec::<(NumberFor<Block>, Vec<Block::Hash>)>::decode(&mut &leaves[..])
+				.map_err(|_| sp_blockchain::Error::Backend("Error decoding leaves".into()))
+		})
+		.transpose()?
+		.unwrap_or_default();
+
+	let mut leaf_set = backend.blockchain.leaves.write();
+	let mut stale = false;
+	for (number, hashes) in leaves {
+		for hash in hashes {
+			if backend.blockchain.header(hash)?.is_none() {
+				report.problems.push(Problem::StaleLeaf { number, hash });
+				leaf_set.remove(hash, number, None);
+				stale = true;
+			}
+		}
+	}
+	if stale {
+		leaf_set.prepare_transaction(transaction, columns::META, meta_keys::LEAF_PREFIX);
+	}
+	Ok(())
+}
+
+fn check_states<Block: BlockT>(
+	backend: &Backend<Block>,
+	report: &mut CheckReport<Block::Hash, NumberFor<Block>>,
+	all_states: bool,
+) -> ClientResult<()> {
+	let info = backend.blockchain.info();
+	let mut blocks = Vec::new();
+	if all_states {
+		let mut number = Zero::zero();
+		while number <= info.best_number {
+			blocks.extend(canonical_hash::<Block>(&*backend.storage.db, number));
+			number += One::one();
+		}
+		blocks.extend(backend.blockchain.leaves.read().hashes());
+	} else if let Some((hash, _)) = info.finalized_state {
+		// The blocks imported on top of the finalized state have their state.
+		blocks.extend([hash, info.best_hash]);
+	}
+
+	let mut checked = HashSet::new();
+	for hash in blocks {
+		// The blocks without a valid header are reported by the chain checks.
+		let Some(header) = backend.blockchain.header(hash).ok().flatten() else { continue };
+		let number = *header.number();
+		if !checked.insert(hash) ||
+			(all_states && !sc_client_api::Backend::have_state_at(backend, hash, number))
+		{
+			continue
+		}
+		info!("Checking the state of #{number} ({hash:?})");
+		let storage = CountingStorage { storage: &*backend.storage, nodes: AtomicU64::new(0) };
+		if let Err(error) = utils::walk_state::<Block, _>(&storage, *header.state_root()) {
+			report.problems.push(Problem::InvalidState { number, hash, error });
+		}
+		report.state_nodes += storage.nodes.load(Ordering::Relaxed);
+	}
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{
+		tests::{insert_header, Block},
+		DatabaseSource, PruningMode,
+	};
+	use sc_client_api::{backend::Backend as _, blockchain::Backend as _, leaves::LeafSet};
+	use sp_core::H256;
+	use sp_runtime::traits::BlakeTwo256;
+	use sp_trie::{prefixed_key, EMPTY_PREFIX};
+	use std::sync::Arc;
+
+	fn settings(db: Arc<dyn Database<DbHash>>, require_create_flag: bool) -> DatabaseSettings {
+		DatabaseSettings {
+			trie_cache_maximum_size: None,
+			state_pruning: Some(PruningMode::blocks_pruning(4)),
+			source: DatabaseSource::Custom { db, require_create_flag },
+			blocks_pruning: BlocksPruning::Some(2),
+			metrics_registry: None,
+		}
+	}
+
+	/// Import 6 blocks and finalize the 5th one, either block by block or at once.
+	fn chain(finalize_each: bool) -> (Arc<dyn Database<DbHash>>, Vec<H256>) {
+		let db = sp_database::as_database(kvdb_memorydb::create(utils::NUM_COLUMNS));
+		let backend = Backend::<Block>::new(settings(db.clone(), true), 0).unwrap();
+		let mut hashes = Vec::new();
+		let mut parent = Default::default();
+		for number in 0..6 {
+			parent = insert_header(&backend, number, parent, None, Default::default());
+			hashes.push(parent);
+		}
+		let first = if finalize_each { 1 } else { 4 };
+		for hash in &hashes[first..5] {
+			backend.finalize_block(*hash, None).unwrap();
+		}
+		(db, hashes)
+	}
+
+	fn check(db: &Arc<dyn Database<DbHash>>, repair: bool) -> CheckReport<H256, u64> {
+		let options = CheckOptions { repair, all_states: true };
+		check_database::<Block>(&settings(db.clone(), false), options).unwrap()
+	}
+
+	#[test]
+	fn consistent_database_passes() {
+		let (db, _) = chain(true);
+		let report = check(&db, false);
+		assert_eq!(report.problems, vec![]);
+		assert_eq!(report.blocks, 6);
+		assert!(report.state_nodes > 0);
+	}
+
+	#[test]
+	fn missing_data_is_reported() {
+		let (db, hashes) = chain(true);
+		let backend = Backend::<Block>::new(settings(db.clone(), false), 0).unwrap();
+		let root = *backend.blockchain().expect_header(hashes[4]).unwrap().state_root();
+		drop(backend);
+
+		let mut transaction = Transaction::new();
+		let lookup_key = utils::number_and_hash_to_lookup_key(3, hashes[3]).unwrap();
+		transaction.remove(columns::BODY, &lookup_key);
+		transaction.remove(columns::STATE, &prefixed_key::<BlakeTwo256>(&root, EMPTY_PREFIX));
+		db.commit(transaction).unwrap();
+
+		let report = check(&db, true);
+		assert_eq!(report.problems.len(), 2);
+		assert_eq!(report.problems[0], Problem::MissingBody { number: 3, hash: hashes[3] });
+		assert!(matches!(report.problems[1], Problem::InvalidState { number: 4, .. }));
+		assert_eq!(report.remaining().count(), 2);
+	}
+
+	#[test]
+	fn stale_data_is_repaired() {
+		let (db, hashes) = chain(false);
+		let mut leaves =
+			LeafSet::<H256, u64>::read_from_db(&*db, columns::META, meta_keys::LEAF_PREFIX)
+				.unwrap();
+		leaves.import(H256::repeat_byte(7), 9, H256::repeat_byte(8));
+		let mut transaction = Transaction::new();
+		leaves.prepare_transaction(&mut transaction, columns::META, meta_keys::LEAF_PREFIX);
+		db.commit(transaction).unwrap();
+
+		// Finalizing several blocks at once only prunes the body of the last one's ancestor.
+		let report = check(&db, true);
+		assert_eq!(
+			report.problems,
+			vec![
+				Problem::UnprunedBlock { number: 0, hash: hashes[0] },
+				Problem::UnprunedBlock { number: 1, hash: hashes[1] },
+				Problem::StaleLeaf { number: 9, hash: H256::repeat_byte(7) },
+			]
+		);
+		assert_eq!(report.remaining().count(), 0);
+		assert_eq!(check(&db, false).problems, vec![]);
+
+		let backend = Backend::<Block>::new(settings(db, false), 0).unwrap();
+		assert_eq!(backend.blockchain().leaves().unwrap(), vec![hashes[5]]);
+		assert_eq!(backend.blockchain().body(hashes[1]).unwrap(), None);
+	}
+}
@@ -31,6 +31,10 @@
 pub mod offchain;
 
 pub mod bench;
+pub mod check;
+#[cfg(any(feature = "rocksdb", test))]
+pub mod migrate;
+pub mod snapshot;
 
 mod children;
 mod parity_db;
@@ -96,7 +100,9 @@ use sp_trie::{cache::SharedTrieCache, prefixed_key, MemoryDB, MerkleValue, Prefi
 use utils::BLOCK_GAP_CURRENT_VERSION;
 
 // Re-export the Database trait so that one can pass an implementation of it.
-pub use sc_state_db::PruningMode;
+pub use sc_state_db::{
+	Constraints as PruningConstraints, PruningMode, WindowLimits as PruningWindowLimits,
+};
 pub use sp_database::Database;
 
 pub use bench::BenchmarkingState;
@@ -1746,6 +1752,38 @@ impl<Block: BlockT> Backend<Block> {
 									debug!(target: "db", "Update block gap. {block_gap:?}");
 								}
 								block_gap_updated = true;
+							} else if number == gap.end {
+								// Gap decreased when downloading the block history backwards from
+								// the warp sync target. Only the block the chain above links to
+								// can close it.
+								let child_parent_hash =
+									match self.blockchain.hash(number + One::one())? {
+										Some(child) =>
+											self.blockchain.header(child)?.map(|h| *h.parent_hash()),
+										None => None,
+									};
+								if child_parent_hash != Some(hash) {
+									return Err(sp_blockchain::Error::Backend(format!(
+										"Block #{number} ({hash:?}) is not the parent of the block above the gap"
+									)))
+								}
+								utils::insert_number_to_key_mapping(
+									&mut transaction,
+									columns::KEY_LOOKUP,
+									number,
+									hash,
+								)?;
+								if gap.start == gap.end {
+									transaction.remove(columns::META, meta_keys::BLOCK_GAP);
+									transaction.remove(columns::META, meta_keys::BLOCK_GAP_VERSION);
+									block_gap = None;
+									debug!(target: "db", "Removed block gap.");
+								} else {
+									gap.end -= One::one();
+									insert_new_gap(&mut transaction, gap, &mut block_gap);
+									debug!(target: "db", "Update block gap. {block_gap:?}");
+								}
+								block_gap_updated = true;
 							},
 						BlockGapType::MissingBody => {
 							// Gap increased when syncing the header chain during fast sync.
@@ -2592,6 +2630,11 @@ impl<Block: BlockT> sc_client_api::backend::Backend<Block> for Backend<Block> {
 		)
 	}
 
+	fn snapshot(&self, path: &Path) -> ClientResult<(Block::Hash, NumberFor<Block>)> {
+		Backend::<Block>::snapshot(self, path)
+			.map(|manifest| (manifest.finalized_hash, manifest.finalized_number))
+	}
+
 	fn pin_block(&self, hash: <Block as BlockT>::Hash) -> sp_blockchain::Result<()> {
 		let hint = || {
 			let header_metadata = self.blockchain.header_metadata(hash);
@@ -3237,6 +3280,47 @@ pub(crate) mod tests {
 		}
 	}
 
+	#[test]
+	fn block_gap_is_filled_backwards() {
+		// g -> <unimported a1 -> a2 -> a3> -> a4
+		let backend = Backend::<Block>::new_test(1000, 100);
+		let blockchain = backend.blockchain();
+		let genesis_hash = insert_header(&backend, 0, Default::default(), None, Default::default());
+		let header = |number: u64, parent_hash| Header {
+			number,
+			parent_hash,
+			state_root: Default::default(),
+			digest: Default::default(),
+			extrinsics_root: H256::from([number as u8; 32]),
+		};
+		let a1 = header(1, genesis_hash);
+		let a2 = header(2, a1.hash());
+		let a3 = header(3, a2.hash());
+		insert_disconnected_header(&backend, 4, a3.hash(), H256::from([4; 32]), true);
+		assert_eq!(
+			blockchain.info().block_gap,
+			Some(BlockGap { start: 1, end: 3, gap_type: BlockGapType::MissingHeaderAndBody })
+		);
+
+		// A block at the top of the gap must be the parent of the block above it.
+		let mut op = backend.begin_operation().unwrap();
+		op.set_block_data(header(3, a1.hash()), Some(vec![]), None, None, NewBlockState::Normal)
+			.unwrap();
+		assert!(backend.commit_operation(op).is_err());
+
+		insert_disconnected_header(&backend, 3, a2.hash(), H256::from([3; 32]), false);
+		assert_eq!(
+			blockchain.info().block_gap,
+			Some(BlockGap { start: 1, end: 2, gap_type: BlockGapType::MissingHeaderAndBody })
+		);
+		assert_eq!(blockchain.hash(3).unwrap(), Some(a3.hash()));
+
+		insert_disconnected_header(&backend, 2, a1.hash(), H256::from([2; 32]), false);
+		insert_disconnected_header(&backend, 1, genesis_hash, H256::from([1; 32]), false);
+		assert_eq!(blockchain.info().block_gap, None);
+		assert_eq!(blockchain.hash(1).unwrap(), Some(a1.hash()));
+	}
+
 	#[test]
 	fn displaced_leaves_after_finalizing_works_with_disconnect() {
 		// In this test we will create a situation that can typically happen after warp sync.
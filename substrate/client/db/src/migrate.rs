@@ -0,0 +1,851 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Migration of a database between RocksDB and ParityDB.
+//!
+//! When migrating from RocksDB, every column of the source database is streamed into the target
+//! database in key order. Once all the columns are migrated, every entry of the source database
+//! is checked against the target database.
+//!
+//! The state is stored under prefixed keys by RocksDB, and under the hash of the nodes by
+//! ParityDB, which keeps reference counts instead. The state-db journals of both databases can't
+//! be converted into each other, so the migrated database starts from the last finalized block
+//! of the source database, as if restored from a snapshot: the unfinalized blocks are not
+//! migrated and the state-db meta-data is reset to the finalized block. With a constrained
+//! pruning mode, only the state of the finalized block is migrated, by walking its trie. The
+//! archive modes migrate the whole state column, whose nodes are then never pruned.
+//!
+//! ParityDB does not keep the keys of most of its columns, so a migration from ParityDB walks
+//! the data instead: the canonical blocks up to the last finalized block, and the trie of its
+//! state, whose nodes are written under their prefixed keys. The migrated database holds the
+//! state of the finalized block only. Unfinalized blocks, the states of older blocks, the
+//! auxiliary data and the offchain storage can't be enumerated and are lost, so this migration
+//! has to be allowed explicitly.
+//!
+//! In both directions, the progress of the migration is committed to the target database along
+//! with the migrated entries, so an interrupted migration resumes where it stopped.
+
+use crate::{
+	apply_state_commit, columns,
+	utils::{self, meta_keys, DatabaseType, OpenDbError, NUM_COLUMNS},
+	Database, DatabaseSource, DbExtrinsic, DbHash, StateMetaDb, DB_HASH_LEN,
+};
+use codec::{Decode, Encode};
+use hash_db::{Hasher, Prefix};
+use kvdb::KeyValueDB;
+use log::{info, warn};
+use parking_lot::Mutex;
+use sc_client_api::leaves::LeafSet;
+use sc_state_db::PruningMode;
+use sp_blockchain::{Error as ClientError, Result as ClientResult};
+use sp_core::hexdisplay::HexDisplay;
+use sp_database::Transaction;
+use sp_runtime::{
+	generic::BlockId,
+	traits::{Block as BlockT, Header as HeaderT, SaturatedConversion},
+};
+use sp_state_machine::{DBValue, TrieBackendStorage};
+use sp_trie::prefixed_key;
+use std::{
+	path::Path,
+	sync::Arc,
+	time::{Duration, Instant},
+};
+
+/// The key of the migration progress in the meta column of the target database.
+const MIGRATION_PROGRESS: &[u8] = b"migration_progress";
+
+/// The amount of data written to the target database in a single transaction.
+const MIGRATION_BATCH_BYTES: usize = 64 * 1024 * 1024;
+
+/// The interval between two progress reports.
+const PROGRESS_INTERVAL: Duration = Duration::from_secs(10);
+
+/// The maximum number of mismatching entries reported by the consistency check.
+const MAX_REPORTED_MISMATCHES: u64 = 16;
+
+/// The progress of a migration from RocksDB.
+#[derive(Debug, Default, Encode, Decode)]
+struct MigrationProgress {
+	/// The column being migrated, all the columns are migrated once it equals [`NUM_COLUMNS`].
+	column: u32,
+	/// The last migrated key of the column.
+	last_key: Option<Vec<u8>>,
+	/// Whether the state of the finalized block is migrated, when it is walked.
+	state: bool,
+	/// The number of entries migrated so far.
+	entries: u64,
+}
+
+/// The progress of a migration from ParityDB.
+#[derive(Debug, Default, Encode, Decode)]
+struct ParityDbMigrationProgress {
+	/// The next block to migrate.
+	next_block: u32,
+	/// Whether the state of the finalized block is migrated.
+	state: bool,
+	/// The number of entries migrated so far.
+	entries: u64,
+}
+
+fn io_error(error: std::io::Error) -> ClientError {
+	ClientError::Backend(format!("Failed to read the source database: {error}"))
+}
+
+/// The source database of a migration from RocksDB.
+struct RocksDbSource {
+	db: kvdb_rocksdb::Database,
+	/// The number of the last finalized block, the blocks above it are not migrated.
+	finalized: u32,
+	/// Whether the whole state column is migrated, rather than the state of the finalized block.
+	copy_state: bool,
+}
+
+impl RocksDbSource {
+	/// Returns the number of the block with the given hash, if it is known.
+	fn block_number(&self, hash: &[u8]) -> ClientResult<Option<u32>> {
+		let lookup_key = self.db.get(columns::KEY_LOOKUP, hash).map_err(io_error)?;
+		Ok(lookup_key.as_deref().and_then(lookup_key_number))
+	}
+
+	/// Returns whether the given block number is migrated.
+	fn is_migrated(&self, number: Option<u32>) -> bool {
+		number.map_or(true, |number| number <= self.finalized)
+	}
+}
+
+/// Reads the state pruning mode from the meta column of a RocksDB database.
+struct RocksDbStateMeta<'a>(&'a kvdb_rocksdb::Database);
+
+impl sc_state_db::MetaDb for RocksDbStateMeta<'_> {
+	type Error = std::io::Error;
+
+	fn get_meta(&self, key: &[u8]) -> Result<Option<Vec<u8>>, Self::Error> {
+		self.0.get(columns::STATE_META, key)
+	}
+}
+
+/// Returns the block number of a lookup key, see [`utils::number_and_hash_to_lookup_key`].
+fn lookup_key_number(lookup_key: &[u8]) -> Option<u32> {
+	lookup_key.get(..4)?.try_into().ok().map(u32::from_be_bytes)
+}
+
+/// Returns the entry of the target database for the given entry of the source database.
+///
+/// Returns `None` for the entries which are not migrated: the reference counters of the
+/// transaction column, which are migrated along with the transactions, the state-db meta-data,
+/// the unfinalized blocks and the meta-data referencing them. The state column is skipped too
+/// when only the state of the finalized block is migrated.
+fn target_entry(
+	source: &RocksDbSource,
+	target: &dyn Database<DbHash>,
+	column: u32,
+	mut key: Vec<u8>,
+	value: Vec<u8>,
+) -> ClientResult<Option<(Vec<u8>, Vec<u8>)>> {
+	let migrated = match column {
+		columns::STATE if source.copy_state => {
+			target.sanitize_key(&mut key);
+			true
+		},
+		columns::STATE | columns::STATE_META => false,
+		columns::TRANSACTION => key.len() == DB_HASH_LEN,
+		columns::HEADER | columns::BODY | columns::BODY_INDEX | columns::JUSTIFICATIONS =>
+			source.is_migrated(lookup_key_number(&key)),
+		columns::KEY_LOOKUP if key.len() == 4 => source.is_migrated(lookup_key_number(&key)),
+		columns::KEY_LOOKUP => source.is_migrated(lookup_key_number(&value)),
+		// The best block and the leaves are reset to the finalized block.
+		columns::META if key.as_slice() == meta_keys::BEST_BLOCK => false,
+		columns::META if key.starts_with(meta_keys::LEAF_PREFIX) => false,
+		// The children of the finalized block and of the blocks above are not migrated.
+		columns::META => match key.strip_prefix(meta_keys::CHILDREN_PREFIX) {
+			Some(parent) => source.block_number(parent)?.map_or(true, |n| n < source.finalized),
+			None => true,
+		},
+		_ => true,
+	};
+	Ok(migrated.then_some((key, value)))
+}
+
+/// Returns the number of references of a transaction, see `sp_database::kvdb`.
+fn transaction_references(source: &kvdb_rocksdb::Database, key: &[u8]) -> ClientResult<u32> {
+	let mut counter_key = key.to_vec();
+	counter_key.push(0);
+	Ok(match source.get(columns::TRANSACTION, &counter_key).map_err(io_error)? {
+		Some(counter) => counter
+			.try_into()
+			.map(u32::from_le_bytes)
+			.map_err(|_| ClientError::Backend("Invalid transaction reference counter".into()))?,
+		None => 1,
+	})
+}
+
+/// Reports the progress of a column or of the blocks every [`PROGRESS_INTERVAL`].
+struct ProgressReport {
+	what: String,
+	estimated: u64,
+	done: u64,
+	last_report: Instant,
+}
+
+impl ProgressReport {
+	fn new(what: String, estimated: u64) -> Self {
+		Self { what, estimated, done: 0, last_report: Instant::now() }
+	}
+
+	fn column(source: &kvdb_rocksdb::Database, column: u32, action: &str) -> Self {
+		let estimated = source.num_keys(column).unwrap_or_default();
+		info!("{action} column {column}/{}, about {estimated} entries", NUM_COLUMNS - 1);
+		Self::new(format!("Column {column}"), estimated)
+	}
+
+	fn blocks(from: u32, to: u32, action: &str) -> Self {
+		info!("{action} blocks #{from} to #{to}");
+		Self::new("Blocks".into(), u64::from(to.saturating_sub(from)) + 1)
+	}
+
+	fn note_entry(&mut self) {
+		self.done += 1;
+		if self.last_report.elapsed() >= PROGRESS_INTERVAL {
+			let percent = (self.done * 100).checked_div(self.estimated).unwrap_or(100).min(100);
+			info!("{}: {} entries ({percent}%)", self.what, self.done);
+			self.last_report = Instant::now();
+		}
+	}
+}
+
+/// Notes an entry of the target database which does not match the source database.
+fn note_mismatch(mismatches: &mut u64, column: u32, key: &[u8]) {
+	*mismatches += 1;
+	if *mismatches <= MAX_REPORTED_MISMATCHES {
+		warn!("Mismatching entry in column {column}: 0x{}", HexDisplay::from(&key));
+	}
+}
+
+/// Opens the target database of a migration, along with the progress of the migration.
+///
+/// The target database is created if it does not exist. An existing target database is only
+/// opened if it is the target of an interrupted migration.
+fn open_target<Block: BlockT, P: Encode + Decode + Default>(
+	target: &DatabaseSource,
+) -> ClientResult<(Arc<dyn Database<DbHash>>, P)> {
+	match utils::open_database::<Block>(target, DatabaseType::Full, false) {
+		Ok(db) => match db.get(columns::META, MIGRATION_PROGRESS) {
+			Some(progress) => {
+				let progress = P::decode(&mut &progress[..]).map_err(|e| {
+					ClientError::Backend(format!("Invalid migration progress: {e}"))
+				})?;
+				Ok((db, progress))
+			},
+			None => Err(ClientError::Backend("The target database already exists".into())),
+		},
+		Err(OpenDbError::DoesNotExist) => {
+			let db = utils::open_database::<Block>(target, DatabaseType::Full, true)?;
+			let mut transaction = Transaction::new();
+			transaction.set_from_vec(columns::META, MIGRATION_PROGRESS, P::default().encode());
+			db.commit(transaction)?;
+			Ok((db, Default::default()))
+		},
+		Err(e) => Err(e.into()),
+	}
+}
+
+fn no_database(path: Option<&Path>) -> ClientError {
+	ClientError::Backend(format!(
+		"No database found at {}",
+		path.map_or_else(String::new, |path| path.display().to_string())
+	))
+}
+
+/// Migrate the database `source` to the database `target`, from RocksDB to ParityDB or back.
+///
+/// The target database shall not exist, unless it is the target of an interrupted migration from
+/// the same source. The source database is left untouched. Returns the number of migrated
+/// entries.
+///
+/// A migration from ParityDB loses the data which can't be enumerated, see the module
+/// documentation, and fails unless `allow_lossy` is set.
+pub fn migrate_database<Block: BlockT>(
+	source: &DatabaseSource,
+	target: &DatabaseSource,
+	allow_lossy: bool,
+) -> ClientResult<u64> {
+	match (source, target) {
+		(DatabaseSource::RocksDb { path, cache_size }, DatabaseSource::ParityDb { .. }) =>
+			migrate_from_rocksdb::<Block>(path, *cache_size, target),
+		(DatabaseSource::ParityDb { .. }, DatabaseSource::RocksDb { .. }) if allow_lossy =>
+			migrate_from_paritydb::<Block>(source, target),
+		(DatabaseSource::ParityDb { .. }, DatabaseSource::RocksDb { .. }) =>
+			Err(ClientError::Backend(
+				"Migrating from ParityDB loses the unfinalized blocks, the states of the blocks \
+				 before the finalized one, the auxiliary data and the offchain storage, and has to \
+				 be allowed explicitly"
+					.into(),
+			)),
+		_ => Err(ClientError::Backend(
+			"Databases can only be migrated from RocksDB to ParityDB, or back".into(),
+		)),
+	}
+}
+
+fn migrate_from_rocksdb<Block: BlockT>(
+	source_path: &Path,
+	cache_size: usize,
+	target: &DatabaseSource,
+) -> ClientResult<u64> {
+	let db = match utils::open_raw_kvdb_rocksdb::<Block>(
+		source_path,
+		DatabaseType::Full,
+		false,
+		cache_size,
+	) {
+		Ok(db) => db,
+		Err(OpenDbError::DoesNotExist) => return Err(no_database(Some(source_path))),
+		Err(e) => return Err(e.into()),
+	};
+	if db.get(columns::META, meta_keys::TYPE).map_err(io_error)?.as_deref() !=
+		Some(DatabaseType::Full.as_str().as_bytes())
+	{
+		return Err(ClientError::Backend("The source database is not a full node database".into()))
+	}
+
+	let lookup_key = db
+		.get(columns::META, meta_keys::FINALIZED_BLOCK)
+		.map_err(io_error)?
+		.ok_or_else(|| ClientError::Backend("The source database has no finalized block".into()))?;
+	let header = db
+		.get(columns::HEADER, &lookup_key)
+		.map_err(io_error)?
+		.ok_or_else(|| ClientError::UnknownBlock("Missing finalized header".into()))?;
+	let header = Block::Header::decode(&mut &header[..])
+		.map_err(|e| ClientError::Backend(format!("Error decoding header: {e}")))?;
+	let (hash, number) = (header.hash(), *header.number());
+	if db.get(columns::META, meta_keys::FINALIZED_STATE).map_err(io_error)? != Some(lookup_key) {
+		return Err(ClientError::UnknownBlock(format!(
+			"The state of the finalized block #{number} ({hash:?}) is not available"
+		)))
+	}
+	let mode = sc_state_db::fetch_stored_pruning_mode(&RocksDbStateMeta(&db))
+		.map_err(|e| ClientError::Backend(format!("Error reading the state pruning mode: {e:?}")))?
+		.ok_or_else(|| ClientError::Backend("The state pruning mode is not stored".into()))?;
+	let source = RocksDbSource {
+		db,
+		finalized: number.saturated_into(),
+		copy_state: !matches!(mode, PruningMode::Constrained(_) | PruningMode::Limited { .. }),
+	};
+
+	let (target, mut progress) = open_target::<Block, MigrationProgress>(target)?;
+	if progress.column > 0 || progress.last_key.is_some() {
+		info!("Resuming the migration from column {}", progress.column);
+	}
+
+	while progress.column < NUM_COLUMNS {
+		copy_column(&source, &*target, &mut progress)?;
+	}
+	if !source.copy_state && !progress.state {
+		info!("Migrating the state of #{number} ({hash:?})");
+		let read = |key: Vec<u8>| source.db.get(columns::STATE, &key).map_err(|e| e.to_string());
+		let pending = copy_state::<Block>(&read, &*target, *header.state_root())?;
+		progress.state = true;
+		progress.entries += pending.entries;
+		let mut transaction = pending.transaction;
+		transaction.set_from_vec(columns::META, MIGRATION_PROGRESS, progress.encode());
+		target.commit(transaction)?;
+	}
+	verify(&source, &*target)?;
+	verify_state::<Block>(&*target, *header.state_root())?;
+
+	let mut transaction = Transaction::new();
+	finalized_meta::<Block>(&mut transaction, &header, &mode)?;
+	target.commit(transaction)?;
+
+	info!("Database migrated at #{number} ({hash:?}), {} entries", progress.entries);
+	if !source.copy_state {
+		info!("The states of the blocks before #{number} are left to the source database");
+	}
+	Ok(progress.entries)
+}
+
+/// Copy the column of `progress` to the target database, starting after the last migrated key.
+fn copy_column(
+	source: &RocksDbSource,
+	target: &dyn Database<DbHash>,
+	progress: &mut MigrationProgress,
+) -> ClientResult<()> {
+	let column = progress.column;
+	let resume_after = progress.last_key.take();
+	let mut report = ProgressReport::column(&source.db, column, "Migrating");
+	let mut transaction = Transaction::new();
+	let mut pending_bytes = 0;
+
+	for entry in source.db.iter(column) {
+		let (key, value) = entry.map_err(io_error)?;
+		if resume_after.as_ref().map_or(false, |last| key.as_ref() <= last.as_slice()) {
+			continue
+		}
+		report.note_entry();
+
+		pending_bytes += key.len() + value.len();
+		let last_key = key.to_vec();
+		match target_entry(source, target, column, key.to_vec(), value)? {
+			Some((key, value)) if column == columns::TRANSACTION => {
+				let hash = DbHash::from_slice(&key);
+				for _ in 0..transaction_references(&source.db, &key)? {
+					transaction.store(column, hash, value.clone());
+				}
+			},
+			Some((key, value)) => transaction.set_from_vec(column, &key, value),
+			None => continue,
+		}
+		progress.entries += 1;
+
+		if pending_bytes >= MIGRATION_BATCH_BYTES {
+			progress.last_key = Some(last_key);
+			transaction.set_from_vec(columns::META, MIGRATION_PROGRESS, progress.encode());
+			target.commit(std::mem::take(&mut transaction))?;
+			pending_bytes = 0;
+		}
+	}
+
+	progress.column += 1;
+	progress.last_key = None;
+	transaction.set_from_vec(columns::META, MIGRATION_PROGRESS, progress.encode());
+	target.commit(transaction)?;
+	Ok(())
+}
+
+/// Check every migrated entry of the source database against the target database.
+fn verify(source: &RocksDbSource, target: &dyn Database<DbHash>) -> ClientResult<()> {
+	let mut mismatches = 0u64;
+	for column in 0..NUM_COLUMNS {
+		let mut report = ProgressReport::column(&source.db, column, "Checking");
+		for entry in source.db.iter(column) {
+			let (key, value) = entry.map_err(io_error)?;
+			report.note_entry();
+			let Some((key, value)) = target_entry(source, target, column, key.to_vec(), value)?
+			else {
+				continue
+			};
+			if target.get(column, &key).as_ref() != Some(&value) {
+				note_mismatch(&mut mismatches, column, &key);
+			}
+		}
+	}
+	if mismatches > 0 {
+		return Err(ClientError::Backend(format!(
+			"Consistency check failed, {mismatches} mismatching entries"
+		)))
+	}
+	Ok(())
+}
+
+/// Check that the target database holds the whole state with the given root.
+fn verify_state<Block: BlockT>(
+	target: &dyn Database<DbHash>,
+	root: Block::Hash,
+) -> ClientResult<()> {
+	utils::walk_state::<Block, _>(PrefixedState(target), root).map_err(|e| {
+		ClientError::Backend(format!("Consistency check failed, incomplete state: {e}"))
+	})?;
+	Ok(())
+}
+
+/// Copy the state with the given root from the source database to the target database.
+///
+/// Returns the nodes which are not committed yet.
+fn copy_state<Block: BlockT>(
+	source: StateReader<'_>,
+	target: &dyn Database<DbHash>,
+	root: Block::Hash,
+) -> ClientResult<PendingNodes> {
+	let state = utils::walk_state::<Block, _>(
+		CopiedState { source, target, pending: Default::default() },
+		root,
+	)
+	.map_err(ClientError::Backend)?;
+	Ok(state.pending.into_inner())
+}
+
+/// Writes the meta-data of a migrated database, whose best block and only leaf is its finalized
+/// block, and completes the migration.
+fn finalized_meta<Block: BlockT>(
+	transaction: &mut Transaction<DbHash>,
+	header: &Block::Header,
+	mode: &PruningMode,
+) -> ClientResult<()> {
+	let (hash, number) = (header.hash(), *header.number());
+	let lookup_key = utils::number_and_hash_to_lookup_key(number, hash)?;
+	transaction.set(columns::META, meta_keys::BEST_BLOCK, &lookup_key);
+	transaction.set(columns::META, meta_keys::FINALIZED_BLOCK, &lookup_key);
+	transaction.set(columns::META, meta_keys::FINALIZED_STATE, &lookup_key);
+	let mut leaves = LeafSet::new();
+	leaves.import(hash, number, *header.parent_hash());
+	leaves.prepare_transaction(transaction, columns::META, meta_keys::LEAF_PREFIX);
+	apply_state_commit(
+		transaction,
+		sc_state_db::restored_meta(mode, &hash, number.saturated_into::<u64>()),
+	);
+	transaction.remove(columns::META, MIGRATION_PROGRESS);
+	Ok(())
+}
+
+/// Migrate the ParityDB database `source` to the RocksDB database `target`.
+fn migrate_from_paritydb<Block: BlockT>(
+	source: &DatabaseSource,
+	target: &DatabaseSource,
+) -> ClientResult<u64> {
+	let source_db = match utils::open_database::<Block>(source, DatabaseType::Full, false) {
+		Ok(db) => db,
+		Err(OpenDbError::DoesNotExist) => return Err(no_database(source.path())),
+		Err(e) => return Err(e.into()),
+	};
+	let source = &*source_db;
+
+	let meta = utils::read_meta::<Block>(source, columns::HEADER)?;
+	let (hash, number) = (meta.finalized_hash, meta.finalized_number);
+	if meta.finalized_state != Some((hash, number)) {
+		return Err(ClientError::UnknownBlock(format!(
+			"The state of the finalized block #{number} ({hash:?}) is not available"
+		)))
+	}
+	let mode = sc_state_db::fetch_stored_pruning_mode(&StateMetaDb(source_db.clone()))
+		.map_err(|e| ClientError::Backend(format!("Error reading the state pruning mode: {e:?}")))?
+		.ok_or_else(|| ClientError::Backend("The state pruning mode is not stored".into()))?;
+	let header = utils::read_header::<Block>(
+		source,
+		columns::KEY_LOOKUP,
+		columns::HEADER,
+		BlockId::Hash(hash),
+	)?
+	.ok_or_else(|| ClientError::UnknownBlock(format!("Missing finalized header {hash:?}")))?;
+
+	let (target, mut progress) = open_target::<Block, ParityDbMigrationProgress>(target)?;
+	if progress.next_block > 0 {
+		info!("Resuming the migration from block #{}", progress.next_block);
+	}
+	let finalized = number.saturated_into::<u32>();
+
+	if progress.next_block <= finalized {
+		copy_blocks::<Block>(source, &*target, finalized, &mut progress)?;
+	}
+	if !progress.state {
+		info!("Migrating the state of #{number} ({hash:?})");
+		let read = |mut key: Vec<u8>| {
+			source.sanitize_key(&mut key);
+			Ok::<_, String>(source.get(columns::STATE, &key))
+		};
+		let pending = copy_state::<Block>(&read, &*target, *header.state_root())?;
+		progress.state = true;
+		progress.entries += pending.entries;
+		let mut transaction = pending.transaction;
+		transaction.set_from_vec(columns::META, MIGRATION_PROGRESS, progress.encode());
+		target.commit(transaction)?;
+	}
+	verify_blocks::<Block>(source, &*target, finalized)?;
+	verify_state::<Block>(&*target, *header.state_root())?;
+
+	let mut transaction = Transaction::new();
+	transaction.set(columns::META, meta_keys::GENESIS_HASH, meta.genesis_hash.as_ref());
+	for key in [&meta_keys::BLOCK_GAP[..], &meta_keys::BLOCK_GAP_VERSION[..]] {
+		if let Some(value) = source.get(columns::META, key) {
+			transaction.set_from_vec(columns::META, key, value);
+		}
+	}
+	finalized_meta::<Block>(&mut transaction, &header, &mode)?;
+	target.commit(transaction)?;
+
+	info!("Database migrated at #{number} ({hash:?}), {} entries", progress.entries);
+	warn!(
+		"Unfinalized blocks, the states of the blocks before #{number}, the auxiliary data and \
+		 the offchain storage were not migrated from ParityDB"
+	);
+	Ok(progress.entries)
+}
+
+/// Copy the canonical blocks up to the `finalized` block, starting at the next block of
+/// `progress`.
+fn copy_blocks<Block: BlockT>(
+	source: &dyn Database<DbHash>,
+	target: &dyn Database<DbHash>,
+	finalized: u32,
+	progress: &mut ParityDbMigrationProgress,
+) -> ClientResult<()> {
+	let mut report = ProgressReport::blocks(progress.next_block, finalized, "Migrating");
+	let mut transaction = Transaction::new();
+	let mut pending_bytes = 0;
+
+	for n in progress.next_block..=finalized {
+		report.note_entry();
+		let number_key = utils::number_index_key(n)?;
+		// Blocks may be missing after a warp sync.
+		let Some(lookup_key) = source.get(columns::KEY_LOOKUP, &number_key) else { continue };
+		let Some(header) = source.get(columns::HEADER, &lookup_key) else { continue };
+		let decoded = Block::Header::decode(&mut &header[..])
+			.map_err(|e| ClientError::Backend(format!("Error decoding header: {e}")))?;
+
+		let mut entries = vec![
+			(columns::KEY_LOOKUP, number_key.to_vec(), lookup_key.clone()),
+			(columns::KEY_LOOKUP, decoded.hash().as_ref().to_vec(), lookup_key.clone()),
+			(columns::HEADER, lookup_key.clone(), header),
+		];
+		if let Some(body) = source.get(columns::BODY, &lookup_key) {
+			entries.push((columns::BODY, lookup_key.clone(), body));
+		}
+		if let Some(body) = source.get(columns::BODY_INDEX, &lookup_key) {
+			let index = Vec::<DbExtrinsic<Block>>::decode(&mut &body[..])
+				.map_err(|e| ClientError::Backend(format!("Error decoding body: {e}")))?;
+			for extrinsic in index {
+				if let DbExtrinsic::Indexed { hash, .. } = extrinsic {
+					// Stored once per block including it, as when the block was imported.
+					if let Some(data) = source.get(columns::TRANSACTION, hash.as_ref()) {
+						pending_bytes += data.len();
+						transaction.store(columns::TRANSACTION, hash, data);
+					}
+				}
+			}
+			entries.push((columns::BODY_INDEX, lookup_key.clone(), body));
+		}
+		if let Some(justifications) = source.get(columns::JUSTIFICATIONS, &lookup_key) {
+			entries.push((columns::JUSTIFICATIONS, lookup_key.clone(), justifications));
+		}
+		if n > 0 {
+			let mut key = meta_keys::CHILDREN_PREFIX.to_vec();
+			decoded.parent_hash().using_encoded(|s| key.extend(s));
+			entries.push((columns::META, key, vec![decoded.hash()].encode()));
+		}
+
+		for (column, key, value) in entries {
+			pending_bytes += key.len() + value.len();
+			progress.entries += 1;
+			transaction.set_from_vec(column, &key, value);
+		}
+		if pending_bytes >= MIGRATION_BATCH_BYTES {
+			progress.next_block = n + 1;
+			transaction.set_from_vec(columns::META, MIGRATION_PROGRESS, progress.encode());
+			target.commit(std::mem::take(&mut transaction))?;
+			pending_bytes = 0;
+		}
+	}
+
+	progress.next_block = finalized.saturating_add(1);
+	transaction.set_from_vec(columns::META, MIGRATION_PROGRESS, progress.encode());
+	target.commit(transaction)?;
+	Ok(())
+}
+
+/// Check the canonical blocks up to the `finalized` block against the source database.
+fn verify_blocks<Block: BlockT>(
+	source: &dyn Database<DbHash>,
+	target: &dyn Database<DbHash>,
+	finalized: u32,
+) -> ClientResult<()> {
+	let mut report = ProgressReport::blocks(0, finalized, "Checking");
+	let mut mismatches = 0u64;
+	for n in 0..=finalized {
+		report.note_entry();
+		let number_key = utils::number_index_key(n)?;
+		let Some(lookup_key) = source.get(columns::KEY_LOOKUP, &number_key) else { continue };
+		if target.get(columns::KEY_LOOKUP, &number_key).as_ref() != Some(&lookup_key) {
+			note_mismatch(&mut mismatches, columns::KEY_LOOKUP, &number_key);
+		}
+		for column in [columns::HEADER, columns::BODY, columns::BODY_INDEX, columns::JUSTIFICATIONS]
+		{
+			if source.get(column, &lookup_key) != target.get(column, &lookup_key) {
+				note_mismatch(&mut mismatches, column, &lookup_key);
+			}
+		}
+	}
+	if mismatches > 0 {
+		return Err(ClientError::Backend(format!(
+			"Consistency check failed, {mismatches} mismatching entries"
+		)))
+	}
+	Ok(())
+}
+
+/// The state nodes not committed to the target database yet.
+#[derive(Default)]
+struct PendingNodes {
+	transaction: Transaction<DbHash>,
+	bytes: usize,
+	/// The number of nodes copied so far.
+	entries: u64,
+}
+
+/// Reads a state node of the source database by its prefixed key.
+type StateReader<'a> = &'a (dyn Fn(Vec<u8>) -> Result<Option<DBValue>, String> + Sync);
+
+/// Trie node storage which copies the nodes read from the source database to the target
+/// database.
+///
+/// Nodes are written under their prefixed key, which is turned into the key used by the target
+/// database.
+struct CopiedState<'a> {
+	source: StateReader<'a>,
+	target: &'a dyn Database<DbHash>,
+	pending: Mutex<PendingNodes>,
+}
+
+impl<H: Hasher> TrieBackendStorage<H> for CopiedState<'_> {
+	fn get(&self, key: &H::Out, prefix: Prefix) -> Result<Option<DBValue>, String> {
+		let mut target_key = prefixed_key::<H>(key, prefix);
+		let Some(value) = (self.source)(target_key.clone())? else { return Ok(None) };
+
+		self.target.sanitize_key(&mut target_key);
+		let mut pending = self.pending.lock();
+		pending.bytes += target_key.len() + value.len();
+		pending.entries += 1;
+		pending.transaction.set_from_vec(columns::STATE, &target_key, value.clone());
+		if pending.bytes >= MIGRATION_BATCH_BYTES {
+			pending.bytes = 0;
+			self.target
+				.commit(std::mem::take(&mut pending.transaction))
+				.map_err(|e| format!("Failed to write the target database: {e}"))?;
+		}
+		Ok(Some(value))
+	}
+}
+
+/// Trie node storage reading the nodes of a database under their prefixed key.
+struct PrefixedState<'a>(&'a dyn Database<DbHash>);
+
+impl<H: Hasher> TrieBackendStorage<H> for PrefixedState<'_> {
+	fn get(&self, key: &H::Out, prefix: Prefix) -> Result<Option<DBValue>, String> {
+		let mut key = prefixed_key::<H>(key, prefix);
+		self.0.sanitize_key(&mut key);
+		Ok(self.0.get(columns::STATE, &key))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{
+		tests::{insert_header, Block},
+		Backend, BlocksPruning, DatabaseSettings, PruningMode,
+	};
+	use sc_client_api::{backend::Backend as _, TrieCacheContext};
+	use sp_blockchain::HeaderBackend;
+	use sp_state_machine::{backend::Backend as _, Backend as _};
+
+	fn settings(source: DatabaseSource) -> DatabaseSettings {
+		DatabaseSettings {
+			trie_cache_maximum_size: None,
+			state_pruning: Some(PruningMode::blocks_pruning(2)),
+			source,
+			blocks_pruning: BlocksPruning::KeepAll,
+			metrics_registry: None,
+		}
+	}
+
+	#[test]
+	fn rocksdb_can_be_migrated_to_paritydb() {
+		let dir = tempfile::tempdir().unwrap();
+		let source = DatabaseSource::RocksDb { path: dir.path().join("db"), cache_size: 16 };
+		let target = DatabaseSource::ParityDb { path: dir.path().join("paritydb") };
+
+		let mut hashes = Vec::new();
+		{
+			let backend = Backend::<Block>::new(settings(source.clone()), 0).unwrap();
+			let mut parent = Default::default();
+			for number in 0..4 {
+				parent = insert_header(&backend, number, parent, None, Default::default());
+				hashes.push(parent);
+			}
+			backend.finalize_block(hashes[1], None).unwrap();
+		}
+
+		assert!(migrate_database::<Block>(&source, &target, false).unwrap() > 0);
+		// The target database is complete, it can't be migrated to again.
+		assert!(migrate_database::<Block>(&source, &target, false).is_err());
+
+		// The unfinalized blocks are not migrated.
+		let backend = Backend::<Block>::new(settings(target), 0).unwrap();
+		let info = backend.blockchain().info();
+		assert_eq!((info.best_hash, info.finalized_hash), (hashes[1], hashes[1]));
+		assert_eq!(info.finalized_state, Some((hashes[1], 1)));
+		assert_eq!(backend.blockchain().hash(2).unwrap(), None);
+		assert_eq!(backend.blockchain().header(hashes[3]).unwrap(), None);
+		assert_eq!(backend.blockchain().leaves().unwrap(), vec![hashes[1]]);
+		let state = backend.state_at(hashes[1], TrieCacheContext::Untrusted).unwrap();
+		assert_eq!(state.storage(hashes[0].as_ref()).unwrap(), Some(hashes[0].as_ref().to_vec()));
+
+		// The state-db of ParityDB goes on from the finalized block, and prunes it.
+		let mut parent = hashes[1];
+		for number in 2..6 {
+			parent = insert_header(&backend, number, parent, None, Default::default());
+			backend.finalize_block(parent, None).unwrap();
+		}
+		assert_eq!(backend.blockchain().info().finalized_hash, parent);
+		assert!(backend.state_at(hashes[1], TrieCacheContext::Untrusted).is_err());
+	}
+
+	#[test]
+	fn paritydb_can_be_migrated_back_to_rocksdb() {
+		let dir = tempfile::tempdir().unwrap();
+		let source = DatabaseSource::RocksDb { path: dir.path().join("db"), cache_size: 16 };
+		let paritydb = DatabaseSource::ParityDb { path: dir.path().join("paritydb") };
+		let target = DatabaseSource::RocksDb { path: dir.path().join("db2"), cache_size: 16 };
+
+		let mut hashes = Vec::new();
+		{
+			let backend = Backend::<Block>::new(settings(source.clone()), 0).unwrap();
+			let mut parent = Default::default();
+			for number in 0..4 {
+				parent = insert_header(&backend, number, parent, None, Default::default());
+				hashes.push(parent);
+			}
+			backend.finalize_block(hashes[2], None).unwrap();
+		}
+
+		migrate_database::<Block>(&source, &paritydb, false).unwrap();
+		// The lossy migration has to be allowed.
+		assert!(migrate_database::<Block>(&paritydb, &target, false).is_err());
+		assert!(!target.path().unwrap().exists());
+		assert!(migrate_database::<Block>(&paritydb, &target, true).unwrap() > 0);
+		assert!(migrate_database::<Block>(&paritydb, &target, true).is_err());
+
+		// The unfinalized block is not migrated back.
+		let backend = Backend::<Block>::new(settings(target), 0).unwrap();
+		let info = backend.blockchain().info();
+		assert_eq!(info.genesis_hash, hashes[0]);
+		assert_eq!((info.best_hash, info.finalized_hash), (hashes[2], hashes[2]));
+		assert_eq!(info.finalized_state, Some((hashes[2], 2)));
+		assert_eq!(backend.blockchain().hash(1).unwrap(), Some(hashes[1]));
+		assert_eq!(backend.blockchain().header(hashes[3]).unwrap(), None);
+		assert_eq!(backend.blockchain().leaves().unwrap(), vec![hashes[2]]);
+		let state = backend.state_at(hashes[2], TrieCacheContext::Untrusted).unwrap();
+		assert_eq!(state.storage(hashes[1].as_ref()).unwrap(), Some(hashes[1].as_ref().to_vec()));
+
+		// The chain goes on from the finalized block.
+		let hash = insert_header(&backend, 3, hashes[2], None, Default::default());
+		backend.finalize_block(hash, None).unwrap();
+		assert_eq!(backend.blockchain().info().finalized_hash, hash);
+	}
+
+	#[test]
+	fn only_rocksdb_and_paritydb_are_migrated_between() {
+		let dir = tempfile::tempdir().unwrap();
+		let source = DatabaseSource::ParityDb { path: dir.path().join("paritydb") };
+		let target = DatabaseSource::ParityDb { path: dir.path().join("paritydb2") };
+		assert!(migrate_database::<Block>(&source, &target, true).is_err());
+		// The source database does not exist.
+		let target = DatabaseSource::RocksDb { path: dir.path().join("db"), cache_size: 16 };
+		assert!(migrate_database::<Block>(&source, &target, true).is_err());
+	}
+}
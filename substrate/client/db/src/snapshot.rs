@@ -0,0 +1,584 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Database snapshots.
+//!
+//! A snapshot is a point-in-time copy of the canonical chain up to the last finalized block,
+//! together with the state of that block. It is written to a directory holding two files:
+//! - [`DATA_FILE`], the SCALE encoded database entries of the snapshot,
+//! - [`MANIFEST_FILE`], the JSON encoded [`SnapshotManifest`], written once the data is complete.
+//!
+//! Snapshots can be taken while the node is running, the snapshot block is pinned until the
+//! snapshot is written. The state is copied by walking the trie of the snapshot block, so the
+//! snapshot does not depend on the database the node is running on, nor on its pruning window.
+//!
+//! Trie nodes read more than once while walking the state are written again, and only stored once
+//! when restoring. Indexed transactions are written once per block including them, matching their
+//! reference counts in the database.
+//!
+//! A snapshot is restored into an empty database. The restore is refused when the manifest does
+//! not match the genesis hash or the state pruning mode of the target database. On-disk databases
+//! are restored into a staging directory next to the target one, which is moved into place once
+//! the restore is complete, so an interrupted restore never leaves a partial database behind.
+
+use crate::{
+	apply_state_commit, columns,
+	utils::{self, meta_keys, DatabaseType, OpenDbError},
+	Backend, DatabaseSettings, DbExtrinsic, DbHash, StorageDb,
+};
+use codec::{Decode, Encode, IoReader};
+use hash_db::Prefix;
+use log::info;
+use parking_lot::Mutex;
+use sc_client_api::leaves::LeafSet;
+use serde::{Deserialize, Serialize};
+use sp_blockchain::{Error as ClientError, HeaderBackend, Result as ClientResult};
+use sp_database::Transaction;
+use sp_runtime::traits::{
+	Block as BlockT, HashingFor, Header as HeaderT, NumberFor, SaturatedConversion,
+};
+use sp_state_machine::{DBValue, TrieBackendStorage};
+use sp_trie::{prefixed_key, EMPTY_PREFIX};
+use std::{
+	collections::HashSet,
+	fs::{self, File},
+	io::{self, BufReader, BufWriter, Write},
+	path::Path,
+};
+
+/// The version of the snapshot format.
+pub const SNAPSHOT_VERSION: u32 = 1;
+
+/// The name of the file holding the manifest of a snapshot.
+pub const MANIFEST_FILE: &str = "manifest.json";
+
+/// The name of the file holding the database entries of a snapshot.
+pub const DATA_FILE: &str = "data";
+
+/// The amount of data written to the database in a single transaction when restoring.
+const RESTORE_BATCH_BYTES: usize = 64 * 1024 * 1024;
+
+/// The suffix of the staging directory a snapshot is restored into.
+const RESTORE_STAGING_SUFFIX: &str = "restoring";
+
+/// The columns a snapshot may hold entries of.
+const SNAPSHOT_COLUMNS: [u32; 8] = [
+	columns::META,
+	columns::STATE,
+	columns::KEY_LOOKUP,
+	columns::HEADER,
+	columns::BODY,
+	columns::JUSTIFICATIONS,
+	columns::TRANSACTION,
+	columns::BODY_INDEX,
+];
+
+/// Description of a snapshot.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SnapshotManifest<Hash, Number> {
+	/// The version of the snapshot format.
+	pub version: u32,
+	/// The genesis hash of the chain.
+	pub genesis_hash: Hash,
+	/// The best block of the node when the snapshot was taken.
+	pub best_hash: Hash,
+	/// The number of the best block.
+	pub best_number: Number,
+	/// The finalized block the snapshot was taken at.
+	pub finalized_hash: Hash,
+	/// The number of the finalized block.
+	pub finalized_number: Number,
+	/// The state pruning mode of the database the snapshot was taken from.
+	pub state_pruning: String,
+	/// The number of entries in the data file.
+	pub entries: u64,
+}
+
+/// A database entry of a snapshot.
+#[derive(Encode, Decode)]
+struct SnapshotEntry {
+	column: u32,
+	key: Vec<u8>,
+	value: Vec<u8>,
+}
+
+fn io_error(context: &str, path: &Path, error: io::Error) -> ClientError {
+	ClientError::Backend(format!("{context} {}: {error}", path.display()))
+}
+
+/// Writes the entries of a snapshot to its data file.
+struct SnapshotWriter {
+	file: BufWriter<File>,
+	entries: u64,
+}
+
+impl SnapshotWriter {
+	fn new(file: File) -> Self {
+		Self { file: BufWriter::new(file), entries: 0 }
+	}
+
+	fn write(&mut self, column: u32, key: Vec<u8>, value: Vec<u8>) -> io::Result<()> {
+		self.file.write_all(&SnapshotEntry { column, key, value }.encode())?;
+		self.entries += 1;
+		Ok(())
+	}
+
+	fn finish(mut self) -> io::Result<u64> {
+		self.file.flush()?;
+		self.file.get_ref().sync_all()?;
+		Ok(self.entries)
+	}
+}
+
+/// Trie node storage which writes the nodes read from the database to the snapshot.
+///
+/// Nodes are always written under their prefixed key, which is turned into the key used by the
+/// database when restoring.
+struct SnapshotStorage<'a, Block: BlockT> {
+	storage: &'a StorageDb<Block>,
+	writer: Mutex<SnapshotWriter>,
+}
+
+impl<Block: BlockT> TrieBackendStorage<HashingFor<Block>> for SnapshotStorage<'_, Block> {
+	fn get(&self, key: &Block::Hash, prefix: Prefix) -> Result<Option<DBValue>, String> {
+		let value = sp_state_machine::Storage::get(self.storage, key, prefix)?;
+		if let Some(value) = &value {
+			self.writer
+				.lock()
+				.write(
+					columns::STATE,
+					prefixed_key::<HashingFor<Block>>(key, prefix),
+					value.clone(),
+				)
+				.map_err(|e| format!("Failed to write snapshot: {e}"))?;
+		}
+		Ok(value)
+	}
+}
+
+impl<Block: BlockT> Backend<Block> {
+	/// Write a snapshot of the last finalized block to the `path` directory.
+	///
+	/// The snapshot holds the canonical headers, bodies and justifications up to the finalized
+	/// block, and the state of the finalized block.
+	pub fn snapshot(
+		&self,
+		path: &Path,
+	) -> ClientResult<SnapshotManifest<Block::Hash, NumberFor<Block>>> {
+		let info = self.blockchain.info();
+		let (hash, number) = (info.finalized_hash, info.finalized_number);
+		if info.finalized_state != Some((hash, number)) {
+			return Err(ClientError::UnknownBlock(format!(
+				"Cannot snapshot #{number} ({hash:?}): state not available"
+			)))
+		}
+
+		fs::create_dir_all(path).map_err(|e| io_error("Failed to create", path, e))?;
+		let manifest_path = path.join(MANIFEST_FILE);
+		if manifest_path.exists() {
+			return Err(ClientError::Backend(format!("{} already holds a snapshot", path.display())))
+		}
+		let data_path = path.join(DATA_FILE);
+		let file =
+			File::create(&data_path).map_err(|e| io_error("Failed to create", &data_path, e))?;
+
+		sc_client_api::Backend::pin_block(self, hash)?;
+		let result = self.write_snapshot(SnapshotWriter::new(file), hash, number);
+		sc_client_api::Backend::unpin_block(self, hash);
+		let entries = result?;
+
+		let manifest = SnapshotManifest {
+			version: SNAPSHOT_VERSION,
+			genesis_hash: info.genesis_hash,
+			best_hash: info.best_hash,
+			best_number: info.best_number,
+			finalized_hash: hash,
+			finalized_number: number,
+			state_pruning: String::from_utf8_lossy(self.storage.state_db.pruning_mode().id())
+				.into_owned(),
+			entries,
+		};
+		let encoded = serde_json::to_vec_pretty(&manifest)
+			.map_err(|e| ClientError::Backend(format!("Failed to encode manifest: {e}")))?;
+		fs::write(&manifest_path, encoded)
+			.map_err(|e| io_error("Failed to write", &manifest_path, e))?;
+
+		info!(
+			"📦 Snapshot of #{number} ({hash:?}) written to {}, {entries} entries",
+			path.display()
+		);
+		Ok(manifest)
+	}
+
+	fn write_snapshot(
+		&self,
+		mut writer: SnapshotWriter,
+		hash: Block::Hash,
+		number: NumberFor<Block>,
+	) -> ClientResult<u64> {
+		let db = &*self.storage.db;
+		let map_e = |e| ClientError::Backend(format!("Failed to write snapshot: {e}"));
+
+		for n in 0..=number.saturated_into::<u32>() {
+			let number_key = utils::number_index_key(n)?;
+			// Blocks may be missing after a warp sync.
+			let Some(lookup_key) = db.get(columns::KEY_LOOKUP, &number_key) else { continue };
+			let Some(header) = db.get(columns::HEADER, &lookup_key) else { continue };
+			let decoded = Block::Header::decode(&mut &header[..])
+				.map_err(|e| ClientError::Backend(format!("Error decoding header: {e}")))?;
+
+			writer
+				.write(columns::KEY_LOOKUP, number_key.to_vec(), lookup_key.clone())
+				.map_err(map_e)?;
+			writer
+				.write(columns::KEY_LOOKUP, decoded.hash().as_ref().to_vec(), lookup_key.clone())
+				.map_err(map_e)?;
+			writer.write(columns::HEADER, lookup_key.clone(), header).map_err(map_e)?;
+			if let Some(body) = db.get(columns::BODY, &lookup_key) {
+				writer.write(columns::BODY, lookup_key.clone(), body).map_err(map_e)?;
+			}
+			if let Some(body) = db.get(columns::BODY_INDEX, &lookup_key) {
+				let index = Vec::<DbExtrinsic<Block>>::decode(&mut &body[..])
+					.map_err(|e| ClientError::Backend(format!("Error decoding body: {e}")))?;
+				for extrinsic in index {
+					if let DbExtrinsic::Indexed { hash, .. } = extrinsic {
+						if let Some(data) = db.get(columns::TRANSACTION, hash.as_ref()) {
+							writer
+								.write(columns::TRANSACTION, hash.as_ref().to_vec(), data)
+								.map_err(map_e)?;
+						}
+					}
+				}
+				writer.write(columns::BODY_INDEX, lookup_key.clone(), body).map_err(map_e)?;
+			}
+			if let Some(justifications) = db.get(columns::JUSTIFICATIONS, &lookup_key) {
+				writer
+					.write(columns::JUSTIFICATIONS, lookup_key, justifications)
+					.map_err(map_e)?;
+			}
+			if n > 0 {
+				let mut key = meta_keys::CHILDREN_PREFIX.to_vec();
+				decoded.parent_hash().using_encoded(|s| key.extend(s));
+				writer.write(columns::META, key, vec![decoded.hash()].encode()).map_err(map_e)?;
+			}
+		}
+
+		for key in [&meta_keys::BLOCK_GAP[..], &meta_keys::BLOCK_GAP_VERSION[..]] {
+			if let Some(value) = db.get(columns::META, key) {
+				writer.write(columns::META, key.to_vec(), value).map_err(map_e)?;
+			}
+		}
+
+		let header = self.blockchain.expect_header(hash)?;
+		let storage = SnapshotStorage { storage: &*self.storage, writer: Mutex::new(writer) };
+		let storage = utils::walk_state::<Block, _>(storage, *header.state_root())
+			.map_err(ClientError::Backend)?;
+
+		storage.writer.into_inner().finish().map_err(map_e)
+	}
+}
+
+/// Read the manifest of the snapshot in the `path` directory.
+pub fn read_manifest<Block: BlockT>(
+	path: &Path,
+) -> ClientResult<SnapshotManifest<Block::Hash, NumberFor<Block>>> {
+	let manifest_path = path.join(MANIFEST_FILE);
+	let manifest =
+		fs::read(&manifest_path).map_err(|e| io_error("Failed to read", &manifest_path, e))?;
+	serde_json::from_slice(&manifest)
+		.map_err(|e| ClientError::Backend(format!("Invalid snapshot manifest: {e}")))
+}
+
+/// Restore the snapshot in the `path` directory into the empty database described by `settings`.
+///
+/// Fails if the snapshot was taken from a chain other than `expected_genesis`, if its state
+/// pruning mode does not match the requested one, or if the database already exists.
+///
+/// A database stored on disk is only created once the restore succeeded. A custom database is
+/// written in place, and holds no chain meta-data until the restore is complete.
+pub fn restore_snapshot<Block: BlockT>(
+	path: &Path,
+	settings: &DatabaseSettings,
+	expected_genesis: Option<Block::Hash>,
+) -> ClientResult<SnapshotManifest<Block::Hash, NumberFor<Block>>> {
+	let manifest = read_manifest::<Block>(path)?;
+	if manifest.version != SNAPSHOT_VERSION {
+		return Err(ClientError::Backend(format!(
+			"Unsupported snapshot version: {}",
+			manifest.version
+		)))
+	}
+	if let Some(genesis) = expected_genesis.filter(|genesis| *genesis != manifest.genesis_hash) {
+		return Err(ClientError::Backend(format!(
+			"Snapshot genesis {:?} does not match the chain genesis {genesis:?}",
+			manifest.genesis_hash
+		)))
+	}
+	let state_pruning = settings.state_pruning.clone().unwrap_or_default();
+	if state_pruning.id() != manifest.state_pruning.as_bytes() {
+		return Err(ClientError::Backend(format!(
+			"Snapshot state pruning `{}` does not match the requested state pruning `{}`",
+			manifest.state_pruning,
+			String::from_utf8_lossy(state_pruning.id()),
+		)))
+	}
+
+	match utils::open_database::<Block>(&settings.source, DatabaseType::Full, false) {
+		Ok(db) =>
+			return Err(match utils::read_genesis_hash::<Block::Hash>(&*db)? {
+				Some(genesis) if genesis != manifest.genesis_hash => ClientError::Backend(format!(
+					"Snapshot genesis {:?} does not match the database genesis {genesis:?}",
+					manifest.genesis_hash
+				)),
+				_ => ClientError::Backend(
+					"Cannot restore a snapshot into an existing database".into(),
+				),
+			}),
+		Err(OpenDbError::DoesNotExist) => (),
+		Err(e) => return Err(e.into()),
+	};
+
+	let target = settings.source.path().map(Path::to_path_buf);
+	let mut source = settings.source.clone();
+	let staging = match &target {
+		Some(target) => {
+			let mut name = target.file_name().unwrap_or_default().to_os_string();
+			name.push(".");
+			name.push(RESTORE_STAGING_SUFFIX);
+			let staging = target.with_file_name(name);
+			// Left over by an interrupted restore.
+			if staging.exists() {
+				fs::remove_dir_all(&staging)
+					.map_err(|e| io_error("Failed to remove", &staging, e))?;
+			}
+			source.set_path(&staging);
+			Some(staging)
+		},
+		None => None,
+	};
+	let db = utils::open_database::<Block>(&source, DatabaseType::Full, true)?;
+
+	let data_path = path.join(DATA_FILE);
+	let file = File::open(&data_path).map_err(|e| io_error("Failed to open", &data_path, e))?;
+	let mut reader = BufReader::new(file);
+	let mut transaction = Transaction::new();
+	let mut pending_bytes = 0;
+	// The trie nodes of the pending transaction, which the database does not hold yet.
+	let mut pending_nodes = HashSet::new();
+	for _ in 0..manifest.entries {
+		let SnapshotEntry { column, mut key, value } =
+			SnapshotEntry::decode(&mut IoReader(&mut reader))
+				.map_err(|e| ClientError::Backend(format!("Invalid snapshot data: {e}")))?;
+		if !SNAPSHOT_COLUMNS.contains(&column) {
+			return Err(ClientError::Backend(format!("Invalid snapshot column: {column}")))
+		}
+		pending_bytes += key.len() + value.len();
+		match column {
+			columns::STATE => {
+				db.sanitize_key(&mut key);
+				// Trie nodes are reference counted by some databases, so each is only stored once.
+				if !pending_nodes.contains(&key) && db.get(column, &key).is_none() {
+					pending_nodes.insert(key.clone());
+					transaction.set_from_vec(column, &key, value);
+				}
+			},
+			columns::TRANSACTION => {
+				if key.len() != crate::DB_HASH_LEN {
+					return Err(ClientError::Backend("Invalid snapshot transaction key".into()))
+				}
+				transaction.store(column, DbHash::from_slice(&key), value);
+			},
+			_ => transaction.set_from_vec(column, &key, value),
+		}
+		if pending_bytes >= RESTORE_BATCH_BYTES {
+			db.commit(std::mem::take(&mut transaction))?;
+			pending_bytes = 0;
+			pending_nodes.clear();
+		}
+	}
+
+	db.commit(transaction)?;
+
+	let (hash, number) = (manifest.finalized_hash, manifest.finalized_number);
+	let lookup_key = utils::number_and_hash_to_lookup_key(number, hash)?;
+	let header = match db.get(columns::HEADER, &lookup_key) {
+		Some(header) => Block::Header::decode(&mut &header[..])
+			.map_err(|e| ClientError::Backend(format!("Error decoding header: {e}")))?,
+		None => return Err(ClientError::Backend("Snapshot is missing the finalized header".into())),
+	};
+	let mut root_key = prefixed_key::<HashingFor<Block>>(header.state_root(), EMPTY_PREFIX);
+	db.sanitize_key(&mut root_key);
+	if header.hash() != hash || db.get(columns::STATE, &root_key).is_none() {
+		return Err(ClientError::Backend("Snapshot is missing the finalized state".into()))
+	}
+
+	let mut transaction = Transaction::new();
+	transaction.set(columns::META, meta_keys::GENESIS_HASH, manifest.genesis_hash.as_ref());
+	transaction.set(columns::META, meta_keys::BEST_BLOCK, &lookup_key);
+	transaction.set(columns::META, meta_keys::FINALIZED_BLOCK, &lookup_key);
+	transaction.set(columns::META, meta_keys::FINALIZED_STATE, &lookup_key);
+	let mut leaves = LeafSet::new();
+	leaves.import(hash, number, *header.parent_hash());
+	leaves.prepare_transaction(&mut transaction, columns::META, meta_keys::LEAF_PREFIX);
+	apply_state_commit(
+		&mut transaction,
+		sc_state_db::restored_meta(&state_pruning, &hash, number.saturated_into::<u64>()),
+	);
+	db.commit(transaction)?;
+	drop(db);
+
+	if let (Some(staging), Some(target)) = (staging, target) {
+		fs::rename(&staging, &target).map_err(|e| io_error("Failed to move", &staging, e))?;
+	}
+
+	info!("📦 Snapshot of #{number} ({hash:?}) restored from {}", path.display());
+	Ok(manifest)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{
+		tests::{insert_header, Block},
+		BlocksPruning, Database, DatabaseSource, PruningMode,
+	};
+	use sc_client_api::{backend::Backend as _, TrieCacheContext};
+	use sp_core::H256;
+	use sp_state_machine::backend::Backend as _;
+	use std::sync::Arc;
+
+	fn memory_db() -> Arc<dyn Database<DbHash>> {
+		sp_database::as_database(kvdb_memorydb::create(crate::utils::NUM_COLUMNS))
+	}
+
+	fn settings(
+		db: Arc<dyn Database<DbHash>>,
+		state_pruning: PruningMode,
+		require_create_flag: bool,
+	) -> DatabaseSettings {
+		DatabaseSettings {
+			trie_cache_maximum_size: None,
+			state_pruning: Some(state_pruning),
+			source: DatabaseSource::Custom { db, require_create_flag },
+			blocks_pruning: BlocksPruning::KeepAll,
+			metrics_registry: None,
+		}
+	}
+
+	fn chain(backend: &Backend<Block>, len: u64) -> Vec<H256> {
+		let mut hashes = Vec::new();
+		let mut parent = Default::default();
+		for number in 0..len {
+			parent = insert_header(backend, number, parent, None, Default::default());
+			hashes.push(parent);
+		}
+		hashes
+	}
+
+	#[test]
+	fn snapshot_can_be_restored() {
+		let backend = Backend::<Block>::new_test(2, 0);
+		let hashes = chain(&backend, 4);
+		backend.finalize_block(hashes[2], None).unwrap();
+
+		let dir = tempfile::tempdir().unwrap();
+		let manifest = backend.snapshot(dir.path()).unwrap();
+		assert_eq!((manifest.finalized_hash, manifest.finalized_number), (hashes[2], 2));
+		assert_eq!((manifest.best_hash, manifest.best_number), (hashes[3], 3));
+		assert_eq!(read_manifest::<Block>(dir.path()).unwrap(), manifest);
+		assert!(backend.snapshot(dir.path()).is_err());
+
+		let db = memory_db();
+		let mode = PruningMode::blocks_pruning(2);
+		restore_snapshot::<Block>(
+			dir.path(),
+			&settings(db.clone(), mode.clone(), true),
+			Some(hashes[0]),
+		)
+		.unwrap();
+
+		let restored = Backend::<Block>::new(settings(db, mode, false), 0).unwrap();
+		let info = restored.blockchain().info();
+		assert_eq!(info.genesis_hash, hashes[0]);
+		assert_eq!((info.best_hash, info.finalized_hash), (hashes[2], hashes[2]));
+		assert_eq!(info.finalized_state, Some((hashes[2], 2)));
+		assert_eq!(restored.blockchain().hash(1).unwrap(), Some(hashes[1]));
+		assert_eq!(restored.blockchain().leaves().unwrap(), vec![hashes[2]]);
+		let state = restored.state_at(hashes[2], TrieCacheContext::Untrusted).unwrap();
+		assert_eq!(state.storage(hashes[1].as_ref()).unwrap(), Some(hashes[1].as_ref().to_vec()));
+
+		// The chain goes on from the restored block.
+		let hash = insert_header(&restored, 3, hashes[2], None, Default::default());
+		restored.finalize_block(hash, None).unwrap();
+		assert_eq!(restored.blockchain().info().finalized_hash, hash);
+	}
+
+	#[test]
+	fn mismatched_restore_is_refused() {
+		let backend = Backend::<Block>::new_test(2, 0);
+		let hashes = chain(&backend, 2);
+		backend.finalize_block(hashes[1], None).unwrap();
+		let dir = tempfile::tempdir().unwrap();
+		backend.snapshot(dir.path()).unwrap();
+
+		let mode = PruningMode::blocks_pruning(2);
+		assert!(restore_snapshot::<Block>(
+			dir.path(),
+			&settings(memory_db(), PruningMode::ArchiveAll, true),
+			None
+		)
+		.is_err());
+		assert!(restore_snapshot::<Block>(
+			dir.path(),
+			&settings(memory_db(), mode.clone(), true),
+			Some(H256::repeat_byte(1))
+		)
+		.is_err());
+
+		let db = memory_db();
+		restore_snapshot::<Block>(dir.path(), &settings(db.clone(), mode.clone(), true), None)
+			.unwrap();
+		assert!(restore_snapshot::<Block>(dir.path(), &settings(db, mode, false), None).is_err());
+	}
+
+	#[test]
+	fn restore_is_moved_into_place() {
+		let backend = Backend::<Block>::new_test(2, 0);
+		let hashes = chain(&backend, 2);
+		backend.finalize_block(hashes[1], None).unwrap();
+		let dir = tempfile::tempdir().unwrap();
+		backend.snapshot(dir.path()).unwrap();
+
+		let db_dir = tempfile::tempdir().unwrap();
+		let path = db_dir.path().join("db");
+		let staging = db_dir.path().join("db.restoring");
+		// Left over by an interrupted restore.
+		fs::create_dir_all(staging.join("stale")).unwrap();
+		let settings = DatabaseSettings {
+			source: DatabaseSource::ParityDb { path: path.clone() },
+			..settings(memory_db(), PruningMode::blocks_pruning(2), false)
+		};
+		restore_snapshot::<Block>(dir.path(), &settings, None).unwrap();
+		assert!(path.exists());
+		assert!(!staging.exists());
+
+		let restored = Backend::<Block>::new(settings, 0).unwrap();
+		assert_eq!(restored.blockchain().info().finalized_hash, hashes[1]);
+	}
+}
@@ -26,14 +26,16 @@ use log::{debug, info};
 use crate::{Database, DatabaseSource, DbHash};
 use codec::Decode;
 use sc_client_api::blockchain::{BlockGap, BlockGapType};
+use sp_core::storage::{well_known_keys, ChildInfo};
 use sp_database::Transaction;
 use sp_runtime::{
 	generic::BlockId,
 	traits::{
-		Block as BlockT, Header as HeaderT, NumberFor, UniqueSaturatedFrom, UniqueSaturatedInto,
-		Zero,
+		Block as BlockT, HashingFor, Header as HeaderT, NumberFor, UniqueSaturatedFrom,
+		UniqueSaturatedInto, Zero,
 	},
 };
+use sp_state_machine::{backend::Backend as _, IterArgs, TrieBackendBuilder, TrieBackendStorage};
 use sp_trie::DBValue;
 
 /// Number of columns in the db. Must be the same for both full && light dbs.
@@ -309,6 +311,18 @@ fn open_kvdb_rocksdb<Block: BlockT>(
 	create: bool,
 	cache_size: usize,
 ) -> OpenDbResult {
+	let db = open_raw_kvdb_rocksdb::<Block>(path, db_type, create, cache_size)?;
+	Ok(sp_database::as_database(db))
+}
+
+/// Opens the RocksDB database at `path`, without wrapping it into a [`Database`].
+#[cfg(any(feature = "rocksdb", test))]
+pub(crate) fn open_raw_kvdb_rocksdb<Block: BlockT>(
+	path: &Path,
+	db_type: DatabaseType,
+	create: bool,
+	cache_size: usize,
+) -> Result<kvdb_rocksdb::Database, OpenDbError> {
 	// first upgrade database to required version
 	match crate::upgrade::upgrade_db::<Block>(path, db_type) {
 		// in case of missing version file, assume that database simply does not exist at given
@@ -349,7 +363,7 @@ fn open_kvdb_rocksdb<Block: BlockT>(
 	let db = kvdb_rocksdb::Database::open(&db_config, path)?;
 	// write database version only after the database is successfully opened
 	crate::upgrade::update_version(path)?;
-	Ok(sp_database::as_database(db))
+	Ok(db)
 }
 
 #[cfg(not(any(feature = "rocksdb", test)))]
@@ -576,6 +590,34 @@ pub fn read_genesis_hash<Hash: Decode>(
 	}
 }
 
+/// Read every node of the state with the given `root`, including the nodes of its child tries.
+///
+/// The nodes are read through `storage`, which is returned once the walk is complete.
+pub(crate) fn walk_state<Block: BlockT, S: TrieBackendStorage<HashingFor<Block>>>(
+	storage: S,
+	root: Block::Hash,
+) -> Result<S, String> {
+	let state = TrieBackendBuilder::new(storage, root).build();
+
+	let mut child_infos = Vec::new();
+	for pair in state.pairs(IterArgs::default())? {
+		let (key, _) = pair?;
+		if let Some(storage_key) =
+			key.strip_prefix(well_known_keys::DEFAULT_CHILD_STORAGE_KEY_PREFIX)
+		{
+			child_infos.push(ChildInfo::new_default(storage_key));
+		}
+	}
+	for child_info in child_infos {
+		let args = IterArgs { child_info: Some(child_info), ..Default::default() };
+		for pair in state.pairs(args)? {
+			pair?;
+		}
+	}
+
+	Ok(state.into_storage())
+}
+
 impl DatabaseType {
 	/// Returns str representation of the type.
 	pub fn as_str(&self) -> &'static str {
@@ -53,3 +53,19 @@ impl Default for SyncMode {
 		Self::Full
 	}
 }
+
+/// How much of the block history missing after warp sync is downloaded.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum BlockHistorySync {
+	/// Download the whole block history forward from genesis.
+	Full,
+	/// Download the block history backwards from the warp sync target, with the bodies of the
+	/// given number of blocks below the last finalized block and only the headers of older blocks.
+	Depth(u32),
+}
+
+impl Default for BlockHistorySync {
+	fn default() -> Self {
+		Self::Full
+	}
+}
@@ -0,0 +1,237 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! [`NetworkBehaviour`] refusing connections with banned peers and IP ranges.
+//!
+//! Bans are checked against the [`PeerStoreProvider`] before dialing, when an inbound connection
+//! is accepted and again once the remote peer is known, so banned peers never get to open any
+//! substream.
+
+use crate::{peer_store::PeerStoreProvider, LOG_TARGET};
+
+use libp2p::{
+	core::{transport::PortUse, Endpoint, Multiaddr},
+	swarm::{
+		dummy, ConnectionDenied, ConnectionId, FromSwarm, NetworkBehaviour, THandler,
+		THandlerInEvent, THandlerOutEvent, ToSwarm,
+	},
+	PeerId,
+};
+
+use std::{
+	sync::Arc,
+	task::{Context, Poll},
+};
+
+/// Error returned when a connection is refused because of a ban.
+#[derive(Debug, thiserror::Error)]
+#[error("{0} is banned")]
+struct Banned(String);
+
+/// Refuses connections with peers and addresses banned in the peer store.
+pub struct BanFilter {
+	peer_store_handle: Arc<dyn PeerStoreProvider>,
+}
+
+impl BanFilter {
+	/// Create a new [`BanFilter`] checking bans against `peer_store_handle`.
+	pub fn new(peer_store_handle: Arc<dyn PeerStoreProvider>) -> Self {
+		Self { peer_store_handle }
+	}
+
+	fn check_peer(&self, peer: &PeerId) -> Result<(), ConnectionDenied> {
+		if self.peer_store_handle.is_banned(&(*peer).into()) {
+			log::debug!(target: LOG_TARGET, "Refusing connection with banned peer {peer}");
+			return Err(ConnectionDenied::new(Banned(peer.to_string())))
+		}
+
+		Ok(())
+	}
+
+	fn check_address(&self, address: &Multiaddr) -> Result<(), ConnectionDenied> {
+		if self.peer_store_handle.is_address_banned(&address.clone().into()) {
+			log::debug!(target: LOG_TARGET, "Refusing connection with banned address {address}");
+			return Err(ConnectionDenied::new(Banned(address.to_string())))
+		}
+
+		Ok(())
+	}
+}
+
+impl NetworkBehaviour for BanFilter {
+	type ConnectionHandler = dummy::ConnectionHandler;
+	type ToSwarm = void::Void;
+
+	fn handle_pending_inbound_connection(
+		&mut self,
+		_connection_id: ConnectionId,
+		_local_addr: &Multiaddr,
+		remote_addr: &Multiaddr,
+	) -> Result<(), ConnectionDenied> {
+		self.check_address(remote_addr)
+	}
+
+	fn handle_pending_outbound_connection(
+		&mut self,
+		_connection_id: ConnectionId,
+		maybe_peer: Option<PeerId>,
+		addresses: &[Multiaddr],
+		_effective_role: Endpoint,
+	) -> Result<Vec<Multiaddr>, ConnectionDenied> {
+		if let Some(peer) = maybe_peer {
+			self.check_peer(&peer)?;
+		}
+
+		// Addresses provided by other behaviours are only known once the connection is
+		// established, so only refuse the dial if all the explicit addresses are banned.
+		if let Some(address) = addresses.first() {
+			if addresses.iter().all(|address| self.check_address(address).is_err()) {
+				return Err(ConnectionDenied::new(Banned(address.to_string())))
+			}
+		}
+
+		Ok(Vec::new())
+	}
+
+	fn handle_established_inbound_connection(
+		&mut self,
+		_connection_id: ConnectionId,
+		peer: PeerId,
+		_local_addr: &Multiaddr,
+		remote_addr: &Multiaddr,
+	) -> Result<THandler<Self>, ConnectionDenied> {
+		self.check_peer(&peer)?;
+		self.check_address(remote_addr)?;
+
+		Ok(dummy::ConnectionHandler)
+	}
+
+	fn handle_established_outbound_connection(
+		&mut self,
+		_connection_id: ConnectionId,
+		peer: PeerId,
+		addr: &Multiaddr,
+		_role_override: Endpoint,
+		_port_use: PortUse,
+	) -> Result<THandler<Self>, ConnectionDenied> {
+		self.check_peer(&peer)?;
+		self.check_address(addr)?;
+
+		Ok(dummy::ConnectionHandler)
+	}
+
+	fn on_swarm_event(&mut self, _event: FromSwarm) {}
+
+	fn on_connection_handler_event(
+		&mut self,
+		_peer_id: PeerId,
+		_connection_id: ConnectionId,
+		event: THandlerOutEvent<Self>,
+	) {
+		void::unreachable(event)
+	}
+
+	fn poll(
+		&mut self,
+		_cx: &mut Context<'_>,
+	) -> Poll<ToSwarm<Self::ToSwarm, THandlerInEvent<Self>>> {
+		Poll::Pending
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::BanFilter;
+	use crate::peer_store::{BanTarget, PeerStore, PeerStoreProvider};
+
+	use libp2p::{
+		core::{transport::PortUse, Endpoint, Multiaddr},
+		swarm::{ConnectionId, NetworkBehaviour},
+		PeerId,
+	};
+	use std::sync::Arc;
+
+	#[test]
+	fn banned_ranges_are_refused_before_connecting() {
+		let handle = Arc::new(PeerStore::new(vec![], None).handle());
+		let mut filter = BanFilter::new(handle.clone());
+		let local: Multiaddr = "/ip4/127.0.0.1/tcp/30333".parse().unwrap();
+		let banned: Multiaddr = "/ip4/10.0.0.7/tcp/30333".parse().unwrap();
+		let allowed: Multiaddr = "/ip4/10.1.0.7/tcp/30333".parse().unwrap();
+		handle.ban("10.0.0.0/16".parse().unwrap(), None);
+
+		assert!(filter
+			.handle_pending_inbound_connection(ConnectionId::new_unchecked(0), &local, &banned)
+			.is_err());
+		assert!(filter
+			.handle_pending_inbound_connection(ConnectionId::new_unchecked(1), &local, &allowed)
+			.is_ok());
+		assert!(filter
+			.handle_pending_outbound_connection(
+				ConnectionId::new_unchecked(2),
+				None,
+				&[banned.clone()],
+				Endpoint::Dialer,
+			)
+			.is_err());
+		assert!(filter
+			.handle_pending_outbound_connection(
+				ConnectionId::new_unchecked(3),
+				None,
+				&[banned, allowed],
+				Endpoint::Dialer,
+			)
+			.is_ok());
+	}
+
+	#[test]
+	fn banned_peers_are_refused() {
+		let handle = Arc::new(PeerStore::new(vec![], None).handle());
+		let mut filter = BanFilter::new(handle.clone());
+		let peer = PeerId::random();
+		let address: Multiaddr = "/ip4/10.1.0.7/tcp/30333".parse().unwrap();
+		handle.ban(BanTarget::Peer(peer.into()), None);
+
+		assert!(filter
+			.handle_pending_outbound_connection(
+				ConnectionId::new_unchecked(0),
+				Some(peer),
+				&[address.clone()],
+				Endpoint::Dialer,
+			)
+			.is_err());
+		assert!(filter
+			.handle_established_outbound_connection(
+				ConnectionId::new_unchecked(1),
+				peer,
+				&address,
+				Endpoint::Dialer,
+				PortUse::New,
+			)
+			.is_err());
+		assert!(filter
+			.handle_established_outbound_connection(
+				ConnectionId::new_unchecked(2),
+				PeerId::random(),
+				&address,
+				Endpoint::Dialer,
+				PortUse::New,
+			)
+			.is_ok());
+	}
+}
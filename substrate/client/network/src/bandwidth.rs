@@ -0,0 +1,502 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+//! Bandwidth accounting and inbound request rate limiting.
+//!
+//! [`BandwidthMeter`] keeps track of the number of bytes exchanged over each notification and
+//! request-response protocol, both in total and per remote peer, and exposes them as Prometheus
+//! metrics.
+//!
+//! [`InboundRequestLimiter`] implements token bucket based rate limiting of inbound
+//! request-response traffic. Each request-response protocol has its own limiter which can enforce
+//! a global limit, shared by all peers, and a limit for each individual peer. Both requests and the
+//! responses sent back consume tokens from the buckets, and a request is refused if any of the
+//! applicable buckets is depleted when it arrives.
+
+use crate::types::ProtocolName;
+
+use parking_lot::Mutex;
+use prometheus_endpoint::{
+	self as prometheus, CounterVec, MetricSource, Opts, PrometheusError, Registry, SourcedCounter,
+	U64,
+};
+use sc_network_types::PeerId;
+
+use std::{
+	collections::HashMap,
+	sync::Arc,
+	time::{Duration, Instant},
+};
+
+/// Maximum number of peers for which bandwidth usage is reported individually.
+///
+/// Once the limit is reached, the peer with the least traffic is evicted to make room for a new
+/// one.
+const MAX_METERED_PEERS: usize = 256;
+
+/// Number of per-peer token buckets after which full (idle) buckets are pruned.
+const MAX_IDLE_PEER_BUCKETS: usize = 1024;
+
+/// Bytes exchanged with a single peer.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+struct PeerTraffic {
+	/// Bytes received from the peer.
+	inbound: u64,
+
+	/// Bytes sent to the peer.
+	outbound: u64,
+}
+
+impl PeerTraffic {
+	/// Total bytes exchanged with the peer.
+	fn total(&self) -> u64 {
+		self.inbound.saturating_add(self.outbound)
+	}
+}
+
+/// Traffic of the metered peers.
+type PeerTrafficMap = Arc<Mutex<HashMap<PeerId, PeerTraffic>>>;
+
+/// Direction of the traffic.
+#[derive(Debug, Clone, Copy)]
+enum Direction {
+	In,
+	Out,
+}
+
+impl Direction {
+	fn as_str(&self) -> &'static str {
+		match self {
+			Self::In => "in",
+			Self::Out => "out",
+		}
+	}
+}
+
+/// Per-protocol and per-peer bandwidth accounting.
+///
+/// Cheap to clone. A meter created with [`BandwidthMeter::default()`] doesn't record anything.
+#[derive(Debug, Clone, Default)]
+pub struct BandwidthMeter {
+	inner: Option<Arc<InnerBandwidthMeter>>,
+}
+
+#[derive(Debug)]
+struct InnerBandwidthMeter {
+	/// Total bytes exchanged, by protocol and direction.
+	protocol_bytes_total: CounterVec<U64>,
+
+	/// Bytes exchanged with each metered peer.
+	peers: PeerTrafficMap,
+}
+
+impl BandwidthMeter {
+	/// Create new [`BandwidthMeter`] and register its metrics in `registry`.
+	pub fn register(registry: &Registry) -> Result<Self, PrometheusError> {
+		let peers = PeerTrafficMap::default();
+
+		let protocol_bytes_total = prometheus::register(
+			CounterVec::new(
+				Opts::new(
+					"substrate_sub_libp2p_protocol_bytes_total",
+					"Total bytes exchanged over notification and request-response protocols, \
+					by protocol and direction",
+				),
+				&["protocol", "direction"],
+			)?,
+			registry,
+		)?;
+		prometheus::register(
+			SourcedCounter::new(
+				&Opts::new(
+					"substrate_sub_libp2p_peer_bytes_total",
+					"Total bytes exchanged over notification and request-response protocols, \
+					by peer and direction",
+				)
+				.variable_label("peer")
+				.variable_label("direction"),
+				PeerBandwidthCounters(peers.clone()),
+			)?,
+			registry,
+		)?;
+
+		Ok(Self { inner: Some(Arc::new(InnerBandwidthMeter { protocol_bytes_total, peers })) })
+	}
+
+	/// Report `bytes` received from `peer` over `protocol`.
+	pub fn report_inbound(&self, protocol: &ProtocolName, peer: &PeerId, bytes: usize) {
+		self.report(protocol, peer, Direction::In, bytes);
+	}
+
+	/// Report `bytes` sent to `peer` over `protocol`.
+	pub fn report_outbound(&self, protocol: &ProtocolName, peer: &PeerId, bytes: usize) {
+		self.report(protocol, peer, Direction::Out, bytes);
+	}
+
+	fn report(&self, protocol: &ProtocolName, peer: &PeerId, direction: Direction, bytes: usize) {
+		let Some(inner) = &self.inner else { return };
+		let bytes = bytes as u64;
+
+		inner
+			.protocol_bytes_total
+			.with_label_values(&[&protocol[..], direction.as_str()])
+			.inc_by(bytes);
+
+		let mut peers = inner.peers.lock();
+		if !peers.contains_key(peer) && peers.len() >= MAX_METERED_PEERS {
+			let least_active = peers
+				.iter()
+				.min_by_key(|(_, traffic)| traffic.total())
+				.map(|(peer, _)| *peer)
+				.expect("`MAX_METERED_PEERS` is non-zero; qed");
+			peers.remove(&least_active);
+		}
+
+		let traffic = peers.entry(*peer).or_default();
+		match direction {
+			Direction::In => traffic.inbound = traffic.inbound.saturating_add(bytes),
+			Direction::Out => traffic.outbound = traffic.outbound.saturating_add(bytes),
+		}
+	}
+}
+
+/// The per-peer bandwidth metric.
+#[derive(Clone)]
+struct PeerBandwidthCounters(PeerTrafficMap);
+
+impl MetricSource for PeerBandwidthCounters {
+	type N = u64;
+
+	fn collect(&self, mut set: impl FnMut(&[&str], Self::N)) {
+		for (peer, traffic) in self.0.lock().iter() {
+			let peer = peer.to_string();
+			set(&[&peer, Direction::In.as_str()], traffic.inbound);
+			set(&[&peer, Direction::Out.as_str()], traffic.outbound);
+		}
+	}
+}
+
+/// Token bucket rate limit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimit {
+	/// Rate at which the bucket is refilled, in bytes per second.
+	pub bytes_per_second: u64,
+
+	/// Capacity of the bucket, i.e. the number of bytes that can be exchanged in a burst.
+	pub burst: u64,
+}
+
+impl RateLimit {
+	/// Create new [`RateLimit`] allowing bursts of one second worth of traffic.
+	pub fn new(bytes_per_second: u64) -> Self {
+		Self { bytes_per_second, burst: bytes_per_second }
+	}
+}
+
+/// Rate limits applied to inbound requests of a request-response protocol.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct InboundRequestLimits {
+	/// Limit shared by all peers.
+	pub global: Option<RateLimit>,
+
+	/// Limit applied to each peer individually.
+	pub per_peer: Option<RateLimit>,
+}
+
+impl InboundRequestLimits {
+	/// Returns `true` if no limit is configured.
+	pub fn is_unlimited(&self) -> bool {
+		self.global.is_none() && self.per_peer.is_none()
+	}
+}
+
+/// Token bucket.
+///
+/// The number of tokens can go negative if a request or response is larger than the tokens left
+/// in the bucket, in which case the debt is paid off before any new request is admitted.
+#[derive(Debug)]
+struct TokenBucket {
+	limit: RateLimit,
+	tokens: i64,
+	last_refill: Instant,
+}
+
+impl TokenBucket {
+	/// Create new, full, [`TokenBucket`].
+	fn new(limit: RateLimit, now: Instant) -> Self {
+		Self { limit, tokens: Self::capacity(&limit), last_refill: now }
+	}
+
+	fn capacity(limit: &RateLimit) -> i64 {
+		i64::try_from(limit.burst).unwrap_or(i64::MAX)
+	}
+
+	/// Add the tokens accumulated since the last refill.
+	fn refill(&mut self, now: Instant) {
+		let capacity = Self::capacity(&self.limit);
+		if self.tokens >= capacity {
+			self.last_refill = now;
+			return
+		}
+
+		let elapsed = now.saturating_duration_since(self.last_refill);
+		let accumulated = (elapsed.as_secs_f64() * self.limit.bytes_per_second as f64) as i64;
+		if accumulated == 0 {
+			return
+		}
+
+		if self.tokens.saturating_add(accumulated) >= capacity {
+			self.tokens = capacity;
+			self.last_refill = now;
+		} else {
+			// Only account for the time that produced whole tokens so that frequent refills
+			// don't lose the fractional remainder.
+			self.tokens += accumulated;
+			self.last_refill +=
+				Duration::from_secs_f64(accumulated as f64 / self.limit.bytes_per_second as f64);
+		}
+	}
+
+	fn has_tokens(&self) -> bool {
+		self.tokens > 0
+	}
+
+	fn is_full(&self) -> bool {
+		self.tokens >= Self::capacity(&self.limit)
+	}
+
+	fn consume(&mut self, bytes: usize) {
+		self.tokens = self.tokens.saturating_sub(i64::try_from(bytes).unwrap_or(i64::MAX));
+	}
+}
+
+#[derive(Debug)]
+struct Buckets {
+	global: Option<TokenBucket>,
+	peers: HashMap<PeerId, TokenBucket>,
+}
+
+/// Token bucket based rate limiter for inbound requests.
+///
+/// Cheap to clone; clones share the same buckets.
+#[derive(Debug, Clone)]
+pub struct InboundRequestLimiter {
+	limits: InboundRequestLimits,
+	buckets: Option<Arc<Mutex<Buckets>>>,
+}
+
+impl Default for InboundRequestLimiter {
+	fn default() -> Self {
+		Self::new(InboundRequestLimits::default())
+	}
+}
+
+impl InboundRequestLimiter {
+	/// Create new [`InboundRequestLimiter`] enforcing `limits`.
+	pub fn new(limits: InboundRequestLimits) -> Self {
+		let buckets = (!limits.is_unlimited()).then(|| {
+			Arc::new(Mutex::new(Buckets {
+				global: limits.global.map(|limit| TokenBucket::new(limit, Instant::now())),
+				peers: HashMap::new(),
+			}))
+		});
+
+		Self { limits, buckets }
+	}
+
+	/// Check whether a request of `size` bytes from `peer` should be processed.
+	///
+	/// If the request is admitted, its size is deducted from the applicable buckets.
+	pub fn admit(&self, peer: &PeerId, size: usize) -> bool {
+		self.admit_at(peer, size, Instant::now())
+	}
+
+	/// Record a response of `size` bytes sent to `peer`.
+	pub fn record_response(&self, peer: &PeerId, size: usize) {
+		self.record_response_at(peer, size, Instant::now())
+	}
+
+	fn admit_at(&self, peer: &PeerId, size: usize, now: Instant) -> bool {
+		let Some(buckets) = &self.buckets else { return true };
+		let Buckets { global, peers } = &mut *buckets.lock();
+
+		if let Some(bucket) = global.as_mut() {
+			bucket.refill(now);
+			if !bucket.has_tokens() {
+				return false
+			}
+		}
+
+		let peer_bucket = match self.limits.per_peer {
+			Some(limit) => {
+				if peers.len() >= MAX_IDLE_PEER_BUCKETS && !peers.contains_key(peer) {
+					peers.retain(|_, bucket| {
+						bucket.refill(now);
+						!bucket.is_full()
+					});
+				}
+
+				let bucket = peers.entry(*peer).or_insert_with(|| TokenBucket::new(limit, now));
+				bucket.refill(now);
+				if !bucket.has_tokens() {
+					return false
+				}
+				Some(bucket)
+			},
+			None => None,
+		};
+
+		global.iter_mut().chain(peer_bucket).for_each(|bucket| bucket.consume(size));
+		true
+	}
+
+	fn record_response_at(&self, peer: &PeerId, size: usize, now: Instant) {
+		let Some(buckets) = &self.buckets else { return };
+		let Buckets { global, peers } = &mut *buckets.lock();
+
+		global.iter_mut().chain(peers.get_mut(peer)).for_each(|bucket| {
+			bucket.refill(now);
+			bucket.consume(size);
+		});
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn unlimited_limiter_admits_everything() {
+		let limiter = InboundRequestLimiter::default();
+		let peer = PeerId::random();
+
+		for _ in 0..100 {
+			assert!(limiter.admit(&peer, usize::MAX));
+			limiter.record_response(&peer, usize::MAX);
+		}
+	}
+
+	#[test]
+	fn per_peer_limit_is_enforced_and_refilled() {
+		let limiter = InboundRequestLimiter::new(InboundRequestLimits {
+			global: None,
+			per_peer: Some(RateLimit::new(1000)),
+		});
+		let (peer, other) = (PeerId::random(), PeerId::random());
+		let now = Instant::now();
+
+		assert!(limiter.admit_at(&peer, 100, now));
+		limiter.record_response_at(&peer, 1500, now);
+
+		// the bucket of `peer` is in debt but other peers are not affected
+		assert!(!limiter.admit_at(&peer, 100, now));
+		assert!(limiter.admit_at(&other, 100, now));
+
+		// after half a second `peer` is still in debt
+		assert!(!limiter.admit_at(&peer, 100, now + Duration::from_millis(500)));
+
+		// ... and after one second it has paid it off
+		assert!(limiter.admit_at(&peer, 100, now + Duration::from_millis(1100)));
+	}
+
+	#[test]
+	fn global_limit_is_shared_by_all_peers() {
+		let limiter = InboundRequestLimiter::new(InboundRequestLimits {
+			global: Some(RateLimit { bytes_per_second: 100, burst: 300 }),
+			per_peer: None,
+		});
+		let now = Instant::now();
+
+		for _ in 0..3 {
+			assert!(limiter.admit_at(&PeerId::random(), 100, now));
+		}
+		assert!(!limiter.admit_at(&PeerId::random(), 100, now));
+		assert!(!limiter.admit_at(&PeerId::random(), 100, now + Duration::from_millis(5)));
+		assert!(limiter.admit_at(&PeerId::random(), 100, now + Duration::from_secs(1)));
+	}
+
+	#[test]
+	fn token_bucket_keeps_fractional_refills() {
+		let now = Instant::now();
+		let mut bucket = TokenBucket::new(RateLimit::new(1000), now);
+		bucket.consume(1000);
+
+		// refilling every 100 microseconds produces 0.1 tokens each time
+		for i in 1..=11_000 {
+			bucket.refill(now + Duration::from_micros(100 * i));
+		}
+
+		assert!(bucket.is_full());
+	}
+
+	#[test]
+	fn idle_peer_buckets_are_pruned() {
+		let limiter = InboundRequestLimiter::new(InboundRequestLimits {
+			global: None,
+			per_peer: Some(RateLimit::new(1000)),
+		});
+		let now = Instant::now();
+
+		for _ in 0..MAX_IDLE_PEER_BUCKETS {
+			assert!(limiter.admit_at(&PeerId::random(), 10, now));
+		}
+		assert_eq!(limiter.buckets.as_ref().unwrap().lock().peers.len(), MAX_IDLE_PEER_BUCKETS);
+
+		assert!(limiter.admit_at(&PeerId::random(), 10, now + Duration::from_secs(1)));
+		assert_eq!(limiter.buckets.as_ref().unwrap().lock().peers.len(), 1);
+	}
+
+	#[test]
+	fn meter_reports_protocol_and_peer_traffic() {
+		let registry = Registry::new();
+		let meter = BandwidthMeter::register(&registry).unwrap();
+		let protocol = ProtocolName::from("/test/1");
+		let peer = PeerId::random();
+
+		meter.report_inbound(&protocol, &peer, 100);
+		meter.report_outbound(&protocol, &peer, 50);
+		meter.report_outbound(&protocol, &peer, 50);
+
+		let inner = meter.inner.as_ref().unwrap();
+		assert_eq!(inner.protocol_bytes_total.with_label_values(&["/test/1", "in"]).get(), 100);
+		assert_eq!(inner.protocol_bytes_total.with_label_values(&["/test/1", "out"]).get(), 100);
+		assert_eq!(
+			inner.peers.lock().get(&peer),
+			Some(&PeerTraffic { inbound: 100, outbound: 100 })
+		);
+	}
+
+	#[test]
+	fn least_active_peer_is_evicted() {
+		let meter = BandwidthMeter::register(&Registry::new()).unwrap();
+		let protocol = ProtocolName::from("/test/1");
+		let quiet = PeerId::random();
+
+		meter.report_inbound(&protocol, &quiet, 1);
+		for _ in 1..MAX_METERED_PEERS {
+			meter.report_inbound(&protocol, &PeerId::random(), 100);
+		}
+
+		let newcomer = PeerId::random();
+		meter.report_outbound(&protocol, &newcomer, 10);
+
+		let peers = &meter.inner.as_ref().unwrap().peers;
+		assert_eq!(peers.lock().len(), MAX_METERED_PEERS);
+		assert!(!peers.lock().contains_key(&quiet));
+		assert!(peers.lock().contains_key(&newcomer));
+	}
+}
@@ -17,6 +17,8 @@
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
 use crate::{
+	ban_filter::BanFilter,
+	bandwidth::{BandwidthMeter, InboundRequestLimits},
 	discovery::{DiscoveryBehaviour, DiscoveryConfig, DiscoveryOut},
 	event::DhtEvent,
 	peer_info,
@@ -54,6 +56,8 @@ pub use crate::request_responses::{InboundFailure, OutboundFailure, ResponseFail
 #[derive(NetworkBehaviour)]
 #[behaviour(to_swarm = "BehaviourOut")]
 pub struct Behaviour<B: BlockT> {
+	/// Refuses connections with banned peers and IP ranges.
+	ban_filter: BanFilter,
 	/// Connection limits.
 	connection_limits: libp2p::connection_limits::Behaviour,
 	/// All the substrate-specific protocols.
@@ -183,11 +187,14 @@ impl<B: BlockT> Behaviour<B> {
 		disco_config: DiscoveryConfig,
 		request_response_protocols: Vec<ProtocolConfig>,
 		peer_store_handle: Arc<dyn PeerStoreProvider>,
+		bandwidth_meter: BandwidthMeter,
+		inbound_request_limits: InboundRequestLimits,
 		external_addresses: Arc<Mutex<HashSet<Multiaddr>>>,
 		public_addresses: Vec<Multiaddr>,
 		connection_limits: ConnectionLimits,
 	) -> Result<Self, request_responses::RegisterError> {
 		Ok(Self {
+			ban_filter: BanFilter::new(Arc::clone(&peer_store_handle)),
 			substrate,
 			peer_info: peer_info::PeerInfoBehaviour::new(
 				user_agent,
@@ -199,6 +206,8 @@ impl<B: BlockT> Behaviour<B> {
 			request_responses: request_responses::RequestResponsesBehaviour::new(
 				request_response_protocols.into_iter(),
 				peer_store_handle,
+				bandwidth_meter,
+				inbound_request_limits,
 			)?,
 			connection_limits: libp2p::connection_limits::Behaviour::new(connection_limits),
 		})
@@ -23,7 +23,7 @@
 
 pub use crate::{
 	discovery::DEFAULT_KADEMLIA_REPLICATION_FACTOR,
-	peer_store::PeerStoreProvider,
+	peer_store::{PeerStorePersistence, PeerStoreProvider},
 	protocol::{notification_service, NotificationsSink, ProtocolHandlePair},
 	request_responses::{
 		IncomingRequest, OutgoingResponse, ProtocolConfig as RequestResponseConfig,
@@ -41,14 +41,17 @@ use sc_network_types::{
 	PeerId,
 };
 
-use crate::service::{ensure_addresses_consistent_with_transport, traits::NetworkBackend};
+use crate::{
+	bandwidth::InboundRequestLimits,
+	service::{ensure_addresses_consistent_with_transport, traits::NetworkBackend},
+};
 use codec::Encode;
 use prometheus_endpoint::Registry;
 use zeroize::Zeroize;
 
 pub use sc_network_common::{
 	role::{Role, Roles},
-	sync::SyncMode,
+	sync::{BlockHistorySync, SyncMode},
 	ExHashT,
 };
 
@@ -655,6 +658,19 @@ pub struct NetworkConfiguration {
 
 	/// Networking backend used for P2P communication.
 	pub network_backend: NetworkBackendType,
+
+	/// Rate limits applied to inbound requests of each request-response protocol.
+	pub inbound_request_limits: InboundRequestLimits,
+}
+
+/// Trusted finalized block used for checkpoint sync.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CheckpointSync {
+	/// SCALE-encoded hash of the block. The header is downloaded from peers.
+	Hash(Vec<u8>),
+	/// JSON file with the header of the block, as returned by the `chain_getHeader` RPC, or with
+	/// the block and its justifications, as returned by the `chain_getBlock` RPC.
+	File(PathBuf),
 }
 
 impl NetworkConfiguration {
@@ -688,6 +704,7 @@ impl NetworkConfiguration {
 				.expect("value is a constant; constant is non-zero; qed."),
 			ipfs_server: false,
 			network_backend: NetworkBackendType::Litep2p,
+			inbound_request_limits: InboundRequestLimits::default(),
 		}
 	}
 
@@ -773,6 +790,18 @@ pub struct FullNetworkConfiguration<B: BlockT + 'static, H: ExHashT, N: NetworkB
 	/// Handle to [`PeerStore`](crate::peer_store::PeerStore).
 	peer_store_handle: Arc<dyn PeerStoreProvider>,
 
+	/// Addresses of peers that could be dialed during previous runs.
+	pub(crate) cached_addresses: Vec<(PeerId, Multiaddr)>,
+
+	/// Directory where networking data is persisted across restarts.
+	data_dir: Option<PathBuf>,
+
+	/// Trusted finalized block to sync the state of, if any.
+	checkpoint_sync: Option<CheckpointSync>,
+
+	/// How much block history to download in the background after warp sync.
+	block_history: BlockHistorySync,
+
 	/// Registry for recording prometheus metrics to.
 	pub metrics_registry: Option<Registry>,
 }
@@ -787,6 +816,10 @@ impl<B: BlockT + 'static, H: ExHashT, N: NetworkBackend<B, H>> FullNetworkConfig
 		Self {
 			peer_store: Some(peer_store),
 			peer_store_handle,
+			cached_addresses: Vec::new(),
+			data_dir: None,
+			checkpoint_sync: None,
+			block_history: BlockHistorySync::default(),
 			notification_protocols: Vec::new(),
 			request_response_protocols: Vec::new(),
 			network_config: network_config.clone(),
@@ -827,6 +860,53 @@ impl<B: BlockT + 'static, H: ExHashT, N: NetworkBackend<B, H>> FullNetworkConfig
 			.expect("`PeerStore` can only be taken once when it's started; qed")
 	}
 
+	/// Persist networking data in `dir` across restarts.
+	///
+	/// Peer reputations, bans and known-good peer addresses persisted by a previous run are
+	/// restored, and kept up to date while the node runs. The restored addresses are only used as
+	/// discovery candidates and may be evicted like any other discovered address.
+	///
+	/// Must be called before [`Self::take_peer_store`].
+	pub fn set_data_dir(&mut self, dir: PathBuf) {
+		let peer_store = self
+			.peer_store
+			.as_ref()
+			.expect("`PeerStore` state must be restored before it's started");
+
+		let persistence = PeerStorePersistence::load(&dir);
+		self.cached_addresses = persistence.known_addresses();
+		peer_store.restore(persistence);
+		self.data_dir = Some(dir);
+	}
+
+	/// Directory where networking data is persisted, if set with [`Self::set_data_dir`].
+	pub fn data_dir(&self) -> Option<&Path> {
+		self.data_dir.as_deref()
+	}
+
+	/// Sync the state of a trusted finalized block instead of proving finality from genesis.
+	///
+	/// Implies warp sync mode and takes precedence over the warp sync provider of the node, which
+	/// keeps serving proofs to other peers.
+	pub fn set_checkpoint_sync(&mut self, checkpoint: CheckpointSync) {
+		self.checkpoint_sync = Some(checkpoint);
+	}
+
+	/// Trusted block to sync from, if set with [`Self::set_checkpoint_sync`].
+	pub fn checkpoint_sync(&self) -> Option<&CheckpointSync> {
+		self.checkpoint_sync.as_ref()
+	}
+
+	/// Limit the block history downloaded in the background after warp sync.
+	pub fn set_block_history(&mut self, block_history: BlockHistorySync) {
+		self.block_history = block_history;
+	}
+
+	/// Block history to download after warp sync, as set with [`Self::set_block_history`].
+	pub fn block_history(&self) -> BlockHistorySync {
+		self.block_history
+	}
+
 	/// Verify addresses are consistent with enabled transports.
 	pub fn sanity_check_addresses(&self) -> Result<(), crate::error::Error> {
 		ensure_addresses_consistent_with_transport(
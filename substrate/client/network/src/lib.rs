@@ -242,6 +242,7 @@
 //!
 //! More precise usage details are still being worked on and will likely change in the future.
 
+mod ban_filter;
 mod behaviour;
 mod bitswap;
 mod litep2p;
@@ -250,6 +251,7 @@ mod protocol;
 #[cfg(test)]
 mod mock;
 
+pub mod bandwidth;
 pub mod config;
 pub mod discovery;
 pub mod error;
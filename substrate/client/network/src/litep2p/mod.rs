@@ -19,6 +19,7 @@
 //! `NetworkBackend` implementation for `litep2p`.
 
 use crate::{
+	bandwidth::InboundRequestLimiter,
 	config::{
 		FullNetworkConfiguration, IncomingRequest, NodeKeyConfig, NotificationHandshake, Params,
 		SetConfig, TransportConfig,
@@ -393,9 +394,17 @@ impl<B: BlockT + 'static, H: ExHashT> NetworkBackend<B, H> for Litep2pNetworkBac
 
 		let mut config_builder =
 			Self::configure_transport(&params.network_config).with_keypair(keypair.clone());
-		let known_addresses = params.network_config.known_addresses();
+		// litep2p evicts addresses that can't be dialed from both the routing table and the address
+		// book, so the addresses of peers dialed during previous runs are seeded like bootnodes.
+		let known_addresses = params
+			.network_config
+			.known_addresses()
+			.into_iter()
+			.chain(params.network_config.cached_addresses.iter().cloned())
+			.collect::<Vec<_>>();
 		let peer_store_handle = params.network_config.peer_store_handle();
 		let executor = Arc::new(Litep2pExecutor { executor: params.executor });
+		let bandwidth_meter = params.notification_metrics.bandwidth_meter();
 
 		let FullNetworkConfiguration {
 			notification_protocols,
@@ -469,6 +478,8 @@ impl<B: BlockT + 'static, H: ExHashT> NetworkBackend<B, H> for Litep2pNetworkBac
 						.expect("receiver exists as it was just added and there are no duplicate protocols; qed"),
 					request_response_senders.clone(),
 					metrics.clone(),
+					bandwidth_meter.clone(),
+					InboundRequestLimiter::new(network_config.inbound_request_limits),
 				);
 
 				executor.run(Box::pin(async move {
@@ -484,6 +495,15 @@ impl<B: BlockT + 'static, H: ExHashT> NetworkBackend<B, H> for Litep2pNetworkBac
 			known_addresses.into_iter().fold(HashMap::new(), |mut acc, (peer, address)| {
 				use sc_network_types::multiaddr::Protocol;
 
+				// litep2p can't refuse connections before they are established, so at least
+				// don't dial banned peers. Connections from banned peers and IP ranges are closed
+				// as soon as they are established, see `PeerStoreProvider::note_connection()`.
+				if peer_store_handle.is_banned(&peer) ||
+					peer_store_handle.is_address_banned(&address)
+				{
+					return acc
+				}
+
 				let address = match address.iter().last() {
 					Some(Protocol::Ws(_) | Protocol::Wss(_) | Protocol::Tcp(_)) =>
 						address.with(Protocol::P2p(peer.into())),
@@ -1107,6 +1127,12 @@ impl<B: BlockT + 'static, H: ExHashT> NetworkBackend<B, H> for Litep2pNetworkBac
 				},
 				event = self.litep2p.next_event() => match event {
 					Some(Litep2pEvent::ConnectionEstablished { peer, endpoint }) => {
+						self.peerstore_handle.note_connection(
+							peer.into(),
+							endpoint.address().clone().into(),
+							matches!(endpoint, Endpoint::Dialer { .. }),
+						);
+
 						let Some(metrics) = &self.metrics else {
 							continue;
 						};
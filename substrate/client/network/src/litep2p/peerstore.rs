@@ -22,7 +22,10 @@
 //! such as their addresses, reputations, supported protocols etc.
 
 use crate::{
-	peer_store::{PeerStoreProvider, ProtocolHandle},
+	peer_store::{
+		persist, Ban, BanReason, BanTarget, PeerBook, PeerStorePersistence, PeerStoreProvider,
+		ProtocolHandle,
+	},
 	service::{metrics::PeerStoreMetrics, traits::PeerStore},
 	ObservedRole, ReputationChange,
 };
@@ -31,12 +34,12 @@ use parking_lot::Mutex;
 use prometheus_endpoint::Registry;
 use wasm_timer::Delay;
 
-use sc_network_types::PeerId;
+use sc_network_types::{multiaddr::Multiaddr, PeerId};
 
 use std::{
 	collections::{HashMap, HashSet},
 	sync::Arc,
-	time::{Duration, Instant},
+	time::{Duration, Instant, SystemTime},
 };
 
 /// Logging target for the file.
@@ -120,6 +123,7 @@ pub struct PeerstoreHandleInner {
 	peers: HashMap<PeerId, PeerInfo>,
 	protocols: Vec<Arc<dyn ProtocolHandle>>,
 	metrics: Option<PeerStoreMetrics>,
+	book: PeerBook,
 }
 
 #[derive(Debug, Clone, Default)]
@@ -132,7 +136,12 @@ impl PeerstoreHandle {
 		protocols: Vec<Arc<dyn ProtocolHandle>>,
 		metrics: Option<PeerStoreMetrics>,
 	) -> Self {
-		Self(Arc::new(Mutex::new(PeerstoreHandleInner { peers, protocols, metrics })))
+		Self(Arc::new(Mutex::new(PeerstoreHandleInner {
+			peers,
+			protocols,
+			metrics,
+			book: PeerBook::default(),
+		})))
 	}
 
 	/// Add known peer to [`Peerstore`].
@@ -168,17 +177,47 @@ impl PeerstoreHandle {
 			}
 			info.reputation != 0 || info.last_updated + FORGET_AFTER > now
 		});
+		let PeerstoreHandleInner { peers, book, .. } = &mut *lock;
+		book.progress_time(|peer| peers.contains_key(peer));
 
 		if let Some(metrics) = &lock.metrics {
 			metrics.num_discovered.set(lock.peers.len() as u64);
 			metrics.num_banned_peers.set(num_banned_peers);
 		}
 	}
+
+	/// Restore the persisted reputations and ban list.
+	fn restore(&self, persistence: PeerStorePersistence) {
+		let mut lock = self.0.lock();
+		let offline_secs = persistence.offline_secs();
+		let (book, reputations) = PeerBook::restore(persistence);
+
+		for (peer, reputation) in reputations {
+			let info = lock.peers.entry(peer).or_default();
+			info.reputation = reputation;
+			info.decay_reputation(offline_secs);
+		}
+		lock.book = book;
+	}
+
+	/// Persist the state if it is due.
+	async fn persist(&self) {
+		let snapshot = {
+			let mut lock = self.0.lock();
+			let PeerstoreHandleInner { peers, book, .. } = &mut *lock;
+			book.snapshot(peers.iter().map(|(peer, info)| (*peer, info.reputation)))
+		};
+
+		if let Some((path, state)) = snapshot {
+			persist(path, state).await;
+		}
+	}
 }
 
 impl PeerStoreProvider for PeerstoreHandle {
 	fn is_banned(&self, peer: &PeerId) -> bool {
-		self.0.lock().peers.get(peer).map_or(false, |info| info.is_banned())
+		let lock = self.0.lock();
+		lock.peers.get(peer).map_or(false, |info| info.is_banned()) || lock.book.is_banned(peer)
 	}
 
 	/// Register a protocol handle to disconnect peers whose reputation drops below the threshold.
@@ -275,7 +314,8 @@ impl PeerStoreProvider for PeerstoreHandle {
 			.peers
 			.iter()
 			.filter_map(|(peer, info)| {
-				(!ignored.contains(&peer) && !info.is_banned()).then_some((*peer, info.reputation))
+				(!ignored.contains(&peer) && !info.is_banned() && !handle.book.is_banned(peer))
+					.then_some((*peer, info.reputation))
 			})
 			.collect::<Vec<(PeerId, _)>>();
 		candidates.sort_by(|(_, a), (_, b)| b.cmp(a));
@@ -290,6 +330,60 @@ impl PeerStoreProvider for PeerstoreHandle {
 	fn add_known_peer(&self, peer: PeerId) {
 		self.0.lock().peers.entry(peer).or_default().last_updated = Instant::now();
 	}
+
+	/// Ban a peer or an IP range.
+	fn ban(&self, target: BanTarget, until: Option<SystemTime>) {
+		let mut lock = self.0.lock();
+		let PeerstoreHandleInner { book, protocols, .. } = &mut *lock;
+
+		for peer in book.ban(target, until) {
+			protocols.iter().for_each(|handle| handle.disconnect_peer(peer));
+		}
+	}
+
+	/// Lift a ban.
+	fn unban(&self, target: &BanTarget) -> bool {
+		let mut lock = self.0.lock();
+		let mut unbanned = lock.book.unban(target);
+
+		if let BanTarget::Peer(peer) = target {
+			if let Some(info) = lock.peers.get_mut(peer).filter(|info| info.is_banned()) {
+				info.reputation = 0;
+				info.bump_last_updated();
+				unbanned = true;
+			}
+		}
+
+		unbanned
+	}
+
+	/// Get the bans currently in place.
+	fn bans(&self) -> Vec<Ban> {
+		let lock = self.0.lock();
+
+		lock.book
+			.bans()
+			.chain(lock.peers.iter().filter(|(_, info)| info.is_banned()).map(|(peer, info)| Ban {
+				target: BanTarget::Peer(*peer),
+				reason: BanReason::Reputation(info.reputation),
+			}))
+			.collect()
+	}
+
+	/// Check whether the IP address of `address` is in a banned range.
+	fn is_address_banned(&self, address: &Multiaddr) -> bool {
+		self.0.lock().book.is_address_banned(address)
+	}
+
+	/// Record an established connection.
+	fn note_connection(&self, peer: PeerId, address: Multiaddr, outbound: bool) {
+		let mut lock = self.0.lock();
+
+		if lock.book.note_connection(peer, address, outbound) {
+			log::debug!(target: LOG_TARGET, "Connected to banned peer {peer}, disconnecting.");
+			lock.protocols.iter().for_each(|handle| handle.disconnect_peer(peer));
+		}
+	}
 }
 
 /// `Peerstore` handle for testing.
@@ -356,6 +450,7 @@ impl Peerstore {
 			};
 
 			self.peerstore_handle.progress_time(seconds_passed);
+			self.peerstore_handle.persist().await;
 			let _ = Delay::new(Duration::from_secs(1)).await;
 		}
 	}
@@ -368,6 +463,11 @@ impl PeerStore for Peerstore {
 		Arc::new(self.peerstore_handle.clone())
 	}
 
+	/// Restore the state persisted by a previous run.
+	fn restore(&self, persistence: PeerStorePersistence) {
+		self.peerstore_handle.restore(persistence);
+	}
+
 	/// Start running `PeerStore` event loop.
 	async fn run(self) {
 		self.run().await;
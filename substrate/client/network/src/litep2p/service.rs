@@ -25,7 +25,7 @@ use crate::{
 		request_response::OutboundRequest,
 	},
 	network_state::NetworkState,
-	peer_store::PeerStoreProvider,
+	peer_store::{Ban, BanTarget, PeerStoreProvider},
 	service::out_events,
 	Event, IfDisconnected, NetworkDHTProvider, NetworkEventStream, NetworkPeers, NetworkRequest,
 	NetworkSigner, NetworkStateInfo, NetworkStatus, NetworkStatusProvider, OutboundFailure,
@@ -55,7 +55,7 @@ use sc_utils::mpsc::TracingUnboundedSender;
 use std::{
 	collections::{HashMap, HashSet},
 	sync::{atomic::Ordering, Arc},
-	time::Instant,
+	time::{Instant, SystemTime},
 };
 
 /// Logging target for the file.
@@ -507,6 +507,18 @@ impl NetworkPeers for Litep2pNetworkService {
 		// the channel can only be closed if `Peerset` no longer exists
 		rx.await.map_err(|_| ())
 	}
+
+	fn ban(&self, target: BanTarget, until: Option<SystemTime>) {
+		self.peer_store_handle.ban(target, until);
+	}
+
+	fn unban(&self, target: &BanTarget) -> bool {
+		self.peer_store_handle.unban(target)
+	}
+
+	fn bans(&self) -> Vec<Ban> {
+		self.peer_store_handle.bans()
+	}
 }
 
 impl NetworkEventStream for Litep2pNetworkService {
@@ -83,7 +83,7 @@ impl MessageSink for Litep2pMessageSink {
 		let size = notification.len();
 
 		match self.sink.send_sync_notification(notification) {
-			Ok(_) => self.metrics.register_notification_sent(&self.protocol, size),
+			Ok(_) => self.metrics.register_notification_sent(&self.protocol, &self.peer, size),
 			Err(error) => log::trace!(
 				target: LOG_TARGET,
 				"{}: failed to send sync notification to {:?}: {error:?}",
@@ -102,7 +102,7 @@ impl MessageSink for Litep2pMessageSink {
 
 		match self.sink.send_async_notification(notification).await {
 			Ok(_) => {
-				self.metrics.register_notification_sent(&self.protocol, size);
+				self.metrics.register_notification_sent(&self.protocol, &self.peer, size);
 				Ok(())
 			},
 			Err(error) => {
@@ -202,7 +202,7 @@ impl NotificationService for NotificationProtocol {
 		let size = notification.len();
 
 		if let Ok(_) = self.handle.send_sync_notification(peer.into(), notification) {
-			self.metrics.register_notification_sent(&self.protocol, size);
+			self.metrics.register_notification_sent(&self.protocol, peer, size);
 		}
 	}
 
@@ -215,7 +215,7 @@ impl NotificationService for NotificationProtocol {
 
 		match self.handle.send_async_notification(peer.into(), notification).await {
 			Ok(_) => {
-				self.metrics.register_notification_sent(&self.protocol, size);
+				self.metrics.register_notification_sent(&self.protocol, peer, size);
 				Ok(())
 			},
 			Err(_) => Err(Error::ChannelClosed),
@@ -345,7 +345,11 @@ impl NotificationService for NotificationProtocol {
 						peer,
 						notification,
 					} => {
-						self.metrics.register_notification_received(&self.protocol, notification.len());
+						self.metrics.register_notification_received(
+							&self.protocol,
+							&peer.into(),
+							notification.len(),
+						);
 
 						if !self.pending_cancels.contains(&peer) {
 							return Some(SubstrateNotificationEvent::NotificationReceived {
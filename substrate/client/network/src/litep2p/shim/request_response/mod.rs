@@ -20,6 +20,7 @@
 //! request-response API.
 
 use crate::{
+	bandwidth::{BandwidthMeter, InboundRequestLimiter},
 	litep2p::shim::request_response::metrics::RequestResponseMetrics,
 	peer_store::PeerStoreProvider,
 	request_responses::{IncomingRequest, OutgoingResponse},
@@ -196,6 +197,12 @@ pub struct RequestResponseProtocol {
 
 	/// Metrics, if enabled.
 	metrics: RequestResponseMetrics,
+
+	/// Per-protocol and per-peer bandwidth accounting.
+	bandwidth: BandwidthMeter,
+
+	/// Rate limiter for inbound requests.
+	inbound_limiter: InboundRequestLimiter,
 }
 
 impl RequestResponseProtocol {
@@ -208,6 +215,8 @@ impl RequestResponseProtocol {
 		request_rx: TracingUnboundedReceiver<OutboundRequest>,
 		request_tx: HashMap<ProtocolName, TracingUnboundedSender<OutboundRequest>>,
 		metrics: Option<Metrics>,
+		bandwidth: BandwidthMeter,
+		inbound_limiter: InboundRequestLimiter,
 	) -> Self {
 		Self {
 			handle,
@@ -219,6 +228,8 @@ impl RequestResponseProtocol {
 			pending_inbound_responses: HashMap::new(),
 			pending_outbound_responses: FuturesUnordered::new(),
 			metrics: RequestResponseMetrics::new(metrics, protocol),
+			bandwidth,
+			inbound_limiter,
 		}
 	}
 
@@ -245,8 +256,11 @@ impl RequestResponseProtocol {
 			dial_options,
 		);
 
+		let size = request.len();
+
 		match self.handle.try_send_request(peer.into(), request, dial_options) {
 			Ok(request_id) => {
+				self.bandwidth.report_outbound(&self.protocol, &peer, size);
 				self.pending_inbound_responses
 					.insert(request_id, PendingRequest::new(tx, Instant::now(), fallback_request));
 			},
@@ -279,6 +293,7 @@ impl RequestResponseProtocol {
 			self.protocol,
 			request.len(),
 		);
+		self.bandwidth.report_inbound(&self.protocol, &peer.into(), request.len());
 
 		let Some(inbound_queue) = &self.inbound_queue else {
 			log::trace!(
@@ -303,6 +318,18 @@ impl RequestResponseProtocol {
 			return;
 		}
 
+		if !self.inbound_limiter.admit(&peer.into(), request.len()) {
+			log::debug!(
+				target: LOG_TARGET,
+				"{}: rejecting inbound request from {peer:?} ({request_id:?}), rate limit exceeded",
+				self.protocol,
+			);
+
+			self.handle.reject_request(request_id);
+			self.metrics.register_inbound_request_failure("rate-limited");
+			return;
+		}
+
 		let (tx, rx) = oneshot::channel();
 
 		match inbound_queue.try_send(IncomingRequest {
@@ -349,6 +376,7 @@ impl RequestResponseProtocol {
 					self.protocol,
 					response.len(),
 				);
+				self.bandwidth.report_inbound(&self.protocol, &peer.into(), response.len());
 
 				let _ = tx.send(Ok((response, self.protocol.clone())));
 				self.metrics.register_outbound_request_success(started.elapsed());
@@ -505,12 +533,15 @@ impl RequestResponseProtocol {
 				self.metrics.register_inbound_request_failure("rejected");
 			},
 			Ok(response) => {
+				let size = response.len();
 				log::trace!(
 					target: LOG_TARGET,
-					"{}: send response ({request_id:?}) to {peer:?}, response size {}",
+					"{}: send response ({request_id:?}) to {peer:?}, response size {size}",
 					self.protocol,
-					response.len(),
 				);
+				let peer = peer.into();
+				self.bandwidth.report_outbound(&self.protocol, &peer, size);
+				self.inbound_limiter.record_response(&peer, size);
 
 				match sent_feedback {
 					None => self.handle.send_response(request_id, response),
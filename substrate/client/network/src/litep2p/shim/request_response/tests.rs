@@ -110,6 +110,8 @@ async fn dial_failure() {
 		outbound_rx,
 		senders,
 		None,
+		Default::default(),
+		Default::default(),
 	);
 
 	tokio::spawn(protocol.run());
@@ -146,6 +148,8 @@ async fn send_request_to_disconnected_peer() {
 		outbound_rx,
 		senders,
 		None,
+		Default::default(),
+		Default::default(),
 	);
 
 	tokio::spawn(protocol.run());
@@ -192,6 +196,8 @@ async fn send_request_to_disconnected_peer_and_dial() {
 		outbound_rx1,
 		senders,
 		None,
+		Default::default(),
+		Default::default(),
 	);
 
 	let (outbound_tx2, outbound_rx2) = tracing_unbounded("outbound-request", 1000);
@@ -206,6 +212,8 @@ async fn send_request_to_disconnected_peer_and_dial() {
 		outbound_rx2,
 		senders,
 		None,
+		Default::default(),
+		Default::default(),
 	);
 
 	tokio::spawn(protocol1.run());
@@ -253,6 +261,8 @@ async fn too_many_inbound_requests() {
 		outbound_rx,
 		senders,
 		None,
+		Default::default(),
+		Default::default(),
 	);
 
 	tokio::spawn(protocol.run());
@@ -311,6 +321,8 @@ async fn feedback_works() {
 		outbound_rx,
 		senders,
 		None,
+		Default::default(),
+		Default::default(),
 	);
 
 	tokio::spawn(protocol.run());
@@ -428,6 +440,8 @@ async fn fallback_request_compatible_peers() {
 		outbound_rx1,
 		senders1.clone(),
 		None,
+		Default::default(),
+		Default::default(),
 	);
 
 	let (tx_fallback, _rx_fallback) = async_channel::bounded(4);
@@ -439,6 +453,8 @@ async fn fallback_request_compatible_peers() {
 		outbound_rx_fallback,
 		senders1,
 		None,
+		Default::default(),
+		Default::default(),
 	);
 
 	let (outbound_tx2, outbound_rx2) = tracing_unbounded("outbound-request", 1000);
@@ -453,6 +469,8 @@ async fn fallback_request_compatible_peers() {
 		outbound_rx2,
 		senders2,
 		None,
+		Default::default(),
+		Default::default(),
 	);
 
 	tokio::spawn(protocol1.run());
@@ -570,6 +588,8 @@ async fn fallback_request_old_peer_receives() {
 		outbound_rx1,
 		senders1.clone(),
 		None,
+		Default::default(),
+		Default::default(),
 	);
 
 	let (tx_fallback, _rx_fallback) = async_channel::bounded(4);
@@ -581,6 +601,8 @@ async fn fallback_request_old_peer_receives() {
 		outbound_rx_fallback,
 		senders1,
 		None,
+		Default::default(),
+		Default::default(),
 	);
 
 	let (outbound_tx2, outbound_rx2) = tracing_unbounded("outbound-request", 1000);
@@ -595,6 +617,8 @@ async fn fallback_request_old_peer_receives() {
 		outbound_rx2,
 		senders2,
 		None,
+		Default::default(),
+		Default::default(),
 	);
 
 	tokio::spawn(protocol1.run());
@@ -712,6 +736,8 @@ async fn fallback_request_old_peer_sends() {
 		outbound_rx1,
 		senders1.clone(),
 		None,
+		Default::default(),
+		Default::default(),
 	);
 
 	let (tx_fallback, rx_fallback) = async_channel::bounded(4);
@@ -723,6 +749,8 @@ async fn fallback_request_old_peer_sends() {
 		outbound_rx_fallback,
 		senders1,
 		None,
+		Default::default(),
+		Default::default(),
 	);
 
 	let (outbound_tx2, outbound_rx2) = tracing_unbounded("outbound-request", 1000);
@@ -737,6 +765,8 @@ async fn fallback_request_old_peer_sends() {
 		outbound_rx2,
 		senders2,
 		None,
+		Default::default(),
+		Default::default(),
 	);
 
 	tokio::spawn(protocol1.run());
@@ -853,6 +883,8 @@ async fn old_protocol_supported_but_no_fallback_provided() {
 		outbound_rx1,
 		senders1.clone(),
 		None,
+		Default::default(),
+		Default::default(),
 	);
 
 	let (tx_fallback, _rx_fallback) = async_channel::bounded(4);
@@ -864,6 +896,8 @@ async fn old_protocol_supported_but_no_fallback_provided() {
 		outbound_rx_fallback,
 		senders1,
 		None,
+		Default::default(),
+		Default::default(),
 	);
 
 	let (outbound_tx2, outbound_rx2) = tracing_unbounded("outbound-request", 1000);
@@ -878,6 +912,8 @@ async fn old_protocol_supported_but_no_fallback_provided() {
 		outbound_rx2,
 		senders2,
 		None,
+		Default::default(),
+		Default::default(),
 	);
 
 	tokio::spawn(protocol1.run());
@@ -19,14 +19,14 @@
 //! Mocked components for tests.
 
 use crate::{
-	peer_store::{PeerStoreProvider, ProtocolHandle},
+	peer_store::{Ban, BanTarget, PeerStoreProvider, ProtocolHandle},
 	ReputationChange,
 };
 
 use sc_network_common::role::ObservedRole;
-use sc_network_types::PeerId;
+use sc_network_types::{multiaddr::Multiaddr, PeerId};
 
-use std::{collections::HashSet, sync::Arc};
+use std::{collections::HashSet, sync::Arc, time::SystemTime};
 
 /// No-op `PeerStore`.
 #[derive(Debug)]
@@ -70,4 +70,20 @@ impl PeerStoreProvider for MockPeerStore {
 	fn add_known_peer(&self, _peer_id: PeerId) {
 		unimplemented!()
 	}
+
+	fn ban(&self, _target: BanTarget, _until: Option<SystemTime>) {
+		unimplemented!()
+	}
+
+	fn unban(&self, _target: &BanTarget) -> bool {
+		unimplemented!()
+	}
+
+	fn bans(&self) -> Vec<Ban> {
+		Vec::new()
+	}
+
+	fn note_connection(&self, _peer_id: PeerId, _address: Multiaddr, _outbound: bool) {
+		// Make sure not to fail.
+	}
 }
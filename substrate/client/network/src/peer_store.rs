@@ -18,21 +18,32 @@
 
 //! [`PeerStore`] manages peer reputations and provides connection candidates to
 //! [`crate::protocol_controller::ProtocolController`].
+//!
+//! Reputations, manual bans and the addresses of peers we managed to dial can be persisted to the
+//! node's database directory with [`PeerStorePersistence`], so that misbehaving peers stay banned
+//! across restarts and bootstrap can start from known-good peers.
 
 use crate::service::{metrics::PeerStoreMetrics, traits::PeerStore as PeerStoreT};
 
+use ip_network::IpNetwork;
 use libp2p::PeerId;
 use log::trace;
 use parking_lot::Mutex;
 use partial_sort::PartialSort;
 use prometheus_endpoint::Registry;
 use sc_network_common::{role::ObservedRole, types::ReputationChange};
+use sc_network_types::multiaddr::{Multiaddr, Protocol};
+use serde::{Deserialize, Serialize};
 use std::{
 	cmp::{Ord, Ordering, PartialOrd},
 	collections::{hash_map::Entry, HashMap, HashSet},
-	fmt::Debug,
+	fmt::{self, Debug},
+	fs, io,
+	net::IpAddr,
+	path::{Path, PathBuf},
+	str::FromStr,
 	sync::Arc,
-	time::{Duration, Instant},
+	time::{Duration, Instant, SystemTime, UNIX_EPOCH},
 };
 use wasm_timer::Delay;
 
@@ -59,6 +70,87 @@ const INVERSE_DECREMENT: i32 = 200;
 /// Amount of time between the moment we last updated the [`PeerStore`] entry and the moment we
 /// remove it, once the reputation value reaches 0.
 const FORGET_AFTER: Duration = Duration::from_secs(3600);
+/// Name of the file in the network configuration directory the peer store state is persisted to.
+const PERSISTENCE_FILE: &str = "peers.json";
+/// Interval between writes of the persisted state, unless the ban list changes in the meantime.
+const PERSIST_INTERVAL: Duration = Duration::from_secs(60);
+/// Maximum number of addresses remembered per peer.
+const MAX_ADDRESSES_PER_PEER: usize = 4;
+/// Maximum number of peers whose addresses are remembered.
+const MAX_KNOWN_ADDRESS_PEERS: usize = 1000;
+
+/// Target of a manual ban.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BanTarget {
+	/// A single peer.
+	Peer(sc_network_types::PeerId),
+	/// All peers whose last connection was established with an address in the range.
+	Range(IpNetwork),
+}
+
+impl BanTarget {
+	fn matches(&self, peer_id: &sc_network_types::PeerId, ip: Option<&IpAddr>) -> bool {
+		match self {
+			Self::Peer(banned) => banned == peer_id,
+			Self::Range(network) => ip.map_or(false, |ip| network.contains(*ip)),
+		}
+	}
+}
+
+impl fmt::Display for BanTarget {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::Peer(peer_id) => fmt::Display::fmt(peer_id, f),
+			Self::Range(network) => fmt::Display::fmt(network, f),
+		}
+	}
+}
+
+/// Error returned when parsing a [`BanTarget`] fails.
+#[derive(Debug, thiserror::Error)]
+#[error("`{0}` is neither a peer ID nor an IP address or range")]
+pub struct InvalidBanTarget(String);
+
+impl FromStr for BanTarget {
+	type Err = InvalidBanTarget;
+
+	/// Parse a peer ID, an IP range in CIDR notation or a single IP address.
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		if let Ok(peer_id) = s.parse::<sc_network_types::PeerId>() {
+			return Ok(Self::Peer(peer_id))
+		}
+		if let Ok(network) = s.parse::<IpNetwork>() {
+			return Ok(Self::Range(network))
+		}
+
+		s.parse::<IpAddr>()
+			.ok()
+			.and_then(|ip| IpNetwork::new(ip, if ip.is_ipv4() { 32 } else { 128 }).ok())
+			.map(Self::Range)
+			.ok_or_else(|| InvalidBanTarget(s.to_owned()))
+	}
+}
+
+/// Reason a ban is in place.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BanReason {
+	/// Banned with [`PeerStoreProvider::ban`], until the given time if any.
+	Manual {
+		/// Time at which the ban expires. `None` means the ban never expires.
+		until: Option<SystemTime>,
+	},
+	/// The reputation of the peer is below [`BANNED_THRESHOLD`].
+	Reputation(i32),
+}
+
+/// Ban reported by [`PeerStoreProvider::bans`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Ban {
+	/// Banned peer or IP range.
+	pub target: BanTarget,
+	/// Why the target is banned.
+	pub reason: BanReason,
+}
 
 /// Trait describing the required functionality from a `Peerset` handle.
 pub trait ProtocolHandle: Debug + Send + Sync {
@@ -98,6 +190,42 @@ pub trait PeerStoreProvider: Debug + Send + Sync {
 
 	/// Add known peer.
 	fn add_known_peer(&self, peer_id: sc_network_types::PeerId);
+
+	/// Ban a peer or an IP range until `until`, or indefinitely if `None`.
+	///
+	/// Connected peers matching the target are disconnected from all protocols.
+	fn ban(&self, _target: BanTarget, _until: Option<SystemTime>) {}
+
+	/// Lift a ban, returning `false` if the target wasn't banned.
+	///
+	/// Lifting the ban of a peer banned because of its reputation resets the reputation to zero.
+	fn unban(&self, _target: &BanTarget) -> bool {
+		false
+	}
+
+	/// Get the bans currently in place.
+	fn bans(&self) -> Vec<Ban> {
+		Vec::new()
+	}
+
+	/// Check whether the IP address of `address` is in a banned range.
+	///
+	/// Used to refuse connections to and from banned ranges before they are established.
+	fn is_address_banned(&self, _address: &Multiaddr) -> bool {
+		false
+	}
+
+	/// Record an established connection with `peer_id` at `address`.
+	///
+	/// The remote IP address is matched against banned IP ranges and the addresses of outbound
+	/// connections are remembered to bootstrap from after a restart.
+	fn note_connection(
+		&self,
+		_peer_id: sc_network_types::PeerId,
+		_address: Multiaddr,
+		_outbound: bool,
+	) {
+	}
 }
 
 /// Actual implementation of peer reputations and connection candidates provider.
@@ -154,6 +282,31 @@ impl PeerStoreProvider for PeerStoreHandle {
 	fn add_known_peer(&self, peer_id: sc_network_types::PeerId) {
 		self.inner.lock().add_known_peer(peer_id.into());
 	}
+
+	fn ban(&self, target: BanTarget, until: Option<SystemTime>) {
+		self.inner.lock().ban(target, until);
+	}
+
+	fn unban(&self, target: &BanTarget) -> bool {
+		self.inner.lock().unban(target)
+	}
+
+	fn bans(&self) -> Vec<Ban> {
+		self.inner.lock().bans()
+	}
+
+	fn is_address_banned(&self, address: &Multiaddr) -> bool {
+		self.inner.lock().book.is_address_banned(address)
+	}
+
+	fn note_connection(
+		&self,
+		peer_id: sc_network_types::PeerId,
+		address: Multiaddr,
+		outbound: bool,
+	) {
+		self.inner.lock().note_connection(peer_id, address, outbound);
+	}
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -234,11 +387,13 @@ struct PeerStoreInner {
 	peers: HashMap<PeerId, PeerInfo>,
 	protocols: Vec<Arc<dyn ProtocolHandle>>,
 	metrics: Option<PeerStoreMetrics>,
+	book: PeerBook,
 }
 
 impl PeerStoreInner {
 	fn is_banned(&self, peer_id: &PeerId) -> bool {
-		self.peers.get(peer_id).map_or(false, |info| info.is_banned())
+		self.peers.get(peer_id).map_or(false, |info| info.is_banned()) ||
+			self.book.is_banned(&peer_id.into())
 	}
 
 	fn register_protocol(&mut self, protocol_handle: Arc<dyn ProtocolHandle>) {
@@ -342,7 +497,10 @@ impl PeerStoreInner {
 			.peers
 			.iter()
 			.filter_map(|(peer_id, info)| {
-				(!info.is_banned() && !ignored.contains(peer_id)).then_some((*peer_id, *info))
+				(!info.is_banned() &&
+					!ignored.contains(peer_id) &&
+					!self.book.is_banned(&peer_id.into()))
+				.then_some((*peer_id, *info))
 			})
 			.collect::<Vec<_>>();
 		let count = std::cmp::min(count, candidates.len());
@@ -372,6 +530,8 @@ impl PeerStoreInner {
 
 			info.reputation != 0 || info.last_updated + FORGET_AFTER > now
 		});
+		let peers = &self.peers;
+		self.book.progress_time(|peer_id| peers.contains_key(&PeerId::from(*peer_id)));
 
 		if let Some(metrics) = &self.metrics {
 			metrics.num_discovered.set(self.peers.len() as u64);
@@ -394,6 +554,59 @@ impl PeerStoreInner {
 			},
 		}
 	}
+
+	fn ban(&mut self, target: BanTarget, until: Option<SystemTime>) {
+		for peer_id in self.book.ban(target, until) {
+			self.protocols.iter().for_each(|handle| handle.disconnect_peer(peer_id));
+		}
+	}
+
+	fn unban(&mut self, target: &BanTarget) -> bool {
+		let mut unbanned = self.book.unban(target);
+
+		if let BanTarget::Peer(peer_id) = target {
+			let peer_id = PeerId::from(*peer_id);
+			if let Some(info) = self.peers.get_mut(&peer_id).filter(|info| info.is_banned()) {
+				info.reputation = 0;
+				info.bump_last_updated();
+				unbanned = true;
+			}
+		}
+
+		unbanned
+	}
+
+	fn bans(&self) -> Vec<Ban> {
+		self.book
+			.bans()
+			.chain(self.peers.iter().filter(|(_, info)| info.is_banned()).map(|(peer_id, info)| {
+				Ban {
+					target: BanTarget::Peer(peer_id.into()),
+					reason: BanReason::Reputation(info.reputation),
+				}
+			}))
+			.collect()
+	}
+
+	fn note_connection(
+		&mut self,
+		peer_id: sc_network_types::PeerId,
+		address: Multiaddr,
+		outbound: bool,
+	) {
+		if self.book.note_connection(peer_id, address, outbound) {
+			log::debug!(target: LOG_TARGET, "Connected to banned peer {peer_id}, disconnecting.");
+			self.protocols.iter().for_each(|handle| handle.disconnect_peer(peer_id));
+		}
+	}
+
+	fn persistence_snapshot(&mut self) -> Option<(PathBuf, PersistedState)> {
+		self.book.snapshot(
+			self.peers
+				.iter()
+				.map(|(peer_id, info)| (sc_network_types::PeerId::from(peer_id), info.reputation)),
+		)
+	}
 }
 
 /// Worker part of [`PeerStoreHandle`]
@@ -424,10 +637,30 @@ impl PeerStore {
 					.collect(),
 				protocols: Vec::new(),
 				metrics,
+				book: PeerBook::default(),
 			})),
 		}
 	}
 
+	/// Restore the state persisted by a previous run and keep persisting it to the same location.
+	pub fn with_persistence(self, persistence: PeerStorePersistence) -> Self {
+		self.restore(persistence);
+		self
+	}
+
+	fn restore(&self, persistence: PeerStorePersistence) {
+		let mut inner = self.inner.lock();
+		let offline_secs = persistence.offline_secs();
+		let (book, reputations) = PeerBook::restore(persistence);
+
+		for (peer_id, reputation) in reputations {
+			let info = inner.peers.entry(peer_id.into()).or_default();
+			info.reputation = reputation;
+			info.decay_reputation(offline_secs);
+		}
+		inner.book = book;
+	}
+
 	/// Get `PeerStoreHandle`.
 	pub fn handle(&self) -> PeerStoreHandle {
 		PeerStoreHandle { inner: self.inner.clone() }
@@ -449,18 +682,451 @@ impl PeerStore {
 				elapsed_now.as_secs() - elapsed_latest.as_secs()
 			};
 
-			self.inner.lock().progress_time(seconds_passed);
+			let snapshot = {
+				let mut inner = self.inner.lock();
+				inner.progress_time(seconds_passed);
+				inner.persistence_snapshot()
+			};
+			if let Some((path, state)) = snapshot {
+				persist(path, state).await;
+			}
+
 			let _ = Delay::new(Duration::from_secs(1)).await;
 		}
 	}
 }
 
+/// Addresses at which a peer was successfully dialed.
+#[derive(Debug, Clone)]
+struct KnownAddresses {
+	/// Addresses, most recently used first.
+	addresses: Vec<Multiaddr>,
+	/// Time of the last connection to the peer.
+	last_seen: SystemTime,
+}
+
+/// Ban list and address book shared by the `libp2p` and `litep2p` peer stores.
+#[derive(Debug, Default)]
+pub(crate) struct PeerBook {
+	/// Manual bans with their expiry time.
+	bans: HashMap<BanTarget, Option<SystemTime>>,
+	/// Remote IP address of the latest connection with each peer.
+	remote_ips: HashMap<sc_network_types::PeerId, IpAddr>,
+	/// Known-good addresses of peers we managed to dial.
+	addresses: HashMap<sc_network_types::PeerId, KnownAddresses>,
+	/// File the state is persisted to, if any.
+	path: Option<PathBuf>,
+	/// Whether the ban list changed since the state was last persisted.
+	dirty: bool,
+	/// Time the state was last persisted.
+	last_persisted: Option<Instant>,
+}
+
+impl PeerBook {
+	/// Restore the ban list and the address book from `persistence`.
+	///
+	/// Returns the book together with the persisted reputations, which are not decayed yet.
+	pub(crate) fn restore(
+		persistence: PeerStorePersistence,
+	) -> (Self, Vec<(sc_network_types::PeerId, i32)>) {
+		let book = Self {
+			bans: persistence.bans,
+			addresses: persistence.addresses,
+			path: Some(persistence.path),
+			..Default::default()
+		};
+
+		(book, persistence.reputations)
+	}
+
+	/// Check whether the peer or the IP address of its latest connection is banned.
+	pub(crate) fn is_banned(&self, peer_id: &sc_network_types::PeerId) -> bool {
+		let now = SystemTime::now();
+		let ip = self.remote_ips.get(peer_id);
+
+		self.bans.iter().any(|(target, until)| {
+			until.map_or(true, |until| until > now) && target.matches(peer_id, ip)
+		})
+	}
+
+	/// Check whether the IP address of `address` is in a banned range.
+	pub(crate) fn is_address_banned(&self, address: &Multiaddr) -> bool {
+		let Some(ip) = remote_ip(address) else { return false };
+		let now = SystemTime::now();
+
+		self.bans.iter().any(|(target, until)| {
+			until.map_or(true, |until| until > now) &&
+				matches!(target, BanTarget::Range(network) if network.contains(ip))
+		})
+	}
+
+	/// Ban `target`, returning the peers that must be disconnected.
+	pub(crate) fn ban(
+		&mut self,
+		target: BanTarget,
+		until: Option<SystemTime>,
+	) -> Vec<sc_network_types::PeerId> {
+		log::info!(
+			target: LOG_TARGET,
+			"Banning {target} {}.",
+			until.map_or("indefinitely".to_string(), |until| format!(
+				"for {}s",
+				until.duration_since(SystemTime::now()).unwrap_or_default().as_secs()
+			)),
+		);
+
+		self.bans.insert(target, until);
+		self.dirty = true;
+
+		match target {
+			BanTarget::Peer(peer_id) => vec![peer_id],
+			BanTarget::Range(_) => self
+				.remote_ips
+				.iter()
+				.filter(|(peer_id, ip)| target.matches(peer_id, Some(ip)))
+				.map(|(peer_id, _)| *peer_id)
+				.collect(),
+		}
+	}
+
+	/// Lift the manual ban of `target`, returning `false` if there was none.
+	pub(crate) fn unban(&mut self, target: &BanTarget) -> bool {
+		let unbanned = self.bans.remove(target).is_some();
+		if unbanned {
+			log::info!(target: LOG_TARGET, "Unbanned {target}.");
+			self.dirty = true;
+		}
+
+		unbanned
+	}
+
+	/// Get manual bans.
+	pub(crate) fn bans(&self) -> impl Iterator<Item = Ban> + '_ {
+		self.bans.iter().map(|(target, until)| Ban {
+			target: *target,
+			reason: BanReason::Manual { until: *until },
+		})
+	}
+
+	/// Record an established connection, returning `true` if the peer is banned.
+	pub(crate) fn note_connection(
+		&mut self,
+		peer_id: sc_network_types::PeerId,
+		mut address: Multiaddr,
+		outbound: bool,
+	) -> bool {
+		if let Some(ip) = remote_ip(&address) {
+			self.remote_ips.insert(peer_id, ip);
+		}
+
+		if self.is_banned(&peer_id) {
+			self.addresses.remove(&peer_id);
+			return true
+		}
+
+		// Only dialed addresses are reachable, the port of an inbound connection is ephemeral.
+		if !outbound {
+			return false
+		}
+		if matches!(address.iter().last(), Some(Protocol::P2p(_))) {
+			address.pop();
+		}
+
+		let known = self.addresses.entry(peer_id).or_insert_with(|| KnownAddresses {
+			addresses: Vec::new(),
+			last_seen: SystemTime::now(),
+		});
+		known.addresses.retain(|known| known != &address);
+		known.addresses.insert(0, address);
+		known.addresses.truncate(MAX_ADDRESSES_PER_PEER);
+		known.last_seen = SystemTime::now();
+
+		if self.addresses.len() > MAX_KNOWN_ADDRESS_PEERS {
+			let oldest = self
+				.addresses
+				.iter()
+				.min_by_key(|(_, known)| known.last_seen)
+				.map(|(peer_id, _)| *peer_id);
+			if let Some(peer_id) = oldest {
+				self.addresses.remove(&peer_id);
+			}
+		}
+
+		false
+	}
+
+	/// Drop expired bans and the remote IP addresses of peers that are no longer tracked.
+	pub(crate) fn progress_time(&mut self, is_tracked: impl Fn(&sc_network_types::PeerId) -> bool) {
+		let now = SystemTime::now();
+		self.bans.retain(|target, until| {
+			let expired = until.map_or(false, |until| until <= now);
+			if expired {
+				log::info!(target: LOG_TARGET, "Ban of {target} expired.");
+				self.dirty = true;
+			}
+
+			!expired
+		});
+		self.remote_ips.retain(|peer_id, _| is_tracked(peer_id));
+	}
+
+	/// Take a snapshot of the state if it is due to be persisted.
+	pub(crate) fn snapshot(
+		&mut self,
+		reputations: impl Iterator<Item = (sc_network_types::PeerId, i32)>,
+	) -> Option<(PathBuf, PersistedState)> {
+		let path = self.path.clone()?;
+		if !self.dirty && self.last_persisted.map_or(false, |at| at.elapsed() < PERSIST_INTERVAL) {
+			return None
+		}
+		self.dirty = false;
+		self.last_persisted = Some(Instant::now());
+
+		let mut peers = reputations
+			.filter(|(_, reputation)| *reputation != 0)
+			.map(|(peer_id, reputation)| {
+				(
+					peer_id,
+					PersistedPeer {
+						peer_id: peer_id.to_base58(),
+						reputation,
+						..Default::default()
+					},
+				)
+			})
+			.collect::<HashMap<_, _>>();
+		for (peer_id, known) in &self.addresses {
+			let peer = persisted_peer(&mut peers, *peer_id);
+			// Only peers in good standing are worth bootstrapping from.
+			if peer.reputation >= 0 {
+				peer.addresses = known.addresses.iter().map(ToString::to_string).collect();
+				peer.last_seen = Some(unix_secs(known.last_seen));
+			}
+		}
+
+		let state = PersistedState {
+			saved_at: unix_secs(SystemTime::now()),
+			peers: peers.into_values().collect(),
+			bans: self
+				.bans
+				.iter()
+				.map(|(target, until)| PersistedBan {
+					target: target.to_string(),
+					until: until.map(unix_secs),
+				})
+				.collect(),
+		};
+
+		Some((path, state))
+	}
+}
+
+/// IP address of `address`, if it has one.
+fn remote_ip(address: &Multiaddr) -> Option<IpAddr> {
+	address.iter().find_map(|protocol| match protocol {
+		Protocol::Ip4(ip) => Some(IpAddr::V4(ip)),
+		Protocol::Ip6(ip) => Some(IpAddr::V6(ip)),
+		_ => None,
+	})
+}
+
+fn persisted_peer(
+	peers: &mut HashMap<sc_network_types::PeerId, PersistedPeer>,
+	peer_id: sc_network_types::PeerId,
+) -> &mut PersistedPeer {
+	peers
+		.entry(peer_id)
+		.or_insert_with(|| PersistedPeer { peer_id: peer_id.to_base58(), ..Default::default() })
+}
+
+/// Seconds elapsed since the UNIX epoch at `time`.
+fn unix_secs(time: SystemTime) -> u64 {
+	time.duration_since(UNIX_EPOCH).map_or(0, |elapsed| elapsed.as_secs())
+}
+
+/// Peer store state as written to disk.
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct PersistedState {
+	/// Seconds since the UNIX epoch at which the state was written.
+	saved_at: u64,
+	/// Peers with a non-zero reputation or known addresses.
+	#[serde(default)]
+	peers: Vec<PersistedPeer>,
+	/// Manual bans.
+	#[serde(default)]
+	bans: Vec<PersistedBan>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PersistedPeer {
+	/// Base58-encoded peer ID.
+	peer_id: String,
+	#[serde(default)]
+	reputation: i32,
+	/// Addresses the peer was dialed at, most recently used first.
+	#[serde(default)]
+	addresses: Vec<String>,
+	/// Seconds since the UNIX epoch at which the peer was last connected to.
+	#[serde(default)]
+	last_seen: Option<u64>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PersistedBan {
+	/// Peer ID or IP range in CIDR notation.
+	target: String,
+	/// Seconds since the UNIX epoch at which the ban expires.
+	#[serde(default)]
+	until: Option<u64>,
+}
+
+/// Write `state` to `path` on a blocking thread, so that disk IO doesn't stall the event loop.
+pub(crate) async fn persist(path: PathBuf, state: PersistedState) {
+	if let Err(err) = tokio::task::spawn_blocking(move || write_state(&path, &state)).await {
+		log::warn!(target: LOG_TARGET, "Failed to persist peer store state: {err}");
+	}
+}
+
+/// Write `state` to `path`, replacing the previous state atomically.
+fn write_state(path: &Path, state: &PersistedState) {
+	let write = || -> io::Result<()> {
+		if let Some(dir) = path.parent() {
+			fs::create_dir_all(dir)?;
+		}
+		let tmp_path = path.with_extension("json.tmp");
+		fs::write(&tmp_path, serde_json::to_vec_pretty(state)?)?;
+		fs::rename(tmp_path, path)
+	};
+
+	if let Err(err) = write() {
+		log::warn!(
+			target: LOG_TARGET,
+			"Failed to persist peer store state to {}: {err}",
+			path.display(),
+		);
+	}
+}
+
+/// Peer store state persisted in the node's database directory by a previous run.
+///
+/// Pass it to [`PeerStore::with_persistence`] to restore reputations and bans, and keep persisting
+/// them to the same location.
+#[derive(Debug)]
+pub struct PeerStorePersistence {
+	path: PathBuf,
+	offline: Duration,
+	reputations: Vec<(sc_network_types::PeerId, i32)>,
+	addresses: HashMap<sc_network_types::PeerId, KnownAddresses>,
+	bans: HashMap<BanTarget, Option<SystemTime>>,
+}
+
+impl PeerStorePersistence {
+	/// Load the state persisted in `dir`.
+	///
+	/// A missing or unreadable file results in an empty state.
+	pub fn load(dir: &Path) -> Self {
+		let path = dir.join(PERSISTENCE_FILE);
+		let state = match fs::read(&path) {
+			Ok(bytes) => serde_json::from_slice(&bytes).unwrap_or_else(|err| {
+				log::warn!(
+					target: LOG_TARGET,
+					"Failed to decode peer store state at {}, starting afresh: {err}",
+					path.display(),
+				);
+				PersistedState::default()
+			}),
+			Err(err) if err.kind() == io::ErrorKind::NotFound => PersistedState::default(),
+			Err(err) => {
+				log::warn!(
+					target: LOG_TARGET,
+					"Failed to read peer store state at {}, starting afresh: {err}",
+					path.display(),
+				);
+				PersistedState::default()
+			},
+		};
+
+		Self::from_state(path, state, SystemTime::now())
+	}
+
+	fn from_state(path: PathBuf, state: PersistedState, now: SystemTime) -> Self {
+		let saved_at = UNIX_EPOCH + Duration::from_secs(state.saved_at);
+		let mut reputations = Vec::new();
+		let mut addresses = HashMap::new();
+
+		for peer in state.peers {
+			let Ok(peer_id) = peer.peer_id.parse::<sc_network_types::PeerId>() else {
+				log::debug!(target: LOG_TARGET, "Ignoring invalid persisted peer {}", peer.peer_id);
+				continue
+			};
+
+			if peer.reputation != 0 {
+				reputations.push((peer_id, peer.reputation));
+			}
+
+			let known = peer
+				.addresses
+				.iter()
+				.filter_map(|address| address.parse::<Multiaddr>().ok())
+				.take(MAX_ADDRESSES_PER_PEER)
+				.collect::<Vec<_>>();
+			if !known.is_empty() {
+				let last_seen = peer
+					.last_seen
+					.map_or(saved_at, |last_seen| UNIX_EPOCH + Duration::from_secs(last_seen));
+				addresses.insert(peer_id, KnownAddresses { addresses: known, last_seen });
+			}
+		}
+
+		let bans = state
+			.bans
+			.into_iter()
+			.filter_map(|ban| {
+				let target = ban.target.parse::<BanTarget>().ok()?;
+				let until = ban.until.map(|until| UNIX_EPOCH + Duration::from_secs(until));
+
+				until.map_or(true, |until| until > now).then_some((target, until))
+			})
+			.collect();
+
+		Self {
+			path,
+			offline: now.duration_since(saved_at).unwrap_or_default(),
+			reputations,
+			addresses,
+			bans,
+		}
+	}
+
+	/// Number of seconds the node was offline, used to decay the persisted reputations.
+	pub(crate) fn offline_secs(&self) -> u64 {
+		self.offline.as_secs()
+	}
+
+	/// Addresses of the peers that could be dialed during previous runs.
+	pub fn known_addresses(&self) -> Vec<(sc_network_types::PeerId, Multiaddr)> {
+		self.addresses
+			.iter()
+			.flat_map(|(peer_id, known)| {
+				known.addresses.iter().map(move |address| (*peer_id, address.clone()))
+			})
+			.collect()
+	}
+}
+
 #[async_trait::async_trait]
 impl PeerStoreT for PeerStore {
 	fn handle(&self) -> Arc<dyn PeerStoreProvider> {
 		Arc::new(self.handle())
 	}
 
+	fn restore(&self, persistence: PeerStorePersistence) {
+		self.restore(persistence);
+	}
+
 	async fn run(self) {
 		self.run().await;
 	}
@@ -468,7 +1134,12 @@ impl PeerStoreT for PeerStore {
 
 #[cfg(test)]
 mod tests {
-	use super::{PeerInfo, PeerStore, PeerStoreProvider};
+	use super::{
+		write_state, BanReason, BanTarget, PeerInfo, PeerStore, PeerStorePersistence,
+		PeerStoreProvider,
+	};
+	use sc_network_types::multiaddr::Multiaddr;
+	use std::time::{Duration, SystemTime};
 
 	#[test]
 	fn decaying_zero_reputation_yields_zero() {
@@ -570,4 +1241,105 @@ mod tests {
 		assert_eq!(metrics.num_discovered.get(), 3);
 		assert_eq!(metrics.num_banned_peers.get(), 2);
 	}
+
+	#[test]
+	fn manual_bans_can_be_lifted() {
+		let peer = sc_network_types::PeerId::random();
+		let handle = PeerStore::new(vec![peer.into()], None).handle();
+
+		handle.ban(BanTarget::Peer(peer), None);
+		assert!(handle.is_banned(&peer));
+		assert!(handle.outgoing_candidates(1, Default::default()).is_empty());
+		assert_eq!(handle.bans()[0].reason, BanReason::Manual { until: None });
+
+		assert!(handle.unban(&BanTarget::Peer(peer)));
+		assert!(!handle.is_banned(&peer));
+		assert!(!handle.unban(&BanTarget::Peer(peer)));
+	}
+
+	#[test]
+	fn ip_range_bans_match_remote_addresses() {
+		let inside = sc_network_types::PeerId::random();
+		let outside = sc_network_types::PeerId::random();
+		let late = sc_network_types::PeerId::random();
+		let handle = PeerStore::new(vec![], None).handle();
+
+		handle.note_connection(inside, "/ip4/198.51.100.19/tcp/30333".parse().unwrap(), false);
+		handle.note_connection(outside, "/ip4/203.0.113.7/tcp/30333".parse().unwrap(), false);
+		handle.ban("198.51.100.0/24".parse().unwrap(), None);
+
+		assert!(handle.is_banned(&inside));
+		assert!(!handle.is_banned(&outside));
+
+		handle.note_connection(late, "/ip4/198.51.100.20/tcp/30333".parse().unwrap(), true);
+		assert!(handle.is_banned(&late));
+
+		assert!(handle.unban(&"198.51.100.0/24".parse().unwrap()));
+		assert!(!handle.is_banned(&inside));
+	}
+
+	#[test]
+	fn expired_bans_are_dropped() {
+		let peer = sc_network_types::PeerId::random();
+		let handle = PeerStore::new(vec![], None).handle();
+
+		handle.ban(BanTarget::Peer(peer), Some(SystemTime::now() - Duration::from_secs(1)));
+		assert!(!handle.is_banned(&peer));
+
+		handle.inner.lock().progress_time(1);
+		assert!(handle.bans().is_empty());
+	}
+
+	#[test]
+	fn unbanning_resets_reputation() {
+		let peer = sc_network_types::PeerId::random();
+		let handle = PeerStore::new(vec![], None).handle();
+
+		handle.report_peer(
+			peer,
+			sc_network_common::types::ReputationChange { value: i32::MIN, reason: "test".into() },
+		);
+		assert!(handle.is_banned(&peer));
+		assert_eq!(handle.bans()[0].reason, BanReason::Reputation(i32::MIN));
+
+		assert!(handle.unban(&BanTarget::Peer(peer)));
+		assert!(!handle.is_banned(&peer));
+		assert_eq!(handle.peer_reputation(&peer), 0);
+	}
+
+	#[test]
+	fn state_survives_restart() {
+		let dir = tempfile::tempdir().unwrap();
+		let misbehaving = sc_network_types::PeerId::random();
+		let banned = sc_network_types::PeerId::random();
+		let good = sc_network_types::PeerId::random();
+		let address: Multiaddr = "/ip4/203.0.113.5/tcp/30333".parse().unwrap();
+
+		let peer_store =
+			PeerStore::new(vec![], None).with_persistence(PeerStorePersistence::load(dir.path()));
+		let handle = peer_store.handle();
+		handle.report_peer(
+			misbehaving,
+			sc_network_common::types::ReputationChange { value: -1000, reason: "test".into() },
+		);
+		handle.ban(BanTarget::Peer(banned), None);
+		handle.ban("198.51.100.0/24".parse().unwrap(), None);
+		handle.note_connection(good, format!("{address}/p2p/{good}").parse().unwrap(), true);
+
+		let (path, state) = peer_store.inner.lock().persistence_snapshot().unwrap();
+		write_state(&path, &state);
+		// Nothing changed since, so nothing is due to be persisted.
+		assert!(peer_store.inner.lock().persistence_snapshot().is_none());
+
+		let persistence = PeerStorePersistence::load(dir.path());
+		assert_eq!(persistence.known_addresses(), vec![(good, address)]);
+
+		let handle = PeerStore::new(vec![], None).with_persistence(persistence).handle();
+		assert!(handle.is_banned(&banned));
+		assert!(handle.peer_reputation(&misbehaving) < 0);
+
+		let in_range = sc_network_types::PeerId::random();
+		handle.note_connection(in_range, "/ip4/198.51.100.1/tcp/30333".parse().unwrap(), false);
+		assert!(handle.is_banned(&in_range));
+	}
 }
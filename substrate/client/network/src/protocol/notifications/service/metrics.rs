@@ -18,6 +18,8 @@
 
 use crate::{service::metrics::NotificationMetrics, types::ProtocolName};
 
+use sc_network_types::PeerId;
+
 /// Register opened substream to Prometheus.
 pub fn register_substream_opened(metrics: &Option<NotificationMetrics>, protocol: &ProtocolName) {
 	if let Some(metrics) = metrics {
@@ -36,10 +38,11 @@ pub fn register_substream_closed(metrics: &Option<NotificationMetrics>, protocol
 pub fn register_notification_sent(
 	metrics: &Option<std::sync::Arc<NotificationMetrics>>,
 	protocol: &ProtocolName,
+	peer: &PeerId,
 	size: usize,
 ) {
 	if let Some(metrics) = metrics {
-		metrics.register_notification_sent(protocol, size);
+		metrics.register_notification_sent(protocol, peer, size);
 	}
 }
 
@@ -47,9 +50,10 @@ pub fn register_notification_sent(
 pub fn register_notification_received(
 	metrics: &Option<NotificationMetrics>,
 	protocol: &ProtocolName,
+	peer: &PeerId,
 	size: usize,
 ) {
 	if let Some(metrics) = metrics {
-		metrics.register_notification_received(protocol, size);
+		metrics.register_notification_received(protocol, peer, size);
 	}
 }
@@ -69,7 +69,12 @@ impl MessageSink for NotificationSink {
 	fn send_sync_notification(&self, notification: Vec<u8>) {
 		let sink = self.lock();
 
-		metrics::register_notification_sent(sink.0.metrics(), &sink.1, notification.len());
+		metrics::register_notification_sent(
+			sink.0.metrics(),
+			&sink.1,
+			&(*sink.0.peer_id()).into(),
+			notification.len(),
+		);
 		sink.0.send_sync_notification(notification);
 	}
 
@@ -90,7 +95,12 @@ impl MessageSink for NotificationSink {
 			.map_err(|_| error::Error::ConnectionClosed)?;
 
 		permit.send(notification).map_err(|_| error::Error::ChannelClosed).inspect(|_| {
-			metrics::register_notification_sent(sink.0.metrics(), &sink.1, notification_len);
+			metrics::register_notification_sent(
+				sink.0.metrics(),
+				&sink.1,
+				&(*sink.0.peer_id()).into(),
+				notification_len,
+			);
 		})
 	}
 }
@@ -237,6 +247,7 @@ impl NotificationService for NotificationHandle {
 			metrics::register_notification_sent(
 				info.sink.metrics(),
 				&self.protocol,
+				peer,
 				notification.len(),
 			);
 
@@ -266,6 +277,7 @@ impl NotificationService for NotificationHandle {
 				metrics::register_notification_sent(
 					sink.metrics(),
 					&self.protocol,
+					peer,
 					notification_len,
 				);
 			})
@@ -577,7 +589,12 @@ impl ProtocolHandle {
 		peer: PeerId,
 		notification: Vec<u8>,
 	) -> Result<(), ()> {
-		metrics::register_notification_received(&self.metrics, &self.protocol, notification.len());
+		metrics::register_notification_received(
+			&self.metrics,
+			&self.protocol,
+			&peer.into(),
+			notification.len(),
+		);
 
 		let mut subscribers = self.subscribers.lock();
 		log::trace!(target: LOG_TARGET, "{}: notification received from {peer:?}", self.protocol);
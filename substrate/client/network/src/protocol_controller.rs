@@ -857,7 +857,7 @@ impl ProtocolController {
 mod tests {
 	use super::*;
 	use crate::{
-		peer_store::{PeerStoreProvider, ProtocolHandle as ProtocolHandleT},
+		peer_store::{Ban, BanTarget, PeerStoreProvider, ProtocolHandle as ProtocolHandleT},
 		ReputationChange,
 	};
 	use libp2p::PeerId;
@@ -879,6 +879,10 @@ mod tests {
 			fn peer_role(&self, peer_id: &sc_network_types::PeerId) -> Option<ObservedRole>;
 			fn outgoing_candidates(&self, count: usize, ignored: HashSet<sc_network_types::PeerId>) -> Vec<sc_network_types::PeerId>;
 			fn add_known_peer(&self, peer_id: sc_network_types::PeerId);
+			fn ban(&self, target: BanTarget, until: Option<std::time::SystemTime>);
+			fn unban(&self, target: &BanTarget) -> bool;
+			fn bans(&self) -> Vec<Ban>;
+			fn note_connection(&self, peer_id: sc_network_types::PeerId, address: sc_network_types::multiaddr::Multiaddr, outbound: bool);
 		}
 	}
 
@@ -35,6 +35,7 @@
 //! is used to handle incoming requests.
 
 use crate::{
+	bandwidth::{BandwidthMeter, InboundRequestLimiter, InboundRequestLimits},
 	peer_store::{PeerStoreProvider, BANNED_THRESHOLD},
 	service::traits::RequestResponseConfig as RequestResponseConfigT,
 	types::ProtocolName,
@@ -353,6 +354,7 @@ struct ProtocolDetails {
 	behaviour: Behaviour<GenericCodec>,
 	inbound_queue: Option<async_channel::Sender<IncomingRequest>>,
 	request_timeout: Duration,
+	inbound_limiter: InboundRequestLimiter,
 }
 
 /// Implementation of `NetworkBehaviour` that provides support for request-response protocols.
@@ -382,6 +384,9 @@ pub struct RequestResponsesBehaviour {
 	/// Primarily used to get a reputation of a node.
 	peer_store: Arc<dyn PeerStoreProvider>,
 
+	/// Per-protocol and per-peer bandwidth accounting.
+	bandwidth: BandwidthMeter,
+
 	/// Interval to check that the requests are not taking too long.
 	///
 	/// We had issues in the past where libp2p did not produce a timeout event in due time.
@@ -403,9 +408,14 @@ struct RequestProcessingOutcome {
 impl RequestResponsesBehaviour {
 	/// Creates a new behaviour. Must be passed a list of supported protocols. Returns an error if
 	/// the same protocol is passed twice.
+	///
+	/// Inbound requests of each protocol are rate limited independently according to
+	/// `inbound_limits`.
 	pub fn new(
 		list: impl Iterator<Item = ProtocolConfig>,
 		peer_store: Arc<dyn PeerStoreProvider>,
+		bandwidth: BandwidthMeter,
+		inbound_limits: InboundRequestLimits,
 	) -> Result<Self, RegisterError> {
 		let mut protocols = HashMap::new();
 		for protocol in list {
@@ -433,6 +443,7 @@ impl RequestResponsesBehaviour {
 					behaviour,
 					inbound_queue: protocol.inbound_queue,
 					request_timeout: protocol.request_timeout,
+					inbound_limiter: InboundRequestLimiter::new(inbound_limits),
 				}),
 				Entry::Occupied(e) => return Err(RegisterError::DuplicateProtocol(e.key().clone())),
 			};
@@ -445,6 +456,7 @@ impl RequestResponsesBehaviour {
 			pending_responses_arrival_time: Default::default(),
 			send_feedback: Default::default(),
 			peer_store,
+			bandwidth,
 			periodic_request_check: tokio::time::interval(PERIODIC_REQUEST_CHECK),
 		})
 	}
@@ -472,6 +484,7 @@ impl RequestResponsesBehaviour {
 			Self::send_request_inner(
 				behaviour,
 				&mut self.pending_requests,
+				&self.bandwidth,
 				target,
 				protocol_name,
 				request,
@@ -492,6 +505,7 @@ impl RequestResponsesBehaviour {
 	fn send_request_inner(
 		behaviour: &mut Behaviour<GenericCodec>,
 		pending_requests: &mut HashMap<ProtocolRequestId<OutboundRequestId>, PendingRequest>,
+		bandwidth: &BandwidthMeter,
 		target: &PeerId,
 		protocol_name: ProtocolName,
 		request: Vec<u8>,
@@ -500,6 +514,8 @@ impl RequestResponsesBehaviour {
 		connect: IfDisconnected,
 	) {
 		if behaviour.is_connected(target) || connect.should_connect() {
+			bandwidth.report_outbound(&protocol_name, &(*target).into(), request.len());
+
 			let request_id = behaviour.send_request(target, request);
 			let prev_req_id = pending_requests.insert(
 				(protocol_name.to_string().into(), request_id).into(),
@@ -688,10 +704,11 @@ impl NetworkBehaviour for RequestResponsesBehaviour {
 				};
 
 				if let Ok(payload) = result {
-					if let Some(ProtocolDetails { behaviour, .. }) =
+					if let Some(ProtocolDetails { behaviour, inbound_limiter, .. }) =
 						self.protocols.get_mut(&*protocol_name)
 					{
-						log::trace!(target: LOG_TARGET, "send response to {peer} ({protocol_name:?}), {} bytes", payload.len());
+						let size = payload.len();
+						log::trace!(target: LOG_TARGET, "send response to {peer} ({protocol_name:?}), {size} bytes");
 
 						if behaviour.send_response(inner_channel, Ok(payload)).is_err() {
 							// Note: Failure is handled further below when receiving
@@ -703,9 +720,15 @@ impl NetworkBehaviour for RequestResponsesBehaviour {
 								 Dropping response",
 								request_id, protocol_name,
 							);
-						} else if let Some(sent_feedback) = sent_feedback {
-							self.send_feedback
-								.insert((protocol_name, request_id).into(), sent_feedback);
+						} else {
+							let peer_id = peer.into();
+							self.bandwidth.report_outbound(&protocol_name, &peer_id, size);
+							inbound_limiter.record_response(&peer_id, size);
+
+							if let Some(sent_feedback) = sent_feedback {
+								self.send_feedback
+									.insert((protocol_name, request_id).into(), sent_feedback);
+							}
 						}
 					}
 				}
@@ -721,7 +744,8 @@ impl NetworkBehaviour for RequestResponsesBehaviour {
 			let mut fallback_requests = vec![];
 
 			// Poll request-responses protocols.
-			for (protocol, ProtocolDetails { behaviour, inbound_queue, .. }) in &mut self.protocols
+			for (protocol, ProtocolDetails { behaviour, inbound_queue, inbound_limiter, .. }) in
+				&mut self.protocols
 			{
 				'poll_protocol: while let Poll::Ready(ev) = behaviour.poll(cx) {
 					let ev = match ev {
@@ -761,7 +785,10 @@ impl NetworkBehaviour for RequestResponsesBehaviour {
 							self.pending_responses_arrival_time
 								.insert((protocol.clone(), request_id).into(), Instant::now());
 
-							let reputation = self.peer_store.peer_reputation(&peer.into());
+							let peer_id = peer.into();
+							self.bandwidth.report_inbound(protocol, &peer_id, request.len());
+
+							let reputation = self.peer_store.peer_reputation(&peer_id);
 
 							if reputation < BANNED_THRESHOLD {
 								log::debug!(
@@ -773,6 +800,17 @@ impl NetworkBehaviour for RequestResponsesBehaviour {
 								continue 'poll_protocol
 							}
 
+							// Dropping the channel is reported as `InboundFailure::Omission`, the
+							// same way as if the response builder was too busy.
+							if !inbound_limiter.admit(&peer_id, request.len()) {
+								log::debug!(
+									target: LOG_TARGET,
+									"Inbound request rate limit exceeded on {protocol:?} for {peer}, \
+									 dropping request",
+								);
+								continue 'poll_protocol
+							}
+
 							let (tx, rx) = oneshot::channel();
 
 							// Submit the request to the "response builder" passed by the user at
@@ -830,11 +868,13 @@ impl NetworkBehaviour for RequestResponsesBehaviour {
 									response_tx: Some(response_tx),
 									..
 								}) => {
+									let size =
+										response.as_ref().map_or(0usize, |response| response.len());
 									log::trace!(
 										target: LOG_TARGET,
-										"received response from {peer} ({protocol:?}), {} bytes",
-										response.as_ref().map_or(0usize, |response| response.len()),
+										"received response from {peer} ({protocol:?}), {size} bytes",
 									);
+									self.bandwidth.report_inbound(protocol, &peer.into(), size);
 
 									let delivered = response_tx
 										.send(
@@ -994,6 +1034,7 @@ impl NetworkBehaviour for RequestResponsesBehaviour {
 					Self::send_request_inner(
 						behaviour,
 						&mut self.pending_requests,
+						&self.bandwidth,
 						&peer,
 						protocol,
 						request,
@@ -1190,7 +1231,13 @@ mod tests {
 			.multiplex(libp2p::yamux::Config::default())
 			.boxed();
 
-		let behaviour = RequestResponsesBehaviour::new(list, Arc::new(MockPeerStore {})).unwrap();
+		let behaviour = RequestResponsesBehaviour::new(
+			list,
+			Arc::new(MockPeerStore {}),
+			BandwidthMeter::default(),
+			InboundRequestLimits::default(),
+		)
+		.unwrap();
 
 		let mut swarm = Swarm::new(
 			transport,
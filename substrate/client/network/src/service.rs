@@ -40,7 +40,7 @@ use crate::{
 	network_state::{
 		NetworkState, NotConnectedPeer as NetworkStateNotConnectedPeer, Peer as NetworkStatePeer,
 	},
-	peer_store::{PeerStore, PeerStoreProvider},
+	peer_store::{Ban, BanTarget, PeerStore, PeerStoreProvider},
 	protocol::{self, Protocol, Ready},
 	protocol_controller::{self, ProtoSetConfig, ProtocolController, SetId},
 	request_responses::{IfDisconnected, ProtocolConfig as RequestResponseConfig, RequestFailure},
@@ -101,7 +101,7 @@ use std::{
 		atomic::{AtomicUsize, Ordering},
 		Arc,
 	},
-	time::{Duration, Instant},
+	time::{Duration, Instant, SystemTime},
 };
 
 pub(crate) mod metrics;
@@ -265,6 +265,7 @@ where
 			notification_protocols,
 			request_response_protocols,
 			mut network_config,
+			cached_addresses,
 			..
 		} = params.network_config;
 
@@ -455,6 +456,7 @@ where
 
 		let num_connected = Arc::new(AtomicUsize::new(0));
 		let external_addresses = Arc::new(Mutex::new(HashSet::new()));
+		let bandwidth_meter = params.notification_metrics.bandwidth_meter();
 
 		let (protocol, notif_protocol_handles) = Protocol::new(
 			From::from(&params.role),
@@ -518,6 +520,8 @@ where
 					discovery_config,
 					request_response_protocols,
 					Arc::clone(&peer_store_handle),
+					bandwidth_meter,
+					network_config.inbound_request_limits,
 					external_addresses.clone(),
 					network_config.public_addresses.iter().cloned().map(Into::into).collect(),
 					ConnectionLimits::default()
@@ -581,6 +585,12 @@ where
 			Swarm::<Behaviour<B>>::add_external_address(&mut swarm, addr.clone().into());
 		}
 
+		// Seed discovery with the addresses of peers dialed during previous runs. Unlike bootnodes
+		// and reserved nodes, they are forgotten once dialing them fails.
+		for (peer_id, addr) in cached_addresses {
+			swarm.behaviour_mut().add_known_address(peer_id.into(), addr.into());
+		}
+
 		let listen_addresses_set = Arc::new(Mutex::new(HashSet::new()));
 
 		let service = Arc::new(NetworkService {
@@ -1177,6 +1187,18 @@ where
 			.map(|peers| peers.into_iter().map(From::from).collect())
 			.map_err(|_| ())
 	}
+
+	fn ban(&self, target: BanTarget, until: Option<SystemTime>) {
+		self.peer_store_handle.ban(target, until);
+	}
+
+	fn unban(&self, target: &BanTarget) -> bool {
+		self.peer_store_handle.unban(target)
+	}
+
+	fn bans(&self) -> Vec<Ban> {
+		self.peer_store_handle.bans()
+	}
 }
 
 impl<B, H> NetworkEventStream for NetworkService<B, H>
@@ -1709,6 +1731,12 @@ where
 					debug!(target: LOG_TARGET, "Libp2p => Connected({:?})", peer_id);
 				}
 
+				self.peer_store_handle.note_connection(
+					peer_id.into(),
+					endpoint.get_remote_address().clone().into(),
+					endpoint.is_dialer(),
+				);
+
 				if let Some(metrics) = self.metrics.as_ref() {
 					let direction = match endpoint {
 						ConnectedPoint::Dialer { .. } => "out",
@@ -16,12 +16,13 @@
 // You should have received a copy of the GNU General Public License
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
-use crate::{service::traits::BandwidthSink, ProtocolName};
+use crate::{bandwidth::BandwidthMeter, service::traits::BandwidthSink, ProtocolName};
 
 use prometheus_endpoint::{
 	self as prometheus, Counter, CounterVec, Gauge, GaugeVec, HistogramOpts, MetricSource, Opts,
 	PrometheusError, Registry, SourcedCounter, SourcedGauge, U64,
 };
+use sc_network_types::PeerId;
 
 use std::{
 	str,
@@ -308,17 +309,28 @@ impl MetricSource for NumConnectedGauge {
 pub struct NotificationMetrics {
 	/// Metrics, if enabled.
 	metrics: Option<InnerNotificationMetrics>,
+
+	/// Per-protocol and per-peer bandwidth accounting.
+	bandwidth: BandwidthMeter,
 }
 
 impl NotificationMetrics {
 	/// Create new [`NotificationMetrics`].
 	pub fn new(registry: Option<&Registry>) -> NotificationMetrics {
-		let metrics = match registry {
-			Some(registry) => InnerNotificationMetrics::register(registry).ok(),
-			None => None,
+		let (metrics, bandwidth) = match registry {
+			Some(registry) => (
+				InnerNotificationMetrics::register(registry).ok(),
+				BandwidthMeter::register(registry).unwrap_or_default(),
+			),
+			None => (None, BandwidthMeter::default()),
 		};
 
-		Self { metrics }
+		Self { metrics, bandwidth }
+	}
+
+	/// Get the bandwidth meter shared by all notification and request-response protocols.
+	pub fn bandwidth_meter(&self) -> BandwidthMeter {
+		self.bandwidth.clone()
 	}
 
 	/// Register opened substream to Prometheus.
@@ -339,7 +351,9 @@ impl NotificationMetrics {
 	}
 
 	/// Register sent notification to Prometheus.
-	pub fn register_notification_sent(&self, protocol: &ProtocolName, size: usize) {
+	pub fn register_notification_sent(&self, protocol: &ProtocolName, peer: &PeerId, size: usize) {
+		self.bandwidth.report_outbound(protocol, peer, size);
+
 		if let Some(metrics) = &self.metrics {
 			metrics
 				.notifications_sizes
@@ -349,7 +363,14 @@ impl NotificationMetrics {
 	}
 
 	/// Register received notification to Prometheus.
-	pub fn register_notification_received(&self, protocol: &ProtocolName, size: usize) {
+	pub fn register_notification_received(
+		&self,
+		protocol: &ProtocolName,
+		peer: &PeerId,
+		size: usize,
+	) {
+		self.bandwidth.report_inbound(protocol, peer, size);
+
 		if let Some(metrics) = &self.metrics {
 			metrics
 				.notifications_sizes
@@ -25,6 +25,7 @@ use crate::{
 	error::{self, Error},
 	event::Event,
 	network_state::NetworkState,
+	peer_store::{Ban, BanTarget, PeerStorePersistence},
 	request_responses::{IfDisconnected, RequestFailure},
 	service::{metrics::NotificationMetrics, signature::Signature, PeerStoreProvider},
 	types::ProtocolName,
@@ -49,7 +50,7 @@ use std::{
 	future::Future,
 	pin::Pin,
 	sync::Arc,
-	time::{Duration, Instant},
+	time::{Duration, Instant, SystemTime},
 };
 
 pub use libp2p::identity::SigningError;
@@ -104,6 +105,11 @@ pub trait PeerStore {
 	/// Get handle to `PeerStore`.
 	fn handle(&self) -> Arc<dyn PeerStoreProvider>;
 
+	/// Restore the state persisted by a previous run and keep persisting it to the same location.
+	///
+	/// Backends that don't support persistence ignore it.
+	fn restore(&self, _persistence: PeerStorePersistence) {}
+
 	/// Start running `PeerStore` event loop.
 	async fn run(self);
 }
@@ -472,6 +478,21 @@ pub trait NetworkPeers {
 	///
 	/// Returns an error if the `NetworkWorker` is no longer running.
 	async fn reserved_peers(&self) -> Result<Vec<PeerId>, ()>;
+
+	/// Ban a peer or an IP range until `until`, or indefinitely if `None`.
+	///
+	/// Connected peers matching the target are disconnected and new connections are refused.
+	fn ban(&self, _target: BanTarget, _until: Option<SystemTime>) {}
+
+	/// Lift a ban, returning `false` if the target wasn't banned.
+	fn unban(&self, _target: &BanTarget) -> bool {
+		false
+	}
+
+	/// Get the bans currently in place.
+	fn bans(&self) -> Vec<Ban> {
+		Vec::new()
+	}
 }
 
 // Manual implementation to avoid extra boxing here
@@ -562,6 +583,18 @@ where
 	{
 		T::reserved_peers(self)
 	}
+
+	fn ban(&self, target: BanTarget, until: Option<SystemTime>) {
+		T::ban(self, target, until)
+	}
+
+	fn unban(&self, target: &BanTarget) -> bool {
+		T::unban(self, target)
+	}
+
+	fn bans(&self) -> Vec<Ban> {
+		T::bans(self)
+	}
 }
 
 /// Provides access to network-level event stream.
@@ -26,6 +26,7 @@ pub mod chain_sync;
 mod disconnected_peers;
 pub mod polkadot;
 pub mod state;
+mod state_checkpoint;
 pub mod state_sync;
 pub mod warp;
 
@@ -46,13 +46,22 @@ use crate::{
 
 use futures::{channel::oneshot, FutureExt};
 use log::{debug, error, info, trace, warn};
-use prometheus_endpoint::{register, Gauge, PrometheusError, Registry, U64};
+use prometheus_endpoint::{
+	register, Counter, Gauge, Histogram, HistogramOpts, PrometheusError, Registry, U64,
+};
 use prost::Message;
-use sc_client_api::{blockchain::BlockGap, BlockBackend, ProofProvider};
+use sc_client_api::{
+	blockchain::{BlockGap, BlockGapType},
+	BlockBackend, ProofProvider,
+};
 use sc_consensus::{BlockImportError, BlockImportStatus, IncomingBlock};
 use sc_network::{IfDisconnected, ProtocolName};
-use sc_network_common::sync::message::{
-	BlockAnnounce, BlockAttributes, BlockData, BlockRequest, BlockResponse, Direction, FromBlock,
+use sc_network_common::sync::{
+	message::{
+		BlockAnnounce, BlockAttributes, BlockData, BlockRequest, BlockResponse, Direction,
+		FromBlock,
+	},
+	BlockHistorySync,
 };
 use sc_network_types::PeerId;
 use sp_arithmetic::traits::Saturating;
@@ -70,8 +79,14 @@ use std::{
 	collections::{HashMap, HashSet},
 	ops::Range,
 	sync::Arc,
+	time::Instant,
 };
 
+use download_stats::DownloadStats;
+use history_backfill::HistoryBackfill;
+
+mod download_stats;
+mod history_backfill;
 #[cfg(test)]
 mod test;
 
@@ -136,6 +151,11 @@ mod rep {
 struct Metrics {
 	queued_blocks: Gauge<U64>,
 	fork_targets: Gauge<U64>,
+	downloaded_blocks: Counter<U64>,
+	block_request_duration: Histogram,
+	block_request_size: Histogram,
+	stalled_block_requests: Counter<U64>,
+	slow_peers: Gauge<U64>,
 }
 
 impl Metrics {
@@ -150,6 +170,47 @@ impl Metrics {
 				let g = Gauge::new("substrate_sync_fork_targets", "Number of fork sync targets")?;
 				register(g, r)?
 			},
+			downloaded_blocks: {
+				let c = Counter::new(
+					"substrate_sync_downloaded_blocks_total",
+					"Number of blocks downloaded in block range responses",
+				)?;
+				register(c, r)?
+			},
+			block_request_duration: {
+				let h = Histogram::with_opts(
+					HistogramOpts::new(
+						"substrate_sync_block_request_duration_seconds",
+						"Time taken by peers to answer block range requests",
+					)
+					.buckets(vec![0.05, 0.1, 0.25, 0.5, 1.0, 2.0, 5.0, 10.0, 20.0]),
+				)?;
+				register(h, r)?
+			},
+			block_request_size: {
+				let h = Histogram::with_opts(
+					HistogramOpts::new(
+						"substrate_sync_block_request_size",
+						"Number of blocks asked for in block range requests",
+					)
+					.buckets(vec![8.0, 16.0, 32.0, 64.0, 128.0]),
+				)?;
+				register(h, r)?
+			},
+			stalled_block_requests: {
+				let c = Counter::new(
+					"substrate_sync_stalled_block_requests_total",
+					"Number of block range requests that stalled and were re-requested from other peers",
+				)?;
+				register(c, r)?
+			},
+			slow_peers: {
+				let g = Gauge::new(
+					"substrate_sync_slow_peers",
+					"Number of peers excluded from block range downloads for being slow",
+				)?;
+				register(g, r)?
+			},
 		})
 	}
 }
@@ -281,6 +342,9 @@ pub(crate) enum PeerSyncState<B: BlockT> {
 	DownloadingState,
 	/// Actively downloading block history after warp sync.
 	DownloadingGap(NumberFor<B>),
+	/// Downloading the block history missing after warp sync backwards, starting from the given
+	/// number.
+	DownloadingHistory(NumberFor<B>),
 }
 
 impl<B: BlockT> PeerSyncState<B> {
@@ -330,6 +394,8 @@ pub struct ChainSync<B: BlockT, Client> {
 	state_request_protocol_name: ProtocolName,
 	/// Total number of downloaded blocks.
 	downloaded_blocks: usize,
+	/// Per-peer download statistics used to size requests and detect slow peers.
+	download_stats: DownloadStats,
 	/// State sync in progress, if any.
 	state_sync: Option<StateSync<B, Client>>,
 	/// Enable importing existing blocks. This is used after the state download to
@@ -339,6 +405,10 @@ pub struct ChainSync<B: BlockT, Client> {
 	block_downloader: Arc<dyn BlockDownloader<B>>,
 	/// Gap download process.
 	gap_sync: Option<GapSync<B>>,
+	/// How much block history to download after warp sync.
+	block_history: BlockHistorySync,
+	/// Backward download of the block history missing after warp sync.
+	history_backfill: Option<HistoryBackfill<B>>,
 	/// Pending actions.
 	actions: Vec<SyncingAction<B>>,
 	/// Prometheus metrics.
@@ -372,6 +442,11 @@ where
 		if let Some(gap_sync) = &mut self.gap_sync {
 			gap_sync.blocks.clear_peer_download(peer_id)
 		}
+		if let Some(history_backfill) = &mut self.history_backfill {
+			history_backfill.remove_peer(peer_id);
+		}
+
+		self.download_stats.remove_peer(peer_id);
 
 		if let Some(state) = self.peers.remove(peer_id) {
 			if !state.state.is_available() {
@@ -836,10 +911,15 @@ where
 			SyncState::Idle
 		};
 
-		let warp_sync_progress = self.gap_sync.as_ref().map(|gap_sync| WarpSyncProgress {
-			phase: WarpSyncPhase::DownloadingBlocks(gap_sync.best_queued_number),
-			total_bytes: 0,
-		});
+		let warp_sync_progress = self
+			.gap_sync
+			.as_ref()
+			.map(|gap_sync| gap_sync.best_queued_number)
+			.or_else(|| self.history_backfill.as_ref().map(|backfill| backfill.next_number()))
+			.map(|number| WarpSyncProgress {
+				phase: WarpSyncPhase::DownloadingBlocks(number),
+				total_bytes: 0,
+			});
 
 		SyncStatus {
 			state: sync_state,
@@ -872,6 +952,8 @@ where
 			}
 		}
 
+		self.reschedule_stalled_requests(Instant::now());
+
 		let block_requests = self
 			.block_requests()
 			.into_iter()
@@ -962,10 +1044,13 @@ where
 			max_blocks_per_request,
 			state_request_protocol_name,
 			downloaded_blocks: 0,
+			download_stats: Default::default(),
 			state_sync: None,
 			import_existing: false,
 			block_downloader,
 			gap_sync: None,
+			block_history: BlockHistorySync::default(),
+			history_backfill: None,
 			actions: Vec::new(),
 			metrics: metrics_registry.and_then(|r| match Metrics::register(r) {
 				Ok(metrics) => Some(metrics),
@@ -987,6 +1072,58 @@ where
 		Ok(sync)
 	}
 
+	/// Set how much block history to download after warp sync.
+	///
+	/// By default the whole history is downloaded forward from genesis. With a limited depth the
+	/// history is downloaded backwards from the warp sync target instead.
+	pub fn with_block_history(mut self, block_history: BlockHistorySync) -> Self {
+		self.block_history = block_history;
+		let info = self.client.info();
+		if let Some(gap) = info.block_gap {
+			match self.new_history_backfill(gap, info.finalized_number) {
+				Ok(Some(history_backfill)) => {
+					self.gap_sync = None;
+					self.history_backfill = Some(history_backfill);
+				},
+				Ok(None) => {},
+				Err(err) => {
+					warn!(
+						target: LOG_TARGET,
+						"Failed to start the block history download, downloading it forward: {err}",
+					);
+				},
+			}
+		}
+		self
+	}
+
+	/// Backward download of the block history missing after warp sync, if the history to
+	/// download with bodies is limited.
+	fn new_history_backfill(
+		&self,
+		gap: BlockGap<NumberFor<B>>,
+		finalized_number: NumberFor<B>,
+	) -> Result<Option<HistoryBackfill<B>>, ClientError> {
+		let BlockHistorySync::Depth(depth) = self.block_history else { return Ok(None) };
+		if gap.gap_type != BlockGapType::MissingHeaderAndBody {
+			return Ok(None)
+		}
+
+		// The lowest block we have is the one right above the gap.
+		let Some(hash) = self.client.hash(gap.end + One::one())? else { return Ok(None) };
+		let Some(header) = self.client.header(hash)? else { return Ok(None) };
+		// The headers are downloaded down to genesis so that no gap is left behind.
+		let bodies_from = std::cmp::max(gap.start, finalized_number.saturating_sub(depth.into()));
+		debug!(
+			target: LOG_TARGET,
+			"Starting block history download #{} - #{} with bodies from #{bodies_from}",
+			gap.end,
+			gap.start,
+		);
+
+		Ok(Some(HistoryBackfill::new(*header.parent_hash(), gap.end, gap.start, bodies_from)))
+	}
+
 	/// Complete the gap sync if the target number is reached and there is a gap.
 	fn complete_gap_if_target(&mut self, number: NumberFor<B>) {
 		let gap_sync_complete = self.gap_sync.as_ref().map_or(false, |s| s.target == number);
@@ -997,6 +1134,15 @@ where
 			);
 			self.gap_sync = None;
 		}
+
+		let history_complete = self
+			.history_backfill
+			.as_ref()
+			.map_or(false, |backfill| backfill.target() == number);
+		if history_complete {
+			info!(target: LOG_TARGET, "Block history download is complete.");
+			self.history_backfill = None;
+		}
 	}
 
 	#[must_use]
@@ -1162,6 +1308,22 @@ where
 			}
 			self.allowed_requests.add(peer_id);
 			if let Some(request) = request {
+				// The range of a stalled request may have been requested from, or even delivered
+				// by, another peer since. A late response must not overwrite it.
+				let stalled = self.download_stats.is_stalled(peer_id);
+				if matches!(
+					peer.state,
+					PeerSyncState::DownloadingNew(_) |
+						PeerSyncState::DownloadingGap(_) |
+						PeerSyncState::DownloadingHistory(_)
+				) {
+					let elapsed =
+						self.download_stats.on_response(peer_id, blocks.len(), Instant::now());
+					if let (Some(metrics), Some(elapsed)) = (&self.metrics, elapsed) {
+						metrics.downloaded_blocks.inc_by(blocks.len() as u64);
+						metrics.block_request_duration.observe(elapsed.as_secs_f64());
+					}
+				}
 				match &mut peer.state {
 					PeerSyncState::DownloadingNew(_) => {
 						self.blocks.clear_peer_download(peer_id);
@@ -1169,7 +1331,14 @@ where
 						if let Some(start_block) =
 							validate_blocks::<B>(&blocks, peer_id, Some(request))?
 						{
-							self.blocks.insert(start_block, blocks, *peer_id);
+							if stalled {
+								debug!(
+									target: LOG_TARGET,
+									"Dropping late response to stalled block request from {peer_id}",
+								);
+							} else {
+								self.blocks.insert(start_block, blocks, *peer_id);
+							}
 						}
 						self.ready_blocks()
 					},
@@ -1180,7 +1349,15 @@ where
 							if let Some(start_block) =
 								validate_blocks::<B>(&blocks, peer_id, Some(request))?
 							{
-								gap_sync.blocks.insert(start_block, blocks, *peer_id);
+								if stalled {
+									debug!(
+										target: LOG_TARGET,
+										"Dropping late response to stalled gap block request from \
+										 {peer_id}",
+									);
+								} else {
+									gap_sync.blocks.insert(start_block, blocks, *peer_id);
+								}
 							}
 							gap = true;
 							let blocks: Vec<_> = gap_sync
@@ -1220,6 +1397,53 @@ where
 							return Err(BadPeer(*peer_id, rep::NO_BLOCK));
 						}
 					},
+					PeerSyncState::DownloadingHistory(_) => {
+						peer.state = PeerSyncState::Available;
+						let Some(history_backfill) = &mut self.history_backfill else {
+							debug!(
+								target: LOG_TARGET,
+								"Unexpected block history response from {peer_id}",
+							);
+							return Err(BadPeer(*peer_id, rep::NOT_REQUESTED));
+						};
+						if !blocks.is_empty() {
+							validate_blocks::<B>(&blocks, peer_id, Some(request))?;
+						}
+						gap = true;
+						let (blocks, bad_peer) = history_backfill.on_response(peer_id, blocks);
+						if let Some(bad_peer) = bad_peer {
+							// Blocks served by another peer may only be found not to link to our
+							// chain now.
+							self.actions.push(SyncingAction::DropPeer(bad_peer));
+						}
+						let blocks: Vec<_> = blocks
+							.into_iter()
+							.map(|b| {
+								let justifications = b
+									.justifications
+									.or_else(|| legacy_justification_mapping(b.justification));
+								IncomingBlock {
+									hash: b.hash,
+									header: b.header,
+									body: b.body,
+									indexed_body: b.indexed_body,
+									justifications,
+									origin: Some(*peer_id),
+									allow_missing_state: true,
+									import_existing: self.import_existing,
+									skip_execution: true,
+									state: None,
+								}
+							})
+							.collect();
+						debug!(
+							target: LOG_TARGET,
+							"Downloaded {} history blocks, next #{}",
+							blocks.len(),
+							history_backfill.next_number(),
+						);
+						blocks
+					},
 					PeerSyncState::DownloadingStale(_) => {
 						peer.state = PeerSyncState::Available;
 						if blocks.is_empty() {
@@ -1655,6 +1879,7 @@ where
 				PeerSyncState::DownloadingNew(_) |
 				PeerSyncState::DownloadingStale(_) |
 				PeerSyncState::DownloadingGap(_) |
+				PeerSyncState::DownloadingHistory(_) |
 				PeerSyncState::DownloadingState => {
 					// Cancel a request first, as `add_peer` may generate a new request.
 					self.actions
@@ -1713,14 +1938,21 @@ where
 			}
 		}
 
-		if let Some(BlockGap { start, end, .. }) = info.block_gap {
-			let old_gap = self.gap_sync.take().map(|g| (g.best_queued_number, g.target));
-			debug!(target: LOG_TARGET, "Starting gap sync #{start} - #{end} (old gap best and target: {old_gap:?})");
-			self.gap_sync = Some(GapSync {
-				best_queued_number: start - One::one(),
-				target: end,
-				blocks: BlockCollection::new(),
-			});
+		self.history_backfill = None;
+		if let Some(gap) = info.block_gap {
+			self.history_backfill = self.new_history_backfill(gap, info.finalized_number)?;
+			if self.history_backfill.is_some() {
+				self.gap_sync = None;
+			} else {
+				let BlockGap { start, end, .. } = gap;
+				let old_gap = self.gap_sync.take().map(|g| (g.best_queued_number, g.target));
+				debug!(target: LOG_TARGET, "Starting gap sync #{start} - #{end} (old gap best and target: {old_gap:?})");
+				self.gap_sync = Some(GapSync {
+					best_queued_number: start - One::one(),
+					target: end,
+					blocks: BlockCollection::new(),
+				});
+			}
 		}
 		trace!(
 			target: LOG_TARGET,
@@ -1804,6 +2036,34 @@ where
 		.collect()
 	}
 
+	/// Make the ranges of stalled block requests available to other peers.
+	///
+	/// The stalled peer keeps its request in flight, but the range no longer waits for it and a
+	/// late response is dropped.
+	fn reschedule_stalled_requests(&mut self, now: Instant) {
+		for peer_id in self.download_stats.stalled_requests(now) {
+			debug!(
+				target: LOG_TARGET,
+				"Block request to {peer_id} stalled, re-requesting the range from other peers.",
+			);
+			self.blocks.clear_peer_download(&peer_id);
+			if let Some(gap_sync) = &mut self.gap_sync {
+				gap_sync.blocks.clear_peer_download(&peer_id);
+			}
+			if let Some(history_backfill) = &mut self.history_backfill {
+				history_backfill.release_peer(&peer_id);
+			}
+			self.allowed_requests.set_all();
+			if let Some(metrics) = &self.metrics {
+				metrics.stalled_block_requests.inc();
+			}
+		}
+
+		if let Some(metrics) = &self.metrics {
+			metrics.slow_peers.set(self.download_stats.num_excluded(now) as u64);
+		}
+	}
+
 	/// Get block requests scheduled by sync to be sent out.
 	fn block_requests(&mut self) -> Vec<(PeerId, BlockRequest<B>)> {
 		if self.allowed_requests.is_empty() || self.state_sync.is_some() {
@@ -1827,8 +2087,23 @@ where
 		let max_parallel = if is_major_syncing { 1 } else { self.max_parallel_downloads };
 		let max_blocks_per_request = self.max_blocks_per_request;
 		let gap_sync = &mut self.gap_sync;
+		// The block history is only downloaded while the import queue has room to spare.
+		let history_backfill = self
+			.history_backfill
+			.as_mut()
+			.filter(|_| queue_blocks.len() < max_blocks_per_request as usize);
 		let disconnected_peers = &mut self.disconnected_peers;
 		let metrics = self.metrics.as_ref();
+		let now = Instant::now();
+		let download_stats = &mut self.download_stats;
+		// Slow peers are only left out of range downloads as long as another peer we can send a
+		// request to right now is able to serve them.
+		let skip_slow_peers = self.peers.iter().any(|(id, peer)| {
+			peer.state.is_available() &&
+				allowed_requests.contains(id) &&
+				!download_stats.is_excluded(id, now) &&
+				disconnected_peers.is_peer_available(id)
+		});
 		let requests = self
 			.peers
 			.iter_mut()
@@ -1840,6 +2115,9 @@ where
 					return None;
 				}
 
+				let download_ranges = !(skip_slow_peers && download_stats.is_excluded(&id, now));
+				let request_size = download_stats.request_size(&id, max_blocks_per_request);
+
 				// If our best queued is more than `MAX_BLOCKS_TO_LOOK_BACKWARDS` blocks away from
 				// the common number, the peer best number is higher than our best queued and the
 				// common number is smaller than the last finalized block number, we should do an
@@ -1865,17 +2143,26 @@ where
 						state: AncestorSearchState::ExponentialBackoff(One::one()),
 					};
 					Some((id, ancestry_request::<B>(current)))
-				} else if let Some((range, req)) = peer_block_request(
-					&id,
-					peer,
-					blocks,
-					attrs,
-					max_parallel,
-					max_blocks_per_request,
-					last_finalized,
-					best_queued,
-				) {
+				} else if let Some((range, req)) = download_ranges
+					.then(|| {
+						peer_block_request(
+							&id,
+							peer,
+							blocks,
+							attrs,
+							max_parallel,
+							request_size,
+							last_finalized,
+							best_queued,
+						)
+					})
+					.flatten()
+				{
 					peer.state = PeerSyncState::DownloadingNew(range.start);
+					download_stats.on_request(&id, request_size, now);
+					if let Some(metrics) = metrics {
+						metrics.block_request_size.observe(request_size as f64);
+					}
 					trace!(
 						target: LOG_TARGET,
 						"New block request for {}, (best:{}, common:{}) {:?}",
@@ -1904,18 +2191,23 @@ where
 					trace!(target: LOG_TARGET, "Downloading fork {hash:?} from {id}");
 					peer.state = PeerSyncState::DownloadingStale(hash);
 					Some((id, req))
-				} else if let Some((range, req)) = gap_sync.as_mut().and_then(|sync| {
-					peer_gap_block_request(
-						&id,
-						peer,
-						&mut sync.blocks,
-						attrs,
-						sync.target,
-						sync.best_queued_number,
-						max_blocks_per_request,
-					)
-				}) {
+				} else if let Some((range, req)) =
+					gap_sync.as_mut().filter(|_| download_ranges).and_then(|sync| {
+						peer_gap_block_request(
+							&id,
+							peer,
+							&mut sync.blocks,
+							attrs,
+							sync.target,
+							sync.best_queued_number,
+							request_size,
+						)
+					}) {
 					peer.state = PeerSyncState::DownloadingGap(range.start);
+					download_stats.on_request(&id, request_size, now);
+					if let Some(metrics) = metrics {
+						metrics.block_request_size.observe(request_size as f64);
+					}
 					trace!(
 						target: LOG_TARGET,
 						"New gap block request for {}, (best:{}, common:{}) {:?}",
@@ -1925,6 +2217,19 @@ where
 						req,
 					);
 					Some((id, req))
+				} else if let Some((number, req)) =
+					history_backfill.as_deref_mut().filter(|_| download_ranges).and_then(
+						|backfill| backfill.request(&id, peer.best_number, attrs, request_size),
+					) {
+					peer.state = PeerSyncState::DownloadingHistory(number);
+					// History ranges may be shorter than a full request.
+					let request_size = req.max.unwrap_or(request_size);
+					download_stats.on_request(&id, request_size, now);
+					if let Some(metrics) = metrics {
+						metrics.block_request_size.observe(request_size as f64);
+					}
+					trace!(target: LOG_TARGET, "New block history request for {id} {req:?}");
+					Some((id, req))
 				} else {
 					None
 				}
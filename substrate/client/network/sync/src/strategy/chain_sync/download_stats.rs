@@ -0,0 +1,319 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Per-peer block download statistics.
+//!
+//! [`DownloadStats`] measures how fast every peer answers block range requests. The measurements
+//! are used to size the next request sent to the peer, to spot requests that stalled so that their
+//! range can be handed to somebody else, and to temporarily stop requesting ranges from peers that
+//! are consistently slow.
+
+use sc_network_types::PeerId;
+use std::{
+	collections::HashMap,
+	time::{Duration, Instant},
+};
+
+/// Response time we aim for when sizing block requests.
+const TARGET_RESPONSE_TIME: Duration = Duration::from_secs(2);
+
+/// Responses taking longer than this count as slow.
+const SLOW_RESPONSE_TIME: Duration = Duration::from_secs(5);
+
+/// Time allowed per requested block before a request is considered stalled and its range is made
+/// available to other peers.
+const STALLED_REQUEST_TIMEOUT_PER_BLOCK: Duration = Duration::from_millis(200);
+
+/// Lower bound of the stall timeout, so that small requests still get a chance to be answered by
+/// a slow peer.
+const MIN_STALLED_REQUEST_TIMEOUT: Duration = Duration::from_secs(6);
+
+/// Upper bound of the stall timeout. This stays below the 20 seconds block request protocol
+/// timeout so that the range is handed over before the request fails.
+const MAX_STALLED_REQUEST_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// Number of consecutive slow or stalled responses after which a peer is excluded from range
+/// downloads.
+const MAX_SLOW_RESPONSES: u32 = 3;
+
+/// For how long a slow peer is excluded from range downloads.
+const SLOW_PEER_COOLDOWN: Duration = Duration::from_secs(60);
+
+/// Smallest request size the adaptive sizing shrinks to.
+const MIN_BLOCKS_PER_REQUEST: u32 = 8;
+
+/// Weight of the most recent sample in the throughput moving average.
+const THROUGHPUT_SMOOTHING: f64 = 0.3;
+
+/// Block request sent to a peer and not answered yet.
+#[derive(Debug, Clone, Copy)]
+struct InFlight {
+	started: Instant,
+	/// How long the request may take before it is considered stalled.
+	timeout: Duration,
+	stalled: bool,
+}
+
+#[derive(Debug, Default)]
+struct PeerStats {
+	/// Request in flight, if any.
+	in_flight: Option<InFlight>,
+	/// Moving average of the peer throughput, in blocks per second.
+	throughput: Option<f64>,
+	/// Number of consecutive slow responses.
+	slow_responses: u32,
+	/// The peer is excluded from range downloads until this instant.
+	excluded_until: Option<Instant>,
+}
+
+/// Download statistics of all peers we request block ranges from.
+#[derive(Debug, Default)]
+pub(super) struct DownloadStats {
+	peers: HashMap<PeerId, PeerStats>,
+}
+
+impl DownloadStats {
+	/// Record that a request for up to `blocks` blocks was sent to `peer`.
+	pub fn on_request(&mut self, peer: &PeerId, blocks: u32, now: Instant) {
+		self.peers.entry(*peer).or_default().in_flight = Some(InFlight {
+			started: now,
+			timeout: stalled_request_timeout(blocks),
+			stalled: false,
+		});
+	}
+
+	/// Whether the request in flight to `peer` has been reported as stalled.
+	pub fn is_stalled(&self, peer: &PeerId) -> bool {
+		self.peers
+			.get(peer)
+			.and_then(|stats| stats.in_flight)
+			.map_or(false, |in_flight| in_flight.stalled)
+	}
+
+	/// Record that `peer` answered its in-flight request with `blocks` blocks.
+	///
+	/// Returns how long the request took, or `None` if there was no request in flight.
+	pub fn on_response(&mut self, peer: &PeerId, blocks: usize, now: Instant) -> Option<Duration> {
+		let stats = self.peers.get_mut(peer)?;
+		let in_flight = stats.in_flight.take()?;
+		let elapsed = now.saturating_duration_since(in_flight.started);
+
+		// An empty response tells nothing about the peer's throughput.
+		if blocks == 0 {
+			return Some(elapsed)
+		}
+
+		let sample = blocks as f64 / elapsed.as_secs_f64().max(0.001);
+		stats.throughput = Some(match stats.throughput {
+			Some(average) => average + THROUGHPUT_SMOOTHING * (sample - average),
+			None => sample,
+		});
+
+		if in_flight.stalled {
+			// Already accounted as slow when the request stalled.
+		} else if elapsed > SLOW_RESPONSE_TIME {
+			stats.on_slow_response(now);
+		} else {
+			stats.slow_responses = 0;
+		}
+
+		Some(elapsed)
+	}
+
+	/// Return the peers whose in-flight request has stalled since the last call.
+	///
+	/// Every stalled request is reported once and counts as a slow response.
+	pub fn stalled_requests(&mut self, now: Instant) -> Vec<PeerId> {
+		self.peers
+			.iter_mut()
+			.filter_map(|(peer, stats)| {
+				let in_flight = stats.in_flight.as_mut()?;
+				if in_flight.stalled ||
+					now.saturating_duration_since(in_flight.started) < in_flight.timeout
+				{
+					return None
+				}
+
+				in_flight.stalled = true;
+				// Whatever the peer managed before, it is clearly slower now.
+				stats.throughput = stats.throughput.map(|t| t / 2.0);
+				stats.on_slow_response(now);
+				Some(*peer)
+			})
+			.collect()
+	}
+
+	/// Number of blocks to request from `peer`, capped at `max_blocks`.
+	///
+	/// Peers we know nothing about are asked for `max_blocks`; afterwards the size follows the
+	/// measured throughput so that a response takes about [`TARGET_RESPONSE_TIME`].
+	pub fn request_size(&self, peer: &PeerId, max_blocks: u32) -> u32 {
+		let Some(throughput) = self.peers.get(peer).and_then(|stats| stats.throughput) else {
+			return max_blocks
+		};
+
+		let size = (throughput * TARGET_RESPONSE_TIME.as_secs_f64()) as u32;
+		size.clamp(MIN_BLOCKS_PER_REQUEST.min(max_blocks), max_blocks)
+	}
+
+	/// Whether `peer` is currently excluded from range downloads for being slow.
+	pub fn is_excluded(&self, peer: &PeerId, now: Instant) -> bool {
+		self.peers
+			.get(peer)
+			.and_then(|stats| stats.excluded_until)
+			.map_or(false, |until| until > now)
+	}
+
+	/// Number of peers currently excluded from range downloads.
+	pub fn num_excluded(&self, now: Instant) -> usize {
+		self.peers.keys().filter(|peer| self.is_excluded(peer, now)).count()
+	}
+
+	/// Forget everything about `peer`.
+	pub fn remove_peer(&mut self, peer: &PeerId) {
+		self.peers.remove(peer);
+	}
+}
+
+/// How long a request for `blocks` blocks may take before it is considered stalled.
+fn stalled_request_timeout(blocks: u32) -> Duration {
+	(STALLED_REQUEST_TIMEOUT_PER_BLOCK * blocks)
+		.clamp(MIN_STALLED_REQUEST_TIMEOUT, MAX_STALLED_REQUEST_TIMEOUT)
+}
+
+impl PeerStats {
+	fn on_slow_response(&mut self, now: Instant) {
+		self.slow_responses += 1;
+		if self.slow_responses >= MAX_SLOW_RESPONSES {
+			self.slow_responses = 0;
+			self.excluded_until = Some(now + SLOW_PEER_COOLDOWN);
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn unknown_peers_get_full_requests() {
+		let stats = DownloadStats::default();
+		assert_eq!(stats.request_size(&PeerId::random(), 64), 64);
+	}
+
+	#[test]
+	fn request_size_follows_throughput() {
+		let mut stats = DownloadStats::default();
+		let fast = PeerId::random();
+		let slow = PeerId::random();
+		let now = Instant::now();
+
+		stats.on_request(&fast, 64, now);
+		stats.on_request(&slow, 64, now);
+		assert_eq!(
+			stats.on_response(&fast, 64, now + Duration::from_millis(200)).unwrap(),
+			Duration::from_millis(200)
+		);
+		// 16 blocks per second.
+		stats.on_response(&slow, 64, now + Duration::from_secs(4));
+
+		assert_eq!(stats.request_size(&fast, 64), 64);
+		assert_eq!(stats.request_size(&slow, 64), 32);
+
+		// Never shrinks below the minimum.
+		for _ in 0..10 {
+			stats.on_request(&slow, 64, now);
+			stats.on_response(&slow, 1, now + Duration::from_secs(4));
+		}
+		assert_eq!(stats.request_size(&slow, 64), MIN_BLOCKS_PER_REQUEST);
+		assert_eq!(stats.request_size(&slow, 4), 4);
+	}
+
+	#[test]
+	fn stalled_requests_are_reported_once() {
+		let mut stats = DownloadStats::default();
+		let peer = PeerId::random();
+		let now = Instant::now();
+
+		stats.on_request(&peer, 64, now);
+		assert!(stats.stalled_requests(now + Duration::from_secs(1)).is_empty());
+		assert!(!stats.is_stalled(&peer));
+		assert_eq!(stats.stalled_requests(now + stalled_request_timeout(64)), vec![peer]);
+		assert!(stats.stalled_requests(now + stalled_request_timeout(64) * 2).is_empty());
+		assert!(stats.is_stalled(&peer));
+
+		// A late response still completes the request.
+		assert!(stats.on_response(&peer, 64, now + stalled_request_timeout(64) * 2).is_some());
+		assert!(stats.on_response(&peer, 64, now + stalled_request_timeout(64) * 2).is_none());
+		assert!(!stats.is_stalled(&peer));
+	}
+
+	#[test]
+	fn stall_timeout_scales_with_request_size() {
+		let mut stats = DownloadStats::default();
+		let small = PeerId::random();
+		let large = PeerId::random();
+		let now = Instant::now();
+
+		stats.on_request(&small, 8, now);
+		stats.on_request(&large, 64, now);
+		assert_eq!(stats.stalled_requests(now + MIN_STALLED_REQUEST_TIMEOUT), vec![small]);
+		assert_eq!(
+			stats.stalled_requests(now + STALLED_REQUEST_TIMEOUT_PER_BLOCK * 64),
+			vec![large]
+		);
+
+		assert_eq!(stalled_request_timeout(1), MIN_STALLED_REQUEST_TIMEOUT);
+		assert_eq!(stalled_request_timeout(u32::MAX), MAX_STALLED_REQUEST_TIMEOUT);
+	}
+
+	#[test]
+	fn consistently_slow_peers_are_excluded() {
+		let mut stats = DownloadStats::default();
+		let peer = PeerId::random();
+		let mut now = Instant::now();
+
+		for _ in 0..MAX_SLOW_RESPONSES - 1 {
+			stats.on_request(&peer, 64, now);
+			now += SLOW_RESPONSE_TIME * 2;
+			stats.on_response(&peer, 64, now);
+		}
+		assert!(!stats.is_excluded(&peer, now));
+
+		// A fast response resets the count.
+		stats.on_request(&peer, 64, now);
+		stats.on_response(&peer, 64, now + Duration::from_millis(100));
+		stats.on_request(&peer, 64, now);
+		stats.on_response(&peer, 64, now + SLOW_RESPONSE_TIME * 2);
+		assert!(!stats.is_excluded(&peer, now));
+
+		// Stalls count as slow responses too.
+		stats.on_request(&peer, 64, now);
+		now += stalled_request_timeout(64);
+		assert_eq!(stats.stalled_requests(now), vec![peer]);
+		stats.on_request(&peer, 64, now);
+		now += stalled_request_timeout(64);
+		assert_eq!(stats.stalled_requests(now), vec![peer]);
+		assert!(stats.is_excluded(&peer, now));
+		assert_eq!(stats.num_excluded(now), 1);
+
+		assert!(!stats.is_excluded(&peer, now + SLOW_PEER_COOLDOWN));
+		stats.remove_peer(&peer);
+		assert_eq!(stats.num_excluded(now), 0);
+	}
+}
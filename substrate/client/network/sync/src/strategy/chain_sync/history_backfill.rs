@@ -0,0 +1,463 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Background download of the block history missing after warp sync.
+//!
+//! Warp sync leaves the database without the blocks below the warp target. [`HistoryBackfill`]
+//! downloads them backwards, starting from the parent of the lowest block we have. Several ranges
+//! are requested from different peers at once; a range is only handed out for import once every
+//! block in it is checked to be the parent of the block above it, so the whole history is verified
+//! against the warp target without trusting the peers serving it.
+//!
+//! Only the most recent blocks are downloaded with their body. Older blocks are downloaded with
+//! their header and justifications only, so that the header chain reaches genesis and no gap is
+//! left in the database.
+
+use super::rep;
+use crate::{types::BadPeer, LOG_TARGET};
+
+use log::{debug, trace};
+use sc_network_common::sync::message::{
+	BlockAttributes, BlockData, BlockRequest, Direction, FromBlock,
+};
+use sc_network_types::PeerId;
+use sp_runtime::traits::{Block as BlockT, Header as HeaderT, NumberFor, One, SaturatedConversion};
+use std::collections::{BTreeMap, HashSet};
+
+/// Maximum number of history requests in flight. The history is downloaded in the background, so
+/// this stays well below what is used to follow the chain.
+const MAX_PARALLEL_REQUESTS: usize = 4;
+
+/// A range of blocks below the verified part of the history.
+enum Range<B: BlockT> {
+	/// Requested from `peer`, `len` blocks down from the top of the range.
+	Requested { peer: PeerId, len: u32 },
+	/// Received from `peer`, highest first, and waiting for the blocks above to be verified.
+	Received { peer: PeerId, blocks: Vec<BlockData<B>> },
+}
+
+impl<B: BlockT> Range<B> {
+	fn len(&self) -> u32 {
+		match self {
+			Self::Requested { len, .. } => *len,
+			Self::Received { blocks, .. } => blocks.len().saturated_into(),
+		}
+	}
+}
+
+/// Backward download of the block history below the lowest block we have.
+pub(super) struct HistoryBackfill<B: BlockT> {
+	/// Hash of the next block to verify, the parent of the lowest block verified so far.
+	next_hash: B::Hash,
+	/// Number of the next block to verify.
+	next_number: NumberFor<B>,
+	/// Lowest block to download.
+	target: NumberFor<B>,
+	/// Blocks below this one are downloaded without body.
+	bodies_from: NumberFor<B>,
+	/// Ranges requested or received, by the number of their highest block.
+	ranges: BTreeMap<NumberFor<B>, Range<B>>,
+	/// Peers that answered without any block, most likely because they warp synced themselves.
+	peers_without_history: HashSet<PeerId>,
+}
+
+impl<B: BlockT> HistoryBackfill<B> {
+	/// Download the history from block `next_number` with hash `next_hash` down to `target`, with
+	/// the bodies of the blocks from `bodies_from` up.
+	pub fn new(
+		next_hash: B::Hash,
+		next_number: NumberFor<B>,
+		target: NumberFor<B>,
+		bodies_from: NumberFor<B>,
+	) -> Self {
+		Self {
+			next_hash,
+			next_number,
+			target,
+			bodies_from,
+			ranges: BTreeMap::new(),
+			peers_without_history: HashSet::new(),
+		}
+	}
+
+	/// Number of the next block to verify.
+	pub fn next_number(&self) -> NumberFor<B> {
+		self.next_number
+	}
+
+	/// Lowest block to download.
+	pub fn target(&self) -> NumberFor<B> {
+		self.target
+	}
+
+	/// Create the next history request for `peer_id`.
+	///
+	/// Returns the number of the highest requested block along with the request. The highest range
+	/// nobody is downloading is requested, by hash if it links to the verified history and by
+	/// number otherwise.
+	pub fn request(
+		&mut self,
+		peer_id: &PeerId,
+		peer_best: NumberFor<B>,
+		attributes: BlockAttributes,
+		max_blocks: u32,
+	) -> Option<(NumberFor<B>, BlockRequest<B>)> {
+		let in_flight = self
+			.ranges
+			.values()
+			.filter(|range| matches!(range, Range::Requested { .. }))
+			.count();
+		if in_flight >= MAX_PARALLEL_REQUESTS || self.peers_without_history.contains(peer_id) {
+			return None
+		}
+
+		let (top, bottom) = self.next_missing_range()?;
+		if peer_best < top {
+			return None
+		}
+
+		// Don't mix blocks with and without body in one request.
+		let (bottom, attributes) = if top >= self.bodies_from {
+			(std::cmp::max(bottom, self.bodies_from), attributes)
+		} else {
+			(bottom, attributes - BlockAttributes::BODY)
+		};
+		let len = std::cmp::min((top - bottom + One::one()).saturated_into(), max_blocks);
+		let from = if top == self.next_number {
+			FromBlock::Hash(self.next_hash)
+		} else {
+			FromBlock::Number(top)
+		};
+
+		self.ranges.insert(top, Range::Requested { peer: *peer_id, len });
+		trace!(target: LOG_TARGET, "New block history request to {peer_id} from #{top} ({from:?})");
+
+		Some((
+			top,
+			BlockRequest::<B> {
+				id: 0,
+				fields: attributes,
+				from,
+				direction: Direction::Descending,
+				max: Some(len),
+			},
+		))
+	}
+
+	/// Highest and lowest block of the highest range nobody is downloading.
+	fn next_missing_range(&self) -> Option<(NumberFor<B>, NumberFor<B>)> {
+		let mut top = self.next_number;
+		for (range_top, range) in self.ranges.iter().rev() {
+			if top < self.target {
+				return None
+			}
+			if *range_top < top {
+				return Some((top, *range_top + One::one()))
+			}
+			let range_bottom = *range_top + One::one() - range.len().into();
+			if range_bottom <= self.target {
+				return None
+			}
+			top = range_bottom - One::one();
+		}
+
+		(top >= self.target).then_some((top, self.target))
+	}
+
+	/// Process the response of `peer_id` to a history request.
+	///
+	/// `blocks` are expected in ascending order and already validated against the request.
+	/// Returns the blocks verified to link to the history above them, highest first, which is the
+	/// order they are inserted into the database. A peer that served blocks that don't link is
+	/// returned as well, and its range requested again.
+	pub fn on_response(
+		&mut self,
+		peer_id: &PeerId,
+		mut blocks: Vec<BlockData<B>>,
+	) -> (Vec<BlockData<B>>, Option<BadPeer>) {
+		let Some(top) = self.requested_range(peer_id) else {
+			debug!(target: LOG_TARGET, "Ignoring stale block history response from {peer_id}");
+			return (Vec::new(), None)
+		};
+
+		if blocks.is_empty() {
+			debug!(target: LOG_TARGET, "{peer_id} doesn't have the block history we need");
+			self.ranges.remove(&top);
+			self.peers_without_history.insert(*peer_id);
+			return (Vec::new(), None)
+		}
+
+		blocks.reverse();
+		self.ranges.insert(top, Range::Received { peer: *peer_id, blocks });
+		self.verify_received()
+	}
+
+	/// Verify the received ranges that link to the verified history, from the top.
+	fn verify_received(&mut self) -> (Vec<BlockData<B>>, Option<BadPeer>) {
+		let mut verified = Vec::new();
+		while let Some(entry) = self.ranges.last_entry() {
+			if *entry.key() != self.next_number || !matches!(entry.get(), Range::Received { .. }) {
+				break
+			}
+			let Range::Received { peer, blocks } = entry.remove() else {
+				unreachable!("range is checked to be received above; qed")
+			};
+
+			let mut next_hash = self.next_hash;
+			let mut next_number = self.next_number;
+			let mut range = Vec::with_capacity(blocks.len());
+			for block in blocks {
+				if next_number < self.target {
+					break
+				}
+
+				let Some(header) = &block.header else {
+					debug!(target: LOG_TARGET, "Missing header in block history from {peer}");
+					return (verified, Some(BadPeer(peer, rep::INCOMPLETE_HEADER)))
+				};
+				if block.hash != next_hash || *header.number() != next_number {
+					debug!(
+						target: LOG_TARGET,
+						"Block history from {peer} doesn't link to our chain. Expected \
+						#{next_number} ({next_hash:?}), got #{} ({:?})",
+						header.number(),
+						block.hash,
+					);
+					return (verified, Some(BadPeer(peer, rep::VERIFICATION_FAIL)))
+				}
+
+				next_hash = *header.parent_hash();
+				next_number -= One::one();
+				range.push(block);
+			}
+
+			self.next_hash = next_hash;
+			self.next_number = next_number;
+			verified.extend(range);
+		}
+
+		(verified, None)
+	}
+
+	/// Top of the range requested from `peer_id`, if any.
+	fn requested_range(&self, peer_id: &PeerId) -> Option<NumberFor<B>> {
+		self.ranges.iter().find_map(|(top, range)| match range {
+			Range::Requested { peer, .. } if peer == peer_id => Some(*top),
+			_ => None,
+		})
+	}
+
+	/// Stop waiting for the request in flight to `peer_id`, if any, so that its range can be
+	/// requested from another peer.
+	pub fn release_peer(&mut self, peer_id: &PeerId) {
+		if let Some(top) = self.requested_range(peer_id) {
+			self.ranges.remove(&top);
+		}
+	}
+
+	/// Forget about a disconnected peer.
+	pub fn remove_peer(&mut self, peer_id: &PeerId) {
+		self.release_peer(peer_id);
+		self.peers_without_history.remove(peer_id);
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use sp_runtime::traits::Hash as HashT;
+	use substrate_test_runtime_client::runtime::{Block, Hash, Header};
+
+	const ATTRIBUTES: BlockAttributes = BlockAttributes::HEADER.union(BlockAttributes::BODY);
+
+	/// Headers of a chain of `len` blocks, genesis first.
+	fn chain(len: u64) -> Vec<Header> {
+		let mut headers: Vec<Header> = Vec::new();
+		for number in 0..len {
+			let parent_hash = headers.last().map_or_else(Default::default, |h| h.hash());
+			headers.push(Header::new(
+				number,
+				Default::default(),
+				<Header as HeaderT>::Hashing::hash(&number.to_le_bytes()),
+				parent_hash,
+				Default::default(),
+			));
+		}
+		headers
+	}
+
+	fn block_data(header: &Header) -> BlockData<Block> {
+		BlockData {
+			hash: header.hash(),
+			header: Some(header.clone()),
+			body: Some(Vec::new()),
+			indexed_body: None,
+			receipt: None,
+			message_queue: None,
+			justification: None,
+			justifications: None,
+		}
+	}
+
+	/// Blocks `from..=to`, in the ascending order responses are handed over in.
+	fn blocks(headers: &[Header], from: usize, to: usize) -> Vec<BlockData<Block>> {
+		headers[from..=to].iter().map(block_data).collect()
+	}
+
+	fn backfill(headers: &[Header], target: u64, bodies_from: u64) -> HistoryBackfill<Block> {
+		let top = headers.last().unwrap();
+		HistoryBackfill::new(*top.parent_hash(), top.number - 1, target, bodies_from)
+	}
+
+	#[test]
+	fn history_is_requested_in_parallel_ranges() {
+		let headers = chain(1000);
+		let mut backfill = backfill(&headers, 1, 1);
+		let peers: Vec<_> = (0..=MAX_PARALLEL_REQUESTS).map(|_| PeerId::random()).collect();
+
+		// The peer must have the blocks.
+		assert!(backfill.request(&peers[0], 500, ATTRIBUTES, 64).is_none());
+
+		// The first range links to our chain, the next ones are requested by number.
+		let (top, request) = backfill.request(&peers[0], 999, ATTRIBUTES, 64).unwrap();
+		assert_eq!(top, 998);
+		assert_eq!(request.from, FromBlock::Hash(headers[998].hash()));
+		assert_eq!(request.direction, Direction::Descending);
+		assert_eq!(request.max, Some(64));
+		for (i, peer) in peers.iter().enumerate().take(MAX_PARALLEL_REQUESTS).skip(1) {
+			let (top, request) = backfill.request(peer, 999, ATTRIBUTES, 64).unwrap();
+			assert_eq!(top, 998 - 64 * i as u64);
+			assert_eq!(request.from, FromBlock::Number(top));
+		}
+		assert!(backfill.request(&peers[MAX_PARALLEL_REQUESTS], 999, ATTRIBUTES, 64).is_none());
+
+		// Releasing a peer makes its range available to others.
+		backfill.release_peer(&peers[1]);
+		let (top, request) =
+			backfill.request(&peers[MAX_PARALLEL_REQUESTS], 999, ATTRIBUTES, 64).unwrap();
+		assert_eq!(top, 934);
+		assert_eq!(request.from, FromBlock::Number(934));
+	}
+
+	#[test]
+	fn ranges_are_verified_from_the_top() {
+		let headers = chain(200);
+		let mut backfill = backfill(&headers, 30, 1);
+		let (first, second, third) = (PeerId::random(), PeerId::random(), PeerId::random());
+
+		backfill.request(&first, 199, ATTRIBUTES, 64).unwrap();
+		backfill.request(&second, 199, ATTRIBUTES, 64).unwrap();
+		let (top, request) = backfill.request(&third, 199, ATTRIBUTES, 64).unwrap();
+		assert_eq!(top, 70);
+		assert_eq!(request.max, Some(41));
+
+		// Ranges received out of order wait for the blocks above them.
+		let (verified, bad_peer) = backfill.on_response(&third, blocks(&headers, 30, 70));
+		assert!(verified.is_empty() && bad_peer.is_none());
+		let (verified, bad_peer) = backfill.on_response(&second, blocks(&headers, 71, 134));
+		assert!(verified.is_empty() && bad_peer.is_none());
+		assert_eq!(backfill.next_number(), 198);
+
+		// Highest first, down to the target.
+		let (verified, bad_peer) = backfill.on_response(&first, blocks(&headers, 135, 198));
+		assert!(bad_peer.is_none());
+		assert_eq!(verified.len(), 198 - 30 + 1);
+		assert_eq!(verified[0].hash, headers[198].hash());
+		assert_eq!(verified.last().unwrap().hash, headers[30].hash());
+		assert!(backfill.next_number() < backfill.target());
+		assert!(backfill.request(&first, 199, ATTRIBUTES, 64).is_none());
+	}
+
+	#[test]
+	fn short_responses_leave_a_hole_to_request_again() {
+		let headers = chain(200);
+		let mut backfill = backfill(&headers, 1, 1);
+		let (first, second) = (PeerId::random(), PeerId::random());
+
+		backfill.request(&first, 199, ATTRIBUTES, 64).unwrap();
+		backfill.request(&second, 199, ATTRIBUTES, 64).unwrap();
+		let (verified, _) = backfill.on_response(&first, blocks(&headers, 170, 198));
+		assert_eq!(verified.len(), 29);
+		assert_eq!(backfill.next_number(), 169);
+
+		let (top, request) = backfill.request(&first, 199, ATTRIBUTES, 64).unwrap();
+		assert_eq!(top, 169);
+		assert_eq!(request.from, FromBlock::Hash(headers[169].hash()));
+		assert_eq!(request.max, Some(169 - 135 + 1));
+	}
+
+	#[test]
+	fn bodies_are_only_requested_within_the_depth() {
+		let headers = chain(200);
+		let mut backfill = backfill(&headers, 1, 150);
+		let (first, second) = (PeerId::random(), PeerId::random());
+
+		let (_, request) = backfill.request(&first, 199, ATTRIBUTES, 64).unwrap();
+		assert_eq!(request.fields, ATTRIBUTES);
+		assert_eq!(request.max, Some(49));
+
+		let (top, request) = backfill.request(&second, 199, ATTRIBUTES, 64).unwrap();
+		assert_eq!(top, 149);
+		assert_eq!(request.fields, BlockAttributes::HEADER);
+		assert_eq!(request.max, Some(64));
+	}
+
+	#[test]
+	fn blocks_not_linking_to_our_chain_are_rejected() {
+		let headers = chain(200);
+		let other_chain = {
+			let mut headers = chain(200);
+			headers[100].extrinsics_root = Hash::repeat_byte(1);
+			headers
+		};
+		let mut backfill = backfill(&headers, 1, 1);
+		let (first, second) = (PeerId::random(), PeerId::random());
+
+		backfill.request(&first, 199, ATTRIBUTES, 64).unwrap();
+		backfill.request(&second, 199, ATTRIBUTES, 64).unwrap();
+		let mut response = blocks(&headers, 71, 134);
+		response[100 - 71] = block_data(&other_chain[100]);
+		backfill.on_response(&second, response);
+
+		// The bad range is only detected once it links to the verified history.
+		let (verified, bad_peer) = backfill.on_response(&first, blocks(&headers, 135, 198));
+		assert_eq!(verified.len(), 64);
+		assert!(matches!(bad_peer, Some(BadPeer(id, _)) if id == second));
+		assert_eq!(backfill.next_number(), 134);
+
+		// And requested again.
+		let (top, _) = backfill.request(&first, 199, ATTRIBUTES, 64).unwrap();
+		assert_eq!(top, 134);
+	}
+
+	#[test]
+	fn peers_without_history_are_skipped() {
+		let headers = chain(100);
+		let mut backfill = backfill(&headers, 1, 1);
+		let peer = PeerId::random();
+
+		backfill.request(&peer, 99, BlockAttributes::HEADER, 64).unwrap();
+		let (verified, bad_peer) = backfill.on_response(&peer, Vec::new());
+		assert!(verified.is_empty() && bad_peer.is_none());
+		assert!(backfill.request(&peer, 99, BlockAttributes::HEADER, 64).is_none());
+
+		// Unless it reconnects.
+		backfill.remove_peer(&peer);
+		assert!(backfill.request(&peer, 99, BlockAttributes::HEADER, 64).is_some());
+	}
+}
@@ -28,7 +28,7 @@ use sc_block_builder::BlockBuilderBuilder;
 use sc_network::RequestFailure;
 use sc_network_common::sync::message::{BlockAnnounce, BlockData, BlockState, FromBlock};
 use sp_blockchain::HeaderBackend;
-use std::sync::Mutex;
+use std::{sync::Mutex, time::Duration};
 use substrate_test_runtime_client::{
 	runtime::{Block, Hash, Header},
 	BlockBuilderExt, ClientBlockImportExt, ClientExt, DefaultTestClientBuilderExt, TestClient,
@@ -878,6 +878,57 @@ fn can_import_response_with_missing_blocks() {
 	sync.on_block_data(&peer_id1, Some(request), response).unwrap();
 	assert_eq!(sync.best_queued_number, 4);
 }
+
+#[test]
+fn stalled_requests_are_rerequested_and_late_responses_dropped() {
+	sp_tracing::try_init_simple();
+	let client2 = TestClientBuilder::new().build();
+	let blocks = (0..4).map(|_| build_block(&client2, None, false)).collect::<Vec<_>>();
+	let best_block = blocks[3].clone();
+
+	let empty_client = Arc::new(TestClientBuilder::new().build());
+
+	let mut sync = ChainSync::new(
+		ChainSyncMode::Full,
+		empty_client.clone(),
+		1,
+		64,
+		ProtocolName::Static(""),
+		Arc::new(MockBlockDownloader::new()),
+		None,
+		std::iter::empty(),
+	)
+	.unwrap();
+
+	let peers = [PeerId::random(), PeerId::random()];
+	for peer_id in peers {
+		sync.add_peer(peer_id, best_block.hash(), *best_block.header().number());
+		sync.peers.get_mut(&peer_id).unwrap().state = PeerSyncState::Available;
+		sync.peers.get_mut(&peer_id).unwrap().common_number = 0;
+	}
+
+	// Only one peer gets the range.
+	let requests = sync.block_requests();
+	assert_eq!(requests.len(), 1);
+	let (stalled_peer, stalled_request) = requests[0].clone();
+	let other_peer = *peers.iter().find(|peer| **peer != stalled_peer).unwrap();
+	assert!(sync.block_requests().is_empty());
+
+	// Once the request stalls, the range is requested from the other peer.
+	sync.reschedule_stalled_requests(Instant::now() + Duration::from_secs(60));
+	let request = get_block_request(&mut sync, stalled_request.from.clone(), 4, &other_peer);
+
+	// The late response of the stalled peer doesn't overwrite the range being re-requested.
+	let response = create_block_response(blocks.iter().rev().cloned().collect());
+	sync.on_block_data(&stalled_peer, Some(stalled_request), response).unwrap();
+	assert_eq!(sync.best_queued_number, 0);
+	assert!(sync.peers.get(&stalled_peer).unwrap().state.is_available());
+
+	let response = create_block_response(blocks.iter().rev().cloned().collect());
+	sync.on_block_data(&other_peer, Some(request), response).unwrap();
+	assert_eq!(sync.best_queued_number, 4);
+}
+
 #[test]
 fn ancestor_search_repeat() {
 	let state = AncestorSearchState::<Block>::BinarySearch(1, 3);
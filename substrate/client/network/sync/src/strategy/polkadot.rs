@@ -26,6 +26,7 @@ use crate::{
 	strategy::{
 		chain_sync::{ChainSync, ChainSyncMode},
 		state::StateStrategy,
+		state_sync::{checkpointed_target, discard_checkpoint, CheckpointedTarget, StateSync},
 		warp::{WarpSync, WarpSyncConfig},
 		StrategyKey, SyncingAction, SyncingStrategy,
 	},
@@ -37,11 +38,24 @@ use prometheus_endpoint::Registry;
 use sc_client_api::{BlockBackend, ProofProvider};
 use sc_consensus::{BlockImportError, BlockImportStatus};
 use sc_network::ProtocolName;
-use sc_network_common::sync::{message::BlockAnnounce, SyncMode};
+use sc_network_common::sync::{message::BlockAnnounce, BlockHistorySync, SyncMode};
 use sc_network_types::PeerId;
 use sp_blockchain::{Error as ClientError, HeaderBackend, HeaderMetadata};
-use sp_runtime::traits::{Block as BlockT, Header, NumberFor};
-use std::{any::Any, collections::HashMap, sync::Arc};
+use sp_runtime::{
+	traits::{Block as BlockT, Header, NumberFor},
+	Justifications,
+};
+use std::{any::Any, collections::HashMap, path::PathBuf, sync::Arc, time::Duration};
+
+/// Maximum age of a state sync checkpoint to resume from.
+///
+/// Peers that don't archive the state are unlikely to keep the state of an older target, and
+/// warp syncing to a recent block is then faster than looking for the few that do.
+const MAX_STATE_SYNC_CHECKPOINT_AGE: Duration = Duration::from_secs(6 * 60 * 60);
+
+/// Number of failed state requests in a row after which a resumed state sync is abandoned in
+/// favor of a new warp sync.
+const MAX_RESUMED_STATE_SYNC_FAILURES: usize = 16;
 
 /// Corresponding `ChainSync` mode.
 fn chain_sync_mode(sync_mode: SyncMode) -> ChainSyncMode {
@@ -73,6 +87,9 @@ where
 	pub state_request_protocol_name: ProtocolName,
 	/// Block downloader
 	pub block_downloader: Arc<dyn BlockDownloader<Block>>,
+	/// Directory where the progress of state sync is checkpointed, allowing it to resume after a
+	/// restart. If `None`, state sync always starts from scratch.
+	pub state_sync_checkpoint_dir: Option<PathBuf>,
 }
 
 /// Proxy to specific syncing strategies used in Polkadot.
@@ -90,6 +107,10 @@ pub struct PolkadotSyncingStrategy<B: BlockT, Client> {
 	/// Connected peers and their best blocks used to seed a new strategy when switching to it in
 	/// `PolkadotSyncingStrategy::proceed_to_next`.
 	peer_best_blocks: HashMap<PeerId, (B::Hash, NumberFor<B>)>,
+	/// Warp sync to start if the state sync resumed from a checkpoint fails.
+	warp_fallback: Option<(WarpSyncConfig<B>, Option<ProtocolName>)>,
+	/// How much block history to download after warp sync.
+	block_history: BlockHistorySync,
 }
 
 impl<B: BlockT, Client> SyncingStrategy<B> for PolkadotSyncingStrategy<B, Client>
@@ -357,6 +378,38 @@ where
 		if let SyncMode::Warp = config.mode {
 			let warp_sync_config = warp_sync_config
 				.expect("Warp sync configuration must be supplied in warp sync mode.");
+
+			if let Some(CheckpointedTarget { header, body, justifications, .. }) =
+				Self::resumable_state_sync(&config, &client, &warp_sync_config)
+			{
+				info!(
+					target: LOG_TARGET,
+					"Found interrupted state sync of #{} ({}), resuming it.",
+					header.number(),
+					header.hash(),
+				);
+				let state_sync = Self::new_state_strategy(
+					&config,
+					client.clone(),
+					header,
+					body,
+					justifications,
+					std::iter::empty(),
+				)
+				.with_max_failed_requests(MAX_RESUMED_STATE_SYNC_FAILURES);
+
+				return Ok(Self {
+					config,
+					client,
+					warp: None,
+					state: Some(state_sync),
+					chain_sync: None,
+					peer_best_blocks: Default::default(),
+					warp_fallback: Some((warp_sync_config, warp_sync_protocol_name)),
+					block_history: BlockHistorySync::default(),
+				})
+			}
+
 			let warp_sync = WarpSync::new(
 				client.clone(),
 				warp_sync_config,
@@ -371,6 +424,8 @@ where
 				state: None,
 				chain_sync: None,
 				peer_best_blocks: Default::default(),
+				warp_fallback: None,
+				block_history: BlockHistorySync::default(),
 			})
 		} else {
 			let chain_sync = ChainSync::new(
@@ -390,10 +445,85 @@ where
 				state: None,
 				chain_sync: Some(chain_sync),
 				peer_best_blocks: Default::default(),
+				warp_fallback: None,
+				block_history: BlockHistorySync::default(),
 			})
 		}
 	}
 
+	/// Set how much block history to download after warp sync.
+	pub fn with_block_history(mut self, block_history: BlockHistorySync) -> Self {
+		self.block_history = block_history;
+		self.chain_sync =
+			self.chain_sync.map(|chain_sync| chain_sync.with_block_history(block_history));
+		self
+	}
+
+	/// Target of the interrupted state sync to resume, if any.
+	///
+	/// A checkpoint is only resumed if it is recent enough for peers to still keep the state of
+	/// its target, and if it targets the block given by `warp_sync_config`, if any. Otherwise it is
+	/// discarded once state sync starts for another target.
+	fn resumable_state_sync(
+		config: &PolkadotSyncingStrategyConfig<B>,
+		client: &Client,
+		warp_sync_config: &WarpSyncConfig<B>,
+	) -> Option<CheckpointedTarget<B>> {
+		if client.info().finalized_state.is_some() {
+			return None
+		}
+		let target = checkpointed_target::<B>(config.state_sync_checkpoint_dir.as_deref()?)?;
+		let hash = target.header.hash();
+
+		let expected_hash = match warp_sync_config {
+			WarpSyncConfig::WithProvider(_) => None,
+			WarpSyncConfig::WithTarget(header) => Some(header.hash()),
+			WarpSyncConfig::WithCheckpoint(checkpoint) => Some(checkpoint.hash),
+		};
+		if expected_hash.map_or(false, |expected_hash| expected_hash != hash) {
+			info!(
+				target: LOG_TARGET,
+				"Not resuming interrupted state sync of #{} ({hash}), another target is configured.",
+				target.header.number(),
+			);
+			return None
+		}
+
+		if target.age > MAX_STATE_SYNC_CHECKPOINT_AGE {
+			info!(
+				target: LOG_TARGET,
+				"Not resuming interrupted state sync of #{} ({hash}), last updated {}s ago.",
+				target.header.number(),
+				target.age.as_secs(),
+			);
+			return None
+		}
+
+		Some(target)
+	}
+
+	/// Create the state strategy downloading the state of `target_header`.
+	fn new_state_strategy(
+		config: &PolkadotSyncingStrategyConfig<B>,
+		client: Arc<Client>,
+		target_header: B::Header,
+		target_body: Option<Vec<B::Extrinsic>>,
+		target_justifications: Option<Justifications>,
+		initial_peers: impl Iterator<Item = (PeerId, NumberFor<B>)>,
+	) -> StateStrategy<B> {
+		let mut state_sync =
+			StateSync::new(client, target_header, target_body, target_justifications, false);
+		if let Some(dir) = &config.state_sync_checkpoint_dir {
+			state_sync = state_sync.with_checkpoint(dir);
+		}
+
+		StateStrategy::new_with_provider(
+			Box::new(state_sync),
+			initial_peers,
+			config.state_request_protocol_name.clone(),
+		)
+	}
+
 	/// Proceed with the next strategy if the active one finished.
 	pub fn proceed_to_next(&mut self) -> Result<(), ClientError> {
 		// The strategies are switched as `WarpSync` -> `StateStrategy` -> `ChainSync`.
@@ -404,16 +534,15 @@ where
 						target: LOG_TARGET,
 						"Warp sync is complete, continuing with state sync."
 					);
-					let state_sync = StateStrategy::new(
+					let state_sync = Self::new_state_strategy(
+						&self.config,
 						self.client.clone(),
 						res.target_header,
 						res.target_body,
 						res.target_justifications,
-						false,
 						self.peer_best_blocks
 							.iter()
 							.map(|(peer_id, (_, best_number))| (*peer_id, *best_number)),
-						self.config.state_request_protocol_name.clone(),
 					);
 
 					self.warp = None;
@@ -437,7 +566,7 @@ where
 							(*peer_id, *best_hash, *best_number)
 						}),
 					) {
-						Ok(chain_sync) => chain_sync,
+						Ok(chain_sync) => chain_sync.with_block_history(self.block_history),
 						Err(e) => {
 							error!(target: LOG_TARGET, "Failed to start `ChainSync`.");
 							return Err(e)
@@ -450,8 +579,32 @@ where
 				},
 			}
 		} else if let Some(state) = &self.state {
+			let warp_fallback = self.warp_fallback.take();
 			if state.is_succeeded() {
 				info!(target: LOG_TARGET, "State sync is complete, continuing with block sync.");
+			} else if let Some((warp_sync_config, warp_sync_protocol_name)) = warp_fallback {
+				warn!(
+					target: LOG_TARGET,
+					"Resumed state sync failed. Discarding its checkpoint and warp syncing again.",
+				);
+				if let Some(dir) = &self.config.state_sync_checkpoint_dir {
+					discard_checkpoint(dir);
+				}
+
+				let mut warp_sync = WarpSync::new(
+					self.client.clone(),
+					warp_sync_config,
+					warp_sync_protocol_name,
+					self.config.block_downloader.clone(),
+					self.config.min_peers_to_start_warp_sync,
+				);
+				for (peer_id, (best_hash, best_number)) in &self.peer_best_blocks {
+					warp_sync.add_peer(*peer_id, *best_hash, *best_number);
+				}
+
+				self.state = None;
+				self.warp = Some(warp_sync);
+				return Ok(())
 			} else {
 				error!(target: LOG_TARGET, "State sync failed. Falling back to full sync.");
 			}
@@ -467,7 +620,7 @@ where
 					(*peer_id, *best_hash, *best_number)
 				}),
 			) {
-				Ok(chain_sync) => chain_sync,
+				Ok(chain_sync) => chain_sync.with_block_history(self.block_history),
 				Err(e) => {
 					error!(target: LOG_TARGET, "Failed to start `ChainSync`.");
 					return Err(e);
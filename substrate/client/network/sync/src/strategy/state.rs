@@ -30,7 +30,7 @@ use crate::{
 	LOG_TARGET,
 };
 use futures::{channel::oneshot, FutureExt};
-use log::{debug, error, trace};
+use log::{debug, error, trace, warn};
 use prost::Message;
 use sc_client_api::ProofProvider;
 use sc_consensus::{BlockImportError, BlockImportStatus, IncomingBlock};
@@ -78,6 +78,10 @@ pub struct StateStrategy<B: BlockT> {
 	actions: Vec<SyncingAction<B>>,
 	protocol_name: ProtocolName,
 	succeeded: bool,
+	/// Number of state requests in a row that failed.
+	failed_requests: usize,
+	/// Give up after this many state requests in a row failed, if set.
+	max_failed_requests: Option<usize>,
 }
 
 impl<B: BlockT> StateStrategy<B> {
@@ -115,6 +119,8 @@ impl<B: BlockT> StateStrategy<B> {
 			actions: Vec::new(),
 			protocol_name,
 			succeeded: false,
+			failed_requests: 0,
+			max_failed_requests: None,
 		}
 	}
 
@@ -138,6 +144,32 @@ impl<B: BlockT> StateStrategy<B> {
 			actions: Vec::new(),
 			protocol_name,
 			succeeded: false,
+			failed_requests: 0,
+			max_failed_requests: None,
+		}
+	}
+
+	/// Give up once `max` state requests in a row failed, for example because peers don't keep the
+	/// state of the target block anymore.
+	///
+	/// The strategy then finishes without succeeding. By default state sync never gives up.
+	pub fn with_max_failed_requests(mut self, max: usize) -> Self {
+		self.max_failed_requests = Some(max);
+		self
+	}
+
+	/// Count a failed state request, giving up if there were too many in a row.
+	fn on_request_failed(&mut self) {
+		self.failed_requests += 1;
+
+		if self.max_failed_requests == Some(self.failed_requests) {
+			warn!(
+				target: LOG_TARGET,
+				"Giving up state sync of #{} after {} failed requests in a row.",
+				self.state_sync.target_number(),
+				self.failed_requests,
+			);
+			self.actions.push(SyncingAction::Finished);
 		}
 	}
 
@@ -150,6 +182,7 @@ impl<B: BlockT> StateStrategy<B> {
 	pub fn remove_peer(&mut self, peer_id: &PeerId) {
 		if let Some(state) = self.peers.remove(peer_id) {
 			if !state.state.is_available() {
+				self.on_request_failed();
 				if let Some(bad_peer) =
 					self.disconnected_peers.on_disconnect_during_request(*peer_id)
 				{
@@ -182,8 +215,12 @@ impl<B: BlockT> StateStrategy<B> {
 
 	/// Process state response.
 	pub fn on_state_response(&mut self, peer_id: &PeerId, response: Vec<u8>) {
-		if let Err(bad_peer) = self.on_state_response_inner(peer_id, &response) {
-			self.actions.push(SyncingAction::DropPeer(bad_peer));
+		match self.on_state_response_inner(peer_id, &response) {
+			Ok(()) => self.failed_requests = 0,
+			Err(bad_peer) => {
+				self.actions.push(SyncingAction::DropPeer(bad_peer));
+				self.on_request_failed();
+			},
 		}
 	}
 
@@ -277,7 +314,10 @@ impl<B: BlockT> StateStrategy<B> {
 					"Failed to import target block with state: {e:?}."
 				);
 			});
-			self.succeeded |= results.into_iter().any(|result| result.is_ok());
+			if results.into_iter().any(|result| result.is_ok()) {
+				self.state_sync.on_imported();
+				self.succeeded = true;
+			}
 			self.actions.push(SyncingAction::Finished);
 		}
 	}
@@ -419,6 +459,7 @@ mod test {
 			fn target_number(&self) -> NumberFor<B>;
 			fn target_hash(&self) -> B::Hash;
 			fn progress(&self) -> StateSyncProgress;
+			fn on_imported(&mut self);
 		}
 	}
 
@@ -675,6 +716,31 @@ mod test {
 		));
 	}
 
+	#[test]
+	fn too_many_failed_requests_finish_strategy() {
+		let mut state_sync_provider = MockStateSync::<Block>::new();
+		state_sync_provider.expect_import().returning(|_| ImportResult::BadResponse);
+		state_sync_provider.expect_target_number().return_const(1u64);
+		let (first, second) = (PeerId::random(), PeerId::random());
+		let mut state_strategy = StateStrategy::new_with_provider(
+			Box::new(state_sync_provider),
+			[(first, 10), (second, 10)].into_iter(),
+			ProtocolName::Static(""),
+		)
+		.with_max_failed_requests(2);
+
+		// A bad response and a peer disconnecting during a request both count as failures.
+		state_strategy.peers.get_mut(&first).unwrap().state = PeerState::DownloadingState;
+		state_strategy.on_state_response(&first, StateResponse::default().encode_to_vec());
+		assert!(!state_strategy.actions.iter().any(SyncingAction::is_finished));
+
+		state_strategy.peers.get_mut(&second).unwrap().state = PeerState::DownloadingState;
+		state_strategy.remove_peer(&second);
+
+		assert!(state_strategy.actions.iter().any(SyncingAction::is_finished));
+		assert!(!state_strategy.is_succeeded());
+	}
+
 	#[test]
 	fn partial_state_response_doesnt_generate_actions() {
 		let mut state_sync_provider = MockStateSync::<Block>::new();
@@ -796,6 +862,7 @@ mod test {
 		let target_hash = Hash::random();
 		let mut state_sync_provider = MockStateSync::<Block>::new();
 		state_sync_provider.expect_target_hash().return_const(target_hash);
+		state_sync_provider.expect_on_imported().times(1).return_const(());
 
 		let mut state_strategy = StateStrategy::new_with_provider(
 			Box::new(state_sync_provider),
@@ -823,6 +890,7 @@ mod test {
 		let target_hash = Hash::random();
 		let mut state_sync_provider = MockStateSync::<Block>::new();
 		state_sync_provider.expect_target_hash().return_const(target_hash);
+		state_sync_provider.expect_on_imported().never();
 
 		let mut state_strategy = StateStrategy::new_with_provider(
 			Box::new(state_sync_provider),
@@ -851,6 +919,7 @@ mod test {
 		let mut state_sync_provider = MockStateSync::<Block>::new();
 		state_sync_provider.expect_target_hash().return_const(target_hash);
 		state_sync_provider.expect_is_complete().return_const(true);
+		state_sync_provider.expect_on_imported().return_const(());
 
 		// Get enough peers for possible spurious requests.
 		let initial_peers = (1..=10).map(|best_number| (PeerId::random(), best_number));
@@ -0,0 +1,331 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+//! On-disk checkpoints of an in-progress state sync.
+//!
+//! A checkpoint consists of two files:
+//!
+//! - an append-only journal holding every verified chunk of key-values received so far, each record
+//!   prefixed by its length;
+//! - a small metadata file, replaced atomically after each response has been journaled, that
+//!   records the sync target, the key cursor to resume from and the length of the journal at that
+//!   point.
+//!
+//! Journal records written after the last metadata update (e.g. because the node stopped in the
+//! middle of processing a response) are discarded when the checkpoint is reopened.
+//!
+//! The journal is only read back once the whole state has been received, so resuming a sync only
+//! reads the metadata file.
+
+use codec::{Decode, Encode};
+
+use std::{
+	fs::{self, File, OpenOptions},
+	io::{self, BufReader, Read, Seek, SeekFrom, Write},
+	path::{Path, PathBuf},
+	time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+/// Name of the metadata file.
+const META_FILE: &str = "checkpoint";
+
+/// Name of the journal file.
+const JOURNAL_FILE: &str = "journal";
+
+/// Key-values of a single trie, keyed by the trie root (empty for the top trie).
+pub(crate) type Chunk = (Vec<u8>, Vec<(Vec<u8>, Vec<u8>)>);
+
+/// Checkpoint metadata.
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode)]
+struct Meta {
+	/// SCALE-encoded hash of the target block.
+	target_hash: Vec<u8>,
+	/// SCALE-encoded target header, body and justifications.
+	target: Vec<u8>,
+	/// Key cursor to resume from.
+	last_key: Vec<Vec<u8>>,
+	/// Bytes imported so far, used for progress reporting.
+	imported_bytes: u64,
+	/// Length of the journal covered by this checkpoint.
+	journal_len: u64,
+	/// Seconds since the UNIX epoch at which the checkpoint was last updated.
+	updated_at: u64,
+}
+
+/// Progress restored from a checkpoint.
+#[derive(Debug)]
+pub(crate) struct RestoredProgress {
+	/// Key cursor to resume from.
+	pub last_key: Vec<Vec<u8>>,
+	/// Bytes imported so far.
+	pub imported_bytes: u64,
+}
+
+/// Checkpoint of an in-progress state sync.
+#[derive(Debug)]
+pub(crate) struct StateSyncCheckpoint {
+	dir: PathBuf,
+	journal: File,
+	journal_len: u64,
+	target_hash: Vec<u8>,
+	target: Vec<u8>,
+}
+
+impl StateSyncCheckpoint {
+	/// Read the encoded target of the checkpoint stored in `dir`, if any, together with the time
+	/// the checkpoint was last updated.
+	pub fn stored_target(dir: &Path) -> Option<(Vec<u8>, SystemTime)> {
+		read_meta(dir)
+			.ok()
+			.flatten()
+			.map(|meta| (meta.target, UNIX_EPOCH + Duration::from_secs(meta.updated_at)))
+	}
+
+	/// Remove the checkpoint stored in `dir`, if any.
+	pub fn discard(dir: &Path) -> io::Result<()> {
+		for file in [META_FILE, JOURNAL_FILE] {
+			match fs::remove_file(dir.join(file)) {
+				Err(error) if error.kind() != io::ErrorKind::NotFound => return Err(error),
+				_ => {},
+			}
+		}
+
+		Ok(())
+	}
+
+	/// Open a checkpoint in `dir` for the target block with the given encoded hash.
+	///
+	/// If `dir` holds a checkpoint of the same target, the progress recorded in it is returned.
+	/// Checkpoints of any other target are discarded.
+	pub fn open(
+		dir: &Path,
+		target_hash: Vec<u8>,
+		target: Vec<u8>,
+	) -> io::Result<(Self, Option<RestoredProgress>)> {
+		fs::create_dir_all(dir)?;
+
+		let meta = match read_meta(dir) {
+			Ok(meta) => meta.filter(|meta| meta.target_hash == target_hash),
+			Err(error) => {
+				log::debug!(
+					target: crate::LOG_TARGET,
+					"Discarding unreadable state sync checkpoint in {}: {error}",
+					dir.display(),
+				);
+				None
+			},
+		};
+
+		let journal_path = dir.join(JOURNAL_FILE);
+		let mut journal =
+			OpenOptions::new().read(true).write(true).create(true).open(&journal_path)?;
+
+		let Some(meta) = meta else {
+			// Nothing to resume from, start a fresh checkpoint.
+			let _ = fs::remove_file(dir.join(META_FILE));
+			journal.set_len(0)?;
+
+			return Ok((
+				Self { dir: dir.into(), journal, journal_len: 0, target_hash, target },
+				None,
+			))
+		};
+
+		if journal.metadata()?.len() < meta.journal_len {
+			return Err(io::Error::new(io::ErrorKind::UnexpectedEof, "state sync journal truncated"))
+		}
+		// Drop records that were never committed and append after the committed ones.
+		journal.set_len(meta.journal_len)?;
+		journal.seek(SeekFrom::Start(meta.journal_len))?;
+
+		let progress =
+			RestoredProgress { last_key: meta.last_key, imported_bytes: meta.imported_bytes };
+
+		Ok((
+			Self { dir: dir.into(), journal, journal_len: meta.journal_len, target_hash, target },
+			Some(progress),
+		))
+	}
+
+	/// Append a chunk of verified key-values to the journal.
+	///
+	/// The chunk only becomes part of the checkpoint once [`Self::commit`] is called.
+	pub fn append(
+		&mut self,
+		state_root: &[u8],
+		key_values: &[(Vec<u8>, Vec<u8>)],
+	) -> io::Result<()> {
+		let record = (state_root, key_values).encode();
+		let len = u32::try_from(record.len())
+			.map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "state chunk too large"))?;
+
+		self.journal.write_all(&len.to_le_bytes())?;
+		self.journal.write_all(&record)?;
+		self.journal_len += 4 + record.len() as u64;
+
+		Ok(())
+	}
+
+	/// Make the journaled chunks part of the checkpoint, together with the cursor to resume from.
+	pub fn commit(&mut self, last_key: &[Vec<u8>], imported_bytes: u64) -> io::Result<()> {
+		self.journal.sync_data()?;
+
+		let meta = Meta {
+			target_hash: self.target_hash.clone(),
+			target: self.target.clone(),
+			last_key: last_key.to_vec(),
+			imported_bytes,
+			journal_len: self.journal_len,
+			updated_at: SystemTime::now()
+				.duration_since(UNIX_EPOCH)
+				.map_or(0, |elapsed| elapsed.as_secs()),
+		};
+		let path = self.dir.join(META_FILE);
+		let tmp_path = path.with_extension("tmp");
+		fs::write(&tmp_path, meta.encode())?;
+		fs::rename(tmp_path, path)
+	}
+
+	/// Read back all the chunks appended to the journal, including uncommitted ones, in the order
+	/// they were appended.
+	pub fn chunks(&self) -> io::Result<impl Iterator<Item = io::Result<Chunk>>> {
+		let journal = File::open(self.dir.join(JOURNAL_FILE))?;
+		let mut reader = BufReader::new(journal.take(self.journal_len));
+
+		Ok(std::iter::from_fn(move || read_record(&mut reader).transpose()))
+	}
+
+	/// Remove the checkpoint from disk.
+	pub fn remove(self) -> io::Result<()> {
+		drop(self.journal);
+		Self::discard(&self.dir)
+	}
+}
+
+fn read_meta(dir: &Path) -> io::Result<Option<Meta>> {
+	let bytes = match fs::read(dir.join(META_FILE)) {
+		Ok(bytes) => bytes,
+		Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(None),
+		Err(error) => return Err(error),
+	};
+
+	Meta::decode(&mut &bytes[..])
+		.map(Some)
+		.map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error.to_string()))
+}
+
+/// Read the next journal record, returning `None` at the end of the journal.
+fn read_record(reader: &mut impl Read) -> io::Result<Option<Chunk>> {
+	let mut len_bytes = [0u8; 4];
+	match reader.read_exact(&mut len_bytes) {
+		Ok(()) => {},
+		Err(error) if error.kind() == io::ErrorKind::UnexpectedEof => return Ok(None),
+		Err(error) => return Err(error),
+	}
+
+	let mut record = vec![0u8; u32::from_le_bytes(len_bytes) as usize];
+	reader.read_exact(&mut record)?;
+	Chunk::decode(&mut &record[..])
+		.map(Some)
+		.map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn chunk(root: &[u8], keys: &[&[u8]]) -> Chunk {
+		(root.to_vec(), keys.iter().map(|key| (key.to_vec(), vec![0u8; 8])).collect())
+	}
+
+	fn chunks(checkpoint: &StateSyncCheckpoint) -> Vec<Chunk> {
+		checkpoint.chunks().unwrap().collect::<io::Result<_>>().unwrap()
+	}
+
+	#[test]
+	fn committed_progress_is_restored() {
+		let dir = tempfile::tempdir().unwrap();
+		let (first, second) = (chunk(b"", &[b"a", b"b"]), chunk(b"child", &[b"c"]));
+
+		let (mut checkpoint, restored) =
+			StateSyncCheckpoint::open(dir.path(), vec![1], vec![2]).unwrap();
+		assert!(restored.is_none());
+
+		checkpoint.append(&first.0, &first.1).unwrap();
+		checkpoint.append(&second.0, &second.1).unwrap();
+		checkpoint.commit(&[b"b".to_vec(), b"c".to_vec()], 42).unwrap();
+		drop(checkpoint);
+
+		let (target, updated_at) = StateSyncCheckpoint::stored_target(dir.path()).unwrap();
+		assert_eq!(target, vec![2]);
+		assert!(updated_at.elapsed().unwrap() < Duration::from_secs(60));
+
+		let (checkpoint, restored) =
+			StateSyncCheckpoint::open(dir.path(), vec![1], vec![2]).unwrap();
+		let restored = restored.unwrap();
+		assert_eq!(restored.last_key, vec![b"b".to_vec(), b"c".to_vec()]);
+		assert_eq!(restored.imported_bytes, 42);
+		assert_eq!(chunks(&checkpoint), vec![first, second]);
+	}
+
+	#[test]
+	fn uncommitted_chunks_are_discarded() {
+		let dir = tempfile::tempdir().unwrap();
+		let (first, second) = (chunk(b"", &[b"a"]), chunk(b"", &[b"b"]));
+
+		let (mut checkpoint, _) = StateSyncCheckpoint::open(dir.path(), vec![1], vec![]).unwrap();
+		checkpoint.append(&first.0, &first.1).unwrap();
+		checkpoint.commit(&[b"a".to_vec()], 1).unwrap();
+		checkpoint.append(&second.0, &second.1).unwrap();
+		// uncommitted chunks are read back until the checkpoint is reopened
+		assert_eq!(chunks(&checkpoint), vec![first.clone(), second.clone()]);
+		drop(checkpoint);
+
+		let (mut checkpoint, restored) =
+			StateSyncCheckpoint::open(dir.path(), vec![1], vec![]).unwrap();
+		assert_eq!(restored.unwrap().last_key, vec![b"a".to_vec()]);
+		assert_eq!(chunks(&checkpoint), vec![first.clone()]);
+
+		// appending after reopening continues from the committed part of the journal
+		checkpoint.append(&second.0, &second.1).unwrap();
+		checkpoint.commit(&[b"b".to_vec()], 2).unwrap();
+		drop(checkpoint);
+
+		let (checkpoint, _) = StateSyncCheckpoint::open(dir.path(), vec![1], vec![]).unwrap();
+		assert_eq!(chunks(&checkpoint), vec![first, second]);
+	}
+
+	#[test]
+	fn checkpoint_of_another_target_is_discarded() {
+		let dir = tempfile::tempdir().unwrap();
+		let first = chunk(b"", &[b"a"]);
+
+		let (mut checkpoint, _) = StateSyncCheckpoint::open(dir.path(), vec![1], vec![]).unwrap();
+		checkpoint.append(&first.0, &first.1).unwrap();
+		checkpoint.commit(&[b"a".to_vec()], 1).unwrap();
+		drop(checkpoint);
+
+		let (checkpoint, restored) =
+			StateSyncCheckpoint::open(dir.path(), vec![2], vec![]).unwrap();
+		assert!(restored.is_none());
+		assert!(chunks(&checkpoint).is_empty());
+		assert_eq!(StateSyncCheckpoint::stored_target(dir.path()), None);
+
+		checkpoint.remove().unwrap();
+		assert!(StateSyncCheckpoint::open(dir.path(), vec![2], vec![]).unwrap().1.is_none());
+	}
+}
@@ -20,10 +20,11 @@
 
 use crate::{
 	schema::v1::{KeyValueStateEntry, StateEntry, StateRequest, StateResponse},
+	strategy::state_checkpoint::StateSyncCheckpoint,
 	LOG_TARGET,
 };
 use codec::{Decode, Encode};
-use log::debug;
+use log::{debug, info, warn};
 use sc_client_api::{CompactProof, KeyValueStates, ProofProvider};
 use sc_consensus::ImportedState;
 use smallvec::SmallVec;
@@ -32,7 +33,13 @@ use sp_runtime::{
 	traits::{Block as BlockT, Header, NumberFor},
 	Justifications,
 };
-use std::{collections::HashMap, fmt, sync::Arc};
+use std::{
+	collections::HashMap,
+	fmt, io,
+	path::Path,
+	sync::Arc,
+	time::{Duration, SystemTime},
+};
 
 /// Generic state sync provider. Used for mocking in tests.
 pub trait StateSyncProvider<B: BlockT>: Send + Sync {
@@ -48,6 +55,8 @@ pub trait StateSyncProvider<B: BlockT>: Send + Sync {
 	fn target_hash(&self) -> B::Hash;
 	/// Returns state sync estimated progress.
 	fn progress(&self) -> StateSyncProgress;
+	/// Notify that the target block was imported with the downloaded state.
+	fn on_imported(&mut self) {}
 }
 
 // Reported state sync phase.
@@ -136,13 +145,99 @@ impl<B: BlockT> StateSyncMetadata<B> {
 	}
 }
 
+/// Target of the state sync checkpointed in a directory.
+pub struct CheckpointedTarget<B: BlockT> {
+	/// Target block header.
+	pub header: B::Header,
+	/// Target block body, if it was downloaded.
+	pub body: Option<Vec<B::Extrinsic>>,
+	/// Target block justifications.
+	pub justifications: Option<Justifications>,
+	/// Time elapsed since the checkpoint was last updated.
+	pub age: Duration,
+}
+
+/// Read the target of the state sync checkpointed in `dir`, if any.
+///
+/// The target was verified before the state sync started, so syncing can resume from it
+/// directly after a restart.
+pub fn checkpointed_target<B: BlockT>(dir: &Path) -> Option<CheckpointedTarget<B>> {
+	let (target, updated_at) = StateSyncCheckpoint::stored_target(dir)?;
+	let (header, body, justifications) = Decode::decode(&mut &target[..]).ok()?;
+	let age = SystemTime::now().duration_since(updated_at).unwrap_or_default();
+
+	Some(CheckpointedTarget { header, body, justifications, age })
+}
+
+/// Remove the state sync checkpoint stored in `dir`, if any.
+pub fn discard_checkpoint(dir: &Path) {
+	if let Err(error) = StateSyncCheckpoint::discard(dir) {
+		warn!(
+			target: LOG_TARGET,
+			"Failed to remove state sync checkpoint in {}: {error}",
+			dir.display(),
+		);
+	}
+}
+
+/// Key-values received so far, along with the storage keys of child tries, by trie root.
+type PartialState = HashMap<Vec<u8>, (Vec<(Vec<u8>, Vec<u8>)>, Vec<Vec<u8>>)>;
+
+/// Add key-values of the trie with `state_root` to `state`, returning the number of bytes added.
+fn add_key_values(
+	state: &mut PartialState,
+	state_root: Vec<u8>,
+	key_values: impl IntoIterator<Item = (Vec<u8>, Vec<u8>)>,
+) -> u64 {
+	let is_top = state_root.is_empty();
+	let entry = state.entry(state_root).or_default();
+
+	if entry.0.len() > 0 && entry.1.len() > 1 {
+		// Already imported child_trie with same root.
+		// Warning this will not work with parallel download.
+		return 0;
+	}
+
+	let mut child_storage_roots = Vec::new();
+	let mut imported_bytes = 0;
+
+	for (key, value) in key_values {
+		// Skip all child key root (will be recalculated on import)
+		if is_top && well_known_keys::is_child_storage_key(key.as_slice()) {
+			child_storage_roots.push((value, key));
+		} else {
+			imported_bytes += key.len() as u64;
+			entry.0.push((key, value));
+		}
+	}
+
+	for (root, storage_key) in child_storage_roots {
+		state.entry(root).or_default().1.push(storage_key);
+	}
+
+	imported_bytes
+}
+
+/// Add all the key-values journaled in `checkpoint` to `state`.
+fn load_checkpoint(checkpoint: &StateSyncCheckpoint, state: &mut PartialState) -> io::Result<()> {
+	checkpoint.chunks()?.try_for_each(|chunk| {
+		let (state_root, key_values) = chunk?;
+		add_key_values(state, state_root, key_values);
+		Ok(())
+	})
+}
+
 /// State sync state machine.
 ///
-/// Accumulates partial state data until it is ready to be imported.
+/// Accumulates partial state data until it is ready to be imported. With a checkpoint, the data
+/// is kept on disk instead and only read back once the state is complete.
 pub struct StateSync<B: BlockT, Client> {
 	metadata: StateSyncMetadata<B>,
-	state: HashMap<Vec<u8>, (Vec<(Vec<u8>, Vec<u8>)>, Vec<Vec<u8>>)>,
+	state: PartialState,
 	client: Arc<Client>,
+	checkpoint: Option<StateSyncCheckpoint>,
+	/// Set when the checkpoint failed in a way that lost received data.
+	needs_restart: bool,
 }
 
 impl<B, Client> StateSync<B, Client>
@@ -170,6 +265,81 @@ where
 				skip_proof,
 			},
 			state: HashMap::default(),
+			checkpoint: None,
+			needs_restart: false,
+		}
+	}
+
+	/// Checkpoint the progress of the state sync in `dir`.
+	///
+	/// Received key-values are journaled to disk instead of being kept in memory, and the sync
+	/// resumes from the last verified key if `dir` already holds a checkpoint of the same target
+	/// block. The whole state is still read into memory once complete, as that is what the import
+	/// expects.
+	pub fn with_checkpoint(mut self, dir: &Path) -> Self {
+		let target = (
+			&self.metadata.target_header,
+			&self.metadata.target_body,
+			&self.metadata.target_justifications,
+		)
+			.encode();
+
+		let (checkpoint, restored) =
+			match StateSyncCheckpoint::open(dir, self.metadata.target_hash().encode(), target) {
+				Ok(result) => result,
+				Err(error) => {
+					warn!(
+						target: LOG_TARGET,
+						"Failed to open state sync checkpoint in {}: {error}",
+						dir.display(),
+					);
+					return self
+				},
+			};
+
+		if let Some(restored) = restored {
+			info!(
+				target: LOG_TARGET,
+				"Resuming state sync of #{} ({}) from checkpoint, {} bytes already imported",
+				self.metadata.target_number(),
+				self.metadata.target_hash(),
+				restored.imported_bytes,
+			);
+
+			self.metadata.last_key = restored.last_key.into();
+			self.metadata.imported_bytes = restored.imported_bytes;
+		}
+
+		self.checkpoint = Some(checkpoint);
+		self
+	}
+
+	/// Stop checkpointing, moving the key-values received so far into memory.
+	fn disable_checkpoint(&mut self) {
+		let Some(checkpoint) = self.checkpoint.take() else { return };
+
+		if let Err(error) = load_checkpoint(&checkpoint, &mut self.state) {
+			warn!(target: LOG_TARGET, "Failed to read state sync checkpoint, restarting: {error}");
+			self.needs_restart = true;
+		}
+		let _ = checkpoint.remove();
+	}
+
+	/// Commit the journaled chunks together with the current key cursor.
+	///
+	/// The last chunk of a complete state is not committed: the checkpoint is kept as is until
+	/// the state is imported, so that a restarted sync downloads the last chunk again if the
+	/// import does not succeed.
+	fn update_checkpoint(&mut self, complete: bool) {
+		let Some(checkpoint) = &mut self.checkpoint else { return };
+		if complete {
+			return
+		}
+
+		if let Err(error) =
+			checkpoint.commit(self.metadata.last_key.as_slice(), self.metadata.imported_bytes)
+		{
+			warn!(target: LOG_TARGET, "Failed to update state sync checkpoint: {error}");
 		}
 	}
 
@@ -178,31 +348,59 @@ where
 		state_root: Vec<u8>,
 		key_values: impl IntoIterator<Item = (Vec<u8>, Vec<u8>)>,
 	) {
-		let is_top = state_root.is_empty();
-
-		let entry = self.state.entry(state_root).or_default();
+		let Some(checkpoint) = &mut self.checkpoint else {
+			self.metadata.imported_bytes += add_key_values(&mut self.state, state_root, key_values);
+			return
+		};
 
-		if entry.0.len() > 0 && entry.1.len() > 1 {
-			// Already imported child_trie with same root.
-			// Warning this will not work with parallel download.
-			return;
+		let is_top = state_root.is_empty();
+		let key_values = key_values.into_iter().collect::<Vec<_>>();
+		match checkpoint.append(&state_root, &key_values) {
+			Ok(()) =>
+				self.metadata.imported_bytes += key_values
+					.iter()
+					.filter(|(key, _)| !(is_top && well_known_keys::is_child_storage_key(key)))
+					.map(|(key, _)| key.len() as u64)
+					.sum::<u64>(),
+			Err(error) => {
+				warn!(target: LOG_TARGET, "Failed to checkpoint state sync, disabling: {error}");
+				self.disable_checkpoint();
+				self.metadata.imported_bytes +=
+					add_key_values(&mut self.state, state_root, key_values);
+			},
 		}
+	}
 
-		let mut child_storage_roots = Vec::new();
+	/// Take the complete state, reading it back from the checkpoint if there is one.
+	fn take_state(&mut self) -> PartialState {
+		if let Some(checkpoint) = &self.checkpoint {
+			let mut state = PartialState::default();
 
-		for (key, value) in key_values {
-			// Skip all child key root (will be recalculated on import)
-			if is_top && well_known_keys::is_child_storage_key(key.as_slice()) {
-				child_storage_roots.push((value, key));
-			} else {
-				self.metadata.imported_bytes += key.len() as u64;
-				entry.0.push((key, value));
+			match load_checkpoint(checkpoint, &mut state) {
+				Ok(()) => return state,
+				Err(error) => {
+					warn!(
+						target: LOG_TARGET,
+						"Failed to read state sync checkpoint, restarting: {error}",
+					);
+					self.needs_restart = true;
+				},
 			}
 		}
 
-		for (root, storage_key) in child_storage_roots {
-			self.state.entry(root).or_default().1.push(storage_key);
+		std::mem::take(&mut self.state)
+	}
+
+	/// Start downloading the state from scratch, without checkpoint.
+	fn restart(&mut self) {
+		if let Some(checkpoint) = self.checkpoint.take() {
+			let _ = checkpoint.remove();
 		}
+		self.needs_restart = false;
+		self.state.clear();
+		self.metadata.last_key.clear();
+		self.metadata.imported_bytes = 0;
+		self.metadata.complete = false;
 	}
 
 	fn process_state_verified(&mut self, values: KeyValueStates) {
@@ -301,13 +499,21 @@ where
 		} else {
 			self.process_state_unverified(response)
 		};
-		if complete {
+		self.update_checkpoint(complete);
+
+		let state = complete.then(|| self.take_state());
+		if self.needs_restart {
+			self.restart();
+			return ImportResult::Continue
+		}
+
+		if let Some(state) = state {
 			self.metadata.complete = true;
 			let target_hash = self.metadata.target_hash();
 			ImportResult::Import(
 				target_hash,
 				self.metadata.target_header.clone(),
-				ImportedState { block: target_hash, state: std::mem::take(&mut self.state).into() },
+				ImportedState { block: target_hash, state: state.into() },
 				self.metadata.target_body.clone(),
 				self.metadata.target_justifications.clone(),
 			)
@@ -340,4 +546,104 @@ where
 	fn progress(&self) -> StateSyncProgress {
 		self.metadata.progress()
 	}
+
+	/// Remove the checkpoint once the state is imported.
+	fn on_imported(&mut self) {
+		let Some(checkpoint) = self.checkpoint.take() else { return };
+
+		if let Err(error) = checkpoint.remove() {
+			warn!(target: LOG_TARGET, "Failed to remove state sync checkpoint: {error}");
+		}
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use sc_block_builder::BlockBuilderBuilder;
+	use substrate_test_runtime_client::{
+		runtime::Block, DefaultTestClientBuilderExt, TestClientBuilder, TestClientBuilderExt,
+	};
+
+	fn response(keys: &[&[u8]], complete: bool) -> StateResponse {
+		StateResponse {
+			entries: vec![KeyValueStateEntry {
+				state_root: Vec::new(),
+				entries: keys
+					.iter()
+					.map(|key| StateEntry { key: key.to_vec(), value: vec![1] })
+					.collect(),
+				complete,
+			}],
+			proof: Vec::new(),
+		}
+	}
+
+	#[test]
+	fn state_sync_resumes_from_checkpoint() {
+		let dir = tempfile::tempdir().unwrap();
+		let client = Arc::new(TestClientBuilder::new().set_no_genesis().build());
+		let target_header = BlockBuilderBuilder::new(&*client)
+			.on_parent_block(client.chain_info().best_hash)
+			.with_parent_block_number(client.chain_info().best_number)
+			.build()
+			.unwrap()
+			.build()
+			.unwrap()
+			.block
+			.header()
+			.clone();
+		let new_state_sync = || {
+			StateSync::new(client.clone(), target_header.clone(), None, None, true)
+				.with_checkpoint(dir.path())
+		};
+
+		let mut state_sync = new_state_sync();
+		assert!(matches!(
+			state_sync.import(response(&[b"a", b"b"], false)),
+			ImportResult::Continue
+		));
+		// received key-values are only kept on disk
+		assert!(state_sync.state.is_empty());
+		drop(state_sync);
+
+		assert_eq!(
+			checkpointed_target::<Block>(dir.path()).map(|target| target.header),
+			Some(target_header.clone()),
+		);
+
+		// a restarted sync continues after the last received key
+		let mut state_sync = new_state_sync();
+		assert_eq!(state_sync.next_request().start, vec![b"b".to_vec()]);
+		assert_eq!(state_sync.progress().size, 2);
+
+		match state_sync.import(response(&[b"c"], true)) {
+			ImportResult::Import(_, _, state, _, _) => {
+				let keys = state
+					.state
+					.0
+					.into_iter()
+					.flat_map(|level| level.key_values)
+					.map(|(key, _)| key)
+					.collect::<Vec<_>>();
+				assert_eq!(keys, vec![b"a".to_vec(), b"b".to_vec(), b"c".to_vec()]);
+			},
+			_ => panic!("state should be complete"),
+		}
+
+		// the checkpoint is kept until the state is imported
+		assert!(checkpointed_target::<Block>(dir.path()).is_some());
+		drop(state_sync);
+
+		// a sync restarted before the import downloads the last chunk again
+		let mut state_sync = new_state_sync();
+		assert_eq!(state_sync.next_request().start, vec![b"b".to_vec()]);
+		assert!(matches!(
+			state_sync.import(response(&[b"c"], true)),
+			ImportResult::Import(_, _, _, _, _)
+		));
+
+		state_sync.on_imported();
+		assert!(checkpointed_target::<Block>(dir.path()).is_none());
+	}
 }
@@ -159,6 +159,23 @@ pub enum WarpSyncConfig<Block: BlockT> {
 	///
 	/// It is expected that the header provider ensures that the header is trusted.
 	WithTarget(<Block as BlockT>::Header),
+	/// Checkpoint sync: skip downloading proofs and sync the state of a trusted finalized block.
+	///
+	/// Unlike [`WarpSyncConfig::WithTarget`] the header does not have to be known in advance, in
+	/// which case it is downloaded from peers and checked against the trusted hash. This makes it
+	/// possible to sync chains without finality proofs, e.g. ones using Aura without GRANDPA.
+	WithCheckpoint(TrustedCheckpoint<Block>),
+}
+
+/// Trusted finalized block used by [`WarpSyncConfig::WithCheckpoint`].
+#[derive(Debug, Clone)]
+pub struct TrustedCheckpoint<Block: BlockT> {
+	/// Hash of the block.
+	pub hash: Block::Hash,
+	/// Header of the block, if known. Must hash to `hash`.
+	pub header: Option<Block::Header>,
+	/// Justifications the block is imported with, overriding the ones sent by peers.
+	pub justifications: Option<Justifications>,
 }
 
 /// Warp sync phase used by warp sync state machine.
@@ -174,6 +191,8 @@ enum Phase<B: BlockT> {
 	},
 	/// Downloading target block.
 	TargetBlock(B::Header),
+	/// Downloading target block of which only the trusted hash is known.
+	TargetHash(B::Hash),
 	/// Warp sync is complete.
 	Complete,
 }
@@ -193,6 +212,17 @@ impl PeerState {
 struct Peer<B: BlockT> {
 	best_number: NumberFor<B>,
 	state: PeerState,
+	/// Best block of the peer when it answered that it doesn't know the target block.
+	///
+	/// Only set while the target is known by hash. The peer isn't asked again until it announces
+	/// a better block.
+	missing_target_at: Option<NumberFor<B>>,
+}
+
+impl<B: BlockT> Peer<B> {
+	fn may_have_target(&self) -> bool {
+		self.missing_target_at.map_or(true, |number| self.best_number > number)
+	}
 }
 
 pub struct WarpSyncResult<B: BlockT> {
@@ -215,6 +245,8 @@ pub struct WarpSync<B: BlockT, Client> {
 	result: Option<WarpSyncResult<B>>,
 	/// Number of peers that need to be connected before warp sync is started.
 	min_peers_to_start_warp_sync: usize,
+	/// Justifications of a trusted checkpoint, imported with the target block.
+	trusted_justifications: Option<Justifications>,
 }
 
 impl<B, Client> WarpSync<B, Client>
@@ -254,13 +286,22 @@ where
 				actions: vec![SyncingAction::Finished],
 				result: None,
 				min_peers_to_start_warp_sync,
+				trusted_justifications: None,
 			}
 		}
 
+		let mut trusted_justifications = None;
 		let phase = match warp_sync_config {
 			WarpSyncConfig::WithProvider(warp_sync_provider) =>
 				Phase::WaitingForPeers { warp_sync_provider },
 			WarpSyncConfig::WithTarget(target_header) => Phase::TargetBlock(target_header),
+			WarpSyncConfig::WithCheckpoint(checkpoint) => {
+				trusted_justifications = checkpoint.justifications;
+				match checkpoint.header {
+					Some(header) => Phase::TargetBlock(header),
+					None => Phase::TargetHash(checkpoint.hash),
+				}
+			},
 		};
 
 		Self {
@@ -275,12 +316,16 @@ where
 			actions: Vec::new(),
 			result: None,
 			min_peers_to_start_warp_sync,
+			trusted_justifications,
 		}
 	}
 
 	/// Notify that a new peer has connected.
 	pub fn add_peer(&mut self, peer_id: PeerId, _best_hash: B::Hash, best_number: NumberFor<B>) {
-		self.peers.insert(peer_id, Peer { best_number, state: PeerState::Available });
+		self.peers.insert(
+			peer_id,
+			Peer { best_number, state: PeerState::Available, missing_target_at: None },
+		);
 
 		self.try_to_start_warp_sync();
 	}
@@ -452,12 +497,29 @@ where
 			peer.state = PeerState::Available;
 		}
 
-		let Phase::TargetBlock(header) = &mut self.phase else {
-			debug!(target: LOG_TARGET, "Unexpected target block response from {peer_id}");
-			return Err(BadPeer(peer_id, rep::UNEXPECTED_RESPONSE))
+		let target_hash = match &self.phase {
+			Phase::TargetBlock(header) => header.hash(),
+			Phase::TargetHash(hash) => *hash,
+			_ => {
+				debug!(target: LOG_TARGET, "Unexpected target block response from {peer_id}");
+				return Err(BadPeer(peer_id, rep::UNEXPECTED_RESPONSE))
+			},
 		};
 
 		if blocks.is_empty() {
+			// The number of a target known only by hash is unknown, so the peer may simply not
+			// have synced up to it yet.
+			if matches!(self.phase, Phase::TargetHash(_)) {
+				debug!(
+					target: LOG_TARGET,
+					"Peer {peer_id} doesn't know the target block {target_hash} yet",
+				);
+				if let Some(peer) = self.peers.get_mut(&peer_id) {
+					peer.missing_target_at = Some(peer.best_number);
+				}
+				return Ok(())
+			}
+
 			debug!(
 				target: LOG_TARGET,
 				"Downloading target block failed: empty block response from {peer_id}",
@@ -478,7 +540,7 @@ where
 
 		let block = blocks.pop().expect("`blocks` len checked above; qed");
 
-		let Some(block_header) = &block.header else {
+		let Some(block_header) = block.header else {
 			debug!(
 				target: LOG_TARGET,
 				"Downloading target block failed: missing header in response from {peer_id}.",
@@ -486,7 +548,7 @@ where
 			return Err(BadPeer(peer_id, rep::VERIFICATION_FAIL))
 		};
 
-		if block_header != header {
+		if block_header.hash() != target_hash {
 			debug!(
 				target: LOG_TARGET,
 				"Downloading target block failed: different header in response from {peer_id}.",
@@ -503,9 +565,9 @@ where
 		}
 
 		self.result = Some(WarpSyncResult {
-			target_header: header.clone(),
+			target_header: block_header,
 			target_body: block.body,
-			target_justifications: block.justifications,
+			target_justifications: self.trusted_justifications.take().or(block.justifications),
 		});
 		self.phase = Phase::Complete;
 		self.actions.push(SyncingAction::Finished);
@@ -530,6 +592,7 @@ where
 		for (peer_id, peer) in self.peers.iter_mut() {
 			if peer.state.is_available() &&
 				peer.best_number >= threshold &&
+				peer.may_have_target() &&
 				self.disconnected_peers.is_peer_available(peer_id)
 			{
 				peer.state = new_state;
@@ -573,7 +636,12 @@ where
 
 	/// Produce target block request.
 	fn target_block_request(&mut self) -> Option<(PeerId, BlockRequest<B>)> {
-		let Phase::TargetBlock(target_header) = &self.phase else { return None };
+		// Cut the borrowing tie. The number of a target known only by hash is unknown.
+		let (target_hash, target_number) = match &self.phase {
+			Phase::TargetBlock(header) => (header.hash(), Some(*header.number())),
+			Phase::TargetHash(hash) => (*hash, None),
+			_ => return None,
+		};
 
 		if self
 			.peers
@@ -584,16 +652,11 @@ where
 			return None
 		}
 
-		// Cut the borrowing tie.
-		let target_hash = target_header.hash();
-		let target_number = *target_header.number();
-
-		let peer_id =
-			self.schedule_next_peer(PeerState::DownloadingTargetBlock, Some(target_number))?;
+		let peer_id = self.schedule_next_peer(PeerState::DownloadingTargetBlock, target_number)?;
 
 		trace!(
 			target: LOG_TARGET,
-			"New target block request to {peer_id}, target: {} ({}).",
+			"New target block request to {peer_id}, target: {} ({:?}).",
 			target_hash,
 			target_number,
 		);
@@ -625,7 +688,7 @@ where
 				phase: WarpSyncPhase::DownloadingWarpProofs,
 				total_bytes: self.total_proof_bytes,
 			},
-			Phase::TargetBlock(_) => WarpSyncProgress {
+			Phase::TargetBlock(_) | Phase::TargetHash(_) => WarpSyncProgress {
 				phase: WarpSyncPhase::DownloadingTargetBlock,
 				total_bytes: self.total_proof_bytes,
 			},
@@ -648,12 +711,14 @@ where
 				Phase::WaitingForPeers { .. } => SyncState::Downloading { target: Zero::zero() },
 				Phase::WarpProof { .. } => SyncState::Downloading { target: Zero::zero() },
 				Phase::TargetBlock(header) => SyncState::Downloading { target: *header.number() },
+				Phase::TargetHash(_) => SyncState::Downloading { target: Zero::zero() },
 				Phase::Complete => SyncState::Idle,
 			},
 			best_seen_block: match &self.phase {
 				Phase::WaitingForPeers { .. } => None,
 				Phase::WarpProof { .. } => None,
 				Phase::TargetBlock(header) => Some(*header.number()),
+				Phase::TargetHash(_) => None,
 				Phase::Complete => None,
 			},
 			num_peers: self.peers.len().saturated_into(),
@@ -1718,4 +1783,87 @@ mod test {
 		assert_eq!(result.target_body, body);
 		assert_eq!(result.target_justifications, justifications);
 	}
+
+	#[test]
+	fn checkpoint_target_known_by_hash_is_downloaded_and_verified() {
+		let client = Arc::new(TestClientBuilder::new().set_no_genesis().build());
+		let target_block = BlockBuilderBuilder::new(&*client)
+			.on_parent_block(client.chain_info().best_hash)
+			.with_parent_block_number(client.chain_info().best_number)
+			.build()
+			.unwrap()
+			.build()
+			.unwrap()
+			.block;
+		let trusted_justifications = Some(Justifications::from((*b"FRNK", vec![1, 2, 3])));
+		let config = WarpSyncConfig::WithCheckpoint(TrustedCheckpoint {
+			hash: target_block.header().hash(),
+			header: None,
+			justifications: trusted_justifications.clone(),
+		});
+		let mut warp_sync =
+			WarpSync::new(client, config, None, Arc::new(MockBlockDownloader::new()), None);
+
+		for best_number in 1..11 {
+			warp_sync.add_peer(PeerId::random(), Hash::random(), best_number);
+		}
+		assert!(matches!(warp_sync.phase, Phase::TargetHash(_)));
+
+		let (peer_id, request) = warp_sync.target_block_request().unwrap();
+		assert_eq!(request.from, FromBlock::Hash(target_block.header().hash()));
+
+		// A block with a different hash is rejected.
+		let other_header = <Block as BlockT>::Header::new(
+			1,
+			Default::default(),
+			Default::default(),
+			Default::default(),
+			Default::default(),
+		);
+		let response = vec![BlockData::<Block> {
+			hash: other_header.hash(),
+			header: Some(other_header),
+			body: Some(Vec::new()),
+			indexed_body: None,
+			receipt: None,
+			message_queue: None,
+			justification: None,
+			justifications: None,
+		}];
+		assert!(matches!(
+			warp_sync.on_block_response_inner(peer_id, request.clone(), response),
+			Err(BadPeer(id, _rep)) if id == peer_id,
+		));
+		assert!(matches!(warp_sync.phase, Phase::TargetHash(_)));
+
+		// A peer that doesn't know the target yet isn't banned, but isn't asked again until it
+		// announces a better block.
+		let (peer_id, request) = warp_sync.target_block_request().unwrap();
+		assert!(warp_sync.on_block_response_inner(peer_id, request, Vec::new()).is_ok());
+		assert!(!warp_sync.peers.get(&peer_id).unwrap().may_have_target());
+		let (other_peer_id, request) = warp_sync.target_block_request().unwrap();
+		assert_ne!(other_peer_id, peer_id);
+		warp_sync.peers.get_mut(&peer_id).unwrap().best_number += 1;
+		assert!(warp_sync.peers.get(&peer_id).unwrap().may_have_target());
+
+		// The block matching the trusted hash is accepted and imported with the trusted
+		// justifications.
+		let body = Some(target_block.extrinsics().iter().cloned().collect::<Vec<_>>());
+		let response = vec![BlockData::<Block> {
+			hash: target_block.header().hash(),
+			header: Some(target_block.header().clone()),
+			body: body.clone(),
+			indexed_body: None,
+			receipt: None,
+			message_queue: None,
+			justification: None,
+			justifications: Some(Justifications::from((*b"FRNK", Vec::new()))),
+		}];
+		assert!(warp_sync.on_block_response_inner(other_peer_id, request, response).is_ok());
+
+		let result = warp_sync.take_result().unwrap();
+		assert_eq!(result.target_header, *target_block.header());
+		assert_eq!(result.target_body, body);
+		assert_eq!(result.target_justifications, trusted_justifications);
+	}
 }
@@ -68,6 +68,13 @@ pub trait TransactionPool<H: ExHashT, B: BlockT>: Send + Sync {
 	fn on_broadcasted(&self, propagations: HashMap<H, Vec<String>>);
 	/// Get transaction by hash.
 	fn transaction(&self, hash: &H) -> Option<Arc<B::Extrinsic>>;
+	/// Get the hashes of the transactions to propagate again, even to the peers which already
+	/// know them.
+	///
+	/// The default implementation never rebroadcasts.
+	fn transactions_to_rebroadcast(&self) -> Vec<H> {
+		Vec::new()
+	}
 }
 
 /// Dummy implementation of the [`TransactionPool`] trait for a transaction pool that is always
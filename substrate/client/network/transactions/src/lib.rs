@@ -52,7 +52,7 @@ use sc_utils::mpsc::{tracing_unbounded, TracingUnboundedReceiver, TracingUnbound
 use sp_runtime::traits::Block as BlockT;
 
 use std::{
-	collections::{hash_map::Entry, HashMap},
+	collections::{hash_map::Entry, HashMap, HashSet},
 	iter,
 	num::NonZeroUsize,
 	pin::Pin,
@@ -460,16 +460,20 @@ where
 
 		debug!(target: LOG_TARGET, "Propagating transaction [{:?}]", hash);
 		if let Some(transaction) = self.transaction_pool.transaction(hash) {
-			let propagated_to = self.do_propagate_transactions(&[(hash.clone(), transaction)]);
+			let propagated_to =
+				self.do_propagate_transactions(&[(hash.clone(), transaction)], &HashSet::new());
 			self.transaction_pool.on_broadcasted(propagated_to);
 		} else {
 			debug!(target: "sync", "Propagating transaction failure [{:?}]", hash);
 		}
 	}
 
+	/// Sends the transactions to the peers which don't know them yet, and the `rebroadcast` ones
+	/// to every peer.
 	fn do_propagate_transactions(
 		&mut self,
 		transactions: &[(H, Arc<B::Extrinsic>)],
+		rebroadcast: &HashSet<H>,
 	) -> HashMap<H, Vec<String>> {
 		let mut propagated_to = HashMap::<_, Vec<_>>::new();
 		let mut propagated_transactions = 0;
@@ -482,7 +486,9 @@ where
 
 			let (hashes, to_send): (Vec<_>, Transactions<_>) = transactions
 				.iter()
-				.filter(|(hash, _)| peer.known_transactions.insert(hash.clone()))
+				.filter(|(hash, _)| {
+					peer.known_transactions.insert(hash.clone()) || rebroadcast.contains(hash)
+				})
 				.cloned()
 				.unzip();
 
@@ -532,7 +538,12 @@ where
 
 		debug!(target: LOG_TARGET, "Propagating transactions");
 
-		let propagated_to = self.do_propagate_transactions(&transactions);
+		let rebroadcast = self
+			.transaction_pool
+			.transactions_to_rebroadcast()
+			.into_iter()
+			.collect::<HashSet<_>>();
+		let propagated_to = self.do_propagate_transactions(&transactions, &rebroadcast);
 		self.transaction_pool.on_broadcasted(propagated_to);
 	}
 }
@@ -33,6 +33,8 @@ pub mod child_state;
 pub mod dev;
 pub mod mixnet;
 pub mod offchain;
+pub mod snapshot;
 pub mod state;
 pub mod statement;
 pub mod system;
+pub mod txpool;
@@ -0,0 +1,46 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Substrate snapshot API, taking snapshots of the database of a running node. The endpoints in
+//! this RPC module are marked `unsafe`.
+
+pub mod error;
+
+use error::Error;
+use jsonrpsee::proc_macros::rpc;
+use serde::{Deserialize, Serialize};
+
+/// The block a snapshot taken by the `snapshot_create` RPC is at.
+#[derive(Eq, PartialEq, Clone, Copy, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SnapshotBlock<Hash, Number> {
+	/// The hash of the block.
+	pub hash: Hash,
+	/// The number of the block.
+	pub number: Number,
+}
+
+/// Substrate snapshot API.
+#[rpc(client, server)]
+pub trait SnapshotApi<Hash, Number> {
+	/// Write a snapshot of the last finalized block to the `path` directory of the node.
+	///
+	/// The snapshot can be restored with the `restore-snapshot` command.
+	#[method(name = "snapshot_create", blocking, with_extensions)]
+	fn create(&self, path: String) -> Result<SnapshotBlock<Hash, Number>, Error>;
+}
@@ -71,6 +71,18 @@ pub struct PeerInfo<Hash, Number> {
 	pub best_number: Number,
 }
 
+/// Ban of a peer or an IP range.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BannedPeer {
+	/// Base58-encoded peer ID or IP range in CIDR notation.
+	pub target: String,
+	/// Reputation of the peer, if it is banned because of a low reputation.
+	pub reputation: Option<i32>,
+	/// Seconds since the UNIX epoch at which a manual ban expires, if it ever does.
+	pub until: Option<u64>,
+}
+
 /// The role the node is running as
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub enum NodeRole {
@@ -123,6 +135,19 @@ mod tests {
 		);
 	}
 
+	#[test]
+	fn should_serialize_banned_peer() {
+		assert_eq!(
+			::serde_json::to_string(&BannedPeer {
+				target: "192.0.2.0/24".into(),
+				reputation: None,
+				until: Some(1_700_000_000),
+			})
+			.unwrap(),
+			r#"{"target":"192.0.2.0/24","reputation":null,"until":1700000000}"#,
+		);
+	}
+
 	#[test]
 	fn should_serialize_sync_state() {
 		assert_eq!(
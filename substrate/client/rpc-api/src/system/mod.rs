@@ -23,7 +23,7 @@ pub mod helpers;
 
 use jsonrpsee::{core::JsonValue, proc_macros::rpc};
 
-pub use self::helpers::{Health, NodeRole, PeerInfo, SyncState, SystemInfo};
+pub use self::helpers::{BannedPeer, Health, NodeRole, PeerInfo, SyncState, SystemInfo};
 pub use error::Error;
 
 /// Substrate system RPC API
@@ -98,6 +98,23 @@ pub trait SystemApi<Hash, Number> {
 	#[method(name = "system_reservedPeers")]
 	async fn system_reserved_peers(&self) -> Result<Vec<String>, Error>;
 
+	/// Returns the peers and IP ranges that are currently banned, either manually or because of
+	/// a low reputation.
+	#[method(name = "system_bannedPeers", with_extensions)]
+	async fn system_banned_peers(&self) -> Result<Vec<BannedPeer>, Error>;
+
+	/// Bans a peer or an IP range. The target is either a base58-encoded PeerId, an IP address or
+	/// an IP range in CIDR notation, e.g. `198.51.100.0/24`.
+	///
+	/// The ban lasts for `duration` seconds, or until it is lifted if `duration` is not given.
+	/// Connected peers matching the target are disconnected.
+	#[method(name = "system_banPeer", with_extensions)]
+	async fn system_ban_peer(&self, target: String, duration: Option<u64>) -> Result<(), Error>;
+
+	/// Lifts the ban of a peer or an IP range. Returns `false` if the target wasn't banned.
+	#[method(name = "system_unbanPeer", with_extensions)]
+	async fn system_unban_peer(&self, target: String) -> Result<bool, Error>;
+
 	/// Returns the roles the node is running as.
 	#[method(name = "system_nodeRoles")]
 	async fn system_node_roles(&self) -> Result<Vec<NodeRole>, Error>;
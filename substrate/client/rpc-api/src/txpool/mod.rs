@@ -0,0 +1,124 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Substrate transaction pool introspection API.
+//!
+//! Lists the transactions kept by the pool, grouped by sender, along with the transactions
+//! recently dropped from it. Intended to debug stuck transactions.
+
+pub mod error;
+
+use error::Error;
+use jsonrpsee::proc_macros::rpc;
+use sc_transaction_pool_api::{
+	RecentlyDroppedTransaction, TransactionLongevity, TransactionPriority,
+};
+use serde::{Deserialize, Serialize};
+use sp_core::Bytes;
+
+/// The details of a transaction in the pool, returned by `txpool_content`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionDetails<Hash, BlockHash> {
+	/// The hash of the transaction.
+	pub hash: Hash,
+	/// The encoded transaction.
+	pub extrinsic: Bytes,
+	/// The priority of the transaction.
+	pub priority: TransactionPriority,
+	/// The number of blocks the transaction stays valid for.
+	pub longevity: TransactionLongevity,
+	/// The tags required by the transaction.
+	pub requires: Vec<Bytes>,
+	/// The tags provided by the transaction.
+	pub provides: Vec<Bytes>,
+	/// Whether the transaction is propagated to the other peers.
+	pub propagate: bool,
+	/// The hashes of the blocks whose views contain the transaction.
+	///
+	/// The single-state transaction pool reports the best block its state is kept at.
+	pub views: Vec<BlockHash>,
+}
+
+/// The summary of a transaction in the pool, returned by `txpool_inspect`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionSummary<Hash> {
+	/// The hash of the transaction.
+	pub hash: Hash,
+	/// The length of the encoded transaction, in bytes.
+	pub len: u32,
+	/// The priority of the transaction.
+	pub priority: TransactionPriority,
+	/// The number of views containing the transaction.
+	pub views: u32,
+}
+
+/// The transactions of a single sender.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SenderTransactions<Transaction> {
+	/// The encoded sender, `None` if it can't be determined.
+	pub sender: Option<Bytes>,
+	/// The transactions of the sender in the ready queue, best first.
+	pub ready: Vec<Transaction>,
+	/// The transactions of the sender in the future queue.
+	pub future: Vec<Transaction>,
+}
+
+/// The content of the pool.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PoolContent<Transaction, Hash> {
+	/// The transactions of the pool, grouped by sender.
+	pub senders: Vec<SenderTransactions<Transaction>>,
+	/// The transactions recently dropped from the pool, oldest first.
+	pub dropped: Vec<RecentlyDroppedTransaction<Hash>>,
+}
+
+/// The number of transactions in the pool, returned by `txpool_status`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PoolStatus {
+	/// The number of transactions in the ready queue.
+	pub ready: u32,
+	/// The size of the transactions in the ready queue, in bytes.
+	pub ready_bytes: u32,
+	/// The number of transactions in the future queue.
+	pub future: u32,
+	/// The size of the transactions in the future queue, in bytes.
+	pub future_bytes: u32,
+	/// The number of transactions recently dropped from the pool.
+	pub dropped: u32,
+}
+
+/// Substrate transaction pool introspection API.
+#[rpc(client, server)]
+pub trait TxPoolApi<Hash, BlockHash> {
+	/// Returns the details of all the transactions of the pool, grouped by sender.
+	#[method(name = "txpool_content", with_extensions)]
+	fn content(&self) -> Result<PoolContent<TransactionDetails<Hash, BlockHash>, Hash>, Error>;
+
+	/// Returns the summary of all the transactions of the pool, grouped by sender.
+	#[method(name = "txpool_inspect", with_extensions)]
+	fn inspect(&self) -> Result<PoolContent<TransactionSummary<Hash>, Hash>, Error>;
+
+	/// Returns the number of transactions in the pool.
+	#[method(name = "txpool_status")]
+	fn status(&self) -> Result<PoolStatus, Error>;
+}
@@ -55,6 +55,8 @@ pub struct Transaction<Pool, Client> {
 	executor: SubscriptionTaskExecutor,
 	/// Metrics for transactions.
 	metrics: Option<Metrics>,
+	/// Are the transactions submitted to the local lane of the pool.
+	local_lane: bool,
 }
 
 impl<Pool, Client> Transaction<Pool, Client> {
@@ -65,15 +67,22 @@ impl<Pool, Client> Transaction<Pool, Client> {
 		executor: SubscriptionTaskExecutor,
 		metrics: Option<Metrics>,
 	) -> Self {
-		Transaction { client, pool, executor, metrics }
+		Transaction { client, pool, executor, metrics, local_lane: false }
+	}
+
+	/// Submits the transactions to the local lane of the pool.
+	///
+	/// Only meant for RPC servers which are exclusively used by the operator of the node.
+	pub fn with_local_lane(mut self, local_lane: bool) -> Self {
+		self.local_lane = local_lane;
+		self
 	}
 }
 
 /// Currently we treat all RPC transactions as externals.
 ///
-/// Possibly in the future we could allow opt-in for special treatment
-/// of such transactions, so that the block authors can inject
-/// some unique transactions via RPC and have them included in the pool.
+/// The node operator may opt-in for special treatment of such transactions, so that they are
+/// submitted to the local lane of the pool, see [`Transaction::with_local_lane`].
 const TX_SOURCE: TransactionSource = TransactionSource::External;
 
 #[async_trait]
@@ -87,6 +96,7 @@ where
 	fn submit_and_watch(&self, pending: PendingSubscriptionSink, xt: Bytes) {
 		let client = self.client.clone();
 		let pool = self.pool.clone();
+		let local_lane = self.local_lane;
 
 		// Get a new transaction metrics instance and increment the counter.
 		let mut metrics = InstanceMetrics::new(self.metrics.clone());
@@ -113,13 +123,16 @@ where
 
 			let best_block_hash = client.info().best_hash;
 
-			let submit = pool
-				.submit_and_watch(best_block_hash, TX_SOURCE, decoded_extrinsic)
-				.map_err(|e| {
-					e.into_pool_error()
-						.map(Error::from)
-						.unwrap_or_else(|e| Error::Verification(Box::new(e)))
-				});
+			let submit = if local_lane {
+				pool.submit_and_watch_to_local_lane(best_block_hash, TX_SOURCE, decoded_extrinsic)
+			} else {
+				pool.submit_and_watch(best_block_hash, TX_SOURCE, decoded_extrinsic)
+			};
+			let submit = submit.map_err(|e| {
+				e.into_pool_error()
+					.map(Error::from)
+					.unwrap_or_else(|e| Error::Verification(Box::new(e)))
+			});
 
 			let Ok(sink) = pending.accept().await.map(Subscription::from) else {
 				return;
@@ -54,6 +54,8 @@ pub struct TransactionBroadcast<Pool: TransactionPool, Client> {
 	broadcast_ids: Arc<RwLock<HashMap<String, BroadcastState<Pool>>>>,
 	/// Keep track of how many concurrent operations are active for each connection.
 	rpc_connections: RpcConnections,
+	/// Are the transactions submitted to the local lane of the pool.
+	local_lane: bool,
 }
 
 /// The state of a broadcast operation.
@@ -78,9 +80,18 @@ impl<Pool: TransactionPool, Client> TransactionBroadcast<Pool, Client> {
 			executor,
 			broadcast_ids: Default::default(),
 			rpc_connections: RpcConnections::new(max_transactions_per_connection),
+			local_lane: false,
 		}
 	}
 
+	/// Submits the transactions to the local lane of the pool.
+	///
+	/// Only meant for RPC servers which are exclusively used by the operator of the node.
+	pub fn with_local_lane(mut self, local_lane: bool) -> Self {
+		self.local_lane = local_lane;
+		self
+	}
+
 	/// Generate an unique operation ID for the `transaction_broadcast` RPC method.
 	pub fn generate_unique_id(&self) -> String {
 		let generate_operation_id = || {
@@ -108,9 +119,8 @@ impl<Pool: TransactionPool, Client> TransactionBroadcast<Pool, Client> {
 
 /// Currently we treat all RPC transactions as externals.
 ///
-/// Possibly in the future we could allow opt-in for special treatment
-/// of such transactions, so that the block authors can inject
-/// some unique transactions via RPC and have them included in the pool.
+/// The node operator may opt-in for special treatment of such transactions, so that they are
+/// submitted to the local lane of the pool, see [`TransactionBroadcast::with_local_lane`].
 const TX_SOURCE: TransactionSource = TransactionSource::External;
 
 #[async_trait]
@@ -123,6 +133,7 @@ where
 {
 	async fn broadcast(&self, ext: &Extensions, bytes: Bytes) -> RpcResult<Option<String>> {
 		let pool = self.pool.clone();
+		let local_lane = self.local_lane;
 		let conn_id = ext
 			.get::<ConnectionId>()
 			.copied()
@@ -182,10 +193,16 @@ where
 					return;
 				};
 
-				let mut stream = match pool
-					.submit_and_watch(best_block_hash, TX_SOURCE, decoded_extrinsic.clone())
-					.await
-				{
+				let submit = if local_lane {
+					pool.submit_and_watch_to_local_lane(
+						best_block_hash,
+						TX_SOURCE,
+						decoded_extrinsic.clone(),
+					)
+				} else {
+					pool.submit_and_watch(best_block_hash, TX_SOURCE, decoded_extrinsic.clone())
+				};
+				let mut stream = match submit.await {
 					Ok(stream) => stream,
 					// The transaction was not included to the pool.
 					Err(e) => {
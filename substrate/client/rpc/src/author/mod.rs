@@ -54,6 +54,8 @@ pub struct Author<P, Client> {
 	keystore: KeystorePtr,
 	/// Executor to spawn subscriptions.
 	executor: SubscriptionTaskExecutor,
+	/// Are the transactions submitted to the local lane of the pool.
+	local_lane: bool,
 }
 
 impl<P, Client> Author<P, Client> {
@@ -64,15 +66,22 @@ impl<P, Client> Author<P, Client> {
 		keystore: KeystorePtr,
 		executor: SubscriptionTaskExecutor,
 	) -> Self {
-		Author { client, pool, keystore, executor }
+		Author { client, pool, keystore, executor, local_lane: false }
+	}
+
+	/// Submits the transactions to the local lane of the pool.
+	///
+	/// Only meant for RPC servers which are exclusively used by the operator of the node.
+	pub fn with_local_lane(mut self, local_lane: bool) -> Self {
+		self.local_lane = local_lane;
+		self
 	}
 }
 
 /// Currently we treat all RPC transactions as externals.
 ///
-/// Possibly in the future we could allow opt-in for special treatment
-/// of such transactions, so that the block authors can inject
-/// some unique transactions via RPC and have them included in the pool.
+/// The node operator may opt-in for special treatment of such transactions, so that they are
+/// submitted to the local lane of the pool, see [`Author::with_local_lane`].
 const TX_SOURCE: TransactionSource = TransactionSource::External;
 
 #[async_trait]
@@ -90,7 +99,12 @@ where
 			Err(err) => return Err(Error::Client(Box::new(err)).into()),
 		};
 		let best_block_hash = self.client.info().best_hash;
-		self.pool.submit_one(best_block_hash, TX_SOURCE, xt).await.map_err(|e| {
+		let submit = if self.local_lane {
+			self.pool.submit_one_to_local_lane(best_block_hash, TX_SOURCE, xt)
+		} else {
+			self.pool.submit_one(best_block_hash, TX_SOURCE, xt)
+		};
+		submit.await.map_err(|e| {
 			e.into_pool_error()
 				.map(|e| Error::Pool(e))
 				.unwrap_or_else(|e| Error::Verification(Box::new(e)))
@@ -190,13 +204,18 @@ where
 		};
 
 		let pool = self.pool.clone();
+		let local_lane = self.local_lane;
 		let fut = async move {
-			let submit =
-				pool.submit_and_watch(best_block_hash, TX_SOURCE, dxt).await.map_err(|e| {
-					e.into_pool_error()
-						.map(error::Error::from)
-						.unwrap_or_else(|e| error::Error::Verification(Box::new(e)))
-				});
+			let submit = if local_lane {
+				pool.submit_and_watch_to_local_lane(best_block_hash, TX_SOURCE, dxt)
+			} else {
+				pool.submit_and_watch(best_block_hash, TX_SOURCE, dxt)
+			};
+			let submit = submit.await.map_err(|e| {
+				e.into_pool_error()
+					.map(error::Error::from)
+					.unwrap_or_else(|e| error::Error::Verification(Box::new(e)))
+			});
 
 			let stream = match submit {
 				Ok(stream) => stream,
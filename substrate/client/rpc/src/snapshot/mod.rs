@@ -0,0 +1,64 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Implementation of the [`SnapshotApiServer`] trait, writing snapshots of the database of a
+//! running node.
+
+#[cfg(test)]
+mod tests;
+
+use jsonrpsee::Extensions;
+use sc_client_api::Backend;
+use sc_rpc_api::{check_if_safe, snapshot::error::Error};
+use sp_runtime::traits::{Block as BlockT, NumberFor};
+use std::{marker::PhantomData, path::Path, sync::Arc};
+
+pub use sc_rpc_api::snapshot::{SnapshotApiServer, SnapshotBlock};
+
+/// The Snapshot API. All methods are unsafe.
+pub struct Snapshot<Block: BlockT, BE> {
+	backend: Arc<BE>,
+	_phantom: PhantomData<Block>,
+}
+
+impl<Block: BlockT, BE> Snapshot<Block, BE> {
+	/// Create a new Snapshot API.
+	pub fn new(backend: Arc<BE>) -> Self {
+		Self { backend, _phantom: PhantomData }
+	}
+}
+
+impl<Block, BE> SnapshotApiServer<Block::Hash, NumberFor<Block>> for Snapshot<Block, BE>
+where
+	Block: BlockT + 'static,
+	BE: Backend<Block> + 'static,
+{
+	fn create(
+		&self,
+		ext: &Extensions,
+		path: String,
+	) -> Result<SnapshotBlock<Block::Hash, NumberFor<Block>>, Error> {
+		check_if_safe(ext)?;
+
+		let (hash, number) = self
+			.backend
+			.snapshot(Path::new(&path))
+			.map_err(|e| Error::SnapshotFailed(e.to_string()))?;
+		Ok(SnapshotBlock { hash, number })
+	}
+}
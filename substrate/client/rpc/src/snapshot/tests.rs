@@ -0,0 +1,63 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use super::*;
+use crate::DenyUnsafe;
+use sp_blockchain::HeaderBackend;
+use substrate_test_runtime_client::{prelude::*, runtime::Block};
+
+#[tokio::test]
+async fn snapshot_of_running_node_works() {
+	let builder = TestClientBuilder::new();
+	let backend = builder.backend();
+	let client = builder.build();
+	let mut api = <Snapshot<Block, _>>::new(backend).into_rpc();
+	api.extensions_mut().insert(DenyUnsafe::No);
+
+	let dir = tempfile::tempdir().unwrap();
+	let path = dir.path().join("snapshot").to_string_lossy().into_owned();
+	let block: SnapshotBlock<_, u64> = api.call("snapshot_create", [&path]).await.unwrap();
+	assert_eq!(block, SnapshotBlock { hash: client.info().finalized_hash, number: 0 });
+	assert!(dir.path().join("snapshot").join("manifest.json").exists());
+
+	// A directory already holding a snapshot is not overwritten.
+	assert!(api
+		.call::<_, SnapshotBlock<sp_core::H256, u64>>("snapshot_create", [&path])
+		.await
+		.is_err());
+}
+
+#[tokio::test]
+async fn deny_unsafe_works() {
+	let backend = TestClientBuilder::new().backend();
+	let mut api = <Snapshot<Block, _>>::new(backend).into_rpc();
+	api.extensions_mut().insert(DenyUnsafe::Yes);
+
+	let dir = tempfile::tempdir().unwrap();
+	let request = format!(
+		"{{\"jsonrpc\":\"2.0\",\"method\":\"snapshot_create\",\"params\":[{}],\"id\":1}}",
+		serde_json::to_string(&dir.path()).unwrap()
+	);
+	let (resp, _) = api.raw_json_request(&request, 1).await.expect("Raw calls should succeed");
+
+	assert_eq!(
+		resp,
+		r#"{"jsonrpc":"2.0","id":1,"error":{"code":-32601,"message":"RPC call is unsafe to be called externally"}}"#
+	);
+	assert!(std::fs::read_dir(dir.path()).unwrap().next().is_none());
+}
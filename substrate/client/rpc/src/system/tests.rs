@@ -113,6 +113,27 @@ fn api<T: Into<Option<Status>>>(sync: T) -> RpcModule<System<Block>> {
 					let _ = sender
 						.send(vec!["QmSk5HQbn6LhUwDiNMseVUjuRYhEtYj4aUZ6WfWoGURpdV".to_string()]);
 				},
+				Request::NetworkBannedPeers(sender) => {
+					let _ = sender.send(vec![BannedPeer {
+						target: "QmSk5HQbn6LhUwDiNMseVUjuRYhEtYj4aUZ6WfWoGURpdV".to_string(),
+						reputation: Some(i32::MIN),
+						until: None,
+					}]);
+				},
+				Request::NetworkBanPeer(target, _until, sender) => {
+					let _ = match target.parse::<sc_network::peer_store::BanTarget>() {
+						Ok(_) => sender.send(Ok(())),
+						Err(s) =>
+							sender.send(Err(error::Error::MalformattedPeerArg(s.to_string()))),
+					};
+				},
+				Request::NetworkUnbanPeer(target, sender) => {
+					let _ = match target.parse::<sc_network::peer_store::BanTarget>() {
+						Ok(_) => sender.send(Ok(true)),
+						Err(s) =>
+							sender.send(Err(error::Error::MalformattedPeerArg(s.to_string()))),
+					};
+				},
 				Request::NodeRoles(sender) => {
 					let _ = sender.send(vec![NodeRole::Authority]);
 				},
@@ -337,6 +358,47 @@ async fn system_network_reserved_peers() {
 	assert_eq!(reserved_peers, vec!["QmSk5HQbn6LhUwDiNMseVUjuRYhEtYj4aUZ6WfWoGURpdV".to_string()],);
 }
 
+#[tokio::test]
+async fn system_network_banned_peers() {
+	let banned_peers: Vec<BannedPeer> =
+		api(None).call("system_bannedPeers", EmptyParams::new()).await.unwrap();
+	assert_eq!(
+		banned_peers,
+		vec![BannedPeer {
+			target: "QmSk5HQbn6LhUwDiNMseVUjuRYhEtYj4aUZ6WfWoGURpdV".to_string(),
+			reputation: Some(i32::MIN),
+			until: None,
+		}],
+	);
+}
+
+#[tokio::test]
+async fn system_network_ban_and_unban() {
+	let _good_peer: () = api(None)
+		.call("system_banPeer", ("QmSk5HQbn6LhUwDiNMseVUjuRYhEtYj4aUZ6WfWoGURpdV", Some(60)))
+		.await
+		.expect("call with good peer id works");
+	let _good_range: () = api(None)
+		.call("system_banPeer", ("198.51.100.0/24", None::<u64>))
+		.await
+		.expect("call with good IP range works");
+
+	assert_matches!(
+		api(None).call::<_, ()>("system_banPeer", ("198.51.100.0/33", None::<u64>)).await,
+		Err(RpcError::JsonRpc(err)) if err.message().contains("is neither a peer ID nor an IP address or range")
+	);
+	assert_matches!(
+		api(None).call::<_, ()>("system_banPeer", ("198.51.100.0/24", Some(u64::MAX))).await,
+		Err(RpcError::JsonRpc(err)) if err.message().contains("is too long")
+	);
+
+	let unbanned: bool = api(None)
+		.call("system_unbanPeer", ["198.51.100.19"])
+		.await
+		.expect("call with good IP address works");
+	assert!(unbanned);
+}
+
 #[test]
 fn test_add_reset_log_filter() {
 	const EXPECTED_BEFORE_ADD: &'static str = "EXPECTED_BEFORE_ADD";
@@ -0,0 +1,162 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Substrate transaction pool introspection API.
+
+#[cfg(test)]
+mod tests;
+
+use codec::{Compact, Decode, Encode};
+use jsonrpsee::Extensions;
+use sc_rpc_api::{check_if_safe, txpool::error::Error};
+use sc_transaction_pool_api::{BlockHash, InPoolTransaction, TransactionPool, TxHash};
+use sp_runtime::{generic::Preamble, traits::Block as BlockT};
+use std::{collections::BTreeMap, sync::Arc};
+
+pub use sc_rpc_api::txpool::*;
+
+/// Extracts the encoded sender of a transaction, `None` if it can't be determined.
+pub type SenderOf<Block> =
+	Arc<dyn Fn(&<Block as BlockT>::Extrinsic) -> Option<Vec<u8>> + Send + Sync>;
+
+/// Returns a [`SenderOf`] for runtimes using [`sp_runtime::generic::UncheckedExtrinsic`] with the
+/// given `Address`, `Signature` and `Extension` types.
+///
+/// The sender of a signed transaction is its encoded address, other transactions have none.
+pub fn signer_of<Block, Address, Signature, Extension>() -> SenderOf<Block>
+where
+	Block: BlockT,
+	Address: Decode + Encode,
+	Signature: Decode,
+	Extension: Decode,
+{
+	Arc::new(|xt| {
+		// The extrinsic is prefixed with its length, and its call comes after the preamble.
+		let encoded = xt.encode();
+		match <(Compact<u32>, Preamble<Address, Signature, Extension>)>::decode(&mut &encoded[..]) {
+			Ok((_, Preamble::Signed(address, _, _))) => Some(address.encode()),
+			_ => None,
+		}
+	})
+}
+
+/// Transaction pool introspection API.
+///
+/// `sc_service` registers an instance without a [`SenderOf`]. A node knowing its extrinsic format
+/// can register its own instance in its RPC builder, which replaces the default one.
+pub struct TxPool<P: TransactionPool> {
+	/// Transactions pool.
+	pool: Arc<P>,
+	/// Extracts the sender of the transactions, used to group them.
+	sender_of: Option<SenderOf<P::Block>>,
+}
+
+impl<P: TransactionPool> TxPool<P> {
+	/// Create new instance of the transaction pool introspection API.
+	///
+	/// All the transactions are reported with an unknown sender, unless
+	/// [`Self::with_sender_of`] is used.
+	pub fn new(pool: Arc<P>) -> Self {
+		TxPool { pool, sender_of: None }
+	}
+
+	/// Sets the function extracting the sender of the transactions.
+	pub fn with_sender_of(mut self, sender_of: SenderOf<P::Block>) -> Self {
+		self.sender_of = Some(sender_of);
+		self
+	}
+
+	/// Returns the ready and future transactions of the pool grouped by sender, mapped with `f`.
+	fn group_by_sender<T>(
+		&self,
+		mut f: impl FnMut(&P::InPoolTransaction) -> T,
+	) -> Vec<SenderTransactions<T>> {
+		let mut senders = BTreeMap::<Option<Vec<u8>>, SenderTransactions<T>>::new();
+		let mut entry = |tx: &P::InPoolTransaction| {
+			let sender = self.sender_of.as_ref().and_then(|sender_of| sender_of(tx.data()));
+			senders.entry(sender.clone()).or_insert_with(|| SenderTransactions {
+				sender: sender.map(Into::into),
+				ready: Vec::new(),
+				future: Vec::new(),
+			});
+			sender
+		};
+
+		let ready = self.pool.ready().map(|tx| (entry(&*tx), f(&*tx))).collect::<Vec<_>>();
+		let future = self.pool.futures().iter().map(|tx| (entry(tx), f(tx))).collect::<Vec<_>>();
+		for (sender, tx) in ready {
+			senders.get_mut(&sender).expect("entry inserted above; qed").ready.push(tx);
+		}
+		for (sender, tx) in future {
+			senders.get_mut(&sender).expect("entry inserted above; qed").future.push(tx);
+		}
+		senders.into_values().collect()
+	}
+}
+
+impl<P> TxPoolApiServer<TxHash<P>, BlockHash<P>> for TxPool<P>
+where
+	P: TransactionPool + Sync + Send + 'static,
+{
+	fn content(
+		&self,
+		ext: &Extensions,
+	) -> Result<PoolContent<TransactionDetails<TxHash<P>, BlockHash<P>>, TxHash<P>>, Error> {
+		check_if_safe(ext)?;
+
+		let mut views = self.pool.transaction_views();
+		let senders = self.group_by_sender(|tx| TransactionDetails {
+			hash: tx.hash().clone(),
+			extrinsic: tx.data().encode().into(),
+			priority: *tx.priority(),
+			longevity: *tx.longevity(),
+			requires: tx.requires().iter().cloned().map(Into::into).collect(),
+			provides: tx.provides().iter().cloned().map(Into::into).collect(),
+			propagate: tx.is_propagable(),
+			views: views.remove(tx.hash()).unwrap_or_default(),
+		});
+		Ok(PoolContent { senders, dropped: self.pool.recently_dropped() })
+	}
+
+	fn inspect(
+		&self,
+		ext: &Extensions,
+	) -> Result<PoolContent<TransactionSummary<TxHash<P>>, TxHash<P>>, Error> {
+		check_if_safe(ext)?;
+
+		let views = self.pool.transaction_views();
+		let senders = self.group_by_sender(|tx| TransactionSummary {
+			hash: tx.hash().clone(),
+			len: tx.data().encoded_size() as u32,
+			priority: *tx.priority(),
+			views: views.get(tx.hash()).map_or(0, |views| views.len() as u32),
+		});
+		Ok(PoolContent { senders, dropped: self.pool.recently_dropped() })
+	}
+
+	fn status(&self) -> Result<PoolStatus, Error> {
+		let status = self.pool.status();
+		Ok(PoolStatus {
+			ready: status.ready as u32,
+			ready_bytes: status.ready_bytes as u32,
+			future: status.future as u32,
+			future_bytes: status.future_bytes as u32,
+			dropped: self.pool.recently_dropped().len() as u32,
+		})
+	}
+}
@@ -0,0 +1,139 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use super::*;
+
+use assert_matches::assert_matches;
+use codec::Encode;
+use jsonrpsee::{core::EmptyServerParams as EmptyParams, MethodsError as RpcError, RpcModule};
+use sc_rpc_api::DenyUnsafe;
+use sc_transaction_pool::{BasicPool, FullChainApi};
+use sc_transaction_pool_api::TransactionSource;
+use sp_blockchain::HeaderBackend;
+use sp_core::H256;
+use substrate_test_runtime_client::{
+	self,
+	runtime::{Address, Block, Extrinsic, ExtrinsicBuilder, Signature, Transfer, TxExtension},
+	Backend, Client, Sr25519Keyring,
+};
+
+type FullTransactionPool = BasicPool<FullChainApi<Client<Backend>, Block>, Block>;
+
+fn uxt(sender: Sr25519Keyring, nonce: u64) -> Extrinsic {
+	let tx = Transfer {
+		amount: Default::default(),
+		nonce,
+		from: sender.into(),
+		to: Sr25519Keyring::Bob.into(),
+	};
+	ExtrinsicBuilder::new_transfer(tx).build()
+}
+
+struct TestSetup {
+	client: Arc<Client<Backend>>,
+	pool: Arc<FullTransactionPool>,
+	api: RpcModule<TxPool<FullTransactionPool>>,
+}
+
+impl TestSetup {
+	fn new(deny_unsafe: DenyUnsafe) -> Self {
+		let client = Arc::new(substrate_test_runtime_client::new());
+		let pool = Arc::from(BasicPool::new_full(
+			Default::default(),
+			true.into(),
+			None,
+			sp_core::testing::TaskExecutor::new(),
+			client.clone(),
+		));
+		let sender_of = signer_of::<Block, Address, Signature, TxExtension>();
+		let mut api = TxPool::new(pool.clone()).with_sender_of(sender_of).into_rpc();
+		api.extensions_mut().insert(deny_unsafe);
+		TestSetup { client, pool, api }
+	}
+
+	async fn submit(&self, xt: Extrinsic) -> H256 {
+		let at = self.client.info().best_hash;
+		self.pool.submit_one(at, TransactionSource::External, xt).await.unwrap()
+	}
+}
+
+#[tokio::test]
+async fn txpool_content_groups_transactions_by_sender() {
+	let setup = TestSetup::new(DenyUnsafe::No);
+	let api = &setup.api;
+	let alice0 = setup.submit(uxt(Sr25519Keyring::Alice, 0)).await;
+	let alice2 = setup.submit(uxt(Sr25519Keyring::Alice, 2)).await;
+	let bob0 = setup.submit(uxt(Sr25519Keyring::Bob, 0)).await;
+
+	let content: PoolContent<TransactionDetails<H256, H256>, H256> =
+		api.call("txpool_content", EmptyParams::new()).await.unwrap();
+	assert_eq!(content.senders.len(), 2);
+	assert!(content.dropped.is_empty());
+
+	let sender = |keyring: Sr25519Keyring| {
+		let sender = Some(keyring.to_account_id().encode().into());
+		content.senders.iter().find(|s| s.sender == sender).unwrap()
+	};
+	let alice = sender(Sr25519Keyring::Alice);
+	assert_eq!(alice.ready.iter().map(|tx| tx.hash).collect::<Vec<_>>(), vec![alice0]);
+	assert_eq!(alice.future.iter().map(|tx| tx.hash).collect::<Vec<_>>(), vec![alice2]);
+	assert_eq!(alice.ready[0].extrinsic, uxt(Sr25519Keyring::Alice, 0).encode().into());
+	assert!(!alice.future[0].requires.is_empty());
+	assert_eq!(alice.ready[0].views, vec![setup.client.info().best_hash]);
+	let bob = sender(Sr25519Keyring::Bob);
+	assert_eq!(bob.ready.iter().map(|tx| tx.hash).collect::<Vec<_>>(), vec![bob0]);
+	assert!(bob.future.is_empty());
+
+	let inspect: PoolContent<TransactionSummary<H256>, H256> =
+		api.call("txpool_inspect", EmptyParams::new()).await.unwrap();
+	assert_eq!(inspect.senders.len(), 2);
+	assert!(inspect
+		.senders
+		.iter()
+		.flat_map(|s| s.ready.iter().chain(&s.future))
+		.all(|tx| tx.views == 1));
+
+	let status: PoolStatus = api.call("txpool_status", EmptyParams::new()).await.unwrap();
+	assert_eq!((status.ready, status.future, status.dropped), (2, 1, 0));
+}
+
+#[tokio::test]
+async fn txpool_content_is_unsafe() {
+	let api = TestSetup::new(DenyUnsafe::Yes).api;
+
+	assert_matches!(
+		api.call::<_, PoolContent<TransactionDetails<H256, H256>, H256>>(
+			"txpool_content",
+			EmptyParams::new()
+		)
+		.await,
+		Err(RpcError::JsonRpc(err)) if err.message().contains("RPC call is unsafe")
+	);
+	assert!(api.call::<_, PoolStatus>("txpool_status", EmptyParams::new()).await.is_ok());
+}
+
+#[test]
+fn signer_of_returns_the_address_of_signed_transactions() {
+	let sender_of = signer_of::<Block, Address, Signature, TxExtension>();
+
+	assert_eq!(
+		sender_of(&uxt(Sr25519Keyring::Alice, 0)),
+		Some(Sr25519Keyring::Alice.to_account_id().encode())
+	);
+	assert_eq!(sender_of(&ExtrinsicBuilder::new_include_data(vec![1]).unsigned().build()), None);
+}
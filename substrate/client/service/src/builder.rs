@@ -25,6 +25,7 @@ use crate::{
 	start_rpc_servers, BuildGenesisBlock, GenesisBlockBuilder, RpcHandlers, SpawnTaskHandle,
 	TaskManager, TransactionPoolAdapter,
 };
+use codec::DecodeAll;
 use futures::{select, FutureExt, StreamExt};
 use jsonrpsee::RpcModule;
 use log::{debug, error, info};
@@ -43,7 +44,7 @@ use sc_executor::{
 };
 use sc_keystore::LocalKeystore;
 use sc_network::{
-	config::{FullNetworkConfiguration, ProtocolId, SyncMode},
+	config::{CheckpointSync, FullNetworkConfiguration, ProtocolId, SyncMode},
 	multiaddr::Protocol,
 	service::{
 		traits::{PeerStore, RequestResponseConfig},
@@ -64,14 +65,16 @@ use sc_network_sync::{
 		SyncingStrategy,
 	},
 	warp_request_handler::RequestHandler as WarpSyncRequestHandler,
-	SyncingService, WarpSyncConfig,
+	SyncingService, TrustedCheckpoint, WarpSyncConfig,
 };
 use sc_rpc::{
 	author::AuthorApiServer,
 	chain::ChainApiServer,
 	offchain::OffchainApiServer,
+	snapshot::SnapshotApiServer,
 	state::{ChildStateApiServer, StateApiServer},
 	system::SystemApiServer,
+	txpool::TxPoolApiServer,
 	DenyUnsafe, SubscriptionTaskExecutor,
 };
 use sc_rpc_spec_v2::{
@@ -90,7 +93,7 @@ use sp_consensus::block_validation::{
 };
 use sp_core::traits::{CodeExecutor, SpawnNamed};
 use sp_keystore::KeystorePtr;
-use sp_runtime::traits::{Block as BlockT, BlockIdTo, NumberFor, Zero};
+use sp_runtime::traits::{Block as BlockT, BlockIdTo, Header as HeaderT, NumberFor, Zero};
 use sp_storage::{ChildInfo, ChildType, PrefixedStorageKey};
 use std::{
 	str::FromStr,
@@ -602,8 +605,9 @@ where
 		.map(|registry| sc_rpc_spec_v2::transaction::TransactionMetrics::new(registry))
 		.transpose()?;
 
+	let rpc_local_lane = config.transaction_pool.rpc_local_lane();
 	let gen_rpc_module = || {
-		gen_rpc_module(
+		build_rpc_module(
 			task_manager.spawn_handle(),
 			client.clone(),
 			transaction_pool.clone(),
@@ -617,6 +621,7 @@ where
 			backend.clone(),
 			&*rpc_builder,
 			rpc_v2_metrics.clone(),
+			rpc_local_lane,
 		)
 	};
 
@@ -766,6 +771,61 @@ pub fn gen_rpc_module<TBl, TBackend, TCl, TRpc, TExPool>(
 	rpc_builder: &(dyn Fn(SubscriptionTaskExecutor) -> Result<RpcModule<TRpc>, Error>),
 	metrics: Option<sc_rpc_spec_v2::transaction::TransactionMetrics>,
 ) -> Result<RpcModule<()>, Error>
+where
+	TBl: BlockT,
+	TCl: ProvideRuntimeApi<TBl>
+		+ BlockchainEvents<TBl>
+		+ HeaderBackend<TBl>
+		+ HeaderMetadata<TBl, Error = sp_blockchain::Error>
+		+ ExecutorProvider<TBl>
+		+ CallApiAt<TBl>
+		+ ProofProvider<TBl>
+		+ StorageProvider<TBl, TBackend>
+		+ BlockBackend<TBl>
+		+ Send
+		+ Sync
+		+ 'static,
+	TBackend: sc_client_api::backend::Backend<TBl> + 'static,
+	<TCl as ProvideRuntimeApi<TBl>>::Api: sp_session::SessionKeys<TBl> + sp_api::Metadata<TBl>,
+	TExPool: MaintainedTransactionPool<Block = TBl, Hash = <TBl as BlockT>::Hash> + 'static,
+	TBl::Hash: Unpin,
+	TBl::Header: Unpin,
+{
+	build_rpc_module(
+		spawn_handle,
+		client,
+		transaction_pool,
+		keystore,
+		system_rpc_tx,
+		impl_name,
+		impl_version,
+		chain_spec,
+		state_pruning,
+		blocks_pruning,
+		backend,
+		rpc_builder,
+		metrics,
+		false,
+	)
+}
+
+/// Generate RPC module, optionally submitting RPC transactions into the local lane of the pool.
+fn build_rpc_module<TBl, TBackend, TCl, TRpc, TExPool>(
+	spawn_handle: SpawnTaskHandle,
+	client: Arc<TCl>,
+	transaction_pool: Arc<TExPool>,
+	keystore: KeystorePtr,
+	system_rpc_tx: TracingUnboundedSender<sc_rpc::system::Request<TBl>>,
+	impl_name: String,
+	impl_version: String,
+	chain_spec: &dyn ChainSpec,
+	state_pruning: &Option<PruningMode>,
+	blocks_pruning: BlocksPruning,
+	backend: Arc<TBackend>,
+	rpc_builder: &(dyn Fn(SubscriptionTaskExecutor) -> Result<RpcModule<TRpc>, Error>),
+	metrics: Option<sc_rpc_spec_v2::transaction::TransactionMetrics>,
+	rpc_local_lane: bool,
+) -> Result<RpcModule<()>, Error>
 where
 	TBl: BlockT,
 	TCl: ProvideRuntimeApi<TBl>
@@ -814,6 +874,7 @@ where
 		task_executor.clone(),
 		MAX_TRANSACTION_PER_CONNECTION,
 	)
+	.with_local_lane(rpc_local_lane)
 	.into_rpc();
 
 	let transaction_v2 = sc_rpc_spec_v2::transaction::Transaction::new(
@@ -822,6 +883,7 @@ where
 		task_executor.clone(),
 		metrics,
 	)
+	.with_local_lane(rpc_local_lane)
 	.into_rpc();
 
 	let chain_head_v2 = sc_rpc_spec_v2::chain_head::ChainHead::new(
@@ -859,15 +921,24 @@ where
 	)
 	.into_rpc();
 
+	// Additional [`RpcModule`]s defined in the node to fit the specific blockchain
+	let extra_rpcs = rpc_builder(task_executor.clone())?;
+
+	// The node may provide its own `txpool` RPCs, able to group the transactions by sender.
+	let txpool = (!extra_rpcs.method_names().any(|name| name == "txpool_content"))
+		.then(|| sc_rpc::txpool::TxPool::new(transaction_pool.clone()).into_rpc());
+
 	let author = sc_rpc::author::Author::new(
 		client.clone(),
 		transaction_pool,
 		keystore,
 		task_executor.clone(),
 	)
+	.with_local_lane(rpc_local_lane)
 	.into_rpc();
 
 	let system = sc_rpc::system::System::new(system_info, system_rpc_tx).into_rpc();
+	let snapshot = sc_rpc::snapshot::Snapshot::new(backend.clone()).into_rpc();
 
 	if let Some(storage) = backend.offchain_storage() {
 		let offchain = sc_rpc::offchain::Offchain::new(storage).into_rpc();
@@ -886,11 +957,13 @@ where
 	// Part of the old RPC spec.
 	rpc_api.merge(chain).map_err(|e| Error::Application(e.into()))?;
 	rpc_api.merge(author).map_err(|e| Error::Application(e.into()))?;
+	if let Some(txpool) = txpool {
+		rpc_api.merge(txpool).map_err(|e| Error::Application(e.into()))?;
+	}
 	rpc_api.merge(system).map_err(|e| Error::Application(e.into()))?;
+	rpc_api.merge(snapshot).map_err(|e| Error::Application(e.into()))?;
 	rpc_api.merge(state).map_err(|e| Error::Application(e.into()))?;
 	rpc_api.merge(child_state).map_err(|e| Error::Application(e.into()))?;
-	// Additional [`RpcModule`]s defined in the node to fit the specific blockchain
-	let extra_rpcs = rpc_builder(task_executor.clone())?;
 	rpc_api.merge(extra_rpcs).map_err(|e| Error::Application(e.into()))?;
 
 	Ok(rpc_api)
@@ -973,6 +1046,12 @@ where
 		Box::new(DefaultBlockAnnounceValidator)
 	};
 
+	// Keep networking and syncing data next to the database, so that it is removed together with
+	// the chain data.
+	if let Some(path) = config.database.path() {
+		net_config.set_data_dir(path.join("network"));
+	}
+
 	let network_service_provider = NetworkServiceProvider::new();
 	let protocol_id = config.protocol_id();
 	let fork_id = config.chain_spec.fork_id();
@@ -1411,7 +1490,25 @@ where
 		+ 'static,
 	Net: NetworkBackend<Block, <Block as BlockT>::Hash>,
 {
-	if warp_sync_config.is_none() && net_config.network_config.sync_mode.is_warp() {
+	let checkpoint =
+		net_config.checkpoint_sync().map(load_trusted_checkpoint::<Block>).transpose()?;
+	if let Some(checkpoint) = &checkpoint {
+		info!(
+			"🔖 Checkpoint sync from trusted block {}{}",
+			checkpoint.hash,
+			checkpoint
+				.header
+				.as_ref()
+				.map(|header| format!(" (#{})", header.number()))
+				.unwrap_or_default(),
+		);
+		net_config.network_config.sync_mode = SyncMode::Warp;
+	}
+
+	if warp_sync_config.is_none() &&
+		checkpoint.is_none() &&
+		net_config.network_config.sync_mode.is_warp()
+	{
 		return Err("Warp sync enabled, but no warp sync provider configured.".into())
 	}
 
@@ -1467,11 +1564,54 @@ where
 		metrics_registry: metrics_registry.cloned(),
 		state_request_protocol_name,
 		block_downloader,
+		state_sync_checkpoint_dir: net_config.data_dir().map(|dir| dir.join("state-sync")),
 	};
-	Ok(Box::new(PolkadotSyncingStrategy::new(
-		syncing_config,
-		client,
-		warp_sync_config,
-		warp_sync_protocol_name,
-	)?))
+	// The warp sync provider keeps serving proofs to other peers, but we sync from the checkpoint.
+	let warp_sync_config = checkpoint.map(WarpSyncConfig::WithCheckpoint).or(warp_sync_config);
+	Ok(Box::new(
+		PolkadotSyncingStrategy::new(
+			syncing_config,
+			client,
+			warp_sync_config,
+			warp_sync_protocol_name,
+		)?
+		.with_block_history(net_config.block_history()),
+	))
+}
+
+/// Load the trusted block of a checkpoint sync configuration.
+fn load_trusted_checkpoint<Block: BlockT>(
+	checkpoint: &CheckpointSync,
+) -> Result<TrustedCheckpoint<Block>, Error> {
+	match checkpoint {
+		CheckpointSync::Hash(hash) => {
+			let hash = Block::Hash::decode_all(&mut &hash[..])
+				.map_err(|e| format!("Invalid checkpoint sync block hash: {e}"))?;
+			Ok(TrustedCheckpoint { hash, header: None, justifications: None })
+		},
+		CheckpointSync::File(path) => {
+			let file = std::fs::File::open(path).map_err(|e| {
+				format!("Failed to open checkpoint sync file {}: {e}", path.display())
+			})?;
+			let json: serde_json::Value = serde_json::from_reader(std::io::BufReader::new(file))
+				.map_err(|e| format!("Invalid checkpoint sync file {}: {e}", path.display()))?;
+
+			// Either the output of `chain_getBlock` or of `chain_getHeader`.
+			let (header, justifications) = match json.get("block") {
+				Some(block) => (
+					block.get("header").cloned().unwrap_or_default(),
+					json.get("justifications").cloned().unwrap_or_default(),
+				),
+				None => (json, serde_json::Value::Null),
+			};
+			let header: Block::Header = serde_json::from_value(header).map_err(|e| {
+				format!("Invalid header in checkpoint sync file {}: {e}", path.display())
+			})?;
+			let justifications = serde_json::from_value(justifications).map_err(|e| {
+				format!("Invalid justifications in checkpoint sync file {}: {e}", path.display())
+			})?;
+
+			Ok(TrustedCheckpoint { hash: header.hash(), header: Some(header), justifications })
+		},
+	}
 }
@@ -53,7 +53,7 @@ use sp_api::{
 	ProvideRuntimeApi,
 };
 use sp_blockchain::{
-	self as blockchain, Backend as ChainBackend, CachedHeaderMetadata, Error,
+	self as blockchain, Backend as ChainBackend, BlockGapType, CachedHeaderMetadata, Error,
 	HeaderBackend as ChainHeaderBackend, HeaderMetadata, Info as BlockchainInfo,
 };
 use sp_consensus::{BlockOrigin, BlockStatus, Error as ConsensusError};
@@ -571,8 +571,12 @@ where
 		}
 
 		let info = self.backend.blockchain().info();
-		let gap_block =
-			info.block_gap.map_or(false, |gap| *import_headers.post().number() == gap.start);
+		let gap_block = info.block_gap.map_or(false, |gap| {
+			let number = *import_headers.post().number();
+			// Warp sync gaps are also filled backwards, from the top.
+			number == gap.start ||
+				(gap.gap_type == BlockGapType::MissingHeaderAndBody && number == gap.end)
+		});
 
 		// the block is lower than our last finalized block so it must revert
 		// finality, refusing import.
@@ -1798,6 +1802,12 @@ where
 		{
 			BlockStatus::InChainWithState | BlockStatus::Queued => {},
 			BlockStatus::Unknown if allow_missing_parent => {},
+			// The block history after warp sync is downloaded backwards, so the block at the top
+			// of the gap is imported before its parent.
+			BlockStatus::Unknown
+				if self.backend.blockchain().info().block_gap.map_or(false, |gap| {
+					gap.gap_type == BlockGapType::MissingHeaderAndBody && number == gap.end
+				}) => {},
 			BlockStatus::Unknown => return Ok(ImportResult::UnknownParent),
 			BlockStatus::InChainPruned if allow_missing_state => {},
 			BlockStatus::InChainPruned => return Ok(ImportResult::MissingState),
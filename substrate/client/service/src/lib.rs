@@ -43,8 +43,10 @@ use jsonrpsee::RpcModule;
 use log::{debug, error, trace, warn};
 use sc_client_api::{blockchain::HeaderBackend, BlockBackend, BlockchainEvents, ProofProvider};
 use sc_network::{
-	config::MultiaddrWithPeerId, service::traits::NetworkService, NetworkBackend, NetworkBlock,
-	NetworkPeers, NetworkStateInfo,
+	config::MultiaddrWithPeerId,
+	peer_store::{BanReason, BanTarget},
+	service::traits::NetworkService,
+	NetworkBackend, NetworkBlock, NetworkPeers, NetworkStateInfo,
 };
 use sc_network_sync::SyncingService;
 use sc_network_types::PeerId;
@@ -77,7 +79,8 @@ pub use sc_chain_spec::{
 };
 
 pub use config::{
-	BasePath, BlocksPruning, Configuration, DatabaseSource, PruningMode, Role, RpcMethods, TaskType,
+	BasePath, BlocksPruning, Configuration, DatabaseSource, PruningConstraints, PruningMode,
+	PruningWindowLimits, Role, RpcMethods, TaskType,
 };
 pub use sc_chain_spec::{
 	ChainSpec, ChainType, Extension as ChainSpecExtension, GenericChainSpec, NoExtension,
@@ -348,6 +351,51 @@ pub async fn build_system_rpc_future<
 				let _ =
 					sender.send(reserved_peers.iter().map(|peer_id| peer_id.to_base58()).collect());
 			},
+			sc_rpc::system::Request::NetworkBannedPeers(sender) => {
+				let banned_peers = network_service
+					.bans()
+					.into_iter()
+					.map(|ban| {
+						let (reputation, until) = match ban.reason {
+							BanReason::Manual { until } => (
+								None,
+								until.map(|until| {
+									until
+										.duration_since(std::time::UNIX_EPOCH)
+										.map_or(0, |since| since.as_secs())
+								}),
+							),
+							BanReason::Reputation(reputation) => (Some(reputation), None),
+						};
+
+						sc_rpc::system::BannedPeer {
+							target: ban.target.to_string(),
+							reputation,
+							until,
+						}
+					})
+					.collect();
+				let _ = sender.send(banned_peers);
+			},
+			sc_rpc::system::Request::NetworkBanPeer(target, until, sender) => {
+				let _ = match target.parse::<BanTarget>() {
+					Ok(target) => {
+						network_service.ban(target, until);
+						sender.send(Ok(()))
+					},
+					Err(e) => sender.send(Err(sc_rpc::system::error::Error::MalformattedPeerArg(
+						e.to_string(),
+					))),
+				};
+			},
+			sc_rpc::system::Request::NetworkUnbanPeer(target, sender) => {
+				let _ = match target.parse::<BanTarget>() {
+					Ok(target) => sender.send(Ok(network_service.unban(&target))),
+					Err(e) => sender.send(Err(sc_rpc::system::error::Error::MalformattedPeerArg(
+						e.to_string(),
+					))),
+				};
+			},
 			sc_rpc::system::Request::NodeRoles(sender) => {
 				use sc_rpc::system::NodeRole;
 
@@ -565,6 +613,10 @@ where
 			|tx| tx.is_propagable().then(|| tx.data().clone()),
 		)
 	}
+
+	fn transactions_to_rebroadcast(&self) -> Vec<H> {
+		self.pool.transactions_to_rebroadcast()
+	}
 }
 
 #[cfg(test)]
@@ -39,14 +39,17 @@
 //!
 //! # Pruning.
 //! See `RefWindow` for pruning algorithm details. `StateDb` prunes on each canonicalization until
-//! pruning constraints are satisfied.
+//! pruning constraints are satisfied. The constraints limit the number of blocks of the pruning
+//! window, which may also be limited by its age or its size. A sparse archive additionally keeps
+//! the state of every checkpoint block, i.e. every canonical block whose number is a multiple of
+//! the checkpoint interval.
 
 mod noncanonical;
 mod pruning;
 #[cfg(test)]
 mod test;
 
-use codec::Codec;
+use codec::{Codec, Decode, Encode};
 use log::trace;
 use noncanonical::NonCanonicalOverlay;
 use parking_lot::RwLock;
@@ -54,6 +57,8 @@ use pruning::{HaveBlock, RefWindow};
 use std::{
 	collections::{hash_map::Entry, HashMap},
 	fmt,
+	num::NonZeroU32,
+	time::{Duration, SystemTime},
 };
 
 const LOG_TARGET: &str = "state-db";
@@ -62,7 +67,13 @@ const PRUNING_MODE: &[u8] = b"mode";
 const PRUNING_MODE_ARCHIVE: &[u8] = b"archive";
 const PRUNING_MODE_ARCHIVE_CANON: &[u8] = b"archive_canonical";
 const PRUNING_MODE_CONSTRAINED: &[u8] = b"constrained";
+const PRUNING_MODE_SPARSE_ARCHIVE: &[u8] = b"sparse_archive";
+const CHECKPOINT_INTERVAL: &[u8] = b"checkpoint_interval";
 pub(crate) const DEFAULT_MAX_BLOCK_CONSTRAINT: u32 = 256;
+/// The maximum number of blocks of a pruning window limited by its age or size, unless limited
+/// explicitly. This is about a week of 6 second blocks.
+pub const DEFAULT_MAX_LIMITED_BLOCKS: u32 = 100_800;
+const DEFAULT_CHECKPOINT_INTERVAL: u32 = 1000;
 
 /// Database value type.
 pub type DBValue = Vec<u8>;
@@ -226,6 +237,40 @@ pub struct Constraints {
 	pub max_blocks: Option<u32>,
 }
 
+/// Limits of a pruning window on top of its [`Constraints`]. Blocks are pruned until all the
+/// specified limits are satisfied.
+///
+/// The window is always limited in blocks: when [`Constraints::max_blocks`] is not specified, it
+/// keeps at most [`DEFAULT_MAX_LIMITED_BLOCKS`] blocks. This bounds the window while syncing,
+/// when blocks are canonicalized much faster than they are produced.
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct WindowLimits {
+	/// Maximum age of the blocks, measured from the time they were canonicalized.
+	pub max_age: Option<Duration>,
+	/// Maximum size in bytes of the state nodes inserted by the blocks in the pruning window.
+	///
+	/// This approximates the space taken on disk by the pruning window, on top of the latest
+	/// state.
+	pub max_size: Option<u64>,
+}
+
+impl WindowLimits {
+	/// Returns `true` if the pruning window `pruning` satisfies the limits at time `now`.
+	fn are_satisfied<BlockHash: Hash, Key: Hash, D: MetaDb>(
+		&self,
+		pruning: &RefWindow<BlockHash, Key, D>,
+		now: u64,
+	) -> bool {
+		let age = self.max_age.map_or(true, |max_age| {
+			pruning.next_timestamp().map_or(true, |timestamp| {
+				now.saturating_sub(timestamp) <= max_age.as_millis() as u64
+			})
+		});
+		let size = self.max_size.map_or(true, |max_size| pruning.state_size() <= max_size);
+		age && size
+	}
+}
+
 /// Pruning mode.
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub enum PruningMode {
@@ -235,6 +280,24 @@ pub enum PruningMode {
 	ArchiveAll,
 	/// Canonicalization discards non-canonical nodes. All the canonical nodes are kept in the DB.
 	ArchiveCanonical,
+	/// Maintain a pruning window, which is also limited by the age or the size of the kept
+	/// states.
+	Limited {
+		/// The constraints of the pruning window.
+		constraints: Constraints,
+		/// The limits of the pruning window.
+		limits: WindowLimits,
+	},
+	/// Maintain a pruning window, but keep the state of every canonical block whose number is a
+	/// multiple of `checkpoint_interval`.
+	SparseArchive {
+		/// The number of blocks between two checkpoints.
+		checkpoint_interval: NonZeroU32,
+		/// The constraints of the pruning window.
+		constraints: Constraints,
+		/// The limits of the pruning window.
+		limits: WindowLimits,
+	},
 }
 
 impl PruningMode {
@@ -247,16 +310,53 @@ impl PruningMode {
 	pub fn is_archive(&self) -> bool {
 		match *self {
 			PruningMode::ArchiveAll | PruningMode::ArchiveCanonical => true,
-			PruningMode::Constrained(_) => false,
+			PruningMode::Constrained(_) |
+			PruningMode::Limited { .. } |
+			PruningMode::SparseArchive { .. } => false,
+		}
+	}
+
+	/// Returns the constraints of the pruning window, if any.
+	pub fn constraints(&self) -> Option<&Constraints> {
+		match self {
+			PruningMode::Constrained(constraints) |
+			PruningMode::Limited { constraints, .. } |
+			PruningMode::SparseArchive { constraints, .. } => Some(constraints),
+			PruningMode::ArchiveAll | PruningMode::ArchiveCanonical => None,
+		}
+	}
+
+	/// Returns the limits of the pruning window, if any.
+	pub fn window_limits(&self) -> Option<&WindowLimits> {
+		match self {
+			PruningMode::Limited { limits, .. } | PruningMode::SparseArchive { limits, .. } =>
+				Some(limits),
+			PruningMode::Constrained(_) |
+			PruningMode::ArchiveAll |
+			PruningMode::ArchiveCanonical => None,
 		}
 	}
 
+	/// Returns the maximum number of blocks of the pruning window, if any.
+	fn max_window_blocks(&self) -> Option<u32> {
+		let constraints = self.constraints()?;
+		let limited = self
+			.window_limits()
+			.map_or(false, |limits| limits.max_age.is_some() || limits.max_size.is_some());
+		Some(match constraints.max_blocks {
+			Some(max_blocks) => max_blocks,
+			None if limited => DEFAULT_MAX_LIMITED_BLOCKS,
+			None => 0,
+		})
+	}
+
 	/// Returns the pruning mode
 	pub fn id(&self) -> &[u8] {
 		match self {
 			PruningMode::ArchiveAll => PRUNING_MODE_ARCHIVE,
 			PruningMode::ArchiveCanonical => PRUNING_MODE_ARCHIVE_CANON,
-			PruningMode::Constrained(_) => PRUNING_MODE_CONSTRAINED,
+			PruningMode::Constrained(_) | PruningMode::Limited { .. } => PRUNING_MODE_CONSTRAINED,
+			PruningMode::SparseArchive { .. } => PRUNING_MODE_SPARSE_ARCHIVE,
 		}
 	}
 
@@ -265,9 +365,24 @@ impl PruningMode {
 			PRUNING_MODE_ARCHIVE => Some(Self::ArchiveAll),
 			PRUNING_MODE_ARCHIVE_CANON => Some(Self::ArchiveCanonical),
 			PRUNING_MODE_CONSTRAINED => Some(Self::Constrained(Default::default())),
+			PRUNING_MODE_SPARSE_ARCHIVE => Some(Self::SparseArchive {
+				checkpoint_interval: NonZeroU32::new(DEFAULT_CHECKPOINT_INTERVAL)
+					.expect("the default checkpoint interval is not zero"),
+				constraints: Default::default(),
+				limits: Default::default(),
+			}),
 			_ => None,
 		}
 	}
+
+	/// Returns the meta-data describing the mode.
+	fn meta(&self) -> Vec<(Vec<u8>, DBValue)> {
+		let mut meta = vec![(to_meta_key(PRUNING_MODE, &()), self.id().to_owned())];
+		if let PruningMode::SparseArchive { checkpoint_interval, .. } = self {
+			meta.push((to_meta_key(CHECKPOINT_INTERVAL, &()), checkpoint_interval.encode()));
+		}
+		meta
+	}
 }
 
 impl Default for PruningMode {
@@ -282,6 +397,13 @@ impl Default for Constraints {
 	}
 }
 
+/// Returns the current time in milliseconds since the UNIX epoch.
+fn now() -> u64 {
+	SystemTime::now()
+		.duration_since(SystemTime::UNIX_EPOCH)
+		.map_or(0, |since_epoch| since_epoch.as_millis() as u64)
+}
+
 fn to_meta_key<S: Codec>(suffix: &[u8], data: &S) -> Vec<u8> {
 	let mut buffer = data.encode();
 	buffer.extend(suffix);
@@ -316,10 +438,14 @@ impl<BlockHash: Hash, Key: Hash, D: MetaDb> StateDbSync<BlockHash, Key, D> {
 		trace!(target: LOG_TARGET, "StateDb settings: {:?}. Ref-counting: {}", mode, ref_counting);
 
 		let non_canonical: NonCanonicalOverlay<BlockHash, Key> = NonCanonicalOverlay::new(&db)?;
-		let pruning: Option<RefWindow<BlockHash, Key, D>> = match mode {
-			PruningMode::Constrained(Constraints { max_blocks }) =>
-				Some(RefWindow::new(db, max_blocks.unwrap_or(0), ref_counting)?),
-			PruningMode::ArchiveAll | PruningMode::ArchiveCanonical => None,
+		let checkpoint_interval = match &mode {
+			PruningMode::SparseArchive { checkpoint_interval, .. } => Some(*checkpoint_interval),
+			_ => None,
+		};
+		let pruning: Option<RefWindow<BlockHash, Key, D>> = match mode.max_window_blocks() {
+			Some(max_blocks) =>
+				Some(RefWindow::new(db, max_blocks, ref_counting, checkpoint_interval)?),
+			None => None,
 		};
 
 		Ok(StateDbSync { mode, non_canonical, pruning, pinned: Default::default(), ref_counting })
@@ -338,14 +464,22 @@ impl<BlockHash: Hash, Key: Hash, D: MetaDb> StateDbSync<BlockHash, Key, D> {
 				// write changes immediately
 				Ok(CommitSet { data: changeset, meta: Default::default() })
 			},
-			PruningMode::Constrained(_) | PruningMode::ArchiveCanonical => self
+			PruningMode::Constrained(_) |
+			PruningMode::ArchiveCanonical |
+			PruningMode::Limited { .. } |
+			PruningMode::SparseArchive { .. } => self
 				.non_canonical
 				.insert(hash, number, parent_hash, changeset)
 				.map_err(Into::into),
 		}
 	}
 
-	fn canonicalize_block(&mut self, hash: &BlockHash) -> Result<CommitSet<Key>, Error<D::Error>> {
+	/// Canonicalize a block at time `now`, in milliseconds since the UNIX epoch.
+	fn canonicalize_block(
+		&mut self,
+		hash: &BlockHash,
+		now: u64,
+	) -> Result<CommitSet<Key>, Error<D::Error>> {
 		// NOTE: it is important that the change to `LAST_CANONICAL` (emit from
 		// `non_canonical.canonicalize`) and the insert of the new pruning journal (emit from
 		// `pruning.note_canonical`) are collected into the same `CommitSet` and are committed to
@@ -359,9 +493,9 @@ impl<BlockHash: Hash, Key: Hash, D: MetaDb> StateDbSync<BlockHash, Key, D> {
 			commit.data.deleted.clear();
 		}
 		if let Some(ref mut pruning) = self.pruning {
-			pruning.note_canonical(hash, number, &mut commit)?;
+			pruning.note_canonical(hash, number, now, &mut commit)?;
 		}
-		self.prune(&mut commit)?;
+		self.prune(&mut commit, now)?;
 		Ok(commit)
 	}
 
@@ -380,7 +514,10 @@ impl<BlockHash: Hash, Key: Hash, D: MetaDb> StateDbSync<BlockHash, Key, D> {
 	fn is_pruned(&self, hash: &BlockHash, number: u64) -> IsPruned {
 		match self.mode {
 			PruningMode::ArchiveAll => IsPruned::NotPruned,
-			PruningMode::ArchiveCanonical | PruningMode::Constrained(_) => {
+			PruningMode::ArchiveCanonical |
+			PruningMode::Constrained(_) |
+			PruningMode::Limited { .. } |
+			PruningMode::SparseArchive { .. } => {
 				if self
 					.non_canonical
 					.last_canonicalized_block_number()
@@ -407,12 +544,15 @@ impl<BlockHash: Hash, Key: Hash, D: MetaDb> StateDbSync<BlockHash, Key, D> {
 		}
 	}
 
-	fn prune(&mut self, commit: &mut CommitSet<Key>) -> Result<(), Error<D::Error>> {
-		if let (&mut Some(ref mut pruning), PruningMode::Constrained(constraints)) =
-			(&mut self.pruning, &self.mode)
+	fn prune(&mut self, commit: &mut CommitSet<Key>, now: u64) -> Result<(), Error<D::Error>> {
+		if let (&mut Some(ref mut pruning), Some(max_blocks)) =
+			(&mut self.pruning, self.mode.max_window_blocks())
 		{
+			let limits = self.mode.window_limits();
 			loop {
-				if pruning.window_size() <= constraints.max_blocks.unwrap_or(0) as u64 {
+				if pruning.window_size() <= max_blocks as u64 &&
+					limits.map_or(true, |limits| limits.are_satisfied(pruning, now))
+				{
 					break
 				}
 
@@ -442,16 +582,20 @@ impl<BlockHash: Hash, Key: Hash, D: MetaDb> StateDbSync<BlockHash, Key, D> {
 	fn revert_one(&mut self) -> Option<CommitSet<Key>> {
 		match self.mode {
 			PruningMode::ArchiveAll => Some(CommitSet::default()),
-			PruningMode::ArchiveCanonical | PruningMode::Constrained(_) =>
-				self.non_canonical.revert_one(),
+			PruningMode::ArchiveCanonical |
+			PruningMode::Constrained(_) |
+			PruningMode::Limited { .. } |
+			PruningMode::SparseArchive { .. } => self.non_canonical.revert_one(),
 		}
 	}
 
 	fn remove(&mut self, hash: &BlockHash) -> Option<CommitSet<Key>> {
 		match self.mode {
 			PruningMode::ArchiveAll => Some(CommitSet::default()),
-			PruningMode::ArchiveCanonical | PruningMode::Constrained(_) =>
-				self.non_canonical.remove(hash),
+			PruningMode::ArchiveCanonical |
+			PruningMode::Constrained(_) |
+			PruningMode::Limited { .. } |
+			PruningMode::SparseArchive { .. } => self.non_canonical.remove(hash),
 		}
 	}
 
@@ -461,7 +605,10 @@ impl<BlockHash: Hash, Key: Hash, D: MetaDb> StateDbSync<BlockHash, Key, D> {
 	{
 		match self.mode {
 			PruningMode::ArchiveAll => Ok(()),
-			PruningMode::ArchiveCanonical | PruningMode::Constrained(_) => {
+			PruningMode::ArchiveCanonical |
+			PruningMode::Constrained(_) |
+			PruningMode::Limited { .. } |
+			PruningMode::SparseArchive { .. } => {
 				let have_block = self.non_canonical.have_block(hash) ||
 					self.pruning.as_ref().map_or_else(
 						|| hint(),
@@ -556,18 +703,15 @@ impl<BlockHash: Hash, Key: Hash, D: MetaDb> StateDb<BlockHash, Key, D> {
 			(false, Some(stored), Some(requested)) => choose_pruning_mode(stored, requested)?,
 		};
 
-		let db_init_commit_set = if should_init {
-			let mut cs: CommitSet<Key> = Default::default();
-
-			let key = to_meta_key(PRUNING_MODE, &());
-			let value = selected_mode.id().to_owned();
-
-			cs.meta.inserted.push((key, value));
-
-			cs
-		} else {
-			Default::default()
-		};
+		// The checkpoint interval of a sparse archive may change between runs.
+		let db_init_commit_set =
+			if should_init || matches!(selected_mode, PruningMode::SparseArchive { .. }) {
+				let mut cs: CommitSet<Key> = Default::default();
+				cs.meta.inserted.extend(selected_mode.meta());
+				cs
+			} else {
+				Default::default()
+			};
 
 		let state_db =
 			StateDb { db: RwLock::new(StateDbSync::new(selected_mode, ref_counting, db)?) };
@@ -592,7 +736,7 @@ impl<BlockHash: Hash, Key: Hash, D: MetaDb> StateDb<BlockHash, Key, D> {
 
 	/// Finalize a previously inserted block.
 	pub fn canonicalize_block(&self, hash: &BlockHash) -> Result<CommitSet<Key>, Error<D::Error>> {
-		self.db.write().canonicalize_block(hash)
+		self.db.write().canonicalize_block(hash, now())
 	}
 
 	/// Prevents pruning of specified block and its descendants.
@@ -660,6 +804,117 @@ impl<BlockHash: Hash, Key: Hash, D: MetaDb> StateDb<BlockHash, Key, D> {
 	}
 }
 
+/// Returns the meta-data of a database holding the canonical state of a single block.
+///
+/// This is used when the state of `hash` was written to an empty database out of band, e.g. when
+/// restoring a snapshot. The block is treated as the last canonicalized and the last pruned block,
+/// so that its descendants can be imported on top of it.
+pub fn restored_meta<BlockHash: Hash, Key: Hash>(
+	mode: &PruningMode,
+	hash: &BlockHash,
+	number: u64,
+) -> CommitSet<Key> {
+	let mut commit = CommitSet::default();
+	commit.meta.inserted.extend(mode.meta());
+	commit
+		.meta
+		.inserted
+		.push((to_meta_key(noncanonical::LAST_CANONICAL, &()), (hash.clone(), number).encode()));
+	if mode.constraints().is_some() {
+		commit
+			.meta
+			.inserted
+			.push((to_meta_key(pruning::LAST_PRUNED, &()), number.encode()));
+	}
+	commit
+}
+
+/// An inconsistency of the meta-data found by [`check_meta`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MetaIssue<BlockHash> {
+	/// The entry stored under `key` can't be decoded.
+	Undecodable { key: Vec<u8> },
+	/// The journal of a non-canonical block doesn't descend from the last canonicalized block.
+	OrphanJournal { number: u64, hash: BlockHash },
+	/// The last canonicalized block is not the canonical block with this number.
+	NonCanonicalLastCanonicalized { number: u64, hash: BlockHash },
+	/// A block of the pruning window has no journal.
+	MissingPruningJournal { number: u64 },
+	/// The journal of a block of the pruning window belongs to another block.
+	MismatchedPruningJournal { number: u64, expected: BlockHash, found: BlockHash },
+}
+
+impl<BlockHash> MetaIssue<BlockHash> {
+	/// Returns whether the change set returned by [`check_meta`] repairs the issue.
+	pub fn is_repairable(&self) -> bool {
+		!matches!(self, MetaIssue::NonCanonicalLastCanonicalized { .. })
+	}
+}
+
+impl<BlockHash: fmt::Debug> fmt::Display for MetaIssue<BlockHash> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			MetaIssue::Undecodable { key } =>
+				write!(f, "Undecodable state meta-data entry {:02x?}", key),
+			MetaIssue::OrphanJournal { number, hash } => write!(
+				f,
+				"Journal of non-canonical block #{} ({:?}) has no known parent",
+				number, hash
+			),
+			MetaIssue::NonCanonicalLastCanonicalized { number, hash } =>
+				write!(f, "Last canonicalized block #{} ({:?}) is not canonical", number, hash),
+			MetaIssue::MissingPruningJournal { number } =>
+				write!(f, "Pruning journal of block #{} is missing", number),
+			MetaIssue::MismatchedPruningJournal { number, expected, found } => write!(
+				f,
+				"Pruning journal of block #{} belongs to {:?} instead of {:?}",
+				number, found, expected
+			),
+		}
+	}
+}
+
+/// The result of [`check_meta`].
+#[derive(Debug)]
+pub struct MetaCheck<BlockHash: Hash, Key: Hash> {
+	/// The inconsistencies found.
+	pub issues: Vec<MetaIssue<BlockHash>>,
+	/// The meta-data changes repairing the issues that can be repaired.
+	pub repair: CommitSet<Key>,
+}
+
+/// Checks the consistency of the meta-data stored in `db`.
+///
+/// The journals of the blocks that are not canonicalized yet must descend from the last
+/// canonicalized block, which must be canonical. The journals of the pruning window must exist
+/// and belong to the canonical blocks. `canonical_hash` returns the hash of the canonical block
+/// with the given number, the blocks it doesn't know about are not checked.
+///
+/// This doesn't need the database to be opened with [`StateDb::open`], so that it also works on
+/// meta-data that can't be loaded.
+pub fn check_meta<BlockHash: Hash, Key: Hash, D: MetaDb>(
+	db: &D,
+	canonical_hash: impl Fn(u64) -> Option<BlockHash>,
+) -> Result<MetaCheck<BlockHash, Key>, Error<D::Error>> {
+	let mut check = MetaCheck { issues: Vec::new(), repair: CommitSet::default() };
+	let last_canonicalized_key = to_meta_key(noncanonical::LAST_CANONICAL, &());
+	let Some(last_canonicalized) = db.get_meta(&last_canonicalized_key).map_err(Error::Db)? else {
+		return Ok(check)
+	};
+	let (hash, number) = <(BlockHash, u64)>::decode(&mut last_canonicalized.as_slice())?;
+	if canonical_hash(number).map_or(false, |canonical| canonical != hash) {
+		check
+			.issues
+			.push(MetaIssue::NonCanonicalLastCanonicalized { number, hash: hash.clone() });
+	}
+	noncanonical::check_journals(db, &(hash, number), &mut check.issues, &mut check.repair)?;
+	let mode = fetch_stored_pruning_mode(db)?;
+	if mode.map_or(false, |mode| mode.constraints().is_some()) {
+		pruning::check_journals(db, number, canonical_hash, &mut check.issues, &mut check.repair)?;
+	}
+	Ok(check)
+}
+
 /// The result return by `StateDb::is_pruned`
 #[derive(Debug, PartialEq, Eq)]
 pub enum IsPruned {
@@ -671,10 +926,20 @@ pub enum IsPruned {
 	MaybePruned,
 }
 
-fn fetch_stored_pruning_mode<D: MetaDb>(db: &D) -> Result<Option<PruningMode>, Error<D::Error>> {
+/// Returns the pruning mode stored in the meta-data of the database, if any.
+pub fn fetch_stored_pruning_mode<D: MetaDb>(
+	db: &D,
+) -> Result<Option<PruningMode>, Error<D::Error>> {
 	let meta_key_mode = to_meta_key(PRUNING_MODE, &());
 	if let Some(stored_mode) = db.get_meta(&meta_key_mode).map_err(Error::Db)? {
-		if let Some(mode) = PruningMode::from_id(&stored_mode) {
+		if let Some(mut mode) = PruningMode::from_id(&stored_mode) {
+			if let PruningMode::SparseArchive { checkpoint_interval, .. } = &mut mode {
+				let key = to_meta_key(CHECKPOINT_INTERVAL, &());
+				let stored_interval = db.get_meta(&key).map_err(Error::Db)?.ok_or_else(|| {
+					StateDbError::Metadata("The checkpoint interval is not stored".into())
+				})?;
+				*checkpoint_interval = Decode::decode(&mut stored_interval.as_slice())?;
+			}
 			Ok(Some(mode))
 		} else {
 			Err(StateDbError::Metadata(format!(
@@ -698,6 +963,10 @@ fn choose_pruning_mode(
 			Ok(PruningMode::ArchiveCanonical),
 		(PruningMode::Constrained(_), PruningMode::Constrained(requested)) =>
 			Ok(PruningMode::Constrained(requested)),
+		// The limits are not stored, a constrained window may be limited or not between runs.
+		(PruningMode::Constrained(_), requested @ PruningMode::Limited { .. }) => Ok(requested),
+		(PruningMode::SparseArchive { .. }, requested @ PruningMode::SparseArchive { .. }) =>
+			Ok(requested),
 		(stored, requested) => Err(StateDbError::IncompatiblePruningModes { requested, stored }),
 	}
 }
@@ -705,10 +974,13 @@ fn choose_pruning_mode(
 #[cfg(test)]
 mod tests {
 	use crate::{
+		check_meta, noncanonical, pruning,
 		test::{make_changeset, make_db, TestDb},
-		Constraints, Error, IsPruned, PruningMode, StateDb, StateDbError,
+		to_meta_key, CommitSet, Constraints, Error, IsPruned, LastCanonicalized, MetaIssue,
+		PruningMode, StateDb, StateDbError, WindowLimits, DEFAULT_MAX_LIMITED_BLOCKS,
 	};
 	use sp_core::H256;
+	use std::{num::NonZeroU32, time::Duration};
 
 	fn make_test_db(settings: PruningMode) -> (TestDb, StateDb<H256, H256, TestDb>) {
 		let mut db = make_db(&[91, 921, 922, 93, 94]);
@@ -773,6 +1045,73 @@ mod tests {
 		(db, state_db)
 	}
 
+	#[test]
+	fn check_meta_finds_and_repairs_issues() {
+		let canonical_hash = |number| match number {
+			1 => Some(H256::from_low_u64_be(1)),
+			2 => Some(H256::from_low_u64_be(21)),
+			3 => Some(H256::from_low_u64_be(3)),
+			_ => None,
+		};
+		let (mut db, state_db) = make_test_db(PruningMode::blocks_pruning(256));
+		let check = check_meta::<H256, H256, _>(&db, canonical_hash).unwrap();
+		assert!(check.issues.is_empty());
+
+		db.commit(
+			&state_db
+				.insert_block(
+					&H256::from_low_u64_be(44),
+					4,
+					&H256::from_low_u64_be(3),
+					make_changeset(&[44], &[]),
+				)
+				.unwrap(),
+		);
+		db.commit(
+			&state_db
+				.insert_block(
+					&H256::from_low_u64_be(5),
+					5,
+					&H256::from_low_u64_be(44),
+					make_changeset(&[5], &[]),
+				)
+				.unwrap(),
+		);
+		// Lose the journals of block 44 and of the canonical block 2.
+		let mut commit = CommitSet::default();
+		commit
+			.meta
+			.deleted
+			.push(to_meta_key(noncanonical::NON_CANONICAL_JOURNAL, &(4u64, 1u64)));
+		commit.meta.deleted.push(to_meta_key(pruning::PRUNING_JOURNAL, &2u64));
+		db.commit(&commit);
+
+		let check = check_meta::<H256, H256, _>(&db, canonical_hash).unwrap();
+		assert_eq!(
+			check.issues,
+			vec![
+				MetaIssue::OrphanJournal { number: 5, hash: H256::from_low_u64_be(5) },
+				MetaIssue::MissingPruningJournal { number: 2 },
+			]
+		);
+		assert!(check.issues.iter().all(MetaIssue::is_repairable));
+		db.commit(&check.repair);
+		assert!(check_meta::<H256, H256, _>(&db, canonical_hash).unwrap().issues.is_empty());
+		let (_, state_db) = StateDb::<H256, H256, _>::open(db, None, false, false).unwrap();
+		assert!(state_db.canonicalize_block(&H256::from_low_u64_be(4)).is_ok());
+	}
+
+	#[test]
+	fn check_meta_reports_non_canonical_last_canonicalized() {
+		let (db, _) = make_test_db(PruningMode::blocks_pruning(256));
+		let check = check_meta::<H256, H256, _>(&db, |_| Some(H256::from_low_u64_be(42))).unwrap();
+		assert_eq!(
+			check.issues[0],
+			MetaIssue::NonCanonicalLastCanonicalized { number: 3, hash: H256::from_low_u64_be(3) }
+		);
+		assert!(!check.issues[0].is_repairable());
+	}
+
 	#[test]
 	fn full_archive_keeps_everything() {
 		let (db, sdb) = make_test_db(PruningMode::ArchiveAll);
@@ -788,8 +1127,7 @@ mod tests {
 
 	#[test]
 	fn block_record_unavailable() {
-		let (mut db, state_db) =
-			make_test_db(PruningMode::Constrained(Constraints { max_blocks: Some(1) }));
+		let (mut db, state_db) = make_test_db(PruningMode::blocks_pruning(1));
 		// import 2 blocks
 		for i in &[5, 6] {
 			db.commit(
@@ -823,13 +1161,13 @@ mod tests {
 
 	#[test]
 	fn prune_window_0() {
-		let (db, _) = make_test_db(PruningMode::Constrained(Constraints { max_blocks: Some(0) }));
+		let (db, _) = make_test_db(PruningMode::blocks_pruning(0));
 		assert!(db.data_eq(&make_db(&[21, 3, 922, 94])));
 	}
 
 	#[test]
 	fn prune_window_1() {
-		let (db, sdb) = make_test_db(PruningMode::Constrained(Constraints { max_blocks: Some(1) }));
+		let (db, sdb) = make_test_db(PruningMode::blocks_pruning(1));
 		assert_eq!(sdb.is_pruned(&H256::from_low_u64_be(0), 0), IsPruned::Pruned);
 		assert_eq!(sdb.is_pruned(&H256::from_low_u64_be(1), 1), IsPruned::Pruned);
 		assert_eq!(sdb.is_pruned(&H256::from_low_u64_be(21), 2), IsPruned::Pruned);
@@ -839,7 +1177,7 @@ mod tests {
 
 	#[test]
 	fn prune_window_2() {
-		let (db, sdb) = make_test_db(PruningMode::Constrained(Constraints { max_blocks: Some(2) }));
+		let (db, sdb) = make_test_db(PruningMode::blocks_pruning(2));
 		assert_eq!(sdb.is_pruned(&H256::from_low_u64_be(0), 0), IsPruned::Pruned);
 		assert_eq!(sdb.is_pruned(&H256::from_low_u64_be(1), 1), IsPruned::Pruned);
 		assert_eq!(sdb.is_pruned(&H256::from_low_u64_be(21), 2), IsPruned::NotPruned);
@@ -863,7 +1201,7 @@ mod tests {
 				)
 				.unwrap(),
 		);
-		let new_mode = PruningMode::Constrained(Constraints { max_blocks: Some(2) });
+		let new_mode = PruningMode::blocks_pruning(2);
 		let state_db_open_result: Result<(_, StateDb<H256, H256, TestDb>), _> =
 			StateDb::open(db.clone(), Some(new_mode), false, false);
 		assert!(state_db_open_result.is_err());
@@ -946,4 +1284,128 @@ mod tests {
 			check_stored_and_requested_mode_compatibility(created, reopened, expected);
 		}
 	}
+
+	#[test]
+	fn restored_meta_allows_importing_descendants() {
+		for mode in [PruningMode::blocks_pruning(1), PruningMode::ArchiveCanonical] {
+			let mut db = make_db(&[]);
+			db.commit(&crate::restored_meta(&mode, &H256::from_low_u64_be(10), 10));
+			let (init, state_db) =
+				StateDb::<H256, H256, TestDb>::open(db.clone(), Some(mode), false, false).unwrap();
+			db.commit(&init);
+
+			for i in 11..14 {
+				db.commit(
+					&state_db
+						.insert_block(
+							&H256::from_low_u64_be(i),
+							i,
+							&H256::from_low_u64_be(i - 1),
+							make_changeset(&[i], &[]),
+						)
+						.unwrap(),
+				);
+				db.commit(&state_db.canonicalize_block(&H256::from_low_u64_be(i)).unwrap());
+			}
+			assert_eq!(state_db.last_canonicalized(), LastCanonicalized::Block(13));
+		}
+	}
+
+	/// Imports blocks `1..=count`, each inserting a node and deleting the one of its parent, and
+	/// canonicalizes them at `5s * number`.
+	fn import_chain(mode: PruningMode, count: u64) -> (TestDb, StateDb<H256, H256, TestDb>) {
+		let mut db = make_db(&[]);
+		let (init, state_db) = StateDb::open(db.clone(), Some(mode), false, true).unwrap();
+		db.commit(&init);
+		for i in 1..=count {
+			let hash = H256::from_low_u64_be(i);
+			let deleted = if i > 1 { vec![i - 1] } else { vec![] };
+			db.commit(
+				&state_db
+					.insert_block(
+						&hash,
+						i,
+						&H256::from_low_u64_be(i - 1),
+						make_changeset(&[i], &deleted),
+					)
+					.unwrap(),
+			);
+			db.commit(&state_db.db.write().canonicalize_block(&hash, i * 5000).unwrap());
+		}
+		(db, state_db)
+	}
+
+	#[test]
+	fn prune_by_age() {
+		let mode = PruningMode::Limited {
+			constraints: Constraints { max_blocks: None },
+			limits: WindowLimits { max_age: Some(Duration::from_secs(10)), max_size: None },
+		};
+		assert_eq!(mode.max_window_blocks(), Some(DEFAULT_MAX_LIMITED_BLOCKS));
+		let (_, sdb) = import_chain(mode, 5);
+		assert_eq!(sdb.is_pruned(&H256::from_low_u64_be(2), 2), IsPruned::Pruned);
+		assert_eq!(sdb.is_pruned(&H256::from_low_u64_be(3), 3), IsPruned::NotPruned);
+		assert_eq!(sdb.is_pruned(&H256::from_low_u64_be(5), 5), IsPruned::NotPruned);
+	}
+
+	#[test]
+	fn prune_by_age_is_bounded_in_blocks() {
+		// blocks canonicalized in a burst, as while syncing, are still pruned
+		let mode = PruningMode::Limited {
+			constraints: Constraints { max_blocks: Some(2) },
+			limits: WindowLimits { max_age: Some(Duration::from_secs(3600)), max_size: None },
+		};
+		let (_, sdb) = import_chain(mode, 5);
+		assert_eq!(sdb.is_pruned(&H256::from_low_u64_be(3), 3), IsPruned::Pruned);
+		assert_eq!(sdb.is_pruned(&H256::from_low_u64_be(4), 4), IsPruned::NotPruned);
+	}
+
+	#[test]
+	fn prune_by_size() {
+		// every block inserts a single node of 32 bytes
+		let mode = PruningMode::Limited {
+			constraints: Constraints { max_blocks: None },
+			limits: WindowLimits { max_age: None, max_size: Some(64) },
+		};
+		let (db, sdb) = import_chain(mode, 5);
+		assert_eq!(sdb.is_pruned(&H256::from_low_u64_be(3), 3), IsPruned::Pruned);
+		assert_eq!(sdb.is_pruned(&H256::from_low_u64_be(4), 4), IsPruned::NotPruned);
+		// pruning #3 removed the node of #2
+		assert!(db.data_eq(&make_db(&[3, 4, 5])));
+	}
+
+	#[test]
+	fn sparse_archive_keeps_checkpoints() {
+		let mode = PruningMode::SparseArchive {
+			checkpoint_interval: NonZeroU32::new(2).unwrap(),
+			constraints: Constraints { max_blocks: Some(0) },
+			limits: Default::default(),
+		};
+		let (db, sdb) = import_chain(mode.clone(), 5);
+		// the nodes of #2 and #4 are kept
+		assert!(db.data_eq(&make_db(&[2, 4, 5])));
+		assert_eq!(sdb.is_pruned(&H256::from_low_u64_be(2), 2), IsPruned::MaybePruned);
+		assert_eq!(sdb.is_pruned(&H256::from_low_u64_be(3), 3), IsPruned::Pruned);
+
+		// the checkpoint interval is restored
+		std::mem::drop(sdb);
+		let (_, sdb) = StateDb::<H256, H256, TestDb>::open(db.clone(), None, false, false).unwrap();
+		assert_eq!(
+			sdb.pruning_mode(),
+			PruningMode::SparseArchive {
+				checkpoint_interval: NonZeroU32::new(2).unwrap(),
+				constraints: Default::default(),
+				limits: Default::default(),
+			}
+		);
+		assert!(matches!(
+			StateDb::<H256, H256, TestDb>::open(
+				db,
+				Some(PruningMode::blocks_pruning(256)),
+				false,
+				false
+			),
+			Err(Error::StateDb(StateDbError::IncompatiblePruningModes { .. }))
+		));
+	}
 }
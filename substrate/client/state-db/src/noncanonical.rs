@@ -22,10 +22,12 @@
 
 use crate::{LOG_TARGET, LOG_TARGET_PIN};
 
-use super::{to_meta_key, ChangeSet, CommitSet, DBValue, Error, Hash, MetaDb, StateDbError};
+use super::{
+	to_meta_key, ChangeSet, CommitSet, DBValue, Error, Hash, MetaDb, MetaIssue, StateDbError,
+};
 use codec::{Decode, Encode};
 use log::trace;
-use std::collections::{hash_map::Entry, HashMap, VecDeque};
+use std::collections::{hash_map::Entry, HashMap, HashSet, VecDeque};
 
 const NON_CANONICAL_JOURNAL: &[u8] = b"noncanonical_journal";
 pub(crate) const LAST_CANONICAL: &[u8] = b"last_canonical";
@@ -81,6 +83,49 @@ fn to_journal_key(block: u64, index: u64) -> Vec<u8> {
 	to_meta_key(NON_CANONICAL_JOURNAL, &(block, index))
 }
 
+/// Check the journal records of the blocks above `last_canonicalized`.
+///
+/// Every record must decode and descend from the last canonicalized block. The records failing
+/// either check are deleted by `repair`.
+pub(crate) fn check_journals<BlockHash: Hash, Key: Hash, D: MetaDb>(
+	db: &D,
+	last_canonicalized: &(BlockHash, u64),
+	issues: &mut Vec<MetaIssue<BlockHash>>,
+	repair: &mut CommitSet<Key>,
+) -> Result<(), Error<D::Error>> {
+	let mut parents = HashSet::from([last_canonicalized.0.clone()]);
+	let mut block = last_canonicalized.1 + 1;
+	loop {
+		let mut found = false;
+		let mut level = HashSet::new();
+		for index in 0..MAX_BLOCKS_PER_LEVEL {
+			let journal_key = to_journal_key(block, index);
+			let Some(record) = db.get_meta(&journal_key).map_err(Error::Db)? else { continue };
+			found = true;
+			match JournalRecord::<BlockHash, Key>::decode(&mut record.as_slice()) {
+				Ok(record) if parents.contains(&record.parent_hash) => {
+					level.insert(record.hash);
+				},
+				Ok(record) => {
+					issues.push(MetaIssue::OrphanJournal { number: block, hash: record.hash });
+					repair.meta.deleted.push(journal_key);
+				},
+				Err(_) => {
+					issues.push(MetaIssue::Undecodable { key: journal_key.clone() });
+					repair.meta.deleted.push(journal_key);
+				},
+			}
+		}
+		// The overlay stops loading the journal at the first empty level.
+		if !found {
+			break
+		}
+		parents = level;
+		block += 1;
+	}
+	Ok(())
+}
+
 #[cfg_attr(test, derive(PartialEq, Debug))]
 struct BlockOverlay<BlockHash: Hash, Key: Hash> {
 	hash: BlockHash,
@@ -23,17 +23,25 @@
 //! If a node is re-inserted into the window it gets removed from
 //! the death list.
 //! The changes are journaled in the DB.
+//!
+//! When checkpoints are kept, a pruned block only removes the nodes that were inserted after the
+//! last pruned checkpoint, see `Checkpoints`.
 
 use crate::{
-	noncanonical::LAST_CANONICAL, to_meta_key, CommitSet, Error, Hash, MetaDb, StateDbError,
-	DEFAULT_MAX_BLOCK_CONSTRAINT, LOG_TARGET,
+	noncanonical::LAST_CANONICAL, to_meta_key, CommitSet, Error, Hash, MetaDb, MetaIssue,
+	StateDbError, DEFAULT_MAX_BLOCK_CONSTRAINT, LOG_TARGET,
 };
 use codec::{Decode, Encode};
 use log::trace;
-use std::collections::{HashMap, HashSet, VecDeque};
+use std::{
+	collections::{HashMap, HashSet, VecDeque},
+	num::NonZeroU32,
+};
 
 pub(crate) const LAST_PRUNED: &[u8] = b"last_pruned";
 const PRUNING_JOURNAL: &[u8] = b"pruning_journal";
+const PRUNING_STATS: &[u8] = b"pruning_stats";
+const RETAINED_JOURNALS: &[u8] = b"retained_journals";
 
 /// See module documentation.
 pub struct RefWindow<BlockHash: Hash, Key: Hash, D: MetaDb> {
@@ -42,6 +50,38 @@ pub struct RefWindow<BlockHash: Hash, Key: Hash, D: MetaDb> {
 	queue: DeathRowQueue<BlockHash, Key, D>,
 	/// Block number that is next to be pruned.
 	base: u64,
+	/// The statistics of each block in the pruning window.
+	stats: VecDeque<BlockStats>,
+	/// The total size of the state nodes inserted by the blocks in the pruning window.
+	size: u64,
+	/// The checkpoints to keep, if any.
+	checkpoints: Option<Checkpoints<Key>>,
+}
+
+/// Statistics about a block in the pruning window.
+#[derive(Encode, Decode, Default, Clone, Copy, Debug, PartialEq, Eq)]
+struct BlockStats {
+	/// The time the block was canonicalized at, in milliseconds since the UNIX epoch.
+	timestamp: u64,
+	/// The size of the state nodes inserted by the block.
+	size: u64,
+}
+
+/// Keeps the state of every checkpoint, i.e. every canonical block whose number is a multiple of
+/// the checkpoint interval.
+///
+/// The state of a checkpoint is made of all the nodes inserted before it and deleted after it. So
+/// when a block is pruned, only the nodes it deleted that were inserted after the last pruned
+/// checkpoint are removed from the database. The journals of the blocks pruned since the last
+/// checkpoint are kept until the next checkpoint is pruned, so that the inserted nodes can be
+/// tracked again after a restart.
+struct Checkpoints<Key: Hash> {
+	interval: u64,
+	/// The nodes inserted by the blocks pruned since the last checkpoint, with the number of
+	/// insertions not matched by a deletion yet.
+	inserted: HashMap<Key, u32>,
+	/// The number of the first pruned block whose journal is kept.
+	retained_from: Option<u64>,
 }
 
 /// `DeathRowQueue` used to keep track of blocks in the pruning window, there are two flavors:
@@ -55,6 +95,8 @@ enum DeathRowQueue<BlockHash: Hash, Key: Hash, D: MetaDb> {
 		death_rows: VecDeque<DeathRow<BlockHash, Key>>,
 		/// An index that maps each key from `death_rows` to block number.
 		death_index: HashMap<Key, u64>,
+		/// Whether the inserted keys are kept in the death rows.
+		keep_inserted: bool,
 	},
 	DbBacked {
 		// The backend database
@@ -67,16 +109,23 @@ enum DeathRowQueue<BlockHash: Hash, Key: Hash, D: MetaDb> {
 		cache_capacity: usize,
 		/// Last block number added to the window
 		last: Option<u64>,
+		/// Whether the inserted keys are kept in the death rows.
+		keep_inserted: bool,
 	},
 }
 
 impl<BlockHash: Hash, Key: Hash, D: MetaDb> DeathRowQueue<BlockHash, Key, D> {
 	/// Return a `DeathRowQueue` that all blocks are keep in memory
-	fn new_mem(db: &D, base: u64) -> Result<DeathRowQueue<BlockHash, Key, D>, Error<D::Error>> {
+	fn new_mem(
+		db: &D,
+		base: u64,
+		keep_inserted: bool,
+	) -> Result<DeathRowQueue<BlockHash, Key, D>, Error<D::Error>> {
 		let mut block = base;
 		let mut queue = DeathRowQueue::<BlockHash, Key, D>::Mem {
 			death_rows: VecDeque::new(),
 			death_index: HashMap::new(),
+			keep_inserted,
 		};
 		// read the journal
 		trace!(
@@ -113,6 +162,7 @@ impl<BlockHash: Hash, Key: Hash, D: MetaDb> DeathRowQueue<BlockHash, Key, D> {
 		base: u64,
 		last: Option<u64>,
 		window_size: u32,
+		keep_inserted: bool,
 	) -> Result<DeathRowQueue<BlockHash, Key, D>, Error<D::Error>> {
 		// limit the cache capacity from 1 to `DEFAULT_MAX_BLOCK_CONSTRAINT`
 		let cache_capacity = window_size.clamp(1, DEFAULT_MAX_BLOCK_CONSTRAINT) as usize;
@@ -122,8 +172,8 @@ impl<BlockHash: Hash, Key: Hash, D: MetaDb> DeathRowQueue<BlockHash, Key, D> {
 			"Reading pruning journal for the database-backed queue. Pending #{}",
 			base
 		);
-		DeathRowQueue::load_batch_from_db(&db, &mut cache, base, cache_capacity)?;
-		Ok(DeathRowQueue::DbBacked { db, cache, cache_capacity, last })
+		DeathRowQueue::load_batch_from_db(&db, &mut cache, base, cache_capacity, keep_inserted)?;
+		Ok(DeathRowQueue::DbBacked { db, cache, cache_capacity, last, keep_inserted })
 	}
 
 	/// import a new block to the back of the queue
@@ -131,20 +181,25 @@ impl<BlockHash: Hash, Key: Hash, D: MetaDb> DeathRowQueue<BlockHash, Key, D> {
 		let JournalRecord { hash, inserted, deleted } = journal_record;
 		trace!(target: LOG_TARGET, "Importing {}, base={}", num, base);
 		match self {
-			DeathRowQueue::DbBacked { cache, cache_capacity, last, .. } => {
+			DeathRowQueue::DbBacked { cache, cache_capacity, last, keep_inserted, .. } => {
 				// If the new block continues cached range and there is space, load it directly into
 				// cache.
 				if num == base + cache.len() as u64 && cache.len() < *cache_capacity {
 					trace!(target: LOG_TARGET, "Adding to DB backed cache {:?} (#{})", hash, num);
-					cache.push_back(DeathRow { hash, deleted: deleted.into_iter().collect() });
+					let inserted = if *keep_inserted { inserted } else { Vec::new() };
+					cache.push_back(DeathRow {
+						hash,
+						deleted: deleted.into_iter().collect(),
+						inserted,
+					});
 				}
 				*last = Some(num);
 			},
-			DeathRowQueue::Mem { death_rows, death_index } => {
+			DeathRowQueue::Mem { death_rows, death_index, keep_inserted } => {
 				// remove all re-inserted keys from death rows
-				for k in inserted {
-					if let Some(block) = death_index.remove(&k) {
-						death_rows[(block - base) as usize].deleted.remove(&k);
+				for k in inserted.iter() {
+					if let Some(block) = death_index.remove(k) {
+						death_rows[(block - base) as usize].deleted.remove(k);
 					}
 				}
 				// add new keys
@@ -152,7 +207,12 @@ impl<BlockHash: Hash, Key: Hash, D: MetaDb> DeathRowQueue<BlockHash, Key, D> {
 				for k in deleted.iter() {
 					death_index.insert(k.clone(), imported_block);
 				}
-				death_rows.push_back(DeathRow { hash, deleted: deleted.into_iter().collect() });
+				let inserted = if *keep_inserted { inserted } else { Vec::new() };
+				death_rows.push_back(DeathRow {
+					hash,
+					deleted: deleted.into_iter().collect(),
+					inserted,
+				});
 			},
 		}
 	}
@@ -164,13 +224,19 @@ impl<BlockHash: Hash, Key: Hash, D: MetaDb> DeathRowQueue<BlockHash, Key, D> {
 		base: u64,
 	) -> Result<Option<DeathRow<BlockHash, Key>>, Error<D::Error>> {
 		match self {
-			DeathRowQueue::DbBacked { db, cache, cache_capacity, .. } => {
+			DeathRowQueue::DbBacked { db, cache, cache_capacity, keep_inserted, .. } => {
 				if cache.is_empty() {
-					DeathRowQueue::load_batch_from_db(db, cache, base, *cache_capacity)?;
+					DeathRowQueue::load_batch_from_db(
+						db,
+						cache,
+						base,
+						*cache_capacity,
+						*keep_inserted,
+					)?;
 				}
 				Ok(cache.pop_front())
 			},
-			DeathRowQueue::Mem { death_rows, death_index } => match death_rows.pop_front() {
+			DeathRowQueue::Mem { death_rows, death_index, .. } => match death_rows.pop_front() {
 				Some(row) => {
 					for k in row.deleted.iter() {
 						death_index.remove(k);
@@ -189,11 +255,12 @@ impl<BlockHash: Hash, Key: Hash, D: MetaDb> DeathRowQueue<BlockHash, Key, D> {
 		cache: &mut VecDeque<DeathRow<BlockHash, Key>>,
 		base: u64,
 		cache_capacity: usize,
+		keep_inserted: bool,
 	) -> Result<(), Error<D::Error>> {
 		let start = base + cache.len() as u64;
 		let batch_size = cache_capacity;
 		for i in 0..batch_size as u64 {
-			match load_death_row_from_db::<BlockHash, Key, D>(db, start + i)? {
+			match load_death_row_from_db::<BlockHash, Key, D>(db, start + i, keep_inserted)? {
 				Some(row) => {
 					cache.push_back(row);
 				},
@@ -233,7 +300,7 @@ impl<BlockHash: Hash, Key: Hash, D: MetaDb> DeathRowQueue<BlockHash, Key, D> {
 	) -> Option<(&VecDeque<DeathRow<BlockHash, Key>>, &HashMap<Key, u64>)> {
 		match self {
 			DeathRowQueue::DbBacked { .. } => None,
-			DeathRowQueue::Mem { death_rows, death_index } => Some((death_rows, death_index)),
+			DeathRowQueue::Mem { death_rows, death_index, .. } => Some((death_rows, death_index)),
 		}
 	}
 
@@ -251,21 +318,131 @@ impl<BlockHash: Hash, Key: Hash, D: MetaDb> DeathRowQueue<BlockHash, Key, D> {
 fn load_death_row_from_db<BlockHash: Hash, Key: Hash, D: MetaDb>(
 	db: &D,
 	block: u64,
+	keep_inserted: bool,
 ) -> Result<Option<DeathRow<BlockHash, Key>>, Error<D::Error>> {
 	let journal_key = to_journal_key(block);
 	match db.get_meta(&journal_key).map_err(Error::Db)? {
 		Some(record) => {
-			let JournalRecord { hash, deleted, .. } = Decode::decode(&mut record.as_slice())?;
-			Ok(Some(DeathRow { hash, deleted: deleted.into_iter().collect() }))
+			let JournalRecord { hash, inserted, deleted } = Decode::decode(&mut record.as_slice())?;
+			let inserted = if keep_inserted { inserted } else { Vec::new() };
+			Ok(Some(DeathRow { hash, deleted: deleted.into_iter().collect(), inserted }))
 		},
 		None => Ok(None),
 	}
 }
 
+/// Load the statistics of the `len` blocks of the pruning window starting at `base`.
+fn load_stats<D: MetaDb>(
+	db: &D,
+	base: u64,
+	len: u64,
+) -> Result<VecDeque<BlockStats>, Error<D::Error>> {
+	(base..base + len)
+		.map(|block| match db.get_meta(&to_stats_key(block)).map_err(Error::Db)? {
+			Some(stats) => Ok(BlockStats::decode(&mut stats.as_slice())?),
+			// Blocks canonicalized by older versions have no statistics, they are considered to be
+			// as old as possible.
+			None => Ok(BlockStats::default()),
+		})
+		.collect()
+}
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 struct DeathRow<BlockHash: Hash, Key: Hash> {
 	hash: BlockHash,
 	deleted: HashSet<Key>,
+	/// The keys inserted by the block, only kept when checkpoints are kept.
+	inserted: Vec<Key>,
+}
+
+impl<Key: Hash> Checkpoints<Key> {
+	/// Restore the checkpoints tracking of a pruning window starting at `base`.
+	fn load<BlockHash: Hash, D: MetaDb>(
+		db: &D,
+		interval: NonZeroU32,
+		base: u64,
+	) -> Result<Checkpoints<Key>, Error<D::Error>> {
+		let retained_from =
+			match db.get_meta(&to_meta_key(RETAINED_JOURNALS, &())).map_err(Error::Db)? {
+				Some(buffer) => Some(u64::decode(&mut buffer.as_slice())?),
+				None => None,
+			};
+		let mut checkpoints = Checkpoints {
+			interval: interval.get() as u64,
+			inserted: HashMap::new(),
+			retained_from,
+		};
+		for block in retained_from.unwrap_or(base)..base {
+			if let Some(record) = db.get_meta(&to_journal_key(block)).map_err(Error::Db)? {
+				let JournalRecord::<BlockHash, Key> { inserted, deleted, .. } =
+					Decode::decode(&mut record.as_slice())?;
+				deleted.iter().for_each(|key| {
+					checkpoints.take(key);
+				});
+				checkpoints.note_inserted(inserted);
+			}
+		}
+		trace!(
+			target: LOG_TARGET,
+			"Tracking {} keys inserted since the last checkpoint",
+			checkpoints.inserted.len(),
+		);
+		Ok(checkpoints)
+	}
+
+	fn is_checkpoint(&self, number: u64) -> bool {
+		number % self.interval == 0
+	}
+
+	/// Returns `true` if the deleted node `key` was inserted after the last pruned checkpoint.
+	fn take(&mut self, key: &Key) -> bool {
+		match self.inserted.get_mut(key) {
+			Some(count) => {
+				*count -= 1;
+				if *count == 0 {
+					self.inserted.remove(key);
+				}
+				true
+			},
+			None => false,
+		}
+	}
+
+	fn note_inserted(&mut self, inserted: Vec<Key>) {
+		for key in inserted {
+			*self.inserted.entry(key).or_default() += 1;
+		}
+	}
+
+	/// Prune the block `number`, removing the deleted nodes that no checkpoint needs.
+	fn prune<BlockHash: Hash>(
+		&mut self,
+		number: u64,
+		row: DeathRow<BlockHash, Key>,
+		commit: &mut CommitSet<Key>,
+	) {
+		let deleted = row.deleted.into_iter().filter(|key| self.take(key)).collect::<Vec<_>>();
+		commit.data.deleted.extend(deleted);
+
+		let retained_from = if self.is_checkpoint(number) {
+			// Everything inserted so far is part of the state of the checkpoint.
+			let first = self.retained_from.unwrap_or(number);
+			commit.meta.deleted.extend((first..=number).map(to_journal_key));
+			self.inserted.clear();
+			number + 1
+		} else {
+			self.note_inserted(row.inserted);
+			match self.retained_from {
+				Some(_) => return,
+				None => number,
+			}
+		};
+		self.retained_from = Some(retained_from);
+		commit
+			.meta
+			.inserted
+			.push((to_meta_key(RETAINED_JOURNALS, &()), retained_from.encode()));
+	}
 }
 
 #[derive(Encode, Decode, Default)]
@@ -279,6 +456,48 @@ fn to_journal_key(block: u64) -> Vec<u8> {
 	to_meta_key(PRUNING_JOURNAL, &block)
 }
 
+fn to_stats_key(block: u64) -> Vec<u8> {
+	to_meta_key(PRUNING_STATS, &block)
+}
+
+/// Check the journal records of the canonical blocks in the pruning window, i.e. the blocks from
+/// the last pruned one up to `last_canonicalized`.
+///
+/// Every record must decode and belong to the block returned by `canonical_hash`. The records
+/// failing either check are replaced by `repair` with an empty record: the nodes they would have
+/// deleted are kept in the database, which only wastes space.
+pub(crate) fn check_journals<BlockHash: Hash, Key: Hash, D: MetaDb>(
+	db: &D,
+	last_canonicalized: u64,
+	canonical_hash: impl Fn(u64) -> Option<BlockHash>,
+	issues: &mut Vec<MetaIssue<BlockHash>>,
+	repair: &mut CommitSet<Key>,
+) -> Result<(), Error<D::Error>> {
+	let base = match db.get_meta(&to_meta_key(LAST_PRUNED, &())).map_err(Error::Db)? {
+		Some(buffer) => u64::decode(&mut buffer.as_slice())? + 1,
+		None => 0,
+	};
+	for number in base..=last_canonicalized {
+		// Blocks missing from the chain are reported by the caller.
+		let Some(hash) = canonical_hash(number) else { continue };
+		let journal_key = to_journal_key(number);
+		let record = db.get_meta(&journal_key).map_err(Error::Db)?;
+		match record.map(|record| JournalRecord::<BlockHash, Key>::decode(&mut record.as_slice())) {
+			Some(Ok(record)) if record.hash == hash => continue,
+			Some(Ok(record)) => issues.push(MetaIssue::MismatchedPruningJournal {
+				number,
+				expected: hash.clone(),
+				found: record.hash,
+			}),
+			Some(Err(_)) => issues.push(MetaIssue::Undecodable { key: journal_key.clone() }),
+			None => issues.push(MetaIssue::MissingPruningJournal { number }),
+		}
+		let record = JournalRecord::<BlockHash, Key> { hash, ..Default::default() };
+		repair.meta.inserted.push((journal_key, record.encode()));
+	}
+	Ok(())
+}
+
 /// The result return by `RefWindow::have_block`
 #[derive(Debug, PartialEq, Eq)]
 pub enum HaveBlock {
@@ -305,6 +524,7 @@ impl<BlockHash: Hash, Key: Hash, D: MetaDb> RefWindow<BlockHash, Key, D> {
 		db: D,
 		window_size: u32,
 		count_insertions: bool,
+		checkpoint_interval: Option<NonZeroU32>,
 	) -> Result<RefWindow<BlockHash, Key, D>, Error<D::Error>> {
 		// the block number of the first block in the queue or the next block number if the queue is
 		// empty
@@ -319,7 +539,12 @@ impl<BlockHash: Hash, Key: Hash, D: MetaDb> RefWindow<BlockHash, Key, D> {
 				None => None,
 			};
 
-		let queue = if count_insertions {
+		let checkpoints = checkpoint_interval
+			.map(|interval| Checkpoints::load::<BlockHash, D>(&db, interval, base))
+			.transpose()?;
+		let keep_inserted = checkpoints.is_some();
+
+		let (queue, stats) = if count_insertions {
 			// Highly scientific crafted number for deciding when to print the warning!
 			//
 			// Rocksdb doesn't support refcounting and requires that we load the entire pruning
@@ -332,7 +557,9 @@ impl<BlockHash: Hash, Key: Hash, D: MetaDb> RefWindow<BlockHash, Key, D> {
 				);
 			}
 
-			DeathRowQueue::new_mem(&db, base)?
+			let queue = DeathRowQueue::new_mem(&db, base, keep_inserted)?;
+			let stats = load_stats(&db, base, queue.len(base))?;
+			(queue, stats)
 		} else {
 			let last = match last_canonicalized_number {
 				Some(last_canonicalized_number) => {
@@ -344,10 +571,12 @@ impl<BlockHash: Hash, Key: Hash, D: MetaDb> RefWindow<BlockHash, Key, D> {
 				// ever been committed to the db, thus set `unload` to zero
 				None => None,
 			};
-			DeathRowQueue::new_db_backed(db, base, last, window_size)?
+			let stats = load_stats(&db, base, last.map_or(0, |last| last + 1 - base))?;
+			(DeathRowQueue::new_db_backed(db, base, last, window_size, keep_inserted)?, stats)
 		};
+		let size = stats.iter().map(|stats| stats.size).sum();
 
-		Ok(RefWindow { queue, base })
+		Ok(RefWindow { queue, base, stats, size, checkpoints })
 	}
 
 	pub fn window_size(&self) -> u64 {
@@ -357,9 +586,15 @@ impl<BlockHash: Hash, Key: Hash, D: MetaDb> RefWindow<BlockHash, Key, D> {
 	/// Get the hash of the next pruning block
 	pub fn next_hash(&mut self) -> Result<Option<BlockHash>, Error<D::Error>> {
 		let res = match &mut self.queue {
-			DeathRowQueue::DbBacked { db, cache, cache_capacity, .. } => {
+			DeathRowQueue::DbBacked { db, cache, cache_capacity, keep_inserted, .. } => {
 				if cache.is_empty() {
-					DeathRowQueue::load_batch_from_db(db, cache, self.base, *cache_capacity)?;
+					DeathRowQueue::load_batch_from_db(
+						db,
+						cache,
+						self.base,
+						*cache_capacity,
+						*keep_inserted,
+					)?;
 				}
 				cache.front().map(|r| r.hash.clone())
 			},
@@ -372,8 +607,27 @@ impl<BlockHash: Hash, Key: Hash, D: MetaDb> RefWindow<BlockHash, Key, D> {
 		self.window_size() == 0
 	}
 
+	/// Get the time the next pruning block was canonicalized at, in milliseconds since the UNIX
+	/// epoch.
+	pub fn next_timestamp(&self) -> Option<u64> {
+		self.stats.front().map(|stats| stats.timestamp)
+	}
+
+	/// Get the total size of the state nodes inserted by the blocks in the pruning window.
+	pub fn state_size(&self) -> u64 {
+		self.size
+	}
+
 	// Check if a block is in the pruning window and not be pruned yet
 	pub fn have_block(&self, hash: &BlockHash, number: u64) -> HaveBlock {
+		// the state of pruned checkpoints is kept, but there is no record of their hash
+		if number < self.base &&
+			self.checkpoints
+				.as_ref()
+				.map_or(false, |checkpoints| checkpoints.is_checkpoint(number))
+		{
+			return HaveBlock::Maybe
+		}
 		// if the queue is empty or the block number exceed the pruning window, we definitely
 		// do not have this block
 		if self.is_empty() || number < self.base || number >= self.base + self.window_size() {
@@ -387,9 +641,18 @@ impl<BlockHash: Hash, Key: Hash, D: MetaDb> RefWindow<BlockHash, Key, D> {
 		if let Some(pruned) = self.queue.pop_front(self.base)? {
 			trace!(target: LOG_TARGET, "Pruning {:?} ({} deleted)", pruned.hash, pruned.deleted.len());
 			let index = self.base;
-			commit.data.deleted.extend(pruned.deleted.into_iter());
+			match &mut self.checkpoints {
+				Some(checkpoints) => checkpoints.prune(index, pruned, commit),
+				None => {
+					commit.data.deleted.extend(pruned.deleted.into_iter());
+					commit.meta.deleted.push(to_journal_key(index));
+				},
+			}
+			if let Some(stats) = self.stats.pop_front() {
+				self.size = self.size.saturating_sub(stats.size);
+			}
 			commit.meta.inserted.push((to_meta_key(LAST_PRUNED, &()), index.encode()));
-			commit.meta.deleted.push(to_journal_key(self.base));
+			commit.meta.deleted.push(to_stats_key(index));
 			self.base += 1;
 			Ok(())
 		} else {
@@ -398,11 +661,15 @@ impl<BlockHash: Hash, Key: Hash, D: MetaDb> RefWindow<BlockHash, Key, D> {
 		}
 	}
 
-	/// Add a change set to the window. Creates a journal record and pushes it to `commit`
+	/// Add a change set to the window. Creates a journal record and pushes it to `commit`.
+	///
+	/// `timestamp` is the time the block is canonicalized at, in milliseconds since the UNIX
+	/// epoch.
 	pub fn note_canonical(
 		&mut self,
 		hash: &BlockHash,
 		number: u64,
+		timestamp: u64,
 		commit: &mut CommitSet<Key>,
 	) -> Result<(), Error<D::Error>> {
 		if self.base == 0 && self.is_empty() && number > 0 {
@@ -424,15 +691,23 @@ impl<BlockHash: Hash, Key: Hash, D: MetaDb> RefWindow<BlockHash, Key, D> {
 			commit.data.inserted.len(),
 			commit.data.deleted.len(),
 		);
-		let inserted = if matches!(self.queue, DeathRowQueue::Mem { .. }) {
-			commit.data.inserted.iter().map(|(k, _)| k.clone()).collect()
-		} else {
-			Default::default()
+		let inserted =
+			if matches!(self.queue, DeathRowQueue::Mem { .. }) || self.checkpoints.is_some() {
+				commit.data.inserted.iter().map(|(k, _)| k.clone()).collect()
+			} else {
+				Default::default()
+			};
+		let stats = BlockStats {
+			timestamp,
+			size: commit.data.inserted.iter().map(|(_, value)| value.len() as u64).sum(),
 		};
 		let deleted = std::mem::take(&mut commit.data.deleted);
 		let journal_record = JournalRecord { hash: hash.clone(), inserted, deleted };
 		commit.meta.inserted.push((to_journal_key(number), journal_record.encode()));
+		commit.meta.inserted.push((to_stats_key(number), stats.encode()));
 		self.queue.import(self.base, number, journal_record);
+		self.size += stats.size;
+		self.stats.push_back(stats);
 		Ok(())
 	}
 }
@@ -443,24 +718,27 @@ mod tests {
 	use crate::{
 		noncanonical::LAST_CANONICAL,
 		test::{make_commit, make_db, TestDb},
-		to_meta_key, CommitSet, Error, Hash, StateDbError, DEFAULT_MAX_BLOCK_CONSTRAINT,
+		to_meta_key, CommitSet, Error, Hash, MetaDb, StateDbError, DEFAULT_MAX_BLOCK_CONSTRAINT,
 	};
 	use codec::Encode;
 	use sp_core::H256;
+	use std::num::NonZeroU32;
 
 	fn check_journal(pruning: &RefWindow<H256, H256, TestDb>, db: &TestDb) {
 		let count_insertions = matches!(pruning.queue, DeathRowQueue::Mem { .. });
 		let restored: RefWindow<H256, H256, TestDb> =
-			RefWindow::new(db.clone(), DEFAULT_MAX_BLOCK_CONSTRAINT, count_insertions).unwrap();
+			RefWindow::new(db.clone(), DEFAULT_MAX_BLOCK_CONSTRAINT, count_insertions, None)
+				.unwrap();
 		assert_eq!(pruning.base, restored.base);
 		assert_eq!(pruning.queue.get_mem_queue_state(), restored.queue.get_mem_queue_state());
+		assert_eq!(pruning.stats, restored.stats);
 	}
 
 	#[test]
 	fn created_from_empty_db() {
 		let db = make_db(&[]);
 		let pruning: RefWindow<H256, H256, TestDb> =
-			RefWindow::new(db, DEFAULT_MAX_BLOCK_CONSTRAINT, true).unwrap();
+			RefWindow::new(db, DEFAULT_MAX_BLOCK_CONSTRAINT, true, None).unwrap();
 		assert_eq!(pruning.base, 0);
 		let (death_rows, death_index) = pruning.queue.get_mem_queue_state().unwrap();
 		assert!(death_rows.is_empty());
@@ -471,7 +749,7 @@ mod tests {
 	fn prune_empty() {
 		let db = make_db(&[]);
 		let mut pruning: RefWindow<H256, H256, TestDb> =
-			RefWindow::new(db, DEFAULT_MAX_BLOCK_CONSTRAINT, true).unwrap();
+			RefWindow::new(db, DEFAULT_MAX_BLOCK_CONSTRAINT, true, None).unwrap();
 		let mut commit = CommitSet::default();
 		assert_eq!(
 			Err(Error::StateDb(StateDbError::BlockUnavailable)),
@@ -487,10 +765,10 @@ mod tests {
 	fn prune_one() {
 		let mut db = make_db(&[1, 2, 3]);
 		let mut pruning: RefWindow<H256, H256, TestDb> =
-			RefWindow::new(db.clone(), DEFAULT_MAX_BLOCK_CONSTRAINT, true).unwrap();
+			RefWindow::new(db.clone(), DEFAULT_MAX_BLOCK_CONSTRAINT, true, None).unwrap();
 		let mut commit = make_commit(&[4, 5], &[1, 3]);
 		let hash = H256::random();
-		pruning.note_canonical(&hash, 0, &mut commit).unwrap();
+		pruning.note_canonical(&hash, 0, 0, &mut commit).unwrap();
 		db.commit(&commit);
 		assert_eq!(pruning.have_block(&hash, 0), HaveBlock::Yes);
 		assert_eq!(pruning.have_block(&hash, 0), HaveBlock::Yes);
@@ -517,12 +795,12 @@ mod tests {
 	fn prune_two() {
 		let mut db = make_db(&[1, 2, 3]);
 		let mut pruning: RefWindow<H256, H256, TestDb> =
-			RefWindow::new(db.clone(), DEFAULT_MAX_BLOCK_CONSTRAINT, true).unwrap();
+			RefWindow::new(db.clone(), DEFAULT_MAX_BLOCK_CONSTRAINT, true, None).unwrap();
 		let mut commit = make_commit(&[4], &[1]);
-		pruning.note_canonical(&H256::random(), 0, &mut commit).unwrap();
+		pruning.note_canonical(&H256::random(), 0, 0, &mut commit).unwrap();
 		db.commit(&commit);
 		let mut commit = make_commit(&[5], &[2]);
-		pruning.note_canonical(&H256::random(), 1, &mut commit).unwrap();
+		pruning.note_canonical(&H256::random(), 1, 0, &mut commit).unwrap();
 		db.commit(&commit);
 		assert!(db.data_eq(&make_db(&[1, 2, 3, 4, 5])));
 
@@ -543,12 +821,12 @@ mod tests {
 	fn prune_two_pending() {
 		let mut db = make_db(&[1, 2, 3]);
 		let mut pruning: RefWindow<H256, H256, TestDb> =
-			RefWindow::new(db.clone(), DEFAULT_MAX_BLOCK_CONSTRAINT, true).unwrap();
+			RefWindow::new(db.clone(), DEFAULT_MAX_BLOCK_CONSTRAINT, true, None).unwrap();
 		let mut commit = make_commit(&[4], &[1]);
-		pruning.note_canonical(&H256::random(), 0, &mut commit).unwrap();
+		pruning.note_canonical(&H256::random(), 0, 0, &mut commit).unwrap();
 		db.commit(&commit);
 		let mut commit = make_commit(&[5], &[2]);
-		pruning.note_canonical(&H256::random(), 1, &mut commit).unwrap();
+		pruning.note_canonical(&H256::random(), 1, 0, &mut commit).unwrap();
 		db.commit(&commit);
 		assert!(db.data_eq(&make_db(&[1, 2, 3, 4, 5])));
 		let mut commit = CommitSet::default();
@@ -566,15 +844,15 @@ mod tests {
 	fn reinserted_survives() {
 		let mut db = make_db(&[1, 2, 3]);
 		let mut pruning: RefWindow<H256, H256, TestDb> =
-			RefWindow::new(db.clone(), DEFAULT_MAX_BLOCK_CONSTRAINT, true).unwrap();
+			RefWindow::new(db.clone(), DEFAULT_MAX_BLOCK_CONSTRAINT, true, None).unwrap();
 		let mut commit = make_commit(&[], &[2]);
-		pruning.note_canonical(&H256::random(), 0, &mut commit).unwrap();
+		pruning.note_canonical(&H256::random(), 0, 0, &mut commit).unwrap();
 		db.commit(&commit);
 		let mut commit = make_commit(&[2], &[]);
-		pruning.note_canonical(&H256::random(), 1, &mut commit).unwrap();
+		pruning.note_canonical(&H256::random(), 1, 0, &mut commit).unwrap();
 		db.commit(&commit);
 		let mut commit = make_commit(&[], &[2]);
-		pruning.note_canonical(&H256::random(), 2, &mut commit).unwrap();
+		pruning.note_canonical(&H256::random(), 2, 0, &mut commit).unwrap();
 		db.commit(&commit);
 		assert!(db.data_eq(&make_db(&[1, 2, 3])));
 
@@ -598,15 +876,15 @@ mod tests {
 	fn reinserted_survive_pending() {
 		let mut db = make_db(&[1, 2, 3]);
 		let mut pruning: RefWindow<H256, H256, TestDb> =
-			RefWindow::new(db.clone(), DEFAULT_MAX_BLOCK_CONSTRAINT, true).unwrap();
+			RefWindow::new(db.clone(), DEFAULT_MAX_BLOCK_CONSTRAINT, true, None).unwrap();
 		let mut commit = make_commit(&[], &[2]);
-		pruning.note_canonical(&H256::random(), 0, &mut commit).unwrap();
+		pruning.note_canonical(&H256::random(), 0, 0, &mut commit).unwrap();
 		db.commit(&commit);
 		let mut commit = make_commit(&[2], &[]);
-		pruning.note_canonical(&H256::random(), 1, &mut commit).unwrap();
+		pruning.note_canonical(&H256::random(), 1, 0, &mut commit).unwrap();
 		db.commit(&commit);
 		let mut commit = make_commit(&[], &[2]);
-		pruning.note_canonical(&H256::random(), 2, &mut commit).unwrap();
+		pruning.note_canonical(&H256::random(), 2, 0, &mut commit).unwrap();
 		db.commit(&commit);
 		assert!(db.data_eq(&make_db(&[1, 2, 3])));
 
@@ -628,15 +906,15 @@ mod tests {
 	fn reinserted_ignores() {
 		let mut db = make_db(&[1, 2, 3]);
 		let mut pruning: RefWindow<H256, H256, TestDb> =
-			RefWindow::new(db.clone(), DEFAULT_MAX_BLOCK_CONSTRAINT, false).unwrap();
+			RefWindow::new(db.clone(), DEFAULT_MAX_BLOCK_CONSTRAINT, false, None).unwrap();
 		let mut commit = make_commit(&[], &[2]);
-		pruning.note_canonical(&H256::random(), 0, &mut commit).unwrap();
+		pruning.note_canonical(&H256::random(), 0, 0, &mut commit).unwrap();
 		db.commit(&commit);
 		let mut commit = make_commit(&[2], &[]);
-		pruning.note_canonical(&H256::random(), 1, &mut commit).unwrap();
+		pruning.note_canonical(&H256::random(), 1, 0, &mut commit).unwrap();
 		db.commit(&commit);
 		let mut commit = make_commit(&[], &[2]);
-		pruning.note_canonical(&H256::random(), 2, &mut commit).unwrap();
+		pruning.note_canonical(&H256::random(), 2, 0, &mut commit).unwrap();
 		db.commit(&commit);
 		assert!(db.data_eq(&make_db(&[1, 2, 3])));
 
@@ -666,7 +944,7 @@ mod tests {
 
 		fn load_pruning_from_db(db: TestDb) -> (usize, u64) {
 			let pruning: RefWindow<u64, H256, TestDb> =
-				RefWindow::new(db, DEFAULT_MAX_BLOCK_CONSTRAINT, false).unwrap();
+				RefWindow::new(db, DEFAULT_MAX_BLOCK_CONSTRAINT, false, None).unwrap();
 			let (cache, _) = pruning.queue.get_db_backed_queue_state().unwrap();
 			(cache.len(), pruning.base)
 		}
@@ -720,7 +998,7 @@ mod tests {
 	fn db_backed_queue() {
 		let mut db = make_db(&[]);
 		let mut pruning: RefWindow<u64, H256, TestDb> =
-			RefWindow::new(db.clone(), DEFAULT_MAX_BLOCK_CONSTRAINT, false).unwrap();
+			RefWindow::new(db.clone(), DEFAULT_MAX_BLOCK_CONSTRAINT, false, None).unwrap();
 		let cache_capacity = DEFAULT_MAX_BLOCK_CONSTRAINT as usize;
 
 		// start as an empty queue
@@ -732,7 +1010,7 @@ mod tests {
 		// queue size and content should match
 		for i in 0..(cache_capacity + 10) {
 			let mut commit = make_commit(&[], &[]);
-			pruning.note_canonical(&(i as u64), i as u64, &mut commit).unwrap();
+			pruning.note_canonical(&(i as u64), i as u64, 0, &mut commit).unwrap();
 			push_last_canonicalized(i as u64, &mut commit);
 			db.commit(&commit);
 			// blocks will fill the cache first
@@ -756,7 +1034,12 @@ mod tests {
 		// won't keep the new block in memory
 		let mut commit = CommitSet::default();
 		pruning
-			.note_canonical(&(cache_capacity as u64 + 10), cache_capacity as u64 + 10, &mut commit)
+			.note_canonical(
+				&(cache_capacity as u64 + 10),
+				cache_capacity as u64 + 10,
+				0,
+				&mut commit,
+			)
 			.unwrap();
 		assert_eq!(pruning.window_size(), cache_capacity as u64 + 11);
 		let (cache, _) = pruning.queue.get_db_backed_queue_state().unwrap();
@@ -764,7 +1047,7 @@ mod tests {
 
 		// revert the last add that no apply yet
 		// NOTE: do not commit the previous `CommitSet` to db
-		pruning = RefWindow::new(db.clone(), DEFAULT_MAX_BLOCK_CONSTRAINT, false).unwrap();
+		pruning = RefWindow::new(db.clone(), DEFAULT_MAX_BLOCK_CONSTRAINT, false, None).unwrap();
 		let cache_capacity = DEFAULT_MAX_BLOCK_CONSTRAINT as usize;
 		assert_eq!(pruning.window_size(), cache_capacity as u64 + 10);
 		let (cache, _) = pruning.queue.get_db_backed_queue_state().unwrap();
@@ -785,7 +1068,7 @@ mod tests {
 		// load a new queue from db
 		// `cache` is full again but the content of the queue should be the same
 		let pruning: RefWindow<u64, H256, TestDb> =
-			RefWindow::new(db, DEFAULT_MAX_BLOCK_CONSTRAINT, false).unwrap();
+			RefWindow::new(db, DEFAULT_MAX_BLOCK_CONSTRAINT, false, None).unwrap();
 		assert_eq!(pruning.window_size(), cache_capacity as u64 + 9);
 		let (cache, _) = pruning.queue.get_db_backed_queue_state().unwrap();
 		assert_eq!(cache.len(), cache_capacity);
@@ -798,13 +1081,13 @@ mod tests {
 	fn load_block_from_db() {
 		let mut db = make_db(&[]);
 		let mut pruning: RefWindow<u64, H256, TestDb> =
-			RefWindow::new(db.clone(), DEFAULT_MAX_BLOCK_CONSTRAINT, false).unwrap();
+			RefWindow::new(db.clone(), DEFAULT_MAX_BLOCK_CONSTRAINT, false, None).unwrap();
 		let cache_capacity = DEFAULT_MAX_BLOCK_CONSTRAINT as usize;
 
 		// import blocks
 		for i in 0..(cache_capacity as u64 * 2 + 10) {
 			let mut commit = make_commit(&[], &[]);
-			pruning.note_canonical(&i, i, &mut commit).unwrap();
+			pruning.note_canonical(&i, i, 0, &mut commit).unwrap();
 			push_last_canonicalized(i as u64, &mut commit);
 			db.commit(&commit);
 		}
@@ -835,7 +1118,7 @@ mod tests {
 		// load a new queue from db
 		// `cache` should be the same
 		let pruning: RefWindow<u64, H256, TestDb> =
-			RefWindow::new(db, DEFAULT_MAX_BLOCK_CONSTRAINT, false).unwrap();
+			RefWindow::new(db, DEFAULT_MAX_BLOCK_CONSTRAINT, false, None).unwrap();
 		assert_eq!(pruning.window_size(), 10);
 		let (cache, _) = pruning.queue.get_db_backed_queue_state().unwrap();
 		assert_eq!(cache.len(), 10);
@@ -848,20 +1131,20 @@ mod tests {
 	fn get_block_from_queue() {
 		let mut db = make_db(&[]);
 		let mut pruning: RefWindow<u64, H256, TestDb> =
-			RefWindow::new(db.clone(), DEFAULT_MAX_BLOCK_CONSTRAINT, false).unwrap();
+			RefWindow::new(db.clone(), DEFAULT_MAX_BLOCK_CONSTRAINT, false, None).unwrap();
 		let cache_capacity = DEFAULT_MAX_BLOCK_CONSTRAINT as u64;
 
 		// import blocks and commit to db
 		let mut commit = make_commit(&[], &[]);
 		for i in 0..(cache_capacity + 10) {
-			pruning.note_canonical(&i, i, &mut commit).unwrap();
+			pruning.note_canonical(&i, i, 0, &mut commit).unwrap();
 		}
 		db.commit(&commit);
 
 		// import a block but not commit to db yet
 		let mut pending_commit = make_commit(&[], &[]);
 		let index = cache_capacity + 10;
-		pruning.note_canonical(&index, index, &mut pending_commit).unwrap();
+		pruning.note_canonical(&index, index, 0, &mut pending_commit).unwrap();
 
 		let mut commit = make_commit(&[], &[]);
 		// prune blocks that had committed to db
@@ -890,21 +1173,114 @@ mod tests {
 		for count_insertions in [true, false] {
 			let mut db = make_db(&[]);
 			let mut pruning: RefWindow<u64, H256, TestDb> =
-				RefWindow::new(db.clone(), DEFAULT_MAX_BLOCK_CONSTRAINT, count_insertions).unwrap();
+				RefWindow::new(db.clone(), DEFAULT_MAX_BLOCK_CONSTRAINT, count_insertions, None)
+					.unwrap();
 			let block = 10000;
 
 			// import blocks
 			let mut commit = make_commit(&[], &[]);
-			pruning.note_canonical(&block, block, &mut commit).unwrap();
+			pruning.note_canonical(&block, block, 0, &mut commit).unwrap();
 			push_last_canonicalized(block, &mut commit);
 			db.commit(&commit);
 
 			// load a new queue from db
 			// `cache` should be the same
 			let pruning: RefWindow<u64, H256, TestDb> =
-				RefWindow::new(db, DEFAULT_MAX_BLOCK_CONSTRAINT, count_insertions).unwrap();
+				RefWindow::new(db, DEFAULT_MAX_BLOCK_CONSTRAINT, count_insertions, None).unwrap();
 
 			assert_eq!(HaveBlock::Yes, pruning.have_block(&block, block));
 		}
 	}
+
+	#[test]
+	fn stats_are_tracked() {
+		for count_insertions in [true, false] {
+			let mut db = make_db(&[]);
+			let mut pruning: RefWindow<u64, H256, TestDb> =
+				RefWindow::new(db.clone(), DEFAULT_MAX_BLOCK_CONSTRAINT, count_insertions, None)
+					.unwrap();
+			assert_eq!(pruning.next_timestamp(), None);
+
+			// every inserted node is 32 bytes long
+			for (block, inserted) in [(0, &[1, 2][..]), (1, &[3]), (2, &[4, 5, 6])] {
+				let mut commit = make_commit(inserted, &[]);
+				pruning.note_canonical(&block, block, 1000 + block, &mut commit).unwrap();
+				push_last_canonicalized(block, &mut commit);
+				db.commit(&commit);
+			}
+			assert_eq!(pruning.next_timestamp(), Some(1000));
+			assert_eq!(pruning.state_size(), 6 * 32);
+
+			let mut commit = CommitSet::default();
+			pruning.prune_one(&mut commit).unwrap();
+			db.commit(&commit);
+			assert_eq!(pruning.next_timestamp(), Some(1001));
+			assert_eq!(pruning.state_size(), 4 * 32);
+
+			let restored: RefWindow<u64, H256, TestDb> =
+				RefWindow::new(db, DEFAULT_MAX_BLOCK_CONSTRAINT, count_insertions, None).unwrap();
+			assert_eq!(restored.next_timestamp(), Some(1001));
+			assert_eq!(restored.state_size(), 4 * 32);
+		}
+	}
+
+	#[test]
+	fn checkpoints_are_kept() {
+		let interval = NonZeroU32::new(2);
+		for count_insertions in [true, false] {
+			let mut db = make_db(&[]);
+			let mut pruning: RefWindow<u64, H256, TestDb> = RefWindow::new(
+				db.clone(),
+				DEFAULT_MAX_BLOCK_CONSTRAINT,
+				count_insertions,
+				interval,
+			)
+			.unwrap();
+			// the states of the checkpoints are {1, 2} at #0 and {2, 4} at #2
+			let changes = [
+				(&[1, 2][..], &[][..]),
+				(&[3], &[1]),
+				(&[4], &[3]),
+				(&[5], &[2, 4]),
+				(&[6], &[5]),
+				(&[], &[]),
+			];
+			for (block, (inserted, deleted)) in changes.into_iter().enumerate() {
+				let block = block as u64;
+				let mut commit = make_commit(inserted, deleted);
+				pruning.note_canonical(&block, block, 0, &mut commit).unwrap();
+				push_last_canonicalized(block, &mut commit);
+				db.commit(&commit);
+			}
+
+			for _ in 0..4 {
+				let mut commit = CommitSet::default();
+				pruning.prune_one(&mut commit).unwrap();
+				db.commit(&commit);
+			}
+			assert!(db.data_eq(&make_db(&[1, 2, 4, 5, 6])));
+			// the journal of #3 is needed to know that #5 can be removed after a restart
+			assert!(db.get_meta(&to_journal_key(2)).unwrap().is_none());
+			assert!(db.get_meta(&to_journal_key(3)).unwrap().is_some());
+
+			let mut pruning: RefWindow<u64, H256, TestDb> = RefWindow::new(
+				db.clone(),
+				DEFAULT_MAX_BLOCK_CONSTRAINT,
+				count_insertions,
+				interval,
+			)
+			.unwrap();
+			let mut commit = CommitSet::default();
+			pruning.prune_one(&mut commit).unwrap();
+			db.commit(&commit);
+			assert!(db.data_eq(&make_db(&[1, 2, 4, 6])));
+			for block in 0..5 {
+				assert!(db.get_meta(&to_journal_key(block)).unwrap().is_none());
+			}
+
+			assert_eq!(pruning.have_block(&1, 1), HaveBlock::No);
+			assert_eq!(pruning.have_block(&2, 2), HaveBlock::Maybe);
+			assert_eq!(pruning.have_block(&5, 5), HaveBlock::Yes);
+		}
+	}
 }
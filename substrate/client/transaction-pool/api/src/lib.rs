@@ -189,6 +189,59 @@ impl<Hash, BlockHash> TransactionStatus<Hash, BlockHash> {
 	}
 }
 
+/// Represents a transaction that was removed from the transaction pool, including the reason of its
+/// removal.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DroppedTransaction<Hash> {
+	/// Hash of the dropped extrinsic.
+	pub tx_hash: Hash,
+	/// Reason of the transaction being dropped.
+	pub reason: DroppedReason<Hash>,
+}
+
+impl<Hash> DroppedTransaction<Hash> {
+	/// Creates a new instance with reason set to `DroppedReason::Usurped(by)`.
+	pub fn new_usurped(tx_hash: Hash, by: Hash) -> Self {
+		Self { reason: DroppedReason::Usurped(by), tx_hash }
+	}
+
+	/// Creates a new instance with reason set to `DroppedReason::LimitsEnforced`.
+	pub fn new_enforced_by_limts(tx_hash: Hash) -> Self {
+		Self { reason: DroppedReason::LimitsEnforced, tx_hash }
+	}
+
+	/// Creates a new instance with reason set to `DroppedReason::Invalid`.
+	pub fn new_invalid(tx_hash: Hash) -> Self {
+		Self { reason: DroppedReason::Invalid, tx_hash }
+	}
+}
+
+/// Provides reason of why transactions was dropped.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum DroppedReason<Hash> {
+	/// Transaction was replaced by other transaction (e.g. because of higher priority).
+	Usurped(Hash),
+	/// Transaction was dropped because of internal pool limits being enforced.
+	LimitsEnforced,
+	/// Transaction was dropped because of being invalid.
+	Invalid,
+}
+
+/// A transaction recently removed from the transaction pool, see
+/// [`TransactionPool::recently_dropped`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecentlyDroppedTransaction<Hash> {
+	/// Hash of the dropped extrinsic.
+	pub tx_hash: Hash,
+	/// Reason of the transaction being dropped.
+	pub reason: DroppedReason<Hash>,
+	/// Details of the removal, e.g. the view dropping the transaction or its validity error.
+	pub detail: String,
+}
+
 /// The stream of transaction events.
 pub type TransactionStatusStream<Hash, BlockHash> =
 	dyn Stream<Item = TransactionStatus<Hash, BlockHash>> + Send;
@@ -280,6 +333,35 @@ pub trait TransactionPool: Send + Sync {
 		xt: TransactionFor<Self>,
 	) -> Result<Pin<Box<TransactionStatusStreamFor<Self>>>, Self::Error>;
 
+	/// Asynchronously imports one unverified transaction to the local lane of the pool.
+	///
+	/// The transaction is validated with the given `source`, but it is treated like the
+	/// transactions submitted by the node itself, e.g. when the limits of the pool are enforced.
+	/// Intended for the transactions submitted over RPC by the operator of the node.
+	///
+	/// The default implementation has no local lane and falls back to [`Self::submit_one`].
+	async fn submit_one_to_local_lane(
+		&self,
+		at: <Self::Block as BlockT>::Hash,
+		source: TransactionSource,
+		xt: TransactionFor<Self>,
+	) -> Result<TxHash<Self>, Self::Error> {
+		self.submit_one(at, source, xt).await
+	}
+
+	/// Asynchronously imports a single transaction to the local lane of the pool and starts to
+	/// watch its progress, see [`Self::submit_one_to_local_lane`].
+	///
+	/// The default implementation has no local lane and falls back to [`Self::submit_and_watch`].
+	async fn submit_and_watch_to_local_lane(
+		&self,
+		at: <Self::Block as BlockT>::Hash,
+		source: TransactionSource,
+		xt: TransactionFor<Self>,
+	) -> Result<Pin<Box<TransactionStatusStreamFor<Self>>>, Self::Error> {
+		self.submit_and_watch(at, source, xt).await
+	}
+
 	// *** Block production / Networking
 	/// Get an iterator for ready transactions ordered by priority.
 	///
@@ -335,6 +417,14 @@ pub trait TransactionPool: Send + Sync {
 	/// Returns transaction hash
 	fn hash_of(&self, xt: &TransactionFor<Self>) -> TxHash<Self>;
 
+	/// Returns the ready transactions which shall be propagated again to the peers, even to the
+	/// ones already knowing them.
+	///
+	/// The default implementation never requests a rebroadcast.
+	fn transactions_to_rebroadcast(&self) -> Vec<TxHash<Self>> {
+		Default::default()
+	}
+
 	/// Return specific ready transaction by hash, if there is one.
 	fn ready_transaction(&self, hash: &TxHash<Self>) -> Option<Arc<Self::InPoolTransaction>>;
 
@@ -347,6 +437,21 @@ pub trait TransactionPool: Send + Sync {
 		at: <Self::Block as BlockT>::Hash,
 		timeout: std::time::Duration,
 	) -> Box<dyn ReadyTransactions<Item = Arc<Self::InPoolTransaction>> + Send>;
+
+	// *** RPC introspection
+	/// Returns the hashes of the blocks whose views contain the transactions of the pool.
+	///
+	/// The default implementation returns an empty map.
+	fn transaction_views(&self) -> HashMap<TxHash<Self>, Vec<BlockHash<Self>>> {
+		Default::default()
+	}
+
+	/// Returns the transactions recently dropped from the pool, oldest first.
+	///
+	/// The default implementation does not keep track of the dropped transactions.
+	fn recently_dropped(&self) -> Vec<RecentlyDroppedTransaction<TxHash<Self>>> {
+		Default::default()
+	}
 }
 
 /// An iterator of ready transactions.
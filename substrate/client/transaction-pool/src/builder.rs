@@ -19,14 +19,22 @@
 //! Utility for building substrate transaction pool trait object.
 
 use crate::{
-	common::api::FullChainApi,
-	fork_aware_txpool::ForkAwareTxPool as ForkAwareFullPool,
-	graph::{base_pool::Transaction, ChainApi, ExtrinsicFor, ExtrinsicHash, IsValidator, Options},
+	common::{
+		api::FullChainApi,
+		journal::{Journal, JournalOptions},
+	},
+	fork_aware_txpool::{ForkAwareTxPool as ForkAwareFullPool, TxMemPoolPolicy},
+	graph::{
+		base_pool::Transaction, ChainApi, ExtrinsicFor, ExtrinsicHash, IsValidator,
+		LocalLaneOptions, Options,
+	},
 	single_state_txpool::BasicPool as SingleStateFullPool,
 	TransactionPoolWrapper, LOG_TARGET,
 };
 use prometheus_endpoint::Registry as PrometheusRegistry;
-use sc_transaction_pool_api::{LocalTransactionPool, MaintainedTransactionPool};
+use sc_transaction_pool_api::{
+	LocalTransactionPool, MaintainedTransactionPool, TransactionPriority,
+};
 use sp_core::traits::SpawnEssentialNamed;
 use sp_runtime::traits::Block as BlockT;
 use std::{marker::PhantomData, sync::Arc, time::Duration};
@@ -45,11 +53,18 @@ pub enum TransactionPoolType {
 pub struct TransactionPoolOptions {
 	txpool_type: TransactionPoolType,
 	options: Options,
+	journal: Option<JournalOptions>,
+	rpc_local_lane: bool,
 }
 
 impl Default for TransactionPoolOptions {
 	fn default() -> Self {
-		Self { txpool_type: TransactionPoolType::SingleState, options: Default::default() }
+		Self {
+			txpool_type: TransactionPoolType::SingleState,
+			options: Default::default(),
+			journal: None,
+			rpc_local_lane: false,
+		}
 	}
 }
 
@@ -81,7 +96,7 @@ impl TransactionPoolOptions {
 			Duration::from_secs(30 * 60)
 		};
 
-		TransactionPoolOptions { options, txpool_type }
+		TransactionPoolOptions { options, txpool_type, journal: None, rpc_local_lane: false }
 	}
 
 	/// Creates predefined options for benchmarking
@@ -98,10 +113,43 @@ impl TransactionPoolOptions {
 				},
 				reject_future_transactions: false,
 				ban_time: Duration::from_secs(30 * 60),
+				min_priority: 0,
+				local_lane: Default::default(),
 			},
 			txpool_type: TransactionPoolType::SingleState,
+			journal: None,
+			rpc_local_lane: false,
 		}
 	}
+
+	/// Sets the minimum priority of the transactions accepted to the pool.
+	pub fn with_min_priority(mut self, min_priority: TransactionPriority) -> Self {
+		self.options.min_priority = min_priority;
+		self
+	}
+
+	/// Sets the treatment of the transactions submitted by the node itself.
+	pub fn with_local_lane(mut self, local_lane: LocalLaneOptions) -> Self {
+		self.options.local_lane = local_lane;
+		self
+	}
+
+	/// Enables the journal persisting the transactions of the pool across restarts.
+	pub fn with_journal(mut self, journal: JournalOptions) -> Self {
+		self.journal = Some(journal);
+		self
+	}
+
+	/// Sets whether the transactions submitted over RPC are submitted to the local lane.
+	pub fn with_rpc_local_lane(mut self, rpc_local_lane: bool) -> Self {
+		self.rpc_local_lane = rpc_local_lane;
+		self
+	}
+
+	/// Are the transactions submitted over RPC submitted to the local lane.
+	pub fn rpc_local_lane(&self) -> bool {
+		self.rpc_local_lane
+	}
 }
 
 /// `FullClientTransactionPool` is a trait that combines the functionality of
@@ -175,6 +223,7 @@ pub struct Builder<'a, Block, Client> {
 	prometheus: Option<&'a PrometheusRegistry>,
 	client: Arc<Client>,
 	spawner: Box<dyn SpawnEssentialNamed>,
+	mempool_policy: Option<Arc<dyn TxMemPoolPolicy<Block>>>,
 	_phantom: PhantomData<(Client, Block)>,
 }
 
@@ -207,6 +256,7 @@ where
 			client,
 			is_validator,
 			prometheus: None,
+			mempool_policy: None,
 		}
 	}
 
@@ -222,6 +272,14 @@ where
 		self
 	}
 
+	/// Sets the replacement and eviction policy of the fork-aware transaction pool's mempool.
+	///
+	/// Ignored by the single-state transaction pool.
+	pub fn with_mempool_policy(mut self, policy: Arc<dyn TxMemPoolPolicy<Block>>) -> Self {
+		self.mempool_policy = Some(policy);
+		self
+	}
+
 	/// Creates an instance of transaction pool.
 	pub fn build(self) -> TransactionPoolHandle<Block, Client> {
 		tracing::info!(
@@ -229,23 +287,30 @@ where
 			txpool_type = ?self.options.txpool_type,
 			ready = ?self.options.options.ready,
 			future = ?self.options.options.future,
+			journal = ?self.options.journal.as_ref().map(|journal| &journal.path),
 			"Creating transaction pool"
 		);
-		TransactionPoolWrapper::<Block, Client>(match self.options.txpool_type {
-			TransactionPoolType::SingleState => Box::new(SingleStateFullPool::new_full(
-				self.options.options,
-				self.is_validator,
-				self.prometheus,
-				self.spawner,
-				self.client,
-			)),
-			TransactionPoolType::ForkAware => Box::new(ForkAwareFullPool::new_full(
-				self.options.options,
-				self.is_validator,
-				self.prometheus,
-				self.spawner,
-				self.client,
-			)),
-		})
+		let journal = self.options.journal.map(Journal::new);
+		TransactionPoolWrapper::<Block, Client>::new(
+			match self.options.txpool_type {
+				TransactionPoolType::SingleState => Box::new(SingleStateFullPool::new_full(
+					self.options.options,
+					self.is_validator,
+					self.prometheus,
+					self.spawner,
+					self.client,
+				)),
+				TransactionPoolType::ForkAware =>
+					Box::new(ForkAwareFullPool::new_full_with_mempool_policy(
+						self.options.options,
+						self.is_validator,
+						self.prometheus,
+						self.spawner,
+						self.client,
+						self.mempool_policy,
+					)),
+			},
+			journal,
+		)
 	}
 }
@@ -0,0 +1,422 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Transaction pool journal.
+//!
+//! Persists the ready and future transactions of the pool on disk, so they are not lost when the
+//! node restarts. The journal is periodically rewritten when the content of the pool changed and
+//! flushed when the pool is dropped on shutdown, then read back once the first block is notified
+//! to the restarted pool. Restored transactions are submitted again, and thus revalidated, like
+//! any other transaction. Transactions which were submitted locally are restored as local
+//! transactions, all the others as external ones.
+//!
+//! The size of the journal is bounded: transactions with the lowest priority are not journaled
+//! when they do not fit within the limit. Transactions which were first journaled longer than the
+//! configured age limit ago are neither journaled nor restored.
+
+use crate::LOG_TARGET;
+use codec::{Decode, Encode};
+use parking_lot::Mutex;
+use sc_transaction_pool_api::{TransactionPriority, TransactionSource};
+use std::{
+	collections::HashMap,
+	hash::Hash,
+	io,
+	path::PathBuf,
+	time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+use tracing::{debug, warn};
+
+/// The minimum interval between two rewrites of the journal.
+pub(crate) const JOURNAL_ROTATION_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Options of the transaction pool journal.
+#[derive(Debug, Clone)]
+pub struct JournalOptions {
+	/// The path of the journal file.
+	pub path: PathBuf,
+	/// The maximum size of all the transactions kept in the journal, in bytes.
+	pub max_bytes: usize,
+	/// The maximum age of a journaled transaction.
+	pub max_age: Duration,
+}
+
+impl JournalOptions {
+	/// Creates the journal options with the default limits.
+	pub fn new(path: impl Into<PathBuf>) -> Self {
+		Self {
+			path: path.into(),
+			max_bytes: 10 * 1024 * 1024,
+			max_age: Duration::from_secs(3 * 60 * 60),
+		}
+	}
+}
+
+/// A transaction kept in the journal.
+#[derive(Debug, Clone, PartialEq, Encode, Decode)]
+struct JournalEntry<H> {
+	/// The hash of the transaction.
+	hash: H,
+	/// When the transaction was first journaled, in seconds since the unix epoch.
+	journaled_at: u64,
+	/// The source of the transaction.
+	source: TransactionSource,
+	/// The encoded transaction.
+	extrinsic: Vec<u8>,
+}
+
+/// A transaction of the pool to be journaled.
+pub(crate) struct PoolTransaction<H> {
+	/// The hash of the transaction.
+	pub(crate) hash: H,
+	/// The source of the transaction.
+	pub(crate) source: TransactionSource,
+	/// The priority of the transaction.
+	pub(crate) priority: TransactionPriority,
+	/// The encoded transaction.
+	pub(crate) extrinsic: Vec<u8>,
+}
+
+struct JournalState<H> {
+	/// When the journaled transactions were first journaled.
+	journaled_at: HashMap<H, u64>,
+	/// Whether the journal was already restored.
+	restored: bool,
+	/// When the journal was last rewritten.
+	last_rotation: Option<Instant>,
+	/// Whether the journal was written by this instance, thus holds exactly `journaled_at`.
+	written: bool,
+}
+
+/// The transaction pool journal.
+pub(crate) struct Journal<H> {
+	options: JournalOptions,
+	state: Mutex<JournalState<H>>,
+}
+
+/// Returns the current time, in seconds since the unix epoch.
+fn unix_now() -> u64 {
+	SystemTime::now()
+		.duration_since(UNIX_EPOCH)
+		.map(|d| d.as_secs())
+		.unwrap_or_default()
+}
+
+impl<H> Journal<H>
+where
+	H: Encode + Decode + Eq + Hash + Copy,
+{
+	/// Creates a new journal with the given options.
+	pub(crate) fn new(options: JournalOptions) -> Self {
+		Self {
+			options,
+			state: Mutex::new(JournalState {
+				journaled_at: Default::default(),
+				restored: false,
+				last_rotation: None,
+				written: false,
+			}),
+		}
+	}
+
+	/// Returns `true` if the entry journaled at `journaled_at` is too old to be kept.
+	fn is_expired(&self, journaled_at: u64, now: u64) -> bool {
+		now.saturating_sub(journaled_at) > self.options.max_age.as_secs()
+	}
+
+	/// Reads the journaled transactions.
+	///
+	/// Returns the source and the encoded body of the transactions which are not expired, or
+	/// `None` if the journal was already restored. Only local transactions keep their source, the
+	/// others are restored as external transactions.
+	pub(crate) fn restore(&self) -> Option<Vec<(TransactionSource, Vec<u8>)>> {
+		let mut state = self.state.lock();
+		if std::mem::replace(&mut state.restored, true) {
+			return None
+		}
+
+		let entries = match std::fs::read(&self.options.path) {
+			Ok(data) => Vec::<JournalEntry<H>>::decode(&mut &data[..]).unwrap_or_else(|error| {
+				warn!(target: LOG_TARGET, ?error, path = ?self.options.path, "Corrupted txpool journal");
+				Default::default()
+			}),
+			Err(error) if error.kind() == io::ErrorKind::NotFound => Default::default(),
+			Err(error) => {
+				warn!(target: LOG_TARGET, ?error, path = ?self.options.path, "Failed to read txpool journal");
+				Default::default()
+			},
+		};
+
+		let now = unix_now();
+		let restored = entries
+			.into_iter()
+			.filter(|entry| !self.is_expired(entry.journaled_at, now))
+			.map(|entry| {
+				state.journaled_at.insert(entry.hash, entry.journaled_at);
+				let source = match entry.source {
+					TransactionSource::Local => TransactionSource::Local,
+					TransactionSource::InBlock | TransactionSource::External =>
+						TransactionSource::External,
+				};
+				(source, entry.extrinsic)
+			})
+			.collect::<Vec<_>>();
+		debug!(target: LOG_TARGET, count = restored.len(), "txpool journal restored");
+		Some(restored)
+	}
+
+	/// Returns `true` if the journal was restored and is due to be rewritten.
+	pub(crate) fn should_rotate(&self) -> bool {
+		let state = self.state.lock();
+		state.restored &&
+			state.last_rotation.map_or(true, |last_rotation| {
+				last_rotation.elapsed() >= JOURNAL_ROTATION_INTERVAL
+			})
+	}
+
+	/// Rewrites the journal with the given transactions, unless it was not restored yet.
+	///
+	/// Returns the number of journaled transactions, or `None` if the journal was left untouched
+	/// as it still holds the transactions of the previous run.
+	pub(crate) fn flush(
+		&self,
+		transactions: impl IntoIterator<Item = PoolTransaction<H>>,
+	) -> io::Result<Option<usize>> {
+		if !self.state.lock().restored {
+			return Ok(None)
+		}
+		self.rotate(transactions).map(Some)
+	}
+
+	/// Rewrites the journal with the given transactions.
+	///
+	/// Expired transactions are skipped, and the transactions with the highest priority are kept
+	/// when the size limit is reached: a transaction which does not fit is skipped, but smaller
+	/// transactions with a lower priority may still be journaled. The file is not written if the
+	/// journaled transactions did not change since the last rotation. Returns the number of
+	/// journaled transactions.
+	pub(crate) fn rotate(
+		&self,
+		transactions: impl IntoIterator<Item = PoolTransaction<H>>,
+	) -> io::Result<usize> {
+		let mut state = self.state.lock();
+		state.last_rotation = Some(Instant::now());
+
+		let mut transactions = transactions.into_iter().collect::<Vec<_>>();
+		transactions.sort_by_key(|tx| std::cmp::Reverse(tx.priority));
+
+		let now = unix_now();
+		let mut total_bytes = 0usize;
+		let entries = transactions
+			.into_iter()
+			.map(|tx| JournalEntry {
+				hash: tx.hash,
+				journaled_at: state.journaled_at.get(&tx.hash).copied().unwrap_or(now),
+				source: tx.source,
+				extrinsic: tx.extrinsic,
+			})
+			.filter(|entry| !self.is_expired(entry.journaled_at, now))
+			.filter(|entry| {
+				let Some(new_total) = total_bytes
+					.checked_add(entry.extrinsic.len())
+					.filter(|new_total| *new_total <= self.options.max_bytes)
+				else {
+					return false
+				};
+				total_bytes = new_total;
+				true
+			})
+			.collect::<Vec<_>>();
+
+		let unchanged = state.written &&
+			entries.len() == state.journaled_at.len() &&
+			entries.iter().all(|entry| state.journaled_at.contains_key(&entry.hash));
+		if unchanged {
+			return Ok(entries.len())
+		}
+
+		if let Some(parent) = self.options.path.parent() {
+			std::fs::create_dir_all(parent)?;
+		}
+		let tmp_path = self.options.path.with_extension("tmp");
+		std::fs::write(&tmp_path, entries.encode())?;
+		std::fs::rename(&tmp_path, &self.options.path)?;
+
+		state.journaled_at = entries.iter().map(|entry| (entry.hash, entry.journaled_at)).collect();
+		state.written = true;
+		debug!(target: LOG_TARGET, count = entries.len(), "txpool journal rotated");
+		Ok(entries.len())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use sp_core::H256;
+
+	fn tx(hash: u64, priority: TransactionPriority, size: usize) -> PoolTransaction<H256> {
+		PoolTransaction {
+			hash: H256::from_low_u64_be(hash),
+			source: TransactionSource::External,
+			priority,
+			extrinsic: vec![hash as u8; size],
+		}
+	}
+
+	#[test]
+	fn journal_survives_restart() {
+		let dir = tempfile::tempdir().unwrap();
+		let options = JournalOptions::new(dir.path().join("txpool").join("journal"));
+
+		let journal = Journal::<H256>::new(options.clone());
+		assert!(!journal.should_rotate());
+		assert_eq!(journal.restore(), Some(vec![]));
+		assert!(journal.should_rotate());
+		assert_eq!(journal.rotate(vec![tx(1, 10, 4), tx(2, 20, 4)]).unwrap(), 2);
+		assert!(!journal.should_rotate());
+
+		let journal = Journal::<H256>::new(options);
+		assert_eq!(
+			journal.restore(),
+			Some(vec![
+				(TransactionSource::External, vec![2u8; 4]),
+				(TransactionSource::External, vec![1u8; 4]),
+			])
+		);
+		assert_eq!(journal.restore(), None);
+	}
+
+	#[test]
+	fn journal_is_flushed_once_restored() {
+		let dir = tempfile::tempdir().unwrap();
+		let options = JournalOptions::new(dir.path().join("journal"));
+
+		let journal = Journal::<H256>::new(options.clone());
+		journal.restore();
+		journal.rotate(vec![tx(1, 10, 4)]).unwrap();
+		// Rotated recently, but flushing rewrites the journal anyway.
+		assert!(!journal.should_rotate());
+		assert_eq!(journal.flush(vec![tx(1, 10, 4), tx(2, 20, 4)]).unwrap(), Some(2));
+
+		// A journal which was not restored yet is not overwritten.
+		let journal = Journal::<H256>::new(options);
+		assert_eq!(journal.flush(vec![]).unwrap(), None);
+		assert_eq!(
+			journal.restore(),
+			Some(vec![
+				(TransactionSource::External, vec![2u8; 4]),
+				(TransactionSource::External, vec![1u8; 4]),
+			])
+		);
+	}
+
+	#[test]
+	fn journal_is_bounded() {
+		let dir = tempfile::tempdir().unwrap();
+		let options =
+			JournalOptions { max_bytes: 10, ..JournalOptions::new(dir.path().join("journal")) };
+
+		let journal = Journal::<H256>::new(options.clone());
+		journal.restore();
+		assert_eq!(
+			journal
+				.rotate(vec![tx(1, 10, 6), tx(2, 20, 6), tx(3, 30, 4), tx(4, 5, 12), tx(5, 1, 0)])
+				.unwrap(),
+			3
+		);
+
+		// The transactions which do not fit are skipped, not the following ones.
+		let restored = Journal::<H256>::new(options).restore().unwrap();
+		assert_eq!(
+			restored,
+			vec![
+				(TransactionSource::External, vec![3u8; 4]),
+				(TransactionSource::External, vec![2u8; 6]),
+				(TransactionSource::External, vec![]),
+			]
+		);
+	}
+
+	#[test]
+	fn unchanged_journal_is_not_rewritten() {
+		let dir = tempfile::tempdir().unwrap();
+		let options = JournalOptions::new(dir.path().join("journal"));
+
+		let journal = Journal::<H256>::new(options.clone());
+		journal.restore();
+		assert_eq!(journal.rotate(vec![tx(1, 10, 4)]).unwrap(), 1);
+
+		std::fs::remove_file(&options.path).unwrap();
+		assert_eq!(journal.rotate(vec![tx(1, 10, 4)]).unwrap(), 1);
+		assert!(!options.path.exists());
+
+		assert_eq!(journal.rotate(vec![tx(1, 10, 4), tx(2, 10, 4)]).unwrap(), 2);
+		assert!(options.path.exists());
+	}
+
+	#[test]
+	fn only_local_transactions_keep_their_source() {
+		let dir = tempfile::tempdir().unwrap();
+		let options = JournalOptions::new(dir.path().join("journal"));
+
+		let journal = Journal::<H256>::new(options.clone());
+		journal.restore();
+		let with_source = |hash, source| PoolTransaction { source, ..tx(hash, 10, 4) };
+		journal
+			.rotate(vec![
+				with_source(1, TransactionSource::InBlock),
+				with_source(2, TransactionSource::Local),
+				with_source(3, TransactionSource::External),
+			])
+			.unwrap();
+
+		let restored = Journal::<H256>::new(options).restore().unwrap();
+		assert_eq!(
+			restored,
+			vec![
+				(TransactionSource::External, vec![1u8; 4]),
+				(TransactionSource::Local, vec![2u8; 4]),
+				(TransactionSource::External, vec![3u8; 4]),
+			]
+		);
+	}
+
+	#[test]
+	fn expired_transactions_are_dropped() {
+		let dir = tempfile::tempdir().unwrap();
+		let options = JournalOptions {
+			max_age: Duration::from_secs(60),
+			..JournalOptions::new(dir.path().join("journal"))
+		};
+
+		let journal = Journal::<H256>::new(options.clone());
+		journal.restore();
+		journal.rotate(vec![tx(1, 10, 4), tx(2, 10, 4)]).unwrap();
+
+		// Pretend the first transaction was journaled long ago.
+		journal
+			.state
+			.lock()
+			.journaled_at
+			.insert(H256::from_low_u64_be(1), unix_now() - 120);
+		assert_eq!(journal.rotate(vec![tx(1, 10, 4), tx(2, 10, 4)]).unwrap(), 1);
+
+		let restored = Journal::<H256>::new(options).restore().unwrap();
+		assert_eq!(restored, vec![(TransactionSource::External, vec![2u8; 4])]);
+	}
+}
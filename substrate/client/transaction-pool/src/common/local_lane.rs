@@ -0,0 +1,66 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Helpers of the local transactions lane.
+
+use parking_lot::Mutex;
+use std::time::{Duration, Instant};
+
+/// Keeps track of the periodic rebroadcast of the local transactions.
+pub(crate) struct LocalRebroadcast {
+	/// The interval between two rebroadcasts, `None` if disabled.
+	interval: Option<Duration>,
+	/// When the local transactions were last rebroadcasted.
+	last: Mutex<Instant>,
+}
+
+impl LocalRebroadcast {
+	/// Creates a new instance, rebroadcasting every `interval` if given.
+	pub(crate) fn new(interval: Option<Duration>) -> Self {
+		Self { interval, last: Mutex::new(Instant::now()) }
+	}
+
+	/// Returns `true` if the local transactions shall be rebroadcasted now.
+	///
+	/// The next rebroadcast is scheduled one interval later.
+	pub(crate) fn is_due(&self) -> bool {
+		let Some(interval) = self.interval else { return false };
+		let mut last = self.last.lock();
+		if last.elapsed() < interval {
+			return false
+		}
+		*last = Instant::now();
+		true
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn rebroadcast_is_scheduled() {
+		assert!(!LocalRebroadcast::new(None).is_due());
+
+		let rebroadcast = LocalRebroadcast::new(Some(Duration::from_secs(60)));
+		assert!(!rebroadcast.is_due());
+		*rebroadcast.last.lock() = Instant::now() - Duration::from_secs(61);
+		assert!(rebroadcast.is_due());
+		assert!(!rebroadcast.is_due());
+	}
+}
@@ -21,6 +21,8 @@
 pub(crate) mod api;
 pub(crate) mod enactment_state;
 pub(crate) mod error;
+pub(crate) mod journal;
+pub(crate) mod local_lane;
 pub(crate) mod metrics;
 pub(crate) mod sliding_stat;
 #[cfg(test)]
@@ -28,58 +28,71 @@ use crate::{
 	LOG_TARGET,
 };
 use futures::stream::StreamExt;
+use parking_lot::Mutex;
 use sc_transaction_pool_api::TransactionStatus;
 use sc_utils::mpsc;
 use sp_runtime::traits::Block as BlockT;
 use std::{
 	collections::{
 		hash_map::{Entry, OccupiedEntry},
-		HashMap, HashSet,
+		HashMap, HashSet, VecDeque,
 	},
 	fmt::{self, Debug, Formatter},
 	pin::Pin,
+	sync::Arc,
 };
 use tokio_stream::StreamMap;
 use tracing::{debug, trace};
 
-/// Represents a transaction that was removed from the transaction pool, including the reason of its
-/// removal.
-#[derive(Debug, PartialEq)]
-pub struct DroppedTransaction<Hash> {
-	/// Hash of the dropped extrinsic.
-	pub tx_hash: Hash,
-	/// Reason of the transaction being dropped.
-	pub reason: DroppedReason<Hash>,
+pub use sc_transaction_pool_api::{DroppedReason, DroppedTransaction, RecentlyDroppedTransaction};
+
+/// The maximum number of recently dropped transactions kept for introspection.
+const RECENTLY_DROPPED_CAPACITY: usize = 1024;
+
+/// The transactions recently sent out by the [`StreamOfDropped`] or recorded by the pool, kept for
+/// introspection.
+struct RecentlyDropped<H> {
+	/// The recorded transactions, oldest first.
+	transactions: VecDeque<RecentlyDroppedTransaction<H>>,
+	/// The validity errors of the transactions reported as invalid, waiting for the views to
+	/// report them as dropped.
+	invalidity: HashMap<H, String>,
 }
 
-impl<Hash> DroppedTransaction<Hash> {
-	/// Creates a new instance with reason set to `DroppedReason::Usurped(by)`.
-	pub fn new_usurped(tx_hash: Hash, by: Hash) -> Self {
-		Self { reason: DroppedReason::Usurped(by), tx_hash }
+impl<H: std::hash::Hash + Eq + Clone> RecentlyDropped<H> {
+	fn new() -> Self {
+		Self { transactions: VecDeque::new(), invalidity: HashMap::new() }
 	}
 
-	/// Creates a new instance with reason set to `DroppedReason::LimitsEnforced`.
-	pub fn new_enforced_by_limts(tx_hash: Hash) -> Self {
-		Self { reason: DroppedReason::LimitsEnforced, tx_hash }
+	/// Records the dropped transaction.
+	///
+	/// The validity error noted for an invalid transaction, if any, supersedes the given `detail`.
+	fn record(&mut self, dropped: &DroppedTransaction<H>, detail: String) {
+		let noted = self.invalidity.remove(&dropped.tx_hash);
+		let detail = match dropped.reason {
+			DroppedReason::Invalid => noted.unwrap_or(detail),
+			_ => detail,
+		};
+		if self.transactions.len() == RECENTLY_DROPPED_CAPACITY {
+			self.transactions.pop_front();
+		}
+		self.transactions.push_back(RecentlyDroppedTransaction {
+			tx_hash: dropped.tx_hash.clone(),
+			reason: dropped.reason.clone(),
+			detail,
+		});
 	}
 
-	/// Creates a new instance with reason set to `DroppedReason::Invalid`.
-	pub fn new_invalid(tx_hash: Hash) -> Self {
-		Self { reason: DroppedReason::Invalid, tx_hash }
+	/// Notes the validity error of a transaction, until it is reported as invalid.
+	fn note_invalid(&mut self, tx_hash: H, error: String) {
+		// Errors of transactions never reported as dropped must not accumulate.
+		if self.invalidity.len() >= RECENTLY_DROPPED_CAPACITY {
+			self.invalidity.clear();
+		}
+		self.invalidity.insert(tx_hash, error);
 	}
 }
 
-/// Provides reason of why transactions was dropped.
-#[derive(Debug, PartialEq)]
-pub enum DroppedReason<Hash> {
-	/// Transaction was replaced by other transaction (e.g. because of higher priority).
-	Usurped(Hash),
-	/// Transaction was dropped because of internal pool limits being enforced.
-	LimitsEnforced,
-	/// Transaction was dropped because of being invalid.
-	Invalid,
-}
-
 /// Dropped-logic related event from the single view.
 pub type ViewStreamEvent<C> =
 	crate::fork_aware_txpool::view::TransactionStatusEvent<ExtrinsicHash<C>, BlockHash<C>>;
@@ -164,6 +177,9 @@ where
 
 	/// Transactions that need to be notified as dropped.
 	pending_dropped_transactions: Vec<ExtrinsicHash<ChainApi>>,
+
+	/// The transactions sent out by the stream, shared with the controller.
+	recently_dropped: Arc<Mutex<RecentlyDropped<ExtrinsicHash<ChainApi>>>>,
 }
 
 impl<C> MultiViewDropWatcherContext<C>
@@ -338,7 +354,9 @@ where
 	/// This method initializes the internal structures and unfolds the stream of dropped
 	/// transactions. Returns a tuple containing this stream and the controller for managing
 	/// this stream.
-	fn event_stream() -> (StreamOfDropped<C>, Controller<Command<C>>) {
+	fn event_stream(
+		recently_dropped: Arc<Mutex<RecentlyDropped<ExtrinsicHash<C>>>>,
+	) -> (StreamOfDropped<C>, Controller<Command<C>>) {
 		//note: 64 allows to avoid warning messages during execution of unit tests.
 		const CHANNEL_SIZE: usize = 64;
 		let (sender, command_receiver) = sc_utils::mpsc::tracing_unbounded::<Command<C>>(
@@ -352,19 +370,35 @@ where
 			ready_transaction_views: Default::default(),
 			future_transaction_views: Default::default(),
 			pending_dropped_transactions: Default::default(),
+			recently_dropped,
 		};
 
 		let stream_map = futures::stream::unfold(ctx, |mut ctx| async move {
 			loop {
 				if let Some(dropped) = ctx.get_pending_dropped_transaction() {
 					trace!("dropped_watcher: sending out (pending): {dropped:?}");
+					ctx.recently_dropped.lock().record(
+						&dropped,
+						"future transaction no longer referenced by any view".into(),
+					);
 					return Some((dropped, ctx));
 				}
 				tokio::select! {
 					biased;
 					Some(event) = next_event(&mut ctx.stream_map) => {
-						if let Some(dropped) = ctx.handle_event(event.0, event.1) {
+						let block_hash = event.0;
+						if let Some(dropped) = ctx.handle_event(block_hash, event.1) {
 							trace!("dropped_watcher: sending out: {dropped:?}");
+							let detail = match &dropped.reason {
+								DroppedReason::Usurped(by) =>
+									format!("replaced by {by:?} in the view at {block_hash:?}"),
+								DroppedReason::LimitsEnforced => format!(
+									"dropped by the view at {block_hash:?} to enforce the pool limits"
+								),
+								DroppedReason::Invalid =>
+									format!("invalid in the view at {block_hash:?}"),
+							};
+							ctx.recently_dropped.lock().record(&dropped, detail);
 							return Some((dropped, ctx));
 						}
 					},
@@ -388,11 +422,16 @@ where
 pub struct MultiViewDroppedWatcherController<ChainApi: graph::ChainApi> {
 	/// A controller allowing to update the state of the associated [`StreamOfDropped`].
 	controller: Controller<Command<ChainApi>>,
+	/// The most recent transactions sent out by the associated [`StreamOfDropped`].
+	recently_dropped: Arc<Mutex<RecentlyDropped<ExtrinsicHash<ChainApi>>>>,
 }
 
 impl<ChainApi: graph::ChainApi> Clone for MultiViewDroppedWatcherController<ChainApi> {
 	fn clone(&self) -> Self {
-		Self { controller: self.controller.clone() }
+		Self {
+			controller: self.controller.clone(),
+			recently_dropped: self.recently_dropped.clone(),
+		}
 	}
 }
 
@@ -402,9 +441,35 @@ where
 	<<ChainApi as graph::ChainApi>::Block as BlockT>::Hash: Unpin,
 {
 	/// Creates new [`StreamOfDropped`] and its controller.
+	///
+	/// The transactions sent out by the stream are recorded, see
+	/// [`Self::recently_dropped`].
 	pub fn new() -> (MultiViewDroppedWatcherController<ChainApi>, StreamOfDropped<ChainApi>) {
-		let (stream_map, ctrl) = MultiViewDropWatcherContext::<ChainApi>::event_stream();
-		(Self { controller: ctrl }, stream_map.boxed())
+		let recently_dropped = Arc::new(Mutex::new(RecentlyDropped::new()));
+		let (stream_map, ctrl) =
+			MultiViewDropWatcherContext::<ChainApi>::event_stream(recently_dropped.clone());
+		(Self { controller: ctrl, recently_dropped }, stream_map)
+	}
+
+	/// Returns the transactions recently sent out by the [`StreamOfDropped`] or recorded with
+	/// [`Self::record_dropped`], oldest first.
+	pub fn recently_dropped(&self) -> Vec<RecentlyDroppedTransaction<ExtrinsicHash<ChainApi>>> {
+		self.recently_dropped.lock().transactions.iter().cloned().collect()
+	}
+
+	/// Records a transaction dropped by the pool without going through the views.
+	pub fn record_dropped(
+		&self,
+		dropped: &DroppedTransaction<ExtrinsicHash<ChainApi>>,
+		detail: String,
+	) {
+		self.recently_dropped.lock().record(dropped, detail);
+	}
+
+	/// Notes the validity error of a transaction being removed from the views as invalid, reported
+	/// as the detail of its removal by [`Self::recently_dropped`].
+	pub fn note_invalid(&self, tx_hash: ExtrinsicHash<ChainApi>, error: String) {
+		self.recently_dropped.lock().note_invalid(tx_hash, error);
 	}
 
 	/// Notifies the [`StreamOfDropped`] that new view was created.
@@ -588,4 +653,66 @@ mod dropped_watcher_tests {
 		let handle = tokio::spawn(async move { output_stream.take(1).collect::<Vec<_>>().await });
 		assert_eq!(handle.await.unwrap(), vec![DroppedTransaction::new_enforced_by_limts(tx_hash)]);
 	}
+
+	#[tokio::test]
+	async fn recently_dropped_are_recorded() {
+		sp_tracing::try_init_simple();
+		let (watcher, output_stream) = MultiViewDroppedWatcher::new();
+
+		let block_hash = H256::repeat_byte(0x01);
+		let tx_hash0 = H256::repeat_byte(0x0a);
+		let tx_hash1 = H256::repeat_byte(0x0b);
+
+		let view_stream = futures::stream::iter(vec![
+			(tx_hash0, TransactionStatus::Ready),
+			(tx_hash1, TransactionStatus::Ready),
+			(tx_hash0, TransactionStatus::Dropped),
+			(tx_hash1, TransactionStatus::Invalid),
+		])
+		.boxed();
+
+		watcher.add_view(block_hash, view_stream);
+		assert!(watcher.recently_dropped().is_empty());
+		let handle = tokio::spawn(async move { output_stream.take(2).collect::<Vec<_>>().await });
+		let dropped = vec![
+			DroppedTransaction::new_enforced_by_limts(tx_hash0),
+			DroppedTransaction::new_invalid(tx_hash1),
+		];
+		assert_eq!(handle.await.unwrap(), dropped);
+		let recorded = watcher.recently_dropped();
+		assert_eq!(
+			recorded.iter().map(|tx| (tx.tx_hash, tx.reason.clone())).collect::<Vec<_>>(),
+			dropped.into_iter().map(|tx| (tx.tx_hash, tx.reason)).collect::<Vec<_>>(),
+		);
+		assert!(recorded[0].detail.contains("to enforce the pool limits"));
+		assert!(recorded[1].detail.starts_with("invalid in the view"));
+	}
+
+	#[tokio::test]
+	async fn recently_dropped_report_noted_validity_errors() {
+		sp_tracing::try_init_simple();
+		let (watcher, output_stream) = MultiViewDroppedWatcher::new();
+
+		let block_hash = H256::repeat_byte(0x01);
+		let tx_hash = H256::repeat_byte(0x0a);
+
+		watcher.note_invalid(tx_hash, "BadProof".into());
+		let view_stream = futures::stream::iter(vec![
+			(tx_hash, TransactionStatus::Ready),
+			(tx_hash, TransactionStatus::Invalid),
+		])
+		.boxed();
+
+		watcher.add_view(block_hash, view_stream);
+		let handle = tokio::spawn(async move { output_stream.take(1).collect::<Vec<_>>().await });
+		assert_eq!(handle.await.unwrap(), vec![DroppedTransaction::new_invalid(tx_hash)]);
+		assert_eq!(
+			watcher.recently_dropped(),
+			vec![RecentlyDroppedTransaction {
+				tx_hash,
+				reason: DroppedReason::Invalid,
+				detail: "BadProof".into(),
+			}]
+		);
+	}
 }
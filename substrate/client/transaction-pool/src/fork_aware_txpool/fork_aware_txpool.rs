@@ -23,20 +23,21 @@ use super::{
 	import_notification_sink::MultiViewImportNotificationSink,
 	metrics::{EventsMetricsCollector, MetricsLink as PrometheusMetrics},
 	multi_view_listener::MultiViewListener,
-	tx_mem_pool::{InsertionInfo, TxMemPool},
+	tx_mem_pool::{InsertionInfo, MempoolTxSource, TxMemPool, TxMemPoolPolicy},
 	view::View,
 	view_store::ViewStore,
 };
 use crate::{
 	api::FullChainApi,
 	common::{
+		local_lane::LocalRebroadcast,
 		sliding_stat::DurationSlidingStats,
 		tracing_log_xt::{log_xt_debug, log_xt_trace},
 		STAT_SLIDING_WINDOW,
 	},
 	enactment_state::{EnactmentAction, EnactmentState},
 	fork_aware_txpool::{
-		dropped_watcher::{DroppedReason, DroppedTransaction},
+		dropped_watcher::{DroppedReason, DroppedTransaction, RecentlyDroppedTransaction},
 		revalidation_worker,
 	},
 	graph::{
@@ -200,6 +201,9 @@ where
 
 	/// Stats for submit_and_watch call durations
 	submit_and_watch_stats: DurationSlidingStats,
+
+	/// Schedules the rebroadcast of the local transactions.
+	local_rebroadcast: LocalRebroadcast,
 }
 
 impl<ChainApi, Block> ForkAwareTxPool<ChainApi, Block>
@@ -250,6 +254,8 @@ where
 			Default::default(),
 			mempool_max_transactions_count,
 			ready_limits.total_bytes + future_limits.total_bytes,
+			None,
+			None,
 		);
 		let mempool = Arc::from(mempool);
 
@@ -304,6 +310,7 @@ where
 				submit_and_watch_stats: DurationSlidingStats::new(Duration::from_secs(
 					STAT_SLIDING_WINDOW,
 				)),
+				local_rebroadcast: LocalRebroadcast::new(None),
 			},
 			[combined_tasks, mempool_task],
 		)
@@ -381,6 +388,31 @@ where
 		spawner: impl SpawnEssentialNamed,
 		best_block_hash: Block::Hash,
 		finalized_hash: Block::Hash,
+	) -> Self {
+		Self::new_with_background_worker_and_mempool_policy(
+			options,
+			is_validator,
+			pool_api,
+			prometheus,
+			spawner,
+			best_block_hash,
+			finalized_hash,
+			None,
+		)
+	}
+
+	/// Same as [`Self::new_with_background_worker`], with an optional `mempool_policy` which
+	/// defines how the transactions identified by their sender and nonce are replaced and evicted,
+	/// see [`TxMemPoolPolicy`].
+	pub fn new_with_background_worker_and_mempool_policy(
+		options: Options,
+		is_validator: IsValidator,
+		pool_api: Arc<ChainApi>,
+		prometheus: Option<&PrometheusRegistry>,
+		spawner: impl SpawnEssentialNamed,
+		best_block_hash: Block::Hash,
+		finalized_hash: Block::Hash,
+		mempool_policy: Option<Arc<dyn TxMemPoolPolicy<Block>>>,
 	) -> Self {
 		let metrics = PrometheusMetrics::new(prometheus);
 		let (events_metrics_collector, event_metrics_task) =
@@ -402,6 +434,8 @@ where
 			metrics.clone(),
 			options.total_count(),
 			options.ready.total_bytes + options.future.total_bytes,
+			mempool_policy,
+			options.local_lane.exempt_from_limits.then(|| options.local_lane.limit.clone()),
 		);
 		let mempool = Arc::from(mempool);
 
@@ -439,6 +473,8 @@ where
 			blocking_mempool_task,
 		);
 
+		let local_rebroadcast = LocalRebroadcast::new(options.local_lane.rebroadcast_interval);
+
 		Self {
 			mempool,
 			api: pool_api,
@@ -460,6 +496,7 @@ where
 			submit_and_watch_stats: DurationSlidingStats::new(Duration::from_secs(
 				STAT_SLIDING_WINDOW,
 			)),
+			local_rebroadcast,
 		}
 	}
 
@@ -713,13 +750,13 @@ where
 	async fn submit_and_watch_inner(
 		&self,
 		at: Block::Hash,
-		source: TransactionSource,
+		source: MempoolTxSource,
 		xt: TransactionFor<Self>,
 	) -> Result<Pin<Box<TransactionStatusStreamFor<Self>>>, ChainApi::Error> {
 		let xt = Arc::from(xt);
 
 		let insertion = match self.mempool.push_watched(source, xt.clone()).await {
-			Ok(result) => result,
+			Ok(result) => self.post_attempt_transaction_replacement(result.hash, result)?,
 			Err(TxPoolApiError::ImmediatelyDropped) =>
 				self.attempt_transaction_replacement(source, true, xt.clone()).await?,
 			Err(e) => return Err(e.into()),
@@ -745,7 +782,7 @@ where
 	/// Refer to [`Self::submit_at`]
 	async fn submit_at_inner(
 		&self,
-		source: TransactionSource,
+		source: MempoolTxSource,
 		xts: Vec<TransactionFor<Self>>,
 	) -> Result<Vec<Result<TxHash<Self>, ChainApi::Error>>, ChainApi::Error> {
 		let view_store = self.view_store.clone();
@@ -762,7 +799,11 @@ where
 		if view_store.is_empty() {
 			return Ok(mempool_results
 				.into_iter()
-				.map(|r| r.map(|r| r.hash).map_err(Into::into))
+				.map(|r| {
+					r.and_then(|r| self.post_attempt_transaction_replacement(r.hash, r))
+						.map(|r| r.hash)
+						.map_err(Into::into)
+				})
 				.collect::<Vec<_>>())
 		}
 
@@ -772,9 +813,11 @@ where
 			.zip(xts.clone())
 			.map(|(result, xt)| async move {
 				match result {
+					Ok(insertion) =>
+						self.post_attempt_transaction_replacement(insertion.hash, insertion),
 					Err(TxPoolApiError::ImmediatelyDropped) =>
 						self.attempt_transaction_replacement(source, false, xt).await,
-					_ => result,
+					Err(e) => Err(e),
 				}
 			})
 			.collect::<Vec<_>>();
@@ -934,7 +977,7 @@ where
 		);
 		log_xt_trace!(target: LOG_TARGET, xts.iter().map(|xt| self.tx_hash(xt)), "fatp::submit_at");
 
-		let result = self.submit_at_inner(source, xts).await;
+		let result = self.submit_at_inner(source.into(), xts).await;
 
 		insert_and_log_throttled!(
 			Level::DEBUG,
@@ -986,7 +1029,7 @@ where
 			views = self.active_views_count(),
 			"fatp::submit_and_watch"
 		);
-		let result = self.submit_and_watch_inner(at, source, xt).await;
+		let result = self.submit_and_watch_inner(at, source.into(), xt).await;
 		insert_and_log_throttled!(
 			Level::DEBUG,
 			target:LOG_TARGET_STAT,
@@ -997,6 +1040,48 @@ where
 		result
 	}
 
+	/// Submits a single transaction to the local lane.
+	///
+	/// The transaction is inserted to the mempool as a transaction of the local lane, the rest of
+	/// the submission process is the same as for [`Self::submit_at`].
+	async fn submit_one_to_local_lane(
+		&self,
+		_at: <Self::Block as BlockT>::Hash,
+		source: TransactionSource,
+		xt: TransactionFor<Self>,
+	) -> Result<TxHash<Self>, Self::Error> {
+		trace!(
+			target: LOG_TARGET,
+			tx_hash = ?self.tx_hash(&xt),
+			active_views_count = self.active_views_count(),
+			"fatp::submit_one_to_local_lane"
+		);
+		match self.submit_at_inner(MempoolTxSource::new_local_lane(source), vec![xt]).await {
+			Ok(mut v) =>
+				v.pop().expect("There is exactly one element in result of submit_at. qed."),
+			Err(e) => Err(e),
+		}
+	}
+
+	/// Submits a transaction to the local lane and starts to watch its progress in the pool.
+	///
+	/// Refer to [`Self::submit_one_to_local_lane`] and [`Self::submit_and_watch`].
+	async fn submit_and_watch_to_local_lane(
+		&self,
+		at: <Self::Block as BlockT>::Hash,
+		source: TransactionSource,
+		xt: TransactionFor<Self>,
+	) -> Result<Pin<Box<TransactionStatusStreamFor<Self>>>, Self::Error> {
+		trace!(
+			target: LOG_TARGET,
+			tx_hash = ?self.tx_hash(&xt),
+			views = self.active_views_count(),
+			"fatp::submit_and_watch_to_local_lane"
+		);
+		self.submit_and_watch_inner(at, MempoolTxSource::new_local_lane(source), xt)
+			.await
+	}
+
 	/// Reports invalid transactions to the transaction pool.
 	///
 	/// This function takes an array of tuples, each consisting of a transaction hash and the
@@ -1114,6 +1199,32 @@ where
 	) -> ReadyIteratorFor<ChainApi> {
 		self.ready_at_with_timeout_internal(at, timeout).await
 	}
+
+	/// Returns the hashes of the active views containing the transactions, either as ready or
+	/// future ones.
+	fn transaction_views(&self) -> HashMap<TxHash<Self>, Vec<Block::Hash>> {
+		self.view_store.transaction_views()
+	}
+
+	/// Returns the transactions recently reported as dropped by the views.
+	fn recently_dropped(&self) -> Vec<RecentlyDroppedTransaction<TxHash<Self>>> {
+		self.view_store.dropped_stream_controller.recently_dropped()
+	}
+
+	/// Returns the ready transactions of the local lane, once per rebroadcast interval.
+	fn transactions_to_rebroadcast(&self) -> Vec<TxHash<Self>> {
+		if !self.local_rebroadcast.is_due() {
+			return Vec::new()
+		}
+		let local = self
+			.view_store
+			.ready()
+			.filter(|tx| tx.source.is_local_lane())
+			.map(|tx| tx.hash)
+			.collect::<Vec<_>>();
+		debug!(target: LOG_TARGET, count = local.len(), "rebroadcasting local transactions");
+		local
+	}
 }
 
 impl<ChainApi, Block> sc_transaction_pool_api::LocalTransactionPool
@@ -1147,12 +1258,13 @@ where
 			.remove(0);
 
 		let insertion = match result {
+			Ok(insertion) => self.post_attempt_transaction_replacement(insertion.hash, insertion),
 			Err(TxPoolApiError::ImmediatelyDropped) => self.attempt_transaction_replacement_sync(
 				TransactionSource::Local,
 				false,
 				xt.clone(),
 			),
-			_ => result,
+			Err(e) => Err(e),
 		}?;
 
 		self.view_store
@@ -1809,7 +1921,7 @@ where
 	#[instrument(level = Level::TRACE, skip_all, target = "txpool", name = "fatp::attempt_transaction_replacement")]
 	async fn attempt_transaction_replacement(
 		&self,
-		source: TransactionSource,
+		source: MempoolTxSource,
 		watched: bool,
 		xt: ExtrinsicFor<ChainApi>,
 	) -> Result<InsertionInfo<ExtrinsicHash<ChainApi>>, TxPoolApiError> {
@@ -1826,7 +1938,8 @@ where
 			.verify_one(
 				best_view.at.hash,
 				best_view.at.number,
-				TimedTransactionSource::from_transaction_source(source, false),
+				TimedTransactionSource::from_transaction_source(source.source, false)
+					.with_local_lane(source.local_lane),
 				xt.clone(),
 				crate::graph::CheckBannedBeforeVerify::Yes,
 				ValidateTransactionPriority::Submitted,
@@ -1880,6 +1993,24 @@ where
 		tx_hash: ExtrinsicHash<ChainApi>,
 		insertion_info: InsertionInfo<ExtrinsicHash<ChainApi>>,
 	) -> Result<InsertionInfo<ExtrinsicHash<ChainApi>>, TxPoolApiError> {
+		for usurped_hash in &insertion_info.usurped {
+			trace!(
+				target: LOG_TARGET,
+				tx_hash = ?usurped_hash,
+				new_tx_hash = ?tx_hash,
+				"usurped: replaced by"
+			);
+			let dropped = DroppedTransaction::new_usurped(*usurped_hash, tx_hash);
+			self.view_store
+				.dropped_stream_controller
+				.record_dropped(&dropped, format!("replaced by {tx_hash:?} in the mempool"));
+			self.view_store.listener.transaction_dropped(dropped);
+
+			// The replaced transaction is no longer in the mempool. Its descendants are kept in
+			// the mempool and get back into the views with the replacement on the next update.
+			self.view_store.remove_transaction_subtree(*usurped_hash, |_, _| {});
+		}
+
 		for worst_hash in &insertion_info.removed {
 			trace!(
 				target: LOG_TARGET,
@@ -1887,9 +2018,12 @@ where
 				new_tx_hash = ?tx_hash,
 				"removed: replaced by"
 			);
-			self.view_store
-				.listener
-				.transaction_dropped(DroppedTransaction::new_enforced_by_limts(*worst_hash));
+			let dropped = DroppedTransaction::new_enforced_by_limts(*worst_hash);
+			self.view_store.dropped_stream_controller.record_dropped(
+				&dropped,
+				format!("removed from the full mempool in favour of {tx_hash:?}"),
+			);
+			self.view_store.listener.transaction_dropped(dropped);
 
 			self.view_store
 				.remove_transaction_subtree(*worst_hash, |listener, removed_tx_hash| {
@@ -2025,9 +2159,21 @@ where
 		prometheus: Option<&PrometheusRegistry>,
 		spawner: impl SpawnEssentialNamed,
 		client: Arc<Client>,
+	) -> Self {
+		Self::new_full_with_mempool_policy(options, is_validator, prometheus, spawner, client, None)
+	}
+
+	/// Same as [`Self::new_full`], with an optional `mempool_policy`, see [`TxMemPoolPolicy`].
+	pub fn new_full_with_mempool_policy(
+		options: Options,
+		is_validator: IsValidator,
+		prometheus: Option<&PrometheusRegistry>,
+		spawner: impl SpawnEssentialNamed,
+		client: Arc<Client>,
+		mempool_policy: Option<Arc<dyn TxMemPoolPolicy<Block>>>,
 	) -> Self {
 		let pool_api = Arc::new(FullChainApi::new(client.clone(), prometheus, &spawner));
-		let pool = Self::new_with_background_worker(
+		let pool = Self::new_with_background_worker_and_mempool_policy(
 			options,
 			is_validator,
 			pool_api,
@@ -2035,6 +2181,7 @@ where
 			spawner,
 			client.usage_info().chain.best_hash,
 			client.usage_info().chain.finalized_hash,
+			mempool_policy,
 		);
 
 		pool
@@ -305,6 +305,12 @@
 //! while not referenced by the others), what means that transaction can also be
 //! [removed][`dropped_monitor_task`] from the *mempool*.
 //!
+//! Chains accepting Ethereum-compatible transactions can provide a [`TxMemPoolPolicy`]. The
+//! *mempool* then identifies transactions by their sender and nonce: a transaction only replaces
+//! a transaction with the same nonce if its fee is sufficiently bumped, the number of transactions
+//! with a nonce gap is limited per sender, and the transactions of the senders paying the lowest
+//! fee are evicted first when the *mempool* is full.
+//!
 //!
 //! ## API Considerations
 //! Refer to github issue: <https://github.com/paritytech/polkadot-sdk/issues/5491>
@@ -323,6 +329,7 @@
 //! [`mp::revalidate`]: crate::fork_aware_txpool::tx_mem_pool::TxMemPool::revalidate
 //! [`batch_size`]: crate::fork_aware_txpool::tx_mem_pool::TXMEMPOOL_MAX_REVALIDATION_BATCH_SIZE
 //! [`TxInMemPool`]: crate::fork_aware_txpool::tx_mem_pool::TxInMemPool
+//! [`TxMemPoolPolicy`]: crate::fork_aware_txpool::tx_mem_pool::TxMemPoolPolicy
 //! [`MultiViewListener`]: crate::fork_aware_txpool::multi_view_listener::MultiViewListener
 //! [`Pool`]: crate::graph::Pool
 //! [`Watcher`]: crate::graph::watcher::Watcher
@@ -377,6 +384,7 @@ mod view;
 mod view_store;
 
 pub use fork_aware_txpool::{ForkAwareTxPool, ForkAwareTxPoolTask};
+pub use tx_mem_pool::{TxMemPoolPolicy, TxSlot};
 
 mod stream_map_util {
 	use futures::Stream;
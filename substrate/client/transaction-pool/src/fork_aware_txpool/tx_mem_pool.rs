@@ -41,7 +41,7 @@ use sp_runtime::{
 	transaction_validity::{InvalidTransaction, TransactionValidityError},
 };
 use std::{
-	collections::HashSet,
+	collections::{BTreeSet, HashSet},
 	future::Future,
 	pin::Pin,
 	sync::{
@@ -59,17 +59,23 @@ use tracing::{debug, trace};
 use crate::{
 	common::tracing_log_xt::log_xt_trace,
 	graph,
-	graph::{base_pool::TimedTransactionSource, ExtrinsicFor, ExtrinsicHash},
+	graph::{
+		base_pool::{Limit, TimedTransactionSource},
+		ExtrinsicFor, ExtrinsicHash,
+	},
 	ValidateTransactionPriority, LOG_TARGET,
 };
 
 use super::{
-	metrics::MetricsLink as PrometheusMetrics, multi_view_listener::MultiViewListener,
-	view_store::ViewStore,
+	dropped_watcher::DroppedTransaction, metrics::MetricsLink as PrometheusMetrics,
+	multi_view_listener::MultiViewListener, view_store::ViewStore,
 };
 
+mod policy;
 mod tx_mem_pool_map;
 
+pub use policy::{TxMemPoolPolicy, TxSlot};
+
 /// The minimum interval between single transaction revalidations. Given in blocks.
 pub(crate) const TXMEMPOOL_REVALIDATION_PERIOD: u64 = 10;
 
@@ -100,6 +106,12 @@ where
 	/// Priority of transaction at some block. It is assumed it will not be changed often. None if
 	/// not known.
 	priority: RwLock<Option<TransactionPriority>>,
+	/// The slot of the transaction, if the mempool policy applies to it.
+	slot: Option<TxSlot>,
+	/// Is the transaction exempt from the limits of the mempool.
+	///
+	/// Set for local transactions if the local lane is configured to exempt them from limits.
+	protected: bool,
 }
 
 impl<ChainApi, Block> TxInMemPool<ChainApi, Block>
@@ -115,19 +127,27 @@ where
 	}
 
 	/// Creates a new instance of wrapper for unwatched transaction.
-	fn new_unwatched(source: TransactionSource, tx: ExtrinsicFor<ChainApi>, bytes: usize) -> Self {
+	fn new_unwatched(
+		source: impl Into<MempoolTxSource>,
+		tx: ExtrinsicFor<ChainApi>,
+		bytes: usize,
+	) -> Self {
 		Self::new(false, source, tx, bytes)
 	}
 
 	/// Creates a new instance of wrapper for watched transaction.
-	fn new_watched(source: TransactionSource, tx: ExtrinsicFor<ChainApi>, bytes: usize) -> Self {
+	fn new_watched(
+		source: impl Into<MempoolTxSource>,
+		tx: ExtrinsicFor<ChainApi>,
+		bytes: usize,
+	) -> Self {
 		Self::new(true, source, tx, bytes)
 	}
 
 	/// Creates a new instance of wrapper for a transaction with no priority.
 	fn new(
 		watched: bool,
-		source: TransactionSource,
+		source: impl Into<MempoolTxSource>,
 		tx: ExtrinsicFor<ChainApi>,
 		bytes: usize,
 	) -> Self {
@@ -137,7 +157,7 @@ where
 	/// Creates a new instance of wrapper for a transaction with given priority.
 	fn new_with_priority(
 		watched: bool,
-		source: TransactionSource,
+		source: impl Into<MempoolTxSource>,
 		tx: ExtrinsicFor<ChainApi>,
 		bytes: usize,
 		priority: TransactionPriority,
@@ -148,18 +168,22 @@ where
 	/// Creates a new instance of wrapper for a transaction with optional priority.
 	fn new_with_optional_priority(
 		watched: bool,
-		source: TransactionSource,
+		source: impl Into<MempoolTxSource>,
 		tx: ExtrinsicFor<ChainApi>,
 		bytes: usize,
 		priority: Option<TransactionPriority>,
 	) -> Self {
+		let source = source.into();
 		Self {
 			watched,
 			tx,
-			source: TimedTransactionSource::from_transaction_source(source, true),
+			source: TimedTransactionSource::from_transaction_source(source.source, true)
+				.with_local_lane(source.local_lane),
 			validated_at: AtomicU64::new(0),
 			bytes,
 			priority: priority.into(),
+			slot: None,
+			protected: false,
 		}
 	}
 
@@ -194,6 +218,8 @@ where
 			.field("source", &self.source)
 			.field("validated_at", &self.validated_at)
 			.field("priority", &self.priority)
+			.field("slot", &self.slot)
+			.field("protected", &self.protected)
 			.finish()
 	}
 }
@@ -214,22 +240,48 @@ where
 	}
 }
 
+/// The source of a transaction submitted to the mempool.
 #[derive(Debug, Clone, Copy, Eq, PartialEq)]
-struct MempoolTxPriority(pub Option<TransactionPriority>);
+pub(super) struct MempoolTxSource {
+	/// The source the transaction is validated with.
+	pub(super) source: TransactionSource,
+	/// Was the transaction explicitly submitted to the local lane.
+	pub(super) local_lane: bool,
+}
 
-impl Ord for MempoolTxPriority {
-	fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-		match (&self.0, &other.0) {
-			(Some(a), Some(b)) => a.cmp(b),
-			(Some(_), None) => std::cmp::Ordering::Less,
-			(None, Some(_)) => std::cmp::Ordering::Greater,
-			(None, None) => std::cmp::Ordering::Equal,
-		}
+impl MempoolTxSource {
+	/// Creates a source for a transaction explicitly submitted to the local lane.
+	pub(super) fn new_local_lane(source: TransactionSource) -> Self {
+		Self { source, local_lane: true }
+	}
+}
+
+impl From<TransactionSource> for MempoolTxSource {
+	fn from(source: TransactionSource) -> Self {
+		Self { source, local_lane: false }
 	}
 }
-impl From<Option<TransactionPriority>> for MempoolTxPriority {
-	fn from(value: Option<TransactionPriority>) -> Self {
-		MempoolTxPriority(value)
+
+/// The priority used to order the transactions of the mempool.
+///
+/// Protected transactions are always considered better than the other ones, so they are never
+/// replaced by [`TxMemPool::try_insert_with_replacement`].
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+struct MempoolTxPriority {
+	protected: bool,
+	priority: Option<TransactionPriority>,
+}
+
+impl Ord for MempoolTxPriority {
+	fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+		self.protected
+			.cmp(&other.protected)
+			.then_with(|| match (&self.priority, &other.priority) {
+				(Some(a), Some(b)) => a.cmp(b),
+				(Some(_), None) => std::cmp::Ordering::Less,
+				(None, Some(_)) => std::cmp::Ordering::Greater,
+				(None, None) => std::cmp::Ordering::Equal,
+			})
 	}
 }
 
@@ -258,7 +310,7 @@ where
 	type Timestamp = Option<Instant>;
 
 	fn priority(&self) -> Self::Priority {
-		TxInMemPool::priority(self).into()
+		MempoolTxPriority { protected: self.protected, priority: TxInMemPool::priority(self) }
 	}
 
 	fn timestamp(&self) -> Self::Timestamp {
@@ -266,12 +318,34 @@ where
 	}
 }
 
+impl<ChainApi, Block> tx_mem_pool_map::PolicySlot for Arc<TxInMemPool<ChainApi, Block>>
+where
+	Block: BlockT,
+	ChainApi: graph::ChainApi<Block = Block> + 'static,
+{
+	fn slot(&self) -> Option<&TxSlot> {
+		self.slot.as_ref()
+	}
+
+	fn is_protected(&self) -> bool {
+		self.protected
+	}
+}
+
 type InternalTxMemPoolMap<ChainApi, Block> = tx_mem_pool_map::SizeTrackedStore<
 	ExtrinsicHash<ChainApi>,
 	tx_mem_pool_map::PriorityKey<MempoolTxPriority, Option<Instant>>,
 	Arc<TxInMemPool<ChainApi, Block>>,
 >;
 
+type InternalTxMemPoolMapWriteAccess<'a, ChainApi, Block> =
+	tx_mem_pool_map::SizeTrackedStoreWriteAccess<
+		'a,
+		ExtrinsicHash<ChainApi>,
+		tx_mem_pool_map::PriorityKey<MempoolTxPriority, Option<Instant>>,
+		Arc<TxInMemPool<ChainApi, Block>>,
+	>;
+
 /// Internal (blocking) task for bridging sync and async code.
 ///
 /// Should be polled in blocking task.
@@ -316,6 +390,17 @@ where
 
 	/// Maximal size of encodings of all transactions in the memory pool.
 	max_transactions_total_bytes: usize,
+
+	/// The optional replacement and eviction policy.
+	///
+	/// See [`TxMemPoolPolicy`] for details.
+	policy: Option<Arc<dyn TxMemPoolPolicy<Block>>>,
+
+	/// The limits of the local lane, if its transactions are exempt from the limits of the
+	/// mempool.
+	///
+	/// See [`crate::LocalLaneOptions::exempt_from_limits`].
+	local_lane_limit: Option<Limit>,
 }
 
 /// Helper structure to encapsulate a result of [`TxMemPool::try_insert`].
@@ -324,6 +409,8 @@ pub(super) struct InsertionInfo<Hash> {
 	pub(super) hash: Hash,
 	pub(super) source: TimedTransactionSource,
 	pub(super) removed: Vec<Hash>,
+	/// The transactions replaced by the inserted one, according to the mempool policy.
+	pub(super) usurped: Vec<Hash>,
 }
 
 impl<Hash> InsertionInfo<Hash> {
@@ -331,7 +418,7 @@ impl<Hash> InsertionInfo<Hash> {
 		Self::new_with_removed(hash, source, Default::default())
 	}
 	fn new_with_removed(hash: Hash, source: TimedTransactionSource, removed: Vec<Hash>) -> Self {
-		Self { hash, source, removed }
+		Self { hash, source, removed, usurped: Default::default() }
 	}
 }
 
@@ -342,13 +429,15 @@ where
 	<Block as BlockT>::Hash: Unpin,
 {
 	/// Creates a new `TxMemPool` instance with the given API, listener, metrics,
-	/// and max transaction count.
+	/// max transaction count, optional replacement policy and local lane setting.
 	pub(super) fn new(
 		api: Arc<ChainApi>,
 		listener: Arc<MultiViewListener<ChainApi>>,
 		metrics: PrometheusMetrics,
 		max_transactions_count: usize,
 		max_transactions_total_bytes: usize,
+		policy: Option<Arc<dyn TxMemPoolPolicy<Block>>>,
+		local_lane_limit: Option<Limit>,
 	) -> (Self, TxMemPoolBlockingTask) {
 		let (sync_channel, rx) = sync_bridge_channel();
 		let task = Self::sync_bridge_task(rx);
@@ -361,6 +450,8 @@ where
 				metrics,
 				max_transactions_count,
 				max_transactions_total_bytes,
+				policy,
+				local_lane_limit,
 			},
 			task.boxed(),
 		)
@@ -383,6 +474,8 @@ where
 			sync_channel,
 			max_transactions_count,
 			max_transactions_total_bytes,
+			policy: None,
+			local_lane_limit: None,
 		}
 	}
 
@@ -418,21 +511,60 @@ where
 			current_total_bytes > self.max_transactions_total_bytes
 	}
 
+	/// Returns the slot of the given transaction, if the mempool policy applies to it.
+	fn slot(&self, tx: &ExtrinsicFor<ChainApi>) -> Option<TxSlot> {
+		self.policy.as_ref().and_then(|policy| policy.slot(tx))
+	}
+
+	/// Returns the nonce of the sender of the given slot as known by the chain, if the mempool
+	/// policy provides it.
+	fn account_nonce(&self, slot: &TxSlot) -> Option<u64> {
+		self.policy.as_ref().and_then(|policy| policy.account_nonce(&slot.sender))
+	}
+
+	/// Returns true if a transaction of the given source and size is exempt from the limits.
+	///
+	/// Only the transactions of the local lane are exempt, as long as the local lane stays within
+	/// its own limits.
+	fn is_protected(
+		&self,
+		transactions: &InternalTxMemPoolMapWriteAccess<'_, ChainApi, Block>,
+		source: &TimedTransactionSource,
+		bytes: usize,
+	) -> bool {
+		let Some(limit) = self.local_lane_limit.as_ref() else { return false };
+		if !source.is_local_lane() {
+			return false
+		}
+		let (count, protected_bytes) = transactions.protected_usage();
+		!limit.is_exceeded(count + 1, protected_bytes + bytes)
+	}
+
 	/// Attempts to insert a transaction into the memory pool, ensuring it does not
 	/// exceed the maximum allowed transaction count.
+	///
+	/// Transactions subject to the mempool policy are inserted according to its rules, see
+	/// [`TxMemPoolPolicy`]. Protected local transactions are inserted regardless of the limits of
+	/// the mempool.
 	async fn try_insert(
 		&self,
 		tx_hash: ExtrinsicHash<ChainApi>,
-		tx: TxInMemPool<ChainApi, Block>,
+		mut tx: TxInMemPool<ChainApi, Block>,
 	) -> Result<InsertionInfo<ExtrinsicHash<ChainApi>>, sc_transaction_pool_api::error::Error> {
-		let mut transactions = self.transactions.write().await;
+		// The policy may query the chain, which is done before locking the transactions.
+		tx.slot = self.slot(&tx.tx);
+		let account_nonce = tx.slot.as_ref().and_then(|slot| self.account_nonce(slot));
 
+		let mut transactions = self.transactions.write().await;
 		let bytes = self.transactions.bytes();
+		tx.protected = self.is_protected(&transactions, &tx.source, tx.bytes);
 
 		let result = match (
-			self.is_limit_exceeded(transactions.len() + 1, bytes + tx.bytes),
+			self.is_limit_exceeded(transactions.len() + 1, bytes + tx.bytes) && !tx.protected,
 			transactions.contains_key(&tx_hash),
 		) {
+			(_, false) if tx.slot.is_some() =>
+				self.try_insert_with_policy(&mut transactions, tx_hash, tx, account_nonce),
 			(false, false) => {
 				let source = tx.source();
 				transactions.insert(tx_hash, Arc::from(tx));
@@ -451,6 +583,108 @@ where
 		result
 	}
 
+	/// Attempts to insert a transaction which is subject to the mempool policy.
+	///
+	/// A transaction with the same sender and nonce as a pooled transaction is only accepted if
+	/// it pays the minimum replacement fee. The replaced transaction is dropped from the mempool
+	/// and its hash is returned in [`InsertionInfo::usurped`].
+	///
+	/// A transaction which is not contiguous with the nonce of its sender (`account_nonce`, or the
+	/// lowest nonce of the sender in the mempool if unknown) is rejected if the sender already has
+	/// too many gapped transactions.
+	///
+	/// If the limits of the mempool are exceeded, the transactions of the senders paying a lower
+	/// fee are dropped, starting from the sender paying the lowest fee and from its highest nonce.
+	/// The hashes of the dropped transactions are returned in [`InsertionInfo::removed`]. Protected
+	/// transactions are never dropped, and are inserted even if the limits are exceeded.
+	fn try_insert_with_policy(
+		&self,
+		transactions: &mut InternalTxMemPoolMapWriteAccess<'_, ChainApi, Block>,
+		tx_hash: ExtrinsicHash<ChainApi>,
+		tx: TxInMemPool<ChainApi, Block>,
+		account_nonce: Option<u64>,
+	) -> Result<InsertionInfo<ExtrinsicHash<ChainApi>>, sc_transaction_pool_api::error::Error> {
+		let (Some(policy), Some(slot)) = (self.policy.as_ref(), tx.slot.as_ref()) else {
+			return Err(sc_transaction_pool_api::error::Error::ImmediatelyDropped)
+		};
+
+		let to_priority = |fee: u128| TransactionPriority::try_from(fee).unwrap_or(u64::MAX);
+		let replaced = transactions
+			.sender_items(&slot.sender)
+			.filter(|(_, pooled)| pooled.slot.as_ref().is_some_and(|s| s.nonce == slot.nonce))
+			.map(|(hash, pooled)| (*hash, pooled.clone()))
+			.collect::<Vec<_>>();
+		if let Some(replaced_fee) = replaced
+			.iter()
+			.filter_map(|(_, pooled)| pooled.slot.as_ref())
+			.map(|s| s.fee)
+			.max()
+		{
+			if slot.fee < policy::replacement_fee(replaced_fee, policy.min_replacement_bump()) {
+				return Err(sc_transaction_pool_api::error::Error::TooLowPriority {
+					old: to_priority(replaced_fee),
+					new: to_priority(slot.fee),
+				})
+			}
+		} else {
+			let nonces = transactions
+				.sender_items(&slot.sender)
+				.filter_map(|(_, pooled)| pooled.slot.as_ref().map(|pooled_slot| pooled_slot.nonce))
+				.chain(std::iter::once(slot.nonce))
+				.collect::<BTreeSet<_>>();
+			let gapped = policy::gapped_nonces(&nonces, account_nonce);
+			if gapped.len() > policy.max_future_per_sender() && gapped.contains(&slot.nonce) {
+				return Err(sc_transaction_pool_api::error::Error::RejectedFutureTransaction)
+			}
+		}
+
+		// The replaced transactions make room for the new one.
+		let mut length = transactions.len() + 1 - replaced.len();
+		let mut bytes = self.transactions.bytes() + tx.bytes -
+			replaced.iter().map(|(_, pooled)| pooled.bytes).sum::<usize>();
+		let mut removed = Vec::new();
+		if !tx.protected && self.is_limit_exceeded(length, bytes) {
+			// The first evictable transaction of a sender is the one paying its lowest fee, so the
+			// senders are visited from the one paying the lowest fee.
+			let mut visited = HashSet::new();
+			for (_, pooled) in transactions.evictable_items() {
+				let Some(pooled_slot) = pooled.slot.as_ref() else { continue };
+				if pooled_slot.fee >= slot.fee || !self.is_limit_exceeded(length, bytes) {
+					break
+				}
+				if pooled_slot.sender == slot.sender || !visited.insert(&pooled_slot.sender) {
+					continue
+				}
+
+				for (hash, pooled) in transactions.sender_items(&pooled_slot.sender).rev() {
+					if !self.is_limit_exceeded(length, bytes) {
+						break
+					}
+					if pooled.protected {
+						continue
+					}
+					removed.push(*hash);
+					length -= 1;
+					bytes -= pooled.bytes;
+				}
+			}
+
+			if self.is_limit_exceeded(length, bytes) {
+				return Err(sc_transaction_pool_api::error::Error::ImmediatelyDropped)
+			}
+		}
+
+		let usurped = replaced.into_iter().map(|(hash, _)| hash).collect::<Vec<_>>();
+		for hash in removed.iter().chain(&usurped) {
+			transactions.remove(hash);
+		}
+		let source = tx.source();
+		transactions.insert(tx_hash, Arc::from(tx));
+		let mut insertion = InsertionInfo::new_with_removed(tx_hash, source, removed);
+		insertion.usurped = usurped;
+		Ok(insertion)
+	}
+
 	/// Attempts to insert a new transaction in the memory pool and drop some worse existing
 	/// transactions.
 	///
@@ -467,21 +701,30 @@ where
 		&self,
 		new_tx: ExtrinsicFor<ChainApi>,
 		priority: TransactionPriority,
-		source: TransactionSource,
+		source: impl Into<MempoolTxSource>,
 		watched: bool,
 	) -> Result<InsertionInfo<ExtrinsicHash<ChainApi>>, sc_transaction_pool_api::error::Error> {
 		let (hash, length) = self.api.hash_and_length(&new_tx);
-		let new_tx = TxInMemPool::new_with_priority(watched, source, new_tx, length, priority);
-		if new_tx.bytes > self.max_transactions_total_bytes {
-			return Err(sc_transaction_pool_api::error::Error::ImmediatelyDropped);
-		}
+		let mut new_tx = TxInMemPool::new_with_priority(watched, source, new_tx, length, priority);
+		new_tx.slot = self.slot(&new_tx.tx);
 
 		let mut transactions = self.transactions.write().await;
+		new_tx.protected = self.is_protected(&transactions, &new_tx.source, new_tx.bytes);
+		if new_tx.bytes > self.max_transactions_total_bytes && !new_tx.protected {
+			return Err(sc_transaction_pool_api::error::Error::ImmediatelyDropped);
+		}
 
 		if transactions.contains_key(&hash) {
 			return Err(sc_transaction_pool_api::error::Error::AlreadyImported(Box::new(hash)));
 		}
 
+		// Protected transactions are not subject to the limits, there is nothing to replace.
+		if new_tx.protected {
+			let source = new_tx.source();
+			transactions.insert(hash, Arc::new(new_tx));
+			return Ok(InsertionInfo::new(hash, source));
+		}
+
 		// When pushing higher prio transaction, we need to find a number of lower prio txs, such
 		// that the sum of their bytes is ge then size of new tx. Otherwise we could overflow size
 		// limits. Naive way to do it - rev-sort by priority and eat the tail.
@@ -494,7 +737,14 @@ where
 			hash,
 			new_tx,
 		);
-		debug_assert!(!self.is_limit_exceeded(transactions.len(), self.transactions.bytes()));
+		// Only the protected transactions of the local lane may exceed the limits.
+		debug_assert!({
+			let (protected_count, protected_bytes) = transactions.protected_usage();
+			!self.is_limit_exceeded(
+				transactions.len() - protected_count,
+				self.transactions.bytes() - protected_bytes,
+			)
+		});
 		match insertion_result {
 			None => Err(sc_transaction_pool_api::error::Error::ImmediatelyDropped),
 			Some(to_be_removed) => Ok(InsertionInfo::new_with_removed(hash, source, to_be_removed)),
@@ -507,10 +757,11 @@ where
 	/// vector.
 	pub(super) async fn extend_unwatched(
 		&self,
-		source: TransactionSource,
+		source: impl Into<MempoolTxSource>,
 		xts: &[ExtrinsicFor<ChainApi>],
 	) -> Vec<Result<InsertionInfo<ExtrinsicHash<ChainApi>>, sc_transaction_pool_api::error::Error>>
 	{
+		let source = source.into();
 		let insert_futures = xts.into_iter().map(|xt| {
 			let api = self.api.clone();
 			let xt = xt.clone();
@@ -527,7 +778,7 @@ where
 	/// transaction count.
 	pub(super) async fn push_watched(
 		&self,
-		source: TransactionSource,
+		source: impl Into<MempoolTxSource>,
 		xt: ExtrinsicFor<ChainApi>,
 	) -> Result<InsertionInfo<ExtrinsicHash<ChainApi>>, sc_transaction_pool_api::error::Error> {
 		let (hash, length) = self.api.hash_and_length(&xt);
@@ -565,7 +816,7 @@ where
 
 	/// Revalidates a batch of transactions against the provided finalized block.
 	///
-	/// Returns a vector of invalid transaction hashes.
+	/// Returns a vector of invalid transaction hashes, along with their validation errors.
 	async fn revalidate_inner(&self, finalized_block: HashAndNumber<Block>) -> Vec<Block::Hash> {
 		trace!(
 			target: LOG_TARGET,
@@ -626,7 +877,12 @@ where
 						?validation_result,
 						"mempool::revalidate_inner invalid"
 					);
-					Some(tx_hash)
+					let error = match validation_result {
+						Ok(Err(e)) => format!("{e:?}"),
+						Ok(Ok(_)) => Default::default(),
+						Err(e) => e.to_string(),
+					};
+					Some((tx_hash, error))
 				},
 			})
 			.collect::<Vec<_>>();
@@ -670,9 +926,11 @@ where
 	) {
 		let revalidated_invalid_hashes = self.revalidate_inner(finalized_block.clone()).await;
 
-		let mut invalid_hashes_subtrees =
-			revalidated_invalid_hashes.clone().into_iter().collect::<HashSet<_>>();
-		for tx in &revalidated_invalid_hashes {
+		let mut invalid_hashes_subtrees = revalidated_invalid_hashes
+			.iter()
+			.map(|(tx_hash, _)| *tx_hash)
+			.collect::<HashSet<_>>();
+		for (tx, _) in &revalidated_invalid_hashes {
 			invalid_hashes_subtrees.extend(
 				view_store
 					.remove_transaction_subtree(*tx, |_, _| {})
@@ -706,6 +964,12 @@ where
 		view_store
 			.import_notification_sink
 			.clean_notified_items(&invalid_hashes_subtrees);
+		for (tx_hash, error) in revalidated_invalid_hashes {
+			view_store.dropped_stream_controller.record_dropped(
+				&DroppedTransaction::new_invalid(tx_hash),
+				format!("invalid at the finalized block {:?}: {error}", finalized_block.hash),
+			);
+		}
 		view_store
 			.dropped_stream_controller
 			.remove_transactions(invalid_hashes_subtrees);
@@ -932,9 +1196,12 @@ where
 
 #[cfg(test)]
 mod tx_mem_pool_tests {
+	use codec::Encode;
 	use futures::future::join_all;
-	use substrate_test_runtime::{AccountId, Extrinsic, ExtrinsicBuilder, Transfer, H256};
-	use substrate_test_runtime_client::Sr25519Keyring::*;
+	use substrate_test_runtime::{
+		AccountId, Block, Extrinsic, ExtrinsicBuilder, Transfer, TransferData, H256,
+	};
+	use substrate_test_runtime_client::{Sr25519Keyring, Sr25519Keyring::*};
 
 	use crate::{
 		common::tests::TestApi, fork_aware_txpool::view_store::ViewStoreSubmitOutcome,
@@ -1102,6 +1369,47 @@ mod tx_mem_pool_tests {
 		));
 	}
 
+	#[tokio::test]
+	async fn protected_local_txs_are_exempt_from_limits() {
+		let max = 2;
+		let api = Arc::from(TestApi::default());
+		let xt_size = api.hash_and_length(&Arc::from(uxt(0))).1;
+		let mut mempool = TxMemPool::new_test(api.clone(), usize::MAX, max * xt_size);
+		mempool.local_lane_limit = Some(Limit { count: max + 1, total_bytes: usize::MAX });
+
+		let xts = (0..max).map(|x| Arc::from(uxt(x as _))).collect::<Vec<_>>();
+		let results = mempool.extend_unwatched(TransactionSource::Local, &xts).await;
+		assert!(results.iter().all(Result::is_ok));
+
+		// The limit is reached, but the transactions of the local lane are still accepted.
+		let xt = Arc::from(uxt(97));
+		let source = MempoolTxSource::new_local_lane(TransactionSource::External);
+		assert!(mempool.push_watched(source, xt).await.is_ok());
+		assert_eq!(mempool.len(), max + 1);
+
+		// Until the local lane is full.
+		let xt = Arc::from(uxt(96));
+		assert!(matches!(
+			mempool.push_watched(TransactionSource::Local, xt).await.unwrap_err(),
+			sc_transaction_pool_api::error::Error::ImmediatelyDropped
+		));
+
+		// Other transactions are not, and can't replace the local ones.
+		let xt = Arc::from(uxt(98));
+		assert!(matches!(
+			mempool.push_watched(TransactionSource::External, xt.clone()).await.unwrap_err(),
+			sc_transaction_pool_api::error::Error::ImmediatelyDropped
+		));
+		assert!(matches!(
+			mempool
+				.try_insert_with_replacement(xt, u64::MAX, TransactionSource::External, false)
+				.await
+				.unwrap_err(),
+			sc_transaction_pool_api::error::Error::ImmediatelyDropped
+		));
+		assert_eq!(mempool.len(), max + 1);
+	}
+
 	#[tokio::test]
 	async fn replacing_txs_works_for_same_tx_size() {
 		sp_tracing::try_init_simple();
@@ -1302,4 +1610,187 @@ mod tx_mem_pool_tests {
 			sc_transaction_pool_api::error::Error::ImmediatelyDropped
 		));
 	}
+
+	/// Identifies the transfers by their sender and nonce, the amount being used as the fee.
+	#[derive(Default)]
+	struct TestPolicy {
+		account_nonce: Option<u64>,
+	}
+
+	impl TxMemPoolPolicy<Block> for TestPolicy {
+		fn slot(&self, xt: &Extrinsic) -> Option<TxSlot> {
+			let transfer = TransferData::try_from(xt).ok()?;
+			Some(TxSlot {
+				sender: transfer.from.encode(),
+				nonce: transfer.nonce,
+				fee: transfer.amount.into(),
+			})
+		}
+
+		fn account_nonce(&self, _sender: &[u8]) -> Option<u64> {
+			self.account_nonce
+		}
+
+		fn max_future_per_sender(&self) -> usize {
+			2
+		}
+	}
+
+	fn new_test_with_policy(max_transactions_count: usize) -> TxMemPool<TestApi, Block> {
+		new_test_with_test_policy(max_transactions_count, TestPolicy::default())
+	}
+
+	fn new_test_with_test_policy(
+		max_transactions_count: usize,
+		policy: TestPolicy,
+	) -> TxMemPool<TestApi, Block> {
+		let api = Arc::from(TestApi::default());
+		let mut mempool = TxMemPool::new_test(api, max_transactions_count, usize::MAX);
+		mempool.policy = Some(Arc::new(policy));
+		mempool
+	}
+
+	fn transfer(from: Sr25519Keyring, nonce: u64, amount: u64) -> Arc<Extrinsic> {
+		Arc::from(crate::common::tests::uxt(Transfer {
+			from: from.into(),
+			to: AccountId::from_h256(H256::from_low_u64_be(2)),
+			amount,
+			nonce,
+		}))
+	}
+
+	#[tokio::test]
+	async fn policy_replacement_requires_fee_bump() {
+		sp_tracing::try_init_simple();
+		let api = TestApi::default();
+		let mempool = new_test_with_policy(10);
+
+		let xt = transfer(Alice, 0, 100);
+		assert!(mempool.push_watched(TransactionSource::External, xt.clone()).await.is_ok());
+		assert!(matches!(
+			mempool
+				.push_watched(TransactionSource::External, transfer(Alice, 0, 109))
+				.await
+				.unwrap_err(),
+			sc_transaction_pool_api::error::Error::TooLowPriority { old: 100, new: 109 }
+		));
+
+		let result = mempool
+			.push_watched(TransactionSource::External, transfer(Alice, 0, 110))
+			.await
+			.unwrap();
+		assert!(result.removed.is_empty());
+		// The replaced transaction is dropped from the mempool.
+		assert_eq!(result.usurped, vec![api.hash_and_length(&xt).0]);
+		assert_eq!(mempool.len(), 1);
+
+		// The replacement shall pay more than the transaction it replaced.
+		assert!(matches!(
+			mempool
+				.push_watched(TransactionSource::External, transfer(Alice, 0, 120))
+				.await
+				.unwrap_err(),
+			sc_transaction_pool_api::error::Error::TooLowPriority { old: 110, new: 120 }
+		));
+	}
+
+	#[tokio::test]
+	async fn policy_limits_gapped_nonces_per_sender() {
+		sp_tracing::try_init_simple();
+		let mempool = new_test_with_policy(10);
+
+		for nonce in [0, 2, 3] {
+			assert!(mempool
+				.push_watched(TransactionSource::External, transfer(Alice, nonce, 10))
+				.await
+				.is_ok());
+		}
+		assert!(matches!(
+			mempool
+				.push_watched(TransactionSource::External, transfer(Alice, 5, 10))
+				.await
+				.unwrap_err(),
+			sc_transaction_pool_api::error::Error::RejectedFutureTransaction
+		));
+
+		// Other senders are not affected.
+		assert!(mempool
+			.push_watched(TransactionSource::External, transfer(Bob, 5, 10))
+			.await
+			.is_ok());
+
+		// Filling the gap makes room for more transactions.
+		assert!(mempool
+			.push_watched(TransactionSource::External, transfer(Alice, 1, 10))
+			.await
+			.is_ok());
+		assert!(mempool
+			.push_watched(TransactionSource::External, transfer(Alice, 5, 10))
+			.await
+			.is_ok());
+		assert_eq!(mempool.len(), 6);
+	}
+
+	#[tokio::test]
+	async fn policy_gaps_are_relative_to_account_nonce() {
+		sp_tracing::try_init_simple();
+		let mempool = new_test_with_test_policy(10, TestPolicy { account_nonce: Some(0) });
+
+		// The nonces are contiguous, but not with the nonce of the account.
+		for nonce in [3, 4] {
+			assert!(mempool
+				.push_watched(TransactionSource::External, transfer(Alice, nonce, 10))
+				.await
+				.is_ok());
+		}
+		assert!(matches!(
+			mempool
+				.push_watched(TransactionSource::External, transfer(Alice, 5, 10))
+				.await
+				.unwrap_err(),
+			sc_transaction_pool_api::error::Error::RejectedFutureTransaction
+		));
+
+		// The transaction with the nonce of the account is not gapped.
+		assert!(mempool
+			.push_watched(TransactionSource::External, transfer(Alice, 0, 10))
+			.await
+			.is_ok());
+		assert_eq!(mempool.len(), 3);
+	}
+
+	#[tokio::test]
+	async fn policy_evicts_lowest_fee_senders_first() {
+		sp_tracing::try_init_simple();
+		let api = TestApi::default();
+		let mempool = new_test_with_policy(4);
+
+		let xts = [
+			transfer(Alice, 0, 10),
+			transfer(Alice, 1, 30),
+			transfer(Bob, 0, 50),
+			transfer(Bob, 1, 50),
+		];
+		for xt in xts.iter() {
+			assert!(mempool.push_watched(TransactionSource::External, xt.clone()).await.is_ok());
+		}
+
+		// Alice pays the lowest fee, her highest nonce is evicted first.
+		let result = mempool
+			.push_watched(TransactionSource::External, transfer(Charlie, 0, 20))
+			.await
+			.unwrap();
+		assert_eq!(result.removed, vec![api.hash_and_length(&xts[1]).0]);
+		assert_eq!(mempool.len(), 4);
+
+		// No sender pays less than this transaction.
+		assert!(matches!(
+			mempool
+				.push_watched(TransactionSource::External, transfer(Dave, 0, 10))
+				.await
+				.unwrap_err(),
+			sc_transaction_pool_api::error::Error::ImmediatelyDropped
+		));
+		assert_eq!(mempool.len(), 4);
+	}
 }
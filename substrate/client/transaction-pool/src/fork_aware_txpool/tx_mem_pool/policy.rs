@@ -0,0 +1,121 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Replacement and eviction policy of the [`TxMemPool`](super::TxMemPool).
+//!
+//! By default the mempool does not know anything about the transactions it keeps, apart from the
+//! priority given by the runtime. Chains accepting Ethereum-compatible transactions can provide a
+//! [`TxMemPoolPolicy`] which identifies the sender, the nonce and the fee of a transaction. The
+//! mempool then follows the semantics wallets expect from geth:
+//! - a transaction with the same sender and nonce as a pooled transaction only replaces it if its
+//!   fee is bumped by at least [`TxMemPoolPolicy::min_replacement_bump`]; the replaced transaction
+//!   is then dropped from the mempool,
+//! - the number of transactions with a nonce gap (i.e. not contiguous with the nonce of the sender
+//!   given by [`TxMemPoolPolicy::account_nonce`]) is limited per sender,
+//! - when the mempool is full, the transactions of the senders paying the lowest fee are evicted
+//!   first, starting from their highest nonce.
+//!
+//! Transactions for which [`TxMemPoolPolicy::slot`] returns `None` are not subject to the policy.
+
+use sp_runtime::{traits::Block as BlockT, Percent};
+use std::collections::BTreeSet;
+
+/// Identifies the slot of a transaction: its sender and nonce, and the fee paid for it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TxSlot {
+	/// The encoded sender of the transaction.
+	pub sender: Vec<u8>,
+	/// The nonce of the transaction.
+	pub nonce: u64,
+	/// The fee paid for the inclusion of the transaction, used to compare transactions.
+	pub fee: u128,
+}
+
+/// The replacement and eviction policy of the mempool.
+pub trait TxMemPoolPolicy<Block: BlockT>: Send + Sync {
+	/// Returns the slot of the given transaction, or `None` if the policy does not apply to it.
+	fn slot(&self, xt: &Block::Extrinsic) -> Option<TxSlot>;
+
+	/// Returns the nonce of the next transaction of `sender`, as known by the chain.
+	///
+	/// The nonce gaps are computed relative to this nonce. If `None` is returned, they are
+	/// computed relative to the lowest nonce of the sender kept in the mempool.
+	fn account_nonce(&self, _sender: &[u8]) -> Option<u64> {
+		None
+	}
+
+	/// The minimum fee increase required to replace a transaction with the same slot.
+	fn min_replacement_bump(&self) -> Percent {
+		Percent::from_percent(10)
+	}
+
+	/// The maximum number of transactions with a nonce gap kept in the mempool per sender.
+	fn max_future_per_sender(&self) -> usize {
+		64
+	}
+}
+
+/// Returns the minimum fee a transaction shall pay to replace a transaction paying `fee`.
+///
+/// The replacement shall always pay strictly more than the replaced transaction.
+pub(super) fn replacement_fee(fee: u128, bump: Percent) -> u128 {
+	fee.saturating_add(bump.mul_ceil(fee)).max(fee.saturating_add(1))
+}
+
+/// Returns the nonces which are not contiguous with `account_nonce`, or with the lowest nonce if
+/// the nonce of the account is not known.
+///
+/// Nonces lower than `account_nonce` are stale and ignored.
+pub(super) fn gapped_nonces(nonces: &BTreeSet<u64>, account_nonce: Option<u64>) -> BTreeSet<u64> {
+	let Some(mut next) = account_nonce.or_else(|| nonces.first().copied()) else {
+		return BTreeSet::new()
+	};
+	let mut gapped = BTreeSet::new();
+	for &nonce in nonces.range(next..) {
+		if nonce == next && gapped.is_empty() {
+			next = next.saturating_add(1);
+		} else {
+			gapped.insert(nonce);
+		}
+	}
+	gapped
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn gapped_nonces_works() {
+		let nonces = BTreeSet::from([1, 2, 4, 5]);
+		assert_eq!(gapped_nonces(&nonces, None), BTreeSet::from([4, 5]));
+		assert_eq!(gapped_nonces(&nonces, Some(1)), BTreeSet::from([4, 5]));
+		assert_eq!(gapped_nonces(&nonces, Some(0)), nonces);
+		// Stale nonces are ignored.
+		assert_eq!(gapped_nonces(&nonces, Some(2)), BTreeSet::from([4, 5]));
+		assert_eq!(gapped_nonces(&nonces, Some(4)), BTreeSet::new());
+		assert_eq!(gapped_nonces(&BTreeSet::new(), None), BTreeSet::new());
+	}
+
+	#[test]
+	fn replacement_fee_works() {
+		assert_eq!(replacement_fee(100, Percent::from_percent(10)), 110);
+		assert_eq!(replacement_fee(0, Percent::from_percent(10)), 1);
+		assert_eq!(replacement_fee(u128::MAX, Percent::from_percent(10)), u128::MAX);
+	}
+}
@@ -22,9 +22,13 @@
 //! `IndexedStorage`, which manages transaction entries by key and priority. Transactions are stored
 //! efficiently with operations to insert items based on priority and manage space utilization. This
 //! module provides core functionality for maintaining the `TxMemPool` state.
+//!
+//! Items subject to the mempool policy are additionally indexed by sender and by fee, so that the
+//! policy rules can be applied without scanning the whole map.
 
+use super::policy::TxSlot;
 use std::{
-	collections::{BTreeMap, HashMap},
+	collections::{BTreeMap, BTreeSet, HashMap},
 	sync::{
 		atomic::{AtomicIsize, Ordering as AtomicOrdering},
 		Arc,
@@ -50,6 +54,15 @@ pub(super) trait PriorityAndTimestamp {
 	fn timestamp(&self) -> Self::Timestamp;
 }
 
+/// Something that may be subject to the mempool policy.
+pub(super) trait PolicySlot {
+	/// The slot of the item, if the mempool policy applies to it.
+	fn slot(&self) -> Option<&TxSlot>;
+
+	/// Is the item exempt from eviction.
+	fn is_protected(&self) -> bool;
+}
+
 /// A dual-key struct for ordering by priority and timestamp.
 ///
 /// `PriorityKey<U, V>` allows sorting where the primary criteria is `Priority`
@@ -89,6 +102,10 @@ where
 	items_by_hashes: HashMap<K, V>,
 	/// BTreeMap ordering transactions for prioritized access based on sort key.
 	items_by_priority: BTreeMap<SortKey<S, K>, V>,
+	/// Keys of the items subject to the mempool policy, grouped by sender and ordered by nonce.
+	items_by_sender: HashMap<Vec<u8>, BTreeSet<(u64, K)>>,
+	/// Keys of the items subject to the mempool policy which can be evicted, ordered by fee.
+	evictable_by_fee: BTreeSet<(u128, K)>,
 }
 
 /// Core structure for storing and managing transactions in TxMemPool.
@@ -118,7 +135,12 @@ where
 	S: Ord,
 {
 	fn default() -> Self {
-		Self { items_by_hashes: Default::default(), items_by_priority: Default::default() }
+		Self {
+			items_by_hashes: Default::default(),
+			items_by_priority: Default::default(),
+			items_by_sender: Default::default(),
+			evictable_by_fee: Default::default(),
+		}
 	}
 }
 
@@ -148,6 +170,17 @@ where
 		self.items_by_hashes.len()
 	}
 
+	/// Returns the number and the total size of the protected items.
+	pub fn protected_usage(&self) -> (usize, usize)
+	where
+		V: PolicySlot + Size,
+	{
+		self.items_by_hashes
+			.values()
+			.filter(|item| item.is_protected())
+			.fold((0, 0), |(count, bytes), item| (count + 1, bytes + item.size()))
+	}
+
 	/// Removes and returns the first entry in the priority map, if it exists (testing only).
 	#[cfg(test)]
 	pub fn pop_first(&mut self) -> Option<V> {
@@ -160,6 +193,54 @@ where
 	{
 		f(self.items_by_hashes.iter())
 	}
+
+	/// Returns the items subject to the mempool policy sent by `sender`, ordered by nonce.
+	pub fn sender_items<'a>(
+		&'a self,
+		sender: &[u8],
+	) -> impl DoubleEndedIterator<Item = (&'a K, &'a V)> + 'a {
+		self.items_by_sender.get(sender).into_iter().flatten().map(|(_, key)| {
+			(key, self.items_by_hashes.get(key).expect("indexed items are stored. qed."))
+		})
+	}
+
+	/// Returns the evictable items subject to the mempool policy, starting from the lowest fee.
+	pub fn evictable_items(&self) -> impl Iterator<Item = (&K, &V)> {
+		self.evictable_by_fee.iter().map(|(_, key)| {
+			(key, self.items_by_hashes.get(key).expect("indexed items are stored. qed."))
+		})
+	}
+}
+
+impl<K, S, V> IndexedStorage<K, S, V>
+where
+	K: Ord + std::hash::Hash + Copy,
+	S: Ord,
+	V: PolicySlot,
+{
+	/// Adds the item to the policy indexes, if the policy applies to it.
+	fn index_slot(&mut self, key: &K, item: &V) {
+		let Some(slot) = item.slot() else { return };
+		self.items_by_sender
+			.entry(slot.sender.clone())
+			.or_default()
+			.insert((slot.nonce, *key));
+		if !item.is_protected() {
+			self.evictable_by_fee.insert((slot.fee, *key));
+		}
+	}
+
+	/// Removes the item from the policy indexes.
+	fn unindex_slot(&mut self, key: &K, item: &V) {
+		let Some(slot) = item.slot() else { return };
+		if let Some(keys) = self.items_by_sender.get_mut(&slot.sender) {
+			keys.remove(&(slot.nonce, *key));
+			if keys.is_empty() {
+				self.items_by_sender.remove(&slot.sender);
+			}
+		}
+		self.evictable_by_fee.remove(&(slot.fee, *key));
+	}
 }
 
 impl<K, A, B, V> IndexedStorage<K, PriorityKey<A, B>, V>
@@ -167,7 +248,7 @@ where
 	K: Ord + std::hash::Hash + Copy,
 	A: Ord,
 	B: Ord,
-	V: Clone + PriorityAndTimestamp<Priority = A, Timestamp = B>,
+	V: Clone + PriorityAndTimestamp<Priority = A, Timestamp = B> + PolicySlot,
 	V: std::cmp::PartialEq + std::fmt::Debug,
 {
 	/// Inserts a key-value pair into the map, ordering by priority.
@@ -177,7 +258,9 @@ where
 		if let Some(ref removed) = r {
 			let a = self.items_by_priority.remove(&SortKey::new(&key, removed));
 			debug_assert_eq!(r, a);
+			self.unindex_slot(&key, removed);
 		}
+		self.index_slot(&key, &val);
 		let a = self.items_by_priority.insert(SortKey::new(&key, &val), val);
 		debug_assert!(a.is_none());
 		r
@@ -190,6 +273,7 @@ where
 			let k = SortKey::new(key, r);
 			let a = self.items_by_priority.remove(&k);
 			debug_assert_eq!(r.clone(), a.expect("item should be in both maps. qed."));
+			self.unindex_slot(key, r);
 		});
 		r
 	}
@@ -221,7 +305,7 @@ where
 	K: Ord + std::hash::Hash + Copy + std::fmt::Debug,
 	A: Ord + std::fmt::Debug,
 	B: Ord + std::fmt::Debug,
-	V: Clone + PriorityAndTimestamp<Priority = A, Timestamp = B> + Size,
+	V: Clone + PriorityAndTimestamp<Priority = A, Timestamp = B> + Size + PolicySlot,
 	V: std::cmp::PartialEq + std::fmt::Debug,
 {
 	/// Attempts to insert an item with replacement based on free space and priority.
@@ -379,7 +463,7 @@ where
 	K: Ord + std::hash::Hash + Copy + std::fmt::Debug,
 	A: Ord + std::fmt::Debug,
 	B: Ord + std::fmt::Debug,
-	V: Clone + PriorityAndTimestamp<Priority = A, Timestamp = B> + Size,
+	V: Clone + PriorityAndTimestamp<Priority = A, Timestamp = B> + Size + PolicySlot,
 	V: std::cmp::PartialEq + std::fmt::Debug,
 {
 	/// Insert value and return previous (if any).
@@ -412,7 +496,7 @@ where
 	) -> Option<Vec<K>> {
 		let item_size = item.size();
 		let current_bytes = std::cmp::max(self.bytes.load(AtomicOrdering::Relaxed), 0) as usize;
-		let free_bytes = max_total_bytes - current_bytes;
+		let free_bytes = max_total_bytes.saturating_sub(current_bytes);
 		let (removed_keys, removed_bytes) =
 			self.inner_guard.try_insert_with_replacement(free_bytes, key, item);
 
@@ -451,6 +535,27 @@ where
 		self.inner_guard.len()
 	}
 
+	/// Refer to [`IndexedStorage::sender_items`]
+	pub fn sender_items<'a>(
+		&'a self,
+		sender: &[u8],
+	) -> impl DoubleEndedIterator<Item = (&'a K, &'a V)> + 'a {
+		self.inner_guard.sender_items(sender)
+	}
+
+	/// Refer to [`IndexedStorage::evictable_items`]
+	pub fn evictable_items(&self) -> impl Iterator<Item = (&K, &V)> {
+		self.inner_guard.evictable_items()
+	}
+
+	/// Refer to [`IndexedStorage::protected_usage`]
+	pub fn protected_usage(&self) -> (usize, usize)
+	where
+		V: PolicySlot + Size,
+	{
+		self.inner_guard.protected_usage()
+	}
+
 	#[cfg(test)]
 	pub fn pop_first(&mut self) -> Option<V> {
 		self.inner_guard.pop_first()
@@ -468,6 +573,8 @@ mod tests {
 		size: usize,
 		prio: u32,
 		ts: u32,
+		slot: Option<TxSlot>,
+		protected: bool,
 	}
 
 	impl PriorityAndTimestamp for TestItem {
@@ -488,9 +595,23 @@ mod tests {
 		}
 	}
 
+	impl PolicySlot for TestItem {
+		fn slot(&self) -> Option<&TxSlot> {
+			self.slot.as_ref()
+		}
+		fn is_protected(&self) -> bool {
+			self.protected
+		}
+	}
+
 	impl TestItem {
 		fn new(prio: u32, ts: u32, size: usize) -> Self {
-			Self { prio, ts, size }
+			Self { prio, ts, size, slot: None, protected: false }
+		}
+
+		fn with_slot(sender: u8, nonce: u64, fee: u128) -> Self {
+			let slot = TxSlot { sender: vec![sender], nonce, fee };
+			Self { slot: Some(slot), ..Self::new(1, 0, 10) }
 		}
 	}
 
@@ -761,4 +882,44 @@ mod tests {
 		assert_eq!(map.write().await.pop_first().unwrap(), i0);
 		assert_eq!(map.write().await.pop_first().unwrap(), i2);
 	}
+
+	#[tokio::test]
+	async fn policy_indexes_follow_content() {
+		let map = SizeTrackedStore::default();
+
+		map.write().await.insert(0xa, TestItem::with_slot(1, 1, 30));
+		map.write().await.insert(0xb, TestItem::with_slot(1, 0, 20));
+		map.write().await.insert(0xc, TestItem::with_slot(2, 0, 10));
+		map.write()
+			.await
+			.insert(0xd, TestItem { protected: true, ..TestItem::with_slot(2, 1, 5) });
+		map.write().await.insert(0xe, TestItem::new(1, 0, 10));
+
+		let sender_keys =
+			|map: &SizeTrackedStoreWriteAccess<'_, i32, PriorityKey<u32, u32>, TestItem>,
+			 sender: u8| map.sender_items(&[sender]).map(|(key, _)| *key).collect::<Vec<_>>();
+		let mut access = map.write().await;
+		assert_eq!(sender_keys(&access, 1), vec![0xb, 0xa]);
+		assert_eq!(sender_keys(&access, 2), vec![0xc, 0xd]);
+		assert_eq!(
+			access.evictable_items().map(|(key, _)| *key).collect::<Vec<_>>(),
+			vec![0xc, 0xb, 0xa]
+		);
+
+		// Replacing an item re-indexes it.
+		access.insert(0xb, TestItem::with_slot(2, 2, 40));
+		assert_eq!(sender_keys(&access, 1), vec![0xa]);
+		assert_eq!(sender_keys(&access, 2), vec![0xc, 0xd, 0xb]);
+		assert_eq!(
+			access.evictable_items().map(|(key, _)| *key).collect::<Vec<_>>(),
+			vec![0xc, 0xa, 0xb]
+		);
+
+		access.remove(&0xa);
+		access.remove(&0xc);
+		assert!(sender_keys(&access, 1).is_empty());
+		assert_eq!(sender_keys(&access, 2), vec![0xd, 0xb]);
+		assert_eq!(access.evictable_items().map(|(key, _)| *key).collect::<Vec<_>>(), vec![0xb]);
+		assert!(!access.inner_guard.items_by_sender.contains_key([1u8].as_slice()));
+	}
 }
@@ -448,6 +448,21 @@ where
 			.unwrap_or_default()
 	}
 
+	/// Returns the hashes of the active views containing each transaction, either in the ready or
+	/// in the future queue.
+	pub(super) fn transaction_views(&self) -> HashMap<ExtrinsicHash<ChainApi>, Vec<Block::Hash>> {
+		let mut transaction_views = HashMap::<_, Vec<_>>::new();
+		for (block_hash, view) in self.active_views.read().iter() {
+			let pool = view.pool.validated_pool().pool.read();
+			let ready = pool.ready().map(|tx| tx.hash);
+			let future = pool.futures().map(|tx| tx.hash);
+			for tx_hash in ready.chain(future) {
+				transaction_views.entry(tx_hash).or_default().push(*block_hash);
+			}
+		}
+		transaction_views
+	}
+
 	/// Returns a list of future transactions in the view at given block hash.
 	pub(super) fn futures_at(
 		&self,
@@ -727,7 +742,9 @@ where
 			)) => {
 				remove_from_view.push(hash);
 			},
-			_ => {
+			e => {
+				let error = e.map_or_else(|| "reported invalid".into(), |e| format!("{e:?}"));
+				self.dropped_stream_controller.note_invalid(hash, error);
 				remove_from_pool.push(hash);
 			},
 		});
@@ -91,6 +91,10 @@ pub struct TimedTransactionSource {
 
 	/// The time at which the transaction was submitted.
 	pub timestamp: Option<Instant>,
+
+	/// Was the transaction explicitly submitted to the local lane, see
+	/// [`crate::LocalLaneOptions`].
+	pub local_lane: bool,
 }
 
 impl From<TimedTransactionSource> for TransactionSource {
@@ -103,21 +107,31 @@ impl TimedTransactionSource {
 	/// Creates a new instance with an internal `TransactionSource::InBlock` source and an optional
 	/// timestamp.
 	pub fn new_in_block(with_timestamp: bool) -> Self {
-		Self { source: TransactionSource::InBlock, timestamp: with_timestamp.then(Instant::now) }
+		Self::from_transaction_source(TransactionSource::InBlock, with_timestamp)
 	}
 	/// Creates a new instance with an internal `TransactionSource::External` source and an optional
 	/// timestamp.
 	pub fn new_external(with_timestamp: bool) -> Self {
-		Self { source: TransactionSource::External, timestamp: with_timestamp.then(Instant::now) }
+		Self::from_transaction_source(TransactionSource::External, with_timestamp)
 	}
 	/// Creates a new instance with an internal `TransactionSource::Local` source and an optional
 	/// timestamp.
 	pub fn new_local(with_timestamp: bool) -> Self {
-		Self { source: TransactionSource::Local, timestamp: with_timestamp.then(Instant::now) }
+		Self::from_transaction_source(TransactionSource::Local, with_timestamp)
 	}
 	/// Creates a new instance with an given source and an optional timestamp.
 	pub fn from_transaction_source(source: TransactionSource, with_timestamp: bool) -> Self {
-		Self { source, timestamp: with_timestamp.then(Instant::now) }
+		Self { source, timestamp: with_timestamp.then(Instant::now), local_lane: false }
+	}
+	/// Sets whether the transaction was explicitly submitted to the local lane.
+	pub fn with_local_lane(mut self, local_lane: bool) -> Self {
+		self.local_lane = local_lane;
+		self
+	}
+	/// Returns `true` if the transaction belongs to the local lane, i.e. if it was submitted by
+	/// the node itself or explicitly submitted to the local lane.
+	pub fn is_local_lane(&self) -> bool {
+		self.local_lane || self.source == TransactionSource::Local
 	}
 }
 
@@ -460,18 +474,33 @@ impl<Hash: hash::Hash + Member + Serialize, Ex: std::fmt::Debug> BasePool<Hash,
 	/// them. Technically the worst transaction should be evaluated by computing the entire pending
 	/// set. We use a simplified approach to remove transactions with the lowest priority first or
 	/// those that occupy the pool for the longest time in case priority is the same.
+	///
+	/// If the `local` limit is given, the transactions of the local lane are never chosen as the
+	/// worst ones as long as they stay within it. Once the local lane exceeds its own limit, its
+	/// transactions are treated like any other.
 	pub fn enforce_limits(
 		&mut self,
 		ready: &Limit,
 		future: &Limit,
+		local: Option<&Limit>,
 	) -> Vec<Arc<Transaction<Hash, Ex>>> {
 		let mut removed = vec![];
+		let exempt_local = local.is_some_and(|local| {
+			let (count, bytes) = self.local_lane_usage();
+			!local.is_exceeded(count, bytes)
+		});
+		let is_exempt = |transaction: &Transaction<Hash, Ex>| {
+			exempt_local && transaction.source.is_local_lane()
+		};
 
 		while ready.is_exceeded(self.ready.len(), self.ready.bytes()) {
 			// find the worst transaction
 			let worst =
 				self.ready.fold::<Option<TransactionRef<Hash, Ex>>, _>(None, |worst, current| {
 					let transaction = &current.transaction;
+					if is_exempt(&*transaction.transaction) {
+						return worst
+					}
 					worst
 						.map(|worst| {
 							// Here we don't use `TransactionRef`'s ordering implementation because
@@ -503,6 +532,7 @@ impl<Hash: hash::Hash + Member + Serialize, Ex: std::fmt::Debug> BasePool<Hash,
 		while future.is_exceeded(self.future.len(), self.future.bytes()) {
 			// find the worst transaction
 			let worst = self.future.fold(|worst, current| match worst {
+				_ if is_exempt(&*current.transaction) => worst,
 				None => Some(current.clone()),
 				Some(worst) => Some(
 					match (worst.transaction.source.timestamp, current.transaction.source.timestamp)
@@ -534,6 +564,23 @@ impl<Hash: hash::Hash + Member + Serialize, Ex: std::fmt::Debug> BasePool<Hash,
 		removed
 	}
 
+	/// Returns the number and the total size of the ready and future transactions of the local
+	/// lane.
+	fn local_lane_usage(&self) -> (usize, usize) {
+		let ready = self.ready.fold((0, 0), |(count, bytes), current| {
+			let transaction = &current.transaction.transaction;
+			if transaction.source.is_local_lane() {
+				(count + 1, bytes + transaction.bytes)
+			} else {
+				(count, bytes)
+			}
+		});
+		self.future
+			.all()
+			.filter(|transaction| transaction.source.is_local_lane())
+			.fold(ready, |(count, bytes), transaction| (count + 1, bytes + transaction.bytes))
+	}
+
 	/// Removes all transactions represented by the hashes and all other transactions
 	/// that depend on them.
 	///
@@ -1169,7 +1216,7 @@ mod tests {
 			),
 			"Transaction { \
 hash: 4, priority: 1000, valid_till: 64, bytes: 1, propagate: true, \
-source: TimedTransactionSource { source: TransactionSource::External, timestamp: None }, requires: [03, 02], provides: [04], data: [4]}"
+source: TimedTransactionSource { source: TransactionSource::External, timestamp: None, local_lane: false }, requires: [03, 02], provides: [04], data: [4]}"
 				.to_owned()
 		);
 	}
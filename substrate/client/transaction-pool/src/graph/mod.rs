@@ -38,8 +38,8 @@ pub mod base_pool;
 pub mod watcher;
 
 pub use self::pool::{
-	BlockHash, ChainApi, ExtrinsicFor, ExtrinsicHash, NumberFor, Options, Pool, RawExtrinsicFor,
-	TransactionFor, ValidateTransactionPriority, ValidatedTransactionFor,
+	BlockHash, ChainApi, ExtrinsicFor, ExtrinsicHash, LocalLaneOptions, NumberFor, Options, Pool,
+	RawExtrinsicFor, TransactionFor, ValidateTransactionPriority, ValidatedTransactionFor,
 };
 pub use validated_pool::{
 	BaseSubmitOutcome, EventDispatcher, IsValidator, ValidatedPoolSubmitOutcome,
@@ -19,7 +19,7 @@
 use crate::{common::tracing_log_xt::log_xt_trace, LOG_TARGET};
 use futures::{channel::mpsc::Receiver, Future};
 use indexmap::IndexMap;
-use sc_transaction_pool_api::error;
+use sc_transaction_pool_api::{error, TransactionPriority};
 use sp_blockchain::{HashAndNumber, TreeRoute};
 use sp_runtime::{
 	generic::BlockId,
@@ -160,6 +160,10 @@ pub struct Options {
 	pub reject_future_transactions: bool,
 	/// How long the extrinsic is banned for.
 	pub ban_time: Duration,
+	/// The minimum priority of the transactions accepted to the pool.
+	pub min_priority: TransactionPriority,
+	/// The treatment of the local transactions.
+	pub local_lane: LocalLaneOptions,
 }
 
 impl Default for Options {
@@ -169,6 +173,39 @@ impl Default for Options {
 			future: base::Limit { count: 512, total_bytes: 1 * 1024 * 1024 },
 			reject_future_transactions: false,
 			ban_time: Duration::from_secs(60 * 30),
+			min_priority: 0,
+			local_lane: Default::default(),
+		}
+	}
+}
+
+/// Options of the local transactions lane.
+///
+/// The local lane applies to the transactions submitted by the node itself, i.e. with the
+/// [`TransactionSource::Local`] source, and to the transactions explicitly submitted to it, see
+/// [`sc_transaction_pool_api::TransactionPool::submit_one_to_local_lane`], so they are not crowded
+/// out by the transactions received from the network.
+#[derive(Debug, Clone)]
+pub struct LocalLaneOptions {
+	/// Local transactions are not dropped to enforce the count and size limits of the pool, as
+	/// long as the local lane stays within [`Self::limit`].
+	pub exempt_from_limits: bool,
+	/// The limits of the local lane, counting both its ready and future transactions.
+	pub limit: base::Limit,
+	/// Local transactions are accepted regardless of [`Options::min_priority`].
+	pub exempt_from_min_priority: bool,
+	/// The interval at which the local transactions still in the pool are announced again to the
+	/// network. The rebroadcast is disabled if `None`.
+	pub rebroadcast_interval: Option<Duration>,
+}
+
+impl Default for LocalLaneOptions {
+	fn default() -> Self {
+		Self {
+			exempt_from_limits: false,
+			limit: base::Limit { count: 256, total_bytes: 2 * 1024 * 1024 },
+			exempt_from_min_priority: false,
+			rebroadcast_interval: None,
 		}
 	}
 }
@@ -614,8 +651,11 @@ mod tests {
 	use substrate_test_runtime::{AccountId, ExtrinsicBuilder, Transfer, H256};
 	use substrate_test_runtime_client::Sr25519Keyring::{Alice, Bob};
 
-	const SOURCE: TimedTransactionSource =
-		TimedTransactionSource { source: TransactionSource::External, timestamp: None };
+	const SOURCE: TimedTransactionSource = TimedTransactionSource {
+		source: TransactionSource::External,
+		timestamp: None,
+		local_lane: false,
+	};
 
 	type Pool<Api> = super::Pool<Api, ()>;
 
@@ -936,6 +976,148 @@ mod tests {
 		assert!(!pool.validated_pool.is_banned(&hash2));
 	}
 
+	#[test]
+	fn should_not_limit_local_futures_if_exempt() {
+		sp_tracing::try_init_simple();
+
+		let xt = uxt(Transfer {
+			from: Alice.into(),
+			to: AccountId::from_h256(H256::from_low_u64_be(2)),
+			amount: 5,
+			nonce: 1,
+		});
+
+		// given
+		let limit = Limit { count: 100, total_bytes: xt.encoded_size() };
+
+		let options = Options {
+			ready: limit.clone(),
+			future: limit.clone(),
+			local_lane: LocalLaneOptions { exempt_from_limits: true, ..Default::default() },
+			..Default::default()
+		};
+
+		let api = Arc::new(TestApi::default());
+		let pool = Pool::new_with_staticly_sized_rotator(options, true.into(), api.clone());
+
+		let local = TimedTransactionSource::new_local(false);
+		let hash1 = block_on(pool.submit_one(&api.expect_hash_and_number(0), local, xt.into()))
+			.unwrap()
+			.hash();
+		assert_eq!(pool.validated_pool().status().future, 1);
+
+		// when
+		let hash2 = block_on(
+			pool.submit_one(
+				&api.expect_hash_and_number(0),
+				SOURCE,
+				uxt(Transfer {
+					from: Bob.into(),
+					to: AccountId::from_h256(H256::from_low_u64_be(2)),
+					amount: 5,
+					nonce: 10,
+				})
+				.into(),
+			),
+		)
+		.map(|o| o.hash());
+
+		// then
+		assert!(hash2.is_err());
+		assert_eq!(pool.validated_pool().status().future, 1);
+		assert!(!pool.validated_pool.is_banned(&hash1));
+	}
+
+	#[test]
+	fn should_limit_local_futures_beyond_local_lane_limit() {
+		sp_tracing::try_init_simple();
+
+		let xt = uxt(Transfer {
+			from: Alice.into(),
+			to: AccountId::from_h256(H256::from_low_u64_be(2)),
+			amount: 5,
+			nonce: 1,
+		});
+
+		// given
+		let limit = Limit { count: 100, total_bytes: xt.encoded_size() };
+
+		let options = Options {
+			ready: limit.clone(),
+			future: limit.clone(),
+			local_lane: LocalLaneOptions {
+				exempt_from_limits: true,
+				limit: Limit { count: 100, total_bytes: xt.encoded_size() - 1 },
+				..Default::default()
+			},
+			..Default::default()
+		};
+
+		let api = Arc::new(TestApi::default());
+		let pool = Pool::new_with_staticly_sized_rotator(options, true.into(), api.clone());
+
+		let local = TimedTransactionSource::new_external(false).with_local_lane(true);
+		let hash1 = block_on(pool.submit_one(&api.expect_hash_and_number(0), local, xt.into()))
+			.unwrap()
+			.hash();
+		assert_eq!(pool.validated_pool().status().future, 1);
+
+		// when
+		let hash2 = block_on(
+			pool.submit_one(
+				&api.expect_hash_and_number(0),
+				SOURCE,
+				uxt(Transfer {
+					from: Bob.into(),
+					to: AccountId::from_h256(H256::from_low_u64_be(2)),
+					amount: 5,
+					nonce: 10,
+				})
+				.into(),
+			),
+		)
+		.map(|o| o.hash());
+
+		// then
+		// The local lane exceeds its own limit, so the older local transaction is dropped.
+		assert!(hash2.is_ok());
+		assert_eq!(pool.validated_pool().status().future, 1);
+		assert!(pool.validated_pool.is_banned(&hash1));
+	}
+
+	#[test]
+	fn should_reject_transactions_below_min_priority() {
+		// given
+		let options = Options {
+			min_priority: 5,
+			local_lane: LocalLaneOptions { exempt_from_min_priority: true, ..Default::default() },
+			..Default::default()
+		};
+		let api = Arc::new(TestApi::default());
+		let pool = Pool::new_with_staticly_sized_rotator(options, true.into(), api.clone());
+		let xt = |nonce| {
+			uxt(Transfer {
+				from: Alice.into(),
+				to: AccountId::from_h256(H256::from_low_u64_be(2)),
+				amount: 5,
+				nonce,
+			})
+		};
+
+		// when
+		let external =
+			block_on(pool.submit_one(&api.expect_hash_and_number(0), SOURCE, xt(0).into()))
+				.map(|o| o.hash());
+		let local = TimedTransactionSource::new_local(false);
+		let local = block_on(pool.submit_one(&api.expect_hash_and_number(0), local, xt(1).into()))
+			.map(|o| o.hash());
+
+		// then
+		assert_matches!(external.unwrap_err(), error::Error::TooLowPriority { old: 5, new: 4 });
+		assert!(local.is_ok());
+		assert_eq!(pool.validated_pool().status().future, 1);
+	}
+
 	#[test]
 	fn should_error_if_reject_immediately() {
 		// given
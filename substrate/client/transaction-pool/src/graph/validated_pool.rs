@@ -342,6 +342,16 @@ impl<B: ChainApi, L: EventHandler<B>> ValidatedPool<B, L> {
 					return Err(error::Error::Unactionable.into())
 				}
 
+				let exempt_from_min_priority =
+					self.options.local_lane.exempt_from_min_priority && tx.source.is_local_lane();
+				if priority < self.options.min_priority && !exempt_from_min_priority {
+					return Err(error::Error::TooLowPriority {
+						old: self.options.min_priority,
+						new: priority,
+					}
+					.into())
+				}
+
 				let imported = self.pool.write().import(tx)?;
 
 				if let base::Imported::Ready { ref hash, .. } = imported {
@@ -393,6 +403,7 @@ impl<B: ChainApi, L: EventHandler<B>> ValidatedPool<B, L> {
 		let status = self.pool.read().status();
 		let ready_limit = &self.options.ready;
 		let future_limit = &self.options.future;
+		let local_lane = &self.options.local_lane;
 
 		if ready_limit.is_exceeded(status.ready, status.ready_bytes) ||
 			future_limit.is_exceeded(status.future, status.future_bytes)
@@ -410,7 +421,11 @@ impl<B: ChainApi, L: EventHandler<B>> ValidatedPool<B, L> {
 			let removed = {
 				let mut pool = self.pool.write();
 				let removed = pool
-					.enforce_limits(ready_limit, future_limit)
+					.enforce_limits(
+						ready_limit,
+						future_limit,
+						local_lane.exempt_from_limits.then_some(&local_lane.limit),
+					)
 					.into_iter()
 					.map(|x| x.hash)
 					.collect::<HashSet<_>>();
@@ -752,6 +767,16 @@ impl<B: ChainApi, L: EventHandler<B>> ValidatedPool<B, L> {
 		stream
 	}
 
+	/// Returns the hashes of the ready transactions of the local lane.
+	pub fn ready_local_lane(&self) -> Vec<ExtrinsicHash<B>> {
+		self.pool
+			.read()
+			.ready()
+			.filter(|tx| tx.source.is_local_lane())
+			.map(|tx| tx.hash)
+			.collect()
+	}
+
 	/// Invoked when extrinsics are broadcasted.
 	pub fn on_broadcasted(&self, propagated: HashMap<ExtrinsicHash<B>, Vec<String>>) {
 		let mut event_dispatcher = self.event_dispatcher.write();
@@ -34,11 +34,11 @@ use std::sync::Arc;
 
 pub use api::FullChainApi;
 pub use builder::{Builder, TransactionPoolHandle, TransactionPoolOptions, TransactionPoolType};
-pub use common::notification_future;
-pub use fork_aware_txpool::{ForkAwareTxPool, ForkAwareTxPoolTask};
+pub use common::{journal::JournalOptions, notification_future};
+pub use fork_aware_txpool::{ForkAwareTxPool, ForkAwareTxPoolTask, TxMemPoolPolicy, TxSlot};
 pub use graph::{
 	base_pool::{Limit as PoolLimit, TimedTransactionSource},
-	ChainApi, Options, Pool, ValidateTransactionPriority,
+	ChainApi, LocalLaneOptions, Options, Pool, ValidateTransactionPriority,
 };
 use single_state_txpool::prune_known_txs_for_block;
 pub use single_state_txpool::{BasicPool, RevalidationType};
@@ -27,6 +27,7 @@ use crate::{
 	common::{
 		enactment_state::{EnactmentAction, EnactmentState},
 		error,
+		local_lane::LocalRebroadcast,
 		tracing_log_xt::log_xt_trace,
 	},
 	graph::{
@@ -75,6 +76,7 @@ where
 	ready_poll: Arc<Mutex<ReadyPoll<ReadyIteratorFor<PoolApi>, Block>>>,
 	metrics: PrometheusMetrics,
 	enactment_state: Arc<Mutex<EnactmentState<Block>>>,
+	local_rebroadcast: Arc<LocalRebroadcast>,
 }
 
 struct ReadyPoll<T, Block: BlockT> {
@@ -152,6 +154,8 @@ where
 		finalized_hash: Block::Hash,
 		options: graph::Options,
 	) -> (Self, Pin<Box<dyn Future<Output = ()> + Send>>) {
+		let local_rebroadcast =
+			Arc::new(LocalRebroadcast::new(options.local_lane.rebroadcast_interval));
 		let pool = Arc::new(graph::Pool::new_with_staticly_sized_rotator(
 			options,
 			true.into(),
@@ -174,6 +178,7 @@ where
 					best_block_hash,
 					finalized_hash,
 				))),
+				local_rebroadcast,
 			},
 			background_task,
 		)
@@ -192,6 +197,8 @@ where
 		best_block_hash: Block::Hash,
 		finalized_hash: Block::Hash,
 	) -> Self {
+		let local_rebroadcast =
+			Arc::new(LocalRebroadcast::new(options.local_lane.rebroadcast_interval));
 		let pool = Arc::new(graph::Pool::new_with_staticly_sized_rotator(
 			options,
 			is_validator,
@@ -229,6 +236,7 @@ where
 				best_block_hash,
 				finalized_hash,
 			))),
+			local_rebroadcast,
 		}
 	}
 
@@ -252,6 +260,43 @@ where
 			_ = futures_timer::Delay::new(timeout)=> self.ready()
 		}
 	}
+
+	/// Refer to [`TransactionPool::submit_one`].
+	async fn submit_one_with_source(
+		&self,
+		at: Block::Hash,
+		source: TimedTransactionSource,
+		xt: graph::RawExtrinsicFor<PoolApi>,
+	) -> Result<graph::ExtrinsicHash<PoolApi>, PoolApi::Error> {
+		let pool = self.pool.clone();
+		let xt = Arc::from(xt);
+
+		self.metrics.report(|metrics| metrics.submitted_transactions.inc());
+
+		let number = self.api.resolve_block_number(at);
+		let at = HashAndNumber { hash: at, number: number? };
+		pool.submit_one(&at, source, xt).await.map(|outcome| outcome.hash())
+	}
+
+	/// Refer to [`TransactionPool::submit_and_watch`].
+	async fn submit_and_watch_with_source(
+		&self,
+		at: Block::Hash,
+		source: TimedTransactionSource,
+		xt: graph::RawExtrinsicFor<PoolApi>,
+	) -> Result<Pin<Box<TransactionStatusStreamFor<Self>>>, PoolApi::Error> {
+		let pool = self.pool.clone();
+		let xt = Arc::from(xt);
+
+		self.metrics.report(|metrics| metrics.submitted_transactions.inc());
+
+		let number = self.api.resolve_block_number(at);
+
+		let at = HashAndNumber { hash: at, number: number? };
+		pool.submit_and_watch(&at, source, xt)
+			.await
+			.map(|mut outcome| outcome.expect_watcher().into_stream().boxed())
+	}
 }
 
 #[async_trait]
@@ -299,16 +344,8 @@ where
 		source: TransactionSource,
 		xt: TransactionFor<Self>,
 	) -> Result<TxHash<Self>, Self::Error> {
-		let pool = self.pool.clone();
-		let xt = Arc::from(xt);
-
-		self.metrics.report(|metrics| metrics.submitted_transactions.inc());
-
-		let number = self.api.resolve_block_number(at);
-		let at = HashAndNumber { hash: at, number: number? };
-		pool.submit_one(&at, TimedTransactionSource::from_transaction_source(source, false), xt)
-			.await
-			.map(|outcome| outcome.hash())
+		let source = TimedTransactionSource::from_transaction_source(source, false);
+		self.submit_one_with_source(at, source, xt).await
 	}
 
 	async fn submit_and_watch(
@@ -317,21 +354,30 @@ where
 		source: TransactionSource,
 		xt: TransactionFor<Self>,
 	) -> Result<Pin<Box<TransactionStatusStreamFor<Self>>>, Self::Error> {
-		let pool = self.pool.clone();
-		let xt = Arc::from(xt);
-
-		self.metrics.report(|metrics| metrics.submitted_transactions.inc());
+		let source = TimedTransactionSource::from_transaction_source(source, false);
+		self.submit_and_watch_with_source(at, source, xt).await
+	}
 
-		let number = self.api.resolve_block_number(at);
+	async fn submit_one_to_local_lane(
+		&self,
+		at: <Self::Block as BlockT>::Hash,
+		source: TransactionSource,
+		xt: TransactionFor<Self>,
+	) -> Result<TxHash<Self>, Self::Error> {
+		let source =
+			TimedTransactionSource::from_transaction_source(source, false).with_local_lane(true);
+		self.submit_one_with_source(at, source, xt).await
+	}
 
-		let at = HashAndNumber { hash: at, number: number? };
-		pool.submit_and_watch(
-			&at,
-			TimedTransactionSource::from_transaction_source(source, false),
-			xt,
-		)
-		.await
-		.map(|mut outcome| outcome.expect_watcher().into_stream().boxed())
+	async fn submit_and_watch_to_local_lane(
+		&self,
+		at: <Self::Block as BlockT>::Hash,
+		source: TransactionSource,
+		xt: TransactionFor<Self>,
+	) -> Result<Pin<Box<TransactionStatusStreamFor<Self>>>, Self::Error> {
+		let source =
+			TimedTransactionSource::from_transaction_source(source, false).with_local_lane(true);
+		self.submit_and_watch_with_source(at, source, xt).await
 	}
 
 	async fn report_invalid(
@@ -358,6 +404,15 @@ where
 		self.pool.hash_of(xt)
 	}
 
+	fn transactions_to_rebroadcast(&self) -> Vec<TxHash<Self>> {
+		if !self.local_rebroadcast.is_due() {
+			return Vec::new()
+		}
+		let local = self.pool.validated_pool().ready_local_lane();
+		trace!(target: LOG_TARGET, count = local.len(), "rebroadcasting local transactions");
+		local
+	}
+
 	fn on_broadcasted(&self, propagations: HashMap<TxHash<Self>, Vec<String>>) {
 		self.pool.validated_pool().on_broadcasted(propagations)
 	}
@@ -416,6 +471,16 @@ where
 	) -> ReadyIteratorFor<PoolApi> {
 		self.ready_at_with_timeout_internal(at, timeout).await
 	}
+
+	/// Returns the recent best block for every transaction, the single state of the pool is kept
+	/// at this block.
+	fn transaction_views(&self) -> HashMap<TxHash<Self>, Vec<<Self::Block as BlockT>::Hash>> {
+		let best = self.enactment_state.lock().recent_best_block();
+		let pool = self.pool.validated_pool().pool.read();
+		let ready = pool.ready().map(|tx| tx.hash);
+		let future = pool.futures().map(|tx| tx.hash);
+		ready.chain(future).map(|tx_hash| (tx_hash, vec![best])).collect()
+	}
 }
 
 impl<Block, Client> BasicPool<FullChainApi<Client, Block>, Block>
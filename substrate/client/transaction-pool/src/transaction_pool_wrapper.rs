@@ -21,23 +21,32 @@
 
 use crate::{
 	builder::FullClientTransactionPool,
+	common::journal::{Journal, PoolTransaction},
 	graph::{base_pool::Transaction, ExtrinsicFor, ExtrinsicHash},
-	ChainApi, FullChainApi, ReadyIteratorFor,
+	ChainApi, FullChainApi, ReadyIteratorFor, LOG_TARGET,
 };
 use async_trait::async_trait;
+use codec::{Decode, Encode};
 use sc_transaction_pool_api::{
 	ChainEvent, ImportNotificationStream, LocalTransactionFor, LocalTransactionPool,
-	MaintainedTransactionPool, PoolStatus, ReadyTransactions, TransactionFor, TransactionPool,
-	TransactionSource, TransactionStatusStreamFor, TxHash, TxInvalidityReportMap,
+	MaintainedTransactionPool, PoolStatus, ReadyTransactions, RecentlyDroppedTransaction,
+	TransactionFor, TransactionPool, TransactionSource, TransactionStatusStreamFor, TxHash,
+	TxInvalidityReportMap,
 };
 use sp_runtime::traits::Block as BlockT;
 use std::{collections::HashMap, pin::Pin, sync::Arc};
+use tracing::{debug, warn};
 
 /// The wrapper for actual object providing implementation of TransactionPool.
 ///
 /// This wraps actual implementation of the TransactionPool, e.g. fork-aware or single-state.
+///
+/// The optional journal persisting the transactions of the pool across restarts is maintained by
+/// the wrapper, so it works with any implementation. It is flushed when the wrapper is dropped on
+/// shutdown, on a blocking task of the runtime if there is one.
 pub struct TransactionPoolWrapper<Block, Client>(
 	pub Box<dyn FullClientTransactionPool<Block, Client>>,
+	Option<Arc<Journal<Block::Hash>>>,
 )
 where
 	Block: BlockT,
@@ -49,6 +58,124 @@ where
 		+ 'static,
 	Client::Api: sp_transaction_pool::runtime_api::TaggedTransactionQueue<Block>;
 
+impl<Block, Client> TransactionPoolWrapper<Block, Client>
+where
+	Block: BlockT,
+	Client: sp_api::ProvideRuntimeApi<Block>
+		+ sc_client_api::BlockBackend<Block>
+		+ sc_client_api::blockchain::HeaderBackend<Block>
+		+ sp_runtime::traits::BlockIdTo<Block>
+		+ sp_blockchain::HeaderMetadata<Block, Error = sp_blockchain::Error>
+		+ 'static,
+	Client::Api: sp_transaction_pool::runtime_api::TaggedTransactionQueue<Block>,
+{
+	/// Wraps the given transaction pool, with an optional journal.
+	pub(crate) fn new(
+		pool: Box<dyn FullClientTransactionPool<Block, Client>>,
+		journal: Option<Journal<Block::Hash>>,
+	) -> Self {
+		Self(pool, journal.map(Arc::new))
+	}
+
+	/// Restores the journaled transactions at the first notified block, and then periodically
+	/// rewrites the journal with the ready and future transactions of the pool.
+	async fn maintain_journal(&self, at: Block::Hash) {
+		let Some(journal) = self.1.clone() else { return };
+
+		if let Some(restored) = journal.restore() {
+			for source in [TransactionSource::Local, TransactionSource::External] {
+				let xts = restored
+					.iter()
+					.filter(|(tx_source, _)| *tx_source == source)
+					.filter_map(|(_, xt)| Block::Extrinsic::decode(&mut &xt[..]).ok())
+					.collect::<Vec<_>>();
+				if xts.is_empty() {
+					continue
+				}
+
+				let count = xts.len();
+				match self.0.submit_at(at, source, xts).await {
+					Ok(results) => debug!(
+						target: LOG_TARGET,
+						?source,
+						count,
+						restored = results.iter().filter(|result| result.is_ok()).count(),
+						"Restored journaled transactions"
+					),
+					Err(error) => warn!(
+						target: LOG_TARGET,
+						?source,
+						?error,
+						"Failed to restore journaled transactions"
+					),
+				}
+			}
+			return
+		}
+
+		if !journal.should_rotate() {
+			return
+		}
+
+		let transactions = self.journal_transactions();
+		match tokio::task::spawn_blocking(move || journal.rotate(transactions)).await {
+			Ok(Ok(_)) => {},
+			Ok(Err(error)) => warn!(target: LOG_TARGET, ?error, "Failed to write txpool journal"),
+			Err(error) => warn!(target: LOG_TARGET, ?error, "txpool journal task failed"),
+		}
+	}
+
+	/// Returns the ready and future transactions of the pool, as they are journaled.
+	fn journal_transactions(&self) -> Vec<PoolTransaction<Block::Hash>> {
+		let to_journal =
+			|tx: &Transaction<Block::Hash, ExtrinsicFor<FullChainApi<Client, Block>>>| {
+				PoolTransaction {
+					hash: tx.hash,
+					source: tx.source.source,
+					priority: tx.priority,
+					extrinsic: tx.data.encode(),
+				}
+			};
+		self.0
+			.ready()
+			.map(|tx| to_journal(&tx))
+			.chain(self.0.futures().iter().map(to_journal))
+			.collect()
+	}
+}
+
+impl<Block, Client> Drop for TransactionPoolWrapper<Block, Client>
+where
+	Block: BlockT,
+	Client: sp_api::ProvideRuntimeApi<Block>
+		+ sc_client_api::BlockBackend<Block>
+		+ sc_client_api::blockchain::HeaderBackend<Block>
+		+ sp_runtime::traits::BlockIdTo<Block>
+		+ sp_blockchain::HeaderMetadata<Block, Error = sp_blockchain::Error>
+		+ 'static,
+	Client::Api: sp_transaction_pool::runtime_api::TaggedTransactionQueue<Block>,
+{
+	fn drop(&mut self) {
+		let Some(journal) = self.1.take() else { return };
+
+		// Transactions received since the last rotation would be lost otherwise.
+		let transactions = self.journal_transactions();
+		let flush = move || match journal.flush(transactions) {
+			Ok(Some(count)) => debug!(target: LOG_TARGET, count, "txpool journal flushed"),
+			Ok(None) => {},
+			Err(error) => warn!(target: LOG_TARGET, ?error, "Failed to flush txpool journal"),
+		};
+
+		// The runtime waits for its blocking tasks when it is shut down, so the flush is not lost.
+		match tokio::runtime::Handle::try_current() {
+			Ok(handle) => {
+				handle.spawn_blocking(flush);
+			},
+			Err(_) => flush(),
+		}
+	}
+}
+
 #[async_trait]
 impl<Block, Client> TransactionPool for TransactionPoolWrapper<Block, Client>
 where
@@ -96,6 +223,24 @@ where
 		self.0.submit_and_watch(at, source, xt).await
 	}
 
+	async fn submit_one_to_local_lane(
+		&self,
+		at: <Self::Block as BlockT>::Hash,
+		source: TransactionSource,
+		xt: TransactionFor<Self>,
+	) -> Result<TxHash<Self>, Self::Error> {
+		self.0.submit_one_to_local_lane(at, source, xt).await
+	}
+
+	async fn submit_and_watch_to_local_lane(
+		&self,
+		at: <Self::Block as BlockT>::Hash,
+		source: TransactionSource,
+		xt: TransactionFor<Self>,
+	) -> Result<Pin<Box<TransactionStatusStreamFor<Self>>>, Self::Error> {
+		self.0.submit_and_watch_to_local_lane(at, source, xt).await
+	}
+
 	async fn ready_at(
 		&self,
 		at: <Self::Block as BlockT>::Hash,
@@ -135,6 +280,10 @@ where
 		self.0.hash_of(xt)
 	}
 
+	fn transactions_to_rebroadcast(&self) -> Vec<TxHash<Self>> {
+		self.0.transactions_to_rebroadcast()
+	}
+
 	fn ready_transaction(&self, hash: &TxHash<Self>) -> Option<Arc<Self::InPoolTransaction>> {
 		self.0.ready_transaction(hash)
 	}
@@ -146,6 +295,14 @@ where
 	) -> ReadyIteratorFor<FullChainApi<Client, Block>> {
 		self.0.ready_at_with_timeout(at, timeout).await
 	}
+
+	fn transaction_views(&self) -> HashMap<TxHash<Self>, Vec<Block::Hash>> {
+		self.0.transaction_views()
+	}
+
+	fn recently_dropped(&self) -> Vec<RecentlyDroppedTransaction<TxHash<Self>>> {
+		self.0.recently_dropped()
+	}
 }
 
 #[async_trait]
@@ -161,7 +318,9 @@ where
 	Client::Api: sp_transaction_pool::runtime_api::TaggedTransactionQueue<Block>,
 {
 	async fn maintain(&self, event: ChainEvent<Self::Block>) {
+		let at = event.hash();
 		self.0.maintain(event).await;
+		self.maintain_journal(at).await;
 	}
 }
 
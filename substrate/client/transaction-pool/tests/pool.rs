@@ -83,8 +83,11 @@ fn create_basic_pool(test_api: TestApi) -> BasicPool<TestApi, Block> {
 	create_basic_pool_with_genesis(Arc::from(test_api)).0
 }
 
-const TSOURCE: TimedTransactionSource =
-	TimedTransactionSource { source: TransactionSource::External, timestamp: None };
+const TSOURCE: TimedTransactionSource = TimedTransactionSource {
+	source: TransactionSource::External,
+	timestamp: None,
+	local_lane: false,
+};
 const SOURCE: TransactionSource = TransactionSource::External;
 
 #[test]
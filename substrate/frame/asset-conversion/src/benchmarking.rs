@@ -134,7 +134,7 @@ fn mint_setup_fee_asset<T: Config>(
 	));
 }
 
-/// Creates a pool for a given asset pair.
+/// Creates a pool with the given `curve` for a given asset pair.
 ///
 /// This action mints the necessary amounts of the given assets for the `caller` to provide initial
 /// liquidity. It returns the LP token ID along with a pair of amounts sufficient for the pool's
@@ -143,6 +143,7 @@ fn create_asset_and_pool<T: Config>(
 	caller: &T::AccountId,
 	asset1: &T::AssetKind,
 	asset2: &T::AssetKind,
+	curve: CurveKind,
 ) -> (T::PoolAssetId, T::Balance, T::Balance)
 where
 	T::Assets: Create<T::AccountId> + Mutate<T::AccountId>,
@@ -157,15 +158,20 @@ where
 
 	mint_setup_fee_asset::<T>(caller, asset1, asset2, &lp_token);
 
-	assert_ok!(AssetConversion::<T>::create_pool(
+	assert_ok!(AssetConversion::<T>::create_pool_with_curve(
 		SystemOrigin::Signed(caller.clone()).into(),
 		Box::new(asset1.clone()),
-		Box::new(asset2.clone())
+		Box::new(asset2.clone()),
+		curve,
 	));
 
 	(lp_token, liquidity1, liquidity2)
 }
 
+/// The curve of the pools swapped through in the swap benchmarks, the costliest to price swaps
+/// with.
+const STABLE_SWAP: CurveKind = CurveKind::StableSwap { amplification: MAX_AMPLIFICATION };
+
 fn assert_last_event<T: Config>(generic_event: <T as Config>::RuntimeEvent) {
 	let events = frame_system::Pallet::<T>::events();
 	let system_event: <T as frame_system::Config>::RuntimeEvent = generic_event.into();
@@ -199,6 +205,87 @@ mod benchmarks {
 		);
 	}
 
+	#[benchmark]
+	fn create_pool_with_curve() {
+		let caller: T::AccountId = whitelisted_caller();
+		let (asset1, asset2) = T::BenchmarkHelper::create_pair(0, 1);
+		create_asset::<T>(&caller, &asset1, T::Assets::minimum_balance(asset1.clone()), true);
+		create_asset::<T>(&caller, &asset2, T::Assets::minimum_balance(asset2.clone()), true);
+
+		let lp_token = AssetConversion::<T>::get_next_pool_asset_id();
+		create_fee_asset::<T>(&caller);
+		mint_setup_fee_asset::<T>(&caller, &asset1, &asset2, &lp_token);
+
+		#[extrinsic_call]
+		_(
+			SystemOrigin::Signed(caller.clone()),
+			Box::new(asset1.clone()),
+			Box::new(asset2.clone()),
+			STABLE_SWAP,
+		);
+
+		let pool_id = T::PoolLocator::pool_id(&asset1, &asset2).unwrap();
+		let curve = PoolCurve::StableSwap(Amplification::constant(
+			MAX_AMPLIFICATION,
+			frame_system::Pallet::<T>::block_number(),
+		));
+		assert_last_event::<T>(Event::PoolCurveSet { pool_id, curve }.into());
+	}
+
+	#[benchmark]
+	fn ramp_amplification() {
+		let caller: T::AccountId = whitelisted_caller();
+		let (asset1, asset2) = T::BenchmarkHelper::create_pair(0, 1);
+
+		create_fee_asset::<T>(&caller);
+		create_asset_and_pool::<T>(&caller, &asset1, &asset2, STABLE_SWAP);
+		let now = frame_system::Pallet::<T>::block_number();
+		let future_block = now + 100u32.into();
+		let future_amplification = MAX_AMPLIFICATION / MAX_AMPLIFICATION_CHANGE;
+
+		#[extrinsic_call]
+		_(
+			SystemOrigin::Root,
+			Box::new(asset1.clone()),
+			Box::new(asset2.clone()),
+			future_amplification,
+			future_block,
+		);
+
+		let pool_id = T::PoolLocator::pool_id(&asset1, &asset2).unwrap();
+		let amplification = Amplification {
+			initial: MAX_AMPLIFICATION,
+			future: future_amplification,
+			initial_block: now,
+			future_block,
+		};
+		assert_last_event::<T>(Event::AmplificationRampStarted { pool_id, amplification }.into());
+	}
+
+	#[benchmark]
+	fn stop_ramp_amplification() {
+		let caller: T::AccountId = whitelisted_caller();
+		let (asset1, asset2) = T::BenchmarkHelper::create_pair(0, 1);
+
+		create_fee_asset::<T>(&caller);
+		create_asset_and_pool::<T>(&caller, &asset1, &asset2, STABLE_SWAP);
+		assert_ok!(AssetConversion::<T>::ramp_amplification(
+			SystemOrigin::Root.into(),
+			Box::new(asset1.clone()),
+			Box::new(asset2.clone()),
+			MAX_AMPLIFICATION / MAX_AMPLIFICATION_CHANGE,
+			frame_system::Pallet::<T>::block_number() + 100u32.into(),
+		));
+
+		#[extrinsic_call]
+		_(SystemOrigin::Root, Box::new(asset1.clone()), Box::new(asset2.clone()));
+
+		let pool_id = T::PoolLocator::pool_id(&asset1, &asset2).unwrap();
+		assert_last_event::<T>(
+			Event::AmplificationRampStopped { pool_id, amplification: MAX_AMPLIFICATION }.into(),
+		);
+	}
+
 	#[benchmark]
 	fn add_liquidity() {
 		let caller: T::AccountId = whitelisted_caller();
@@ -206,7 +293,7 @@ mod benchmarks {
 
 		create_fee_asset::<T>(&caller);
 		let (lp_token, liquidity1, liquidity2) =
-			create_asset_and_pool::<T>(&caller, &asset1, &asset2);
+			create_asset_and_pool::<T>(&caller, &asset1, &asset2, CurveKind::ConstantProduct);
 
 		#[extrinsic_call]
 		_(
@@ -235,7 +322,7 @@ mod benchmarks {
 
 		create_fee_asset::<T>(&caller);
 		let (lp_token, liquidity1, liquidity2) =
-			create_asset_and_pool::<T>(&caller, &asset1, &asset2);
+			create_asset_and_pool::<T>(&caller, &asset1, &asset2, CurveKind::ConstantProduct);
 
 		let remove_lp_amount = T::Balance::one();
 
@@ -269,7 +356,8 @@ mod benchmarks {
 
 	#[benchmark]
 	fn swap_exact_tokens_for_tokens(n: Linear<2, { T::MaxSwapPathLength::get() }>) {
-		let mut swap_amount = T::Balance::one();
+		// StableSwap pools round every hop down, leave some output after the last one.
+		let mut swap_amount = T::Balance::from(10u32);
 		let mut path = vec![];
 
 		let caller: T::AccountId = whitelisted_caller();
@@ -283,7 +371,8 @@ mod benchmarks {
 				path.push(Box::new(asset2.clone()));
 			}
 
-			let (_, liquidity1, liquidity2) = create_asset_and_pool::<T>(&caller, &asset1, &asset2);
+			let (_, liquidity1, liquidity2) =
+				create_asset_and_pool::<T>(&caller, &asset1, &asset2, STABLE_SWAP);
 
 			assert_ok!(AssetConversion::<T>::add_liquidity(
 				SystemOrigin::Signed(caller.clone()).into(),
@@ -321,7 +410,8 @@ mod benchmarks {
 
 	#[benchmark]
 	fn swap_tokens_for_exact_tokens(n: Linear<2, { T::MaxSwapPathLength::get() }>) {
-		let mut max_swap_amount = T::Balance::one();
+		// StableSwap pools round every hop up, leave room for it in the first one.
+		let mut max_swap_amount = T::Balance::from(10u32);
 		let mut path = vec![];
 
 		let caller: T::AccountId = whitelisted_caller();
@@ -335,7 +425,8 @@ mod benchmarks {
 				path.push(Box::new(asset2.clone()));
 			}
 
-			let (_, liquidity1, liquidity2) = create_asset_and_pool::<T>(&caller, &asset1, &asset2);
+			let (_, liquidity1, liquidity2) =
+				create_asset_and_pool::<T>(&caller, &asset1, &asset2, STABLE_SWAP);
 
 			assert_ok!(AssetConversion::<T>::add_liquidity(
 				SystemOrigin::Signed(caller.clone()).into(),
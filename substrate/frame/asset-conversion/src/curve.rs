@@ -0,0 +1,503 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! Invariant curves determining the price of the swaps in a pool.
+//!
+//! A pool picks its curve when it is created:
+//! - [`ConstantProduct`], the Uniswap V2 `x * y = k` invariant, suits any pair of assets;
+//! - [`StableSwap`], the Curve Finance invariant, concentrates the liquidity around a 1:1 price and
+//!   suits pairs of pegged assets with the same number of decimals, like two stablecoins.
+
+use super::*;
+use codec::{Decode, DecodeWithMemTracking, Encode, MaxEncodedLen};
+use scale_info::TypeInfo;
+use sp_arithmetic::traits::Unsigned;
+use sp_runtime::{traits::AtLeast32BitUnsigned, RuntimeDebug, SaturatedConversion};
+
+/// The highest amplification coefficient of a [`StableSwap`] pool.
+pub const MAX_AMPLIFICATION: u32 = 1_000_000;
+
+/// The factor by which a single ramp may change the amplification coefficient at most.
+pub const MAX_AMPLIFICATION_CHANGE: u32 = 10;
+
+/// The most iterations used to compute the [`StableSwap`] invariant and reserves.
+const MAX_ITERATIONS: u32 = 255;
+
+/// Arithmetic the curves are computed with, see [`Config::HigherPrecisionBalance`].
+pub trait CurveBalance: Ensure + Unsigned + From<u32> {}
+impl<T: Ensure + Unsigned + From<u32>> CurveBalance for T {}
+
+/// Errors of the curve computations.
+#[derive(Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub enum CurveError {
+	/// One of the reserves is empty.
+	ZeroLiquidity,
+	/// The requested amount can't be paid out of the reserve.
+	AmountOutTooHigh,
+	/// An overflow happened.
+	Overflow,
+	/// The iterative computation of the invariant didn't converge.
+	NotConverged,
+}
+
+/// The invariant a pool keeps between its reserves.
+///
+/// The `fee` taken by the liquidity providers is given in tenths of a percent, as
+/// [`Config::LPFee`].
+pub trait SwapCurve<Balance> {
+	/// Given an input amount of an asset and pair reserves, returns the maximum output amount of
+	/// the other asset.
+	fn amount_out(
+		&self,
+		amount_in: Balance,
+		reserve_in: Balance,
+		reserve_out: Balance,
+		fee: u32,
+	) -> Result<Balance, CurveError>;
+
+	/// Given an output amount of an asset and pair reserves, returns a required input amount of
+	/// the other asset.
+	fn amount_in(
+		&self,
+		amount_out: Balance,
+		reserve_in: Balance,
+		reserve_out: Balance,
+		fee: u32,
+	) -> Result<Balance, CurveError>;
+
+	/// Returns the amount of the second asset worth `amount` of the first one at the current
+	/// price, without fees.
+	fn quote(
+		&self,
+		amount: Balance,
+		reserve1: Balance,
+		reserve2: Balance,
+	) -> Result<Balance, CurveError>;
+
+	/// Returns the amount of the first asset needed to get `amount_out` of the second one at the
+	/// current price, without fees.
+	fn quote_in(
+		&self,
+		amount_out: Balance,
+		reserve_in: Balance,
+		reserve_out: Balance,
+	) -> Result<Balance, CurveError>;
+
+	/// Returns the amount of the second asset worth `amount` of the first one at the marginal
+	/// price of the pool, the price of an infinitesimal swap without fees.
+	fn spot_quote(
+		&self,
+		amount: Balance,
+		reserve1: Balance,
+		reserve2: Balance,
+	) -> Result<Balance, CurveError>;
+}
+
+/// The constant product `x * y = k` invariant of Uniswap V2.
+#[derive(Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub struct ConstantProduct;
+
+impl<B: CurveBalance> SwapCurve<B> for ConstantProduct {
+	fn amount_out(
+		&self,
+		amount_in: B,
+		reserve_in: B,
+		reserve_out: B,
+		fee: u32,
+	) -> Result<B, CurveError> {
+		if reserve_in.is_zero() || reserve_out.is_zero() {
+			return Err(CurveError::ZeroLiquidity)
+		}
+
+		let amount_in_with_fee =
+			amount_in.checked_mul(&without_fee(fee)?).ok_or(CurveError::Overflow)?;
+
+		let numerator = amount_in_with_fee.checked_mul(&reserve_out).ok_or(CurveError::Overflow)?;
+
+		let denominator = reserve_in
+			.checked_mul(&1000u32.into())
+			.ok_or(CurveError::Overflow)?
+			.checked_add(&amount_in_with_fee)
+			.ok_or(CurveError::Overflow)?;
+
+		numerator.checked_div(&denominator).ok_or(CurveError::Overflow)
+	}
+
+	fn amount_in(
+		&self,
+		amount_out: B,
+		reserve_in: B,
+		reserve_out: B,
+		fee: u32,
+	) -> Result<B, CurveError> {
+		if reserve_in.is_zero() || reserve_out.is_zero() {
+			return Err(CurveError::ZeroLiquidity)
+		}
+
+		if amount_out >= reserve_out {
+			return Err(CurveError::AmountOutTooHigh)
+		}
+
+		let numerator = reserve_in
+			.checked_mul(&amount_out)
+			.ok_or(CurveError::Overflow)?
+			.checked_mul(&1000u32.into())
+			.ok_or(CurveError::Overflow)?;
+
+		let denominator = reserve_out
+			.checked_sub(&amount_out)
+			.ok_or(CurveError::Overflow)?
+			.checked_mul(&without_fee(fee)?)
+			.ok_or(CurveError::Overflow)?;
+
+		numerator
+			.checked_div(&denominator)
+			.ok_or(CurveError::Overflow)?
+			.checked_add(&One::one())
+			.ok_or(CurveError::Overflow)
+	}
+
+	fn quote(&self, amount: B, reserve1: B, reserve2: B) -> Result<B, CurveError> {
+		// (amount * reserve2) / reserve1
+		mul_div(&amount, &reserve2, &reserve1)
+	}
+
+	/// The price doesn't depend on the direction of the swap.
+	fn quote_in(&self, amount_out: B, reserve_in: B, reserve_out: B) -> Result<B, CurveError> {
+		self.quote(amount_out, reserve_out, reserve_in)
+	}
+
+	fn spot_quote(&self, amount: B, reserve1: B, reserve2: B) -> Result<B, CurveError> {
+		if reserve1.is_zero() || reserve2.is_zero() {
+			return Err(CurveError::ZeroLiquidity)
+		}
+		self.quote(amount, reserve1, reserve2)
+	}
+}
+
+/// The StableSwap invariant of Curve Finance for two assets.
+///
+/// `A * 4 * (x + y) + D = A * 4 * D + D^3 / (4 * x * y)`
+///
+/// The higher the amplification coefficient `A`, the flatter the curve is around the 1:1 price,
+/// and the lower the price impact of the swaps keeping the pool close to balance.
+#[derive(Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub struct StableSwap {
+	/// The amplification coefficient `A`.
+	pub amplification: u32,
+}
+
+impl StableSwap {
+	/// `A * n^n` for the two assets of a pool.
+	fn ann<B: CurveBalance>(&self) -> Result<B, CurveError> {
+		B::from(self.amplification)
+			.checked_mul(&4u32.into())
+			.ok_or(CurveError::Overflow)
+	}
+
+	/// Computes the invariant `D` of the reserves `x` and `y` with Newton's method.
+	pub fn invariant<B: CurveBalance>(&self, x: &B, y: &B) -> Result<B, CurveError> {
+		let sum = x.checked_add(y).ok_or(CurveError::Overflow)?;
+		if sum.is_zero() {
+			return Ok(Zero::zero())
+		}
+
+		let ann = self.ann::<B>()?;
+		let two = B::from(2u32);
+		let x2 = x.checked_mul(&two).ok_or(CurveError::Overflow)?;
+		let y2 = y.checked_mul(&two).ok_or(CurveError::Overflow)?;
+		let ann_sum = ann.checked_mul(&sum).ok_or(CurveError::Overflow)?;
+		let ann_minus_one = ann.checked_sub(&One::one()).ok_or(CurveError::Overflow)?;
+
+		let mut d = sum;
+		for _ in 0..MAX_ITERATIONS {
+			// D^3 / (4 * x * y)
+			let d_p = mul_div(&mul_div(&d, &d, &x2)?, &d, &y2)?;
+			// (Ann * S + 2 * D_P) * D / ((Ann - 1) * D + 3 * D_P)
+			let numerator = d_p
+				.checked_mul(&two)
+				.and_then(|n| ann_sum.checked_add(&n))
+				.ok_or(CurveError::Overflow)?;
+			let denominator = ann_minus_one
+				.checked_mul(&d)
+				.and_then(|n| n.checked_add(&d_p.checked_mul(&3u32.into())?))
+				.ok_or(CurveError::Overflow)?;
+			let next = mul_div(&numerator, &d, &denominator)?;
+			if converged(&next, &d) {
+				return Ok(next)
+			}
+			d = next;
+		}
+		Err(CurveError::NotConverged)
+	}
+
+	/// Computes the reserve `y` keeping the invariant `d` with the other reserve at `x`.
+	pub fn other_reserve<B: CurveBalance>(&self, x: &B, d: &B) -> Result<B, CurveError> {
+		if x.is_zero() {
+			return Err(CurveError::ZeroLiquidity)
+		}
+
+		let ann = self.ann::<B>()?;
+		let two = B::from(2u32);
+		// D^3 / (4 * x * Ann)
+		let c = mul_div(
+			&mul_div(d, d, &x.checked_mul(&two).ok_or(CurveError::Overflow)?)?,
+			d,
+			&ann.checked_mul(&two).ok_or(CurveError::Overflow)?,
+		)?;
+		// x + D / Ann
+		let b = d.checked_div(&ann).and_then(|b| b.checked_add(x)).ok_or(CurveError::Overflow)?;
+		// (y^2 + c) / (2 * y + b - D)
+		let next = |y: &B| {
+			let numerator =
+				y.checked_mul(y).and_then(|n| n.checked_add(&c)).ok_or(CurveError::Overflow)?;
+			let denominator = y
+				.checked_mul(&two)
+				.and_then(|n| n.checked_add(&b))
+				.and_then(|n| n.checked_sub(d))
+				.ok_or(CurveError::Overflow)?;
+			numerator.checked_div(&denominator).ok_or(CurveError::Overflow)
+		};
+
+		// Starting from `y = D`.
+		let mut y = next(d)?;
+		for _ in 1..MAX_ITERATIONS {
+			let y_next = next(&y)?;
+			if converged(&y_next, &y) {
+				return Ok(y_next)
+			}
+			y = y_next;
+		}
+		Err(CurveError::NotConverged)
+	}
+}
+
+impl<B: CurveBalance> SwapCurve<B> for StableSwap {
+	fn amount_out(
+		&self,
+		amount_in: B,
+		reserve_in: B,
+		reserve_out: B,
+		fee: u32,
+	) -> Result<B, CurveError> {
+		if reserve_in.is_zero() || reserve_out.is_zero() {
+			return Err(CurveError::ZeroLiquidity)
+		}
+
+		let amount_in_with_fee = mul_div(&amount_in, &without_fee(fee)?, &1000u32.into())?;
+		let d = self.invariant(&reserve_in, &reserve_out)?;
+		let new_reserve_out = self.other_reserve(
+			&reserve_in.checked_add(&amount_in_with_fee).ok_or(CurveError::Overflow)?,
+			&d,
+		)?;
+
+		// Rounded down in favour of the pool.
+		Ok(reserve_out
+			.checked_sub(&new_reserve_out)
+			.and_then(|amount_out| amount_out.checked_sub(&One::one()))
+			.unwrap_or_else(Zero::zero))
+	}
+
+	fn amount_in(
+		&self,
+		amount_out: B,
+		reserve_in: B,
+		reserve_out: B,
+		fee: u32,
+	) -> Result<B, CurveError> {
+		if reserve_in.is_zero() || reserve_out.is_zero() {
+			return Err(CurveError::ZeroLiquidity)
+		}
+
+		if amount_out >= reserve_out {
+			return Err(CurveError::AmountOutTooHigh)
+		}
+
+		let d = self.invariant(&reserve_in, &reserve_out)?;
+		let new_reserve_in = self.other_reserve(
+			&reserve_out.checked_sub(&amount_out).ok_or(CurveError::Overflow)?,
+			&d,
+		)?;
+
+		// Rounded up in favour of the pool.
+		let amount_in_with_fee = new_reserve_in
+			.checked_sub(&reserve_in)
+			.and_then(|amount_in| amount_in.checked_add(&One::one()))
+			.ok_or(CurveError::Overflow)?;
+		mul_div(&amount_in_with_fee, &1000u32.into(), &without_fee(fee)?)?
+			.checked_add(&One::one())
+			.ok_or(CurveError::Overflow)
+	}
+
+	/// The swap of `amount` without fees. Around the 1:1 price the price impact of a swap is
+	/// negligible, unlike for [`ConstantProduct`].
+	fn quote(&self, amount: B, reserve1: B, reserve2: B) -> Result<B, CurveError> {
+		self.amount_out(amount, reserve1, reserve2, 0)
+	}
+
+	/// The swap paying out `amount_out` without fees. The price depends on the direction of the
+	/// swap, so this isn't the reverse [`SwapCurve::quote`].
+	fn quote_in(&self, amount_out: B, reserve_in: B, reserve_out: B) -> Result<B, CurveError> {
+		self.amount_in(amount_out, reserve_in, reserve_out, 0)
+	}
+
+	/// The marginal price `-dy/dx` of the invariant, `(Ann * x + D_P) * y / ((Ann * y + D_P) * x)`
+	/// with `D_P = D^3 / (4 * x * y)`. It tends to `y / x` as `A` tends to zero and to 1 as `A`
+	/// grows.
+	fn spot_quote(&self, amount: B, reserve1: B, reserve2: B) -> Result<B, CurveError> {
+		if reserve1.is_zero() || reserve2.is_zero() {
+			return Err(CurveError::ZeroLiquidity)
+		}
+
+		let ann = self.ann::<B>()?;
+		let two = B::from(2u32);
+		let d = self.invariant(&reserve1, &reserve2)?;
+		let d_p = mul_div(
+			&mul_div(&d, &d, &reserve1.checked_mul(&two).ok_or(CurveError::Overflow)?)?,
+			&d,
+			&reserve2.checked_mul(&two).ok_or(CurveError::Overflow)?,
+		)?;
+		let partial = |reserve: &B| {
+			ann.checked_mul(reserve)
+				.and_then(|n| n.checked_add(&d_p))
+				.ok_or(CurveError::Overflow)
+		};
+
+		mul_div(
+			&mul_div(&partial(&reserve1)?, &amount, &partial(&reserve2)?)?,
+			&reserve2,
+			&reserve1,
+		)
+	}
+}
+
+/// The share of an amount left after the `fee`, in tenths of a percent.
+fn without_fee<B: CurveBalance>(fee: u32) -> Result<B, CurveError> {
+	1000u32.checked_sub(fee).map(Into::into).ok_or(CurveError::Overflow)
+}
+
+/// `a * b / c`
+fn mul_div<B: CurveBalance>(a: &B, b: &B, c: &B) -> Result<B, CurveError> {
+	a.checked_mul(b).and_then(|n| n.checked_div(c)).ok_or(CurveError::Overflow)
+}
+
+/// Whether two successive approximations differ by one at most.
+fn converged<B: CurveBalance>(a: &B, b: &B) -> bool {
+	let difference = if a > b { a.checked_sub(b) } else { b.checked_sub(a) };
+	difference.map_or(false, |difference| difference <= B::one())
+}
+
+/// Curve chosen for a pool at its creation.
+#[derive(
+	Clone,
+	Copy,
+	Encode,
+	Decode,
+	DecodeWithMemTracking,
+	PartialEq,
+	Eq,
+	MaxEncodedLen,
+	TypeInfo,
+	RuntimeDebug,
+)]
+pub enum CurveKind {
+	/// The [`ConstantProduct`] curve.
+	ConstantProduct,
+	/// The [`StableSwap`] curve with the given amplification coefficient.
+	StableSwap {
+		/// The amplification coefficient, from 1 to [`MAX_AMPLIFICATION`].
+		amplification: u32,
+	},
+}
+
+/// Curve of a pool, as stored.
+#[derive(
+	Clone,
+	Copy,
+	Default,
+	Encode,
+	Decode,
+	DecodeWithMemTracking,
+	PartialEq,
+	Eq,
+	MaxEncodedLen,
+	TypeInfo,
+	RuntimeDebug,
+)]
+pub enum PoolCurve<BlockNumber> {
+	/// The [`ConstantProduct`] curve.
+	#[default]
+	ConstantProduct,
+	/// The [`StableSwap`] curve.
+	StableSwap(Amplification<BlockNumber>),
+}
+
+/// Amplification coefficient of a [`StableSwap`] pool, ramping linearly from `initial` at
+/// `initial_block` to `future` at `future_block`.
+#[derive(
+	Clone,
+	Copy,
+	Encode,
+	Decode,
+	DecodeWithMemTracking,
+	PartialEq,
+	Eq,
+	MaxEncodedLen,
+	TypeInfo,
+	RuntimeDebug,
+)]
+pub struct Amplification<BlockNumber> {
+	/// The coefficient at the start of the ramp.
+	pub initial: u32,
+	/// The coefficient at the end of the ramp.
+	pub future: u32,
+	/// The block the ramp starts at.
+	pub initial_block: BlockNumber,
+	/// The block the ramp ends at.
+	pub future_block: BlockNumber,
+}
+
+impl<BlockNumber: AtLeast32BitUnsigned + Copy> Amplification<BlockNumber> {
+	/// A coefficient that doesn't change.
+	pub fn constant(amplification: u32, now: BlockNumber) -> Self {
+		Self {
+			initial: amplification,
+			future: amplification,
+			initial_block: now,
+			future_block: now,
+		}
+	}
+
+	/// The coefficient at block `now`.
+	pub fn at(&self, now: BlockNumber) -> u32 {
+		if now >= self.future_block {
+			return self.future
+		}
+		if now <= self.initial_block {
+			return self.initial
+		}
+
+		let elapsed: u64 = (now - self.initial_block).saturated_into();
+		let duration: u64 = (self.future_block - self.initial_block).saturated_into();
+		let (initial, future) = (self.initial as u64, self.future as u64);
+		let amplification = if future > initial {
+			initial + (future - initial) * elapsed / duration
+		} else {
+			initial - (initial - future) * elapsed / duration
+		};
+		amplification.saturated_into()
+	}
+}
@@ -19,11 +19,16 @@
 //!
 //! Substrate Asset Conversion pallet based on the [Uniswap V2](https://github.com/Uniswap/v2-core) logic.
 //!
+//! Each pool picks the [curve](`SwapCurve`) its swaps are priced with when it is created: the
+//! [constant product](`ConstantProduct`) of Uniswap V2, or the [StableSwap](`StableSwap`) invariant
+//! of Curve Finance for pairs of pegged assets.
+//!
 //! ## Overview
 //!
 //! This pallet allows you to:
 //!
-//!  - [create a liquidity pool](`Pallet::create_pool()`) for 2 assets
+//!  - [create a liquidity pool](`Pallet::create_pool()`) for 2 assets, or one [with a
+//!    curve](`Pallet::create_pool_with_curve()`) other than the constant product
 //!  - [provide the liquidity](`Pallet::add_liquidity()`) and receive back an LP token
 //!  - [exchange the LP token back to assets](`Pallet::remove_liquidity()`)
 //!  - [swap a specific amount of assets for another](`Pallet::swap_exact_tokens_for_tokens()`) if
@@ -34,6 +39,8 @@
 //!    a runtime call endpoint
 //!  - [query the size of a liquidity pool](`AssetConversionApi::get_reserves`) via a runtime api
 //!    endpoint.
+//!  - [query a time-weighted average price](`AssetConversionApi::quote_price_time_weighted`) via a
+//!    runtime api endpoint, or from other pallets via the [`TimeWeightedPrice`] trait.
 //!
 //! The `quote_price_exact_tokens_for_tokens` and `quote_price_tokens_for_exact_tokens` functions
 //! both take a path parameter of the route to take. If you want to swap from native asset to
@@ -56,8 +63,10 @@
 
 #[cfg(feature = "runtime-benchmarks")]
 mod benchmarking;
+mod curve;
 #[cfg(test)]
 mod mock;
+mod oracle;
 mod swap;
 #[cfg(test)]
 mod tests;
@@ -65,6 +74,8 @@ mod types;
 pub mod weights;
 #[cfg(feature = "runtime-benchmarks")]
 pub use benchmarking::{BenchmarkHelper, NativeOrWithIdFactory};
+pub use curve::*;
+pub use oracle::*;
 pub use pallet::*;
 pub use swap::*;
 pub use types::*;
@@ -186,6 +197,14 @@ pub mod pallet {
 		#[pallet::constant]
 		type MaxSwapPathLength: Get<u32>;
 
+		/// The number of past accumulated prices kept for each pool, at most one per block, to
+		/// compute time-weighted average prices with.
+		///
+		/// It bounds the window of the average prices of the pools whose reserves change in every
+		/// block. It should not be lowered without migrating [`PriceObservations`].
+		#[pallet::constant]
+		type MaxPriceObservations: Get<u32>;
+
 		/// The pallet's id, used for deriving its sovereign account ID.
 		#[pallet::constant]
 		type PalletId: Get<PalletId>;
@@ -204,6 +223,34 @@ pub mod pallet {
 	pub type Pools<T: Config> =
 		StorageMap<_, Blake2_128Concat, T::PoolId, PoolInfo<T::PoolAssetId>, OptionQuery>;
 
+	/// The curve of each pool. Pools without an entry use the [`ConstantProduct`] curve.
+	#[pallet::storage]
+	pub type PoolCurves<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::PoolId, PoolCurve<BlockNumberFor<T>>, ValueQuery>;
+
+	/// The prices of the assets of each pool, accumulated over the blocks.
+	#[pallet::storage]
+	pub type PriceAccumulators<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::PoolId,
+		PriceAccumulator<BlockNumberFor<T>>,
+		OptionQuery,
+	>;
+
+	/// The past accumulated prices of each pool, in a ring buffer of
+	/// [`Config::MaxPriceObservations`] slots.
+	#[pallet::storage]
+	pub type PriceObservations<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::PoolId,
+		Twox64Concat,
+		u32,
+		PriceObservation<BlockNumberFor<T>>,
+		OptionQuery,
+	>;
+
 	/// Stores the `PoolAssetId` that is going to be used for the next lp token.
 	/// This gets incremented whenever a new lp pool is created.
 	#[pallet::storage]
@@ -296,6 +343,27 @@ pub mod pallet {
 			/// The account initiating the touch.
 			who: T::AccountId,
 		},
+		/// A pool has been created with a curve other than the constant product.
+		PoolCurveSet {
+			/// The ID of the pool.
+			pool_id: T::PoolId,
+			/// The curve of the pool.
+			curve: PoolCurve<BlockNumberFor<T>>,
+		},
+		/// The amplification coefficient of a StableSwap pool started ramping to a new value.
+		AmplificationRampStarted {
+			/// The ID of the pool.
+			pool_id: T::PoolId,
+			/// The ramp of the amplification coefficient.
+			amplification: Amplification<BlockNumberFor<T>>,
+		},
+		/// The amplification coefficient of a StableSwap pool stopped ramping.
+		AmplificationRampStopped {
+			/// The ID of the pool.
+			pool_id: T::PoolId,
+			/// The amplification coefficient the pool keeps.
+			amplification: u32,
+		},
 	}
 
 	#[pallet::error]
@@ -349,6 +417,26 @@ pub mod pallet {
 		IncorrectPoolAssetId,
 		/// The destination account cannot exist with the swapped funds.
 		BelowMinimum,
+		/// The pool doesn't use the StableSwap curve.
+		NotStableSwapPool,
+		/// The amplification coefficient must be between 1 and [`MAX_AMPLIFICATION`].
+		InvalidAmplification,
+		/// The amplification coefficient can only ramp up to a future block, and by a factor of
+		/// [`MAX_AMPLIFICATION_CHANGE`] at most.
+		InvalidAmplificationRamp,
+		/// The price of the swap could not be computed on the pool's curve.
+		CurveDidNotConverge,
+	}
+
+	impl<T> From<CurveError> for Error<T> {
+		fn from(error: CurveError) -> Self {
+			match error {
+				CurveError::ZeroLiquidity => Error::<T>::ZeroLiquidity,
+				CurveError::AmountOutTooHigh => Error::<T>::AmountOutTooHigh,
+				CurveError::Overflow => Error::<T>::Overflow,
+				CurveError::NotConverged => Error::<T>::CurveDidNotConverge,
+			}
+		}
 	}
 
 	#[pallet::hooks]
@@ -358,6 +446,10 @@ pub mod pallet {
 				T::MaxSwapPathLength::get() > 1,
 				"the `MaxSwapPathLength` should be greater than 1",
 			);
+			assert!(
+				T::MaxPriceObservations::get() > 0,
+				"the `MaxPriceObservations` should be greater than 0",
+			);
 		}
 	}
 
@@ -376,51 +468,7 @@ pub mod pallet {
 			asset2: Box<T::AssetKind>,
 		) -> DispatchResult {
 			let sender = ensure_signed(origin)?;
-			ensure!(asset1 != asset2, Error::<T>::InvalidAssetPair);
-
-			// prepare pool_id
-			let pool_id = T::PoolLocator::pool_id(&asset1, &asset2)
-				.map_err(|_| Error::<T>::InvalidAssetPair)?;
-			ensure!(!Pools::<T>::contains_key(&pool_id), Error::<T>::PoolExists);
-
-			let pool_account =
-				T::PoolLocator::address(&pool_id).map_err(|_| Error::<T>::InvalidAssetPair)?;
-
-			// pay the setup fee
-			let fee =
-				Self::withdraw(T::PoolSetupFeeAsset::get(), &sender, T::PoolSetupFee::get(), true)?;
-			T::PoolSetupFeeTarget::on_unbalanced(fee);
-
-			if T::Assets::should_touch(*asset1.clone(), &pool_account) {
-				T::Assets::touch(*asset1, &pool_account, &sender)?
-			};
-
-			if T::Assets::should_touch(*asset2.clone(), &pool_account) {
-				T::Assets::touch(*asset2, &pool_account, &sender)?
-			};
-
-			let lp_token = NextPoolAssetId::<T>::get()
-				.or(T::PoolAssetId::initial_value())
-				.ok_or(Error::<T>::IncorrectPoolAssetId)?;
-			let next_lp_token_id = lp_token.increment().ok_or(Error::<T>::IncorrectPoolAssetId)?;
-			NextPoolAssetId::<T>::set(Some(next_lp_token_id));
-
-			T::PoolAssets::create(lp_token.clone(), pool_account.clone(), false, 1u32.into())?;
-			if T::PoolAssets::should_touch(lp_token.clone(), &pool_account) {
-				T::PoolAssets::touch(lp_token.clone(), &pool_account, &sender)?
-			};
-
-			let pool_info = PoolInfo { lp_token: lp_token.clone() };
-			Pools::<T>::insert(pool_id.clone(), pool_info);
-
-			Self::deposit_event(Event::PoolCreated {
-				creator: sender,
-				pool_id,
-				pool_account,
-				lp_token,
-			});
-
-			Ok(())
+			Self::do_create_pool(sender, *asset1, *asset2, CurveKind::ConstantProduct)
 		}
 
 		/// Provide liquidity into the pool of `asset1` and `asset2`.
@@ -438,7 +486,10 @@ pub mod pallet {
 		/// Once liquidity is added, someone may successfully call
 		/// [`Pallet::swap_exact_tokens_for_tokens`].
 		#[pallet::call_index(1)]
-		#[pallet::weight(T::WeightInfo::add_liquidity())]
+		#[pallet::weight(
+			T::WeightInfo::add_liquidity()
+				.saturating_add(Pallet::<T>::price_accumulators_weight(1))
+		)]
 		pub fn add_liquidity(
 			origin: OriginFor<T>,
 			asset1: Box<T::AssetKind>,
@@ -505,6 +556,14 @@ pub mod pallet {
 				Error::<T>::AmountTwoLessThanMinimal
 			);
 
+			Self::update_price_accumulator(
+				&pool_id,
+				&asset1,
+				&asset2,
+				reserve1.saturating_add(amount1),
+				reserve2.saturating_add(amount2),
+			);
+
 			T::Assets::transfer(*asset1, &sender, &pool_account, amount1, Preserve)?;
 			T::Assets::transfer(*asset2, &sender, &pool_account, amount2, Preserve)?;
 
@@ -548,7 +607,10 @@ pub mod pallet {
 		/// burned in the process. With the usage of `amount1_min_receive`/`amount2_min_receive`
 		/// it's possible to control the min amount of returned tokens you're happy with.
 		#[pallet::call_index(2)]
-		#[pallet::weight(T::WeightInfo::remove_liquidity())]
+		#[pallet::weight(
+			T::WeightInfo::remove_liquidity()
+				.saturating_add(Pallet::<T>::price_accumulators_weight(1))
+		)]
 		pub fn remove_liquidity(
 			origin: OriginFor<T>,
 			asset1: Box<T::AssetKind>,
@@ -608,6 +670,14 @@ pub mod pallet {
 				Polite,
 			)?;
 
+			Self::update_price_accumulator(
+				&pool_id,
+				&asset1,
+				&asset2,
+				reserve1_left,
+				reserve2_left,
+			);
+
 			T::Assets::transfer(*asset1, &pool_account, &withdraw_to, amount1, Expendable)?;
 			T::Assets::transfer(*asset2, &pool_account, &withdraw_to, amount2, Expendable)?;
 
@@ -632,7 +702,11 @@ pub mod pallet {
 		/// [`AssetConversionApi::quote_price_exact_tokens_for_tokens`] runtime call can be called
 		/// for a quote.
 		#[pallet::call_index(3)]
-		#[pallet::weight(T::WeightInfo::swap_exact_tokens_for_tokens(path.len() as u32))]
+		#[pallet::weight({
+			let hops = (path.len() as u32).saturating_sub(1);
+			T::WeightInfo::swap_exact_tokens_for_tokens(path.len() as u32)
+				.saturating_add(Pallet::<T>::price_accumulators_weight(hops))
+		})]
 		pub fn swap_exact_tokens_for_tokens(
 			origin: OriginFor<T>,
 			path: Vec<Box<T::AssetKind>>,
@@ -660,7 +734,11 @@ pub mod pallet {
 		/// [`AssetConversionApi::quote_price_tokens_for_exact_tokens`] runtime call can be called
 		/// for a quote.
 		#[pallet::call_index(4)]
-		#[pallet::weight(T::WeightInfo::swap_tokens_for_exact_tokens(path.len() as u32))]
+		#[pallet::weight({
+			let hops = (path.len() as u32).saturating_sub(1);
+			T::WeightInfo::swap_tokens_for_exact_tokens(path.len() as u32)
+				.saturating_add(Pallet::<T>::price_accumulators_weight(hops))
+		})]
 		pub fn swap_tokens_for_exact_tokens(
 			origin: OriginFor<T>,
 			path: Vec<Box<T::AssetKind>>,
@@ -723,9 +801,188 @@ pub mod pallet {
 			Self::deposit_event(Event::Touched { pool_id, who });
 			Ok(Some(T::WeightInfo::touch(refunds_number)).into())
 		}
+
+		/// Creates an empty liquidity pool pricing its swaps with the given `curve`, and an
+		/// associated new `lp_token` asset.
+		///
+		/// A [`CurveKind::StableSwap`] pool suits pegged assets with the same number of decimals,
+		/// like two stablecoins. Its amplification coefficient can later be changed with
+		/// [`Pallet::ramp_amplification`].
+		///
+		/// Once a pool is created, someone may [`Pallet::add_liquidity`] to it.
+		#[pallet::call_index(6)]
+		#[pallet::weight(T::WeightInfo::create_pool_with_curve())]
+		pub fn create_pool_with_curve(
+			origin: OriginFor<T>,
+			asset1: Box<T::AssetKind>,
+			asset2: Box<T::AssetKind>,
+			curve: CurveKind,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			Self::do_create_pool(sender, *asset1, *asset2, curve)
+		}
+
+		/// Ramp the amplification coefficient of the StableSwap pool of `asset1` and `asset2`
+		/// linearly from its current value to `future_amplification` at `future_block`.
+		///
+		/// The coefficient can change by a factor of [`MAX_AMPLIFICATION_CHANGE`] at most in a
+		/// single ramp. Any ramp in progress is replaced.
+		///
+		/// The origin must be Root.
+		#[pallet::call_index(7)]
+		#[pallet::weight(T::WeightInfo::ramp_amplification())]
+		pub fn ramp_amplification(
+			origin: OriginFor<T>,
+			asset1: Box<T::AssetKind>,
+			asset2: Box<T::AssetKind>,
+			future_amplification: u32,
+			future_block: BlockNumberFor<T>,
+		) -> DispatchResult {
+			ensure_root(origin)?;
+			ensure!(
+				(1..=MAX_AMPLIFICATION).contains(&future_amplification),
+				Error::<T>::InvalidAmplification
+			);
+
+			let (pool_id, amplification) = Self::stable_swap_amplification(&asset1, &asset2)?;
+			let now = frame_system::Pallet::<T>::block_number();
+			let current = amplification.at(now);
+			ensure!(
+				future_block > now &&
+					future_amplification <= current.saturating_mul(MAX_AMPLIFICATION_CHANGE) &&
+					current <= future_amplification.saturating_mul(MAX_AMPLIFICATION_CHANGE),
+				Error::<T>::InvalidAmplificationRamp
+			);
+
+			let amplification = Amplification {
+				initial: current,
+				future: future_amplification,
+				initial_block: now,
+				future_block,
+			};
+			PoolCurves::<T>::insert(&pool_id, PoolCurve::StableSwap(amplification));
+
+			Self::deposit_event(Event::AmplificationRampStarted { pool_id, amplification });
+			Ok(())
+		}
+
+		/// Stop the ramp of the amplification coefficient of the StableSwap pool of `asset1` and
+		/// `asset2`, keeping its current value.
+		///
+		/// The origin must be Root.
+		#[pallet::call_index(8)]
+		#[pallet::weight(T::WeightInfo::stop_ramp_amplification())]
+		pub fn stop_ramp_amplification(
+			origin: OriginFor<T>,
+			asset1: Box<T::AssetKind>,
+			asset2: Box<T::AssetKind>,
+		) -> DispatchResult {
+			ensure_root(origin)?;
+
+			let (pool_id, amplification) = Self::stable_swap_amplification(&asset1, &asset2)?;
+			let now = frame_system::Pallet::<T>::block_number();
+			let current = amplification.at(now);
+			PoolCurves::<T>::insert(
+				&pool_id,
+				PoolCurve::StableSwap(Amplification::constant(current, now)),
+			);
+
+			Self::deposit_event(Event::AmplificationRampStopped {
+				pool_id,
+				amplification: current,
+			});
+			Ok(())
+		}
 	}
 
 	impl<T: Config> Pallet<T> {
+		/// Create a pool of `asset1` and `asset2` with the given `curve`.
+		fn do_create_pool(
+			sender: T::AccountId,
+			asset1: T::AssetKind,
+			asset2: T::AssetKind,
+			curve: CurveKind,
+		) -> DispatchResult {
+			ensure!(asset1 != asset2, Error::<T>::InvalidAssetPair);
+			let curve = match curve {
+				CurveKind::ConstantProduct => PoolCurve::ConstantProduct,
+				CurveKind::StableSwap { amplification } => {
+					ensure!(
+						(1..=MAX_AMPLIFICATION).contains(&amplification),
+						Error::<T>::InvalidAmplification
+					);
+					PoolCurve::StableSwap(Amplification::constant(
+						amplification,
+						frame_system::Pallet::<T>::block_number(),
+					))
+				},
+			};
+
+			// prepare pool_id
+			let pool_id = T::PoolLocator::pool_id(&asset1, &asset2)
+				.map_err(|_| Error::<T>::InvalidAssetPair)?;
+			ensure!(!Pools::<T>::contains_key(&pool_id), Error::<T>::PoolExists);
+
+			let pool_account =
+				T::PoolLocator::address(&pool_id).map_err(|_| Error::<T>::InvalidAssetPair)?;
+
+			// pay the setup fee
+			let fee =
+				Self::withdraw(T::PoolSetupFeeAsset::get(), &sender, T::PoolSetupFee::get(), true)?;
+			T::PoolSetupFeeTarget::on_unbalanced(fee);
+
+			if T::Assets::should_touch(asset1.clone(), &pool_account) {
+				T::Assets::touch(asset1, &pool_account, &sender)?
+			};
+
+			if T::Assets::should_touch(asset2.clone(), &pool_account) {
+				T::Assets::touch(asset2, &pool_account, &sender)?
+			};
+
+			let lp_token = NextPoolAssetId::<T>::get()
+				.or(T::PoolAssetId::initial_value())
+				.ok_or(Error::<T>::IncorrectPoolAssetId)?;
+			let next_lp_token_id = lp_token.increment().ok_or(Error::<T>::IncorrectPoolAssetId)?;
+			NextPoolAssetId::<T>::set(Some(next_lp_token_id));
+
+			T::PoolAssets::create(lp_token.clone(), pool_account.clone(), false, 1u32.into())?;
+			if T::PoolAssets::should_touch(lp_token.clone(), &pool_account) {
+				T::PoolAssets::touch(lp_token.clone(), &pool_account, &sender)?
+			};
+
+			let pool_info = PoolInfo { lp_token: lp_token.clone() };
+			Pools::<T>::insert(pool_id.clone(), pool_info);
+
+			Self::deposit_event(Event::PoolCreated {
+				creator: sender,
+				pool_id: pool_id.clone(),
+				pool_account,
+				lp_token,
+			});
+
+			if curve != PoolCurve::ConstantProduct {
+				PoolCurves::<T>::insert(&pool_id, curve);
+				Self::deposit_event(Event::PoolCurveSet { pool_id, curve });
+			}
+
+			Ok(())
+		}
+
+		/// Returns the id and the amplification coefficient of the StableSwap pool of `asset1`
+		/// and `asset2`.
+		fn stable_swap_amplification(
+			asset1: &T::AssetKind,
+			asset2: &T::AssetKind,
+		) -> Result<(T::PoolId, Amplification<BlockNumberFor<T>>), DispatchError> {
+			let pool_id = T::PoolLocator::pool_id(asset1, asset2)
+				.map_err(|_| Error::<T>::InvalidAssetPair)?;
+			ensure!(Pools::<T>::contains_key(&pool_id), Error::<T>::PoolNotFound);
+			match PoolCurves::<T>::get(&pool_id) {
+				PoolCurve::StableSwap(amplification) => Ok((pool_id, amplification)),
+				PoolCurve::ConstantProduct => Err(Error::<T>::NotStableSwapPool.into()),
+			}
+		}
+
 		/// Swap exactly `amount_in` of asset `path[0]` for asset `path[1]`.
 		/// If an `amount_out_min` is specified, it will return an error if it is unable to acquire
 		/// the amount desired.
@@ -1000,6 +1257,8 @@ pub mod pallet {
 			T::Assets::resolve(&pool_to, credit_in)
 				.map_err(|c| (c, Error::<T>::BelowMinimum.into()))?;
 
+			Self::update_price_accumulators(path);
+
 			Ok(credit_out)
 		}
 
@@ -1065,9 +1324,18 @@ pub mod pallet {
 						break
 					},
 				};
+				let pool_id = T::PoolLocator::pool_id(asset1, &asset2)
+					.map_err(|_| Error::<T>::InvalidAssetPair)?;
 				let (reserve_in, reserve_out) = Self::get_reserves(asset1.clone(), asset2.clone())?;
 				balance_path.push((asset2, amount_in));
-				amount_in = Self::get_amount_in(&amount_in, &reserve_in, &reserve_out)?;
+				amount_in = Self::with_pool_curve(&pool_id, |curve| {
+					curve.amount_in(
+						amount_in.into(),
+						reserve_in.into(),
+						reserve_out.into(),
+						T::LPFee::get(),
+					)
+				})?;
 			}
 			balance_path.reverse();
 
@@ -1091,9 +1359,18 @@ pub mod pallet {
 						break
 					},
 				};
+				let pool_id = T::PoolLocator::pool_id(&asset1, asset2)
+					.map_err(|_| Error::<T>::InvalidAssetPair)?;
 				let (reserve_in, reserve_out) = Self::get_reserves(asset1.clone(), asset2.clone())?;
 				balance_path.push((asset1, amount_out));
-				amount_out = Self::get_amount_out(&amount_out, &reserve_in, &reserve_out)?;
+				amount_out = Self::with_pool_curve(&pool_id, |curve| {
+					curve.amount_out(
+						amount_out.into(),
+						reserve_in.into(),
+						reserve_out.into(),
+						T::LPFee::get(),
+					)
+				})?;
 			}
 			Ok(balance_path)
 		}
@@ -1105,16 +1382,22 @@ pub mod pallet {
 			amount: T::Balance,
 			include_fee: bool,
 		) -> Option<T::Balance> {
-			let pool_account = T::PoolLocator::pool_address(&asset1, &asset2).ok()?;
+			let pool_id = T::PoolLocator::pool_id(&asset1, &asset2).ok()?;
+			let pool_account = T::PoolLocator::address(&pool_id).ok()?;
 
 			let balance1 = Self::get_balance(&pool_account, asset1);
 			let balance2 = Self::get_balance(&pool_account, asset2);
 			if !balance1.is_zero() {
-				if include_fee {
-					Self::get_amount_out(&amount, &balance1, &balance2).ok()
-				} else {
-					Self::quote(&amount, &balance1, &balance2).ok()
-				}
+				Self::with_pool_curve(&pool_id, |curve| {
+					let (amount, balance1, balance2) =
+						(amount.into(), balance1.into(), balance2.into());
+					if include_fee {
+						curve.amount_out(amount, balance1, balance2, T::LPFee::get())
+					} else {
+						curve.quote(amount, balance1, balance2)
+					}
+				})
+				.ok()
 			} else {
 				None
 			}
@@ -1127,16 +1410,22 @@ pub mod pallet {
 			amount: T::Balance,
 			include_fee: bool,
 		) -> Option<T::Balance> {
-			let pool_account = T::PoolLocator::pool_address(&asset1, &asset2).ok()?;
+			let pool_id = T::PoolLocator::pool_id(&asset1, &asset2).ok()?;
+			let pool_account = T::PoolLocator::address(&pool_id).ok()?;
 
 			let balance1 = Self::get_balance(&pool_account, asset1);
 			let balance2 = Self::get_balance(&pool_account, asset2);
 			if !balance1.is_zero() {
-				if include_fee {
-					Self::get_amount_in(&amount, &balance1, &balance2).ok()
-				} else {
-					Self::quote(&amount, &balance2, &balance1).ok()
-				}
+				Self::with_pool_curve(&pool_id, |curve| {
+					let (amount, balance1, balance2) =
+						(amount.into(), balance1.into(), balance2.into());
+					if include_fee {
+						curve.amount_in(amount, balance1, balance2, T::LPFee::get())
+					} else {
+						curve.quote_in(amount, balance1, balance2)
+					}
+				})
+				.ok()
 			} else {
 				None
 			}
@@ -1183,7 +1472,24 @@ pub mod pallet {
 			result.try_into().map_err(|_| Error::<T>::Overflow)
 		}
 
-		/// Calculates amount out.
+		/// Calculates the amount of a swap in the pool `pool_id` with the pool's curve.
+		fn with_pool_curve(
+			pool_id: &T::PoolId,
+			swap: impl FnOnce(
+				&dyn SwapCurve<T::HigherPrecisionBalance>,
+			) -> Result<T::HigherPrecisionBalance, CurveError>,
+		) -> Result<T::Balance, Error<T>> {
+			let amount = match PoolCurves::<T>::get(pool_id) {
+				PoolCurve::ConstantProduct => swap(&ConstantProduct),
+				PoolCurve::StableSwap(amplification) => swap(&StableSwap {
+					amplification: amplification.at(frame_system::Pallet::<T>::block_number()),
+				}),
+			}?;
+
+			amount.try_into().map_err(|_| Error::<T>::Overflow)
+		}
+
+		/// Calculates amount out of a constant product pool.
 		///
 		/// Given an input amount of an asset and pair reserves, returns the maximum output amount
 		/// of the other asset.
@@ -1192,33 +1498,17 @@ pub mod pallet {
 			reserve_in: &T::Balance,
 			reserve_out: &T::Balance,
 		) -> Result<T::Balance, Error<T>> {
-			let amount_in = T::HigherPrecisionBalance::from(*amount_in);
-			let reserve_in = T::HigherPrecisionBalance::from(*reserve_in);
-			let reserve_out = T::HigherPrecisionBalance::from(*reserve_out);
-
-			if reserve_in.is_zero() || reserve_out.is_zero() {
-				return Err(Error::<T>::ZeroLiquidity)
-			}
-
-			let amount_in_with_fee = amount_in
-				.checked_mul(&(T::HigherPrecisionBalance::from(1000u32) - (T::LPFee::get().into())))
-				.ok_or(Error::<T>::Overflow)?;
-
-			let numerator =
-				amount_in_with_fee.checked_mul(&reserve_out).ok_or(Error::<T>::Overflow)?;
-
-			let denominator = reserve_in
-				.checked_mul(&1000u32.into())
-				.ok_or(Error::<T>::Overflow)?
-				.checked_add(&amount_in_with_fee)
-				.ok_or(Error::<T>::Overflow)?;
-
-			let result = numerator.checked_div(&denominator).ok_or(Error::<T>::Overflow)?;
+			let amount_out = ConstantProduct.amount_out(
+				T::HigherPrecisionBalance::from(*amount_in),
+				T::HigherPrecisionBalance::from(*reserve_in),
+				T::HigherPrecisionBalance::from(*reserve_out),
+				T::LPFee::get(),
+			)?;
 
-			result.try_into().map_err(|_| Error::<T>::Overflow)
+			amount_out.try_into().map_err(|_| Error::<T>::Overflow)
 		}
 
-		/// Calculates amount in.
+		/// Calculates amount in of a constant product pool.
 		///
 		/// Given an output amount of an asset and pair reserves, returns a required input amount
 		/// of the other asset.
@@ -1227,37 +1517,14 @@ pub mod pallet {
 			reserve_in: &T::Balance,
 			reserve_out: &T::Balance,
 		) -> Result<T::Balance, Error<T>> {
-			let amount_out = T::HigherPrecisionBalance::from(*amount_out);
-			let reserve_in = T::HigherPrecisionBalance::from(*reserve_in);
-			let reserve_out = T::HigherPrecisionBalance::from(*reserve_out);
-
-			if reserve_in.is_zero() || reserve_out.is_zero() {
-				Err(Error::<T>::ZeroLiquidity)?
-			}
-
-			if amount_out >= reserve_out {
-				Err(Error::<T>::AmountOutTooHigh)?
-			}
-
-			let numerator = reserve_in
-				.checked_mul(&amount_out)
-				.ok_or(Error::<T>::Overflow)?
-				.checked_mul(&1000u32.into())
-				.ok_or(Error::<T>::Overflow)?;
-
-			let denominator = reserve_out
-				.checked_sub(&amount_out)
-				.ok_or(Error::<T>::Overflow)?
-				.checked_mul(&(T::HigherPrecisionBalance::from(1000u32) - T::LPFee::get().into()))
-				.ok_or(Error::<T>::Overflow)?;
-
-			let result = numerator
-				.checked_div(&denominator)
-				.ok_or(Error::<T>::Overflow)?
-				.checked_add(&One::one())
-				.ok_or(Error::<T>::Overflow)?;
+			let amount_in = ConstantProduct.amount_in(
+				T::HigherPrecisionBalance::from(*amount_out),
+				T::HigherPrecisionBalance::from(*reserve_in),
+				T::HigherPrecisionBalance::from(*reserve_out),
+				T::LPFee::get(),
+			)?;
 
-			result.try_into().map_err(|_| Error::<T>::Overflow)
+			amount_in.try_into().map_err(|_| Error::<T>::Overflow)
 		}
 
 		/// Ensure that a path is valid.
@@ -1294,6 +1561,7 @@ pub mod pallet {
 sp_api::decl_runtime_apis! {
 	/// This runtime api allows people to query the size of the liquidity pools
 	/// and quote prices for swaps.
+	#[api_version(2)]
 	pub trait AssetConversionApi<Balance, AssetId>
 	where
 		Balance: frame_support::traits::tokens::Balance + MaybeDisplay,
@@ -1323,6 +1591,18 @@ sp_api::decl_runtime_apis! {
 
 		/// Returns the size of the liquidity pool for the given asset pair.
 		fn get_reserves(asset1: AssetId, asset2: AssetId) -> Option<(Balance, Balance)>;
+
+		/// Quotes the amount of `asset2` worth the `amount` of `asset1` at the average price of
+		/// the pool over the last `window` blocks.
+		///
+		/// Unlike the other quotes, this price cannot be moved within a single block.
+		#[api_version(2)]
+		fn quote_price_time_weighted(
+			asset1: AssetId,
+			asset2: AssetId,
+			amount: Balance,
+			window: u32,
+		) -> Option<Balance>;
 	}
 }
 
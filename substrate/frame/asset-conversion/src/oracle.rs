@@ -0,0 +1,366 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Time-weighted average prices of the pools.
+//!
+//! Every pool accumulates the price of each of its assets, weighted by the number of blocks the
+//! price lasted for. The price of a block is the one the pool is left with at its end, so moving
+//! the price within a block, e.g. by sandwiching a transaction, does not count towards the average
+//! unless the price is held, against arbitrage, for whole blocks.
+//!
+//! The price of a pool is the marginal price of its curve, the price of an infinitesimal swap. A
+//! [`StableSwap`] pool whose amplification coefficient is ramping keeps the price it was left with
+//! at its last swap or liquidity change, until the next one.
+//!
+//! The accumulated prices are recorded at most once per block in a ring buffer of
+//! [`Config::MaxPriceObservations`] observations. The average price over a window is derived from
+//! the accumulated prices at both of its ends.
+
+use super::*;
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::{traits::StorageInfoTrait, weights::Weight};
+use frame_system::pallet_prelude::BlockNumberFor;
+use scale_info::TypeInfo;
+use sp_runtime::{
+	traits::AtLeast32BitUnsigned, FixedPointNumber, FixedU128, RuntimeDebug, SaturatedConversion,
+};
+
+/// Trait providing manipulation-resistant prices between asset classes, averaged over time.
+///
+/// Unlike [`QuotePrice`], these prices cannot be moved within a single block, since every price is
+/// weighted by the number of blocks it lasted for.
+pub trait TimeWeightedPrice {
+	/// Measurement units of the asset classes for pricing.
+	type Balance: Balance;
+	/// Type representing the kind of assets for which the price is being quoted.
+	type AssetKind;
+	/// Type of the block numbers the prices are averaged over.
+	type BlockNumber;
+	/// Returns the average price of `asset1` in `asset2` over the last `window` blocks.
+	///
+	/// If the pool does not exist or its price was not observed for the whole `window`, `None` is
+	/// returned.
+	fn time_weighted_average_price(
+		asset1: Self::AssetKind,
+		asset2: Self::AssetKind,
+		window: Self::BlockNumber,
+	) -> Option<FixedU128>;
+	/// Quotes the amount of `asset2` worth the `amount` of `asset1` at the average price of the
+	/// last `window` blocks.
+	///
+	/// If the pool does not exist or its price was not observed for the whole `window`, `None` is
+	/// returned.
+	fn quote_price_time_weighted(
+		asset1: Self::AssetKind,
+		asset2: Self::AssetKind,
+		amount: Self::Balance,
+		window: Self::BlockNumber,
+	) -> Option<Self::Balance>;
+}
+
+/// Prices of the assets of a pool, accumulated over the blocks.
+///
+/// The first asset of a pool is the one with the lowest SCALE encoding. The accumulated prices are
+/// sums of [`FixedU128`] inner values that wrap around on overflow, only the difference between
+/// two of them is meaningful.
+#[derive(Clone, Copy, Encode, Decode, PartialEq, Eq, MaxEncodedLen, TypeInfo, RuntimeDebug)]
+pub struct PriceAccumulator<BlockNumber> {
+	/// The prices accumulated up to `last_block`.
+	pub cumulative: PriceObservation<BlockNumber>,
+	/// The price of the first asset in the second one since `last_block`.
+	pub price1: FixedU128,
+	/// The price of the second asset in the first one since `last_block`.
+	pub price2: FixedU128,
+	/// The slot of `cumulative` in [`PriceObservations`].
+	pub latest_observation: u32,
+	/// The number of observations recorded in [`PriceObservations`].
+	pub observations: u32,
+}
+
+/// Prices of the assets of a pool accumulated up to a block.
+#[derive(Clone, Copy, Encode, Decode, PartialEq, Eq, MaxEncodedLen, TypeInfo, RuntimeDebug)]
+pub struct PriceObservation<BlockNumber> {
+	/// The block the prices are accumulated up to.
+	pub block: BlockNumber,
+	/// The accumulated price of the first asset in the second one.
+	pub price1_cumulative: u128,
+	/// The accumulated price of the second asset in the first one.
+	pub price2_cumulative: u128,
+}
+
+impl<BlockNumber: AtLeast32BitUnsigned + Copy> PriceAccumulator<BlockNumber> {
+	/// The prices accumulated up to `block`, which is not before the last change of the prices.
+	pub fn observation_at(&self, block: BlockNumber) -> PriceObservation<BlockNumber> {
+		let elapsed: u128 = block.saturating_sub(self.cumulative.block).saturated_into();
+		let accumulate = |cumulative: u128, price: FixedU128| {
+			cumulative.wrapping_add(price.into_inner().wrapping_mul(elapsed))
+		};
+		PriceObservation {
+			block,
+			price1_cumulative: accumulate(self.cumulative.price1_cumulative, self.price1),
+			price2_cumulative: accumulate(self.cumulative.price2_cumulative, self.price2),
+		}
+	}
+}
+
+impl<BlockNumber: AtLeast32BitUnsigned + Copy> PriceObservation<BlockNumber> {
+	/// The prices accumulated up to `block`, between this observation and the `next` one.
+	///
+	/// The prices do not change between two observations, the accumulated prices grow linearly.
+	fn interpolate(&self, next: &Self, block: BlockNumber) -> Self {
+		let elapsed: u128 = block.saturating_sub(self.block).saturated_into();
+		let duration: u128 = next.block.saturating_sub(self.block).saturated_into();
+		let interpolate = |from: u128, to: u128| {
+			from.wrapping_add(
+				to.wrapping_sub(from).checked_div(duration).unwrap_or_default() * elapsed,
+			)
+		};
+		Self {
+			block,
+			price1_cumulative: interpolate(self.price1_cumulative, next.price1_cumulative),
+			price2_cumulative: interpolate(self.price2_cumulative, next.price2_cumulative),
+		}
+	}
+}
+
+/// The proof size of reading the item of the storage `S` with the largest encoding, as estimated by
+/// the benchmarks for a map with unbounded `max_values`: the item and five trie layers of `15 * 33`
+/// bytes.
+fn worst_case_read_proof_size<S: StorageInfoTrait>() -> u64 {
+	S::storage_info()
+		.iter()
+		.map(|info| u64::from(info.max_size.unwrap_or_default()) + 5 * 15 * 33)
+		.sum()
+}
+
+/// Whether `asset1` is the first asset of its pool with `asset2`.
+fn is_first_asset<AssetKind: Encode>(asset1: &AssetKind, asset2: &AssetKind) -> bool {
+	asset1.using_encoded(|asset1| asset2.using_encoded(|asset2| asset1 <= asset2))
+}
+
+impl<T: Config> Pallet<T> {
+	/// The weight of updating the price accumulators of the pools along `hops` hops, on top of the
+	/// benchmarked weights of the swaps and liquidity changes.
+	///
+	/// Every hop reads the curve of its pool, reads and writes its accumulator and records an
+	/// observation.
+	pub fn price_accumulators_weight(hops: u32) -> Weight {
+		let proof_size = worst_case_read_proof_size::<PoolCurves<T>>()
+			.saturating_add(worst_case_read_proof_size::<PriceAccumulators<T>>());
+		T::DbWeight::get()
+			.reads_writes(2, 2)
+			.saturating_add(Weight::from_parts(0, proof_size))
+			.saturating_mul(hops.into())
+	}
+
+	/// The marginal prices of the assets of the pool `pool_id` left with `reserve1` of the first
+	/// one and `reserve2` of the second one, zero for an empty pool.
+	fn spot_prices(
+		pool_id: &T::PoolId,
+		reserve1: T::Balance,
+		reserve2: T::Balance,
+	) -> (FixedU128, FixedU128) {
+		let price = |reserve1: T::Balance, reserve2: T::Balance| {
+			// the inner value of the price, `FixedU128::DIV` of the first asset quoted in the
+			// second
+			let unit = T::HigherPrecisionBalance::from(1_000_000_000u32)
+				.checked_mul(&1_000_000_000u32.into())?;
+			let (reserve1, reserve2): (T::HigherPrecisionBalance, T::HigherPrecisionBalance) =
+				(reserve1.into(), reserve2.into());
+			let quote = match PoolCurves::<T>::get(pool_id) {
+				PoolCurve::ConstantProduct => ConstantProduct.spot_quote(unit, reserve1, reserve2),
+				PoolCurve::StableSwap(amplification) => StableSwap {
+					amplification: amplification.at(frame_system::Pallet::<T>::block_number()),
+				}
+				.spot_quote(unit, reserve1, reserve2),
+			}
+			.ok()?;
+			let quote: T::Balance = quote.try_into().ok()?;
+			Some(FixedU128::from_inner(quote.saturated_into()))
+		};
+		(
+			price(reserve1, reserve2).unwrap_or_default(),
+			price(reserve2, reserve1).unwrap_or_default(),
+		)
+	}
+
+	/// Accumulates the prices of the pool of `asset1` and `asset2` up to the current block, and
+	/// sets its prices from the `reserve1` and `reserve2` it is left with.
+	pub(crate) fn update_price_accumulator(
+		pool_id: &T::PoolId,
+		asset1: &T::AssetKind,
+		asset2: &T::AssetKind,
+		reserve1: T::Balance,
+		reserve2: T::Balance,
+	) {
+		let (reserve1, reserve2) = if is_first_asset(asset1, asset2) {
+			(reserve1, reserve2)
+		} else {
+			(reserve2, reserve1)
+		};
+		let (price1, price2) = Self::spot_prices(pool_id, reserve1, reserve2);
+		let now = frame_system::Pallet::<T>::block_number();
+
+		PriceAccumulators::<T>::mutate(pool_id, |maybe_accumulator| {
+			if let Some(accumulator) = maybe_accumulator {
+				if accumulator.cumulative.block < now {
+					let max_observations = T::MaxPriceObservations::get();
+					accumulator.cumulative = accumulator.observation_at(now);
+					accumulator.latest_observation =
+						accumulator.latest_observation.saturating_add(1) % max_observations;
+					accumulator.observations =
+						accumulator.observations.saturating_add(1).min(max_observations);
+					PriceObservations::<T>::insert(
+						pool_id,
+						accumulator.latest_observation,
+						accumulator.cumulative,
+					);
+				}
+				accumulator.price1 = price1;
+				accumulator.price2 = price2;
+			} else {
+				let cumulative =
+					PriceObservation { block: now, price1_cumulative: 0, price2_cumulative: 0 };
+				PriceObservations::<T>::insert(pool_id, 0, cumulative);
+				*maybe_accumulator = Some(PriceAccumulator {
+					cumulative,
+					price1,
+					price2,
+					latest_observation: 0,
+					observations: 1,
+				});
+			}
+		});
+	}
+
+	/// Updates the price accumulators of the pools along the `path` of a swap.
+	pub(crate) fn update_price_accumulators(path: &BalancePath<T>) {
+		for hop in path.windows(2) {
+			let [(asset1, _), (asset2, _)] = hop else { continue };
+			let Ok(pool_id) = T::PoolLocator::pool_id(asset1, asset2) else { continue };
+			let Ok(pool_account) = T::PoolLocator::address(&pool_id) else { continue };
+			let reserve1 = Self::get_balance(&pool_account, asset1.clone());
+			let reserve2 = Self::get_balance(&pool_account, asset2.clone());
+			Self::update_price_accumulator(&pool_id, asset1, asset2, reserve1, reserve2);
+		}
+	}
+
+	/// Returns the prices of the pool accumulated up to `block`, from the latest observation at or
+	/// before `block` and the one following it.
+	///
+	/// Returns `None` if `block` is before the oldest observation kept.
+	fn price_observation_at(
+		pool_id: &T::PoolId,
+		accumulator: &PriceAccumulator<BlockNumberFor<T>>,
+		block: BlockNumberFor<T>,
+	) -> Option<PriceObservation<BlockNumberFor<T>>> {
+		if block >= accumulator.cumulative.block {
+			return Some(accumulator.observation_at(block))
+		}
+
+		let max_observations = T::MaxPriceObservations::get();
+		let observations = accumulator.observations.min(max_observations);
+		// the slot of the `index`th oldest observation
+		let slot = |index: u32| {
+			(accumulator.latest_observation + max_observations - observations + 1 + index) %
+				max_observations
+		};
+
+		// the latest observation is `accumulator.cumulative`, after `block`
+		let (mut before, mut after) = (0, observations.checked_sub(1)?);
+		let mut observation_before = PriceObservations::<T>::get(pool_id, slot(before))?;
+		if observation_before.block > block {
+			return None
+		}
+		while after - before > 1 {
+			let middle = before + (after - before) / 2;
+			let observation = PriceObservations::<T>::get(pool_id, slot(middle))?;
+			if observation.block <= block {
+				before = middle;
+				observation_before = observation;
+			} else {
+				after = middle;
+			}
+		}
+		let observation_after = PriceObservations::<T>::get(pool_id, slot(after))?;
+
+		Some(observation_before.interpolate(&observation_after, block))
+	}
+
+	/// Returns the average price of `asset1` in `asset2` over the last `window` blocks, each price
+	/// weighted by the number of blocks it lasted for.
+	///
+	/// Returns `None` if the pool does not exist or its price was not observed for the whole
+	/// `window`.
+	pub fn time_weighted_average_price(
+		asset1: &T::AssetKind,
+		asset2: &T::AssetKind,
+		window: BlockNumberFor<T>,
+	) -> Option<FixedU128> {
+		if window.is_zero() {
+			return None
+		}
+
+		let pool_id = T::PoolLocator::pool_id(asset1, asset2).ok()?;
+		let accumulator = PriceAccumulators::<T>::get(&pool_id)?;
+		let now = frame_system::Pallet::<T>::block_number();
+		let start = Self::price_observation_at(&pool_id, &accumulator, now.checked_sub(&window)?)?;
+		let end = accumulator.observation_at(now);
+
+		let cumulative = if is_first_asset(asset1, asset2) {
+			end.price1_cumulative.wrapping_sub(start.price1_cumulative)
+		} else {
+			end.price2_cumulative.wrapping_sub(start.price2_cumulative)
+		};
+		Some(FixedU128::from_inner(cumulative / window.saturated_into::<u128>()))
+	}
+
+	/// Quotes the amount of `asset2` worth the `amount` of `asset1` at the
+	/// [`Self::time_weighted_average_price`] of the last `window` blocks.
+	pub fn quote_price_time_weighted(
+		asset1: T::AssetKind,
+		asset2: T::AssetKind,
+		amount: T::Balance,
+		window: BlockNumberFor<T>,
+	) -> Option<T::Balance> {
+		Self::time_weighted_average_price(&asset1, &asset2, window)?
+			.checked_mul_int(amount.saturated_into::<u128>())?
+			.try_into()
+			.ok()
+	}
+}
+
+impl<T: Config> TimeWeightedPrice for Pallet<T> {
+	type Balance = T::Balance;
+	type AssetKind = T::AssetKind;
+	type BlockNumber = BlockNumberFor<T>;
+	fn time_weighted_average_price(
+		asset1: Self::AssetKind,
+		asset2: Self::AssetKind,
+		window: Self::BlockNumber,
+	) -> Option<FixedU128> {
+		Self::time_weighted_average_price(&asset1, &asset2, window)
+	}
+	fn quote_price_time_weighted(
+		asset1: Self::AssetKind,
+		asset2: Self::AssetKind,
+		amount: Self::Balance,
+		window: Self::BlockNumber,
+	) -> Option<Self::Balance> {
+		Self::quote_price_time_weighted(asset1, asset2, amount, window)
+	}
+}
@@ -27,7 +27,8 @@ use frame_support::{
 		Get,
 	},
 };
-use sp_arithmetic::Permill;
+use sp_arithmetic::{traits::One, FixedPointNumber, FixedU128, Permill};
+use sp_core::U256;
 use sp_runtime::{DispatchError, TokenError};
 
 fn events() -> Vec<Event<Test>> {
@@ -2493,3 +2494,563 @@ fn swap_credit_invalid_path() {
 		assert_eq!(error, (expected_credit_in, Error::<Test>::InvalidPath.into()));
 	});
 }
+
+/// Creates a pool of the native asset and `token` with the given `curve` and adds `liquidity` of
+/// both assets to it.
+fn create_pool_with_liquidity(
+	user: u128,
+	token: NativeOrWithId<u32>,
+	curve: CurveKind,
+	liquidity: u128,
+) {
+	create_tokens(user, vec![token.clone()]);
+	assert_ok!(AssetConversion::create_pool_with_curve(
+		RuntimeOrigin::signed(user),
+		Box::new(NativeOrWithId::Native),
+		Box::new(token.clone()),
+		curve,
+	));
+
+	let native_balance = Balances::free_balance(user);
+	assert_ok!(Balances::force_set_balance(
+		RuntimeOrigin::root(),
+		user,
+		native_balance + liquidity + get_native_ed()
+	));
+	let asset_id = match token {
+		NativeOrWithId::WithId(id) => id,
+		_ => unreachable!("invalid token"),
+	};
+	assert_ok!(Assets::mint(RuntimeOrigin::signed(user), asset_id, user, liquidity * 2));
+
+	assert_ok!(AssetConversion::add_liquidity(
+		RuntimeOrigin::signed(user),
+		Box::new(NativeOrWithId::Native),
+		Box::new(token),
+		liquidity,
+		liquidity,
+		1,
+		1,
+		user,
+	));
+}
+
+#[test]
+fn can_create_stable_swap_pool() {
+	new_test_ext().execute_with(|| {
+		let user = 1;
+		let token_1 = NativeOrWithId::Native;
+		let token_2 = NativeOrWithId::WithId(2);
+		let pool_id = (token_1.clone(), token_2.clone());
+
+		create_tokens(user, vec![token_2.clone()]);
+		assert_ok!(AssetConversion::create_pool_with_curve(
+			RuntimeOrigin::signed(user),
+			Box::new(token_1.clone()),
+			Box::new(token_2.clone()),
+			CurveKind::StableSwap { amplification: 100 },
+		));
+
+		let curve = PoolCurve::StableSwap(Amplification::constant(100, 1));
+		assert_eq!(PoolCurves::<Test>::get(&pool_id), curve);
+		let events = events();
+		assert!(matches!(events[events.len() - 2], Event::PoolCreated { .. }));
+		assert_eq!(events[events.len() - 1], Event::<Test>::PoolCurveSet { pool_id, curve });
+	});
+}
+
+#[test]
+fn create_pool_keeps_constant_product_curve() {
+	new_test_ext().execute_with(|| {
+		let user = 1;
+		let token_1 = NativeOrWithId::Native;
+		let token_2 = NativeOrWithId::WithId(2);
+
+		create_tokens(user, vec![token_2.clone()]);
+		assert_ok!(AssetConversion::create_pool_with_curve(
+			RuntimeOrigin::signed(user),
+			Box::new(token_1.clone()),
+			Box::new(token_2.clone()),
+			CurveKind::ConstantProduct,
+		));
+
+		let pool_id = (token_1, token_2);
+		assert!(!PoolCurves::<Test>::contains_key(&pool_id));
+		assert!(!events().iter().any(|e| matches!(e, Event::PoolCurveSet { .. })));
+	});
+}
+
+#[test]
+fn create_stable_swap_pool_with_invalid_amplification_fails() {
+	new_test_ext().execute_with(|| {
+		let user = 1;
+		let token_1 = NativeOrWithId::Native;
+		let token_2 = NativeOrWithId::WithId(2);
+
+		create_tokens(user, vec![token_2.clone()]);
+		for amplification in [0, MAX_AMPLIFICATION + 1] {
+			assert_noop!(
+				AssetConversion::create_pool_with_curve(
+					RuntimeOrigin::signed(user),
+					Box::new(token_1.clone()),
+					Box::new(token_2.clone()),
+					CurveKind::StableSwap { amplification },
+				),
+				Error::<Test>::InvalidAmplification
+			);
+		}
+	});
+}
+
+#[test]
+fn stable_swap_pool_has_lower_price_impact() {
+	new_test_ext().execute_with(|| {
+		let user = 1;
+		let token_1 = NativeOrWithId::Native;
+		let token_2 = NativeOrWithId::WithId(2);
+		let token_3 = NativeOrWithId::WithId(3);
+
+		create_pool_with_liquidity(user, token_2.clone(), CurveKind::ConstantProduct, 10000);
+		create_pool_with_liquidity(
+			user,
+			token_3.clone(),
+			CurveKind::StableSwap { amplification: 100 },
+			10000,
+		);
+
+		let exchange_amount = 1000;
+		let constant_product_out = AssetConversion::quote_price_exact_tokens_for_tokens(
+			token_1.clone(),
+			token_2.clone(),
+			exchange_amount,
+			true,
+		)
+		.unwrap();
+		let stable_swap_out = AssetConversion::quote_price_exact_tokens_for_tokens(
+			token_1.clone(),
+			token_3.clone(),
+			exchange_amount,
+			true,
+		)
+		.unwrap();
+		assert_eq!(constant_product_out, 906);
+		assert!(stable_swap_out > 990 && stable_swap_out < exchange_amount);
+
+		// without the fee, the stable swap pool trades close to 1:1
+		let stable_swap_quote = AssetConversion::quote_price_exact_tokens_for_tokens(
+			token_1.clone(),
+			token_3.clone(),
+			exchange_amount,
+			false,
+		)
+		.unwrap();
+		assert!(stable_swap_quote > stable_swap_out && stable_swap_quote < exchange_amount);
+
+		let balance_before = balance(user, token_3.clone());
+		assert_ok!(AssetConversion::swap_exact_tokens_for_tokens(
+			RuntimeOrigin::signed(user),
+			bvec![token_1, token_3.clone()],
+			exchange_amount,
+			stable_swap_out,
+			user,
+			false,
+		));
+		assert_eq!(balance(user, token_3), balance_before + stable_swap_out);
+	});
+}
+
+#[test]
+fn stable_swap_amount_in_covers_amount_out() {
+	new_test_ext().execute_with(|| {
+		let user = 1;
+		let token_1 = NativeOrWithId::Native;
+		let token_2 = NativeOrWithId::WithId(2);
+
+		create_pool_with_liquidity(
+			user,
+			token_2.clone(),
+			CurveKind::StableSwap { amplification: 100 },
+			10000,
+		);
+
+		let amount_out = 500;
+		let amount_in = AssetConversion::quote_price_tokens_for_exact_tokens(
+			token_1.clone(),
+			token_2.clone(),
+			amount_out,
+			true,
+		)
+		.unwrap();
+		assert!(amount_in > amount_out);
+		let curve = StableSwap { amplification: 100 };
+		assert!(curve.amount_out(amount_in, 10000u128, 10000, 3).unwrap() >= amount_out);
+
+		// Without fees, the quote is the input of the swap rather than the output of the reverse
+		// swap, which differ on this curve.
+		let amount_in_without_fee = AssetConversion::quote_price_tokens_for_exact_tokens(
+			token_1.clone(),
+			token_2.clone(),
+			amount_out,
+			false,
+		)
+		.unwrap();
+		assert_eq!(
+			amount_in_without_fee,
+			curve.amount_in(amount_out, 10000u128, 10000, 0).unwrap()
+		);
+		assert!(amount_in_without_fee > amount_out && amount_in_without_fee < amount_in);
+
+		let balance_before = balance(user, token_2.clone());
+		assert_ok!(AssetConversion::swap_tokens_for_exact_tokens(
+			RuntimeOrigin::signed(user),
+			bvec![token_1, token_2.clone()],
+			amount_out,
+			amount_in,
+			user,
+			false,
+		));
+		assert_eq!(balance(user, token_2), balance_before + amount_out);
+	});
+}
+
+#[test]
+fn stable_swap_invariant_holds_for_imbalanced_reserves() {
+	let curve = StableSwap { amplification: 100 };
+	let d = curve.invariant(&1_000_000u128, &10_000).unwrap();
+	assert!(d > 10_000 * 2 && d < 1_010_000);
+	let y = curve.other_reserve(&1_000_000u128, &d).unwrap();
+	assert!(y.abs_diff(10_000) <= 1);
+
+	assert_eq!(curve.invariant(&0u128, &0), Ok(0));
+	assert_eq!(curve.amount_out(10u128, 0, 10, 0), Err(CurveError::ZeroLiquidity));
+	assert_eq!(curve.amount_in(10u128, 10, 10, 0), Err(CurveError::AmountOutTooHigh));
+}
+
+#[test]
+fn ramp_amplification_interpolates_and_stops() {
+	new_test_ext().execute_with(|| {
+		let user = 1;
+		let token_1 = NativeOrWithId::Native;
+		let token_2 = NativeOrWithId::WithId(2);
+		let pool_id = (token_1.clone(), token_2.clone());
+
+		create_pool_with_liquidity(
+			user,
+			token_2.clone(),
+			CurveKind::StableSwap { amplification: 100 },
+			10000,
+		);
+		let quote_before = AssetConversion::quote_price_exact_tokens_for_tokens(
+			token_1.clone(),
+			token_2.clone(),
+			5000,
+			false,
+		)
+		.unwrap();
+
+		assert_ok!(AssetConversion::ramp_amplification(
+			RuntimeOrigin::root(),
+			Box::new(token_1.clone()),
+			Box::new(token_2.clone()),
+			1000,
+			101,
+		));
+		let amplification =
+			Amplification { initial: 100, future: 1000, initial_block: 1, future_block: 101 };
+		assert_eq!(
+			events().last(),
+			Some(&Event::<Test>::AmplificationRampStarted {
+				pool_id: pool_id.clone(),
+				amplification
+			})
+		);
+
+		System::set_block_number(51);
+		let PoolCurve::StableSwap(ramp) = PoolCurves::<Test>::get(&pool_id) else {
+			panic!("not a StableSwap pool")
+		};
+		assert_eq!(ramp.at(1), 100);
+		assert_eq!(ramp.at(51), 550);
+		assert_eq!(ramp.at(200), 1000);
+
+		// a higher coefficient flattens the curve
+		let quote_during = AssetConversion::quote_price_exact_tokens_for_tokens(
+			token_1.clone(),
+			token_2.clone(),
+			5000,
+			false,
+		)
+		.unwrap();
+		assert!(quote_during > quote_before);
+
+		assert_ok!(AssetConversion::stop_ramp_amplification(
+			RuntimeOrigin::root(),
+			Box::new(token_1.clone()),
+			Box::new(token_2.clone()),
+		));
+		assert_eq!(
+			events().last(),
+			Some(&Event::<Test>::AmplificationRampStopped {
+				pool_id: pool_id.clone(),
+				amplification: 550
+			})
+		);
+
+		System::set_block_number(101);
+		assert_eq!(
+			PoolCurves::<Test>::get(&pool_id),
+			PoolCurve::StableSwap(Amplification::constant(550, 51))
+		);
+	});
+}
+
+#[test]
+fn ramp_amplification_is_validated() {
+	new_test_ext().execute_with(|| {
+		let user = 1;
+		let token_1 = NativeOrWithId::Native;
+		let token_2 = NativeOrWithId::WithId(2);
+		let token_3 = NativeOrWithId::WithId(3);
+
+		create_pool_with_liquidity(
+			user,
+			token_2.clone(),
+			CurveKind::StableSwap { amplification: 100 },
+			10000,
+		);
+		create_pool_with_liquidity(user, token_3.clone(), CurveKind::ConstantProduct, 10000);
+
+		let ramp = |origin, token: &NativeOrWithId<u32>, amplification, block| {
+			AssetConversion::ramp_amplification(
+				origin,
+				Box::new(token_1.clone()),
+				Box::new(token.clone()),
+				amplification,
+				block,
+			)
+		};
+
+		assert_noop!(
+			ramp(RuntimeOrigin::signed(user), &token_2, 200, 101),
+			DispatchError::BadOrigin
+		);
+		assert_noop!(
+			ramp(RuntimeOrigin::root(), &token_3, 200, 101),
+			Error::<Test>::NotStableSwapPool
+		);
+		assert_noop!(
+			ramp(RuntimeOrigin::root(), &NativeOrWithId::WithId(4), 200, 101),
+			Error::<Test>::PoolNotFound
+		);
+		assert_noop!(
+			ramp(RuntimeOrigin::root(), &token_2, 0, 101),
+			Error::<Test>::InvalidAmplification
+		);
+		assert_noop!(
+			ramp(RuntimeOrigin::root(), &token_2, 1001, 101),
+			Error::<Test>::InvalidAmplificationRamp
+		);
+		assert_noop!(
+			ramp(RuntimeOrigin::root(), &token_2, 9, 101),
+			Error::<Test>::InvalidAmplificationRamp
+		);
+		assert_noop!(
+			ramp(RuntimeOrigin::root(), &token_2, 200, 1),
+			Error::<Test>::InvalidAmplificationRamp
+		);
+		assert_noop!(
+			AssetConversion::stop_ramp_amplification(
+				RuntimeOrigin::root(),
+				Box::new(token_1.clone()),
+				Box::new(token_3.clone()),
+			),
+			Error::<Test>::NotStableSwapPool
+		);
+	});
+}
+
+/// Swaps `amount` of the native asset for `token` in their pool.
+fn swap_native_for(user: u128, token: NativeOrWithId<u32>, amount: u128) {
+	assert_ok!(AssetConversion::swap_exact_tokens_for_tokens(
+		RuntimeOrigin::signed(user),
+		bvec![NativeOrWithId::Native, token],
+		amount,
+		1,
+		user,
+		false,
+	));
+}
+
+/// The prices of the native asset in `token` and of `token` in the native asset given the
+/// reserves of their pool.
+fn spot_prices(token: NativeOrWithId<u32>) -> (FixedU128, FixedU128) {
+	let (reserve1, reserve2) =
+		AssetConversion::get_reserves(NativeOrWithId::Native, token).unwrap();
+	(
+		FixedU128::checked_from_rational(reserve2, reserve1).unwrap(),
+		FixedU128::checked_from_rational(reserve1, reserve2).unwrap(),
+	)
+}
+
+#[test]
+fn time_weighted_average_price_needs_observed_window() {
+	new_test_ext().execute_with(|| {
+		let user = 1;
+		let token_1 = NativeOrWithId::Native;
+		let token_2 = NativeOrWithId::WithId(2);
+
+		create_pool_with_liquidity(user, token_2.clone(), CurveKind::ConstantProduct, 10000);
+		assert_eq!(AssetConversion::time_weighted_average_price(&token_1, &token_2, 1), None);
+
+		System::set_block_number(11);
+		assert_eq!(
+			AssetConversion::time_weighted_average_price(&token_1, &token_2, 10),
+			Some(FixedU128::one())
+		);
+		assert_eq!(AssetConversion::time_weighted_average_price(&token_1, &token_2, 11), None);
+		assert_eq!(AssetConversion::time_weighted_average_price(&token_1, &token_2, 0), None);
+		assert_eq!(
+			AssetConversion::time_weighted_average_price(&token_1, &NativeOrWithId::WithId(3), 10),
+			None
+		);
+	});
+}
+
+#[test]
+fn time_weighted_average_price_weights_prices_by_blocks() {
+	new_test_ext().execute_with(|| {
+		let user = 1;
+		let token_1 = NativeOrWithId::Native;
+		let token_2 = NativeOrWithId::WithId(2);
+
+		create_pool_with_liquidity(user, token_2.clone(), CurveKind::ConstantProduct, 10000);
+		let (price_before, inverse_price_before) = spot_prices(token_2.clone());
+
+		System::set_block_number(11);
+		swap_native_for(user, token_2.clone(), 1000);
+		let (price_after, inverse_price_after) = spot_prices(token_2.clone());
+		assert!(price_after < price_before);
+
+		System::set_block_number(21);
+		let average = |prices: &[(FixedU128, u128)]| {
+			let blocks: u128 = prices.iter().map(|(_, blocks)| blocks).sum();
+			let cumulative: u128 =
+				prices.iter().map(|(price, blocks)| price.into_inner() * blocks).sum();
+			FixedU128::from_inner(cumulative / blocks)
+		};
+		assert_eq!(
+			AssetConversion::time_weighted_average_price(&token_1, &token_2, 20),
+			Some(average(&[(price_before, 10), (price_after, 10)]))
+		);
+		// the window starts between two observations
+		assert_eq!(
+			AssetConversion::time_weighted_average_price(&token_1, &token_2, 15),
+			Some(average(&[(price_before, 5), (price_after, 10)]))
+		);
+		assert_eq!(
+			AssetConversion::time_weighted_average_price(&token_1, &token_2, 5),
+			Some(price_after)
+		);
+
+		// the price of the other asset is averaged on its own rather than inverted
+		assert_eq!(
+			AssetConversion::time_weighted_average_price(&token_2, &token_1, 20),
+			Some(average(&[(inverse_price_before, 10), (inverse_price_after, 10)]))
+		);
+
+		assert_eq!(
+			AssetConversion::quote_price_time_weighted(token_1, token_2, 1000, 5),
+			Some(price_after.saturating_mul_int(1000))
+		);
+	});
+}
+
+#[test]
+fn time_weighted_average_price_ignores_price_moved_within_block() {
+	new_test_ext().execute_with(|| {
+		let user = 1;
+		let token_1 = NativeOrWithId::Native;
+		let token_2 = NativeOrWithId::WithId(2);
+
+		create_pool_with_liquidity(user, token_2.clone(), CurveKind::ConstantProduct, 10000);
+
+		System::set_block_number(11);
+		swap_native_for(user, token_2.clone(), 5000);
+		assert!(spot_prices(token_2.clone()).0 < FixedU128::one());
+		assert_eq!(
+			AssetConversion::time_weighted_average_price(&token_1, &token_2, 10),
+			Some(FixedU128::one())
+		);
+		assert_eq!(
+			<AssetConversion as TimeWeightedPrice>::quote_price_time_weighted(
+				token_1, token_2, 1000, 10
+			),
+			Some(1000)
+		);
+	});
+}
+
+#[test]
+fn time_weighted_average_price_follows_stable_swap_curve() {
+	new_test_ext().execute_with(|| {
+		let user = 1;
+		let token_1 = NativeOrWithId::Native;
+		let token_2 = NativeOrWithId::WithId(2);
+		let amplification = 100;
+
+		create_pool_with_liquidity(
+			user,
+			token_2.clone(),
+			CurveKind::StableSwap { amplification },
+			10000,
+		);
+		swap_native_for(user, token_2.clone(), 5000);
+		let (reserve1, reserve2) =
+			AssetConversion::get_reserves(token_1.clone(), token_2.clone()).unwrap();
+
+		System::set_block_number(11);
+		let unit = U256::from(FixedU128::DIV);
+		let curve = StableSwap { amplification };
+		let spot_price = |reserve1: u128, reserve2: u128| {
+			let quote = curve.spot_quote(unit, U256::from(reserve1), U256::from(reserve2)).unwrap();
+			FixedU128::from_inner(quote.as_u128())
+		};
+		let price = AssetConversion::time_weighted_average_price(&token_1, &token_2, 10).unwrap();
+		assert_eq!(price, spot_price(reserve1, reserve2));
+		// the marginal price of the curve, much closer to 1:1 than the ratio of the reserves
+		assert!(price < FixedU128::one());
+		assert!(price > spot_prices(token_2.clone()).0.saturating_mul(FixedU128::from(2)));
+		assert_eq!(
+			AssetConversion::time_weighted_average_price(&token_2, &token_1, 10),
+			Some(spot_price(reserve2, reserve1))
+		);
+	});
+}
+
+#[test]
+fn price_observations_are_bounded() {
+	new_test_ext().execute_with(|| {
+		let user = 1;
+		let token_1 = NativeOrWithId::Native;
+		let token_2 = NativeOrWithId::WithId(2);
+		let pool_id = (token_1.clone(), token_2.clone());
+		let max_observations: u32 = <Test as Config>::MaxPriceObservations::get();
+
+		create_pool_with_liquidity(user, token_2.clone(), CurveKind::ConstantProduct, 10000);
+		for block in 2..=max_observations as u64 + 1 {
+			System::set_block_number(block);
+			swap_native_for(user, token_2.clone(), 10);
+			// a single observation per block
+			swap_native_for(user, token_2.clone(), 10);
+		}
+		assert_eq!(
+			PriceObservations::<Test>::iter_prefix(&pool_id).count(),
+			max_observations as usize
+		);
+
+		// the observation of the first block was overwritten
+		System::set_block_number(10);
+		assert_eq!(AssetConversion::time_weighted_average_price(&token_1, &token_2, 9), None);
+		assert!(AssetConversion::time_weighted_average_price(&token_1, &token_2, 8).is_some());
+	});
+}
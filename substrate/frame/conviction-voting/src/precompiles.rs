@@ -0,0 +1,328 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A precompile exposing conviction voting to contracts.
+
+use crate::{
+	AccountVote, BalanceOf, ClassOf, Config, Conviction, Pallet, PollIndexOf, Vote, WeightInfo,
+};
+use alloc::vec::Vec;
+use core::{marker::PhantomData, num::NonZero};
+use frame_support::traits::Get;
+use pallet_revive::precompiles::{
+	alloy::{self, primitives::Address},
+	dispatch, dispatch_with_refund, evm_to_native, AddressMapper, AddressMatcher, Error, Ext,
+	Precompile, H160,
+};
+use sp_runtime::traits::StaticLookup;
+
+alloy::sol!("src/precompiles/IConvictionVoting.sol");
+use IConvictionVoting::IConvictionVotingCalls;
+
+const ERR_BALANCE_CONVERSION_FAILED: &str = "Balance conversion failed";
+const ERR_INVALID_CONVICTION: &str = "Invalid conviction";
+const ERR_INVALID_TRACK: &str = "Invalid track id";
+const ERR_INVALID_POLL: &str = "Invalid poll index";
+
+/// The conviction voting precompile, available at the fixed address `0x0D`.
+///
+/// Votes and delegations are made on behalf of the calling contract.
+pub struct ConvictionVotingPrecompile<Runtime, Instance = ()>(PhantomData<(Runtime, Instance)>);
+
+impl<Runtime, Instance: 'static> Precompile for ConvictionVotingPrecompile<Runtime, Instance>
+where
+	Runtime: Config<Instance> + pallet_revive::Config,
+	ClassOf<Runtime, Instance>: TryFrom<u16>,
+	PollIndexOf<Runtime, Instance>: TryFrom<u32>,
+	alloy::primitives::U256: TryInto<BalanceOf<Runtime, Instance>>,
+{
+	type T = Runtime;
+	const MATCHER: AddressMatcher = AddressMatcher::Fixed(NonZero::new(13).unwrap());
+	const HAS_CONTRACT_INFO: bool = false;
+	type Interface = IConvictionVoting::IConvictionVotingCalls;
+
+	fn call(
+		_address: &[u8; 20],
+		input: &Self::Interface,
+		env: &mut impl Ext<T = Self::T>,
+	) -> Result<Vec<u8>, Error> {
+		let vote_weight = <Runtime as Config<Instance>>::WeightInfo::vote_new()
+			.max(<Runtime as Config<Instance>>::WeightInfo::vote_existing());
+
+		match input {
+			IConvictionVotingCalls::vote(IConvictionVoting::voteCall {
+				pollIndex: poll_index,
+				aye,
+				conviction,
+				balance,
+			}) => {
+				let poll_index = Self::to_poll_index(*poll_index)?;
+				let vote = AccountVote::Standard {
+					vote: Vote { aye: *aye, conviction: Self::to_conviction(*conviction)? },
+					balance: Self::to_balance(*balance)?,
+				};
+				dispatch(env, vote_weight, |origin| {
+					Pallet::<Runtime, Instance>::vote(origin, poll_index, vote)
+				})
+			},
+			IConvictionVotingCalls::voteSplit(IConvictionVoting::voteSplitCall {
+				pollIndex: poll_index,
+				aye,
+				nay,
+			}) => {
+				let poll_index = Self::to_poll_index(*poll_index)?;
+				let vote = AccountVote::Split {
+					aye: Self::to_balance(*aye)?,
+					nay: Self::to_balance(*nay)?,
+				};
+				dispatch(env, vote_weight, |origin| {
+					Pallet::<Runtime, Instance>::vote(origin, poll_index, vote)
+				})
+			},
+			IConvictionVotingCalls::voteSplitAbstain(IConvictionVoting::voteSplitAbstainCall {
+				pollIndex: poll_index,
+				aye,
+				nay,
+				abstain,
+			}) => {
+				let poll_index = Self::to_poll_index(*poll_index)?;
+				let vote = AccountVote::SplitAbstain {
+					aye: Self::to_balance(*aye)?,
+					nay: Self::to_balance(*nay)?,
+					abstain: Self::to_balance(*abstain)?,
+				};
+				dispatch(env, vote_weight, |origin| {
+					Pallet::<Runtime, Instance>::vote(origin, poll_index, vote)
+				})
+			},
+			IConvictionVotingCalls::removeVote(IConvictionVoting::removeVoteCall {
+				trackId: track_id,
+				pollIndex: poll_index,
+			}) => {
+				let class = Self::to_class(*track_id)?;
+				let poll_index = Self::to_poll_index(*poll_index)?;
+				dispatch(env, <Runtime as Config<Instance>>::WeightInfo::remove_vote(), |origin| {
+					Pallet::<Runtime, Instance>::remove_vote(origin, Some(class), poll_index)
+				})
+			},
+			IConvictionVotingCalls::delegate(IConvictionVoting::delegateCall {
+				trackId: track_id,
+				to,
+				conviction,
+				balance,
+			}) => {
+				let class = Self::to_class(*track_id)?;
+				let to = Self::to_lookup(to);
+				let conviction = Self::to_conviction(*conviction)?;
+				let balance = Self::to_balance(*balance)?;
+				dispatch_with_refund(
+					env,
+					<Runtime as Config<Instance>>::WeightInfo::delegate(Runtime::MaxVotes::get()),
+					|origin| {
+						Pallet::<Runtime, Instance>::delegate(
+							origin, class, to, conviction, balance,
+						)
+					},
+				)
+			},
+			IConvictionVotingCalls::undelegate(IConvictionVoting::undelegateCall {
+				trackId: track_id,
+			}) => {
+				let class = Self::to_class(*track_id)?;
+				dispatch_with_refund(
+					env,
+					<Runtime as Config<Instance>>::WeightInfo::undelegate(Runtime::MaxVotes::get()),
+					|origin| Pallet::<Runtime, Instance>::undelegate(origin, class),
+				)
+			},
+			IConvictionVotingCalls::unlock(IConvictionVoting::unlockCall {
+				trackId: track_id,
+				target,
+			}) => {
+				let class = Self::to_class(*track_id)?;
+				let target = Self::to_lookup(target);
+				dispatch(env, <Runtime as Config<Instance>>::WeightInfo::unlock(), |origin| {
+					Pallet::<Runtime, Instance>::unlock(origin, class, target)
+				})
+			},
+		}
+	}
+}
+
+impl<Runtime, Instance: 'static> ConvictionVotingPrecompile<Runtime, Instance>
+where
+	Runtime: Config<Instance> + pallet_revive::Config,
+	ClassOf<Runtime, Instance>: TryFrom<u16>,
+	PollIndexOf<Runtime, Instance>: TryFrom<u32>,
+	alloy::primitives::U256: TryInto<BalanceOf<Runtime, Instance>>,
+{
+	/// Map an Ethereum address to the lookup source of its account.
+	fn to_lookup(address: &Address) -> <Runtime::Lookup as StaticLookup>::Source {
+		let account = <Runtime as pallet_revive::Config>::AddressMapper::to_account_id(
+			&H160::from(address.into_array()),
+		);
+		Runtime::Lookup::unlookup(account)
+	}
+
+	/// Convert a `U256` value in ETH decimals to the balance type of the pallet.
+	fn to_balance(value: alloy::primitives::U256) -> Result<BalanceOf<Runtime, Instance>, Error> {
+		evm_to_native::<Runtime>(value)?
+			.try_into()
+			.map_err(|_| Error::Revert(ERR_BALANCE_CONVERSION_FAILED.into()))
+	}
+
+	/// Convert a conviction multiplier to a [`Conviction`].
+	fn to_conviction(conviction: u8) -> Result<Conviction, Error> {
+		conviction.try_into().map_err(|_| Error::Revert(ERR_INVALID_CONVICTION.into()))
+	}
+
+	/// Convert a track id to the class of the polls.
+	fn to_class(track_id: u16) -> Result<ClassOf<Runtime, Instance>, Error> {
+		track_id.try_into().map_err(|_| Error::Revert(ERR_INVALID_TRACK.into()))
+	}
+
+	/// Convert a poll index to the index type of the polls.
+	fn to_poll_index(poll_index: u32) -> Result<PollIndexOf<Runtime, Instance>, Error> {
+		poll_index.try_into().map_err(|_| Error::Revert(ERR_INVALID_POLL.into()))
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::{
+		tests::{new_test_ext, RuntimeOrigin, Test, TestPolls},
+		Tally, TallyOf, Voting, VotingFor,
+	};
+	use frame_support::{traits::Polling, weights::Weight};
+	use pallet_revive::{
+		precompiles::alloy::{hex, primitives::U256, sol_types::SolInterface},
+		DepositLimit, ExecReturnValue,
+	};
+
+	fn precompile_address() -> H160 {
+		H160::from(hex::const_decode_to_array(b"00000000000000000000000000000000000D0000").unwrap())
+	}
+
+	fn address_of(account: u64) -> Address {
+		<Test as pallet_revive::Config>::AddressMapper::to_address(&account).0.into()
+	}
+
+	fn call(origin: u64, call: IConvictionVotingCalls) -> ExecReturnValue {
+		pallet_revive::Pallet::<Test>::bare_call(
+			RuntimeOrigin::signed(origin),
+			precompile_address(),
+			0u64,
+			Weight::MAX,
+			DepositLimit::UnsafeOnlyForDryRun,
+			call.abi_encode(),
+		)
+		.result
+		.unwrap()
+	}
+
+	fn tally(index: u8) -> TallyOf<Test> {
+		<TestPolls as Polling<TallyOf<Test>>>::as_ongoing(index).expect("No poll").0
+	}
+
+	#[test]
+	fn vote_and_remove_vote_works() {
+		new_test_ext().execute_with(|| {
+			let result = call(
+				1,
+				IConvictionVotingCalls::vote(IConvictionVoting::voteCall {
+					pollIndex: 3,
+					aye: true,
+					conviction: 5,
+					balance: U256::from(2),
+				}),
+			);
+			assert!(!result.did_revert());
+			assert_eq!(tally(3), Tally::from_parts(10, 0, 2));
+
+			let result = call(
+				1,
+				IConvictionVotingCalls::removeVote(IConvictionVoting::removeVoteCall {
+					trackId: 0,
+					pollIndex: 3,
+				}),
+			);
+			assert!(!result.did_revert());
+			assert_eq!(tally(3), Tally::from_parts(0, 0, 0));
+		});
+	}
+
+	#[test]
+	fn split_abstain_vote_works() {
+		new_test_ext().execute_with(|| {
+			let result = call(
+				1,
+				IConvictionVotingCalls::voteSplitAbstain(IConvictionVoting::voteSplitAbstainCall {
+					pollIndex: 3,
+					aye: U256::from(0),
+					nay: U256::from(0),
+					abstain: U256::from(10),
+				}),
+			);
+			assert!(!result.did_revert());
+			assert_eq!(tally(3), Tally::from_parts(0, 0, 10));
+		});
+	}
+
+	#[test]
+	fn delegate_and_undelegate_works() {
+		new_test_ext().execute_with(|| {
+			let result = call(
+				2,
+				IConvictionVotingCalls::delegate(IConvictionVoting::delegateCall {
+					trackId: 0,
+					to: address_of(1),
+					conviction: 1,
+					balance: U256::from(20),
+				}),
+			);
+			assert!(!result.did_revert());
+			assert!(matches!(VotingFor::<Test>::get(2, 0), Voting::Delegating(_)));
+
+			let result = call(
+				2,
+				IConvictionVotingCalls::undelegate(IConvictionVoting::undelegateCall {
+					trackId: 0,
+				}),
+			);
+			assert!(!result.did_revert());
+			assert!(matches!(VotingFor::<Test>::get(2, 0), Voting::Casting(_)));
+		});
+	}
+
+	#[test]
+	fn invalid_conviction_reverts() {
+		new_test_ext().execute_with(|| {
+			let result = call(
+				1,
+				IConvictionVotingCalls::vote(IConvictionVoting::voteCall {
+					pollIndex: 3,
+					aye: true,
+					conviction: 7,
+					balance: U256::from(2),
+				}),
+			);
+			assert!(result.did_revert());
+			assert_eq!(tally(3), Tally::from_parts(0, 0, 0));
+		});
+	}
+}
@@ -0,0 +1,47 @@
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.20;
+
+/// @title Defines all functions that can be used to vote on polls with conviction
+/// @dev Balances are in ETH decimals (18), like the balance of an account
+interface IConvictionVoting {
+    /// @notice Vote in a poll, locking `balance` with the given conviction
+    /// @param pollIndex The index of the poll
+    /// @param aye Whether the vote approves the poll
+    /// @param conviction The conviction multiplier, from 0 (none) to 6 (locked 32x)
+    /// @param balance The balance backing the vote
+    function vote(uint32 pollIndex, bool aye, uint8 conviction, uint256 balance) external;
+
+    /// @notice Vote in a poll, splitting the balance between aye and nay, without conviction
+    /// @param pollIndex The index of the poll
+    /// @param aye The balance voting aye
+    /// @param nay The balance voting nay
+    function voteSplit(uint32 pollIndex, uint256 aye, uint256 nay) external;
+
+    /// @notice Vote in a poll, splitting the balance between aye, nay and abstain, without conviction
+    /// @param pollIndex The index of the poll
+    /// @param aye The balance voting aye
+    /// @param nay The balance voting nay
+    /// @param abstain The balance abstaining
+    function voteSplitAbstain(uint32 pollIndex, uint256 aye, uint256 nay, uint256 abstain) external;
+
+    /// @notice Remove the vote of the caller in a poll
+    /// @param trackId The class (track) of the poll
+    /// @param pollIndex The index of the poll
+    function removeVote(uint16 trackId, uint32 pollIndex) external;
+
+    /// @notice Delegate the voting power of the caller for a class (track) of polls
+    /// @param trackId The class (track) of polls to delegate
+    /// @param to The account to delegate to
+    /// @param conviction The conviction multiplier, from 0 (none) to 6 (locked 32x)
+    /// @param balance The balance to delegate
+    function delegate(uint16 trackId, address to, uint8 conviction, uint256 balance) external;
+
+    /// @notice Undelegate the voting power of the caller for a class (track) of polls
+    /// @param trackId The class (track) of polls to undelegate
+    function undelegate(uint16 trackId) external;
+
+    /// @notice Remove the expired voting locks of `target` for a class (track) of polls
+    /// @param trackId The class (track) of polls
+    /// @param target The account to unlock
+    function unlock(uint16 trackId, address target) external;
+}
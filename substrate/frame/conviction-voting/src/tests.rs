@@ -36,6 +36,7 @@ frame_support::construct_runtime!(
 		System: frame_system,
 		Balances: pallet_balances,
 		Voting: pallet_conviction_voting,
+		Revive: pallet_revive,
 	}
 );
 
@@ -59,6 +60,13 @@ impl pallet_balances::Config for Test {
 	type AccountStore = System;
 }
 
+#[derive_impl(pallet_revive::config_preludes::TestDefaultConfig)]
+impl pallet_revive::Config for Test {
+	type AddressMapper = pallet_revive::TestAccountMapper<Self>;
+	type Currency = Balances;
+	type Precompiles = (crate::precompiles::ConvictionVotingPrecompile<Self>,);
+}
+
 #[derive(Clone, PartialEq, Eq, Debug)]
 pub enum TestPollState {
 	Ongoing(TallyOf<Test>, u8),
@@ -107,6 +107,14 @@ impl pallet_scheduler::Config for Test {
 	type OriginPrivilegeCmp = EqualPrivilegeOnly;
 	type Preimages = ();
 	type BlockNumberProvider = frame_system::Pallet<Test>;
+	type UnixTime = NoTime;
+}
+
+pub struct NoTime;
+impl frame_support::traits::UnixTime for NoTime {
+	fn now() -> core::time::Duration {
+		Default::default()
+	}
 }
 
 #[derive_impl(pallet_balances::config_preludes::TestDefaultConfig)]
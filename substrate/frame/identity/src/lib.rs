@@ -109,6 +109,7 @@
 mod benchmarking;
 pub mod legacy;
 pub mod migration;
+pub mod precompiles;
 #[cfg(test)]
 mod tests;
 mod types;
@@ -0,0 +1,320 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A precompile exposing the identities to contracts.
+
+use crate::{BalanceOf, Config, Data, IdentityOf, Judgement, Registration, SubsOf, SuperOf};
+use alloc::vec::Vec;
+use codec::Encode;
+use core::{marker::PhantomData, num::NonZero};
+use frame_support::BoundedVec;
+use pallet_revive::precompiles::{
+	alloy::{
+		self,
+		primitives::{Address, U256},
+		sol_types::{Revert, SolCall},
+	},
+	charge_read, native_to_evm, AddressMapper, AddressMatcher, Error, Ext, Precompile, H160,
+};
+
+alloy::sol!("src/precompiles/IIdentity.sol");
+use IIdentity::IIdentityCalls;
+
+const ERR_BALANCE_CONVERSION_FAILED: &str = "Balance conversion failed";
+
+/// The identity precompile, available at the fixed address `0x0C`.
+///
+/// Identities are read-only from contracts. Accounts that are not mapped to an Ethereum address
+/// are returned at their fallback address.
+pub struct IdentityPrecompile<T>(PhantomData<T>);
+
+impl<Runtime> Precompile for IdentityPrecompile<Runtime>
+where
+	Runtime: Config + pallet_revive::Config,
+	U256: TryFrom<BalanceOf<Runtime>>,
+{
+	type T = Runtime;
+	const MATCHER: AddressMatcher = AddressMatcher::Fixed(NonZero::new(12).unwrap());
+	const HAS_CONTRACT_INFO: bool = false;
+	type Interface = IIdentity::IIdentityCalls;
+
+	fn call(
+		_address: &[u8; 20],
+		input: &Self::Interface,
+		env: &mut impl Ext<T = Self::T>,
+	) -> Result<Vec<u8>, Error> {
+		match input {
+			IIdentityCalls::identityOf(IIdentity::identityOfCall { who }) => {
+				charge_read::<Runtime, RegistrationOf<Runtime>>(env)?;
+				let registration = match IdentityOf::<Runtime>::get(Self::to_account_id(who)) {
+					Some(registration) => IIdentity::Registration {
+						exists: true,
+						judgements: registration
+							.judgements
+							.iter()
+							.map(|(index, judgement)| Self::to_judgement(*index, judgement))
+							.collect::<Result<_, _>>()?,
+						deposit: Self::to_u256(registration.deposit)?,
+						info: registration.info.encode().into(),
+					},
+					None => IIdentity::Registration {
+						exists: false,
+						judgements: Vec::new(),
+						deposit: U256::ZERO,
+						info: Default::default(),
+					},
+				};
+				Ok(IIdentity::identityOfCall::abi_encode_returns(&registration))
+			},
+			IIdentityCalls::superOf(IIdentity::superOfCall { who }) => {
+				charge_read::<Runtime, (Runtime::AccountId, Data)>(env)?;
+				let ret = match SuperOf::<Runtime>::get(Self::to_account_id(who)) {
+					Some((parent, name)) => IIdentity::superOfReturn {
+						exists: true,
+						parent: Self::to_address(&parent),
+						name: Self::data_to_bytes(&name).into(),
+					},
+					None => IIdentity::superOfReturn {
+						exists: false,
+						parent: Address::ZERO,
+						name: Default::default(),
+					},
+				};
+				Ok(IIdentity::superOfCall::abi_encode_returns(&ret))
+			},
+			IIdentityCalls::subsOf(IIdentity::subsOfCall { who }) => {
+				charge_read::<
+					Runtime,
+					(BalanceOf<Runtime>, BoundedVec<Runtime::AccountId, Runtime::MaxSubAccounts>),
+				>(env)?;
+				let (_, subs) = SubsOf::<Runtime>::get(Self::to_account_id(who));
+				let subs = subs.iter().map(Self::to_address).collect::<Vec<_>>();
+				Ok(IIdentity::subsOfCall::abi_encode_returns(&subs))
+			},
+			IIdentityCalls::isVerified(IIdentity::isVerifiedCall { who }) => {
+				charge_read::<Runtime, RegistrationOf<Runtime>>(env)?;
+				let is_verified =
+					IdentityOf::<Runtime>::get(Self::to_account_id(who)).is_some_and(|id| {
+						id.judgements.iter().any(|(_, judgement)| {
+							matches!(judgement, Judgement::Reasonable | Judgement::KnownGood)
+						})
+					});
+				Ok(IIdentity::isVerifiedCall::abi_encode_returns(&is_verified))
+			},
+		}
+	}
+}
+
+/// The registration of an identity, as stored by the pallet.
+type RegistrationOf<T> =
+	Registration<BalanceOf<T>, <T as Config>::MaxRegistrars, <T as Config>::IdentityInformation>;
+
+impl<Runtime> IdentityPrecompile<Runtime>
+where
+	Runtime: Config + pallet_revive::Config,
+	U256: TryFrom<BalanceOf<Runtime>>,
+{
+	/// Map an Ethereum address to its account.
+	fn to_account_id(address: &Address) -> Runtime::AccountId {
+		<Runtime as pallet_revive::Config>::AddressMapper::to_account_id(&H160::from(
+			address.into_array(),
+		))
+	}
+
+	/// Map an account to its Ethereum address.
+	fn to_address(account: &Runtime::AccountId) -> Address {
+		<Runtime as pallet_revive::Config>::AddressMapper::to_address(account).0.into()
+	}
+
+	/// Convert a balance to a `U256` value in ETH decimals.
+	fn to_u256(value: BalanceOf<Runtime>) -> Result<U256, Error> {
+		let value = U256::try_from(value)
+			.map_err(|_| Error::Revert(Revert { reason: ERR_BALANCE_CONVERSION_FAILED.into() }))?;
+		native_to_evm::<Runtime>(value)
+	}
+
+	/// Convert the judgement of a registrar to its Solidity representation.
+	fn to_judgement(
+		registrar_index: u32,
+		judgement: &Judgement<BalanceOf<Runtime>>,
+	) -> Result<IIdentity::Judgement, Error> {
+		let (kind, fee) = match judgement {
+			Judgement::Unknown => (0, U256::ZERO),
+			Judgement::FeePaid(fee) => (1, Self::to_u256(*fee)?),
+			Judgement::Reasonable => (2, U256::ZERO),
+			Judgement::KnownGood => (3, U256::ZERO),
+			Judgement::OutOfDate => (4, U256::ZERO),
+			Judgement::LowQuality => (5, U256::ZERO),
+			Judgement::Erroneous => (6, U256::ZERO),
+		};
+		Ok(IIdentity::Judgement { registrarIndex: registrar_index, kind, fee })
+	}
+
+	/// The raw name, or the hash of the name if only the hash was published.
+	fn data_to_bytes(data: &Data) -> Vec<u8> {
+		match data {
+			Data::None => Vec::new(),
+			Data::Raw(raw) => raw.to_vec(),
+			Data::BlakeTwo256(hash) |
+			Data::Sha256(hash) |
+			Data::Keccak256(hash) |
+			Data::ShaThree256(hash) => hash.to_vec(),
+		}
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::{
+		legacy::IdentityInfo,
+		tests::{new_test_ext, Balances, Identity, RuntimeOrigin, Test},
+	};
+	use alloc::{boxed::Box, vec};
+	use frame_support::{assert_ok, traits::Currency, weights::Weight};
+	use pallet_revive::{
+		precompiles::alloy::{hex, sol_types::SolInterface},
+		DepositLimit, ExecReturnValue,
+	};
+	use sp_runtime::traits::{BlakeTwo256, Hash};
+
+	type AccountId = <Test as frame_system::Config>::AccountId;
+
+	fn precompile_address() -> H160 {
+		H160::from(hex::const_decode_to_array(b"00000000000000000000000000000000000C0000").unwrap())
+	}
+
+	/// An account derived from an Ethereum address, which doesn't need to be mapped.
+	fn eth_account(byte: u8) -> AccountId {
+		let account = <Test as pallet_revive::Config>::AddressMapper::to_fallback_account_id(
+			&H160::repeat_byte(byte),
+		);
+		Balances::make_free_balance_be(&account, 1000);
+		account
+	}
+
+	fn address_of(account: &AccountId) -> Address {
+		<Test as pallet_revive::Config>::AddressMapper::to_address(account).0.into()
+	}
+
+	fn call(origin: &AccountId, call: IIdentityCalls) -> ExecReturnValue {
+		pallet_revive::Pallet::<Test>::bare_call(
+			RuntimeOrigin::signed(origin.clone()),
+			precompile_address(),
+			0u64,
+			Weight::MAX,
+			DepositLimit::UnsafeOnlyForDryRun,
+			call.abi_encode(),
+		)
+		.result
+		.unwrap()
+	}
+
+	fn info() -> IdentityInfo<crate::tests::MaxAdditionalFields> {
+		IdentityInfo {
+			display: Data::Raw(b"ten".to_vec().try_into().unwrap()),
+			..Default::default()
+		}
+	}
+
+	#[test]
+	fn identity_of_works() {
+		new_test_ext().execute_with(|| {
+			let (caller, who, registrar) = (eth_account(1), eth_account(10), eth_account(3));
+
+			let registration = |caller: &AccountId| {
+				let result = call(
+					caller,
+					IIdentityCalls::identityOf(IIdentity::identityOfCall { who: address_of(&who) }),
+				);
+				IIdentity::identityOfCall::abi_decode_returns(&result.data).unwrap()
+			};
+			assert!(!registration(&caller).exists);
+
+			assert_ok!(Identity::set_identity(
+				RuntimeOrigin::signed(who.clone()),
+				Box::new(info())
+			));
+			assert_ok!(Identity::add_registrar(RuntimeOrigin::root(), registrar.clone()));
+			assert_ok!(Identity::provide_judgement(
+				RuntimeOrigin::signed(registrar),
+				0,
+				who.clone(),
+				Judgement::KnownGood,
+				BlakeTwo256::hash_of(&info()),
+			));
+
+			let registration = registration(&caller);
+			assert!(registration.exists);
+			assert_eq!(registration.judgements.len(), 1);
+			assert_eq!(registration.judgements[0].registrarIndex, 0);
+			assert_eq!(registration.judgements[0].kind, 3);
+			assert_eq!(
+				registration.deposit,
+				U256::from(IdentityOf::<Test>::get(&who).unwrap().deposit)
+			);
+			assert_eq!(registration.info.to_vec(), info().encode());
+
+			let result = call(
+				&caller,
+				IIdentityCalls::isVerified(IIdentity::isVerifiedCall { who: address_of(&who) }),
+			);
+			assert!(IIdentity::isVerifiedCall::abi_decode_returns(&result.data).unwrap());
+		});
+	}
+
+	#[test]
+	fn subs_and_super_of_works() {
+		new_test_ext().execute_with(|| {
+			let (caller, parent, sub) = (eth_account(1), eth_account(10), eth_account(20));
+			let name = Data::Raw(b"sub".to_vec().try_into().unwrap());
+
+			assert_ok!(Identity::set_identity(
+				RuntimeOrigin::signed(parent.clone()),
+				Box::new(info())
+			));
+			assert_ok!(Identity::set_subs(
+				RuntimeOrigin::signed(parent.clone()),
+				vec![(sub.clone(), name)]
+			));
+
+			let result = call(
+				&caller,
+				IIdentityCalls::subsOf(IIdentity::subsOfCall { who: address_of(&parent) }),
+			);
+			assert_eq!(
+				IIdentity::subsOfCall::abi_decode_returns(&result.data).unwrap(),
+				vec![address_of(&sub)]
+			);
+
+			let result = call(
+				&caller,
+				IIdentityCalls::superOf(IIdentity::superOfCall { who: address_of(&sub) }),
+			);
+			let ret = IIdentity::superOfCall::abi_decode_returns(&result.data).unwrap();
+			assert!(ret.exists);
+			assert_eq!(ret.parent, address_of(&parent));
+			assert_eq!(ret.name.to_vec(), b"sub".to_vec());
+
+			let result = call(
+				&caller,
+				IIdentityCalls::superOf(IIdentity::superOfCall { who: address_of(&parent) }),
+			);
+			assert!(!IIdentity::superOfCall::abi_decode_returns(&result.data).unwrap().exists);
+		});
+	}
+}
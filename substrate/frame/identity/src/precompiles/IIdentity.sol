@@ -0,0 +1,52 @@
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.20;
+
+/// @title Defines all functions that can be used to read on-chain identities
+/// @dev Deposits are in ETH decimals (18), like the balance of an account
+interface IIdentity {
+    /// A judgement given by a registrar
+    struct Judgement {
+        /// The index of the registrar
+        uint32 registrarIndex;
+        /// Unknown (0), FeePaid (1), Reasonable (2), KnownGood (3), OutOfDate (4),
+        /// LowQuality (5) or Erroneous (6)
+        uint8 kind;
+        /// The fee held for the judgement, only set for `FeePaid`
+        uint256 fee;
+    }
+
+    /// The identity of an account
+    struct Registration {
+        /// Whether the account has an identity
+        bool exists;
+        /// The judgements given by the registrars
+        Judgement[] judgements;
+        /// The amount held on deposit for the identity
+        uint256 deposit;
+        /// The identity information, SCALE encoded
+        bytes info;
+    }
+
+    /// @notice Get the identity of an account
+    /// @param who The account to look up
+    /// @return The identity of the account, `exists` is false if it has none
+    function identityOf(address who) external view returns (Registration memory);
+
+    /// @notice Get the parent of a sub-account
+    /// @dev The name is the raw name, or its 32 bytes hash if only the hash was published
+    /// @param who The sub-account to look up
+    /// @return exists Whether the account is a sub-account
+    /// @return parent The parent account
+    /// @return name The name of the sub-account
+    function superOf(address who) external view returns (bool exists, address parent, bytes memory name);
+
+    /// @notice Get the sub-accounts of an account
+    /// @param who The account to look up
+    /// @return The sub-accounts of the account
+    function subsOf(address who) external view returns (address[] memory);
+
+    /// @notice Check whether the identity of an account was judged `Reasonable` or `KnownGood`
+    /// @param who The account to look up
+    /// @return Whether the identity of the account is verified
+    function isVerified(address who) external view returns (bool);
+}
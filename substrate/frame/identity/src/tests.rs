@@ -50,6 +50,7 @@ frame_support::construct_runtime!(
 		System: frame_system,
 		Balances: pallet_balances,
 		Identity: pallet_identity,
+		Revive: pallet_revive,
 	}
 );
 
@@ -66,6 +67,13 @@ impl pallet_balances::Config for Test {
 	type AccountStore = System;
 }
 
+#[derive_impl(pallet_revive::config_preludes::TestDefaultConfig)]
+impl pallet_revive::Config for Test {
+	type AddressMapper = pallet_revive::AccountId32Mapper<Self>;
+	type Currency = Balances;
+	type Precompiles = (crate::precompiles::IdentityPrecompile<Self>,);
+}
+
 parameter_types! {
 	pub const MaxAdditionalFields: u32 = 2;
 	pub const MaxRegistrars: u32 = 20;
@@ -45,6 +45,33 @@ fn setup_multi<T: Config>(
 	Ok((signatories, Box::new(call)))
 }
 
+/// Set up `s` members of a stateful multisig with a weight of one each, and a funded creator.
+fn setup_stateful_multi<T: Config>(
+	s: u32,
+) -> Result<(Vec<(T::AccountId, u32)>, T::AccountId), &'static str> {
+	let (signatories, _) = setup_multi::<T>(s, 0)?;
+	let members = signatories.into_iter().map(|member| (member, 1)).collect();
+	let caller: T::AccountId = whitelisted_caller();
+	T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value());
+	Ok((members, caller))
+}
+
+/// Create a stateful multisig from `caller`, returning its account.
+fn create_stateful_multi<T: Config>(
+	caller: &T::AccountId,
+	members: Vec<(T::AccountId, u32)>,
+	threshold: u32,
+) -> Result<T::AccountId, &'static str> {
+	let multisig = Multisig::<T>::stateful_multi_account_id(caller, &Multisig::<T>::timepoint());
+	Multisig::<T>::create_stateful_multisig(
+		RawOrigin::Signed(caller.clone()).into(),
+		members,
+		threshold,
+	)
+	.map_err(|_| "failed to create the stateful multisig")?;
+	Ok(multisig)
+}
+
 #[benchmarks]
 mod benchmarks {
 	use super::*;
@@ -359,5 +386,87 @@ mod benchmarks {
 		Ok(())
 	}
 
+	/// `s`: Members
+	#[benchmark]
+	fn create_stateful_multisig(
+		s: Linear<1, { T::MaxSignatories::get() }>,
+	) -> Result<(), BenchmarkError> {
+		let (members, caller) = setup_stateful_multi::<T>(s)?;
+		let multisig =
+			Multisig::<T>::stateful_multi_account_id(&caller, &Multisig::<T>::timepoint());
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller), members, s);
+
+		assert!(StatefulMultisigs::<T>::contains_key(multisig));
+
+		Ok(())
+	}
+
+	/// `s`: Members
+	/// `o`: Operations underway
+	#[benchmark]
+	fn update_stateful_multisig(
+		s: Linear<1, { T::MaxSignatories::get() }>,
+		o: Linear<0, { T::MaxSignatories::get() }>,
+	) -> Result<(), BenchmarkError> {
+		let (members, _) = setup_stateful_multi::<T>(s)?;
+		// The creator holding the deposit is not the caller, which is the multisig.
+		let caller: T::AccountId = account("creator", 0, SEED);
+		T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value());
+		let multisig = create_stateful_multi::<T>(&caller, members.clone(), s)?;
+
+		// Every member approved every operation, and all of them are replaced by twice as many,
+		// up to the maximum, so that the creator has more of the deposit reserved.
+		for i in 0..o {
+			let call_hash = i.using_encoded(blake2_256);
+			for (index, (member, _)) in members.iter().enumerate() {
+				Multisig::<T>::approve_as_multi(
+					RawOrigin::Signed(member.clone()).into(),
+					0,
+					vec![multisig.clone()],
+					(index > 0).then(Multisig::<T>::timepoint),
+					call_hash,
+					Weight::zero(),
+				)?;
+			}
+		}
+		let new_len = s.saturating_mul(2).min(T::MaxSignatories::get());
+		let mut new_members: Vec<(T::AccountId, u32)> =
+			(0..new_len).map(|i| (account("member", i, SEED), 1)).collect();
+		new_members.sort();
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(multisig.clone()), new_members, 1);
+
+		let config = StatefulMultisigs::<T>::get(&multisig).ok_or("multisig not created")?;
+		assert_eq!(config.threshold, 1);
+		assert_eq!(config.open_operations, o);
+		assert_eq!(T::Currency::reserved_balance(&caller), Multisig::<T>::deposit(new_len as u16));
+		assert!(Multisigs::<T>::iter_prefix_values(&multisig).all(|m| m.approvals.is_empty()));
+
+		Ok(())
+	}
+
+	/// `s`: Members
+	#[benchmark]
+	fn destroy_stateful_multisig(
+		s: Linear<1, { T::MaxSignatories::get() }>,
+	) -> Result<(), BenchmarkError> {
+		let (members, _) = setup_stateful_multi::<T>(s)?;
+		// The creator holding the deposit is not the caller, which is the multisig.
+		let caller: T::AccountId = account("creator", 0, SEED);
+		T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value());
+		let multisig = create_stateful_multi::<T>(&caller, members, s)?;
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(multisig.clone()));
+
+		assert!(!StatefulMultisigs::<T>::contains_key(&multisig));
+		assert!(T::Currency::reserved_balance(&caller).is_zero());
+
+		Ok(())
+	}
+
 	impl_benchmark_test_suite!(Multisig, crate::tests::new_test_ext(), crate::tests::Test);
 }
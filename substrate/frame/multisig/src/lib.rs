@@ -31,6 +31,14 @@
 //! operation. This is useful for multisig wallets where cryptographic threshold signatures are
 //! not available or desired.
 //!
+//! Such an account changes whenever the signatories or the threshold do. A stateful multisig
+//! instead has a persistent account, whose members, their voting weights and the threshold are
+//! kept on-chain and can be changed by the multisig itself. Its operations go through the same
+//! calls, timepoint, deposit and approval flow, with a threshold of zero and the account of the
+//! stateful multisig as the only other signatory. A call is dispatched once the weights of its
+//! approving members add up to the threshold. At most `MaxSignatories` of its operations can be
+//! underway at once.
+//!
 //! ## Interface
 //!
 //! ### Dispatchable Functions
@@ -39,6 +47,9 @@
 //!   number of signed origins.
 //! * `approve_as_multi` - Approve a call from a composite origin.
 //! * `cancel_as_multi` - Cancel a call from a composite origin.
+//! * `create_stateful_multisig` - Create a stateful multisig with weighted members.
+//! * `update_stateful_multisig` - Change the members, weights and threshold of a stateful multisig.
+//! * `destroy_stateful_multisig` - Remove a stateful multisig.
 
 // Ensure we're `no_std` when compiling for Wasm.
 #![cfg_attr(not(feature = "std"), no_std)]
@@ -120,6 +131,47 @@ where
 	pub approvals: BoundedVec<AccountId, MaxApprovals>,
 }
 
+/// A multisig account whose members, their voting weights and the threshold are kept in storage.
+#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+#[scale_info(skip_type_params(MaxMembers))]
+pub struct StatefulMultisig<AccountId, Balance, MaxMembers>
+where
+	MaxMembers: Get<u32>,
+{
+	/// The members and their voting weights. Always sorted by account.
+	pub members: BoundedVec<(AccountId, u32), MaxMembers>,
+	/// The total weight of the approvals needed to dispatch a call.
+	pub threshold: u32,
+	/// The account holding the deposit for this configuration, i.e. its creator.
+	pub depositor: AccountId,
+	/// The amount held in reserve of the `depositor`.
+	pub deposit: Balance,
+	/// The number of operations underway, at most `MaxMembers`.
+	pub open_operations: u32,
+}
+
+impl<AccountId: Ord, Balance, MaxMembers: Get<u32>>
+	StatefulMultisig<AccountId, Balance, MaxMembers>
+{
+	/// The voting weight of `who`, zero if they are not a member.
+	pub fn weight_of(&self, who: &AccountId) -> u32 {
+		self.members
+			.binary_search_by(|(member, _)| member.cmp(who))
+			.map_or(0, |index| self.members[index].1)
+	}
+
+	/// Whether `who` is a member.
+	pub fn is_member(&self, who: &AccountId) -> bool {
+		self.members.binary_search_by(|(member, _)| member.cmp(who)).is_ok()
+	}
+}
+
+type StatefulMultisigOf<T> = StatefulMultisig<
+	<T as frame_system::Config>::AccountId,
+	BalanceOf<T>,
+	<T as Config>::MaxSignatories,
+>;
+
 type CallHash = [u8; 32];
 
 enum CallOrHash<T: Config> {
@@ -127,6 +179,39 @@ enum CallOrHash<T: Config> {
 	Hash([u8; 32]),
 }
 
+/// How the approvals of a multisig operation are counted.
+enum ApprovalRule<T: Config> {
+	/// Every signatory counts once towards the threshold.
+	Count(u16),
+	/// Every member counts with its voting weight towards the threshold of the stateful multisig.
+	Weighted(StatefulMultisigOf<T>),
+}
+
+impl<T: Config> ApprovalRule<T> {
+	/// Whether `approvals`, together with `extra`, are enough to dispatch the call.
+	fn is_met(&self, approvals: &[T::AccountId], extra: Option<&T::AccountId>) -> bool {
+		match self {
+			Self::Count(threshold) =>
+				approvals.len() + extra.map_or(0, |_| 1) >= *threshold as usize,
+			Self::Weighted(multisig) => {
+				let approved = approvals
+					.iter()
+					.chain(extra)
+					.fold(0u32, |total, who| total.saturating_add(multisig.weight_of(who)));
+				approved >= multisig.threshold
+			},
+		}
+	}
+
+	/// The deposit taken for opening an operation.
+	fn deposit(&self) -> BalanceOf<T> {
+		match self {
+			Self::Count(threshold) => Pallet::<T>::deposit(*threshold),
+			Self::Weighted(multisig) => Pallet::<T>::deposit(multisig.members.len() as u16),
+		}
+	}
+}
+
 #[frame::pallet]
 pub mod pallet {
 	use super::*;
@@ -211,6 +296,11 @@ pub mod pallet {
 		Multisig<BlockNumberFor<T>, BalanceOf<T>, T::AccountId, T::MaxSignatories>,
 	>;
 
+	/// The configuration of stateful multisig accounts.
+	#[pallet::storage]
+	pub type StatefulMultisigs<T: Config> =
+		StorageMap<_, Twox64Concat, T::AccountId, StatefulMultisigOf<T>>;
+
 	#[pallet::error]
 	pub enum Error<T> {
 		/// Threshold must be 2 or greater.
@@ -242,6 +332,18 @@ pub mod pallet {
 		MaxWeightTooLow,
 		/// The data to be stored is already stored.
 		AlreadyStored,
+		/// The stateful multisig does not exist.
+		UnknownMultisig,
+		/// The sender is not a member of the stateful multisig.
+		NotMember,
+		/// A member was given a voting weight of zero.
+		ZeroWeight,
+		/// The threshold is zero or higher than the total weight of the members.
+		InvalidThreshold,
+		/// The stateful multisig has as many operations underway as it may have members.
+		TooManyOperations,
+		/// The account of the stateful multisig still exists, holding funds or references.
+		AccountInUse,
 	}
 
 	#[pallet::event]
@@ -278,6 +380,12 @@ pub mod pallet {
 			old_deposit: BalanceOf<T>,
 			new_deposit: BalanceOf<T>,
 		},
+		/// A stateful multisig has been created.
+		StatefulMultisigCreated { creator: T::AccountId, multisig: T::AccountId },
+		/// The members, weights or threshold of a stateful multisig have been changed.
+		StatefulMultisigUpdated { multisig: T::AccountId },
+		/// A stateful multisig has been removed.
+		StatefulMultisigDestroyed { multisig: T::AccountId },
 	}
 
 	#[pallet::hooks]
@@ -366,8 +474,11 @@ pub mod pallet {
 		/// The dispatch origin for this call must be _Signed_.
 		///
 		/// - `threshold`: The total number of approvals for this dispatch before it is executed.
+		/// Zero for an operation of a stateful multisig, which is dispatched once the weights of
+		/// its approving members reach its threshold.
 		/// - `other_signatories`: The accounts (other than the sender) who can approve this
-		/// dispatch. May not be empty.
+		/// dispatch. May not be empty. Only the account of the stateful multisig if `threshold`
+		/// is zero, in which case the sender must be one of its members.
 		/// - `maybe_timepoint`: If this is the first approval, then this must be `None`. If it is
 		/// not the first approval, then it must be `Some`, with the timepoint (block number and
 		/// transaction index) of the first approval transaction.
@@ -395,12 +506,13 @@ pub mod pallet {
 		///   taken for its lifetime of `DepositBase + threshold * DepositFactor`.
 		#[pallet::call_index(1)]
 		#[pallet::weight({
-			let s = other_signatories.len() as u32;
+			let (s, stateful_weight) = Pallet::<T>::signatories_weight(*threshold, other_signatories);
 			let z = call.using_encoded(|d| d.len()) as u32;
 
 			T::WeightInfo::as_multi_create(s, z)
 			.max(T::WeightInfo::as_multi_approve(s, z))
 			.max(T::WeightInfo::as_multi_complete(s, z))
+			.saturating_add(stateful_weight)
 			.saturating_add(*max_weight)
 		})]
 		pub fn as_multi(
@@ -432,8 +544,10 @@ pub mod pallet {
 		/// The dispatch origin for this call must be _Signed_.
 		///
 		/// - `threshold`: The total number of approvals for this dispatch before it is executed.
+		/// Zero for an operation of a stateful multisig.
 		/// - `other_signatories`: The accounts (other than the sender) who can approve this
-		/// dispatch. May not be empty.
+		/// dispatch. May not be empty. Only the account of the stateful multisig if `threshold`
+		/// is zero.
 		/// - `maybe_timepoint`: If this is the first approval, then this must be `None`. If it is
 		/// not the first approval, then it must be `Some`, with the timepoint (block number and
 		/// transaction index) of the first approval transaction.
@@ -454,10 +568,11 @@ pub mod pallet {
 		///   taken for its lifetime of `DepositBase + threshold * DepositFactor`.
 		#[pallet::call_index(2)]
 		#[pallet::weight({
-			let s = other_signatories.len() as u32;
+			let (s, stateful_weight) = Pallet::<T>::signatories_weight(*threshold, other_signatories);
 
 			T::WeightInfo::approve_as_multi_create(s)
 				.max(T::WeightInfo::approve_as_multi_approve(s))
+				.saturating_add(stateful_weight)
 				.saturating_add(*max_weight)
 		})]
 		pub fn approve_as_multi(
@@ -485,8 +600,11 @@ pub mod pallet {
 		/// The dispatch origin for this call must be _Signed_.
 		///
 		/// - `threshold`: The total number of approvals for this dispatch before it is executed.
+		/// Zero for an operation of a stateful multisig, which can be cancelled even after the
+		/// stateful multisig was destroyed.
 		/// - `other_signatories`: The accounts (other than the sender) who can approve this
-		/// dispatch. May not be empty.
+		/// dispatch. May not be empty. Only the account of the stateful multisig if `threshold`
+		/// is zero.
 		/// - `timepoint`: The timepoint (block number and transaction index) of the first approval
 		/// transaction for this dispatch.
 		/// - `call_hash`: The hash of the call to be executed.
@@ -501,7 +619,11 @@ pub mod pallet {
 		/// - I/O: 1 read `O(S)`, one remove.
 		/// - Storage: removes one item.
 		#[pallet::call_index(3)]
-		#[pallet::weight(T::WeightInfo::cancel_as_multi(other_signatories.len() as u32))]
+		#[pallet::weight({
+			let (s, stateful_weight) = Pallet::<T>::signatories_weight(*threshold, other_signatories);
+
+			T::WeightInfo::cancel_as_multi(s).saturating_add(stateful_weight)
+		})]
 		pub fn cancel_as_multi(
 			origin: OriginFor<T>,
 			threshold: u16,
@@ -510,6 +632,12 @@ pub mod pallet {
 			call_hash: [u8; 32],
 		) -> DispatchResult {
 			let who = ensure_signed(origin)?;
+			if threshold == 0 {
+				let id = Self::stateful_account(other_signatories)?;
+				Self::do_cancel(who, id.clone(), timepoint, call_hash)?;
+				Self::close_stateful_operation(&id);
+				return Ok(())
+			}
 			ensure!(threshold >= 2, Error::<T>::MinimumThreshold);
 			let max_sigs = T::MaxSignatories::get() as usize;
 			ensure!(!other_signatories.is_empty(), Error::<T>::TooFewSignatories);
@@ -518,21 +646,7 @@ pub mod pallet {
 
 			let id = Self::multi_account_id(&signatories, threshold);
 
-			let m = <Multisigs<T>>::get(&id, call_hash).ok_or(Error::<T>::NotFound)?;
-			ensure!(m.when == timepoint, Error::<T>::WrongTimepoint);
-			ensure!(m.depositor == who, Error::<T>::NotOwner);
-
-			let err_amount = T::Currency::unreserve(&m.depositor, m.deposit);
-			debug_assert!(err_amount.is_zero());
-			<Multisigs<T>>::remove(&id, &call_hash);
-
-			Self::deposit_event(Event::MultisigCancelled {
-				cancelling: who,
-				timepoint,
-				multisig: id,
-				call_hash,
-			});
-			Ok(())
+			Self::do_cancel(who, id, timepoint, call_hash)
 		}
 
 		/// Poke the deposit reserved for an existing multisig operation.
@@ -612,6 +726,138 @@ pub mod pallet {
 				},
 			)
 		}
+
+		/// Create a stateful multisig, whose account does not change with its members or threshold.
+		///
+		/// Payment: `DepositBase` plus `DepositFactor` for every member is reserved from the
+		/// sender, until the multisig changes its configuration or is destroyed.
+		///
+		/// The dispatch origin for this call must be _Signed_.
+		///
+		/// - `members`: The members and their voting weights, sorted by account. Weights may not be
+		/// zero.
+		/// - `threshold`: The total weight of the approvals needed to dispatch a call. May not be
+		/// zero or higher than the total weight of the members.
+		///
+		/// The account of the multisig is given by `stateful_multi_account_id`, and emitted in
+		/// `StatefulMultisigCreated`.
+		#[pallet::call_index(5)]
+		#[pallet::weight(T::WeightInfo::create_stateful_multisig(members.len() as u32))]
+		pub fn create_stateful_multisig(
+			origin: OriginFor<T>,
+			members: Vec<(T::AccountId, u32)>,
+			threshold: u32,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let members = Self::ensure_valid_members(members, threshold)?;
+
+			let id = Self::stateful_multi_account_id(&who, &Self::timepoint());
+			ensure!(!StatefulMultisigs::<T>::contains_key(&id), Error::<T>::AlreadyStored);
+
+			let deposit = Self::deposit(members.len() as u16);
+			T::Currency::reserve(&who, deposit)?;
+
+			StatefulMultisigs::<T>::insert(
+				&id,
+				StatefulMultisig {
+					members,
+					threshold,
+					depositor: who.clone(),
+					deposit,
+					open_operations: 0,
+				},
+			);
+			Self::deposit_event(Event::StatefulMultisigCreated { creator: who, multisig: id });
+			Ok(())
+		}
+
+		/// Change the members, their voting weights and the threshold of a stateful multisig.
+		///
+		/// Operations already underway are kept: the approvals of former members are removed from
+		/// them, and the remaining ones are counted with the new weights and threshold.
+		///
+		/// Payment: The deposit for the configuration stays with its depositor, who has the
+		/// difference reserved or returned as the number of members changes.
+		///
+		/// The dispatch origin for this call must be _Signed_ by the stateful multisig.
+		///
+		/// - `members`: The new members and their voting weights, sorted by account.
+		/// - `threshold`: The new total weight of the approvals needed to dispatch a call.
+		#[pallet::call_index(6)]
+		#[pallet::weight(T::WeightInfo::update_stateful_multisig(
+			T::MaxSignatories::get(),
+			T::MaxSignatories::get(),
+		))]
+		pub fn update_stateful_multisig(
+			origin: OriginFor<T>,
+			members: Vec<(T::AccountId, u32)>,
+			threshold: u32,
+		) -> DispatchResultWithPostInfo {
+			let id = ensure_signed(origin)?;
+			let members = Self::ensure_valid_members(members, threshold)?;
+
+			let (multisig, old_members_len) = StatefulMultisigs::<T>::try_mutate(
+				&id,
+				|maybe_multisig| -> Result<_, DispatchError> {
+					let multisig = maybe_multisig.as_mut().ok_or(Error::<T>::UnknownMultisig)?;
+					let old_members_len = multisig.members.len() as u32;
+
+					let deposit = Self::deposit(members.len() as u16);
+					if deposit > multisig.deposit {
+						T::Currency::reserve(&multisig.depositor, deposit - multisig.deposit)?;
+					} else if deposit < multisig.deposit {
+						let err_amount =
+							T::Currency::unreserve(&multisig.depositor, multisig.deposit - deposit);
+						debug_assert!(err_amount.is_zero());
+					}
+
+					multisig.members = members;
+					multisig.threshold = threshold;
+					multisig.deposit = deposit;
+					Ok((multisig.clone(), old_members_len))
+				},
+			)?;
+
+			// Former members could otherwise have their approvals counted again once re-added.
+			// There are at most `MaxSignatories` operations underway, see `open_operations`.
+			let mut operations_len = 0;
+			for (call_hash, mut operation) in Multisigs::<T>::iter_prefix(&id) {
+				operations_len += 1;
+				let approvals_len = operation.approvals.len();
+				operation.approvals.retain(|who| multisig.is_member(who));
+				if operation.approvals.len() != approvals_len {
+					Multisigs::<T>::insert(&id, call_hash, operation);
+				}
+			}
+
+			Self::deposit_event(Event::StatefulMultisigUpdated { multisig: id });
+			let members_len = old_members_len.max(multisig.members.len() as u32);
+			Ok(Some(T::WeightInfo::update_stateful_multisig(members_len, operations_len)).into())
+		}
+
+		/// Remove a stateful multisig, returning the deposit for its configuration.
+		///
+		/// Nobody can dispatch from the account of the multisig afterwards, so the account must no
+		/// longer exist: its funds, in any asset, and its references must be moved or removed
+		/// first, e.g. with `transfer_all` in a `batch_all` together with this call.
+		///
+		/// Its operations still underway can no longer be approved, but their depositors can
+		/// cancel them with `cancel_as_multi`.
+		///
+		/// The dispatch origin for this call must be _Signed_ by the stateful multisig.
+		#[pallet::call_index(7)]
+		#[pallet::weight(T::WeightInfo::destroy_stateful_multisig(T::MaxSignatories::get()))]
+		pub fn destroy_stateful_multisig(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
+			let id = ensure_signed(origin)?;
+			let multisig = StatefulMultisigs::<T>::take(&id).ok_or(Error::<T>::UnknownMultisig)?;
+			ensure!(!frame_system::Pallet::<T>::account_exists(&id), Error::<T>::AccountInUse);
+
+			let err_amount = T::Currency::unreserve(&multisig.depositor, multisig.deposit);
+			debug_assert!(err_amount.is_zero());
+
+			Self::deposit_event(Event::StatefulMultisigDestroyed { multisig: id });
+			Ok(Some(T::WeightInfo::destroy_stateful_multisig(multisig.members.len() as u32)).into())
+		}
 	}
 }
 
@@ -626,6 +872,19 @@ impl<T: Config> Pallet<T> {
 			.expect("infinite length input; no invalid inputs for type; qed")
 	}
 
+	/// Derive the account of a stateful multisig created by `who` at `when`.
+	///
+	/// Unlike [`Self::multi_account_id`], it does not depend on the members or the threshold,
+	/// which can then change without moving any funds.
+	pub fn stateful_multi_account_id(
+		who: &T::AccountId,
+		when: &Timepoint<BlockNumberFor<T>>,
+	) -> T::AccountId {
+		let entropy = (b"modlpy/utilisuba", b"stateful", who, when).using_encoded(blake2_256);
+		Decode::decode(&mut TrailingZeroInput::new(entropy.as_ref()))
+			.expect("infinite length input; no invalid inputs for type; qed")
+	}
+
 	fn operate(
 		who: T::AccountId,
 		threshold: u16,
@@ -634,6 +893,10 @@ impl<T: Config> Pallet<T> {
 		call_or_hash: CallOrHash<T>,
 		max_weight: Weight,
 	) -> DispatchResultWithPostInfo {
+		if threshold == 0 {
+			let id = Self::stateful_account(other_signatories)?;
+			return Self::operate_stateful(who, id, maybe_timepoint, call_or_hash, max_weight)
+		}
 		ensure!(threshold >= 2, Error::<T>::MinimumThreshold);
 		let max_sigs = T::MaxSignatories::get() as usize;
 		ensure!(!other_signatories.is_empty(), Error::<T>::TooFewSignatories);
@@ -643,6 +906,55 @@ impl<T: Config> Pallet<T> {
 
 		let id = Self::multi_account_id(&signatories, threshold);
 
+		Self::do_operate(
+			who,
+			id,
+			ApprovalRule::Count(threshold),
+			other_signatories_len as u32,
+			Weight::zero(),
+			maybe_timepoint,
+			call_or_hash,
+			max_weight,
+		)
+	}
+
+	fn operate_stateful(
+		who: T::AccountId,
+		id: T::AccountId,
+		maybe_timepoint: Option<Timepoint<BlockNumberFor<T>>>,
+		call_or_hash: CallOrHash<T>,
+		max_weight: Weight,
+	) -> DispatchResultWithPostInfo {
+		let multisig = StatefulMultisigs::<T>::get(&id).ok_or(Error::<T>::UnknownMultisig)?;
+		ensure!(multisig.is_member(&who), Error::<T>::NotMember);
+		let other_members_len = multisig.members.len().saturating_sub(1) as u32;
+
+		Self::do_operate(
+			who,
+			id,
+			ApprovalRule::Weighted(multisig),
+			other_members_len,
+			// Reading the configuration of the multisig, and counting the operation in it.
+			T::DbWeight::get().reads_writes(1, 1),
+			maybe_timepoint,
+			call_or_hash,
+			max_weight,
+		)
+	}
+
+	/// Approve, and dispatch once `rule` is met, an operation of the multisig `id`.
+	///
+	/// `other_signatories_len` and `extra_weight` are only used to compute the actual weight.
+	fn do_operate(
+		who: T::AccountId,
+		id: T::AccountId,
+		rule: ApprovalRule<T>,
+		other_signatories_len: u32,
+		extra_weight: Weight,
+		maybe_timepoint: Option<Timepoint<BlockNumberFor<T>>>,
+		call_or_hash: CallOrHash<T>,
+		max_weight: Weight,
+	) -> DispatchResultWithPostInfo {
 		// Threshold > 1; this means it's a multi-step operation. We extract the `call_hash`.
 		let (call_hash, call_len, maybe_call) = match call_or_hash {
 			CallOrHash::Call(call) => {
@@ -659,16 +971,14 @@ impl<T: Config> Pallet<T> {
 			ensure!(m.when == timepoint, Error::<T>::WrongTimepoint);
 
 			// Ensure that either we have not yet signed or that it is at threshold.
-			let mut approvals = m.approvals.len() as u16;
+			let at_threshold = rule.is_met(&m.approvals, None);
 			// We only bother with the approval if we're below threshold.
-			let maybe_pos = m.approvals.binary_search(&who).err().filter(|_| approvals < threshold);
-			// Bump approvals if not yet voted and the vote is needed.
-			if maybe_pos.is_some() {
-				approvals += 1;
-			}
+			let maybe_pos = m.approvals.binary_search(&who).err().filter(|_| !at_threshold);
+			// Count our approval if not yet voted and the vote is needed.
+			let approved = rule.is_met(&m.approvals, maybe_pos.map(|_| &who));
 
 			// We only bother fetching/decoding call if we know that we're ready to execute.
-			if let Some(call) = maybe_call.filter(|_| approvals >= threshold) {
+			if let Some(call) = maybe_call.filter(|_| approved) {
 				// verify weight
 				ensure!(
 					call.get_dispatch_info().call_weight.all_lte(max_weight),
@@ -679,24 +989,19 @@ impl<T: Config> Pallet<T> {
 				// attack.
 				<Multisigs<T>>::remove(&id, call_hash);
 				T::Currency::unreserve(&m.depositor, m.deposit);
+				if let ApprovalRule::Weighted(_) = rule {
+					Self::close_stateful_operation(&id);
+				}
 
-				let result = call.dispatch(RawOrigin::Signed(id.clone()).into());
-				Self::deposit_event(Event::MultisigExecuted {
-					approving: who,
+				Self::dispatch_approved(
+					who,
+					id,
 					timepoint,
-					multisig: id,
 					call_hash,
-					result: result.map(|_| ()).map_err(|e| e.error),
-				});
-				Ok(get_result_weight(result)
-					.map(|actual_weight| {
-						T::WeightInfo::as_multi_complete(
-							other_signatories_len as u32,
-							call_len as u32,
-						)
-						.saturating_add(actual_weight)
-					})
-					.into())
+					call,
+					T::WeightInfo::as_multi_complete(other_signatories_len, call_len as u32)
+						.saturating_add(extra_weight),
+				)
 			} else {
 				// We cannot dispatch the call now; either it isn't available, or it is, but we
 				// don't have threshold approvals even with our signature.
@@ -720,7 +1025,8 @@ impl<T: Config> Pallet<T> {
 				}
 
 				let final_weight =
-					T::WeightInfo::as_multi_approve(other_signatories_len as u32, call_len as u32);
+					T::WeightInfo::as_multi_approve(other_signatories_len, call_len as u32)
+						.saturating_add(extra_weight);
 				// Call is not made, so the actual weight does not include call
 				Ok(Some(final_weight).into())
 			}
@@ -728,8 +1034,37 @@ impl<T: Config> Pallet<T> {
 			// Not yet started; there should be no timepoint given.
 			ensure!(maybe_timepoint.is_none(), Error::<T>::UnexpectedTimepoint);
 
+			// A member of a stateful multisig may weigh enough to dispatch on their own.
+			if let Some(call) = maybe_call.filter(|_| rule.is_met(&[], Some(&who))) {
+				ensure!(
+					call.get_dispatch_info().call_weight.all_lte(max_weight),
+					Error::<T>::MaxWeightTooLow
+				);
+
+				return Self::dispatch_approved(
+					who,
+					id,
+					Self::timepoint(),
+					call_hash,
+					call,
+					T::WeightInfo::as_multi_complete(other_signatories_len, call_len as u32)
+						.saturating_add(extra_weight),
+				)
+			}
+
 			// Just start the operation by recording it in storage.
-			let deposit = Self::deposit(threshold);
+			if let ApprovalRule::Weighted(multisig) = &rule {
+				ensure!(
+					multisig.open_operations < T::MaxSignatories::get(),
+					Error::<T>::TooManyOperations
+				);
+				StatefulMultisigs::<T>::mutate(&id, |maybe_multisig| {
+					if let Some(multisig) = maybe_multisig {
+						multisig.open_operations = multisig.open_operations.saturating_add(1);
+					}
+				});
+			}
+			let deposit = rule.deposit();
 
 			T::Currency::reserve(&who, deposit)?;
 
@@ -749,12 +1084,112 @@ impl<T: Config> Pallet<T> {
 			Self::deposit_event(Event::NewMultisig { approving: who, multisig: id, call_hash });
 
 			let final_weight =
-				T::WeightInfo::as_multi_create(other_signatories_len as u32, call_len as u32);
+				T::WeightInfo::as_multi_create(other_signatories_len, call_len as u32)
+					.saturating_add(extra_weight);
 			// Call is not made, so the actual weight does not include call
 			Ok(Some(final_weight).into())
 		}
 	}
 
+	/// Dispatch the approved `call` from the multisig `id`, on top of `weight`.
+	fn dispatch_approved(
+		who: T::AccountId,
+		id: T::AccountId,
+		timepoint: Timepoint<BlockNumberFor<T>>,
+		call_hash: CallHash,
+		call: <T as Config>::RuntimeCall,
+		weight: Weight,
+	) -> DispatchResultWithPostInfo {
+		let result = call.dispatch(RawOrigin::Signed(id.clone()).into());
+		Self::deposit_event(Event::MultisigExecuted {
+			approving: who,
+			timepoint,
+			multisig: id,
+			call_hash,
+			result: result.map(|_| ()).map_err(|e| e.error),
+		});
+		Ok(get_result_weight(result)
+			.map(|actual_weight| weight.saturating_add(actual_weight))
+			.into())
+	}
+
+	/// Cancel the operation `call_hash` of the multisig `id`, opened by `who` at `timepoint`.
+	fn do_cancel(
+		who: T::AccountId,
+		id: T::AccountId,
+		timepoint: Timepoint<BlockNumberFor<T>>,
+		call_hash: CallHash,
+	) -> DispatchResult {
+		let m = <Multisigs<T>>::get(&id, call_hash).ok_or(Error::<T>::NotFound)?;
+		ensure!(m.when == timepoint, Error::<T>::WrongTimepoint);
+		ensure!(m.depositor == who, Error::<T>::NotOwner);
+
+		let err_amount = T::Currency::unreserve(&m.depositor, m.deposit);
+		debug_assert!(err_amount.is_zero());
+		<Multisigs<T>>::remove(&id, &call_hash);
+
+		Self::deposit_event(Event::MultisigCancelled {
+			cancelling: who,
+			timepoint,
+			multisig: id,
+			call_hash,
+		});
+		Ok(())
+	}
+
+	/// Count an operation of the stateful multisig `id` as no longer underway, if the multisig
+	/// still exists.
+	fn close_stateful_operation(id: &T::AccountId) {
+		StatefulMultisigs::<T>::mutate(id, |maybe_multisig| {
+			if let Some(multisig) = maybe_multisig {
+				multisig.open_operations = multisig.open_operations.saturating_sub(1);
+			}
+		});
+	}
+
+	/// The account of the stateful multisig given as the only one of `other_signatories`.
+	fn stateful_account(
+		other_signatories: Vec<T::AccountId>,
+	) -> Result<T::AccountId, DispatchError> {
+		match <[T::AccountId; 1]>::try_from(other_signatories) {
+			Ok([id]) => Ok(id),
+			Err(others) if others.is_empty() => Err(Error::<T>::TooFewSignatories.into()),
+			Err(_) => Err(Error::<T>::TooManySignatories.into()),
+		}
+	}
+
+	/// The number of signatories an operation is weighed with, together with the extra weight
+	/// of reading and counting the operation in the configuration of a stateful multisig.
+	///
+	/// Operations of a stateful multisig, with a `threshold` of zero, are weighed with the
+	/// maximum number of members.
+	pub fn signatories_weight(threshold: u16, other_signatories: &[T::AccountId]) -> (u32, Weight) {
+		if threshold == 0 {
+			(T::MaxSignatories::get(), T::DbWeight::get().reads_writes(1, 1))
+		} else {
+			(other_signatories.len() as u32, Weight::zero())
+		}
+	}
+
+	/// Check that `members` are sorted, with non-zero weights adding up to at least `threshold`.
+	fn ensure_valid_members(
+		members: Vec<(T::AccountId, u32)>,
+		threshold: u32,
+	) -> Result<BoundedVec<(T::AccountId, u32), T::MaxSignatories>, DispatchError> {
+		ensure!(!members.is_empty(), Error::<T>::TooFewSignatories);
+		let members: BoundedVec<_, T::MaxSignatories> =
+			members.try_into().map_err(|_| Error::<T>::TooManySignatories)?;
+		ensure!(
+			members.windows(2).all(|pair| pair[0].0 < pair[1].0),
+			Error::<T>::SignatoriesOutOfOrder
+		);
+		ensure!(members.iter().all(|(_, weight)| *weight > 0), Error::<T>::ZeroWeight);
+		let total_weight =
+			members.iter().fold(0u32, |total, (_, weight)| total.saturating_add(*weight));
+		ensure!(threshold > 0 && threshold <= total_weight, Error::<T>::InvalidThreshold);
+		Ok(members)
+	}
+
 	/// The current `Timepoint`.
 	pub fn timepoint() -> Timepoint<BlockNumberFor<T>> {
 		Timepoint {
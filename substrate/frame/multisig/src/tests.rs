@@ -54,6 +54,7 @@ impl Contains<RuntimeCall> for TestBaseCallFilter {
 			RuntimeCall::Balances(_) => true,
 			// Needed for benchmarking
 			RuntimeCall::System(frame_system::Call::remark { .. }) => true,
+			RuntimeCall::Multisig(pallet_multisig::Call::update_stateful_multisig { .. }) => true,
 			_ => false,
 		}
 	}
@@ -468,6 +469,7 @@ fn multisig_2_of_3_cannot_reissue_same_call() {
 fn minimum_threshold_check_works() {
 	new_test_ext().execute_with(|| {
 		let call = call_transfer(6, 15);
+		// A threshold of zero is reserved for operations of stateful multisigs.
 		assert_noop!(
 			Multisig::as_multi(
 				RuntimeOrigin::signed(1),
@@ -477,7 +479,7 @@ fn minimum_threshold_check_works() {
 				call.clone(),
 				Weight::zero()
 			),
-			Error::<Test>::MinimumThreshold,
+			Error::<Test>::UnknownMultisig,
 		);
 		assert_noop!(
 			Multisig::as_multi(
@@ -1015,3 +1017,475 @@ fn poke_deposit_handles_insufficient_balance() {
 		);
 	});
 }
+
+fn create_stateful(creator: u64, members: Vec<(u64, u32)>, threshold: u32) -> u64 {
+	let multisig = Multisig::stateful_multi_account_id(&creator, &now());
+	assert_ok!(Multisig::create_stateful_multisig(
+		RuntimeOrigin::signed(creator),
+		members,
+		threshold
+	));
+	multisig
+}
+
+#[test]
+fn create_stateful_multisig_works() {
+	new_test_ext().execute_with(|| {
+		let multisig = create_stateful(4, vec![(1, 1), (2, 2), (3, 1)], 3);
+		System::assert_last_event(
+			pallet_multisig::Event::StatefulMultisigCreated { creator: 4, multisig }.into(),
+		);
+
+		let config = StatefulMultisigs::<Test>::get(multisig).unwrap();
+		assert_eq!(config.members.to_vec(), vec![(1, 1), (2, 2), (3, 1)]);
+		assert_eq!(config.threshold, 3);
+		assert_eq!(config.weight_of(&2), 2);
+		assert_eq!(config.weight_of(&5), 0);
+		assert_eq!(Balances::free_balance(4), 1);
+		assert_eq!(Balances::reserved_balance(4), 4);
+
+		// The account is derived from the creator and the timepoint only.
+		assert_noop!(
+			Multisig::create_stateful_multisig(RuntimeOrigin::signed(4), vec![(1, 1)], 1),
+			Error::<Test>::AlreadyStored,
+		);
+	});
+}
+
+#[test]
+fn create_stateful_multisig_checks_members() {
+	new_test_ext().execute_with(|| {
+		let origin = || RuntimeOrigin::signed(1);
+		assert_noop!(
+			Multisig::create_stateful_multisig(origin(), vec![], 1),
+			Error::<Test>::TooFewSignatories,
+		);
+		assert_noop!(
+			Multisig::create_stateful_multisig(origin(), vec![(1, 1), (2, 1), (3, 1), (4, 1)], 1),
+			Error::<Test>::TooManySignatories,
+		);
+		assert_noop!(
+			Multisig::create_stateful_multisig(origin(), vec![(2, 1), (1, 1)], 1),
+			Error::<Test>::SignatoriesOutOfOrder,
+		);
+		assert_noop!(
+			Multisig::create_stateful_multisig(origin(), vec![(1, 1), (1, 1)], 1),
+			Error::<Test>::SignatoriesOutOfOrder,
+		);
+		assert_noop!(
+			Multisig::create_stateful_multisig(origin(), vec![(1, 1), (2, 0)], 1),
+			Error::<Test>::ZeroWeight,
+		);
+		assert_noop!(
+			Multisig::create_stateful_multisig(origin(), vec![(1, 1), (2, 1)], 0),
+			Error::<Test>::InvalidThreshold,
+		);
+		assert_noop!(
+			Multisig::create_stateful_multisig(origin(), vec![(1, 1), (2, 1)], 3),
+			Error::<Test>::InvalidThreshold,
+		);
+	});
+}
+
+#[test]
+fn stateful_multisig_weighted_approvals_work() {
+	new_test_ext().execute_with(|| {
+		let multisig = create_stateful(4, vec![(1, 1), (2, 2), (3, 1)], 3);
+		assert_ok!(Balances::transfer_allow_death(RuntimeOrigin::signed(1), multisig, 5));
+		assert_ok!(Balances::transfer_allow_death(RuntimeOrigin::signed(2), multisig, 5));
+
+		let call = call_transfer(6, 10);
+		let call_weight = call.get_dispatch_info().call_weight;
+		let hash = blake2_256(&call.encode());
+		assert_ok!(Multisig::approve_as_multi(
+			RuntimeOrigin::signed(1),
+			0,
+			vec![multisig],
+			None,
+			hash,
+			Weight::zero()
+		));
+		assert_eq!(Balances::free_balance(1), 1);
+		assert_eq!(Balances::reserved_balance(1), 4);
+
+		// Members 1 and 3 only weigh 2 together.
+		assert_noop!(
+			Multisig::as_multi(
+				RuntimeOrigin::signed(5),
+				0,
+				vec![multisig],
+				Some(now()),
+				call.clone(),
+				call_weight
+			),
+			Error::<Test>::NotMember,
+		);
+		// Only the account of the stateful multisig is given as other signatory.
+		assert_noop!(
+			Multisig::as_multi(
+				RuntimeOrigin::signed(3),
+				0,
+				vec![],
+				Some(now()),
+				call.clone(),
+				call_weight
+			),
+			Error::<Test>::TooFewSignatories,
+		);
+		assert_noop!(
+			Multisig::as_multi(
+				RuntimeOrigin::signed(3),
+				0,
+				vec![1, multisig],
+				Some(now()),
+				call.clone(),
+				call_weight
+			),
+			Error::<Test>::TooManySignatories,
+		);
+		assert_ok!(Multisig::as_multi(
+			RuntimeOrigin::signed(3),
+			0,
+			vec![multisig],
+			Some(now()),
+			call.clone(),
+			call_weight
+		));
+		assert_eq!(Balances::free_balance(6), 0);
+
+		assert_ok!(Multisig::as_multi(
+			RuntimeOrigin::signed(2),
+			0,
+			vec![multisig],
+			Some(now()),
+			call,
+			call_weight
+		));
+		System::assert_last_event(
+			pallet_multisig::Event::MultisigExecuted {
+				approving: 2,
+				timepoint: now(),
+				multisig,
+				call_hash: hash,
+				result: Ok(()),
+			}
+			.into(),
+		);
+		assert_eq!(Balances::free_balance(6), 10);
+		assert_eq!(Balances::free_balance(1), 5);
+		assert_eq!(Balances::reserved_balance(1), 0);
+	});
+}
+
+#[test]
+fn stateful_multisig_member_at_threshold_dispatches_immediately() {
+	new_test_ext().execute_with(|| {
+		let multisig = create_stateful(4, vec![(1, 1), (2, 2), (3, 1)], 2);
+		assert_ok!(Balances::transfer_allow_death(RuntimeOrigin::signed(1), multisig, 5));
+
+		let call = call_transfer(6, 5);
+		let call_weight = call.get_dispatch_info().call_weight;
+		assert_noop!(
+			Multisig::as_multi(
+				RuntimeOrigin::signed(2),
+				0,
+				vec![multisig],
+				None,
+				call.clone(),
+				Weight::zero()
+			),
+			Error::<Test>::MaxWeightTooLow,
+		);
+		assert_ok!(Multisig::as_multi(
+			RuntimeOrigin::signed(2),
+			0,
+			vec![multisig],
+			None,
+			call,
+			call_weight
+		));
+		assert_eq!(Balances::free_balance(6), 5);
+		assert_eq!(Balances::reserved_balance(2), 0);
+	});
+}
+
+#[test]
+fn stateful_multisig_can_update_itself() {
+	new_test_ext().execute_with(|| {
+		let multisig = create_stateful(4, vec![(1, 1), (2, 1), (3, 1)], 2);
+		assert_ok!(Balances::transfer_allow_death(RuntimeOrigin::signed(1), multisig, 5));
+
+		let call =
+			Box::new(RuntimeCall::Multisig(pallet_multisig::Call::update_stateful_multisig {
+				members: vec![(1, 2), (2, 1)],
+				threshold: 2,
+			}));
+		let call_weight = call.get_dispatch_info().call_weight;
+		assert_ok!(Multisig::as_multi(
+			RuntimeOrigin::signed(1),
+			0,
+			vec![multisig],
+			None,
+			call.clone(),
+			Weight::zero()
+		));
+		assert_ok!(Multisig::as_multi(
+			RuntimeOrigin::signed(2),
+			0,
+			vec![multisig],
+			Some(now()),
+			call,
+			call_weight
+		));
+		System::assert_has_event(
+			pallet_multisig::Event::StatefulMultisigUpdated { multisig }.into(),
+		);
+
+		// The creator keeps the deposit for the configuration, which shrank with the members.
+		let config = StatefulMultisigs::<Test>::get(multisig).unwrap();
+		assert_eq!(config.members.to_vec(), vec![(1, 2), (2, 1)]);
+		assert_eq!(config.depositor, 4);
+		assert_eq!(Balances::reserved_balance(4), 3);
+		assert_eq!(Balances::free_balance(4), 2);
+		assert_eq!(Balances::reserved_balance(multisig), 0);
+		assert_eq!(Balances::free_balance(multisig), 5);
+
+		// Member 1 now weighs enough on its own, and 3 is no longer a member.
+		assert_ok!(Multisig::as_multi(
+			RuntimeOrigin::signed(1),
+			0,
+			vec![multisig],
+			None,
+			call_transfer(6, 1),
+			call_transfer(6, 1).get_dispatch_info().call_weight
+		));
+		assert_eq!(Balances::free_balance(6), 1);
+		assert_noop!(
+			Multisig::approve_as_multi(
+				RuntimeOrigin::signed(3),
+				0,
+				vec![multisig],
+				None,
+				[0; 32],
+				Weight::zero()
+			),
+			Error::<Test>::NotMember,
+		);
+
+		// Only the multisig itself can change its configuration.
+		assert_noop!(
+			Multisig::update_stateful_multisig(RuntimeOrigin::signed(1), vec![(1, 1)], 1),
+			Error::<Test>::UnknownMultisig,
+		);
+	});
+}
+
+#[test]
+fn stateful_multisig_pending_operations_survive_updates() {
+	new_test_ext().execute_with(|| {
+		let multisig = create_stateful(4, vec![(1, 1), (2, 1), (3, 1)], 2);
+		assert_ok!(Balances::transfer_allow_death(RuntimeOrigin::signed(1), multisig, 5));
+		assert_ok!(Balances::transfer_allow_death(RuntimeOrigin::signed(2), multisig, 5));
+
+		let call = call_transfer(6, 5);
+		let call_weight = call.get_dispatch_info().call_weight;
+		let hash = blake2_256(&call.encode());
+		assert_ok!(Multisig::approve_as_multi(
+			RuntimeOrigin::signed(3),
+			0,
+			vec![multisig],
+			None,
+			hash,
+			Weight::zero()
+		));
+		assert_eq!(Balances::reserved_balance(3), 4);
+
+		assert_ok!(Multisig::update_stateful_multisig(
+			RuntimeOrigin::signed(multisig),
+			vec![(1, 1), (2, 1)],
+			2
+		));
+		assert_eq!(StatefulMultisigs::<Test>::get(multisig).unwrap().open_operations, 1);
+
+		// The approval of former member 3 was removed, while they still hold the deposit.
+		let operation = Multisigs::<Test>::get(multisig, hash).unwrap();
+		assert!(operation.approvals.is_empty());
+		assert_eq!(operation.depositor, 3);
+		assert_ok!(Multisig::as_multi(
+			RuntimeOrigin::signed(1),
+			0,
+			vec![multisig],
+			Some(now()),
+			call.clone(),
+			call_weight
+		));
+		assert_eq!(Balances::free_balance(6), 0);
+
+		assert_ok!(Multisig::as_multi(
+			RuntimeOrigin::signed(2),
+			0,
+			vec![multisig],
+			Some(now()),
+			call,
+			call_weight
+		));
+		assert_eq!(Balances::free_balance(6), 5);
+		assert_eq!(Balances::free_balance(3), 10);
+		assert_eq!(Balances::reserved_balance(3), 0);
+		assert_eq!(StatefulMultisigs::<Test>::get(multisig).unwrap().open_operations, 0);
+	});
+}
+
+#[test]
+fn stateful_multisig_operations_are_bounded() {
+	new_test_ext().execute_with(|| {
+		let multisig = create_stateful(4, vec![(1, 1), (2, 1), (3, 1)], 2);
+		let approve = |who: u64, index: u8| {
+			Multisig::approve_as_multi(
+				RuntimeOrigin::signed(who),
+				0,
+				vec![multisig],
+				None,
+				[index; 32],
+				Weight::zero(),
+			)
+		};
+		assert_ok!(approve(1, 0));
+		assert_ok!(approve(2, 1));
+		assert_ok!(approve(3, 2));
+		assert_eq!(StatefulMultisigs::<Test>::get(multisig).unwrap().open_operations, 3);
+
+		// No more operations than `MaxSignatories` can be underway.
+		assert_noop!(approve(1, 3), Error::<Test>::TooManyOperations);
+
+		assert_ok!(Multisig::cancel_as_multi(
+			RuntimeOrigin::signed(1),
+			0,
+			vec![multisig],
+			now(),
+			[0; 32]
+		));
+		assert_eq!(StatefulMultisigs::<Test>::get(multisig).unwrap().open_operations, 2);
+		assert_ok!(approve(1, 3));
+	});
+}
+
+#[test]
+fn update_stateful_multisig_adjusts_the_deposit_of_its_creator() {
+	new_test_ext().execute_with(|| {
+		let multisig = create_stateful(1, vec![(1, 1), (2, 1)], 2);
+		assert_eq!(Balances::reserved_balance(1), 3);
+
+		assert_ok!(Multisig::update_stateful_multisig(
+			RuntimeOrigin::signed(multisig),
+			vec![(1, 1), (2, 1), (3, 1)],
+			2
+		));
+		assert_eq!(StatefulMultisigs::<Test>::get(multisig).unwrap().deposit, 4);
+		assert_eq!(Balances::reserved_balance(1), 4);
+		assert_eq!(Balances::free_balance(1), 6);
+
+		// The creator must afford a larger deposit.
+		let multisig = create_stateful(4, vec![(1, 1)], 1);
+		assert_eq!(Balances::free_balance(4), 3);
+		assert_ok!(Balances::transfer_allow_death(RuntimeOrigin::signed(4), 5, 2));
+		assert_noop!(
+			Multisig::update_stateful_multisig(
+				RuntimeOrigin::signed(multisig),
+				vec![(1, 1), (2, 1), (3, 1)],
+				1
+			),
+			BalancesError::<Test, _>::InsufficientBalance,
+		);
+	});
+}
+
+#[test]
+fn cancel_stateful_operation_returns_deposit() {
+	new_test_ext().execute_with(|| {
+		let multisig = create_stateful(4, vec![(1, 1), (2, 1), (3, 1)], 2);
+		let hash = blake2_256(&call_transfer(6, 5).encode());
+		assert_ok!(Multisig::approve_as_multi(
+			RuntimeOrigin::signed(1),
+			0,
+			vec![multisig],
+			None,
+			hash,
+			Weight::zero()
+		));
+		assert_eq!(Balances::reserved_balance(1), 4);
+
+		assert_noop!(
+			Multisig::cancel_as_multi(RuntimeOrigin::signed(2), 0, vec![multisig], now(), hash),
+			Error::<Test>::NotOwner,
+		);
+		assert_ok!(Multisig::cancel_as_multi(
+			RuntimeOrigin::signed(1),
+			0,
+			vec![multisig],
+			now(),
+			hash
+		));
+		assert_eq!(Balances::free_balance(1), 10);
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert!(Multisigs::<Test>::get(multisig, hash).is_none());
+	});
+}
+
+#[test]
+fn destroy_stateful_multisig_works() {
+	new_test_ext().execute_with(|| {
+		let multisig = create_stateful(4, vec![(1, 1), (2, 1), (3, 1)], 2);
+		assert_ok!(Balances::transfer_allow_death(RuntimeOrigin::signed(2), multisig, 5));
+		let hash = blake2_256(&call_transfer(6, 5).encode());
+		assert_ok!(Multisig::approve_as_multi(
+			RuntimeOrigin::signed(1),
+			0,
+			vec![multisig],
+			None,
+			hash,
+			Weight::zero()
+		));
+
+		assert_noop!(
+			Multisig::destroy_stateful_multisig(RuntimeOrigin::signed(1)),
+			Error::<Test>::UnknownMultisig,
+		);
+		// The funds of the multisig must be moved first.
+		assert_noop!(
+			Multisig::destroy_stateful_multisig(RuntimeOrigin::signed(multisig)),
+			Error::<Test>::AccountInUse,
+		);
+		assert_ok!(Balances::transfer_all(RuntimeOrigin::signed(multisig), 6, false));
+		assert_ok!(Multisig::destroy_stateful_multisig(RuntimeOrigin::signed(multisig)));
+		System::assert_last_event(
+			pallet_multisig::Event::StatefulMultisigDestroyed { multisig }.into(),
+		);
+		assert!(!StatefulMultisigs::<Test>::contains_key(multisig));
+		assert_eq!(Balances::free_balance(4), 5);
+		assert_eq!(Balances::reserved_balance(4), 0);
+
+		assert_eq!(Balances::free_balance(6), 5);
+
+		// Pending operations can no longer be approved, only cancelled.
+		assert_noop!(
+			Multisig::approve_as_multi(
+				RuntimeOrigin::signed(2),
+				0,
+				vec![multisig],
+				Some(now()),
+				hash,
+				Weight::zero()
+			),
+			Error::<Test>::UnknownMultisig,
+		);
+		assert_ok!(Multisig::cancel_as_multi(
+			RuntimeOrigin::signed(1),
+			0,
+			vec![multisig],
+			now(),
+			hash
+		));
+		assert_eq!(Balances::reserved_balance(1), 0);
+	});
+}
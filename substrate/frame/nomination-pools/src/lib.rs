@@ -402,6 +402,7 @@ mod tests;
 
 pub mod adapter;
 pub mod migration;
+pub mod precompiles;
 pub mod weights;
 
 pub use pallet::*;
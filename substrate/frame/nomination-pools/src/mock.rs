@@ -438,6 +438,13 @@ impl pallet_balances::Config for Runtime {
 	type RuntimeFreezeReason = RuntimeFreezeReason;
 }
 
+#[derive_impl(pallet_revive::config_preludes::TestDefaultConfig)]
+impl pallet_revive::Config for Runtime {
+	type AddressMapper = pallet_revive::TestAccountMapper<Self>;
+	type Currency = Balances;
+	type Precompiles = (crate::precompiles::NominationPoolsPrecompile<Self>,);
+}
+
 pub struct BalanceToU256;
 impl Convert<Balance, U256> for BalanceToU256 {
 	fn convert(n: Balance) -> U256 {
@@ -495,6 +502,7 @@ frame_support::construct_runtime!(
 		System: frame_system,
 		Balances: pallet_balances,
 		Pools: pools,
+		Revive: pallet_revive,
 	}
 );
 
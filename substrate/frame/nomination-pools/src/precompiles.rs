@@ -0,0 +1,319 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A precompile exposing nomination pools to contracts.
+
+use crate::{
+	BalanceOf, BondExtra, Config, LastPoolId, Pallet, PoolMember, PoolMembers, WeightInfo,
+};
+use alloc::vec::Vec;
+use core::{marker::PhantomData, num::NonZero};
+use pallet_revive::precompiles::{
+	alloy::{
+		self,
+		primitives::{Address, U256},
+		sol_types::SolCall,
+	},
+	charge_read, dispatch, dispatch_with_refund, evm_to_native, native_to_evm, AddressMapper,
+	AddressMatcher, Error, Ext, Precompile, H160,
+};
+use sp_runtime::traits::StaticLookup;
+
+alloy::sol!("src/precompiles/INominationPools.sol");
+use INominationPools::INominationPoolsCalls;
+
+const ERR_BALANCE_CONVERSION_FAILED: &str = "Balance conversion failed";
+
+/// The nomination pools precompile, available at the fixed address `0x0E`.
+///
+/// Pools are joined, created and managed on behalf of the calling contract.
+pub struct NominationPoolsPrecompile<T>(PhantomData<T>);
+
+impl<Runtime> Precompile for NominationPoolsPrecompile<Runtime>
+where
+	Runtime: Config + pallet_revive::Config,
+	U256: TryInto<BalanceOf<Runtime>> + TryFrom<BalanceOf<Runtime>>,
+{
+	type T = Runtime;
+	const MATCHER: AddressMatcher = AddressMatcher::Fixed(NonZero::new(14).unwrap());
+	const HAS_CONTRACT_INFO: bool = false;
+	type Interface = INominationPools::INominationPoolsCalls;
+
+	fn call(
+		_address: &[u8; 20],
+		input: &Self::Interface,
+		env: &mut impl Ext<T = Self::T>,
+	) -> Result<Vec<u8>, Error> {
+		type Weights<T> = <T as Config>::WeightInfo;
+
+		match input {
+			INominationPoolsCalls::join(INominationPools::joinCall { amount, poolId: pool_id }) => {
+				let amount = Self::to_balance(*amount)?;
+				dispatch(env, Weights::<Runtime>::join(), |origin| {
+					Pallet::<Runtime>::join(origin, amount, *pool_id)
+				})
+			},
+			INominationPoolsCalls::bondExtra(INominationPools::bondExtraCall { amount }) => {
+				let extra = BondExtra::FreeBalance(Self::to_balance(*amount)?);
+				dispatch(env, Weights::<Runtime>::bond_extra_transfer(), |origin| {
+					Pallet::<Runtime>::bond_extra(origin, extra)
+				})
+			},
+			INominationPoolsCalls::bondExtraRewards(_) =>
+				dispatch(env, Weights::<Runtime>::bond_extra_other(), |origin| {
+					Pallet::<Runtime>::bond_extra(origin, BondExtra::Rewards)
+				}),
+			INominationPoolsCalls::claimPayout(_) =>
+				dispatch(env, Weights::<Runtime>::claim_payout(), |origin| {
+					Pallet::<Runtime>::claim_payout(origin)
+				}),
+			INominationPoolsCalls::unbond(INominationPools::unbondCall { member, points }) => {
+				let member = Self::to_lookup(member);
+				let points = Self::to_balance(*points)?;
+				dispatch(env, Weights::<Runtime>::unbond(), |origin| {
+					Pallet::<Runtime>::unbond(origin, member, points)
+				})
+			},
+			INominationPoolsCalls::withdrawUnbonded(INominationPools::withdrawUnbondedCall {
+				member,
+				numSlashingSpans: num_slashing_spans,
+			}) => {
+				let member = Self::to_lookup(member);
+				dispatch_with_refund(
+					env,
+					Weights::<Runtime>::withdraw_unbonded_kill(*num_slashing_spans),
+					|origin| {
+						Pallet::<Runtime>::withdraw_unbonded(origin, member, *num_slashing_spans)
+					},
+				)
+			},
+			INominationPoolsCalls::create(INominationPools::createCall {
+				amount,
+				root,
+				nominator,
+				bouncer,
+			}) => {
+				let amount = Self::to_balance(*amount)?;
+				let (root, nominator, bouncer) =
+					(Self::to_lookup(root), Self::to_lookup(nominator), Self::to_lookup(bouncer));
+				dispatch(env, Weights::<Runtime>::create(), |origin| {
+					Pallet::<Runtime>::create(origin, amount, root, nominator, bouncer)
+				})?;
+				Ok(INominationPools::createCall::abi_encode_returns(&LastPoolId::<Runtime>::get()))
+			},
+			INominationPoolsCalls::nominate(INominationPools::nominateCall {
+				poolId: pool_id,
+				validators,
+			}) => {
+				let weight = Weights::<Runtime>::nominate(validators.len() as u32);
+				let validators = validators.iter().map(Self::to_account_id).collect();
+				dispatch(env, weight, |origin| {
+					Pallet::<Runtime>::nominate(origin, *pool_id, validators)
+				})
+			},
+			INominationPoolsCalls::memberOf(INominationPools::memberOfCall { member }) => {
+				charge_read::<Runtime, PoolMember<Runtime>>(env)?;
+				let ret = match PoolMembers::<Runtime>::get(Self::to_account_id(member)) {
+					Some(member) => INominationPools::memberOfReturn {
+						exists: true,
+						poolId: member.pool_id,
+						points: Self::to_u256(member.points)?,
+					},
+					None => INominationPools::memberOfReturn {
+						exists: false,
+						poolId: 0,
+						points: U256::ZERO,
+					},
+				};
+				Ok(INominationPools::memberOfCall::abi_encode_returns(&ret))
+			},
+			INominationPoolsCalls::pendingRewards(INominationPools::pendingRewardsCall {
+				member,
+			}) => {
+				// Computing the pending rewards reads the same state as paying them out.
+				env.charge(Weights::<Runtime>::claim_payout())?;
+				let pending = Pallet::<Runtime>::api_pending_rewards(Self::to_account_id(member))
+					.map(Self::to_u256)
+					.transpose()?
+					.unwrap_or_default();
+				Ok(INominationPools::pendingRewardsCall::abi_encode_returns(&pending))
+			},
+		}
+	}
+}
+
+impl<Runtime> NominationPoolsPrecompile<Runtime>
+where
+	Runtime: Config + pallet_revive::Config,
+	U256: TryInto<BalanceOf<Runtime>> + TryFrom<BalanceOf<Runtime>>,
+{
+	/// Map an Ethereum address to its account.
+	fn to_account_id(address: &Address) -> Runtime::AccountId {
+		<Runtime as pallet_revive::Config>::AddressMapper::to_account_id(&H160::from(
+			address.into_array(),
+		))
+	}
+
+	/// Map an Ethereum address to the lookup source of its account.
+	fn to_lookup(address: &Address) -> <Runtime::Lookup as StaticLookup>::Source {
+		Runtime::Lookup::unlookup(Self::to_account_id(address))
+	}
+
+	/// Convert a `U256` value in ETH decimals to the balance type of the pallet.
+	fn to_balance(value: U256) -> Result<BalanceOf<Runtime>, Error> {
+		evm_to_native::<Runtime>(value)?
+			.try_into()
+			.map_err(|_| Error::Revert(ERR_BALANCE_CONVERSION_FAILED.into()))
+	}
+
+	/// Convert a balance to a `U256` value in ETH decimals.
+	fn to_u256(value: BalanceOf<Runtime>) -> Result<U256, Error> {
+		let value = U256::try_from(value)
+			.map_err(|_| Error::Revert(ERR_BALANCE_CONVERSION_FAILED.into()))?;
+		native_to_evm::<Runtime>(value)
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::{
+		mock::{default_reward_account, AccountId, Currency, ExtBuilder, Runtime, RuntimeOrigin},
+		BondedPools,
+	};
+	use frame_support::{
+		traits::fungible::{Inspect, Mutate},
+		weights::Weight,
+	};
+	use pallet_revive::{
+		precompiles::alloy::{hex, sol_types::SolInterface},
+		DepositLimit, ExecReturnValue,
+	};
+
+	fn precompile_address() -> H160 {
+		H160::from(hex::const_decode_to_array(b"00000000000000000000000000000000000E0000").unwrap())
+	}
+
+	fn address_of(account: AccountId) -> Address {
+		<Runtime as pallet_revive::Config>::AddressMapper::to_address(&account).0.into()
+	}
+
+	fn call(origin: AccountId, call: INominationPoolsCalls) -> ExecReturnValue {
+		pallet_revive::Pallet::<Runtime>::bare_call(
+			RuntimeOrigin::signed(origin),
+			precompile_address(),
+			0,
+			Weight::MAX,
+			DepositLimit::UnsafeOnlyForDryRun,
+			call.abi_encode(),
+		)
+		.result
+		.unwrap()
+	}
+
+	fn member_of(member: AccountId) -> INominationPools::memberOfReturn {
+		let result = call(
+			member,
+			INominationPoolsCalls::memberOf(INominationPools::memberOfCall {
+				member: address_of(member),
+			}),
+		);
+		INominationPools::memberOfCall::abi_decode_returns(&result.data).unwrap()
+	}
+
+	fn pending_rewards(member: AccountId) -> U256 {
+		let result = call(
+			member,
+			INominationPoolsCalls::pendingRewards(INominationPools::pendingRewardsCall {
+				member: address_of(member),
+			}),
+		);
+		INominationPools::pendingRewardsCall::abi_decode_returns(&result.data).unwrap()
+	}
+
+	#[test]
+	fn join_and_claim_payout_works() {
+		ExtBuilder::default().build_and_execute(|| {
+			Currency::set_balance(&11, 100);
+			assert!(!member_of(11).exists);
+
+			let result = call(
+				11,
+				INominationPoolsCalls::join(INominationPools::joinCall {
+					amount: U256::from(10),
+					poolId: 1,
+				}),
+			);
+			assert!(!result.did_revert());
+
+			let member = member_of(11);
+			assert!(member.exists);
+			assert_eq!(member.poolId, 1);
+			assert_eq!(member.points, U256::from(10));
+
+			// The depositor and 11 share the rewards equally.
+			let reward_balance = Currency::balance(&default_reward_account());
+			Currency::set_balance(&default_reward_account(), reward_balance + 20);
+			assert_eq!(pending_rewards(11), U256::from(10));
+
+			let result =
+				call(11, INominationPoolsCalls::claimPayout(INominationPools::claimPayoutCall {}));
+			assert!(!result.did_revert());
+			assert_eq!(pending_rewards(11), U256::ZERO);
+		});
+	}
+
+	#[test]
+	fn create_works() {
+		ExtBuilder::default().build_and_execute(|| {
+			Currency::set_balance(&20, 100);
+
+			let result = call(
+				20,
+				INominationPoolsCalls::create(INominationPools::createCall {
+					amount: U256::from(10),
+					root: address_of(20),
+					nominator: address_of(21),
+					bouncer: address_of(22),
+				}),
+			);
+			assert!(!result.did_revert());
+
+			let pool_id = INominationPools::createCall::abi_decode_returns(&result.data).unwrap();
+			assert_eq!(pool_id, 2);
+			let pool = BondedPools::<Runtime>::get(pool_id).unwrap();
+			assert_eq!(pool.roles.depositor, 20);
+			assert_eq!(pool.roles.nominator, Some(21));
+			assert_eq!(member_of(20).poolId, 2);
+		});
+	}
+
+	#[test]
+	fn invalid_amount_reverts() {
+		ExtBuilder::default().build_and_execute(|| {
+			let result = call(
+				11,
+				INominationPoolsCalls::join(INominationPools::joinCall {
+					amount: U256::MAX,
+					poolId: 1,
+				}),
+			);
+			assert!(result.did_revert());
+			assert!(!member_of(11).exists);
+		});
+	}
+}
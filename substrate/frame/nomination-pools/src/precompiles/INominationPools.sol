@@ -0,0 +1,57 @@
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.20;
+
+/// @title Defines all functions that can be used to stake through nomination pools
+/// @dev Amounts, points and rewards are in ETH decimals (18), like the balance of an account
+interface INominationPools {
+    /// @notice Join a pool, bonding `amount` of the caller's free balance
+    /// @param amount The amount to bond
+    /// @param poolId The pool to join
+    function join(uint256 amount, uint32 poolId) external;
+
+    /// @notice Bond more of the caller's free balance into its pool
+    /// @param amount The amount to bond
+    function bondExtra(uint256 amount) external;
+
+    /// @notice Bond the pending rewards of the caller into its pool
+    function bondExtraRewards() external;
+
+    /// @notice Pay out the pending rewards of the caller
+    function claimPayout() external;
+
+    /// @notice Unbond points of a member
+    /// @dev Unbonding the points of another member is only allowed in some pool states
+    /// @param member The member to unbond
+    /// @param points The number of points to unbond
+    function unbond(address member, uint256 points) external;
+
+    /// @notice Withdraw the unlocked funds of a member
+    /// @param member The member to withdraw the funds of
+    /// @param numSlashingSpans The number of slashing spans of the pool's stash
+    function withdrawUnbonded(address member, uint32 numSlashingSpans) external;
+
+    /// @notice Create a pool, bonding `amount` of the caller's free balance as the depositor
+    /// @param amount The amount to bond
+    /// @param root The root of the pool
+    /// @param nominator The account that can nominate for the pool
+    /// @param bouncer The account that can change the state of the pool
+    /// @return poolId The id of the created pool
+    function create(uint256 amount, address root, address nominator, address bouncer) external returns (uint32 poolId);
+
+    /// @notice Nominate validators for a pool, the caller must be its root or nominator
+    /// @param poolId The pool to nominate for
+    /// @param validators The validators to nominate
+    function nominate(uint32 poolId, address[] calldata validators) external;
+
+    /// @notice Get the membership of an account
+    /// @param member The account to look up
+    /// @return exists Whether the account is a member of a pool
+    /// @return poolId The pool of the member
+    /// @return points The points of the member in its pool
+    function memberOf(address member) external view returns (bool exists, uint32 poolId, uint256 points);
+
+    /// @notice Get the pending rewards of a member
+    /// @param member The account to look up
+    /// @return The pending rewards, zero if the account is not a member
+    function pendingRewards(address member) external view returns (uint256);
+}
@@ -0,0 +1,251 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A precompile exposing the proxy pallet to contracts.
+
+use crate::{AccountIdLookupOf, BlockNumberFor, Config, Pallet, WeightInfo};
+use alloc::vec::Vec;
+use codec::DecodeAll;
+use core::{marker::PhantomData, num::NonZero};
+use frame::prelude::*;
+use pallet_revive::precompiles::{
+	alloy::{self, primitives::Address, sol_types::SolValue},
+	dispatch, AddressMapper, AddressMatcher, Error, Ext, Precompile, H160,
+};
+
+alloy::sol!("src/precompiles/IProxy.sol");
+use IProxy::IProxyCalls;
+
+const ERR_INVALID_PROXY_TYPE: &str = "Invalid proxy type";
+
+/// The proxy precompile, available at the fixed address `0x0B`.
+///
+/// Proxies are added and removed on behalf of the calling contract.
+pub struct ProxyPrecompile<T>(PhantomData<T>);
+
+impl<Runtime> Precompile for ProxyPrecompile<Runtime>
+where
+	Runtime: Config + pallet_revive::Config,
+	BlockNumberFor<Runtime>: From<u32>,
+{
+	type T = Runtime;
+	const MATCHER: AddressMatcher = AddressMatcher::Fixed(NonZero::new(11).unwrap());
+	const HAS_CONTRACT_INFO: bool = false;
+	type Interface = IProxy::IProxyCalls;
+
+	fn call(
+		_address: &[u8; 20],
+		input: &Self::Interface,
+		env: &mut impl Ext<T = Self::T>,
+	) -> Result<Vec<u8>, Error> {
+		type Weights<T> = <T as Config>::WeightInfo;
+
+		match input {
+			IProxyCalls::addProxy(IProxy::addProxyCall {
+				delegate,
+				proxyType: proxy_type,
+				delay,
+			}) => {
+				let (delegate, proxy_type) =
+					(Self::to_lookup(delegate), Self::to_proxy_type(proxy_type)?);
+				dispatch(env, Weights::<Runtime>::add_proxy(Runtime::MaxProxies::get()), |origin| {
+					Pallet::<Runtime>::add_proxy(origin, delegate, proxy_type, (*delay).into())
+				})
+			},
+			IProxyCalls::removeProxy(IProxy::removeProxyCall {
+				delegate,
+				proxyType: proxy_type,
+				delay,
+			}) => {
+				let (delegate, proxy_type) =
+					(Self::to_lookup(delegate), Self::to_proxy_type(proxy_type)?);
+				dispatch(
+					env,
+					Weights::<Runtime>::remove_proxy(Runtime::MaxProxies::get()),
+					|origin| {
+						Pallet::<Runtime>::remove_proxy(
+							origin,
+							delegate,
+							proxy_type,
+							(*delay).into(),
+						)
+					},
+				)
+			},
+			IProxyCalls::removeProxies(_) => dispatch(
+				env,
+				Weights::<Runtime>::remove_proxies(Runtime::MaxProxies::get()),
+				|origin| Pallet::<Runtime>::remove_proxies(origin),
+			),
+			IProxyCalls::isProxy(IProxy::isProxyCall { real, delegate, proxyType: proxy_type }) => {
+				// Finding a proxy is the first step of `proxy`, which bounds its cost.
+				env.charge(Weights::<Runtime>::proxy(Runtime::MaxProxies::get()))?;
+				let proxy_type = if proxy_type.is_empty() {
+					None
+				} else {
+					Some(Self::to_proxy_type(proxy_type)?)
+				};
+				let is_proxy = Pallet::<Runtime>::find_proxy(
+					&Self::to_account_id(real),
+					&Self::to_account_id(delegate),
+					proxy_type,
+				)
+				.is_ok();
+				Ok(is_proxy.abi_encode())
+			},
+		}
+	}
+}
+
+impl<Runtime> ProxyPrecompile<Runtime>
+where
+	Runtime: Config + pallet_revive::Config,
+{
+	/// Map an Ethereum address to its account.
+	fn to_account_id(address: &Address) -> Runtime::AccountId {
+		<Runtime as pallet_revive::Config>::AddressMapper::to_account_id(&H160::from(
+			address.into_array(),
+		))
+	}
+
+	/// Map an Ethereum address to the lookup source of its account.
+	fn to_lookup(address: &Address) -> AccountIdLookupOf<Runtime> {
+		<Runtime as frame_system::Config>::Lookup::unlookup(Self::to_account_id(address))
+	}
+
+	/// Decode the SCALE encoded proxy type.
+	fn to_proxy_type(encoded: &[u8]) -> Result<Runtime::ProxyType, Error> {
+		Runtime::ProxyType::decode_all(&mut &encoded[..])
+			.map_err(|_| Error::Revert(ERR_INVALID_PROXY_TYPE.into()))
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::{
+		tests::{new_test_ext, ProxyType, RuntimeOrigin, Test},
+		Proxies, ProxyDefinition,
+	};
+	use alloc::vec;
+	use codec::Encode;
+	use frame::testing_prelude::*;
+	use pallet_revive::{
+		precompiles::alloy::{hex, sol_types::SolInterface},
+		DepositLimit,
+	};
+
+	fn precompile_address() -> H160 {
+		H160::from(hex::const_decode_to_array(b"00000000000000000000000000000000000B0000").unwrap())
+	}
+
+	fn address_of(account: u64) -> Address {
+		<Test as pallet_revive::Config>::AddressMapper::to_address(&account).0.into()
+	}
+
+	fn call(origin: u64, call: IProxyCalls) -> pallet_revive::ExecReturnValue {
+		pallet_revive::Pallet::<Test>::bare_call(
+			RuntimeOrigin::signed(origin),
+			precompile_address(),
+			0u64,
+			Weight::MAX,
+			DepositLimit::UnsafeOnlyForDryRun,
+			call.abi_encode(),
+		)
+		.result
+		.unwrap()
+	}
+
+	#[test]
+	fn add_and_remove_proxy_works() {
+		new_test_ext().execute_with(|| {
+			let result = call(
+				1,
+				IProxyCalls::addProxy(IProxy::addProxyCall {
+					delegate: address_of(2),
+					proxyType: ProxyType::JustTransfer.encode().into(),
+					delay: 1,
+				}),
+			);
+			assert!(!result.did_revert());
+			assert_eq!(
+				Proxies::<Test>::get(1).0.into_inner(),
+				vec![ProxyDefinition {
+					delegate: 2,
+					proxy_type: ProxyType::JustTransfer,
+					delay: 1
+				}]
+			);
+
+			let is_proxy = |proxy_type: Vec<u8>| {
+				let result = call(
+					3,
+					IProxyCalls::isProxy(IProxy::isProxyCall {
+						real: address_of(1),
+						delegate: address_of(2),
+						proxyType: proxy_type.into(),
+					}),
+				);
+				bool::abi_decode(&result.data).unwrap()
+			};
+			assert!(is_proxy(Vec::new()));
+			assert!(is_proxy(ProxyType::JustTransfer.encode()));
+			assert!(!is_proxy(ProxyType::Any.encode()));
+
+			let result = call(
+				1,
+				IProxyCalls::removeProxy(IProxy::removeProxyCall {
+					delegate: address_of(2),
+					proxyType: ProxyType::JustTransfer.encode().into(),
+					delay: 1,
+				}),
+			);
+			assert!(!result.did_revert());
+			assert!(Proxies::<Test>::get(1).0.is_empty());
+			assert!(!is_proxy(Vec::new()));
+		});
+	}
+
+	#[test]
+	fn remove_proxies_works() {
+		new_test_ext().execute_with(|| {
+			for delegate in [2, 3] {
+				assert_ok!(Pallet::<Test>::add_proxy_delegate(&1, delegate, ProxyType::Any, 0));
+			}
+
+			let result = call(1, IProxyCalls::removeProxies(IProxy::removeProxiesCall {}));
+			assert!(!result.did_revert());
+			assert!(Proxies::<Test>::get(1).0.is_empty());
+		});
+	}
+
+	#[test]
+	fn invalid_proxy_type_reverts() {
+		new_test_ext().execute_with(|| {
+			let result = call(
+				1,
+				IProxyCalls::addProxy(IProxy::addProxyCall {
+					delegate: address_of(2),
+					proxyType: vec![42u8].into(),
+					delay: 0,
+				}),
+			);
+			assert!(result.did_revert());
+			assert!(Proxies::<Test>::get(1).0.is_empty());
+		});
+	}
+}
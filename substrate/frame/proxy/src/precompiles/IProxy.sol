@@ -0,0 +1,29 @@
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.20;
+
+/// @title Defines all functions that can be used to manage the proxies of a contract
+/// @dev The `proxyType` parameters MUST use the SCALE encoding of the runtime's proxy type
+interface IProxy {
+    /// @notice Register `delegate` as a proxy of the caller
+    /// @param delegate The account that will be able to dispatch calls on behalf of the caller
+    /// @param proxyType The permissions granted to the proxy
+    /// @param delay The number of blocks an announcement must be in place before the proxy call
+    function addProxy(address delegate, bytes calldata proxyType, uint32 delay) external;
+
+    /// @notice Unregister a proxy of the caller
+    /// @dev The parameters must match the ones used when the proxy was added
+    /// @param delegate The account that was registered as a proxy
+    /// @param proxyType The permissions granted to the proxy
+    /// @param delay The announcement delay of the proxy
+    function removeProxy(address delegate, bytes calldata proxyType, uint32 delay) external;
+
+    /// @notice Unregister all the proxies of the caller
+    function removeProxies() external;
+
+    /// @notice Check whether `delegate` is a proxy of `real`
+    /// @param real The account being proxied
+    /// @param delegate The account acting as a proxy
+    /// @param proxyType The required proxy type, or empty bytes to accept any type
+    /// @return Whether such a proxy is registered
+    function isProxy(address real, address delegate, bytes calldata proxyType) external view returns (bool);
+}
@@ -32,6 +32,7 @@ construct_runtime!(
 		Balances: pallet_balances,
 		Proxy: proxy,
 		Utility: pallet_utility,
+		Revive: pallet_revive,
 	}
 );
 
@@ -48,6 +49,13 @@ impl pallet_balances::Config for Test {
 	type AccountStore = System;
 }
 
+#[derive_impl(pallet_revive::config_preludes::TestDefaultConfig)]
+impl pallet_revive::Config for Test {
+	type AddressMapper = pallet_revive::TestAccountMapper<Self>;
+	type Currency = Balances;
+	type Precompiles = (crate::precompiles::ProxyPrecompile<Self>,);
+}
+
 impl pallet_utility::Config for Test {
 	type RuntimeEvent = RuntimeEvent;
 	type RuntimeCall = RuntimeCall;
@@ -0,0 +1,38 @@
+-- The blocks processed by the receipt provider, along with the bloom of their logs.
+CREATE TABLE IF NOT EXISTS indexed_blocks (
+	block_number INTEGER NOT NULL PRIMARY KEY,
+	block_hash BLOB NOT NULL,
+	logs_bloom BLOB NOT NULL
+);
+
+-- The progress of the historical block indexer, used to resume it after a restart.
+CREATE TABLE IF NOT EXISTS indexer_state (
+	id INTEGER NOT NULL PRIMARY KEY CHECK (id = 0),
+	start_block INTEGER NOT NULL,
+	next_block INTEGER NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_logs_address ON logs (
+	address,
+	block_number
+);
+
+CREATE INDEX IF NOT EXISTS idx_logs_topic_0 ON logs (
+	topic_0,
+	block_number
+);
+
+CREATE INDEX IF NOT EXISTS idx_logs_topic_1 ON logs (
+	topic_1,
+	block_number
+);
+
+CREATE INDEX IF NOT EXISTS idx_logs_topic_2 ON logs (
+	topic_2,
+	block_number
+);
+
+CREATE INDEX IF NOT EXISTS idx_logs_topic_3 ON logs (
+	topic_3,
+	block_number
+);
@@ -0,0 +1,109 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use crate::*;
+use jsonrpsee::{
+	core::SubscriptionResult, proc_macros::rpc, PendingSubscriptionSink, SubscriptionMessage,
+};
+use tokio::sync::broadcast::error::RecvError;
+
+/// Ethereum publish/subscribe JSON-RPC apis.
+#[rpc(server, client)]
+pub trait EthPubSubRpc {
+	/// Subscribe to new block headers, logs or pending transactions.
+	///
+	/// ## References
+	///
+	/// - <https://geth.ethereum.org/docs/interacting-with-geth/rpc/pubsub>
+	#[subscription(
+		name = "eth_subscribe" => "eth_subscription",
+		unsubscribe = "eth_unsubscribe",
+		item = SubscriptionItem
+	)]
+	async fn subscribe(&self, kind: SubscriptionKind, filter: Option<Filter>)
+		-> SubscriptionResult;
+}
+
+pub struct EthPubSubRpcServerImpl {
+	client: client::Client,
+}
+
+impl EthPubSubRpcServerImpl {
+	pub fn new(client: client::Client) -> Self {
+		Self { client }
+	}
+}
+
+/// Return the items to notify to a subscription of the given kind, for the given event.
+fn subscription_items(
+	kind: SubscriptionKind,
+	filter: &Filter,
+	event: ChainEvent,
+) -> Vec<SubscriptionItem> {
+	match (kind, event) {
+		(SubscriptionKind::NewHeads, ChainEvent::Enacted { block, .. }) =>
+			vec![Box::new(Block::clone(&block)).into()],
+		(SubscriptionKind::Logs, ChainEvent::Enacted { logs, .. }) |
+		(SubscriptionKind::Logs, ChainEvent::Retracted { logs, .. }) => logs
+			.iter()
+			.filter(|log| filter.matches_log(log))
+			.cloned()
+			.map(SubscriptionItem::Log)
+			.collect(),
+		(SubscriptionKind::NewPendingTransactions, ChainEvent::PendingTransaction(hash)) =>
+			vec![hash.into()],
+		_ => vec![],
+	}
+}
+
+#[async_trait]
+impl EthPubSubRpcServer for EthPubSubRpcServerImpl {
+	async fn subscribe(
+		&self,
+		pending: PendingSubscriptionSink,
+		kind: SubscriptionKind,
+		filter: Option<Filter>,
+	) -> SubscriptionResult {
+		if filter.is_some() && kind != SubscriptionKind::Logs {
+			pending.reject(EthRpcError::InvalidSubscriptionParams).await;
+			return Ok(());
+		}
+
+		let filter = filter.unwrap_or_default();
+		let mut events = self.client.subscribe_chain_events();
+		let sink = pending.accept().await?;
+
+		loop {
+			let event = tokio::select! {
+				_ = sink.closed() => break,
+				event = events.recv() => event,
+			};
+
+			match event {
+				Ok(event) =>
+					for item in subscription_items(kind, &filter, event) {
+						sink.send(SubscriptionMessage::from_json(&item)?).await?;
+					},
+				Err(RecvError::Lagged(skipped)) => {
+					log::warn!(target: LOG_TARGET, "Subscription lagged behind, {skipped} events skipped");
+				},
+				Err(RecvError::Closed) => break,
+			}
+		}
+
+		Ok(())
+	}
+}
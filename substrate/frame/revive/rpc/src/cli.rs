@@ -17,9 +17,9 @@
 //! The Ethereum JSON-RPC server.
 use crate::{
 	client::{connect, Client, SubscriptionType, SubstrateBlockNumber},
-	DebugRpcServer, DebugRpcServerImpl, EthRpcServer, EthRpcServerImpl, ReceiptExtractor,
-	ReceiptProvider, SubxtBlockInfoProvider, SystemHealthRpcServer, SystemHealthRpcServerImpl,
-	LOG_TARGET,
+	DebugRpcServer, DebugRpcServerImpl, EthPubSubRpcServer, EthPubSubRpcServerImpl, EthRpcServer,
+	EthRpcServerImpl, ReceiptExtractor, ReceiptProvider, SubxtBlockInfoProvider,
+	SystemHealthRpcServer, SystemHealthRpcServerImpl, LOG_TARGET,
 };
 use clap::Parser;
 use futures::{pin_mut, FutureExt};
@@ -65,6 +65,18 @@ pub struct CliCommand {
 	#[clap(long)]
 	pub index_last_n_blocks: Option<SubstrateBlockNumber>,
 
+	/// If provided, index all the finalized blocks from the given block number, and keep indexing
+	/// newly finalized blocks. The indexing resumes where it stopped after a restart.
+	///
+	/// This requires an archive node, and a persistent `--database-url`.
+	#[clap(long)]
+	pub index_from_block: Option<SubstrateBlockNumber>,
+
+	/// The number of finalized blocks whose receipts and logs are kept by the indexer.
+	/// Older blocks are pruned. All the blocks are kept if not provided.
+	#[clap(long, requires = "index_from_block")]
+	pub index_retention_blocks: Option<SubstrateBlockNumber>,
+
 	#[allow(missing_docs)]
 	#[clap(flatten)]
 	pub shared_params: SharedParams,
@@ -162,12 +174,19 @@ pub fn run(cmd: CliCommand) -> anyhow::Result<()> {
 		database_url,
 		earliest_receipt_block,
 		index_last_n_blocks,
+		index_from_block,
+		index_retention_blocks,
 		shared_params,
 		..
 	} = cmd;
 
 	#[cfg(not(test))]
 	init_logger(&shared_params)?;
+
+	if index_from_block.is_some() && database_url == IN_MEMORY_DB {
+		log::warn!(target: LOG_TARGET, "🗄️ Indexing blocks into an in-memory database, only the latest {cache_size} blocks are kept, and the progress is lost on restart");
+	}
+
 	let is_dev = shared_params.dev;
 	let rpc_addrs: Option<Vec<sc_service::config::RpcEndpoint>> = rpc_params
 		.rpc_addr(is_dev, false, 8545)?
@@ -224,6 +243,23 @@ pub fn run(cmd: CliCommand) -> anyhow::Result<()> {
 		None,
 	)?;
 
+	if let Some(index_from_block) = index_from_block {
+		let client = client.clone();
+		task_manager.spawn_essential_handle().spawn("block-indexer", None, async move {
+			client.index_finalized_blocks(index_from_block, index_retention_blocks).await
+		});
+	}
+
+	let filters_client = client.clone();
+	task_manager
+		.spawn_handle()
+		.spawn("filters-purge", None, async move { filters_client.purge_expired_filters().await });
+
+	let pending_client = client.clone();
+	task_manager.spawn_handle().spawn("pending-transactions", None, async move {
+		pending_client.subscribe_pending_transactions().await
+	});
+
 	task_manager
 		.spawn_essential_handle()
 		.spawn("block-subscription", None, async move {
@@ -254,11 +290,13 @@ fn rpc_module(is_dev: bool, client: Client) -> Result<RpcModule<()>, sc_service:
 		.with_accounts(if is_dev { vec![crate::Account::default()] } else { vec![] })
 		.into_rpc();
 
+	let pubsub_api = EthPubSubRpcServerImpl::new(client.clone()).into_rpc();
 	let health_api = SystemHealthRpcServerImpl::new(client.clone()).into_rpc();
 	let debug_api = DebugRpcServerImpl::new(client).into_rpc();
 
 	let mut module = RpcModule::new(());
 	module.merge(eth_api).map_err(|e| sc_service::Error::Application(e.into()))?;
+	module.merge(pubsub_api).map_err(|e| sc_service::Error::Application(e.into()))?;
 	module.merge(health_api).map_err(|e| sc_service::Error::Application(e.into()))?;
 	module.merge(debug_api).map_err(|e| sc_service::Error::Application(e.into()))?;
 	Ok(module)
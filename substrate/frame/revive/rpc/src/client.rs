@@ -24,9 +24,11 @@ use runtime_api::RuntimeApi;
 use storage_api::StorageApi;
 
 use crate::{
+	proof::{self, ProofError},
 	subxt_client::{self, revive::calls::types::EthTransact, SrcChainConfig},
-	BlockInfoProvider, BlockTag, FeeHistoryProvider, ReceiptProvider, SubxtBlockInfoProvider,
-	TracerType, TransactionInfo, LOG_TARGET,
+	BlockInfoProvider, BlockTag, ChainEvent, FeeHistoryProvider, FilterKind, FilterProvider,
+	ReceiptProvider, SubscriptionProvider, SubxtBlockInfoProvider, TracerType, TransactionInfo,
+	LOG_TARGET, MAX_REORG_DEPTH,
 };
 use jsonrpsee::{
 	core::traits::ToRpcParams,
@@ -35,12 +37,18 @@ use jsonrpsee::{
 };
 use pallet_revive::{
 	evm::{
-		decode_revert_reason, Block, BlockNumberOrTag, BlockNumberOrTagOrHash, FeeHistoryResult,
-		Filter, GenericTransaction, Log, ReceiptInfo, SyncingProgress, SyncingStatus, Trace,
-		TransactionSigned, TransactionTrace, H256, U256,
+		decode_revert_reason, AccessListResult, AccountProof, Block, BlockNumberOrTag,
+		BlockNumberOrTagOrHash, Bytes, FeeHistoryResult, Filter, FilterResults, GenericTransaction,
+		Log, ReceiptInfo, StorageProof, SyncingProgress, SyncingStatus, Trace, TransactionSigned,
+		TransactionTrace, H160, H256, U256,
 	},
 	EthTransactError,
 };
+use sc_rpc_api::state::ReadProof;
+use sp_core::{
+	keccak_256,
+	storage::{ChildInfo, StorageKey},
+};
 use sp_runtime::traits::Block as BlockT;
 use sp_weights::Weight;
 use std::{ops::Range, sync::Arc, time::Duration};
@@ -56,6 +64,7 @@ use subxt::{
 	Config, OnlineClient,
 };
 use thiserror::Error;
+use tokio::sync::broadcast;
 
 /// The substrate block type.
 pub type SubstrateBlock = subxt::blocks::Block<SrcChainConfig, OnlineClient<SrcChainConfig>>;
@@ -124,9 +133,41 @@ pub enum ClientError {
 	/// Failed to filter logs.
 	#[error("Failed to filter logs")]
 	LogFilterFailed(#[from] anyhow::Error),
+	/// The filter was not found.
+	#[error("filter not found")]
+	FilterNotFound,
+	/// Too many filters are installed.
+	#[error("too many filters installed")]
+	TooManyFilters,
+	/// A [`ProofError`] wrapper error.
+	#[error(transparent)]
+	ProofError(#[from] ProofError),
 }
 
 const REVERT_CODE: i32 = 3;
+
+/// Returns the payload of an encoded bare `eth_transact` extrinsic, or `None` if the extrinsic is
+/// not an `eth_transact` call.
+fn eth_transact_payload(extrinsic: &[u8], pallet_index: u8, call_index: u8) -> Option<Vec<u8>> {
+	use codec::Decode;
+
+	let extrinsic = Vec::<u8>::decode(&mut &extrinsic[..]).ok()?;
+	// Bare extrinsics of version 4 and 5.
+	let (&[0x04 | 0x05, pallet, call], mut payload) = extrinsic.split_first_chunk::<3>()? else {
+		return None;
+	};
+	if pallet != pallet_index || call != call_index {
+		return None;
+	}
+	Vec::<u8>::decode(&mut payload).ok()
+}
+
+/// The interval at which the block indexer checks for newly finalized blocks.
+const INDEXER_POLL_INTERVAL: Duration = Duration::from_secs(6);
+
+/// The interval at which the transaction pool of the node is polled for new transactions.
+const PENDING_TRANSACTIONS_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
 impl From<ClientError> for ErrorObjectOwned {
 	fn from(err: ClientError) -> Self {
 		match err {
@@ -161,8 +202,11 @@ pub struct Client {
 	receipt_provider: ReceiptProvider,
 	block_provider: SubxtBlockInfoProvider,
 	fee_history_provider: FeeHistoryProvider,
+	subscription_provider: SubscriptionProvider,
+	filter_provider: FilterProvider,
 	chain_id: u64,
 	max_block_weight: Weight,
+	balance_params: proof::BalanceParams,
 }
 
 /// Fetch the chain ID from the substrate chain.
@@ -171,6 +215,18 @@ async fn chain_id(api: &OnlineClient<SrcChainConfig>) -> Result<u64, ClientError
 	api.constants().at(&query).map_err(|err| err.into())
 }
 
+/// Fetch the constants needed to derive EVM balances from the substrate chain.
+async fn balance_params(
+	api: &OnlineClient<SrcChainConfig>,
+) -> Result<proof::BalanceParams, ClientError> {
+	let native_to_eth_ratio =
+		api.constants().at(&subxt_client::constants().revive().native_to_eth_ratio())?;
+	let existential_deposit = api
+		.constants()
+		.at(&subxt_client::constants().balances().existential_deposit())?;
+	Ok(proof::BalanceParams { native_to_eth_ratio, existential_deposit })
+}
+
 /// Fetch the max block weight from the substrate chain.
 async fn max_block_weight(api: &OnlineClient<SrcChainConfig>) -> Result<Weight, ClientError> {
 	let query = subxt_client::constants().system().block_weights();
@@ -218,8 +274,9 @@ impl Client {
 		block_provider: SubxtBlockInfoProvider,
 		receipt_provider: ReceiptProvider,
 	) -> Result<Self, ClientError> {
-		let (chain_id, max_block_weight) =
-			tokio::try_join!(chain_id(&api), max_block_weight(&api))?;
+		let (chain_id, max_block_weight, balance_params) =
+			tokio::try_join!(chain_id(&api), max_block_weight(&api), balance_params(&api))?;
+		let subscription_provider = SubscriptionProvider::default();
 
 		Ok(Self {
 			api,
@@ -228,8 +285,11 @@ impl Client {
 			receipt_provider,
 			block_provider,
 			fee_history_provider: FeeHistoryProvider::default(),
+			filter_provider: FilterProvider::new(subscription_provider.clone()),
+			subscription_provider,
 			chain_id,
 			max_block_weight,
+			balance_params,
 		})
 	}
 
@@ -330,6 +390,9 @@ impl Client {
 
 			let evm_block =
 				self.evm_block_from_receipts(&block, &receipts, signed_txs, false).await;
+			if matches!(subscription_type, SubscriptionType::BestBlocks) {
+				self.update_best_chain(&block, &evm_block, &receipts).await?;
+			}
 			self.block_provider.update_latest(block, subscription_type).await;
 
 			self.fee_history_provider.update_fee_history(&evm_block, &receipts).await;
@@ -338,6 +401,49 @@ impl Client {
 		.await
 	}
 
+	/// Notify the subscribers of a new best block.
+	///
+	/// If the new best block is not a child of the previous best block, the blocks of the new
+	/// branch that were not seen yet are fetched, so that the retracted and enacted logs can be
+	/// notified.
+	async fn update_best_chain(
+		&self,
+		block: &SubstrateBlock,
+		evm_block: &Block,
+		receipts: &[ReceiptInfo],
+	) -> Result<(), ClientError> {
+		let logs = |receipts: &[ReceiptInfo]| {
+			receipts.iter().flat_map(|receipt| receipt.logs.clone()).collect::<Vec<_>>()
+		};
+
+		let mut enacted = vec![(evm_block.clone(), logs(receipts))];
+		let mut parent_hash = block.header().parent_hash;
+		let mut number = block.number().saturating_sub(1);
+
+		// Walk back the new branch until we find a block of the current best chain.
+		while enacted.len() < MAX_REORG_DEPTH {
+			match self.subscription_provider.best_block_hash(number).await {
+				Some(hash) if hash != parent_hash => {},
+				_ => break,
+			}
+
+			let parent =
+				self.block_by_hash(&parent_hash).await?.ok_or(ClientError::BlockNotFound)?;
+			let (signed_txs, receipts): (Vec<_>, Vec<_>) =
+				self.receipt_provider.insert_block_receipts(&parent).await?.into_iter().unzip();
+			let evm_block =
+				self.evm_block_from_receipts(&parent, &receipts, signed_txs, false).await;
+
+			enacted.push((evm_block, logs(&receipts)));
+			parent_hash = parent.header().parent_hash;
+			number = number.saturating_sub(1);
+		}
+
+		enacted.reverse();
+		self.subscription_provider.update_best_chain(enacted).await;
+		Ok(())
+	}
+
 	/// Cache old blocks up to the given block number.
 	pub async fn subscribe_and_cache_blocks(
 		&self,
@@ -356,6 +462,68 @@ impl Client {
 		Ok(())
 	}
 
+	/// Index the finalized blocks from `start_block`, and keep indexing newly finalized blocks.
+	///
+	/// The progress is stored in the database, so that indexing resumes where it stopped after a
+	/// restart. When `retention` is set, only the receipts and logs of the last `retention`
+	/// finalized blocks are kept.
+	///
+	/// Errors are logged, and indexing is retried from the stored progress.
+	pub async fn index_finalized_blocks(
+		&self,
+		start_block: SubstrateBlockNumber,
+		retention: Option<SubstrateBlockNumber>,
+	) {
+		loop {
+			if let Err(err) = self.try_index_finalized_blocks(start_block, retention).await {
+				log::error!(target: LOG_TARGET, "Block indexer failed: {err:?}, retrying");
+				tokio::time::sleep(INDEXER_POLL_INTERVAL).await;
+			}
+		}
+	}
+
+	/// Index the finalized blocks, see [`Self::index_finalized_blocks`].
+	///
+	/// Only returns on error.
+	async fn try_index_finalized_blocks(
+		&self,
+		start_block: SubstrateBlockNumber,
+		retention: Option<SubstrateBlockNumber>,
+	) -> Result<(), ClientError> {
+		let mut next_block = self
+			.receipt_provider
+			.indexer_next_block(start_block)
+			.await?
+			.unwrap_or(start_block);
+		log::info!(target: LOG_TARGET, "🗄️ Indexing finalized blocks from #{next_block}");
+
+		loop {
+			let finalized = self.latest_finalized_block().await.number();
+			if let Some(retention) = retention {
+				let oldest_block = finalized.saturating_sub(retention);
+				next_block = next_block.max(oldest_block);
+				self.receipt_provider.prune_before(oldest_block).await?;
+			}
+
+			while next_block <= finalized {
+				let block = self
+					.block_provider
+					.block_by_number(next_block)
+					.await?
+					.ok_or(ClientError::BlockNotFound)?;
+				self.receipt_provider.insert_block_receipts(&block).await?;
+
+				next_block += 1;
+				self.receipt_provider.set_indexer_next_block(start_block, next_block).await?;
+				if next_block % 1000 == 0 {
+					log::info!(target: LOG_TARGET, "🗄️ Indexed blocks up to #{next_block}");
+				}
+			}
+
+			tokio::time::sleep(INDEXER_POLL_INTERVAL).await;
+		}
+	}
+
 	/// Get the block hash for the given block number or tag.
 	pub async fn block_hash_for_tag(
 		&self,
@@ -424,6 +592,96 @@ impl Client {
 		Ok(sync_state)
 	}
 
+	/// Get the proof of the given storage keys in the main trie.
+	async fn read_proof(
+		&self,
+		at: SubstrateBlockHash,
+		keys: Vec<Vec<u8>>,
+	) -> Result<Vec<Bytes>, ClientError> {
+		let client = RpcClient::new(self.rpc_client.clone());
+		let keys = keys.into_iter().map(StorageKey).collect::<Vec<_>>();
+		let proof: ReadProof<SubstrateBlockHash> = client
+			.request("state_getReadProof", subxt::ext::subxt_rpcs::rpc_params![keys, at])
+			.await?;
+		Ok(proof.proof.into_iter().map(|node| Bytes(node.0)).collect())
+	}
+
+	/// Get the proof of the given storage keys in the child trie of a contract.
+	async fn child_read_proof(
+		&self,
+		at: SubstrateBlockHash,
+		trie_id: &[u8],
+		keys: Vec<Vec<u8>>,
+	) -> Result<Vec<Bytes>, ClientError> {
+		let client = RpcClient::new(self.rpc_client.clone());
+		let child_storage_key = ChildInfo::new_default(trie_id).prefixed_storage_key();
+		let keys = keys.into_iter().map(StorageKey).collect::<Vec<_>>();
+		let proof: ReadProof<SubstrateBlockHash> = client
+			.request(
+				"state_getChildReadProof",
+				subxt::ext::subxt_rpcs::rpc_params![child_storage_key, keys, at],
+			)
+			.await?;
+		Ok(proof.proof.into_iter().map(|node| Bytes(node.0)).collect())
+	}
+
+	/// Get the account proof of the given address, and the storage proofs of the given keys.
+	///
+	/// The proofs are verified against the state root of the block before they are returned, see
+	/// [`crate::proof`] for the format of the proofs.
+	pub async fn get_proof(
+		&self,
+		address: H160,
+		storage_keys: Vec<U256>,
+		block: BlockNumberOrTagOrHash,
+	) -> Result<AccountProof, ClientError> {
+		let hash = self.block_hash_for_tag(block).await?;
+		let block = self.block_by_hash(&hash).await?.ok_or(ClientError::BlockNotFound)?;
+		let state_root = block.header().state_root;
+
+		// The account id and the trie id are needed to know the remaining keys of the proof.
+		let mapping_proof = self
+			.read_proof(
+				hash,
+				vec![proof::original_account_key(&address), proof::contract_info_key(&address)],
+			)
+			.await?;
+		let (account_id, contract_info) =
+			proof::read_account_mapping(state_root, &address, &mapping_proof)?;
+		let trie_id = contract_info.map(|(trie_id, _)| trie_id);
+
+		let account_proof = self
+			.read_proof(hash, proof::account_keys(&address, &account_id, trie_id.as_deref()))
+			.await?;
+		let account =
+			proof::verify_account(state_root, &address, &self.balance_params, &account_proof)?;
+
+		let storage_proof = futures::future::try_join_all(storage_keys.into_iter().map(|key| {
+			let trie_id = account.trie_id.as_deref();
+			async move {
+				let Some(trie_id) = trie_id else {
+					return Ok(StorageProof { key, value: U256::zero(), proof: vec![] });
+				};
+
+				let slot_key = proof::storage_slot_key(&key).to_vec();
+				let proof = self.child_read_proof(hash, trie_id, vec![slot_key]).await?;
+				let value = proof::verify_storage(state_root, trie_id, &key, &proof)?;
+				Ok::<_, ClientError>(StorageProof { key, value, proof })
+			}
+		}))
+		.await?;
+
+		Ok(AccountProof {
+			address,
+			account_proof,
+			balance: account.balance,
+			code_hash: account.code_hash,
+			nonce: account.nonce,
+			storage_hash: account.storage_hash,
+			storage_proof,
+		})
+	}
+
 	/// Get the syncing status of the chain.
 	pub async fn syncing(&self) -> Result<SyncingStatus, ClientError> {
 		let health = self.rpc.system_health().await?;
@@ -591,6 +849,45 @@ impl Client {
 		runtime_api.trace_tx(block, transaction_index.as_u32(), config.clone()).await
 	}
 
+	/// Create an access list for the given transaction.
+	///
+	/// The accessed addresses and storage slots are collected by tracing the transaction, which is
+	/// then dry-run with the access list to compute the gas it uses.
+	pub async fn create_access_list(
+		&self,
+		mut transaction: GenericTransaction,
+		block: BlockNumberOrTagOrHash,
+	) -> Result<AccessListResult, ClientError> {
+		let block_hash = self.block_hash_for_tag(block).await?;
+		let runtime_api = self.runtime_api(block_hash);
+
+		let trace = runtime_api
+			.trace_call(transaction.clone(), TracerType::AccessListTracer)
+			.await?;
+		let Trace::AccessList(access_list) = trace else {
+			return Err(ClientError::TransactError(EthTransactError::Message(
+				"Unexpected trace type".into(),
+			)));
+		};
+
+		transaction.access_list = Some(access_list.clone());
+		match runtime_api.dry_run(transaction).await {
+			Ok(dry_run) =>
+				Ok(AccessListResult { access_list, gas_used: dry_run.eth_gas, error: None }),
+			Err(ClientError::TransactError(err)) => {
+				let error = match err {
+					EthTransactError::Data(data) => match decode_revert_reason(&data) {
+						Some(reason) => format!("execution reverted: {reason}"),
+						None => "execution reverted".to_string(),
+					},
+					EthTransactError::Message(msg) => msg,
+				};
+				Ok(AccessListResult { access_list, gas_used: U256::zero(), error: Some(error) })
+			},
+			Err(err) => Err(err),
+		}
+	}
+
 	/// Get the transaction traces for the given block.
 	pub async fn trace_call(
 		&self,
@@ -692,6 +989,84 @@ impl Client {
 		Ok(logs)
 	}
 
+	/// Subscribe to the best chain and pending transactions events.
+	pub fn subscribe_chain_events(&self) -> broadcast::Receiver<ChainEvent> {
+		self.subscription_provider.subscribe()
+	}
+
+	/// Notify the subscribers of a transaction submitted to the pool.
+	pub fn notify_pending_transaction(&self, hash: H256) {
+		self.subscription_provider.notify_pending_transaction(hash);
+	}
+
+	/// Install a new filter and return its id.
+	pub async fn new_filter(&self, kind: FilterKind) -> Result<U256, ClientError> {
+		self.filter_provider.install(kind).await.ok_or(ClientError::TooManyFilters)
+	}
+
+	/// Periodically uninstall the filters that are no longer polled.
+	pub async fn purge_expired_filters(&self) {
+		let mut interval = tokio::time::interval(crate::FILTER_PURGE_INTERVAL);
+		loop {
+			interval.tick().await;
+			self.filter_provider.purge_expired().await;
+		}
+	}
+
+	/// Get the hashes of the Ethereum transactions in the transaction pool of the node.
+	async fn pending_transaction_hashes(&self) -> Result<Vec<H256>, ClientError> {
+		let metadata = self.api.metadata();
+		let Some((pallet_index, call_index)) =
+			metadata.pallet_by_name("Revive").and_then(|pallet| {
+				Some((pallet.index(), pallet.call_variant_by_name("eth_transact")?.index))
+			})
+		else {
+			return Ok(Vec::new());
+		};
+
+		let client = RpcClient::new(self.rpc_client.clone());
+		let extrinsics: Vec<sp_core::Bytes> =
+			client.request("author_pendingExtrinsics", rpc_params![]).await?;
+
+		Ok(extrinsics
+			.iter()
+			.filter_map(|extrinsic| eth_transact_payload(extrinsic, pallet_index, call_index))
+			.map(|payload| H256(keccak_256(&payload)))
+			.collect())
+	}
+
+	/// Poll the transaction pool of the node, and notify the subscribers of the Ethereum
+	/// transactions added to it, including the ones received from other nodes.
+	pub async fn subscribe_pending_transactions(&self) {
+		let mut interval = tokio::time::interval(PENDING_TRANSACTIONS_POLL_INTERVAL);
+		loop {
+			interval.tick().await;
+			match self.pending_transaction_hashes().await {
+				Ok(hashes) => self.subscription_provider.update_pending_transactions(hashes),
+				Err(err) => {
+					log::debug!(target: LOG_TARGET, "Failed to fetch pending transactions: {err:?}")
+				},
+			}
+		}
+	}
+
+	/// Uninstall the filter with the given id, returns `true` if the filter was installed.
+	pub async fn uninstall_filter(&self, id: &U256) -> bool {
+		self.filter_provider.uninstall(id).await
+	}
+
+	/// Get the changes of the filter with the given id, since it was last polled.
+	pub async fn filter_changes(&self, id: &U256) -> Result<FilterResults, ClientError> {
+		self.filter_provider.changes(id).await.ok_or(ClientError::FilterNotFound)
+	}
+
+	/// Get all the logs matching the log filter with the given id.
+	pub async fn filter_logs(&self, id: &U256) -> Result<Vec<Log>, ClientError> {
+		let filter =
+			self.filter_provider.log_filter(id).await.ok_or(ClientError::FilterNotFound)?;
+		self.logs(Some(filter)).await
+	}
+
 	pub async fn fee_history(
 		&self,
 		block_count: u32,
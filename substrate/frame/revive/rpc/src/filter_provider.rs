@@ -0,0 +1,353 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use crate::{ChainEvent, SubscriptionProvider, LOG_TARGET};
+use pallet_revive::evm::{BlockNumberOrTag, Filter, FilterResults, Log};
+use sp_core::{H256, U256};
+use std::{
+	collections::HashMap,
+	sync::Arc,
+	time::{Duration, Instant},
+};
+use tokio::sync::{broadcast, broadcast::error::TryRecvError, Mutex};
+
+/// Filters that are not polled within this duration are uninstalled.
+const FILTER_TIMEOUT: Duration = Duration::from_secs(5 * 60);
+
+/// The interval at which expired filters are uninstalled.
+pub const FILTER_PURGE_INTERVAL: Duration = Duration::from_secs(30);
+
+/// The maximum number of filters installed at the same time.
+///
+/// Every filter buffers the chain events until it is polled, so the number of filters is capped
+/// to bound the memory used by the server.
+pub const MAX_INSTALLED_FILTERS: usize = 4096;
+
+/// The kind of filter installed with `eth_newFilter`, `eth_newBlockFilter` or
+/// `eth_newPendingTransactionFilter`.
+#[derive(Debug, Clone)]
+pub enum FilterKind {
+	/// Collect the logs matching the filter.
+	Logs(Filter),
+	/// Collect the hashes of new best blocks.
+	Blocks,
+	/// Collect the hashes of submitted transactions.
+	PendingTransactions,
+}
+
+/// A filter installed on the server, along with the events received since it was last polled.
+struct InstalledFilter {
+	kind: FilterKind,
+	receiver: broadcast::Receiver<ChainEvent>,
+	last_poll: Instant,
+}
+
+impl InstalledFilter {
+	/// Drain the events received since the last poll, and return the matching results.
+	fn changes(&mut self) -> FilterResults {
+		self.last_poll = Instant::now();
+
+		let mut hashes = Vec::new();
+		let mut logs = Vec::new();
+		loop {
+			let event = match self.receiver.try_recv() {
+				Ok(event) => event,
+				Err(TryRecvError::Lagged(skipped)) => {
+					log::warn!(target: LOG_TARGET, "Filter lagged behind, {skipped} events skipped");
+					continue;
+				},
+				Err(TryRecvError::Empty | TryRecvError::Closed) => break,
+			};
+
+			match (&self.kind, event) {
+				(FilterKind::Logs(filter), ChainEvent::Enacted { logs: new_logs, .. }) |
+				(FilterKind::Logs(filter), ChainEvent::Retracted { logs: new_logs, .. }) =>
+					logs.extend(new_logs.iter().filter(|log| log_matches(filter, log)).cloned()),
+				(FilterKind::Blocks, ChainEvent::Enacted { block, .. }) => hashes.push(block.hash),
+				(FilterKind::PendingTransactions, ChainEvent::PendingTransaction(hash)) =>
+					hashes.push(hash),
+				_ => {},
+			}
+		}
+
+		match self.kind {
+			FilterKind::Logs(_) => FilterResults::Logs(logs),
+			FilterKind::Blocks | FilterKind::PendingTransactions => FilterResults::Hashes(hashes),
+		}
+	}
+}
+
+/// Returns `true` if the log matches all the criteria of the filter, including the block range.
+///
+/// Block tags in the range are ignored, since they always resolve to the latest block for new
+/// logs.
+pub fn log_matches(filter: &Filter, log: &Log) -> bool {
+	if filter.block_hash.is_some_and(|hash| hash != log.block_hash) {
+		return false;
+	}
+
+	if let Some(BlockNumberOrTag::U256(from_block)) = filter.from_block {
+		if log.block_number < from_block {
+			return false;
+		}
+	}
+
+	if let Some(BlockNumberOrTag::U256(to_block)) = filter.to_block {
+		if log.block_number > to_block {
+			return false;
+		}
+	}
+
+	filter.matches_log(log)
+}
+
+/// FilterProvider keeps track of the filters installed with the `eth_new*Filter` methods, and
+/// collects their changes between two calls to `eth_getFilterChanges`.
+#[derive(Clone)]
+pub struct FilterProvider {
+	/// The provider used to receive chain events.
+	subscription_provider: SubscriptionProvider,
+	/// The installed filters, indexed by id.
+	filters: Arc<Mutex<HashMap<U256, InstalledFilter>>>,
+	/// The id of the last installed filter.
+	last_id: Arc<Mutex<U256>>,
+}
+
+impl FilterProvider {
+	/// Create a new `FilterProvider` receiving events from the given subscription provider.
+	pub fn new(subscription_provider: SubscriptionProvider) -> Self {
+		Self { subscription_provider, filters: Default::default(), last_id: Default::default() }
+	}
+
+	/// Install a new filter and return its id.
+	///
+	/// Returns `None` if [`MAX_INSTALLED_FILTERS`] filters are already installed.
+	pub async fn install(&self, kind: FilterKind) -> Option<U256> {
+		let mut filters = self.filters.lock().await;
+		if filters.len() >= MAX_INSTALLED_FILTERS {
+			Self::purge(&mut filters);
+			if filters.len() >= MAX_INSTALLED_FILTERS {
+				log::debug!(target: LOG_TARGET, "Too many filters installed");
+				return None;
+			}
+		}
+
+		let id = {
+			let mut last_id = self.last_id.lock().await;
+			*last_id = last_id.saturating_add(U256::one());
+			*last_id
+		};
+
+		let receiver = self.subscription_provider.subscribe();
+		filters.insert(id, InstalledFilter { kind, receiver, last_poll: Instant::now() });
+		Some(id)
+	}
+
+	/// Uninstall the filters that have not been polled within [`FILTER_TIMEOUT`].
+	pub async fn purge_expired(&self) {
+		Self::purge(&mut *self.filters.lock().await);
+	}
+
+	fn purge(filters: &mut HashMap<U256, InstalledFilter>) {
+		filters.retain(|id, filter| {
+			let expired = filter.last_poll.elapsed() > FILTER_TIMEOUT;
+			if expired {
+				log::debug!(target: LOG_TARGET, "Uninstalling expired filter {id:?}");
+			}
+			!expired
+		});
+	}
+
+	/// Uninstall the filter with the given id.
+	///
+	/// Returns `true` if the filter was installed.
+	pub async fn uninstall(&self, id: &U256) -> bool {
+		self.filters.lock().await.remove(id).is_some()
+	}
+
+	/// Return the log filter installed with the given id.
+	pub async fn log_filter(&self, id: &U256) -> Option<Filter> {
+		match &self.filters.lock().await.get(id)?.kind {
+			FilterKind::Logs(filter) => Some(filter.clone()),
+			_ => None,
+		}
+	}
+
+	/// Return the changes of the filter since it was last polled.
+	pub async fn changes(&self, id: &U256) -> Option<FilterResults> {
+		let mut filters = self.filters.lock().await;
+		let filter = filters.get_mut(id)?;
+		Some(filter.changes())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use pallet_revive::evm::{Block, FilterTopic};
+	use pretty_assertions::assert_eq;
+	use sp_core::H160;
+
+	fn hashes(results: FilterResults) -> Vec<H256> {
+		match results {
+			FilterResults::Hashes(hashes) => hashes,
+			FilterResults::Logs(logs) => logs.into_iter().map(|log| log.block_hash).collect(),
+		}
+	}
+
+	fn block(number: u32, seed: u8, address: H160) -> (Block, Vec<Log>) {
+		let hash = H256::from([seed; 32]);
+		let log = Log {
+			address,
+			block_hash: hash,
+			block_number: number.into(),
+			topics: vec![H256::from([seed; 32])],
+			..Default::default()
+		};
+		(Block { hash, number: number.into(), ..Default::default() }, vec![log])
+	}
+
+	#[tokio::test]
+	async fn block_filter_works() {
+		let subscription_provider = SubscriptionProvider::default();
+		let provider = FilterProvider::new(subscription_provider.clone());
+		let id = provider.install(FilterKind::Blocks).await.unwrap();
+
+		subscription_provider.update_best_chain(vec![block(1, 1, H160::zero())]).await;
+		subscription_provider.update_best_chain(vec![block(2, 2, H160::zero())]).await;
+		subscription_provider.notify_pending_transaction(H256::from([3u8; 32]));
+
+		let changes = provider.changes(&id).await.map(hashes);
+		assert_eq!(changes, Some(vec![H256::from([1u8; 32]), H256::from([2u8; 32])]));
+
+		// Changes are only returned once.
+		let changes = provider.changes(&id).await.map(hashes);
+		assert_eq!(changes, Some(vec![]));
+	}
+
+	#[tokio::test]
+	async fn pending_transaction_filter_works() {
+		let subscription_provider = SubscriptionProvider::default();
+		let provider = FilterProvider::new(subscription_provider.clone());
+		let id = provider.install(FilterKind::PendingTransactions).await.unwrap();
+
+		subscription_provider.update_best_chain(vec![block(1, 1, H160::zero())]).await;
+		subscription_provider.notify_pending_transaction(H256::from([3u8; 32]));
+
+		let changes = provider.changes(&id).await.map(hashes);
+		assert_eq!(changes, Some(vec![H256::from([3u8; 32])]));
+	}
+
+	#[tokio::test]
+	async fn log_filter_works() {
+		let subscription_provider = SubscriptionProvider::default();
+		let provider = FilterProvider::new(subscription_provider.clone());
+		let address = H160::from([1u8; 20]);
+		let filter = Filter {
+			address: Some(address.into()),
+			topics: Some(vec![FilterTopic::Multiple(vec![
+				H256::from([1u8; 32]),
+				H256::from([3u8; 32]),
+				H256::from([13u8; 32]),
+			])]),
+			..Default::default()
+		};
+		let id = provider.install(FilterKind::Logs(filter.clone())).await.unwrap();
+		assert_eq!(provider.log_filter(&id).await, Some(filter));
+
+		subscription_provider
+			.update_best_chain(vec![
+				block(1, 1, address),
+				block(2, 2, address),
+				block(3, 3, address),
+				block(4, 4, H160::zero()),
+			])
+			.await;
+
+		let Some(FilterResults::Logs(logs)) = provider.changes(&id).await else {
+			panic!("expected logs")
+		};
+		let logs = logs.into_iter().map(|log| (log.block_hash, log.removed)).collect::<Vec<_>>();
+		assert_eq!(
+			logs,
+			vec![(H256::from([1u8; 32]), Some(false)), (H256::from([3u8; 32]), Some(false))]
+		);
+
+		// Re-org block #3, the removed log is reported.
+		subscription_provider.update_best_chain(vec![block(3, 13, address)]).await;
+		let Some(FilterResults::Logs(logs)) = provider.changes(&id).await else {
+			panic!("expected logs")
+		};
+		let logs = logs.into_iter().map(|log| (log.block_hash, log.removed)).collect::<Vec<_>>();
+		assert_eq!(
+			logs,
+			vec![(H256::from([3u8; 32]), Some(true)), (H256::from([13u8; 32]), Some(false))]
+		);
+	}
+
+	#[tokio::test]
+	async fn log_filter_respects_block_range() {
+		let filter = Filter {
+			from_block: Some(U256::from(2).into()),
+			to_block: Some(U256::from(3).into()),
+			..Default::default()
+		};
+
+		let matches = (1..5)
+			.map(|n| block(n, n as u8, H160::zero()).1.remove(0))
+			.map(|log| log_matches(&filter, &log))
+			.collect::<Vec<_>>();
+		assert_eq!(matches, vec![false, true, true, false]);
+	}
+
+	#[tokio::test]
+	async fn uninstall_works() {
+		let provider = FilterProvider::new(SubscriptionProvider::default());
+		let id = provider.install(FilterKind::Blocks).await.unwrap();
+		assert_ne!(provider.install(FilterKind::Blocks).await, Some(id));
+
+		assert!(provider.uninstall(&id).await);
+		assert!(!provider.uninstall(&id).await);
+		assert_eq!(provider.changes(&id).await, None);
+	}
+
+	#[tokio::test]
+	async fn installed_filters_are_capped() {
+		let provider = FilterProvider::new(SubscriptionProvider::default());
+		for _ in 0..MAX_INSTALLED_FILTERS {
+			assert!(provider.install(FilterKind::Blocks).await.is_some());
+		}
+		assert_eq!(provider.install(FilterKind::Blocks).await, None);
+
+		// Expired filters make room for new ones.
+		let expired = Instant::now() - FILTER_TIMEOUT - Duration::from_secs(1);
+		provider.filters.lock().await.values_mut().next().unwrap().last_poll = expired;
+		assert!(provider.install(FilterKind::Blocks).await.is_some());
+	}
+
+	#[tokio::test]
+	async fn purge_expired_works() {
+		let provider = FilterProvider::new(SubscriptionProvider::default());
+		let expired = provider.install(FilterKind::Blocks).await.unwrap();
+		let active = provider.install(FilterKind::Blocks).await.unwrap();
+		provider.filters.lock().await.get_mut(&expired).unwrap().last_poll =
+			Instant::now() - FILTER_TIMEOUT - Duration::from_secs(1);
+
+		provider.purge_expired().await;
+		assert_eq!(provider.changes(&expired).await, None);
+		assert!(provider.changes(&active).await.is_some());
+	}
+}
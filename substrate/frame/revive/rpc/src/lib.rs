@@ -30,6 +30,7 @@ use thiserror::Error;
 pub mod cli;
 pub mod client;
 pub mod example;
+pub mod proof;
 pub mod subxt_client;
 
 #[cfg(test)]
@@ -47,6 +48,12 @@ pub use fee_history_provider::*;
 mod receipt_extractor;
 pub use receipt_extractor::*;
 
+mod subscription_provider;
+pub use subscription_provider::*;
+
+mod filter_provider;
+pub use filter_provider::*;
+
 mod apis;
 pub use apis::*;
 
@@ -98,6 +105,9 @@ pub enum EthRpcError {
 	/// Received an invalid transaction
 	#[error("Invalid transaction {0:?}")]
 	TransactionTypeNotSupported(Byte),
+	/// A filter was provided for a subscription that is not a logs subscription.
+	#[error("Invalid subscription params")]
+	InvalidSubscriptionParams,
 }
 
 // TODO use https://eips.ethereum.org/EIPS/eip-1474#error-codes
@@ -161,6 +171,15 @@ impl EthRpcServer for EthRpcServerImpl {
 		Ok(dry_run.data.into())
 	}
 
+	async fn create_access_list(
+		&self,
+		transaction: GenericTransaction,
+		block: Option<BlockNumberOrTagOrHash>,
+	) -> RpcResult<AccessListResult> {
+		let result = self.client.create_access_list(transaction, block.unwrap_or_default()).await?;
+		Ok(result)
+	}
+
 	async fn send_raw_transaction(&self, transaction: Bytes) -> RpcResult<H256> {
 		let hash = H256(keccak_256(&transaction.0));
 		let call = subxt_client::tx().revive().eth_transact(transaction.0);
@@ -170,6 +189,7 @@ impl EthRpcServer for EthRpcServerImpl {
 		})?;
 
 		log::debug!(target: LOG_TARGET, "send_raw_transaction hash: {hash:?}");
+		self.client.notify_pending_transaction(hash);
 		Ok(hash)
 	}
 
@@ -297,6 +317,40 @@ impl EthRpcServer for EthRpcServerImpl {
 		Ok(FilterResults::Logs(logs))
 	}
 
+	async fn new_filter(&self, filter: Filter) -> RpcResult<U256> {
+		Ok(self.client.new_filter(FilterKind::Logs(filter)).await?)
+	}
+
+	async fn new_block_filter(&self) -> RpcResult<U256> {
+		Ok(self.client.new_filter(FilterKind::Blocks).await?)
+	}
+
+	async fn new_pending_transaction_filter(&self) -> RpcResult<U256> {
+		Ok(self.client.new_filter(FilterKind::PendingTransactions).await?)
+	}
+
+	async fn get_filter_changes(&self, filter_id: U256) -> RpcResult<FilterResults> {
+		Ok(self.client.filter_changes(&filter_id).await?)
+	}
+
+	async fn get_filter_logs(&self, filter_id: U256) -> RpcResult<FilterResults> {
+		let logs = self.client.filter_logs(&filter_id).await?;
+		Ok(FilterResults::Logs(logs))
+	}
+
+	async fn uninstall_filter(&self, filter_id: U256) -> RpcResult<bool> {
+		Ok(self.client.uninstall_filter(&filter_id).await)
+	}
+
+	async fn get_proof(
+		&self,
+		address: H160,
+		storage_keys: Vec<U256>,
+		block: BlockNumberOrTagOrHash,
+	) -> RpcResult<AccountProof> {
+		Ok(self.client.get_proof(address, storage_keys, block).await?)
+	}
+
 	async fn get_storage_at(
 		&self,
 		address: H160,
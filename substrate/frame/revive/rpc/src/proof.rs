@@ -0,0 +1,523 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! Account and storage proofs returned by `eth_getProof`.
+//!
+//! The [`AccountProof`] returned by `eth_getProof` has the shape defined by
+//! [EIP-1186](https://eips.ethereum.org/EIPS/eip-1186), but its proofs are Merkle proofs over the
+//! Substrate state trie (hashed with `BlakeTwo256`) instead of Ethereum Merkle-Patricia proofs.
+//! Every proof is a list of trie nodes, as returned by `state_getReadProof`, that is checked
+//! against the `stateRoot` of the block header.
+//!
+//! - `accountProof` proves the following entries of the main state trie:
+//!   - `Revive::OriginalAccount(address)`: the `AccountId32` the address is mapped to. If the entry
+//!     is absent, the account id is the address followed by twelve `0xEE` bytes.
+//!   - `System::Account(account_id)`: the `nonce` and the balances of the account.
+//!   - `Revive::ContractInfoOf(address)`: the `trie_id` and the `code_hash` of a contract.
+//!   - `:child_storage:default:` ++ `trie_id`: the root of the contract storage child trie.
+//! - `balance` is the balance returned by `Pallet::evm_balance`: the part of the `free` balance
+//!   that is neither frozen nor needed to keep the account alive, multiplied by
+//!   `Revive::NativeToEthRatio`. See [`BalanceParams`].
+//! - `codeHash` is the `keccak256` hash of the contract code, or the hash of the empty code if the
+//!   account is not a contract.
+//! - `storageHash` is the root of the contract storage child trie, or zero if the account is not a
+//!   contract.
+//! - `storageProof[].proof` proves the entry `blake2_256(key)` of the contract child trie. The
+//!   nodes include the path from the state root to the child trie root, so each storage proof can
+//!   be checked on its own against the `stateRoot`.
+//!
+//! [`verify_proof`] can be used by off-chain verifiers to check a proof.
+
+use codec::Decode;
+use pallet_revive::evm::{AccountProof, Bytes, StorageProof, H160, H256, U256};
+use sp_core::storage::ChildInfo;
+use sp_crypto_hashing::{blake2_128, blake2_256, keccak_256, twox_128};
+use sp_runtime::traits::BlakeTwo256;
+use sp_state_machine::{read_child_proof_check, read_proof_check};
+use thiserror::Error;
+
+/// Returns the `keccak256` hash of the empty code, used as the code hash of accounts without code.
+pub fn empty_code_hash() -> H256 {
+	H256(keccak_256(&[]))
+}
+
+/// The name of the system pallet in the runtime.
+const SYSTEM_PALLET: &[u8] = b"System";
+
+/// The name of the revive pallet in the runtime.
+const REVIVE_PALLET: &[u8] = b"Revive";
+
+/// The error type for proof verification.
+#[derive(Error, Debug)]
+pub enum ProofError {
+	/// The trie nodes do not prove the requested entries against the state root.
+	#[error("Invalid proof: {0}")]
+	InvalidProof(String),
+	/// A proven value could not be decoded.
+	#[error("Failed to decode {0}")]
+	DecodingFailed(&'static str),
+	/// A storage value is larger than 32 bytes.
+	#[error("Storage value exceeds 32 bytes")]
+	ValueTooLarge,
+	/// A field of the account proof does not match the proven value.
+	#[error("Proof field {0} does not match the proven value")]
+	Mismatch(&'static str),
+}
+
+/// The runtime constants needed to derive the EVM balance of an account from its `AccountData`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BalanceParams {
+	/// The `Revive::NativeToEthRatio` constant.
+	pub native_to_eth_ratio: u32,
+	/// The `Balances::ExistentialDeposit` constant.
+	pub existential_deposit: u128,
+}
+
+impl BalanceParams {
+	/// Returns the EVM balance of an account with the given `free`, `reserved` and `frozen`
+	/// balances.
+	///
+	/// This mirrors `Pallet::evm_balance`, which converts the `pallet_balances` reducible balance
+	/// with `Preserve` and `Polite`: frozen funds not covered by reserves and the existential
+	/// deposit are not spendable.
+	pub fn evm_balance(&self, free: u128, reserved: u128, frozen: u128) -> U256 {
+		let untouchable = frozen.saturating_sub(reserved).max(self.existential_deposit);
+		U256::from(free.saturating_sub(untouchable)).saturating_mul(self.native_to_eth_ratio.into())
+	}
+}
+
+/// The account state proven by an account proof.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VerifiedAccount {
+	/// The account id the address is mapped to.
+	pub account_id: [u8; 32],
+	/// The nonce of the account.
+	pub nonce: U256,
+	/// The spendable balance of the account, in EVM decimals.
+	pub balance: U256,
+	/// The code hash of the account.
+	pub code_hash: H256,
+	/// The storage trie id, if the account is a contract.
+	pub trie_id: Option<Vec<u8>>,
+	/// The root of the storage child trie, or zero if the account is not a contract.
+	pub storage_hash: H256,
+}
+
+/// Returns the prefix of the given storage item.
+fn storage_prefix(pallet: &[u8], storage: &[u8]) -> Vec<u8> {
+	[twox_128(pallet), twox_128(storage)].concat()
+}
+
+/// Returns the account id used for addresses that are not mapped.
+pub fn fallback_account_id(address: &H160) -> [u8; 32] {
+	let mut account_id = [0xEE; 32];
+	account_id[..20].copy_from_slice(address.as_bytes());
+	account_id
+}
+
+/// Returns the storage key of `Revive::OriginalAccount(address)`.
+pub fn original_account_key(address: &H160) -> Vec<u8> {
+	[storage_prefix(REVIVE_PALLET, b"OriginalAccount"), address.as_bytes().to_vec()].concat()
+}
+
+/// Returns the storage key of `Revive::ContractInfoOf(address)`.
+pub fn contract_info_key(address: &H160) -> Vec<u8> {
+	[storage_prefix(REVIVE_PALLET, b"ContractInfoOf"), address.as_bytes().to_vec()].concat()
+}
+
+/// Returns the storage key of `System::Account(account_id)`.
+pub fn system_account_key(account_id: &[u8; 32]) -> Vec<u8> {
+	[
+		storage_prefix(SYSTEM_PALLET, b"Account"),
+		blake2_128(account_id).to_vec(),
+		account_id.to_vec(),
+	]
+	.concat()
+}
+
+/// Returns the key of the contract storage child trie root in the main trie.
+pub fn child_root_key(trie_id: &[u8]) -> Vec<u8> {
+	ChildInfo::new_default(trie_id).prefixed_storage_key().into_inner()
+}
+
+/// Returns the key of a storage slot in the contract storage child trie.
+pub fn storage_slot_key(key: &U256) -> [u8; 32] {
+	blake2_256(&key.to_big_endian())
+}
+
+/// Returns the main trie keys that must be included in the proof of an account.
+pub fn account_keys(address: &H160, account_id: &[u8; 32], trie_id: Option<&[u8]>) -> Vec<Vec<u8>> {
+	let mut keys = vec![
+		original_account_key(address),
+		contract_info_key(address),
+		system_account_key(account_id),
+	];
+	keys.extend(trie_id.map(child_root_key));
+	keys
+}
+
+/// Check the values of the given keys against the state root.
+fn check_read_proof(
+	state_root: H256,
+	proof: &[Bytes],
+	keys: &[Vec<u8>],
+) -> Result<Vec<Option<Vec<u8>>>, ProofError> {
+	let proof = sp_state_machine::StorageProof::new(proof.iter().map(|node| node.0.clone()));
+	let mut values = read_proof_check::<BlakeTwo256, _>(state_root, proof, keys)
+		.map_err(|err| ProofError::InvalidProof(err.to_string()))?;
+	Ok(keys.iter().map(|key| values.remove(key).flatten()).collect())
+}
+
+/// Read the account id that `address` is mapped to, and the `trie_id` and `code_hash` of the
+/// contract deployed at `address`, if any.
+///
+/// Only the `Revive::OriginalAccount` and `Revive::ContractInfoOf` entries need to be proven, so
+/// this can be used to find the remaining keys of the account proof.
+pub fn read_account_mapping(
+	state_root: H256,
+	address: &H160,
+	proof: &[Bytes],
+) -> Result<([u8; 32], Option<(Vec<u8>, H256)>), ProofError> {
+	let keys = [original_account_key(address), contract_info_key(address)];
+	let mut values = check_read_proof(state_root, proof, &keys)?.into_iter();
+
+	let account_id = match values.next().flatten() {
+		Some(value) => <[u8; 32]>::decode(&mut &value[..])
+			.map_err(|_| ProofError::DecodingFailed("OriginalAccount"))?,
+		None => fallback_account_id(address),
+	};
+
+	// `ContractInfo` starts with the `trie_id` and the `code_hash`.
+	let contract_info = values
+		.next()
+		.flatten()
+		.map(|value| <(Vec<u8>, H256)>::decode(&mut &value[..]))
+		.transpose()
+		.map_err(|_| ProofError::DecodingFailed("ContractInfoOf"))?;
+
+	Ok((account_id, contract_info))
+}
+
+/// Verify the account proof of `address` against the state root, and return the proven account
+/// state.
+pub fn verify_account(
+	state_root: H256,
+	address: &H160,
+	balance_params: &BalanceParams,
+	account_proof: &[Bytes],
+) -> Result<VerifiedAccount, ProofError> {
+	let (account_id, contract_info) = read_account_mapping(state_root, address, account_proof)?;
+
+	let mut keys = vec![system_account_key(&account_id)];
+	if let Some((trie_id, _)) = &contract_info {
+		keys.push(child_root_key(trie_id));
+	}
+	let values = check_read_proof(state_root, account_proof, &keys)?;
+
+	// `AccountInfo` starts with the `nonce` and the reference counters, followed by the `free`,
+	// `reserved` and `frozen` balances of `AccountData`.
+	let (nonce, balance) = match &values[0] {
+		Some(value) => {
+			let (nonce, _consumers, _providers, _sufficients, free, reserved, frozen) =
+				<(u32, u32, u32, u32, u128, u128, u128)>::decode(&mut &value[..])
+					.map_err(|_| ProofError::DecodingFailed("Account"))?;
+			(U256::from(nonce), balance_params.evm_balance(free, reserved, frozen))
+		},
+		None => (U256::zero(), U256::zero()),
+	};
+
+	let storage_hash = match values.get(1).cloned().flatten() {
+		Some(root) if root.len() == 32 => H256::from_slice(&root),
+		Some(_) => return Err(ProofError::DecodingFailed("child trie root")),
+		None => H256::zero(),
+	};
+
+	let (trie_id, code_hash) = match contract_info {
+		Some((trie_id, code_hash)) => (Some(trie_id), code_hash),
+		None => (None, empty_code_hash()),
+	};
+
+	Ok(VerifiedAccount { account_id, nonce, balance, code_hash, trie_id, storage_hash })
+}
+
+/// Verify the proof of a contract storage slot against the state root, and return the proven
+/// value.
+pub fn verify_storage(
+	state_root: H256,
+	trie_id: &[u8],
+	key: &U256,
+	proof: &[Bytes],
+) -> Result<U256, ProofError> {
+	let slot_key = storage_slot_key(key);
+	let proof = sp_state_machine::StorageProof::new(proof.iter().map(|node| node.0.clone()));
+	let mut values = read_child_proof_check::<BlakeTwo256, _>(
+		state_root,
+		proof,
+		&ChildInfo::new_default(trie_id),
+		[&slot_key[..]],
+	)
+	.map_err(|err| ProofError::InvalidProof(err.to_string()))?;
+
+	match values.remove(&slot_key[..]).flatten() {
+		Some(value) if value.len() > 32 => Err(ProofError::ValueTooLarge),
+		Some(value) => Ok(U256::from_big_endian(&value)),
+		None => Ok(U256::zero()),
+	}
+}
+
+/// Verify every field of an [`AccountProof`] against the state root.
+pub fn verify_proof(
+	state_root: H256,
+	balance_params: &BalanceParams,
+	proof: &AccountProof,
+) -> Result<(), ProofError> {
+	let account = verify_account(state_root, &proof.address, balance_params, &proof.account_proof)?;
+
+	if account.nonce != proof.nonce {
+		return Err(ProofError::Mismatch("nonce"));
+	}
+	if account.balance != proof.balance {
+		return Err(ProofError::Mismatch("balance"));
+	}
+	if account.code_hash != proof.code_hash {
+		return Err(ProofError::Mismatch("codeHash"));
+	}
+	if account.storage_hash != proof.storage_hash {
+		return Err(ProofError::Mismatch("storageHash"));
+	}
+
+	for StorageProof { key, value, proof } in &proof.storage_proof {
+		let proven_value = match &account.trie_id {
+			Some(trie_id) => verify_storage(state_root, trie_id, key, proof)?,
+			None => U256::zero(),
+		};
+
+		if &proven_value != value {
+			return Err(ProofError::Mismatch("storageProof"));
+		}
+	}
+
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use codec::Encode;
+	use pretty_assertions::assert_eq;
+	use sp_core::storage::StateVersion;
+	use sp_state_machine::{
+		new_in_mem, prove_child_read_on_trie_backend, prove_read_on_trie_backend, InMemoryBackend,
+	};
+
+	const RATIO: u32 = 1_000_000;
+	const PARAMS: BalanceParams =
+		BalanceParams { native_to_eth_ratio: RATIO, existential_deposit: 1 };
+
+	struct TestState {
+		backend: InMemoryBackend<BlakeTwo256>,
+		contract: H160,
+		eoa: H160,
+		trie_id: Vec<u8>,
+	}
+
+	impl TestState {
+		fn new() -> Self {
+			let contract = H160::from([1u8; 20]);
+			let eoa = H160::from([2u8; 20]);
+			let trie_id = b"trie_id".to_vec();
+
+			let contract_info = (trie_id.clone(), H256::from(keccak_256(b"code")), 0u32).encode();
+			let account_info = |nonce: u32, free: u128, reserved: u128, frozen: u128| {
+				(nonce, 0u32, 1u32, 0u32, free, reserved, frozen, 0u128).encode()
+			};
+
+			let mut backend = new_in_mem::<BlakeTwo256>();
+			backend.insert(
+				vec![
+					(
+						None,
+						vec![
+							(contract_info_key(&contract), Some(contract_info)),
+							(
+								system_account_key(&fallback_account_id(&contract)),
+								Some(account_info(1, 10, 0, 0)),
+							),
+							(
+								system_account_key(&fallback_account_id(&eoa)),
+								Some(account_info(5, 20, 2, 6)),
+							),
+						],
+					),
+					(
+						Some(ChildInfo::new_default(&trie_id)),
+						vec![(
+							storage_slot_key(&U256::from(1)).to_vec(),
+							Some(U256::from(42).to_big_endian().to_vec()),
+						)],
+					),
+				],
+				StateVersion::V1,
+			);
+
+			Self { backend, contract, eoa, trie_id }
+		}
+
+		fn state_root(&self) -> H256 {
+			*self.backend.root()
+		}
+
+		fn account_proof(&self, address: &H160) -> Vec<Bytes> {
+			let trie_id = (address == &self.contract).then_some(&self.trie_id[..]);
+			let keys = account_keys(address, &fallback_account_id(address), trie_id);
+			prove_read_on_trie_backend(&self.backend, keys)
+				.unwrap()
+				.into_iter_nodes()
+				.map(Bytes)
+				.collect()
+		}
+
+		fn storage_proof(&self, key: &U256) -> Vec<Bytes> {
+			prove_child_read_on_trie_backend(
+				&self.backend,
+				&ChildInfo::new_default(&self.trie_id),
+				[storage_slot_key(key)],
+			)
+			.unwrap()
+			.into_iter_nodes()
+			.map(Bytes)
+			.collect()
+		}
+	}
+
+	#[test]
+	fn verify_contract_account_works() {
+		let state = TestState::new();
+		let account = verify_account(
+			state.state_root(),
+			&state.contract,
+			&PARAMS,
+			&state.account_proof(&state.contract),
+		)
+		.unwrap();
+
+		assert_eq!(account.nonce, U256::from(1));
+		// The existential deposit is not spendable.
+		assert_eq!(account.balance, U256::from(9 * RATIO));
+		assert_eq!(account.code_hash, H256::from(keccak_256(b"code")));
+		assert_eq!(account.trie_id, Some(state.trie_id.clone()));
+		assert_ne!(account.storage_hash, H256::zero());
+	}
+
+	#[test]
+	fn verify_eoa_account_works() {
+		let state = TestState::new();
+		let account = verify_account(
+			state.state_root(),
+			&state.eoa,
+			&PARAMS,
+			&state.account_proof(&state.eoa),
+		)
+		.unwrap();
+
+		assert_eq!(account.nonce, U256::from(5));
+		// The frozen balance not covered by the reserved balance is not spendable.
+		assert_eq!(account.balance, U256::from(16 * RATIO));
+		assert_eq!(account.code_hash, empty_code_hash());
+		assert_eq!(account.trie_id, None);
+		assert_eq!(account.storage_hash, H256::zero());
+	}
+
+	#[test]
+	fn verify_storage_works() {
+		let state = TestState::new();
+		let root = state.state_root();
+
+		let value = verify_storage(
+			root,
+			&state.trie_id,
+			&U256::from(1),
+			&state.storage_proof(&U256::from(1)),
+		);
+		assert_eq!(value.unwrap(), U256::from(42));
+
+		let value = verify_storage(
+			root,
+			&state.trie_id,
+			&U256::from(2),
+			&state.storage_proof(&U256::from(2)),
+		);
+		assert_eq!(value.unwrap(), U256::zero());
+	}
+
+	#[test]
+	fn verify_proof_works() {
+		let state = TestState::new();
+		let root = state.state_root();
+		let account =
+			verify_account(root, &state.contract, &PARAMS, &state.account_proof(&state.contract))
+				.unwrap();
+
+		let mut proof = AccountProof {
+			address: state.contract,
+			account_proof: state.account_proof(&state.contract),
+			balance: account.balance,
+			code_hash: account.code_hash,
+			nonce: account.nonce,
+			storage_hash: account.storage_hash,
+			storage_proof: vec![StorageProof {
+				key: U256::from(1),
+				value: U256::from(42),
+				proof: state.storage_proof(&U256::from(1)),
+			}],
+		};
+		assert!(verify_proof(root, &PARAMS, &proof).is_ok());
+
+		proof.storage_proof[0].value = U256::from(43);
+		assert!(matches!(
+			verify_proof(root, &PARAMS, &proof),
+			Err(ProofError::Mismatch("storageProof"))
+		));
+
+		proof.storage_proof[0].value = U256::from(42);
+		proof.balance = U256::from(1);
+		assert!(matches!(
+			verify_proof(root, &PARAMS, &proof),
+			Err(ProofError::Mismatch("balance"))
+		));
+	}
+
+	#[test]
+	fn verify_fails_with_wrong_root() {
+		let state = TestState::new();
+		let result =
+			verify_account(H256::zero(), &state.eoa, &PARAMS, &state.account_proof(&state.eoa));
+		assert!(matches!(result, Err(ProofError::InvalidProof(_))));
+	}
+
+	#[test]
+	fn evm_balance_matches_reducible_balance() {
+		let params = BalanceParams { native_to_eth_ratio: 2, existential_deposit: 10 };
+
+		// Only the existential deposit is kept.
+		assert_eq!(params.evm_balance(100, 0, 0), U256::from(180));
+		// Frozen funds covered by the reserved balance are spendable.
+		assert_eq!(params.evm_balance(100, 50, 40), U256::from(180));
+		assert_eq!(params.evm_balance(100, 20, 50), U256::from(140));
+		// Nothing is spendable below the existential deposit.
+		assert_eq!(params.evm_balance(5, 0, 0), U256::zero());
+	}
+}
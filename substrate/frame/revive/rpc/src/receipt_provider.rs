@@ -19,15 +19,23 @@ use crate::{
 	Address, AddressOrAddresses, BlockInfoProvider, BlockNumberOrTag, BlockTag, Bytes, ClientError,
 	FilterTopic, ReceiptExtractor, SubxtBlockInfoProvider, LOG_TARGET,
 };
-use pallet_revive::evm::{Filter, Log, ReceiptInfo, TransactionSigned};
+use pallet_revive::evm::{Filter, FilterTopics, Log, ReceiptInfo, TransactionSigned};
 use sp_core::{H256, U256};
-use sqlx::{query, QueryBuilder, Row, Sqlite, SqlitePool};
+use sp_crypto_hashing::keccak_256;
+use sqlx::{query, QueryBuilder, Row, Sqlite, SqliteConnection, SqlitePool};
 use std::{
 	collections::{BTreeMap, HashMap},
 	sync::Arc,
 };
 use tokio::sync::Mutex;
 
+/// The maximum number of blocks whose logs bloom is checked before querying the logs of a block
+/// range. Larger ranges are queried directly.
+const MAX_BLOOM_FILTERED_BLOCKS: u64 = 10_000;
+
+/// The size in bytes of a logs bloom.
+const BLOOM_SIZE: usize = 256;
+
 /// ReceiptProvider stores transaction receipts and logs in a SQLite database.
 #[derive(Clone)]
 pub struct ReceiptProvider<B: BlockInfoProvider = SubxtBlockInfoProvider> {
@@ -101,6 +109,17 @@ impl<B: BlockInfoProvider> ReceiptProvider<B> {
 
 	/// Deletes older records from the database.
 	pub async fn remove(&self, block_hashes: &[H256]) -> Result<(), ClientError> {
+		let mut tx = self.pool.begin().await?;
+		Self::remove_with(&mut tx, block_hashes).await?;
+		tx.commit().await?;
+		Ok(())
+	}
+
+	/// Deletes the records of the given blocks using the given connection.
+	async fn remove_with(
+		conn: &mut SqliteConnection,
+		block_hashes: &[H256],
+	) -> Result<(), ClientError> {
 		if block_hashes.is_empty() {
 			return Ok(());
 		}
@@ -113,17 +132,198 @@ impl<B: BlockInfoProvider> ReceiptProvider<B> {
 		let sql = format!("DELETE FROM logs WHERE block_hash in ({placeholders})");
 		let mut delete_logs_query = sqlx::query(&sql);
 
+		let sql = format!("DELETE FROM indexed_blocks WHERE block_hash in ({placeholders})");
+		let mut delete_blocks_query = sqlx::query(&sql);
+
 		for block_hash in block_hashes {
 			delete_tx_query = delete_tx_query.bind(block_hash.as_ref());
 			delete_logs_query = delete_logs_query.bind(block_hash.as_ref());
+			delete_blocks_query = delete_blocks_query.bind(block_hash.as_ref());
+		}
+
+		delete_tx_query.execute(&mut *conn).await?;
+		delete_logs_query.execute(&mut *conn).await?;
+		delete_blocks_query.execute(&mut *conn).await?;
+		Ok(())
+	}
+
+	/// Deletes the records of the indexed blocks older than `block_number`.
+	pub async fn prune_before(
+		&self,
+		block_number: SubstrateBlockNumber,
+	) -> Result<(), ClientError> {
+		log::debug!(target: LOG_TARGET, "Pruning indexed blocks before #{block_number}");
+		let block_number = block_number as i64;
+		let mut tx = self.pool.begin().await?;
+
+		sqlx::query(
+			r#"
+			DELETE FROM transaction_hashes
+			WHERE block_hash IN (SELECT block_hash FROM indexed_blocks WHERE block_number < $1)
+			"#,
+		)
+		.bind(block_number)
+		.execute(&mut *tx)
+		.await?;
+
+		sqlx::query("DELETE FROM logs WHERE block_number < $1")
+			.bind(block_number)
+			.execute(&mut *tx)
+			.await?;
+
+		sqlx::query("DELETE FROM indexed_blocks WHERE block_number < $1")
+			.bind(block_number)
+			.execute(&mut *tx)
+			.await?;
+
+		tx.commit().await?;
+		Ok(())
+	}
+
+	/// Returns the next block to index, if the indexer previously ran from `start_block`.
+	pub async fn indexer_next_block(
+		&self,
+		start_block: SubstrateBlockNumber,
+	) -> Result<Option<SubstrateBlockNumber>, ClientError> {
+		let next_block: Option<i64> = sqlx::query_scalar(
+			"SELECT next_block FROM indexer_state WHERE id = 0 AND start_block = $1",
+		)
+		.bind(start_block as i64)
+		.fetch_optional(&self.pool)
+		.await?;
+
+		Ok(next_block.map(|n| n as SubstrateBlockNumber))
+	}
+
+	/// Store the progress of the indexer started from `start_block`.
+	pub async fn set_indexer_next_block(
+		&self,
+		start_block: SubstrateBlockNumber,
+		next_block: SubstrateBlockNumber,
+	) -> Result<(), ClientError> {
+		sqlx::query(
+			"INSERT OR REPLACE INTO indexer_state (id, start_block, next_block) VALUES (0, $1, $2)",
+		)
+		.bind(start_block as i64)
+		.bind(next_block as i64)
+		.execute(&self.pool)
+		.await?;
+		Ok(())
+	}
+
+	/// Returns `true` if the given block was already indexed.
+	async fn is_indexed(&self, block: &impl BlockInfo) -> Result<bool, ClientError> {
+		let exists = sqlx::query_scalar(
+			"SELECT EXISTS(SELECT 1 FROM indexed_blocks WHERE block_number = $1 AND block_hash = $2)",
+		)
+		.bind(block.number() as i64)
+		.bind(block.hash().as_ref())
+		.fetch_one(&self.pool)
+		.await?;
+		Ok(exists)
+	}
+
+	/// Record the given block as indexed, along with the bloom of its logs.
+	///
+	/// This replaces the record of any other block indexed at the same height.
+	async fn insert_indexed_block(
+		&self,
+		conn: &mut SqliteConnection,
+		block: &impl BlockInfo,
+		receipts: &[(TransactionSigned, ReceiptInfo)],
+	) -> Result<(), ClientError> {
+		let mut logs_bloom = [0u8; BLOOM_SIZE];
+		for log in receipts.iter().flat_map(|(_, receipt)| &receipt.logs) {
+			bloom_accrue(&mut logs_bloom, log.address.as_ref());
+			for topic in &log.topics {
+				bloom_accrue(&mut logs_bloom, topic.as_ref());
+			}
 		}
 
-		let delete_transaction_hashes = delete_tx_query.execute(&self.pool);
-		let delete_logs = delete_logs_query.execute(&self.pool);
-		tokio::try_join!(delete_transaction_hashes, delete_logs)?;
+		let block_number = block.number();
+		sqlx::query(
+			r#"
+			INSERT OR REPLACE INTO indexed_blocks (block_number, block_hash, logs_bloom)
+			VALUES ($1, $2, $3)
+			"#,
+		)
+		.bind(block_number as i64)
+		.bind(block.hash().as_ref())
+		.bind(&logs_bloom[..])
+		.execute(&mut *conn)
+		.await?;
+
+		if let Some(keep_latest_n_blocks) = self.keep_latest_n_blocks {
+			let oldest_block = block_number.saturating_sub(keep_latest_n_blocks as _);
+			sqlx::query("DELETE FROM indexed_blocks WHERE block_number <= $1")
+				.bind(oldest_block as i64)
+				.execute(&mut *conn)
+				.await?;
+		}
 		Ok(())
 	}
 
+	/// Narrow the `from..=to` block range of a logs query, to the blocks whose logs bloom may
+	/// match the given addresses and topics.
+	///
+	/// Returns `None` if no block of the range can match. The range is returned unchanged if
+	/// some of its blocks were not indexed.
+	async fn narrow_block_range(
+		&self,
+		from: u64,
+		to: u64,
+		address: Option<&AddressOrAddresses>,
+		topics: Option<&FilterTopics>,
+	) -> Result<Option<(u64, u64)>, sqlx::Error> {
+		if (address.is_none() && topics.is_none()) || to - from >= MAX_BLOOM_FILTERED_BLOCKS {
+			return Ok(Some((from, to)));
+		}
+
+		let blooms: Vec<(i64, Vec<u8>)> = sqlx::query_as(
+			r#"
+			SELECT block_number, logs_bloom
+			FROM indexed_blocks
+			WHERE block_number BETWEEN $1 AND $2
+			"#,
+		)
+		.bind(from as i64)
+		.bind(to as i64)
+		.fetch_all(&self.pool)
+		.await?;
+
+		if blooms.len() as u64 != to - from + 1 {
+			return Ok(Some((from, to)));
+		}
+
+		let matches = |bloom: &[u8]| {
+			let address_matches = match address {
+				None => true,
+				Some(AddressOrAddresses::Address(address)) =>
+					bloom_contains(bloom, address.as_ref()),
+				Some(AddressOrAddresses::Addresses(addresses)) =>
+					addresses.is_empty() ||
+						addresses.iter().any(|address| bloom_contains(bloom, address.as_ref())),
+			};
+
+			address_matches &&
+				topics.into_iter().flatten().all(|topic| match topic {
+					FilterTopic::Single(topic) => bloom_contains(bloom, topic.as_ref()),
+					FilterTopic::Multiple(topics) =>
+						topics.is_empty() ||
+							topics.iter().any(|topic| bloom_contains(bloom, topic.as_ref())),
+				})
+		};
+
+		let mut candidates = blooms
+			.iter()
+			.filter(|(_, bloom)| matches(bloom))
+			.map(|(block_number, _)| *block_number as u64);
+
+		let Some(first) = candidates.next() else { return Ok(None) };
+		let (min, max) = candidates.fold((first, first), |(min, max), n| (min.min(n), max.max(n)));
+		Ok(Some((min, max)))
+	}
+
 	/// Check if the block is before the earliest block.
 	pub fn is_before_earliest_block(&self, at: &BlockNumberOrTag) -> bool {
 		match at {
@@ -159,6 +359,26 @@ impl<B: BlockInfoProvider> ReceiptProvider<B> {
 		&self,
 		block: &impl BlockInfo,
 		receipts: &[(TransactionSigned, ReceiptInfo)],
+	) -> Result<(), ClientError> {
+		if self.is_indexed(block).await? {
+			return Ok(());
+		}
+
+		// All writes happen in a single transaction, so that a failure midway never leaves the
+		// block marked as indexed without its receipts.
+		let mut tx = self.pool.begin().await?;
+		self.insert_receipts(&mut tx, block, receipts).await?;
+		self.insert_indexed_block(&mut tx, block, receipts).await?;
+		tx.commit().await?;
+		Ok(())
+	}
+
+	/// Insert the transaction hashes and logs of the given receipts.
+	async fn insert_receipts(
+		&self,
+		conn: &mut SqliteConnection,
+		block: &impl BlockInfo,
+		receipts: &[(TransactionSigned, ReceiptInfo)],
 	) -> Result<(), ClientError> {
 		if receipts.is_empty() {
 			return Ok(());
@@ -172,7 +392,7 @@ impl<B: BlockInfoProvider> ReceiptProvider<B> {
 			r#"SELECT EXISTS(SELECT 1 FROM transaction_hashes WHERE block_hash = $1) AS "exists!: bool""#,
 			block_hash_ref
 		)
-		.fetch_one(&self.pool)
+		.fetch_one(&mut *conn)
 		.await?;
 
 		if result.exists {
@@ -200,7 +420,7 @@ impl<B: BlockInfoProvider> ReceiptProvider<B> {
 			}
 
 			log::trace!(target: LOG_TARGET, "Pruning old blocks: {to_remove:?}");
-			self.remove(&to_remove).await?;
+			Self::remove_with(&mut *conn, &to_remove).await?;
 		}
 
 		for (_, receipt) in receipts {
@@ -216,7 +436,7 @@ impl<B: BlockInfoProvider> ReceiptProvider<B> {
 				block_hash_ref,
 				transaction_index
 			)
-			.execute(&self.pool)
+			.execute(&mut *conn)
 			.await?;
 
 			for log in &receipt.logs {
@@ -254,7 +474,7 @@ impl<B: BlockInfoProvider> ReceiptProvider<B> {
 					topic_3,
 					data
 				)
-				.execute(&self.pool)
+				.execute(&mut *conn)
 				.await?;
 			}
 		}
@@ -292,14 +512,24 @@ impl<B: BlockInfoProvider> ReceiptProvider<B> {
 			(Some(from_block), Some(to_block), None) if from_block == to_block => {
 				qb.push(" AND block_number = ").push_bind(from_block.as_u64() as i64);
 			},
-			(Some(from_block), Some(to_block), None) => {
+			(Some(from_block), to_block, None) => {
+				let to_block = to_block.unwrap_or(latest_block);
+				let Some((from, to)) = self
+					.narrow_block_range(
+						from_block.as_u64(),
+						to_block.as_u64(),
+						filter.address.as_ref(),
+						filter.topics.as_ref(),
+					)
+					.await?
+				else {
+					return Ok(vec![]);
+				};
+
 				qb.push(" AND block_number BETWEEN ")
-					.push_bind(from_block.as_u64() as i64)
+					.push_bind(from as i64)
 					.push(" AND ")
-					.push_bind(to_block.as_u64() as i64);
-			},
-			(Some(from_block), None, None) => {
-				qb.push(" AND block_number >= ").push_bind(from_block.as_u64() as i64);
+					.push_bind(to as i64);
 			},
 			(None, Some(to_block), None) => {
 				qb.push(" AND block_number <= ").push_bind(to_block.as_u64() as i64);
@@ -491,6 +721,29 @@ impl<B: BlockInfoProvider> ReceiptProvider<B> {
 	}
 }
 
+/// Returns the `(byte index, mask)` of the three bits set in a logs bloom for the given input.
+///
+/// See the `M3:2048` function of the Ethereum yellow paper.
+fn bloom_bits(input: &[u8]) -> impl Iterator<Item = (usize, u8)> {
+	let hash = keccak_256(input);
+	[0, 2, 4].into_iter().map(move |i| {
+		let bit = (hash[i + 1] as usize + ((hash[i] as usize) << 8)) & 0x7FF;
+		(BLOOM_SIZE - 1 - bit / 8, 1 << (bit % 8))
+	})
+}
+
+/// Add the given address or topic to the logs `bloom`.
+fn bloom_accrue(bloom: &mut [u8; BLOOM_SIZE], input: &[u8]) {
+	for (index, mask) in bloom_bits(input) {
+		bloom[index] |= mask;
+	}
+}
+
+/// Returns `true` if the logs `bloom` may contain the given address or topic.
+fn bloom_contains(bloom: &[u8], input: &[u8]) -> bool {
+	bloom.len() == BLOOM_SIZE && bloom_bits(input).all(|(index, mask)| bloom[index] & mask != 0)
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -610,6 +863,175 @@ mod tests {
 		return Ok(());
 	}
 
+	#[sqlx::test]
+	async fn test_indexed_blocks(pool: SqlitePool) -> anyhow::Result<()> {
+		let provider = setup_sqlite_provider(pool).await;
+
+		// Blocks without receipts are recorded as indexed too.
+		for i in 0..3u8 {
+			let block = MockBlockInfo { hash: H256::from([i; 32]), number: i as _ };
+			provider.insert(&block, &[]).await?;
+			assert!(provider.is_indexed(&block).await?);
+		}
+		assert_eq!(count(&provider.pool, "indexed_blocks", None).await, 3);
+
+		// A fork replaces the block indexed at the same height.
+		let fork = MockBlockInfo { hash: H256::from([4u8; 32]), number: 2 };
+		provider.insert(&fork, &[]).await?;
+		assert!(provider.is_indexed(&fork).await?);
+		assert!(
+			!provider
+				.is_indexed(&MockBlockInfo { hash: H256::from([2u8; 32]), number: 2 })
+				.await?
+		);
+		assert_eq!(count(&provider.pool, "indexed_blocks", None).await, 3);
+		Ok(())
+	}
+
+	#[sqlx::test]
+	async fn test_prune_before(pool: SqlitePool) -> anyhow::Result<()> {
+		let provider = setup_sqlite_provider(pool).await;
+
+		for i in 0..4u8 {
+			let block = MockBlockInfo { hash: H256::from([i; 32]), number: i as _ };
+			let transaction_hash = H256::from([i; 32]);
+			let receipts = vec![(
+				TransactionSigned::default(),
+				ReceiptInfo {
+					transaction_hash,
+					logs: vec![Log {
+						block_hash: block.hash,
+						block_number: block.number.into(),
+						transaction_hash,
+						..Default::default()
+					}],
+					..Default::default()
+				},
+			)];
+			provider.insert(&block, &receipts).await?;
+		}
+
+		provider.prune_before(2).await?;
+		assert_eq!(count(&provider.pool, "indexed_blocks", None).await, 2);
+		assert_eq!(count(&provider.pool, "transaction_hashes", None).await, 2);
+		assert_eq!(count(&provider.pool, "logs", None).await, 2);
+		assert_eq!(provider.fetch_row(&H256::from([1u8; 32])).await, None);
+		assert_eq!(
+			provider.fetch_row(&H256::from([2u8; 32])).await,
+			Some((H256::from([2u8; 32]), 0))
+		);
+		Ok(())
+	}
+
+	#[sqlx::test]
+	async fn test_indexer_next_block(pool: SqlitePool) -> anyhow::Result<()> {
+		let provider = setup_sqlite_provider(pool).await;
+		assert_eq!(provider.indexer_next_block(10).await?, None);
+
+		provider.set_indexer_next_block(10, 15).await?;
+		assert_eq!(provider.indexer_next_block(10).await?, Some(15));
+
+		// The progress is discarded when indexing from another block.
+		assert_eq!(provider.indexer_next_block(0).await?, None);
+		provider.set_indexer_next_block(0, 1).await?;
+		assert_eq!(provider.indexer_next_block(10).await?, None);
+		assert_eq!(provider.indexer_next_block(0).await?, Some(1));
+		Ok(())
+	}
+
+	#[sqlx::test]
+	async fn test_query_logs_with_bloom(pool: SqlitePool) -> anyhow::Result<()> {
+		let provider = setup_sqlite_provider(pool).await;
+		let log = Log {
+			block_hash: H256::from([1u8; 32]),
+			block_number: U256::from(1),
+			address: H160::from([1u8; 20]),
+			topics: vec![H256::from([1u8; 32])],
+			..Default::default()
+		};
+		let receipt = ReceiptInfo::new(
+			log.block_hash,
+			log.block_number,
+			None,
+			H160::zero(),
+			vec![log.clone()],
+			None,
+			U256::zero(),
+			U256::zero(),
+			true,
+			log.transaction_hash,
+			log.transaction_index,
+			Default::default(),
+		);
+		// The bloom is computed like in the runtime.
+		let mut logs_bloom = [0u8; BLOOM_SIZE];
+		bloom_accrue(&mut logs_bloom, log.address.as_ref());
+		bloom_accrue(&mut logs_bloom, log.topics[0].as_ref());
+		assert_eq!(logs_bloom, receipt.logs_bloom.0);
+		assert!(bloom_contains(&logs_bloom, log.address.as_ref()));
+		assert!(!bloom_contains(&logs_bloom, H160::from([2u8; 20]).as_ref()));
+
+		provider
+			.insert(&MockBlockInfo { hash: H256::from([0u8; 32]), number: 0 }, &[])
+			.await?;
+		provider
+			.insert(
+				&MockBlockInfo { hash: log.block_hash, number: 1 },
+				&[(TransactionSigned::default(), receipt)],
+			)
+			.await?;
+		provider
+			.insert(&MockBlockInfo { hash: H256::from([2u8; 32]), number: 2 }, &[])
+			.await?;
+
+		let range = |address: H160, topics: Option<FilterTopics>| {
+			let provider = &provider;
+			async move {
+				let address = AddressOrAddresses::Address(address);
+				provider.narrow_block_range(0, 2, Some(&address), topics.as_ref()).await
+			}
+		};
+		assert_eq!(range(log.address, None).await?, Some((1, 1)));
+		assert_eq!(range(H160::from([2u8; 20]), None).await?, None);
+		assert_eq!(
+			range(log.address, Some(vec![FilterTopic::Single(H256::from([2u8; 32]))])).await?,
+			None
+		);
+		assert_eq!(
+			range(
+				log.address,
+				Some(vec![FilterTopic::Multiple(vec![H256::from([2u8; 32]), log.topics[0]])])
+			)
+			.await?,
+			Some((1, 1))
+		);
+
+		// Ranges that are not fully indexed are not narrowed.
+		assert_eq!(
+			provider.narrow_block_range(0, 3, Some(&log.address.into()), None).await?,
+			Some((0, 3))
+		);
+
+		let logs = provider
+			.logs(Some(Filter {
+				from_block: Some(U256::from(0).into()),
+				address: Some(log.address.into()),
+				..Default::default()
+			}))
+			.await?;
+		assert_eq!(logs, vec![log.clone()]);
+
+		let logs = provider
+			.logs(Some(Filter {
+				from_block: Some(U256::from(0).into()),
+				address: Some(H160::from([2u8; 20]).into()),
+				..Default::default()
+			}))
+			.await?;
+		assert_eq!(logs, vec![]);
+		Ok(())
+	}
+
 	#[sqlx::test]
 	async fn test_receipts_count_per_block(pool: SqlitePool) -> anyhow::Result<()> {
 		let provider = setup_sqlite_provider(pool).await;
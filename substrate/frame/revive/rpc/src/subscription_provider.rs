@@ -0,0 +1,279 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use crate::client::SubstrateBlockNumber;
+use pallet_revive::evm::{Block, Log};
+use sp_core::H256;
+use std::{
+	collections::{BTreeMap, HashSet},
+	sync::Arc,
+};
+use tokio::sync::{broadcast, Mutex};
+
+/// The number of events buffered for each subscriber before it starts lagging.
+const EVENTS_CAPACITY: usize = 1024;
+
+/// The maximum number of best blocks tracked to detect re-orgs.
+pub const MAX_REORG_DEPTH: usize = 256;
+
+/// An event emitted when the best chain changes, or when a transaction is submitted.
+#[derive(Debug, Clone)]
+pub enum ChainEvent {
+	/// A block was added to the best chain.
+	Enacted {
+		/// The Ethereum block, without transactions.
+		block: Arc<Block>,
+		/// The logs emitted in this block.
+		logs: Arc<Vec<Log>>,
+	},
+	/// A block was removed from the best chain by a re-org.
+	Retracted {
+		/// The hash of the retracted block.
+		block_hash: H256,
+		/// The logs emitted in this block, marked as `removed`.
+		logs: Arc<Vec<Log>>,
+	},
+	/// A transaction was added to the pool.
+	PendingTransaction(H256),
+}
+
+/// A block of the best chain tracked by the [`SubscriptionProvider`].
+#[derive(Debug, Clone)]
+struct BestBlock {
+	hash: H256,
+	logs: Arc<Vec<Log>>,
+}
+
+/// SubscriptionProvider tracks the best chain and broadcasts [`ChainEvent`]s to the
+/// `eth_subscribe` subscriptions and the installed filters.
+#[derive(Clone)]
+pub struct SubscriptionProvider {
+	/// The sender used to broadcast chain events.
+	sender: broadcast::Sender<ChainEvent>,
+	/// The latest best blocks, used to detect re-orgs.
+	best_blocks: Arc<Mutex<BTreeMap<SubstrateBlockNumber, BestBlock>>>,
+	/// The hashes of the pending transactions already notified, used to notify every transaction
+	/// once.
+	pending_transactions: Arc<std::sync::Mutex<HashSet<H256>>>,
+}
+
+impl Default for SubscriptionProvider {
+	fn default() -> Self {
+		let (sender, _) = broadcast::channel(EVENTS_CAPACITY);
+		Self { sender, best_blocks: Default::default(), pending_transactions: Default::default() }
+	}
+}
+
+impl SubscriptionProvider {
+	/// Subscribe to the chain events.
+	pub fn subscribe(&self) -> broadcast::Receiver<ChainEvent> {
+		self.sender.subscribe()
+	}
+
+	/// Returns the hash of the best block tracked at the given height, if any.
+	pub async fn best_block_hash(&self, number: SubstrateBlockNumber) -> Option<H256> {
+		self.best_blocks.lock().await.get(&number).map(|block| block.hash)
+	}
+
+	/// Notify a transaction that was submitted to the pool.
+	pub fn notify_pending_transaction(&self, hash: H256) {
+		let mut pending_transactions =
+			self.pending_transactions.lock().expect("lock is not poisoned; qed");
+		if pending_transactions.insert(hash) {
+			// An error only means that there are no subscribers.
+			let _ = self.sender.send(ChainEvent::PendingTransaction(hash));
+		}
+	}
+
+	/// Update the set of transactions in the pool of the node.
+	///
+	/// The transactions that were not notified yet are notified, and the transactions that left
+	/// the pool are forgotten.
+	pub fn update_pending_transactions(&self, hashes: Vec<H256>) {
+		let mut pending_transactions =
+			self.pending_transactions.lock().expect("lock is not poisoned; qed");
+		for hash in &hashes {
+			if !pending_transactions.contains(hash) {
+				let _ = self.sender.send(ChainEvent::PendingTransaction(*hash));
+			}
+		}
+		*pending_transactions = hashes.into_iter().collect();
+	}
+
+	/// Update the best chain.
+	///
+	/// Every tracked block with a number greater than or equal to the first new block of `enacted`
+	/// is retracted, newest first. The `enacted` blocks must be consecutive, and sorted by
+	/// ascending block number. Blocks that are already tracked are skipped.
+	pub async fn update_best_chain(&self, enacted: Vec<(Block, Vec<Log>)>) {
+		let mut best_blocks = self.best_blocks.lock().await;
+		let enacted = enacted
+			.into_iter()
+			.skip_while(|(block, _)| {
+				SubstrateBlockNumber::try_from(block.number)
+					.ok()
+					.and_then(|number| best_blocks.get(&number))
+					.is_some_and(|tracked| tracked.hash == block.hash)
+			})
+			.collect::<Vec<_>>();
+
+		let Some((first, _)) = enacted.first() else { return };
+		let Ok(first_number) = SubstrateBlockNumber::try_from(first.number) else { return };
+
+		let retracted = best_blocks.split_off(&first_number);
+
+		for (_, BestBlock { hash, logs }) in retracted.into_iter().rev() {
+			log::debug!(target: crate::LOG_TARGET, "Retracting block {hash:?}");
+			let logs = logs
+				.iter()
+				.cloned()
+				.map(|log| Log { removed: Some(true), ..log })
+				.collect::<Vec<_>>();
+			let _ = self
+				.sender
+				.send(ChainEvent::Retracted { block_hash: hash, logs: Arc::new(logs) });
+		}
+
+		for (block, logs) in enacted {
+			let Ok(number) = SubstrateBlockNumber::try_from(block.number) else { continue };
+			let logs = Arc::new(
+				logs.into_iter()
+					.map(|log| Log { removed: Some(false), ..log })
+					.collect::<Vec<_>>(),
+			);
+
+			best_blocks.insert(number, BestBlock { hash: block.hash, logs: logs.clone() });
+			let _ = self.sender.send(ChainEvent::Enacted { block: Arc::new(block), logs });
+		}
+
+		while best_blocks.len() > MAX_REORG_DEPTH {
+			best_blocks.pop_first();
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use pretty_assertions::assert_eq;
+
+	fn block(number: u32, seed: u8) -> (Block, Vec<Log>) {
+		let hash = H256::from([seed; 32]);
+		let log = Log { block_hash: hash, block_number: number.into(), ..Default::default() };
+		(Block { hash, number: number.into(), ..Default::default() }, vec![log])
+	}
+
+	fn summary(event: ChainEvent) -> (&'static str, H256, Option<bool>) {
+		match event {
+			ChainEvent::Enacted { block, logs } => ("enacted", block.hash, logs[0].removed),
+			ChainEvent::Retracted { block_hash, logs } =>
+				("retracted", block_hash, logs[0].removed),
+			ChainEvent::PendingTransaction(hash) => ("pending", hash, None),
+		}
+	}
+
+	#[tokio::test]
+	async fn update_best_chain_works() {
+		let provider = SubscriptionProvider::default();
+		let mut receiver = provider.subscribe();
+
+		provider.update_best_chain(vec![block(1, 1)]).await;
+		provider.update_best_chain(vec![block(2, 2)]).await;
+		assert_eq!(
+			summary(receiver.try_recv().unwrap()),
+			("enacted", H256::from([1; 32]), Some(false))
+		);
+		assert_eq!(
+			summary(receiver.try_recv().unwrap()),
+			("enacted", H256::from([2; 32]), Some(false))
+		);
+		assert!(receiver.try_recv().is_err());
+		assert_eq!(provider.best_block_hash(2).await, Some(H256::from([2; 32])));
+	}
+
+	#[tokio::test]
+	async fn reorg_retracts_blocks() {
+		let provider = SubscriptionProvider::default();
+		provider.update_best_chain(vec![block(1, 1), block(2, 2), block(3, 3)]).await;
+
+		let mut receiver = provider.subscribe();
+
+		// Switch to a fork starting at block #2.
+		provider.update_best_chain(vec![block(2, 12), block(3, 13), block(4, 14)]).await;
+
+		let events =
+			std::iter::from_fn(|| receiver.try_recv().ok()).map(summary).collect::<Vec<_>>();
+		assert_eq!(
+			events,
+			vec![
+				("retracted", H256::from([3; 32]), Some(true)),
+				("retracted", H256::from([2; 32]), Some(true)),
+				("enacted", H256::from([12; 32]), Some(false)),
+				("enacted", H256::from([13; 32]), Some(false)),
+				("enacted", H256::from([14; 32]), Some(false)),
+			]
+		);
+		assert_eq!(provider.best_block_hash(1).await, Some(H256::from([1; 32])));
+		assert_eq!(provider.best_block_hash(4).await, Some(H256::from([14; 32])));
+	}
+
+	#[tokio::test]
+	async fn tracked_blocks_are_not_notified_twice() {
+		let provider = SubscriptionProvider::default();
+		provider.update_best_chain(vec![block(1, 1), block(2, 2)]).await;
+
+		let mut receiver = provider.subscribe();
+		provider.update_best_chain(vec![block(1, 1), block(2, 2), block(3, 3)]).await;
+
+		let events =
+			std::iter::from_fn(|| receiver.try_recv().ok()).map(summary).collect::<Vec<_>>();
+		assert_eq!(events, vec![("enacted", H256::from([3; 32]), Some(false))]);
+	}
+
+	#[tokio::test]
+	async fn tracked_blocks_are_bounded() {
+		let provider = SubscriptionProvider::default();
+		for i in 0..(MAX_REORG_DEPTH as u32 + 10) {
+			provider.update_best_chain(vec![block(i, i as u8)]).await;
+		}
+
+		assert_eq!(provider.best_blocks.lock().await.len(), MAX_REORG_DEPTH);
+		assert_eq!(provider.best_block_hash(0).await, None);
+	}
+
+	#[tokio::test]
+	async fn pending_transactions_are_notified_once() {
+		let provider = SubscriptionProvider::default();
+		let mut receiver = provider.subscribe();
+		let hash = |seed: u8| H256::from([seed; 32]);
+
+		provider.notify_pending_transaction(hash(1));
+		provider.update_pending_transactions(vec![hash(1), hash(2)]);
+		provider.update_pending_transactions(vec![hash(2), hash(3)]);
+		provider.notify_pending_transaction(hash(3));
+
+		let events =
+			std::iter::from_fn(|| receiver.try_recv().ok()).map(summary).collect::<Vec<_>>();
+		assert_eq!(
+			events,
+			vec![
+				("pending", hash(1), None),
+				("pending", hash(2), None),
+				("pending", hash(3), None)
+			]
+		);
+	}
+}
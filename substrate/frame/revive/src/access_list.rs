@@ -0,0 +1,138 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Warm and cold storage accesses for the access list of an Ethereum transaction
+//! ([EIP-2930](https://eips.ethereum.org/EIPS/eip-2930)).
+//!
+//! The storage slots declared in the access list of a transaction are paid upfront, as part of
+//! the weight of the dispatched call. In return, the first access to each of them during the
+//! execution is *warm*: it is not charged the overhead of reading from or writing to a full
+//! storage, which is otherwise included in the costs of every storage access.
+//!
+//! Addresses are only used to scope the storage keys. Declaring an address without any storage
+//! key is free and has no effect.
+
+use crate::{evm::AccessList, exec::Key, weights::WeightInfo, Config, Weight};
+use alloc::collections::btree_set::BTreeSet;
+use environmental::environmental;
+use frame_support::{traits::ConstU32, BoundedVec};
+use sp_core::{H160, H256};
+
+/// The maximum number of storage slots an access list can declare.
+pub const MAX_SLOTS: u32 = 1024;
+
+/// The storage slots declared by a transaction, as `(address, storage key)` pairs.
+pub type AccessListSlots = BoundedVec<(H160, H256), ConstU32<MAX_SLOTS>>;
+
+environmental!(warm_slots: BTreeSet<(H160, [u8; 32])>);
+
+/// Convert the access list of an Ethereum transaction into the slots it declares.
+///
+/// Returns `None` if the access list declares more than [`MAX_SLOTS`] storage slots.
+pub fn slots_from_access_list(access_list: Option<AccessList>) -> Option<AccessListSlots> {
+	access_list
+		.unwrap_or_default()
+		.into_iter()
+		.flat_map(|entry| entry.storage_keys.into_iter().map(move |key| (entry.address, key)))
+		.collect::<alloc::vec::Vec<_>>()
+		.try_into()
+		.ok()
+}
+
+/// Execute `f` with the storage slots declared in `slots` being warm.
+pub(crate) fn with_access_list<R>(slots: &AccessListSlots, f: impl FnOnce() -> R) -> R {
+	if slots.is_empty() {
+		return f();
+	}
+
+	let mut warm = slots.iter().map(|(address, key)| (*address, key.0)).collect();
+	warm_slots::using_once(&mut warm, f)
+}
+
+/// Returns `true` if the storage `key` of the contract at `address` was declared in the access
+/// list of the transaction being executed and was not accessed yet.
+///
+/// A declared slot is only paid for once, hence only its first access is warm: the slot is
+/// removed from the warm slots by this call.
+///
+/// Only fixed size keys can be declared, as Ethereum storage keys are 32 bytes long.
+pub(crate) fn take_warm(address: &H160, key: &Key) -> bool {
+	let Key::Fix(key) = key else { return false };
+	warm_slots::with(|slots| slots.remove(&(*address, *key))).unwrap_or(false)
+}
+
+/// The overhead of reading from a full storage, which is not charged for warm reads.
+pub(crate) fn read_overhead<T: Config>() -> Weight {
+	T::WeightInfo::get_storage_full().saturating_sub(T::WeightInfo::get_storage_empty())
+}
+
+/// The overhead of writing to a full storage, which is not charged for warm writes.
+pub(crate) fn write_overhead<T: Config>() -> Weight {
+	T::WeightInfo::set_storage_full().saturating_sub(T::WeightInfo::set_storage_empty())
+}
+
+/// The weight charged upfront for the storage slots declared in `slots`.
+///
+/// Every declared slot is charged the largest overhead it can save, whether it is then read or
+/// written, so that declaring a slot is never cheaper than accessing it cold. Declaring the same
+/// slot twice is charged twice.
+pub fn weight<T: Config>(slots: &AccessListSlots) -> Weight {
+	read_overhead::<T>()
+		.max(write_overhead::<T>())
+		.saturating_mul(slots.len() as u64)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::evm::AccessListEntry;
+	use alloc::vec;
+
+	#[test]
+	fn warm_slots_are_scoped_to_the_access_list() {
+		let address = H160::repeat_byte(1);
+		let key = H256::repeat_byte(2);
+		let slots = slots_from_access_list(Some(vec![
+			AccessListEntry { address, storage_keys: vec![key] },
+			AccessListEntry { address: H160::repeat_byte(4), storage_keys: vec![] },
+		]))
+		.unwrap();
+		assert_eq!(slots.to_vec(), vec![(address, key)]);
+
+		assert!(!take_warm(&address, &Key::Fix(key.0)));
+		with_access_list(&slots, || {
+			assert!(!take_warm(&H160::repeat_byte(3), &Key::Fix(key.0)));
+			assert!(!take_warm(&address, &Key::Fix([0u8; 32])));
+			assert!(!take_warm(&address, &Key::try_from_var(key.0.to_vec()).unwrap()));
+			assert!(take_warm(&address, &Key::Fix(key.0)));
+			// Only the first access is warm.
+			assert!(!take_warm(&address, &Key::Fix(key.0)));
+		});
+		assert!(!take_warm(&address, &Key::Fix(key.0)));
+	}
+
+	#[test]
+	fn access_list_is_bounded() {
+		let entry = |keys| AccessListEntry {
+			address: H160::repeat_byte(1),
+			storage_keys: vec![H256::zero(); keys],
+		};
+		let max = MAX_SLOTS as usize;
+		assert!(slots_from_access_list(Some(vec![entry(max)])).is_some());
+		assert!(slots_from_access_list(Some(vec![entry(max), entry(1)])).is_none());
+	}
+}
@@ -95,7 +95,7 @@ pub struct AccountId32Mapper<T>(PhantomData<T>);
 /// It just trivially returns its inputs and doesn't make use of any state.
 pub struct H160Mapper<T>(PhantomData<T>);
 
-/// An account mapper that can be used for testing u64 account ids.
+/// An account mapper that can be used for testing u64 or u128 account ids.
 pub struct TestAccountMapper<T>(PhantomData<T>);
 
 impl<T> AddressMapper<T> for AccountId32Mapper<T>
@@ -159,11 +159,12 @@ where
 
 impl<T> AddressMapper<T> for TestAccountMapper<T>
 where
-	T: Config<AccountId = u64>,
+	T: Config,
+	T::AccountId: Into<u128> + TryFrom<u128> + From<u64>,
 {
 	fn to_address(account_id: &T::AccountId) -> H160 {
 		let mut bytes = [0u8; 20];
-		bytes[12..].copy_from_slice(&account_id.to_be_bytes());
+		bytes[4..].copy_from_slice(&account_id.clone().into().to_be_bytes());
 		H160::from(bytes)
 	}
 
@@ -172,7 +173,9 @@ where
 	}
 
 	fn to_fallback_account_id(address: &H160) -> T::AccountId {
-		u64::from_be_bytes(address.as_ref()[12..].try_into().unwrap())
+		let id = u128::from_be_bytes(address.as_ref()[4..].try_into().unwrap());
+		// `u64` account ids only keep the last 8 bytes of the address.
+		T::AccountId::try_from(id).unwrap_or_else(|_| T::AccountId::from(id as u64))
 	}
 
 	fn map(_account_id: &T::AccountId) -> DispatchResult {
@@ -27,9 +27,18 @@ pub use rlp;
 mod type_id;
 pub use type_id::*;
 
+mod access_list_rpc_types;
+pub use access_list_rpc_types::*;
+
 mod debug_rpc_types;
 pub use debug_rpc_types::*;
 
+mod proof_rpc_types;
+pub use proof_rpc_types::*;
+
+mod pubsub_rpc_types;
+pub use pubsub_rpc_types::*;
+
 mod rpc_types;
 mod rpc_types_gen;
 pub use rpc_types_gen::*;
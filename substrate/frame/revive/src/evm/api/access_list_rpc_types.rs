@@ -0,0 +1,56 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! Types returned by `eth_createAccessList`.
+
+use crate::evm::AccessList;
+use alloc::string::String;
+use serde::{Deserialize, Serialize};
+use sp_core::U256;
+
+/// The result of `eth_createAccessList`.
+#[derive(Debug, Default, Clone, Serialize, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct AccessListResult {
+	/// The addresses and storage slots accessed by the transaction.
+	pub access_list: AccessList,
+	/// The gas used by the transaction when sent with the access list.
+	pub gas_used: U256,
+	/// The error message of the transaction, if it failed.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub error: Option<String>,
+}
+
+#[test]
+fn access_list_result_serialization() {
+	use crate::evm::AccessListEntry;
+	use sp_core::{H160, H256};
+
+	let result = AccessListResult {
+		access_list: alloc::vec![AccessListEntry {
+			address: H160::from([1u8; 20]),
+			storage_keys: alloc::vec![H256::from([2u8; 32])],
+		}],
+		gas_used: U256::from(21000),
+		error: None,
+	};
+
+	let json = serde_json::to_value(&result).unwrap();
+	assert_eq!(json["gasUsed"], serde_json::json!("0x5208"));
+	assert_eq!(json["accessList"][0]["storageKeys"][0], serde_json::json!(H256::from([2u8; 32])));
+	assert!(json.get("error").is_none());
+	assert_eq!(serde_json::from_value::<AccessListResult>(json).unwrap(), result);
+}
@@ -15,11 +15,13 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::evm::Bytes;
+use crate::evm::{AccessList, Bytes};
 use alloc::{collections::BTreeMap, string::String, vec::Vec};
 use codec::{Decode, Encode};
 use derive_more::From;
 use scale_info::TypeInfo;
+#[cfg(feature = "std")]
+use serde::{de::Error as _, Deserializer};
 use serde::{
 	ser::{SerializeMap, Serializer},
 	Deserialize, Serialize,
@@ -27,7 +29,6 @@ use serde::{
 use sp_core::{H160, H256, U256};
 
 /// The type of tracer to use.
-/// Only "callTracer" is supported for now.
 #[derive(TypeInfo, Debug, Clone, Encode, Decode, Serialize, Deserialize, PartialEq)]
 #[serde(tag = "tracer", content = "tracerConfig", rename_all = "camelCase")]
 pub enum TracerType {
@@ -36,6 +37,20 @@ pub enum TracerType {
 
 	/// A tracer that traces the prestate.
 	PrestateTracer(Option<PrestateTracerConfig>),
+
+	/// A tracer that logs every executed instruction.
+	///
+	/// This is the default tracer when no `tracer` is specified.
+	StructLogger(Option<StructLoggerConfig>),
+
+	/// A tracer that collects the selectors and input sizes of the calls.
+	#[serde(rename = "4byteTracer")]
+	FourByteTracer,
+
+	/// A tracer that collects the addresses and storage slots accessed by the call.
+	///
+	/// Used by eth-rpc `eth_createAccessList`.
+	AccessListTracer,
 }
 
 impl From<CallTracerConfig> for TracerType {
@@ -44,15 +59,16 @@ impl From<CallTracerConfig> for TracerType {
 	}
 }
 
+/// Like geth, the struct logger is used when no tracer is specified.
 impl Default for TracerType {
 	fn default() -> Self {
-		TracerType::CallTracer(Some(CallTracerConfig::default()))
+		TracerType::StructLogger(Some(StructLoggerConfig::default()))
 	}
 }
 
 /// Tracer configuration used to trace calls.
 #[derive(TypeInfo, Debug, Clone, Default, PartialEq)]
-#[cfg_attr(feature = "std", derive(Deserialize, Serialize), serde(rename_all = "camelCase"))]
+#[cfg_attr(feature = "std", derive(Serialize), serde(rename_all = "camelCase"))]
 pub struct TracerConfig {
 	/// The tracer type.
 	#[cfg_attr(feature = "std", serde(flatten, default))]
@@ -63,6 +79,40 @@ pub struct TracerConfig {
 	pub timeout: Option<core::time::Duration>,
 }
 
+/// Like geth, the struct logger is used when no `tracer` is specified, and its options are read
+/// from the top-level object.
+#[cfg(feature = "std")]
+impl<'de> Deserialize<'de> for TracerConfig {
+	fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+		#[derive(Deserialize)]
+		struct Tagged {
+			#[serde(flatten)]
+			config: TracerType,
+			#[serde(with = "humantime_serde", default)]
+			timeout: Option<core::time::Duration>,
+		}
+
+		#[derive(Deserialize)]
+		struct Untagged {
+			#[serde(flatten)]
+			config: StructLoggerConfig,
+			#[serde(with = "humantime_serde", default)]
+			timeout: Option<core::time::Duration>,
+		}
+
+		let value = serde_json::Value::deserialize(deserializer)?;
+		if value.get("tracer").is_some() {
+			let Tagged { config, timeout } =
+				serde_json::from_value(value).map_err(D::Error::custom)?;
+			Ok(Self { config, timeout })
+		} else {
+			let Untagged { config, timeout } =
+				serde_json::from_value(value).map_err(D::Error::custom)?;
+			Ok(Self { config: TracerType::StructLogger(Some(config)), timeout })
+		}
+	}
+}
+
 /// The configuration for the call tracer.
 #[derive(Clone, Debug, Decode, Serialize, Deserialize, Encode, PartialEq, TypeInfo)]
 #[serde(default, rename_all = "camelCase")]
@@ -100,6 +150,26 @@ impl Default for PrestateTracerConfig {
 	}
 }
 
+/// The configuration for the struct logger.
+#[derive(Clone, Debug, Default, Decode, Serialize, Deserialize, Encode, PartialEq, TypeInfo)]
+#[serde(default, rename_all = "camelCase")]
+pub struct StructLoggerConfig {
+	/// Whether to exclude the registers from the trace.
+	pub disable_stack: bool,
+
+	/// Whether to exclude the storage from the trace.
+	pub disable_storage: bool,
+
+	/// Whether to include the return data of the last call in the trace.
+	pub enable_return_data: bool,
+
+	/// Whether to include the memory of the contract in the trace.
+	pub enable_memory: bool,
+
+	/// The maximum number of steps to record, `0` means no limit.
+	pub limit: u64,
+}
+
 /// Serialization should support the following JSON format:
 ///
 /// ```json
@@ -137,6 +207,42 @@ fn test_tracer_config_serialization() {
 				timeout: Some(core::time::Duration::from_millis(10)),
 			},
 		),
+		(
+			r#"{"tracer": "4byteTracer"}"#,
+			TracerConfig { config: TracerType::FourByteTracer, timeout: None },
+		),
+		(
+			r#"{"tracer": "accessListTracer"}"#,
+			TracerConfig { config: TracerType::AccessListTracer, timeout: None },
+		),
+		(
+			r#"{"tracer": "structLogger", "tracerConfig": { "limit": 10 }}"#,
+			TracerConfig {
+				config: TracerType::StructLogger(Some(StructLoggerConfig {
+					limit: 10,
+					..Default::default()
+				})),
+				timeout: None,
+			},
+		),
+		(
+			r#"{}"#,
+			TracerConfig {
+				config: TracerType::StructLogger(Some(StructLoggerConfig::default())),
+				timeout: None,
+			},
+		),
+		(
+			r#"{"disableStorage": true, "enableReturnData": true, "timeout": "1s"}"#,
+			TracerConfig {
+				config: TracerType::StructLogger(Some(StructLoggerConfig {
+					disable_storage: true,
+					enable_return_data: true,
+					..Default::default()
+				})),
+				timeout: Some(core::time::Duration::from_secs(1)),
+			},
+		),
 	];
 
 	for (json_data, expected) in tracers {
@@ -144,6 +250,10 @@ fn test_tracer_config_serialization() {
 			serde_json::from_str(json_data).expect("Deserialization should succeed");
 		assert_eq!(result, expected);
 	}
+
+	// An empty configuration and the default configuration agree.
+	let result: TracerConfig = serde_json::from_str("{}").unwrap();
+	assert_eq!(result, TracerConfig::default());
 }
 
 /// The type of call that was executed.
@@ -173,8 +283,75 @@ pub enum Trace {
 	Call(CallTrace),
 	/// A prestate trace.
 	Prestate(PrestateTrace),
+	/// A struct logger trace.
+	StructLogger(StructLoggerTrace),
+	/// A 4byte trace.
+	FourByte(FourByteTrace),
+	/// An access list trace.
+	AccessList(AccessList),
+}
+
+/// A struct logger trace.
+#[derive(
+	TypeInfo, Default, Encode, Decode, Serialize, Deserialize, Clone, Debug, Eq, PartialEq,
+)]
+#[serde(rename_all = "camelCase")]
+pub struct StructLoggerTrace {
+	/// Amount of gas used by the transaction.
+	pub gas: u64,
+	/// Whether the transaction failed.
+	pub failed: bool,
+	/// The return data of the transaction.
+	pub return_value: Bytes,
+	/// The executed instructions.
+	pub struct_logs: Vec<StructLog>,
 }
 
+/// An instruction executed by a contract.
+///
+/// PolkaVM is a register machine, so the `stack` holds the values of the registers before the
+/// instruction is executed, in the order `ra, sp, t0, t1, t2, s0, s1, a0, a1, a2, a3, a4, a5`.
+#[derive(
+	TypeInfo, Default, Encode, Decode, Serialize, Deserialize, Clone, Debug, Eq, PartialEq,
+)]
+#[serde(rename_all = "camelCase")]
+pub struct StructLog {
+	/// The program counter of the instruction.
+	pub pc: u64,
+	/// The name of the instruction.
+	pub op: String,
+	/// Amount of gas left before the instruction is executed.
+	pub gas: u64,
+	/// Amount of gas used by the instruction, including the host functions and calls it invoked.
+	pub gas_cost: u64,
+	/// The depth of the call stack, starting at 1.
+	pub depth: u32,
+	/// The error message if the call failed at this instruction.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub error: Option<String>,
+	/// The values of the registers.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub stack: Option<Vec<U256>>,
+	/// The memory of the contract, in 32 bytes words.
+	///
+	/// This is the read-write data section of the program, which holds the heap. Trailing words
+	/// that are zero are omitted.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub memory: Option<Vec<Bytes>>,
+	/// The data returned by the last call.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub return_data: Option<Bytes>,
+	/// The storage of the contract accessed so far, if the instruction accessed the storage.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub storage: Option<BTreeMap<Bytes, Bytes>>,
+}
+
+/// A 4byte trace.
+///
+/// Maps `<selector>-<input size>` to the number of calls made with this selector and size, where
+/// the input size excludes the 4 bytes of the selector.
+pub type FourByteTrace = BTreeMap<String, u32>;
+
 /// A prestate Trace
 #[derive(TypeInfo, Encode, Decode, Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
 #[serde(untagged)]
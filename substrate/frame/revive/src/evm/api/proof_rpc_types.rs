@@ -0,0 +1,79 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! Types returned by `eth_getProof`.
+//!
+//! The shape of the response follows [EIP-1186](https://eips.ethereum.org/EIPS/eip-1186), but the
+//! proofs are Merkle proofs over the Substrate state trie, see the `proof` module of the
+//! `pallet-revive-eth-rpc` crate for a description of the format.
+
+use crate::evm::{Address, Bytes};
+use alloc::vec::Vec;
+use serde::{Deserialize, Serialize};
+use sp_core::{H256, U256};
+
+/// The account proof returned by `eth_getProof`.
+#[derive(Debug, Default, Clone, Serialize, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountProof {
+	/// The address of the account.
+	pub address: Address,
+	/// The trie nodes proving the account state against the block state root.
+	pub account_proof: Vec<Bytes>,
+	/// The balance of the account.
+	pub balance: U256,
+	/// The hash of the code of the account.
+	pub code_hash: H256,
+	/// The nonce of the account.
+	pub nonce: U256,
+	/// The root of the account storage trie.
+	pub storage_hash: H256,
+	/// The proofs of the requested storage keys.
+	pub storage_proof: Vec<StorageProof>,
+}
+
+/// The proof of a storage slot returned by `eth_getProof`.
+#[derive(Debug, Default, Clone, Serialize, Deserialize, Eq, PartialEq)]
+pub struct StorageProof {
+	/// The requested storage key.
+	pub key: U256,
+	/// The value stored under the key.
+	pub value: U256,
+	/// The trie nodes proving the value against the block state root.
+	pub proof: Vec<Bytes>,
+}
+
+#[test]
+fn account_proof_serialization() {
+	let proof = AccountProof {
+		address: Address::from([1u8; 20]),
+		account_proof: alloc::vec![Bytes(alloc::vec![1, 2])],
+		balance: U256::from(10),
+		nonce: U256::from(1),
+		storage_proof: alloc::vec![StorageProof {
+			key: U256::from(1),
+			value: U256::from(2),
+			proof: alloc::vec![Bytes(alloc::vec![3])],
+		}],
+		..Default::default()
+	};
+
+	let json = serde_json::to_value(&proof).unwrap();
+	assert_eq!(json["accountProof"], serde_json::json!(["0x0102"]));
+	assert_eq!(json["balance"], serde_json::json!("0xa"));
+	assert_eq!(json["storageProof"][0]["value"], serde_json::json!("0x2"));
+	assert_eq!(serde_json::from_value::<AccountProof>(json).unwrap(), proof);
+}
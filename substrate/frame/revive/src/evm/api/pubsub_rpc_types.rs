@@ -0,0 +1,141 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! Types used by the `eth_subscribe` and `eth_newFilter` family of methods.
+
+use crate::evm::{AddressOrAddresses, Block, Filter, FilterTopic, Log};
+use alloc::boxed::Box;
+use derive_more::From;
+use serde::{Deserialize, Serialize};
+use sp_core::H256;
+
+/// The kind of subscription requested by `eth_subscribe`.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum SubscriptionKind {
+	/// Notify each new block header added to the best chain.
+	NewHeads,
+	/// Notify logs matching the given filter, as they are included or retracted.
+	Logs,
+	/// Notify the hashes of transactions added to the transaction pool.
+	NewPendingTransactions,
+}
+
+/// An item sent with an `eth_subscription` notification.
+#[derive(Debug, Clone, Serialize, Deserialize, From, Eq, PartialEq)]
+#[serde(untagged)]
+pub enum SubscriptionItem {
+	/// A new block header.
+	Header(Box<Block>),
+	/// A log matching the subscription filter.
+	Log(Log),
+	/// The hash of a pending transaction.
+	TransactionHash(H256),
+}
+
+impl Filter {
+	/// Returns `true` if the log matches the address and topics criteria of this filter.
+	///
+	/// The block range criteria (`fromBlock`, `toBlock`, `blockHash`) are not checked.
+	pub fn matches_log(&self, log: &Log) -> bool {
+		let address_matches = match &self.address {
+			None => true,
+			Some(AddressOrAddresses::Address(address)) => address == &log.address,
+			Some(AddressOrAddresses::Addresses(addresses)) =>
+				addresses.is_empty() || addresses.contains(&log.address),
+		};
+
+		if !address_matches {
+			return false;
+		}
+
+		let Some(topics) = &self.topics else { return true };
+		topics.iter().enumerate().all(|(i, topic)| {
+			let Some(log_topic) = log.topics.get(i) else { return false };
+			match topic {
+				FilterTopic::Single(hash) => hash == log_topic,
+				FilterTopic::Multiple(hashes) => hashes.is_empty() || hashes.contains(log_topic),
+			}
+		})
+	}
+}
+
+#[test]
+fn subscription_kind_serialization() {
+	let kinds = [
+		(r#""newHeads""#, SubscriptionKind::NewHeads),
+		(r#""logs""#, SubscriptionKind::Logs),
+		(r#""newPendingTransactions""#, SubscriptionKind::NewPendingTransactions),
+	];
+
+	for (json_data, expected) in kinds {
+		let result: SubscriptionKind =
+			serde_json::from_str(json_data).expect("Deserialization should succeed");
+		assert_eq!(result, expected);
+	}
+}
+
+#[test]
+fn filter_matches_log() {
+	use alloc::vec;
+	use sp_core::H160;
+
+	let log = Log {
+		address: H160::from([1u8; 20]),
+		topics: vec![H256::from([1u8; 32]), H256::from([2u8; 32])],
+		..Default::default()
+	};
+
+	assert!(Filter::default().matches_log(&log));
+	assert!(Filter { address: Some(log.address.into()), ..Default::default() }.matches_log(&log));
+	assert!(!Filter { address: Some(H160::from([2u8; 20]).into()), ..Default::default() }
+		.matches_log(&log));
+	assert!(Filter {
+		address: Some(vec![H160::from([2u8; 20]), log.address].into()),
+		..Default::default()
+	}
+	.matches_log(&log));
+
+	// Topics are matched by position.
+	assert!(Filter {
+		topics: Some(vec![FilterTopic::Single(log.topics[0]), FilterTopic::Single(log.topics[1])]),
+		..Default::default()
+	}
+	.matches_log(&log));
+	assert!(!Filter {
+		topics: Some(vec![FilterTopic::Single(log.topics[1])]),
+		..Default::default()
+	}
+	.matches_log(&log));
+
+	// Any of the topics in a `Multiple` entry matches.
+	assert!(Filter {
+		topics: Some(vec![FilterTopic::Multiple(vec![H256::zero(), log.topics[0]])]),
+		..Default::default()
+	}
+	.matches_log(&log));
+
+	// The filter cannot require more topics than the log has.
+	assert!(!Filter {
+		topics: Some(vec![
+			FilterTopic::Single(log.topics[0]),
+			FilterTopic::Single(log.topics[1]),
+			FilterTopic::Single(H256::zero()),
+		]),
+		..Default::default()
+	}
+	.matches_log(&log));
+}
@@ -300,8 +300,17 @@ pub trait EthExtra {
 		})?;
 
 		let signer = <Self::Config as Config>::AddressMapper::to_fallback_account_id(&signer_addr);
-		let GenericTransaction { nonce, chain_id, to, value, input, gas, gas_price, .. } =
-			GenericTransaction::from_signed(tx, crate::GAS_PRICE.into(), None);
+		let GenericTransaction {
+			nonce,
+			chain_id,
+			to,
+			value,
+			input,
+			gas,
+			gas_price,
+			access_list,
+			..
+		} = GenericTransaction::from_signed(tx, crate::GAS_PRICE.into(), None);
 
 		let Some(gas) = gas else {
 			log::debug!(target: LOG_TARGET, "No gas provided");
@@ -323,6 +332,11 @@ pub trait EthExtra {
 		})?;
 
 		let data = input.to_vec();
+		let access_list =
+			crate::access_list::slots_from_access_list(access_list).ok_or_else(|| {
+				log::debug!(target: LOG_TARGET, "Access list is too long");
+				InvalidTransaction::Call
+			})?;
 
 		let (gas_limit, storage_deposit_limit) =
 			<Self::Config as Config>::EthGasEncoder::decode(gas).ok_or_else(|| {
@@ -348,12 +362,13 @@ pub trait EthExtra {
 
 				call
 			} else {
-				crate::Call::call::<Self::Config> {
+				crate::Call::eth_call::<Self::Config> {
 					dest,
 					value,
 					gas_limit,
 					storage_deposit_limit,
 					data,
+					access_list,
 				}
 				.into()
 			}
@@ -369,14 +384,26 @@ pub trait EthExtra {
 				return Err(InvalidTransaction::Call);
 			};
 
-			crate::Call::eth_instantiate_with_code::<Self::Config> {
-				value,
-				gas_limit,
-				storage_deposit_limit,
-				code: code.to_vec(),
-				data: data.to_vec(),
+			if access_list.is_empty() {
+				crate::Call::eth_instantiate_with_code::<Self::Config> {
+					value,
+					gas_limit,
+					storage_deposit_limit,
+					code: code.to_vec(),
+					data: data.to_vec(),
+				}
+				.into()
+			} else {
+				crate::Call::eth_instantiate_with_code_and_access_list::<Self::Config> {
+					value,
+					gas_limit,
+					storage_deposit_limit,
+					code: code.to_vec(),
+					data: data.to_vec(),
+					access_list,
+				}
+				.into()
 			}
-			.into()
 		};
 
 		let mut info = call.get_dispatch_info();
@@ -604,12 +631,44 @@ mod test {
 
 		assert_eq!(
 			call,
-			crate::Call::call::<Test> {
+			crate::Call::eth_call::<Test> {
+				dest: tx.to.unwrap(),
+				value: tx.value.unwrap_or_default().as_u64(),
+				data: tx.input.to_vec(),
+				gas_limit,
+				storage_deposit_limit,
+				access_list: Default::default(),
+			}
+			.into()
+		);
+	}
+
+	#[test]
+	fn check_eth_transact_access_list_works() {
+		let address = H160::from([2u8; 20]);
+		let storage_keys = vec![H256::from([3u8; 32]), H256::from([4u8; 32])];
+		let mut builder = UncheckedExtrinsicBuilder::call_with(H160::from([1u8; 20]));
+		builder.tx.access_list =
+			Some(vec![AccessListEntry { address, storage_keys: storage_keys.clone() }]);
+
+		let (call, _, tx) = builder.check().unwrap();
+		let (gas_limit, storage_deposit_limit) =
+			<<Test as Config>::EthGasEncoder as GasEncoder<_>>::decode(tx.gas.unwrap()).unwrap();
+
+		assert_eq!(
+			call,
+			crate::Call::eth_call::<Test> {
 				dest: tx.to.unwrap(),
 				value: tx.value.unwrap_or_default().as_u64(),
 				data: tx.input.to_vec(),
 				gas_limit,
-				storage_deposit_limit
+				storage_deposit_limit,
+				access_list: storage_keys
+					.into_iter()
+					.map(|key| (address, key))
+					.collect::<Vec<_>>()
+					.try_into()
+					.unwrap(),
 			}
 			.into()
 		);
@@ -631,7 +690,7 @@ mod test {
 				code,
 				data,
 				gas_limit,
-				storage_deposit_limit
+				storage_deposit_limit,
 			}
 			.into()
 		);
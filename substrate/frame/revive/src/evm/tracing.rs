@@ -15,7 +15,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 use crate::{
-	evm::{CallTrace, Trace},
+	evm::{AccessList, CallTrace, FourByteTrace, StructLoggerTrace, Trace},
 	tracing::Tracing,
 	BalanceOf, Bounded, Config, MomentOf, Weight,
 };
@@ -27,6 +27,15 @@ pub use call_tracing::*;
 mod prestate_tracing;
 pub use prestate_tracing::*;
 
+mod opcode_tracing;
+pub use opcode_tracing::*;
+
+mod four_byte_tracing;
+pub use four_byte_tracing::*;
+
+mod access_list_tracing;
+pub use access_list_tracing::*;
+
 /// A composite tracer.
 #[derive(derive_more::From, Debug)]
 pub enum Tracer<T> {
@@ -34,6 +43,12 @@ pub enum Tracer<T> {
 	CallTracer(CallTracer<U256, fn(Weight) -> U256>),
 	/// A tracer that traces the prestate.
 	PrestateTracer(PrestateTracer<T>),
+	/// A tracer that logs every executed instruction.
+	StructLogger(StructLogger<fn(Weight) -> U256>),
+	/// A tracer that collects the selectors of the calls.
+	FourByteTracer(FourByteTracer),
+	/// A tracer that collects the accessed addresses and storage slots.
+	AccessListTracer(AccessListTracer),
 }
 
 impl<T: Config> Tracer<T>
@@ -48,6 +63,9 @@ where
 		match self {
 			Tracer::CallTracer(_) => CallTrace::default().into(),
 			Tracer::PrestateTracer(tracer) => tracer.empty_trace().into(),
+			Tracer::StructLogger(_) => StructLoggerTrace::default().into(),
+			Tracer::FourByteTracer(_) => FourByteTrace::default().into(),
+			Tracer::AccessListTracer(_) => AccessList::default().into(),
 		}
 	}
 
@@ -56,6 +74,9 @@ where
 		match self {
 			Tracer::CallTracer(inner) => inner as &mut dyn Tracing,
 			Tracer::PrestateTracer(inner) => inner as &mut dyn Tracing,
+			Tracer::StructLogger(inner) => inner as &mut dyn Tracing,
+			Tracer::FourByteTracer(inner) => inner as &mut dyn Tracing,
+			Tracer::AccessListTracer(inner) => inner as &mut dyn Tracing,
 		}
 	}
 
@@ -64,6 +85,9 @@ where
 		match self {
 			Tracer::CallTracer(inner) => inner.collect_trace().map(Trace::Call),
 			Tracer::PrestateTracer(inner) => Some(inner.collect_trace().into()),
+			Tracer::StructLogger(inner) => inner.collect_trace().map(Trace::StructLogger),
+			Tracer::FourByteTracer(inner) => inner.collect_trace().map(Trace::FourByte),
+			Tracer::AccessListTracer(inner) => inner.collect_trace().map(Trace::AccessList),
 		}
 	}
 }
@@ -0,0 +1,162 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use crate::{
+	evm::{AccessList, AccessListEntry},
+	primitives::ExecReturnValue,
+	tracing::Tracing,
+	DispatchError, Key, Weight,
+};
+use alloc::{
+	collections::{BTreeMap, BTreeSet},
+	vec::Vec,
+};
+use sp_core::{H160, H256, U256};
+
+/// A tracer that collects the addresses and storage slots accessed by a call, in the format of
+/// an [EIP-2930](https://eips.ethereum.org/EIPS/eip-2930) access list.
+///
+/// Like geth, the sender and the recipient of the transaction are only included when some of
+/// their storage slots were accessed.
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct AccessListTracer {
+	/// The storage keys accessed per address.
+	accessed: BTreeMap<H160, BTreeSet<H256>>,
+	/// The sender and the recipient of the transaction.
+	excluded: BTreeSet<H160>,
+	/// The addresses of the active calls, the last one owns the storage being accessed.
+	stack: Vec<H160>,
+	/// Whether a call was traced.
+	traced: bool,
+}
+
+impl AccessListTracer {
+	/// Create a new [`AccessListTracer`] instance.
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Collect the trace and return it.
+	pub fn collect_trace(&mut self) -> Option<AccessList> {
+		let accessed = core::mem::take(&mut self.accessed);
+		let excluded = core::mem::take(&mut self.excluded);
+		self.stack.clear();
+
+		core::mem::take(&mut self.traced).then(|| {
+			accessed
+				.into_iter()
+				.filter(|(address, keys)| !keys.is_empty() || !excluded.contains(address))
+				.map(|(address, keys)| AccessListEntry {
+					address,
+					storage_keys: keys.into_iter().collect(),
+				})
+				.collect()
+		})
+	}
+
+	/// Record an access to the storage `key` of the current call.
+	fn record_storage(&mut self, key: &Key) {
+		// Ethereum storage keys are 32 bytes long, variable sized keys can't be declared.
+		let Key::Fix(key) = key else { return };
+		if let Some(address) = self.stack.last() {
+			self.accessed.entry(*address).or_default().insert(H256(*key));
+		}
+	}
+}
+
+impl Tracing for AccessListTracer {
+	fn enter_child_span(
+		&mut self,
+		from: H160,
+		to: H160,
+		_is_delegate_call: bool,
+		_is_read_only: bool,
+		_value: U256,
+		_input: &[u8],
+		_gas: Weight,
+	) {
+		if !self.traced {
+			self.traced = true;
+			self.excluded.extend([from, to]);
+		}
+
+		self.accessed.entry(to).or_default();
+		self.stack.push(to);
+	}
+
+	fn storage_read(&mut self, key: &Key, _value: Option<&[u8]>) {
+		self.record_storage(key);
+	}
+
+	fn storage_write(&mut self, key: &Key, _old_value: Option<Vec<u8>>, _new_value: Option<&[u8]>) {
+		self.record_storage(key);
+	}
+
+	fn exit_child_span(&mut self, _output: &ExecReturnValue, _gas_used: Weight) {
+		self.stack.pop();
+	}
+
+	fn exit_child_span_with_error(&mut self, _error: DispatchError, _gas_used: Weight) {
+		self.stack.pop();
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use alloc::vec;
+
+	fn enter(tracer: &mut AccessListTracer, from: H160, to: H160) {
+		tracer.enter_child_span(from, to, false, false, U256::zero(), &[], Weight::zero());
+	}
+
+	#[test]
+	fn access_list_tracer_works() {
+		let (sender, contract, callee, other) = (
+			H160::repeat_byte(1),
+			H160::repeat_byte(2),
+			H160::repeat_byte(3),
+			H160::repeat_byte(4),
+		);
+		let mut tracer = AccessListTracer::new();
+		assert_eq!(tracer.collect_trace(), None);
+
+		enter(&mut tracer, sender, contract);
+		tracer.storage_read(&Key::Fix([1u8; 32]), None);
+
+		// A nested call that accesses its own storage.
+		enter(&mut tracer, contract, callee);
+		tracer.storage_write(&Key::Fix([2u8; 32]), None, Some(&[0u8; 32]));
+		tracer.storage_read(&Key::try_from_var(vec![3u8; 8]).unwrap(), None);
+		tracer.exit_child_span(&Default::default(), Weight::zero());
+
+		// A call that does not access any storage.
+		enter(&mut tracer, contract, other);
+		tracer.exit_child_span_with_error(DispatchError::Other("failed"), Weight::zero());
+
+		tracer.storage_write(&Key::Fix([1u8; 32]), None, None);
+		tracer.exit_child_span(&Default::default(), Weight::zero());
+
+		assert_eq!(
+			tracer.collect_trace(),
+			Some(vec![
+				AccessListEntry { address: contract, storage_keys: vec![H256([1u8; 32])] },
+				AccessListEntry { address: callee, storage_keys: vec![H256([2u8; 32])] },
+				AccessListEntry { address: other, storage_keys: vec![] },
+			])
+		);
+	}
+}
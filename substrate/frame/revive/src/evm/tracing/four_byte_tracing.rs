@@ -0,0 +1,118 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use crate::{evm::FourByteTrace, tracing::Tracing, Code, Weight};
+use alloc::format;
+use sp_core::{H160, U256};
+
+/// A tracer that collects the function selectors of the calls, along with the size of their
+/// input.
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct FourByteTracer {
+	/// The number of calls per `<selector>-<input size>` key.
+	trace: FourByteTrace,
+	/// Whether the next call instantiates a contract.
+	is_instantiate: bool,
+	/// Whether a call was traced.
+	traced: bool,
+}
+
+impl FourByteTracer {
+	/// Create a new [`FourByteTracer`] instance.
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Collect the trace and return it.
+	pub fn collect_trace(&mut self) -> Option<FourByteTrace> {
+		let trace = core::mem::take(&mut self.trace);
+		core::mem::take(&mut self.traced).then_some(trace)
+	}
+}
+
+impl Tracing for FourByteTracer {
+	fn instantiate_code(&mut self, _code: &Code, _salt: Option<&[u8; 32]>) {
+		self.is_instantiate = true;
+	}
+
+	fn enter_child_span(
+		&mut self,
+		_from: H160,
+		_to: H160,
+		_is_delegate_call: bool,
+		_is_read_only: bool,
+		_value: U256,
+		input: &[u8],
+		_gas: Weight,
+	) {
+		self.traced = true;
+
+		// The input of a contract instantiation is the constructor data, without selector.
+		if core::mem::take(&mut self.is_instantiate) || input.len() < 4 {
+			return;
+		}
+
+		let key = format!(
+			"0x{:02x}{:02x}{:02x}{:02x}-{}",
+			input[0],
+			input[1],
+			input[2],
+			input[3],
+			input.len() - 4
+		);
+		*self.trace.entry(key).or_default() += 1;
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use alloc::{collections::BTreeMap, string::String, vec};
+
+	fn enter(tracer: &mut FourByteTracer, input: &[u8]) {
+		tracer.enter_child_span(
+			H160::zero(),
+			H160::zero(),
+			false,
+			false,
+			U256::zero(),
+			input,
+			Weight::zero(),
+		);
+	}
+
+	#[test]
+	fn four_byte_tracer_works() {
+		let mut tracer = FourByteTracer::new();
+		assert_eq!(tracer.collect_trace(), None);
+
+		enter(&mut tracer, &[0x12, 0x34, 0x56, 0x78, 0, 0]);
+		enter(&mut tracer, &[0x12, 0x34, 0x56, 0x78, 1, 1]);
+		enter(&mut tracer, &[0xaa, 0xbb, 0xcc, 0xdd]);
+		enter(&mut tracer, &[0x01]);
+
+		tracer.instantiate_code(&Code::Upload(vec![]), None);
+		enter(&mut tracer, &[0x12, 0x34, 0x56, 0x78]);
+
+		assert_eq!(
+			tracer.collect_trace(),
+			Some(BTreeMap::from([
+				(String::from("0x12345678-2"), 2),
+				(String::from("0xaabbccdd-0"), 1),
+			]))
+		);
+	}
+}
@@ -0,0 +1,305 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use crate::{
+	evm::{Bytes, StructLog, StructLoggerConfig, StructLoggerTrace},
+	primitives::ExecReturnValue,
+	tracing::Tracing,
+	DispatchError, Key, Weight,
+};
+use alloc::{collections::BTreeMap, format, string::ToString, vec, vec::Vec};
+use sp_core::{H160, U256};
+
+/// The state of a call tracked by the [`StructLogger`].
+#[derive(Default, Debug, Clone, PartialEq)]
+struct Frame {
+	/// The index of the last log recorded in this call.
+	last_log: Option<usize>,
+	/// The storage accessed by this call.
+	storage: BTreeMap<Bytes, Bytes>,
+	/// The data returned by the last call made by this call.
+	return_data: Bytes,
+}
+
+/// A tracer that logs every instruction executed by the contracts.
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct StructLogger<GasMapper> {
+	/// Map Weight to Gas equivalent.
+	gas_mapper: GasMapper,
+	/// The tracer configuration.
+	config: StructLoggerConfig,
+	/// The stack of active calls.
+	frames: Vec<Frame>,
+	/// The trace being built.
+	trace: StructLoggerTrace,
+	/// Whether the top-level call has completed.
+	completed: bool,
+}
+
+impl<GasMapper: Fn(Weight) -> U256> StructLogger<GasMapper> {
+	/// Create a new [`StructLogger`] instance.
+	pub fn new(config: StructLoggerConfig, gas_mapper: GasMapper) -> Self {
+		Self { gas_mapper, config, frames: Vec::new(), trace: Default::default(), completed: false }
+	}
+
+	/// Collect the trace and return it.
+	pub fn collect_trace(&mut self) -> Option<StructLoggerTrace> {
+		self.frames.clear();
+		let trace = core::mem::take(&mut self.trace);
+		core::mem::take(&mut self.completed).then_some(trace)
+	}
+
+	/// Map the weight to gas, saturating at `u64::MAX`.
+	fn gas(&self, weight: Weight) -> u64 {
+		(self.gas_mapper)(weight).try_into().unwrap_or(u64::MAX)
+	}
+
+	/// Record the storage access in the current call, and attach it to the last log.
+	fn record_storage(&mut self, key: &Key, value: Option<&[u8]>) {
+		if self.config.disable_storage {
+			return;
+		}
+
+		let Some(frame) = self.frames.last_mut() else { return };
+		// Like in the EVM, unset values are reported as zero.
+		let value = value.map(|v| v.to_vec()).unwrap_or_else(|| vec![0u8; 32]);
+		frame.storage.insert(key.unhashed().to_vec().into(), value.into());
+
+		if let Some(log) = frame.last_log.and_then(|index| self.trace.struct_logs.get_mut(index)) {
+			log.storage = Some(frame.storage.clone());
+		}
+	}
+
+	/// Pop the current call and record its result.
+	fn exit(&mut self, output: Bytes, failed: bool, gas_used: Weight) {
+		self.frames.pop();
+
+		match self.frames.last_mut() {
+			Some(parent) => parent.return_data = output,
+			None => {
+				self.trace.gas = self.gas(gas_used);
+				self.trace.failed = failed;
+				self.trace.return_value = output;
+				self.completed = true;
+			},
+		}
+	}
+}
+
+/// Split `memory` in 32 bytes words, omitting the trailing words that are zero.
+fn memory_words(memory: &[u8]) -> Vec<Bytes> {
+	let mut words = memory
+		.chunks(32)
+		.map(|chunk| {
+			let mut word = vec![0u8; 32];
+			word[..chunk.len()].copy_from_slice(chunk);
+			Bytes(word)
+		})
+		.collect::<Vec<_>>();
+	while words.last().is_some_and(|word| word.0.iter().all(|&byte| byte == 0)) {
+		words.pop();
+	}
+	words
+}
+
+impl<GasMapper: Fn(Weight) -> U256> Tracing for StructLogger<GasMapper> {
+	fn is_step_tracing(&self) -> bool {
+		true
+	}
+
+	fn enter_child_span(
+		&mut self,
+		_from: H160,
+		_to: H160,
+		_is_delegate_call: bool,
+		_is_read_only: bool,
+		_value: U256,
+		_input: &[u8],
+		_gas: Weight,
+	) {
+		self.frames.push(Frame::default());
+	}
+
+	fn step(
+		&mut self,
+		pc: u64,
+		opcode: &str,
+		gas_left: Weight,
+		registers: &[u64],
+		memory: &dyn Fn() -> Vec<u8>,
+	) {
+		let gas = self.gas(gas_left);
+		let depth = self.frames.len() as u32;
+		let Some(frame) = self.frames.last_mut() else { return };
+
+		// The cost of the previous instruction is only known once the next one is reached.
+		if let Some(log) = frame.last_log.and_then(|index| self.trace.struct_logs.get_mut(index)) {
+			log.gas_cost = log.gas.saturating_sub(gas);
+		}
+
+		if self.config.limit != 0 && self.trace.struct_logs.len() as u64 >= self.config.limit {
+			frame.last_log = None;
+			return;
+		}
+
+		self.trace.struct_logs.push(StructLog {
+			pc,
+			op: opcode.to_string(),
+			gas,
+			depth,
+			stack: (!self.config.disable_stack)
+				.then(|| registers.iter().map(|&value| U256::from(value)).collect()),
+			memory: self.config.enable_memory.then(|| memory_words(&memory())),
+			return_data: self.config.enable_return_data.then(|| frame.return_data.clone()),
+			..Default::default()
+		});
+		frame.last_log = Some(self.trace.struct_logs.len() - 1);
+	}
+
+	fn storage_read(&mut self, key: &Key, value: Option<&[u8]>) {
+		self.record_storage(key, value);
+	}
+
+	fn storage_write(&mut self, key: &Key, _old_value: Option<Vec<u8>>, new_value: Option<&[u8]>) {
+		self.record_storage(key, new_value);
+	}
+
+	fn exit_child_span(&mut self, output: &ExecReturnValue, gas_used: Weight) {
+		self.exit(output.data.clone().into(), output.did_revert(), gas_used);
+	}
+
+	fn exit_child_span_with_error(&mut self, error: DispatchError, gas_used: Weight) {
+		if let Some(log) = self
+			.frames
+			.last()
+			.and_then(|frame| frame.last_log)
+			.and_then(|index| self.trace.struct_logs.get_mut(index))
+		{
+			log.error = Some(match error {
+				DispatchError::Module(sp_runtime::ModuleError { message, .. }) =>
+					message.unwrap_or_default().to_string(),
+				_ => format!("{:?}", error),
+			});
+		}
+
+		self.exit(Default::default(), true, gas_used);
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn tracer(config: StructLoggerConfig) -> StructLogger<fn(Weight) -> U256> {
+		StructLogger::new(config, |weight| U256::from(weight.ref_time()))
+	}
+
+	fn no_memory() -> Vec<u8> {
+		unreachable!("memory is not enabled")
+	}
+
+	fn enter(tracer: &mut impl Tracing) {
+		tracer.enter_child_span(
+			H160::zero(),
+			H160::zero(),
+			false,
+			false,
+			U256::zero(),
+			&[],
+			Weight::zero(),
+		);
+	}
+
+	#[test]
+	fn struct_logger_records_steps() {
+		let mut tracer = tracer(Default::default());
+		enter(&mut tracer);
+		tracer.step(0, "load_imm", Weight::from_parts(100, 0), &[1, 2], &no_memory);
+		tracer.step(2, "ecalli", Weight::from_parts(90, 0), &[1, 2], &no_memory);
+		tracer.storage_write(&Key::Fix([1u8; 32]), None, Some(&[2u8; 32]));
+
+		// Nested call
+		enter(&mut tracer);
+		tracer.step(0, "trap", Weight::from_parts(50, 0), &[], &no_memory);
+		tracer.exit_child_span_with_error(DispatchError::Other("trapped"), Weight::zero());
+
+		tracer.step(4, "ret", Weight::from_parts(20, 0), &[], &no_memory);
+		tracer.exit_child_span(&Default::default(), Weight::from_parts(80, 0));
+
+		let trace = tracer.collect_trace().unwrap();
+		assert_eq!(trace.gas, 80);
+		assert!(!trace.failed);
+
+		let logs = trace
+			.struct_logs
+			.iter()
+			.map(|log| (log.pc, log.op.as_str(), log.gas, log.gas_cost, log.depth))
+			.collect::<Vec<_>>();
+		assert_eq!(
+			logs,
+			vec![
+				(0, "load_imm", 100, 10, 1),
+				(2, "ecalli", 90, 70, 1),
+				(0, "trap", 50, 0, 2),
+				(4, "ret", 20, 0, 1),
+			]
+		);
+
+		assert_eq!(trace.struct_logs[0].stack, Some(vec![U256::from(1), U256::from(2)]));
+		assert_eq!(trace.struct_logs[0].memory, None);
+		assert_eq!(trace.struct_logs[0].storage, None);
+		assert_eq!(
+			trace.struct_logs[1].storage,
+			Some(BTreeMap::from([(Bytes(vec![1u8; 32]), Bytes(vec![2u8; 32]))]))
+		);
+		assert_eq!(trace.struct_logs[2].error, Some("Other(\"trapped\")".to_string()));
+	}
+
+	#[test]
+	fn struct_logger_respects_config() {
+		let mut tracer = tracer(StructLoggerConfig {
+			disable_stack: true,
+			disable_storage: true,
+			enable_return_data: true,
+			enable_memory: true,
+			limit: 1,
+		});
+		let memory = || {
+			let mut memory = vec![0u8; 96];
+			memory[0] = 1;
+			memory[33] = 2;
+			memory
+		};
+		enter(&mut tracer);
+		tracer.step(0, "ecalli", Weight::from_parts(100, 0), &[1], &memory);
+		tracer.storage_read(&Key::Fix([1u8; 32]), None);
+		tracer.step(2, "ret", Weight::from_parts(90, 0), &[1], &memory);
+		tracer.exit_child_span(&Default::default(), Weight::from_parts(10, 0));
+
+		let trace = tracer.collect_trace().unwrap();
+		assert_eq!(trace.struct_logs.len(), 1);
+		assert_eq!(trace.struct_logs[0].gas_cost, 10);
+		assert_eq!(trace.struct_logs[0].stack, None);
+		assert_eq!(trace.struct_logs[0].storage, None);
+		assert_eq!(trace.struct_logs[0].return_data, Some(Bytes::default()));
+
+		// The trailing zero word is omitted.
+		let memory = trace.struct_logs[0].memory.clone().unwrap();
+		assert_eq!(memory.len(), 2);
+		assert_eq!(memory[0].0[0], 1);
+		assert_eq!(memory[1].0[1], 2);
+	}
+}
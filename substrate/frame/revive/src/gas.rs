@@ -285,6 +285,20 @@ impl<T: Config> GasMeter<T> {
 		self.engine_meter.fuel.try_into().map_err(|_| <Error<T>>::OutOfGas.into())
 	}
 
+	/// Returns how much gas is left if the executor has `engine_fuel` left.
+	///
+	/// Unlike [`Self::sync_from_executor`] this doesn't update the meter. It is used to report
+	/// the gas left while the executor is running.
+	pub fn gas_left_with_engine_fuel(&self, engine_fuel: polkavm::Gas) -> Weight {
+		let fuel = u64::try_from(engine_fuel).unwrap_or_default();
+		let consumed = self
+			.engine_meter
+			.fuel
+			.saturating_sub(fuel)
+			.saturating_mul(EngineMeter::<T>::ref_time_per_fuel());
+		self.gas_left.saturating_sub(Weight::from_parts(consumed, 0))
+	}
+
 	/// Turn this GasMeter into a DispatchResult that contains the actually used gas.
 	pub fn into_dispatch_result<R, E>(
 		self,
@@ -22,6 +22,7 @@
 
 extern crate alloc;
 
+mod access_list;
 mod address;
 mod benchmarking;
 mod call_builder;
@@ -43,9 +44,10 @@ pub mod tracing;
 pub mod weights;
 
 use crate::{
+	access_list::with_access_list,
 	evm::{
-		runtime::GAS_PRICE, CallTracer, GasEncoder, GenericTransaction, PrestateTracer, Trace,
-		Tracer, TracerType, TYPE_EIP1559,
+		runtime::GAS_PRICE, AccessListTracer, CallTracer, FourByteTracer, GasEncoder,
+		GenericTransaction, PrestateTracer, StructLogger, Trace, Tracer, TracerType, TYPE_EIP1559,
 	},
 	exec::{AccountIdOf, ExecError, Executable, Key, Stack as ExecStack},
 	gas::GasMeter,
@@ -84,6 +86,7 @@ use sp_runtime::{
 };
 
 pub use crate::{
+	access_list::AccessListSlots,
 	address::{
 		create1, create2, is_eth_derived, AccountId32Mapper, AddressMapper, TestAccountMapper,
 	},
@@ -744,6 +747,49 @@ pub mod pallet {
 			dispatch_result(output.result, output.gas_consumed, T::WeightInfo::call())
 		}
 
+		/// Same as [`Self::call`], but intended to be dispatched **only** by an EVM transaction
+		/// through the EVM compatibility layer.
+		///
+		/// The storage slots declared in the `access_list` of the transaction are charged upfront
+		/// and their first access is then warm. See [`crate::access_list`].
+		#[pallet::call_index(11)]
+		#[pallet::weight(
+			T::WeightInfo::call()
+			.saturating_add(*gas_limit)
+			.saturating_add(crate::access_list::weight::<T>(access_list))
+		)]
+		pub fn eth_call(
+			origin: OriginFor<T>,
+			dest: H160,
+			#[pallet::compact] value: BalanceOf<T>,
+			gas_limit: Weight,
+			#[pallet::compact] storage_deposit_limit: BalanceOf<T>,
+			data: Vec<u8>,
+			access_list: AccessListSlots,
+		) -> DispatchResultWithPostInfo {
+			let mut output = with_access_list(&access_list, || {
+				Self::bare_call(
+					origin,
+					dest,
+					value,
+					gas_limit,
+					DepositLimit::Balance(storage_deposit_limit),
+					data,
+				)
+			});
+
+			if let Ok(return_value) = &output.result {
+				if return_value.did_revert() {
+					output.result = Err(<Error<T>>::ContractReverted.into());
+				}
+			}
+			dispatch_result(
+				output.result,
+				output.gas_consumed,
+				T::WeightInfo::call().saturating_add(crate::access_list::weight::<T>(&access_list)),
+			)
+		}
+
 		/// Instantiates a contract from a previously deployed vm binary.
 		///
 		/// This function is identical to [`Self::instantiate_with_code`] but without the
@@ -870,18 +916,50 @@ pub mod pallet {
 			code: Vec<u8>,
 			data: Vec<u8>,
 		) -> DispatchResultWithPostInfo {
-			let code_len = code.len() as u32;
-			let data_len = data.len() as u32;
-			let mut output = Self::bare_instantiate(
+			Self::eth_instantiate_with_code_and_access_list(
 				origin,
 				value,
 				gas_limit,
-				DepositLimit::Balance(storage_deposit_limit),
-				Code::Upload(code),
+				storage_deposit_limit,
+				code,
 				data,
-				None,
-				BumpNonce::No,
-			);
+				Default::default(),
+			)
+		}
+
+		/// Same as [`Self::eth_instantiate_with_code`], with the `access_list` of the transaction.
+		///
+		/// The storage slots declared in the `access_list` are charged upfront and their first
+		/// access is then warm. See [`crate::access_list`].
+		#[pallet::call_index(12)]
+		#[pallet::weight(
+			T::WeightInfo::instantiate_with_code(code.len() as u32, data.len() as u32)
+			.saturating_add(*gas_limit)
+			.saturating_add(crate::access_list::weight::<T>(access_list))
+		)]
+		pub fn eth_instantiate_with_code_and_access_list(
+			origin: OriginFor<T>,
+			#[pallet::compact] value: BalanceOf<T>,
+			gas_limit: Weight,
+			#[pallet::compact] storage_deposit_limit: BalanceOf<T>,
+			code: Vec<u8>,
+			data: Vec<u8>,
+			access_list: AccessListSlots,
+		) -> DispatchResultWithPostInfo {
+			let code_len = code.len() as u32;
+			let data_len = data.len() as u32;
+			let mut output = with_access_list(&access_list, || {
+				Self::bare_instantiate(
+					origin,
+					value,
+					gas_limit,
+					DepositLimit::Balance(storage_deposit_limit),
+					Code::Upload(code),
+					data,
+					None,
+					BumpNonce::No,
+				)
+			});
 
 			if let Ok(retval) = &output.result {
 				if retval.result.did_revert() {
@@ -891,7 +969,8 @@ pub mod pallet {
 			dispatch_result(
 				output.result.map(|result| result.result),
 				output.gas_consumed,
-				T::WeightInfo::instantiate_with_code(code_len, data_len),
+				T::WeightInfo::instantiate_with_code(code_len, data_len)
+					.saturating_add(crate::access_list::weight::<T>(&access_list)),
 			)
 		}
 
@@ -1236,6 +1315,9 @@ where
 		};
 
 		let input = tx.input.clone().to_vec();
+		let Some(access_list) = access_list::slots_from_access_list(tx.access_list.clone()) else {
+			return Err(EthTransactError::Message("Access list is too long".into()));
+		};
 
 		let extract_error = |err| {
 			if err == Error::<T>::TransferFailed.into() ||
@@ -1282,14 +1364,16 @@ where
 					(result, dispatch_call)
 				} else {
 					// Dry run the call.
-					let result = crate::Pallet::<T>::bare_call(
-						T::RuntimeOrigin::signed(origin),
-						dest,
-						native_value,
-						gas_limit,
-						storage_deposit_limit,
-						input.clone(),
-					);
+					let result = with_access_list(&access_list, || {
+						crate::Pallet::<T>::bare_call(
+							T::RuntimeOrigin::signed(origin),
+							dest,
+							native_value,
+							gas_limit,
+							storage_deposit_limit,
+							input.clone(),
+						)
+					});
 
 					let data = match result.result {
 						Ok(return_value) => {
@@ -1315,12 +1399,13 @@ where
 						result.gas_required,
 						result.storage_deposit,
 					);
-					let dispatch_call: <T as Config>::RuntimeCall = crate::Call::<T>::call {
+					let dispatch_call: <T as Config>::RuntimeCall = crate::Call::<T>::eth_call {
 						dest,
 						value: native_value,
 						gas_limit,
 						storage_deposit_limit,
 						data: input.clone(),
+						access_list,
 					}
 					.into();
 					(result, dispatch_call)
@@ -1342,16 +1427,18 @@ where
 				};
 
 				// Dry run the call.
-				let result = crate::Pallet::<T>::bare_instantiate(
-					T::RuntimeOrigin::signed(origin),
-					native_value,
-					gas_limit,
-					storage_deposit_limit,
-					Code::Upload(code.to_vec()),
-					data.to_vec(),
-					None,
-					BumpNonce::No,
-				);
+				let result = with_access_list(&access_list, || {
+					crate::Pallet::<T>::bare_instantiate(
+						T::RuntimeOrigin::signed(origin),
+						native_value,
+						gas_limit,
+						storage_deposit_limit,
+						Code::Upload(code.to_vec()),
+						data.to_vec(),
+						None,
+						BumpNonce::No,
+					)
+				});
 
 				let returned_data = match result.result {
 					Ok(return_value) => {
@@ -1378,7 +1465,7 @@ where
 					result.gas_required,
 					result.storage_deposit,
 				);
-				let dispatch_call: <T as Config>::RuntimeCall =
+				let dispatch_call: <T as Config>::RuntimeCall = if access_list.is_empty() {
 					crate::Call::<T>::eth_instantiate_with_code {
 						value: native_value,
 						gas_limit,
@@ -1386,7 +1473,18 @@ where
 						code: code.to_vec(),
 						data: data.to_vec(),
 					}
-					.into();
+					.into()
+				} else {
+					crate::Call::<T>::eth_instantiate_with_code_and_access_list {
+						value: native_value,
+						gas_limit,
+						storage_deposit_limit,
+						code: code.to_vec(),
+						data: data.to_vec(),
+						access_list,
+					}
+					.into()
+				};
 				(result, dispatch_call)
 			},
 		};
@@ -1486,6 +1584,13 @@ where
 			.into(),
 			TracerType::PrestateTracer(config) =>
 				PrestateTracer::new(config.unwrap_or_default()).into(),
+			TracerType::StructLogger(config) => StructLogger::new(
+				config.unwrap_or_default(),
+				Self::evm_gas_from_weight as fn(Weight) -> U256,
+			)
+			.into(),
+			TracerType::FourByteTracer => FourByteTracer::new().into(),
+			TracerType::AccessListTracer => AccessListTracer::new().into(),
 		}
 	}
 
@@ -26,6 +26,7 @@
 //! Use `alloy` through our re-export in this module to implement Eth ABI.
 
 mod builtin;
+mod dispatch;
 
 mod tests;
 
@@ -37,6 +38,10 @@ pub use crate::{
 	AddressMapper,
 };
 pub use alloy_core as alloy;
+pub use dispatch::{
+	charge_read, dispatch, dispatch_with_refund, evm_to_native, native_to_evm, signed_caller,
+	signed_origin,
+};
 pub use sp_core::{H160, H256, U256};
 
 use crate::{
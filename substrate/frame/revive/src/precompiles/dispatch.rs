@@ -0,0 +1,114 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Helpers for pre-compiles that dispatch calls of other pallets on behalf of their caller.
+
+use super::{alloy::primitives::U256, Error, Ext};
+use crate::{Config, Error as CrateError};
+use alloc::vec::Vec;
+use codec::MaxEncodedLen;
+use frame_support::{
+	dispatch::{extract_actual_weight, DispatchInfo, DispatchResultWithPostInfo},
+	traits::Get,
+	weights::Weight,
+};
+use frame_system::{pallet_prelude::OriginFor, RawOrigin};
+use sp_runtime::DispatchResult;
+
+const ERR_INVALID_CALLER: &str = "Invalid caller";
+const ERR_BALANCE_CONVERSION_FAILED: &str = "Balance conversion failed";
+
+/// Get the account of the caller, which must be allowed to change the state.
+///
+/// Fails if the pre-compile was called in a read only context or by root.
+pub fn signed_caller<T: Config>(env: &mut impl Ext<T = T>) -> Result<T::AccountId, Error> {
+	if env.is_read_only() {
+		return Err(CrateError::<T>::StateChangeDenied.into());
+	}
+	env.caller()
+		.account_id()
+		.cloned()
+		.map_err(|_| Error::Revert(ERR_INVALID_CALLER.into()))
+}
+
+/// Same as [`signed_caller`] but returns the signed origin of the caller.
+pub fn signed_origin<T: Config>(env: &mut impl Ext<T = T>) -> Result<OriginFor<T>, Error> {
+	signed_caller(env).map(|who| RawOrigin::Signed(who).into())
+}
+
+/// Charge `weight` and dispatch `call` from the signed origin of the caller.
+pub fn dispatch<T: Config>(
+	env: &mut impl Ext<T = T>,
+	weight: Weight,
+	call: impl FnOnce(OriginFor<T>) -> DispatchResult,
+) -> Result<Vec<u8>, Error> {
+	env.charge(weight)?;
+	call(signed_origin(env)?)?;
+	Ok(Vec::new())
+}
+
+/// Same as [`dispatch`] but refunds the weight the call reports as unused.
+///
+/// `weight` must be the worst case weight of `call`.
+pub fn dispatch_with_refund<T: Config>(
+	env: &mut impl Ext<T = T>,
+	weight: Weight,
+	call: impl FnOnce(OriginFor<T>) -> DispatchResultWithPostInfo,
+) -> Result<Vec<u8>, Error> {
+	let charged = env.charge(weight)?;
+	let result = call(signed_origin(env)?);
+
+	let info = DispatchInfo {
+		call_weight: weight,
+		extension_weight: Weight::zero(),
+		..Default::default()
+	};
+	env.adjust_gas(charged, extract_actual_weight(&result, &info));
+
+	result.map(|_| Vec::new()).map_err(|error| error.error.into())
+}
+
+/// Convert an amount of the native token from ETH decimals to native decimals.
+///
+/// Contracts see native balances with the decimals of ETH, see [`Config::NativeToEthRatio`].
+/// Fails if `value` can not be represented in native decimals.
+pub fn evm_to_native<T: Config>(value: U256) -> Result<U256, Error> {
+	let ratio = U256::from(T::NativeToEthRatio::get());
+	let (native, remainder) = value.div_rem(ratio);
+	if !remainder.is_zero() {
+		return Err(CrateError::<T>::DecimalPrecisionLoss.into());
+	}
+	Ok(native)
+}
+
+/// Convert an amount of the native token from native decimals to ETH decimals.
+///
+/// The inverse of [`evm_to_native`].
+pub fn native_to_evm<T: Config>(value: U256) -> Result<U256, Error> {
+	value
+		.checked_mul(U256::from(T::NativeToEthRatio::get()))
+		.ok_or_else(|| Error::Revert(ERR_BALANCE_CONVERSION_FAILED.into()))
+}
+
+/// Charge the weight of reading a single storage value of type `V`.
+pub fn charge_read<T: Config, V: MaxEncodedLen>(env: &mut impl Ext<T = T>) -> Result<(), Error> {
+	let weight = T::DbWeight::get()
+		.reads(1)
+		.saturating_add(Weight::from_parts(0, V::max_encoded_len() as u64));
+	env.charge(weight)?;
+	Ok(())
+}
@@ -21,6 +21,7 @@ mod precompiles;
 use self::test_utils::{ensure_stored, expected_deposit};
 use crate::{
 	self as pallet_revive,
+	access_list::with_access_list,
 	address::{create1, create2, AddressMapper},
 	evm::{runtime::GAS_PRICE, CallTrace, CallTracer, CallType, GenericTransaction},
 	exec::Key,
@@ -39,7 +40,7 @@ use crate::test_utils::builder::Contract;
 use assert_matches::assert_matches;
 use codec::Encode;
 use frame_support::{
-	assert_err, assert_err_ignore_postinfo, assert_noop, assert_ok, derive_impl,
+	assert_err, assert_err_ignore_postinfo, assert_noop, assert_ok, bounded_vec, derive_impl,
 	pallet_prelude::EnsureOrigin,
 	parameter_types,
 	storage::child,
@@ -716,6 +717,35 @@ fn storage_work() {
 	});
 }
 
+#[test]
+fn access_list_makes_storage_warm() {
+	let (code, _code_hash) = compile_module("storage").unwrap();
+
+	ExtBuilder::default().build().execute_with(|| {
+		let _ = <Test as Config>::Currency::set_balance(&ALICE, 1_000_000);
+		let min_balance = Contracts::min_balance();
+		let Contract { addr, .. } = builder::bare_instantiate(Code::Upload(code))
+			.value(min_balance * 100)
+			.build_and_unwrap_contract();
+
+		let cold = builder::bare_call(addr).build();
+		assert!(cold.result.is_ok());
+
+		// Declaring the slot of another contract has no effect.
+		let access_list = bounded_vec![(H160::repeat_byte(0xff), H256::repeat_byte(1))];
+		let other = with_access_list(&access_list, || builder::bare_call(addr).build());
+		assert!(other.result.is_ok());
+		assert_eq!(other.gas_consumed, cold.gas_consumed);
+
+		// The contract only accesses the `[1u8; 32]` key.
+		let access_list = bounded_vec![(addr, H256::repeat_byte(1))];
+		let warm = with_access_list(&access_list, || builder::bare_call(addr).build());
+		assert!(warm.result.is_ok());
+		assert!(warm.gas_consumed.all_lte(cold.gas_consumed));
+		assert!(warm.gas_consumed.any_lt(cold.gas_consumed));
+	});
+}
+
 #[test]
 fn storage_max_value_limit() {
 	let (binary, _code_hash) = compile_module("storage_size").unwrap();
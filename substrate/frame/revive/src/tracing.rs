@@ -40,6 +40,13 @@ pub(crate) fn if_tracing<F: FnOnce(&mut (dyn Tracing + 'static))>(f: F) {
 	tracer::with(f);
 }
 
+/// Returns `true` if the active tracer wants to be notified of every executed instruction.
+///
+/// Always `false` in on-chain code, where tracing is never activated.
+pub(crate) fn is_step_tracing() -> bool {
+	tracer::with(|tracer| tracer.is_step_tracing()).unwrap_or(false)
+}
+
 /// Defines methods to trace contract interactions.
 pub trait Tracing {
 	/// Register an address that should be traced.
@@ -84,4 +91,26 @@ pub trait Tracing {
 
 	/// Called when a contract call terminates with an error
 	fn exit_child_span_with_error(&mut self, _error: DispatchError, _gas_left: Weight) {}
+
+	/// Returns `true` if [`Self::step`] should be called for every executed instruction.
+	///
+	/// Stepping through every instruction is slow, so it is only enabled for the tracers that
+	/// need it.
+	fn is_step_tracing(&self) -> bool {
+		false
+	}
+
+	/// Called before an instruction is executed
+	///
+	/// `memory` reads the memory of the contract. It is only called by the tracers that record
+	/// it, since reading the memory at every step is expensive.
+	fn step(
+		&mut self,
+		_pc: u64,
+		_opcode: &str,
+		_gas_left: Weight,
+		_registers: &[u64],
+		_memory: &dyn Fn() -> Vec<u8>,
+	) {
+	}
 }
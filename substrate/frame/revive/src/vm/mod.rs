@@ -33,11 +33,12 @@ use crate::{
 	gas::{GasMeter, Token},
 	limits,
 	storage::meter::Diff,
+	tracing::{if_tracing, is_step_tracing},
 	weights::WeightInfo,
 	AccountIdOf, BadOrigin, BalanceOf, CodeInfoOf, CodeVec, Config, Error, ExecError, HoldReason,
 	PristineCode, Weight, LOG_TARGET,
 };
-use alloc::vec::Vec;
+use alloc::{format, vec::Vec};
 use codec::{Decode, Encode, MaxEncodedLen};
 use frame_support::{
 	dispatch::DispatchResult,
@@ -288,6 +289,8 @@ pub struct PreparedCall<'a, E: Ext> {
 	module: polkavm::Module,
 	instance: polkavm::RawInstance,
 	runtime: Runtime<'a, E, polkavm::RawInstance>,
+	/// The parsed program, only set when the tracer steps through every instruction.
+	step_tracing_blob: Option<polkavm::ProgramBlob>,
 }
 
 impl<'a, E: Ext> PreparedCall<'a, E>
@@ -298,6 +301,11 @@ where
 	pub fn call(mut self) -> ExecResult {
 		let exec_result = loop {
 			let interrupt = self.instance.run();
+			if let (Ok(polkavm::InterruptKind::Step), Some(blob)) =
+				(&interrupt, &self.step_tracing_blob)
+			{
+				trace_step(blob, &self.instance, self.runtime.ext().gas_meter());
+			}
 			if let Some(exec_result) =
 				self.runtime.handle_interrupt(interrupt, &self.module, &mut self.instance)
 			{
@@ -333,6 +341,30 @@ where
 	}
 }
 
+/// Report the instruction that is about to be executed to the tracer.
+fn trace_step<T: Config>(
+	blob: &polkavm::ProgramBlob,
+	instance: &polkavm::RawInstance,
+	gas_meter: &GasMeter<T>,
+) {
+	let Some(pc) = instance.program_counter() else { return };
+	let opcode = blob
+		.instructions_bounded_at(polkavm::program::ISA64_V1, pc)
+		.next()
+		.map(|instruction| format!("{:?}", instruction.opcode()))
+		.unwrap_or_else(|| "invalid".into());
+	let gas_left = gas_meter.gas_left_with_engine_fuel(instance.gas());
+	let registers = polkavm::Reg::ALL.map(|reg| instance.reg(reg));
+	let memory_map = instance.module().memory_map();
+	let read_memory = || {
+		instance
+			.read_memory(memory_map.rw_data_address(), memory_map.rw_data_size())
+			.unwrap_or_default()
+	};
+
+	if_tracing(|tracer| tracer.step(pc.0.into(), &opcode, gas_left, &registers, &read_memory));
+}
+
 impl<T: Config> ContractBlob<T> {
 	/// Compile and instantiate contract.
 	///
@@ -363,8 +395,15 @@ impl<T: Config> ContractBlob<T> {
 		module_config.set_gas_metering(Some(polkavm::GasMeteringKind::Sync));
 		module_config.set_allow_sbrk(false);
 		module_config.set_aux_data_size(aux_data_size);
-		let module = polkavm::Module::new(&engine, &module_config, self.code.into_inner().into())
-			.map_err(|err| {
+
+		let step_tracing = is_step_tracing();
+		module_config.set_step_tracing(step_tracing);
+		let code = self.code.into_inner();
+		let step_tracing_blob = step_tracing
+			.then(|| polkavm::ProgramBlob::parse(code.clone().into()).ok())
+			.flatten();
+
+		let module = polkavm::Module::new(&engine, &module_config, code.into()).map_err(|err| {
 			log::debug!(target: LOG_TARGET, "failed to create polkavm module: {err:?}");
 			Error::<T>::CodeRejected
 		})?;
@@ -385,7 +424,7 @@ impl<T: Config> ContractBlob<T> {
 		instance.set_gas(gas_limit_polkavm);
 		instance.prepare_call_untyped(entry_program_counter, &[]);
 
-		Ok(PreparedCall { module, instance, runtime })
+		Ok(PreparedCall { module, instance, runtime, step_tracing_blob })
 	}
 }
 
@@ -18,6 +18,7 @@
 //! Environment definition of the vm smart-contract runtime.
 
 use crate::{
+	access_list,
 	address::AddressMapper,
 	evm::runtime::GAS_PRICE,
 	exec::{ExecError, ExecResult, Ext, Key},
@@ -322,6 +323,10 @@ pub enum RuntimeCosts {
 	GetStorage(u32),
 	/// Weight of calling `seal_take_storage` for the given size.
 	TakeStorage(u32),
+	/// Weight of accessing a storage slot declared in the access list of the transaction.
+	///
+	/// See [`RuntimeCosts::warm`].
+	WarmStorage(Weight),
 	/// Weight of calling `seal_set_transient_storage` for the given storage item sizes.
 	SetTransientStorage { old_bytes: u32, new_bytes: u32 },
 	/// Weight of calling `seal_clear_transient_storage` per cleared byte.
@@ -430,6 +435,23 @@ macro_rules! cost_args {
 	(@replace_token $_in:tt) => { 0 };
 }
 
+impl RuntimeCosts {
+	/// Returns the costs of this storage access when the accessed slot is warm.
+	///
+	/// A warm slot was paid upfront by the access list of the transaction, hence the access is
+	/// not charged the overhead of reading from or writing to a full storage.
+	fn warm<T: Config>(self) -> Self {
+		use self::RuntimeCosts::*;
+		let overhead = match self {
+			SetStorage { .. } | ClearStorage(_) | TakeStorage(_) =>
+				access_list::write_overhead::<T>(),
+			ContainsStorage(_) | GetStorage(_) => access_list::read_overhead::<T>(),
+			_ => return self,
+		};
+		WarmStorage(<Self as Token<T>>::weight(&self).saturating_sub(overhead))
+	}
+}
+
 impl<T: Config> Token<T> for RuntimeCosts {
 	fn influence_lowest_gas_limit(&self) -> bool {
 		true
@@ -478,6 +500,7 @@ impl<T: Config> Token<T> for RuntimeCosts {
 			ContainsStorage(len) => cost_storage!(read, seal_contains_storage, len),
 			GetStorage(len) => cost_storage!(read, seal_get_storage, len),
 			TakeStorage(len) => cost_storage!(write, seal_take_storage, len),
+			WarmStorage(weight) => weight,
 			SetTransientStorage { new_bytes, old_bytes } => {
 				cost_storage!(write_transient, seal_set_transient_storage, new_bytes, old_bytes)
 			},
@@ -685,6 +708,24 @@ impl<'a, E: Ext, M: ?Sized + Memory<E::T>> Runtime<'a, E, M> {
 		self.ext.gas_meter_mut().adjust_gas(charged, actual_costs);
 	}
 
+	/// Adjust a previously charged storage access down to its actual amount.
+	///
+	/// Same as [`Self::adjust_gas`], but the first access to a slot declared in the access list
+	/// of the transaction is charged as warm.
+	fn adjust_storage_gas(
+		&mut self,
+		charged: ChargedAmount,
+		key: &Key,
+		actual_costs: RuntimeCosts,
+	) {
+		let actual_costs = if access_list::take_warm(&self.ext.address(), key) {
+			actual_costs.warm::<E::T>()
+		} else {
+			actual_costs
+		};
+		self.adjust_gas(charged, actual_costs);
+	}
+
 	/// Write the given buffer and its length to the designated locations in sandbox memory and
 	/// charge gas according to the token returned by `create_token`.
 	///
@@ -872,7 +913,7 @@ impl<'a, E: Ext, M: ?Sized + Memory<E::T>> Runtime<'a, E, M> {
 			self.ext.set_storage(&key, value, false)?
 		};
 
-		self.adjust_gas(charged, costs(value_len, write_outcome.old_len()));
+		self.adjust_storage_gas(charged, &key, costs(value_len, write_outcome.old_len()));
 		Ok(write_outcome.old_len_with_sentinel())
 	}
 
@@ -898,7 +939,7 @@ impl<'a, E: Ext, M: ?Sized + Memory<E::T>> Runtime<'a, E, M> {
 		} else {
 			self.ext.set_storage(&key, None, false)?
 		};
-		self.adjust_gas(charged, costs(outcome.old_len()));
+		self.adjust_storage_gas(charged, &key, costs(outcome.old_len()));
 		Ok(outcome.old_len_with_sentinel())
 	}
 
@@ -928,7 +969,7 @@ impl<'a, E: Ext, M: ?Sized + Memory<E::T>> Runtime<'a, E, M> {
 		};
 
 		if let Some(value) = outcome {
-			self.adjust_gas(charged, costs(value.len() as u32));
+			self.adjust_storage_gas(charged, &key, costs(value.len() as u32));
 
 			match read_mode {
 				StorageReadMode::FixedOutput32 => {
@@ -958,7 +999,7 @@ impl<'a, E: Ext, M: ?Sized + Memory<E::T>> Runtime<'a, E, M> {
 				},
 			}
 		} else {
-			self.adjust_gas(charged, costs(0));
+			self.adjust_storage_gas(charged, &key, costs(0));
 
 			match read_mode {
 				StorageReadMode::FixedOutput32 => {
@@ -998,7 +1039,7 @@ impl<'a, E: Ext, M: ?Sized + Memory<E::T>> Runtime<'a, E, M> {
 		} else {
 			self.ext.get_storage_size(&key)
 		};
-		self.adjust_gas(charged, costs(outcome.unwrap_or(0)));
+		self.adjust_storage_gas(charged, &key, costs(outcome.unwrap_or(0)));
 		Ok(outcome.unwrap_or(SENTINEL))
 	}
 
@@ -1028,7 +1069,7 @@ impl<'a, E: Ext, M: ?Sized + Memory<E::T>> Runtime<'a, E, M> {
 		};
 
 		if let crate::storage::WriteOutcome::Taken(value) = outcome {
-			self.adjust_gas(charged, costs(value.len() as u32));
+			self.adjust_storage_gas(charged, &key, costs(value.len() as u32));
 			self.write_sandbox_output(
 				memory,
 				out_ptr,
@@ -1039,7 +1080,7 @@ impl<'a, E: Ext, M: ?Sized + Memory<E::T>> Runtime<'a, E, M> {
 			)?;
 			Ok(ReturnErrorCode::Success)
 		} else {
-			self.adjust_gas(charged, costs(0));
+			self.adjust_storage_gas(charged, &key, costs(0));
 			Ok(ReturnErrorCode::KeyNotFound)
 		}
 	}
@@ -62,6 +62,24 @@ fn fill_schedule<T: Config>(when: BlockNumberFor<T>, n: u32) -> Result<(), &'sta
 	Ok(())
 }
 
+/// A time far enough in the future to schedule time-based tasks at.
+fn future_time<T: Config>() -> Moment {
+	T::UnixTime::now().as_secs().saturating_add(3600)
+}
+
+/// Add `n` named, recurring items to the time agenda of `when`.
+fn fill_time_schedule<T: Config>(when: Moment, n: u32) -> Result<(), &'static str> {
+	let origin: <T as Config>::PalletsOrigin = frame_system::RawOrigin::Root.into();
+	for i in 0..n {
+		let call = make_call::<T>(None);
+		let schedule = TimeSchedule::Every { start: when, period: (i + 100).into() };
+		let name = u32_to_name(i);
+		Pallet::<T>::do_schedule_at_time(Some(name), schedule, 0, origin.clone(), call)?;
+	}
+	ensure!(TimeAgenda::<T>::get(slot_of(when)).len() == n as usize, "didn't fill schedule");
+	Ok(())
+}
+
 fn u32_to_name(i: u32) -> TaskName {
 	i.using_encoded(blake2_256)
 }
@@ -506,6 +524,135 @@ mod benchmarks {
 		Ok(())
 	}
 
+	// `service_time_agendas` when no work is done.
+	#[benchmark]
+	fn service_time_agendas_base() {
+		let slot = slot_of(T::UnixTime::now().as_secs());
+		TimeIncompleteSince::<T>::put(slot);
+
+		#[block]
+		{
+			Pallet::<T>::service_time_agendas(&mut WeightMeter::new(), 0);
+		}
+
+		assert_eq!(TimeIncompleteSince::<T>::get(), Some(slot));
+	}
+
+	// `CronSchedule::next_after` for an expression which never matches, so that it searches as
+	// far as it can, mostly through days rather than skipping whole months.
+	#[benchmark]
+	fn next_cron_occurrence() -> Result<(), BenchmarkError> {
+		let cron =
+			time::CronSchedule::parse("0 0 31 2,4,6,9,11 *").ok_or(BenchmarkError::Weightless)?;
+		let next;
+
+		#[block]
+		{
+			next = cron.next_after(0);
+		}
+
+		assert_eq!(next, None);
+
+		Ok(())
+	}
+
+	#[benchmark]
+	fn schedule_at_time(
+		s: Linear<0, { T::MaxScheduledPerBlock::get() - 1 }>,
+	) -> Result<(), BenchmarkError> {
+		let when = future_time::<T>();
+		let priority = 0;
+		// Essentially a no-op call.
+		let call = Box::new(SystemCall::set_storage { items: vec![] }.into());
+
+		fill_time_schedule::<T>(when, s)?;
+
+		#[extrinsic_call]
+		_(RawOrigin::Root, TimeSchedule::At(when), priority, call);
+
+		ensure!(
+			TimeAgenda::<T>::get(slot_of(when)).len() == s as usize + 1,
+			"didn't add to schedule"
+		);
+
+		Ok(())
+	}
+
+	#[benchmark]
+	fn cancel_at_time(
+		s: Linear<1, { T::MaxScheduledPerBlock::get() }>,
+	) -> Result<(), BenchmarkError> {
+		let when = future_time::<T>();
+
+		fill_time_schedule::<T>(when, s)?;
+		let schedule_origin =
+			T::ScheduleOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+
+		#[extrinsic_call]
+		_(schedule_origin as SystemOrigin<T>, slot_of(when), 0);
+
+		ensure!(TimeLookup::<T>::get(u32_to_name(0)).is_none(), "didn't remove from lookup");
+		// Removed schedule is NONE
+		ensure!(
+			s == 1 || TimeAgenda::<T>::get(slot_of(when))[0].is_none(),
+			"didn't remove from schedule if more than 1 task scheduled for `when`"
+		);
+		ensure!(
+			s > 1 || TimeAgenda::<T>::get(slot_of(when)).len() == 0,
+			"remove from schedule if only 1 task scheduled for `when`"
+		);
+
+		Ok(())
+	}
+
+	#[benchmark]
+	fn schedule_named_at_time(
+		s: Linear<0, { T::MaxScheduledPerBlock::get() - 1 }>,
+	) -> Result<(), BenchmarkError> {
+		let id = u32_to_name(s);
+		let when = future_time::<T>();
+		let priority = 0;
+		// Essentially a no-op call.
+		let call = Box::new(SystemCall::set_storage { items: vec![] }.into());
+
+		fill_time_schedule::<T>(when, s)?;
+
+		#[extrinsic_call]
+		_(RawOrigin::Root, id, TimeSchedule::At(when), priority, call);
+
+		ensure!(
+			TimeAgenda::<T>::get(slot_of(when)).len() == s as usize + 1,
+			"didn't add to schedule"
+		);
+
+		Ok(())
+	}
+
+	#[benchmark]
+	fn cancel_named_at_time(
+		s: Linear<1, { T::MaxScheduledPerBlock::get() }>,
+	) -> Result<(), BenchmarkError> {
+		let when = future_time::<T>();
+
+		fill_time_schedule::<T>(when, s)?;
+
+		#[extrinsic_call]
+		_(RawOrigin::Root, u32_to_name(0));
+
+		ensure!(TimeLookup::<T>::get(u32_to_name(0)).is_none(), "didn't remove from lookup");
+		// Removed schedule is NONE
+		ensure!(
+			s == 1 || TimeAgenda::<T>::get(slot_of(when))[0].is_none(),
+			"didn't remove from schedule if more than 1 task scheduled for `when`"
+		);
+		ensure!(
+			s > 1 || TimeAgenda::<T>::get(slot_of(when)).len() == 0,
+			"remove from schedule if only 1 task scheduled for `when`"
+		);
+
+		Ok(())
+	}
+
 	impl_benchmark_test_suite! {
 		Pallet,
 		mock::new_test_ext(),
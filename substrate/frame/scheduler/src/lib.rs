@@ -33,6 +33,12 @@
 //! number or at a specified period. These scheduled runtime calls may be named or anonymous and may
 //! be canceled.
 //!
+//! Runtime calls can also be scheduled at a wall-clock time given by [`Config::UnixTime`], either
+//! once, at a fixed interval, or following a cron expression (see the [`time`] module). They are
+//! dispatched in the first block whose time is at or after each time they are due. Unlike block
+//! based tasks, time-based tasks are serviced in the [`on_poll`] hook, right after the inherents
+//! setting the time of the block, and they cannot be retried.
+//!
 //! __NOTE:__ Instead of using the filter contained in the origin to call `fn schedule`, scheduled
 //! runtime calls will be dispatched with the default filter for the origin: namely
 //! `frame_system::Config::BaseCallFilter` for all origin types (except root which will get no
@@ -72,6 +78,7 @@
 //!       index.
 //!
 //! [`on_initialize`]: frame_support::traits::Hooks::on_initialize
+//! [`on_poll`]: frame_support::traits::Hooks::on_poll
 
 // Ensure we're `no_std` when compiling for Wasm.
 #![cfg_attr(not(feature = "std"), no_std)]
@@ -83,6 +90,7 @@ pub mod migration;
 mod mock;
 #[cfg(test)]
 mod tests;
+pub mod time;
 pub mod weights;
 
 extern crate alloc;
@@ -96,7 +104,7 @@ use frame_support::{
 	traits::{
 		schedule::{self, DispatchTime, MaybeHashed},
 		Bounded, CallerTrait, EnsureOrigin, Get, IsType, OriginTrait, PalletInfoAccess,
-		PrivilegeCmp, QueryPreimage, StorageVersion, StorePreimage,
+		PrivilegeCmp, QueryPreimage, StorageVersion, StorePreimage, UnixTime,
 	},
 	weights::{Weight, WeightMeter},
 };
@@ -109,12 +117,15 @@ use sp_runtime::{
 };
 
 pub use pallet::*;
+use time::{slot_of, Moment, TimeSchedule, TimeSlot, MAX_SLOTS_PER_BLOCK};
 pub use weights::WeightInfo;
 
 /// Just a simple index for naming period tasks.
 pub type PeriodicIndex = u32;
 /// The location of a scheduled task that can be used to remove it.
 pub type TaskAddress<BlockNumber> = (BlockNumber, u32);
+/// The location of a time-based task that can be used to remove it.
+pub type TimeTaskAddress = (TimeSlot, u32);
 
 pub type CallOrHashOf<T> =
 	MaybeHashed<<T as Config>::RuntimeCall, <T as frame_system::Config>::Hash>;
@@ -193,6 +204,23 @@ where
 	}
 }
 
+/// Information regarding an item to be executed at some wall-clock time in the future.
+#[derive(Clone, RuntimeDebug, PartialEq, Eq, Encode, Decode, MaxEncodedLen, TypeInfo)]
+pub struct TimeScheduled<Name, Call, PalletsOrigin> {
+	/// The unique identity for this task, if there is one.
+	pub maybe_id: Option<Name>,
+	/// This task's priority.
+	pub priority: schedule::Priority,
+	/// The call to be dispatched.
+	pub call: Call,
+	/// The time at which the task is next due.
+	pub when: Moment,
+	/// The schedule giving `when`, and the times the task is due after it.
+	pub schedule: TimeSchedule,
+	/// The origin with which to dispatch the call.
+	pub origin: PalletsOrigin,
+}
+
 use crate::{Scheduled as ScheduledV3, Scheduled as ScheduledV2};
 
 pub type ScheduledV2Of<T> = ScheduledV2<
@@ -219,6 +247,9 @@ pub type ScheduledOf<T> = Scheduled<
 	<T as frame_system::Config>::AccountId,
 >;
 
+pub type TimeScheduledOf<T> =
+	TimeScheduled<TaskName, BoundedCallOf<T>, <T as Config>::PalletsOrigin>;
+
 pub(crate) trait MarginalWeightInfo: WeightInfo {
 	fn service_task(maybe_lookup_len: Option<usize>, named: bool, periodic: bool) -> Weight {
 		let base = Self::service_task_base();
@@ -276,6 +307,9 @@ pub mod pallet {
 			+ From<system::Call<Self>>;
 
 		/// The maximum weight that may be scheduled per block for any dispatchables.
+		///
+		/// Time-based tasks are serviced separately, after the inherents, with what the block-based
+		/// tasks of the same block left of this limit.
 		#[pallet::constant]
 		type MaximumWeight: Get<Weight>;
 
@@ -333,6 +367,12 @@ pub mod pallet {
 		/// swap block number providers on the fly, then please at least ensure that you do not run
 		/// any pallet migration in the same runtime upgrade.
 		type BlockNumberProvider: BlockNumberProvider;
+
+		/// The wall-clock time at which time-based tasks are due, usually `pallet_timestamp`.
+		///
+		/// It is read after the inherents of every block, so it must return the time of the
+		/// current block by then.
+		type UnixTime: UnixTime;
 	}
 
 	/// Block number at which the agenda began incomplete execution.
@@ -367,6 +407,31 @@ pub mod pallet {
 	pub type Lookup<T: Config> =
 		StorageMap<_, Twox64Concat, TaskName, TaskAddress<BlockNumberFor<T>>>;
 
+	/// Time slot from which the agendas of time-based tasks may still hold due tasks.
+	#[pallet::storage]
+	pub type TimeIncompleteSince<T: Config> = StorageValue<_, TimeSlot>;
+
+	/// Weight used by the block-based tasks of the current block.
+	///
+	/// The time-based tasks of the block are only given what is left of `MaximumWeight`.
+	#[pallet::storage]
+	#[pallet::whitelist_storage]
+	pub(super) type BlockTasksWeight<T: Config> = StorageValue<_, Weight, ValueQuery>;
+
+	/// Time-based items to be executed, indexed by the time slot that they are due in.
+	#[pallet::storage]
+	pub type TimeAgenda<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		TimeSlot,
+		BoundedVec<Option<TimeScheduledOf<T>>, T::MaxScheduledPerBlock>,
+		ValueQuery,
+	>;
+
+	/// Lookup from a name to the time slot and index of a time-based task.
+	#[pallet::storage]
+	pub type TimeLookup<T: Config> = StorageMap<_, Twox64Concat, TaskName, TimeTaskAddress>;
+
 	/// Events type.
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
@@ -401,6 +466,20 @@ pub mod pallet {
 		PermanentlyOverweight { task: TaskAddress<BlockNumberFor<T>>, id: Option<TaskName> },
 		/// Agenda is incomplete from `when`.
 		AgendaIncomplete { when: BlockNumberFor<T> },
+		/// Scheduled some time-based task, next due at `when`.
+		TimeTaskScheduled { task: TimeTaskAddress, when: Moment },
+		/// Canceled some time-based task.
+		TimeTaskCanceled { task: TimeTaskAddress },
+		/// Dispatched some time-based task.
+		TimeTaskDispatched { task: TimeTaskAddress, id: Option<TaskName>, result: DispatchResult },
+		/// The call for the provided hash was not found so the time-based task has been aborted.
+		TimeTaskCallUnavailable { task: TimeTaskAddress, id: Option<TaskName> },
+		/// The given time-based task was unable to recur since the agenda is full at that time.
+		TimeTaskRecurrenceFailed { task: TimeTaskAddress, id: Option<TaskName> },
+		/// The given time-based task can never be executed since it is overweight.
+		TimeTaskPermanentlyOverweight { task: TimeTaskAddress, id: Option<TaskName> },
+		/// Agenda of time-based tasks is incomplete from `slot`.
+		TimeAgendaIncomplete { slot: TimeSlot },
 	}
 
 	#[pallet::error]
@@ -415,6 +494,10 @@ pub mod pallet {
 		RescheduleNoChange,
 		/// Attempt to use a non-named function on a named task.
 		Named,
+		/// Given target time is in the past.
+		TargetTimeInPast,
+		/// The time schedule is malformed or never due.
+		InvalidTimeSchedule,
 	}
 
 	#[pallet::hooks]
@@ -424,9 +507,26 @@ pub mod pallet {
 			let now = T::BlockNumberProvider::current_block_number();
 			let mut weight_counter = WeightMeter::with_limit(T::MaximumWeight::get());
 			Self::service_agendas(&mut weight_counter, now, u32::MAX);
+			BlockTasksWeight::<T>::put(weight_counter.consumed());
 			weight_counter.consumed()
 		}
 
+		/// Execute the time-based calls which are due.
+		///
+		/// The inherents have been applied at this point, so the time of the block is known. The
+		/// calls share `MaximumWeight` with the block-based ones of the same block.
+		fn on_poll(_n: SystemBlockNumberFor<T>, weight: &mut WeightMeter) {
+			let left = T::MaximumWeight::get().saturating_sub(BlockTasksWeight::<T>::get());
+			let mut weight_counter = WeightMeter::with_limit(weight.remaining().min(left));
+			Self::service_time_agendas(&mut weight_counter, MAX_SLOTS_PER_BLOCK);
+			weight.consume(weight_counter.consumed());
+		}
+
+		fn on_finalize(_n: SystemBlockNumberFor<T>) {
+			// ensure we never go to trie with this value.
+			BlockTasksWeight::<T>::kill();
+		}
+
 		#[cfg(feature = "std")]
 		fn integrity_test() {
 			/// Calculate the maximum weight that a lookup of a given size can take.
@@ -667,6 +767,83 @@ pub mod pallet {
 			Self::deposit_event(Event::RetryCancelled { task, id: Some(id) });
 			Ok(())
 		}
+
+		/// Anonymously schedule a task at a wall-clock time.
+		///
+		/// The task is dispatched in the first block whose time is at or after each time it is due
+		/// according to `schedule`.
+		#[pallet::call_index(10)]
+		#[pallet::weight(
+			<T as Config>::WeightInfo::schedule_at_time(T::MaxScheduledPerBlock::get())
+				.saturating_add(Pallet::<T>::time_schedule_weight(schedule))
+		)]
+		pub fn schedule_at_time(
+			origin: OriginFor<T>,
+			schedule: TimeSchedule,
+			priority: schedule::Priority,
+			call: Box<<T as Config>::RuntimeCall>,
+		) -> DispatchResult {
+			T::ScheduleOrigin::ensure_origin(origin.clone())?;
+			let origin = <T as Config>::RuntimeOrigin::from(origin);
+			Self::do_schedule_at_time(
+				None,
+				schedule,
+				priority,
+				origin.caller().clone(),
+				T::Preimages::bound(*call)?,
+			)?;
+			Ok(())
+		}
+
+		/// Cancel an anonymously scheduled time-based task.
+		#[pallet::call_index(11)]
+		#[pallet::weight(<T as Config>::WeightInfo::cancel_at_time(T::MaxScheduledPerBlock::get()))]
+		pub fn cancel_at_time(origin: OriginFor<T>, slot: TimeSlot, index: u32) -> DispatchResult {
+			T::ScheduleOrigin::ensure_origin(origin.clone())?;
+			let origin = <T as Config>::RuntimeOrigin::from(origin);
+			Self::do_cancel_at_time(Some(origin.caller().clone()), (slot, index))?;
+			Ok(())
+		}
+
+		/// Schedule a named task at a wall-clock time.
+		///
+		/// The name stays the same as a recurring task moves from one time slot to the next.
+		#[pallet::call_index(12)]
+		#[pallet::weight(
+			<T as Config>::WeightInfo::schedule_named_at_time(T::MaxScheduledPerBlock::get())
+				.saturating_add(Pallet::<T>::time_schedule_weight(schedule))
+		)]
+		pub fn schedule_named_at_time(
+			origin: OriginFor<T>,
+			id: TaskName,
+			schedule: TimeSchedule,
+			priority: schedule::Priority,
+			call: Box<<T as Config>::RuntimeCall>,
+		) -> DispatchResult {
+			T::ScheduleOrigin::ensure_origin(origin.clone())?;
+			let origin = <T as Config>::RuntimeOrigin::from(origin);
+			Self::do_schedule_at_time(
+				Some(id),
+				schedule,
+				priority,
+				origin.caller().clone(),
+				T::Preimages::bound(*call)?,
+			)?;
+			Ok(())
+		}
+
+		/// Cancel a named time-based task.
+		#[pallet::call_index(13)]
+		#[pallet::weight(
+			<T as Config>::WeightInfo::cancel_named_at_time(T::MaxScheduledPerBlock::get())
+		)]
+		pub fn cancel_named_at_time(origin: OriginFor<T>, id: TaskName) -> DispatchResult {
+			T::ScheduleOrigin::ensure_origin(origin.clone())?;
+			let origin = <T as Config>::RuntimeOrigin::from(origin);
+			let task = TimeLookup::<T>::get(&id).ok_or(Error::<T>::NotFound)?;
+			Self::do_cancel_at_time(Some(origin.caller().clone()), task)?;
+			Ok(())
+		}
 	}
 }
 
@@ -1205,6 +1382,113 @@ impl<T: Config> Pallet<T> {
 		Retries::<T>::remove((when, index));
 		Ok(())
 	}
+
+	/// The weight of finding when `schedule` is due next, on top of the rest of the work.
+	fn time_schedule_weight(schedule: &TimeSchedule) -> Weight {
+		match schedule {
+			TimeSchedule::Cron(_) => T::WeightInfo::next_cron_occurrence(),
+			TimeSchedule::At(_) | TimeSchedule::Every { .. } => Weight::zero(),
+		}
+	}
+
+	fn place_time_task(
+		what: TimeScheduledOf<T>,
+	) -> Result<TimeTaskAddress, (DispatchError, TimeScheduledOf<T>)> {
+		let (slot, when, maybe_name) = (slot_of(what.when), what.when, what.maybe_id);
+		let mut agenda = TimeAgenda::<T>::get(slot);
+		let index = if (agenda.len() as u32) < T::MaxScheduledPerBlock::get() {
+			// will always succeed due to the above check.
+			let _ = agenda.try_push(Some(what));
+			agenda.len() as u32 - 1
+		} else {
+			if let Some(hole_index) = agenda.iter().position(|i| i.is_none()) {
+				agenda[hole_index] = Some(what);
+				hole_index as u32
+			} else {
+				return Err((DispatchError::Exhausted, what))
+			}
+		};
+		TimeAgenda::<T>::insert(slot, agenda);
+		let address = (slot, index);
+		if let Some(name) = maybe_name {
+			TimeLookup::<T>::insert(name, address)
+		}
+		Self::deposit_event(Event::TimeTaskScheduled { task: address, when });
+		Ok(address)
+	}
+
+	/// Remove trailing `None` items of the time agenda at `slot`. If all items are `None` remove
+	/// the agenda record entirely.
+	fn cleanup_time_agenda(slot: TimeSlot) {
+		let mut agenda = TimeAgenda::<T>::get(slot);
+		match agenda.iter().rposition(|i| i.is_some()) {
+			Some(i) if agenda.len() > i + 1 => {
+				agenda.truncate(i + 1);
+				TimeAgenda::<T>::insert(slot, agenda);
+			},
+			Some(_) => {},
+			None => {
+				TimeAgenda::<T>::remove(slot);
+			},
+		}
+	}
+
+	fn do_schedule_at_time(
+		maybe_id: Option<TaskName>,
+		schedule: TimeSchedule,
+		priority: schedule::Priority,
+		origin: T::PalletsOrigin,
+		call: BoundedCallOf<T>,
+	) -> Result<TimeTaskAddress, DispatchError> {
+		// ensure id it is unique
+		if maybe_id.is_some_and(|id| TimeLookup::<T>::contains_key(id)) {
+			return Err(Error::<T>::FailedToSchedule.into())
+		}
+
+		ensure!(schedule.is_valid(), Error::<T>::InvalidTimeSchedule);
+		let now = T::UnixTime::now().as_secs();
+		let when = schedule.next_after(now).ok_or(match schedule {
+			TimeSchedule::At(_) => Error::<T>::TargetTimeInPast,
+			TimeSchedule::Every { .. } | TimeSchedule::Cron(_) => Error::<T>::InvalidTimeSchedule,
+		})?;
+
+		let lookup_hash = call.lookup_hash();
+
+		let task = TimeScheduled { maybe_id, priority, call, when, schedule, origin };
+		let res = Self::place_time_task(task).map_err(|x| x.0)?;
+
+		if let Some(hash) = lookup_hash {
+			// Request the call to be made available.
+			T::Preimages::request(&hash);
+		}
+
+		Ok(res)
+	}
+
+	fn do_cancel_at_time(
+		origin: Option<T::PalletsOrigin>,
+		(slot, index): TimeTaskAddress,
+	) -> Result<(), DispatchError> {
+		let scheduled = TimeAgenda::<T>::try_mutate(slot, |agenda| {
+			agenda.get_mut(index as usize).map_or(
+				Ok(None),
+				|s| -> Result<Option<TimeScheduledOf<T>>, DispatchError> {
+					if let (Some(o), Some(s)) = (&origin, s.as_ref()) {
+						Self::ensure_privilege(o, &s.origin)?;
+					};
+					Ok(s.take())
+				},
+			)
+		})?;
+		let s = scheduled.ok_or(Error::<T>::NotFound)?;
+		T::Preimages::drop(&s.call);
+		if let Some(id) = s.maybe_id {
+			TimeLookup::<T>::remove(id);
+		}
+		Self::cleanup_time_agenda(slot);
+		Self::deposit_event(Event::TimeTaskCanceled { task: (slot, index) });
+		Ok(())
+	}
 }
 
 enum ServiceTaskError {
@@ -1418,6 +1702,213 @@ impl<T: Config> Pallet<T> {
 		}
 	}
 
+	/// Service up to `max` agendas of time-based tasks, starting from the earliest one which may
+	/// still hold due tasks.
+	fn service_time_agendas(weight: &mut WeightMeter, max: u32) {
+		if weight.try_consume(T::WeightInfo::service_time_agendas_base()).is_err() {
+			return
+		}
+
+		let now = T::UnixTime::now().as_secs();
+		let now_slot = slot_of(now);
+		let mut slot = TimeIncompleteSince::<T>::take().unwrap_or(now_slot);
+		let mut incomplete_since = None;
+
+		let mut count_down = max;
+		let service_agenda_base_weight =
+			T::WeightInfo::service_agenda_base(T::MaxScheduledPerBlock::get());
+		while count_down > 0 && slot <= now_slot && weight.can_consume(service_agenda_base_weight) {
+			if !Self::service_time_agenda(weight, now, slot) {
+				incomplete_since.get_or_insert(slot);
+			}
+			slot.saturating_inc();
+			count_down.saturating_dec();
+		}
+		if let Some(slot) = incomplete_since.or((slot <= now_slot).then_some(slot)) {
+			Self::deposit_event(Event::TimeAgendaIncomplete { slot });
+			TimeIncompleteSince::<T>::put(slot);
+		} else {
+			// Tasks due later within the current slot are still to be serviced.
+			TimeIncompleteSince::<T>::put(now_slot);
+		}
+	}
+
+	/// Service the tasks of the time agenda at `slot` which are due at `now`.
+	///
+	/// Returns `true` if all of them were serviced, `false` if some should be revisited at a later
+	/// block.
+	fn service_time_agenda(weight: &mut WeightMeter, now: Moment, slot: TimeSlot) -> bool {
+		let mut agenda = TimeAgenda::<T>::get(slot);
+		let mut ordered = agenda
+			.iter()
+			.enumerate()
+			.filter_map(|(index, maybe_item)| {
+				maybe_item
+					.as_ref()
+					.filter(|item| item.when <= now)
+					.map(|item| (index as u32, item.priority))
+			})
+			.collect::<Vec<_>>();
+		ordered.sort_by_key(|k| k.1);
+		let within_limit = weight
+			.try_consume(T::WeightInfo::service_agenda_base(agenda.len() as u32))
+			.is_ok();
+		debug_assert!(within_limit, "weight limit should have been checked in advance");
+
+		let mut postponed = 0;
+		// Recurring tasks are placed again once this agenda is written back, since they may be
+		// due later within the same slot.
+		let mut recurring = Vec::new();
+
+		for (agenda_index, _) in ordered {
+			let Some(task) = agenda[agenda_index as usize].take() else { continue };
+			let base_weight =
+				Self::time_task_weight(task.call.lookup_len().map(|x| x as usize), &task);
+			if !weight.can_consume(base_weight) {
+				postponed += 1;
+				agenda[agenda_index as usize] = Some(task);
+				break
+			}
+			let result =
+				Self::service_time_task(weight, now, slot, agenda_index, agenda.len() as u32, task);
+			agenda[agenda_index as usize] = match result {
+				Err((Unavailable, _)) => None,
+				Err((Overweight, task)) => {
+					postponed += 1;
+					task
+				},
+				Ok(maybe_next) => {
+					recurring.extend(maybe_next.map(|task| (agenda_index, task)));
+					None
+				},
+			};
+		}
+		if agenda.iter().any(Option::is_some) {
+			TimeAgenda::<T>::insert(slot, agenda);
+		} else {
+			TimeAgenda::<T>::remove(slot);
+		}
+
+		for (agenda_index, task) in recurring {
+			if let Err((_, task)) = Self::place_time_task(task) {
+				T::Preimages::drop(&task.call);
+				Self::deposit_event(Event::TimeTaskRecurrenceFailed {
+					task: (slot, agenda_index),
+					id: task.maybe_id,
+				});
+			}
+		}
+
+		postponed == 0
+	}
+
+	/// The weight of servicing a time-based `task`, not counting its dispatch.
+	fn time_task_weight(maybe_lookup_len: Option<usize>, task: &TimeScheduledOf<T>) -> Weight {
+		T::WeightInfo::service_task(
+			maybe_lookup_len,
+			task.maybe_id.is_some(),
+			task.schedule.is_recurring(),
+		)
+		.saturating_add(Self::time_schedule_weight(&task.schedule))
+	}
+
+	/// The most weight a time-based task in an agenda of `agenda_len` tasks can ever be given,
+	/// which is in a block without block-based tasks: `MaximumWeight` without the bases of
+	/// servicing the agendas.
+	fn time_task_weight_limit(agenda_len: u32) -> Weight {
+		T::MaximumWeight::get()
+			.saturating_sub(T::WeightInfo::service_agendas_base())
+			.saturating_sub(T::WeightInfo::service_agenda_base(0))
+			.saturating_sub(T::WeightInfo::service_time_agendas_base())
+			.saturating_sub(T::WeightInfo::service_agenda_base(agenda_len))
+	}
+
+	/// Service (i.e. execute) the given time-based task, being careful not to overflow the
+	/// `weight` counter.
+	///
+	/// Returns the task to be placed again when it recurs.
+	fn service_time_task(
+		weight: &mut WeightMeter,
+		now: Moment,
+		slot: TimeSlot,
+		agenda_index: u32,
+		agenda_len: u32,
+		mut task: TimeScheduledOf<T>,
+	) -> Result<Option<TimeScheduledOf<T>>, (ServiceTaskError, Option<TimeScheduledOf<T>>)> {
+		if let Some(ref id) = task.maybe_id {
+			TimeLookup::<T>::remove(id);
+		}
+
+		let (call, lookup_len) = match T::Preimages::peek(&task.call) {
+			Ok(c) => c,
+			Err(_) => {
+				Self::deposit_event(Event::TimeTaskCallUnavailable {
+					task: (slot, agenda_index),
+					id: task.maybe_id,
+				});
+
+				// It was not available when we needed it, so we don't need to have requested it
+				// anymore.
+				T::Preimages::drop(&task.call);
+
+				// We don't know why `peek` failed, thus we most account here for the "full weight".
+				let _ = weight.try_consume(Self::time_task_weight(
+					task.call.lookup_len().map(|x| x as usize),
+					&task,
+				));
+
+				return Err((Unavailable, None))
+			},
+		};
+
+		let task_weight = Self::time_task_weight(lookup_len.map(|x| x as usize), &task);
+		let _ = weight.try_consume(task_weight);
+		// Postponing a task which would not fit even in a block of its own would pin
+		// `TimeIncompleteSince` to its slot forever.
+		let needed = task_weight
+			.saturating_add(
+				T::WeightInfo::execute_dispatch_signed()
+					.max(T::WeightInfo::execute_dispatch_unsigned()),
+			)
+			.saturating_add(call.get_dispatch_info().call_weight);
+		let permanently_overweight = needed.any_gt(Self::time_task_weight_limit(agenda_len));
+
+		match Self::execute_dispatch(weight, task.origin.clone(), call) {
+			Err(()) if permanently_overweight => {
+				T::Preimages::drop(&task.call);
+				Self::deposit_event(Event::TimeTaskPermanentlyOverweight {
+					task: (slot, agenda_index),
+					id: task.maybe_id,
+				});
+				Err((Unavailable, None))
+			},
+			Err(()) => {
+				if let Some(id) = task.maybe_id {
+					TimeLookup::<T>::insert(id, (slot, agenda_index));
+				}
+				Err((Overweight, Some(task)))
+			},
+			Ok(result) => {
+				Self::deposit_event(Event::TimeTaskDispatched {
+					task: (slot, agenda_index),
+					id: task.maybe_id,
+					result,
+				});
+
+				match task.schedule.next_after(now) {
+					Some(when) => {
+						task.when = when;
+						Ok(Some(task))
+					},
+					None => {
+						T::Preimages::drop(&task.call);
+						Ok(None)
+					},
+				}
+			},
+		}
+	}
+
 	/// Make a dispatch to the given `call` from the given `origin`, ensuring that the `weight`
 	/// counter does not exceed its limit and that it is counted accurately (e.g. accounted using
 	/// post info if available).
@@ -22,7 +22,7 @@ use super::*;
 use crate as scheduler;
 use frame_support::{
 	derive_impl, ord_parameter_types, parameter_types,
-	traits::{ConstU32, Contains, EitherOfDiverse, EqualPrivilegeOnly},
+	traits::{ConstU32, Contains, EitherOfDiverse, EqualPrivilegeOnly, UnixTime},
 };
 use frame_system::{EnsureRoot, EnsureSignedBy};
 use sp_runtime::{BuildStorage, Perbill};
@@ -211,10 +211,37 @@ impl WeightInfo for TestWeightInfo {
 	fn cancel_retry_named() -> Weight {
 		Weight::from_parts(50, 0)
 	}
+	fn service_time_agendas_base() -> Weight {
+		Weight::from_parts(0b0000_0001, 0)
+	}
+	fn next_cron_occurrence() -> Weight {
+		Weight::from_parts(0b0000_1000, 0)
+	}
+	fn schedule_at_time(_s: u32) -> Weight {
+		Weight::from_parts(50, 0)
+	}
+	fn cancel_at_time(_s: u32) -> Weight {
+		Weight::from_parts(50, 0)
+	}
+	fn schedule_named_at_time(_s: u32) -> Weight {
+		Weight::from_parts(50, 0)
+	}
+	fn cancel_named_at_time(_s: u32) -> Weight {
+		Weight::from_parts(50, 0)
+	}
 }
 parameter_types! {
 	pub storage MaximumSchedulerWeight: Weight = Perbill::from_percent(80) *
 		BlockWeights::get().max_block;
+	/// The current time, in seconds since the Unix epoch.
+	pub static Now: u64 = 0;
+}
+
+pub struct MockUnixTime;
+impl UnixTime for MockUnixTime {
+	fn now() -> core::time::Duration {
+		core::time::Duration::from_secs(Now::get())
+	}
 }
 
 impl Config for Test {
@@ -229,6 +256,7 @@ impl Config for Test {
 	type WeightInfo = TestWeightInfo;
 	type Preimages = Preimage;
 	type BlockNumberProvider = frame_system::Pallet<Self>;
+	type UnixTime = MockUnixTime;
 }
 
 pub type LoggerCall = logger::Call<Test>;
@@ -24,11 +24,12 @@ use crate::mock::{
 };
 use frame_support::{
 	assert_err, assert_noop, assert_ok,
-	traits::{Contains, GetStorageVersion, OnInitialize, QueryPreimage, StorePreimage},
+	traits::{Contains, GetStorageVersion, OnInitialize, OnPoll, QueryPreimage, StorePreimage},
 	Hashable,
 };
 use sp_runtime::traits::Hash;
 use substrate_test_utils::assert_eq_uvec;
+use time::{slot_of, CronSchedule, TimeSchedule, MAX_SLOTS_PER_BLOCK};
 
 #[test]
 #[docify::export]
@@ -3194,3 +3195,481 @@ fn not_permanently_overweight_when_task_from_not_first_agenda() {
 		assert_eq!(IncompleteSince::<Test>::get(), Some(System::block_number() + 1));
 	});
 }
+
+/// Monday, January 1st 2024, 00:00 UTC.
+const MONDAY: u64 = 1_704_067_200;
+const DAY: u64 = 24 * 60 * 60;
+
+/// Produce the next block at `now` seconds since the Unix epoch, servicing the time-based tasks
+/// which are due.
+fn run_to_time(now: u64) {
+	Now::set(now);
+	System::run_to_block_with::<AllPalletsWithSystem>(
+		System::block_number() + 1,
+		frame_system::RunToBlockHooks::default().after_initialize(|n| {
+			Scheduler::on_poll(n, &mut WeightMeter::new());
+		}),
+	);
+}
+
+/// Produce as many blocks at `now` as it takes to service the time agendas up to it.
+fn catch_up_to_time(now: u64) {
+	let lag =
+		TimeIncompleteSince::<Test>::get().map_or(0, |slot| slot_of(now).saturating_sub(slot));
+	for _ in 0..=lag / MAX_SLOTS_PER_BLOCK as u64 {
+		run_to_time(now);
+	}
+}
+
+#[test]
+fn cron_schedule_parsing_works() {
+	let cron = CronSchedule::parse("0 0 * * 1").unwrap();
+	assert_eq!((cron.minutes, cron.hours, cron.days_of_week), (1, 1, 0b10));
+	assert!(cron.is_valid());
+
+	let cron = CronSchedule::parse("*/15 9-17 * * 1-5").unwrap();
+	assert_eq!(cron.minutes, 1 | 1 << 15 | 1 << 30 | 1 << 45);
+	assert_eq!(cron.hours, 0b11_1111_1110_0000_0000);
+	assert_eq!(cron.days_of_week, 0b11_1110);
+
+	let cron = CronSchedule::parse("30 12 1,15 1-12/3 0,7").unwrap();
+	assert_eq!(cron.days_of_month, 1 << 1 | 1 << 15);
+	assert_eq!(cron.months, 1 << 1 | 1 << 4 | 1 << 7 | 1 << 10);
+	// Sunday is given both as 0 and 7.
+	assert_eq!(cron.days_of_week, 1);
+	assert_eq!(CronSchedule::parse("0 0 * * 7"), CronSchedule::parse("0 0 * * 0"));
+	assert_eq!(CronSchedule::parse("5/20 * * * *").unwrap().minutes, 1 << 5 | 1 << 25 | 1 << 45);
+
+	for invalid in [
+		"",
+		"0 0 * *",
+		"0 0 * * * *",
+		"60 * * * *",
+		"* 24 * * *",
+		"* * 0 * *",
+		"* * * 13 *",
+		"* * * * 8",
+		"*/0 * * * *",
+		"5-1 * * * *",
+		"1,,2 * * * *",
+		"mon * * * *",
+	] {
+		assert_eq!(CronSchedule::parse(invalid), None, "{invalid:?} should not parse");
+	}
+
+	let cron = CronSchedule::parse("* * * * *").unwrap();
+	assert!(!CronSchedule { minutes: 0, ..cron }.is_valid());
+	assert!(!CronSchedule { minutes: 1 << 60, ..cron }.is_valid());
+	assert!(!CronSchedule { days_of_month: 1, ..cron }.is_valid());
+	assert!(!CronSchedule { months: 1 << 13, ..cron }.is_valid());
+	assert!(!CronSchedule { days_of_week: 1 << 7, ..cron }.is_valid());
+}
+
+#[test]
+fn cron_next_occurrence_works() {
+	let every_minute = CronSchedule::parse("* * * * *").unwrap();
+	assert_eq!(every_minute.next_after(MONDAY), Some(MONDAY + 60));
+	assert_eq!(every_minute.next_after(MONDAY + 59), Some(MONDAY + 60));
+
+	let mondays = CronSchedule::parse("0 0 * * 1").unwrap();
+	assert_eq!(mondays.next_after(MONDAY - 1), Some(MONDAY));
+	assert_eq!(mondays.next_after(MONDAY), Some(MONDAY + 7 * DAY));
+	assert_eq!(mondays.next_after(MONDAY + 3 * DAY), Some(MONDAY + 7 * DAY));
+
+	let office_hours = CronSchedule::parse("*/15 9-17 * * 1-5").unwrap();
+	assert_eq!(office_hours.next_after(MONDAY), Some(MONDAY + 9 * 3600));
+	assert_eq!(office_hours.next_after(MONDAY + 9 * 3600 + 1), Some(MONDAY + 9 * 3600 + 900));
+	// From Friday evening to Monday morning.
+	assert_eq!(
+		office_hours.next_after(MONDAY + 4 * DAY + 17 * 3600 + 45 * 60),
+		Some(MONDAY + 7 * DAY + 9 * 3600)
+	);
+
+	// Leap days are found, however far apart.
+	let leap_day = CronSchedule::parse("30 12 29 2 *").unwrap();
+	let first = 1_709_164_800 + 12 * 3600 + 30 * 60;
+	assert_eq!(leap_day.next_after(MONDAY), Some(first));
+	assert_eq!(leap_day.next_after(first), Some(1_835_395_200 + 12 * 3600 + 30 * 60));
+
+	// Days of the month and of the week both restricted match either of them: Friday 5th comes
+	// before the 13th.
+	let friday_or_13th = CronSchedule::parse("0 0 13 * 5").unwrap();
+	assert_eq!(friday_or_13th.next_after(MONDAY), Some(MONDAY + 4 * DAY));
+	assert_eq!(friday_or_13th.next_after(MONDAY + 11 * DAY), Some(MONDAY + 12 * DAY));
+
+	// Expressions which can never match are given up on.
+	assert_eq!(CronSchedule::parse("0 0 30 2 *").unwrap().next_after(MONDAY), None);
+	assert_eq!(CronSchedule::parse("0 0 31 4,6,9,11 *").unwrap().next_after(0), None);
+}
+
+#[test]
+fn time_schedule_next_occurrence_works() {
+	assert_eq!(TimeSchedule::At(100).next_after(99), Some(100));
+	assert_eq!(TimeSchedule::At(100).next_after(100), None);
+
+	let every = TimeSchedule::Every { start: 100, period: 50 };
+	assert_eq!(every.next_after(0), Some(100));
+	assert_eq!(every.next_after(100), Some(150));
+	assert_eq!(every.next_after(149), Some(150));
+	// Missed occurrences are skipped.
+	assert_eq!(every.next_after(275), Some(300));
+	assert_eq!(TimeSchedule::Every { start: u64::MAX - 1, period: 50 }.next_after(u64::MAX), None);
+
+	assert!(!TimeSchedule::Every { start: 100, period: 0 }.is_valid());
+	assert!(!TimeSchedule::At(100).is_recurring());
+	assert!(every.is_recurring());
+}
+
+#[test]
+fn scheduling_at_time_works() {
+	new_test_ext().execute_with(|| {
+		run_to_time(MONDAY - 600);
+		let call =
+			RuntimeCall::Logger(LoggerCall::log { i: 42, weight: Weight::from_parts(10, 0) });
+		assert_ok!(Scheduler::schedule_at_time(
+			RuntimeOrigin::root(),
+			TimeSchedule::At(MONDAY + 30),
+			127,
+			Box::new(call)
+		));
+		assert_eq!(TimeAgenda::<Test>::get(slot_of(MONDAY)).len(), 1);
+
+		run_to_time(MONDAY - 1);
+		assert!(logger::log().is_empty());
+		// Within the same slot, but not due yet.
+		run_to_time(MONDAY + 10);
+		assert!(logger::log().is_empty());
+
+		// Dispatched in the first block at or after the time it is due.
+		run_to_time(MONDAY + 42);
+		assert_eq!(logger::log(), vec![(root(), 42u32)]);
+		System::assert_has_event(
+			crate::Event::TimeTaskDispatched {
+				task: (slot_of(MONDAY), 0),
+				id: None,
+				result: Ok(()),
+			}
+			.into(),
+		);
+		assert_eq!(TimeAgenda::<Test>::iter().count(), 0);
+
+		run_to_time(MONDAY + DAY);
+		assert_eq!(logger::log(), vec![(root(), 42u32)]);
+	});
+}
+
+#[test]
+fn time_tasks_survive_block_gaps() {
+	new_test_ext().execute_with(|| {
+		run_to_time(MONDAY - 600);
+		for (i, when) in [(1, MONDAY), (2, MONDAY + 90), (3, MONDAY + 600)] {
+			let call =
+				RuntimeCall::Logger(LoggerCall::log { i, weight: Weight::from_parts(10, 0) });
+			assert_ok!(Scheduler::do_schedule_at_time(
+				None,
+				TimeSchedule::At(when),
+				127,
+				root(),
+				Preimage::bound(call).unwrap(),
+			));
+		}
+
+		// No block for a few minutes: everything which fell due in between is dispatched.
+		run_to_time(MONDAY + 300);
+		assert_eq!(logger::log(), vec![(root(), 1u32), (root(), 2u32)]);
+		assert_eq!(TimeIncompleteSince::<Test>::get(), Some(slot_of(MONDAY + 300)));
+
+		run_to_time(MONDAY + 601);
+		assert_eq!(logger::log(), vec![(root(), 1u32), (root(), 2u32), (root(), 3u32)]);
+	});
+}
+
+#[test]
+fn time_agendas_are_caught_up_over_several_blocks() {
+	new_test_ext().execute_with(|| {
+		run_to_time(MONDAY);
+		let call = RuntimeCall::Logger(LoggerCall::log { i: 1, weight: Weight::from_parts(10, 0) });
+		assert_ok!(Scheduler::do_schedule_at_time(
+			None,
+			TimeSchedule::At(MONDAY + 90 * 60),
+			127,
+			root(),
+			Preimage::bound(call).unwrap(),
+		));
+
+		// No block for two hours: only `MAX_SLOTS_PER_BLOCK` slots are walked per block.
+		run_to_time(MONDAY + 2 * 3600);
+		assert!(logger::log().is_empty());
+		let next_slot = slot_of(MONDAY) + MAX_SLOTS_PER_BLOCK as u64;
+		System::assert_last_event(crate::Event::TimeAgendaIncomplete { slot: next_slot }.into());
+		assert_eq!(TimeIncompleteSince::<Test>::get(), Some(next_slot));
+
+		run_to_time(MONDAY + 2 * 3600);
+		assert_eq!(logger::log(), vec![(root(), 1u32)]);
+		assert_eq!(TimeIncompleteSince::<Test>::get(), Some(slot_of(MONDAY + 2 * 3600)));
+	});
+}
+
+#[test]
+fn time_tasks_share_maximum_weight_with_block_tasks() {
+	new_test_ext().execute_with(|| {
+		run_to_time(MONDAY - 600);
+		let weight = sp_runtime::Perbill::from_percent(60) * MaximumSchedulerWeight::get();
+		assert_ok!(Scheduler::do_schedule(
+			DispatchTime::At(System::block_number() + 1),
+			None,
+			127,
+			root(),
+			Preimage::bound(RuntimeCall::Logger(LoggerCall::log { i: 1, weight })).unwrap(),
+		));
+		assert_ok!(Scheduler::do_schedule_at_time(
+			None,
+			TimeSchedule::At(MONDAY),
+			127,
+			root(),
+			Preimage::bound(RuntimeCall::Logger(LoggerCall::log { i: 2, weight })).unwrap(),
+		));
+
+		// Both are due, but only the block-based task fits in `MaximumWeight`.
+		run_to_time(MONDAY);
+		assert_eq!(logger::log(), vec![(root(), 1u32)]);
+		assert_eq!(TimeAgenda::<Test>::get(slot_of(MONDAY)).len(), 1);
+		assert_eq!(BlockTasksWeight::<Test>::get(), Weight::zero());
+
+		run_to_time(MONDAY + 6);
+		assert_eq!(logger::log(), vec![(root(), 1u32), (root(), 2u32)]);
+	});
+}
+
+#[test]
+fn recurring_time_schedule_works() {
+	new_test_ext().execute_with(|| {
+		run_to_time(MONDAY - 600);
+		let call =
+			RuntimeCall::Logger(LoggerCall::log { i: 42, weight: Weight::from_parts(10, 0) });
+		let hourly = TimeSchedule::Every { start: MONDAY, period: 3600 };
+		assert_ok!(Scheduler::schedule_named_at_time(
+			RuntimeOrigin::root(),
+			[1u8; 32],
+			hourly,
+			127,
+			Box::new(call)
+		));
+		assert_eq!(TimeLookup::<Test>::get([1u8; 32]), Some((slot_of(MONDAY), 0)));
+
+		run_to_time(MONDAY + 5);
+		assert_eq!(logger::log().len(), 1);
+		// The name follows the task to its next time slot.
+		assert_eq!(TimeLookup::<Test>::get([1u8; 32]), Some((slot_of(MONDAY + 3600), 0)));
+		System::assert_has_event(
+			crate::Event::TimeTaskScheduled {
+				task: (slot_of(MONDAY + 3600), 0),
+				when: MONDAY + 3600,
+			}
+			.into(),
+		);
+
+		run_to_time(MONDAY + 3599);
+		assert_eq!(logger::log().len(), 1);
+		run_to_time(MONDAY + 3600);
+		assert_eq!(logger::log().len(), 2);
+
+		// Occurrences missed while no block was produced are skipped.
+		catch_up_to_time(MONDAY + 5 * 3600 + 10);
+		assert_eq!(logger::log().len(), 3);
+		assert_eq!(TimeLookup::<Test>::get([1u8; 32]), Some((slot_of(MONDAY + 6 * 3600), 0)));
+
+		assert_ok!(Scheduler::cancel_named_at_time(RuntimeOrigin::root(), [1u8; 32]));
+		assert_eq!(TimeLookup::<Test>::get([1u8; 32]), None);
+		assert_eq!(TimeAgenda::<Test>::iter().count(), 0);
+		run_to_time(MONDAY + 7 * 3600);
+		assert_eq!(logger::log().len(), 3);
+	});
+}
+
+#[test]
+fn cron_time_schedule_works() {
+	new_test_ext().execute_with(|| {
+		// Friday.
+		run_to_time(MONDAY - 3 * DAY);
+		let call =
+			RuntimeCall::Logger(LoggerCall::log { i: 42, weight: Weight::from_parts(10, 0) });
+		let mondays = TimeSchedule::Cron(CronSchedule::parse("0 0 * * 1").unwrap());
+		assert_ok!(Scheduler::do_schedule_at_time(
+			Some([1u8; 32]),
+			mondays,
+			127,
+			root(),
+			Preimage::bound(call).unwrap(),
+		));
+		assert_eq!(TimeLookup::<Test>::get([1u8; 32]), Some((slot_of(MONDAY), 0)));
+
+		catch_up_to_time(MONDAY - 1);
+		assert!(logger::log().is_empty());
+		run_to_time(MONDAY + 12);
+		assert_eq!(logger::log(), vec![(root(), 42u32)]);
+		assert_eq!(TimeLookup::<Test>::get([1u8; 32]), Some((slot_of(MONDAY + 7 * DAY), 0)));
+
+		catch_up_to_time(MONDAY + 7 * DAY - 1);
+		assert_eq!(logger::log().len(), 1);
+		run_to_time(MONDAY + 7 * DAY + 6);
+		assert_eq!(logger::log().len(), 2);
+		assert_eq!(TimeLookup::<Test>::get([1u8; 32]), Some((slot_of(MONDAY + 14 * DAY), 0)));
+	});
+}
+
+#[test]
+fn scheduling_at_time_checks_schedule() {
+	new_test_ext().execute_with(|| {
+		run_to_time(MONDAY);
+		let call = Box::new(RuntimeCall::Logger(LoggerCall::log {
+			i: 42,
+			weight: Weight::from_parts(10, 0),
+		}));
+
+		for when in [MONDAY - 1, MONDAY] {
+			assert_noop!(
+				Scheduler::schedule_at_time(
+					RuntimeOrigin::root(),
+					TimeSchedule::At(when),
+					127,
+					call.clone()
+				),
+				Error::<Test>::TargetTimeInPast,
+			);
+		}
+
+		let cron = CronSchedule::parse("* * * * *").unwrap();
+		for schedule in [
+			TimeSchedule::Every { start: MONDAY, period: 0 },
+			TimeSchedule::Cron(CronSchedule { hours: 0, ..cron }),
+			TimeSchedule::Cron(CronSchedule::parse("0 0 30 2 *").unwrap()),
+		] {
+			assert_noop!(
+				Scheduler::schedule_at_time(RuntimeOrigin::root(), schedule, 127, call.clone()),
+				Error::<Test>::InvalidTimeSchedule,
+			);
+		}
+
+		// Recurring schedules which started in the past are fine.
+		assert_ok!(Scheduler::schedule_named_at_time(
+			RuntimeOrigin::root(),
+			[1u8; 32],
+			TimeSchedule::Every { start: MONDAY - 100, period: 60 },
+			127,
+			call.clone()
+		));
+		assert_eq!(TimeLookup::<Test>::get([1u8; 32]), Some((slot_of(MONDAY + 20), 0)));
+
+		// Names are unique.
+		assert_noop!(
+			Scheduler::schedule_named_at_time(
+				RuntimeOrigin::root(),
+				[1u8; 32],
+				TimeSchedule::At(MONDAY + 60),
+				127,
+				call.clone()
+			),
+			Error::<Test>::FailedToSchedule,
+		);
+
+		assert_noop!(
+			Scheduler::schedule_at_time(
+				RuntimeOrigin::signed(2),
+				TimeSchedule::At(MONDAY + 60),
+				127,
+				call
+			),
+			BadOrigin,
+		);
+	});
+}
+
+#[test]
+fn cancel_at_time_works() {
+	new_test_ext().execute_with(|| {
+		run_to_time(MONDAY - 600);
+		for i in [1, 2] {
+			let call =
+				RuntimeCall::Logger(LoggerCall::log { i, weight: Weight::from_parts(10, 0) });
+			assert_ok!(Scheduler::schedule_at_time(
+				RuntimeOrigin::root(),
+				TimeSchedule::At(MONDAY),
+				127,
+				Box::new(call)
+			));
+		}
+		let slot = slot_of(MONDAY);
+
+		// Only as privileged an origin as the one which scheduled the task may cancel it.
+		assert_noop!(Scheduler::cancel_at_time(RuntimeOrigin::signed(1), slot, 0), BadOrigin);
+
+		assert_ok!(Scheduler::cancel_at_time(RuntimeOrigin::root(), slot, 0));
+		System::assert_last_event(crate::Event::TimeTaskCanceled { task: (slot, 0) }.into());
+		assert_noop!(
+			Scheduler::cancel_at_time(RuntimeOrigin::root(), slot, 0),
+			Error::<Test>::NotFound
+		);
+		assert_noop!(
+			Scheduler::cancel_named_at_time(RuntimeOrigin::root(), [1u8; 32]),
+			Error::<Test>::NotFound
+		);
+
+		run_to_time(MONDAY);
+		assert_eq!(logger::log(), vec![(root(), 2u32)]);
+	});
+}
+
+#[test]
+fn time_tasks_are_postponed_when_overweight() {
+	new_test_ext().execute_with(|| {
+		run_to_time(MONDAY - 600);
+		let max_weight: Weight = <Test as Config>::MaximumWeight::get();
+		for (i, priority) in [(1, 0), (2, 1)] {
+			let call = RuntimeCall::Logger(LoggerCall::log { i, weight: max_weight / 3 * 2 });
+			assert_ok!(Scheduler::do_schedule_at_time(
+				None,
+				TimeSchedule::At(MONDAY),
+				priority,
+				root(),
+				Preimage::bound(call).unwrap(),
+			));
+		}
+
+		// Only one of the tasks fits within the weight limit.
+		run_to_time(MONDAY);
+		assert_eq!(logger::log(), vec![(root(), 1u32)]);
+		System::assert_last_event(
+			crate::Event::TimeAgendaIncomplete { slot: slot_of(MONDAY) }.into(),
+		);
+		assert_eq!(TimeIncompleteSince::<Test>::get(), Some(slot_of(MONDAY)));
+
+		// The other one is dispatched in the next block, even though the time slot is over.
+		run_to_time(MONDAY + 60);
+		assert_eq!(logger::log(), vec![(root(), 1u32), (root(), 2u32)]);
+		assert_eq!(TimeAgenda::<Test>::iter().count(), 0);
+		assert_eq!(TimeIncompleteSince::<Test>::get(), Some(slot_of(MONDAY + 60)));
+
+		// A task which would not fit even in a block of its own is dropped right away.
+		let weight = max_weight.saturating_add(Weight::from_parts(1, 0));
+		let call = RuntimeCall::Logger(LoggerCall::log { i: 3, weight });
+		assert_ok!(Scheduler::do_schedule_at_time(
+			None,
+			TimeSchedule::At(MONDAY + 120),
+			127,
+			root(),
+			Preimage::bound(call).unwrap(),
+		));
+		run_to_time(MONDAY + 120);
+		System::assert_last_event(
+			crate::Event::TimeTaskPermanentlyOverweight {
+				task: (slot_of(MONDAY + 120), 0),
+				id: None,
+			}
+			.into(),
+		);
+		assert_eq!(TimeAgenda::<Test>::iter().count(), 0);
+		assert_eq!(TimeIncompleteSince::<Test>::get(), Some(slot_of(MONDAY + 120)));
+		assert_eq!(logger::log().len(), 2);
+	});
+}
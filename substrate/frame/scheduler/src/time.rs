@@ -0,0 +1,310 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Schedules keyed on wall-clock time rather than block numbers.
+//!
+//! All times are in seconds since the Unix epoch, in UTC. Time-based tasks are kept in one agenda
+//! per [`TimeSlot`] of [`SLOT_DURATION`] seconds, which is also the granularity of
+//! [`CronSchedule`].
+
+use codec::{Decode, DecodeWithMemTracking, Encode, MaxEncodedLen};
+use scale_info::TypeInfo;
+use sp_runtime::RuntimeDebug;
+
+/// A point in time, in seconds since the Unix epoch.
+pub type Moment = u64;
+
+/// A minute since the Unix epoch, indexing the agenda of time-based tasks due within it.
+pub type TimeSlot = u64;
+
+/// The duration of a [`TimeSlot`], in seconds.
+pub const SLOT_DURATION: Moment = 60;
+
+/// The most time slots whose agendas are serviced in one block, so that a chain catching up
+/// after a long stall does not spend its blocks walking empty agendas.
+pub const MAX_SLOTS_PER_BLOCK: u32 = 60;
+
+/// The time slot that `moment` falls in.
+pub fn slot_of(moment: Moment) -> TimeSlot {
+	moment / SLOT_DURATION
+}
+
+const MINUTES_PER_DAY: u64 = 24 * 60;
+/// The longest gap between two days that a valid cron expression can match: February 29th,
+/// across a century year which is not a leap year.
+const MAX_SEARCH_YEARS: u64 = 8;
+
+/// When a time-based task is due.
+#[derive(
+	Clone,
+	Copy,
+	RuntimeDebug,
+	PartialEq,
+	Eq,
+	Encode,
+	Decode,
+	DecodeWithMemTracking,
+	MaxEncodedLen,
+	TypeInfo,
+)]
+pub enum TimeSchedule {
+	/// Once, at the given time.
+	At(Moment),
+	/// At `start`, and then every `period` seconds until cancelled.
+	///
+	/// Occurrences missed while no block was produced are skipped.
+	Every { start: Moment, period: Moment },
+	/// At every minute matching the cron expression, until cancelled.
+	Cron(CronSchedule),
+}
+
+impl TimeSchedule {
+	/// Whether the schedule is well-formed.
+	pub fn is_valid(&self) -> bool {
+		match self {
+			Self::At(_) => true,
+			Self::Every { period, .. } => *period > 0,
+			Self::Cron(cron) => cron.is_valid(),
+		}
+	}
+
+	/// Whether the task is due more than once.
+	pub fn is_recurring(&self) -> bool {
+		!matches!(self, Self::At(_))
+	}
+
+	/// The first due time of the schedule strictly after `after`, if any.
+	pub fn next_after(&self, after: Moment) -> Option<Moment> {
+		match *self {
+			Self::At(at) => (at > after).then_some(at),
+			Self::Every { start, .. } if start > after => Some(start),
+			Self::Every { start, period } => {
+				let elapsed = (after - start).checked_div(period)?;
+				start.checked_add(elapsed.checked_add(1)?.checked_mul(period)?)
+			},
+			Self::Cron(cron) => cron.next_after(after),
+		}
+	}
+}
+
+/// A cron expression, with every field given as a bit set of the values it matches.
+///
+/// As with cron, when both the days of the month and the days of the week are restricted, a day
+/// matching either of them matches. A field is restricted unless it matches every value.
+#[derive(
+	Clone,
+	Copy,
+	RuntimeDebug,
+	PartialEq,
+	Eq,
+	Encode,
+	Decode,
+	DecodeWithMemTracking,
+	MaxEncodedLen,
+	TypeInfo,
+)]
+pub struct CronSchedule {
+	/// Bit `n` is set to match minute `n` of the hour, from 0 to 59.
+	pub minutes: u64,
+	/// Bit `n` is set to match hour `n` of the day, from 0 to 23.
+	pub hours: u32,
+	/// Bit `n` is set to match day `n` of the month, from 1 to 31.
+	pub days_of_month: u32,
+	/// Bit `n` is set to match month `n`, from 1 to 12.
+	pub months: u16,
+	/// Bit `n` is set to match day `n` of the week, from 0 (Sunday) to 6 (Saturday).
+	pub days_of_week: u8,
+}
+
+impl CronSchedule {
+	const ALL_MINUTES: u64 = (1 << 60) - 1;
+	const ALL_HOURS: u32 = (1 << 24) - 1;
+	const ALL_DAYS_OF_MONTH: u32 = !1;
+	const ALL_MONTHS: u16 = 0b1_1111_1111_1110;
+	const ALL_DAYS_OF_WEEK: u8 = 0b111_1111;
+
+	/// Parse a standard five-field cron expression: minute, hour, day of the month, month and
+	/// day of the week.
+	///
+	/// Every field is a comma-separated list of `*`, a value `a` or a range `a-b`, each optionally
+	/// followed by a step `/n`. A value followed by a step stands for the range from the value to
+	/// the maximum of the field. Days of the week go from 0 to 7, both standing for Sunday. Names
+	/// of months and days are not supported.
+	///
+	/// For example, `0 0 * * 1` is every Monday at 00:00, and `*/15 9-17 * * 1-5` is every
+	/// quarter of an hour during office hours.
+	pub fn parse(expression: &str) -> Option<Self> {
+		let mut fields = expression.split_whitespace();
+		let minutes = parse_field(fields.next()?, 0, 59)?;
+		let hours = parse_field(fields.next()?, 0, 23)?;
+		let days_of_month = parse_field(fields.next()?, 1, 31)?;
+		let months = parse_field(fields.next()?, 1, 12)?;
+		let days_of_week = parse_field(fields.next()?, 0, 7)?;
+		if fields.next().is_some() {
+			return None
+		}
+		// Sunday may be given as 7.
+		let days_of_week = (days_of_week | days_of_week >> 7) & Self::ALL_DAYS_OF_WEEK as u64;
+
+		Some(Self {
+			minutes,
+			hours: hours as u32,
+			days_of_month: days_of_month as u32,
+			months: months as u16,
+			days_of_week: days_of_week as u8,
+		})
+	}
+
+	/// Whether every field matches at least one value, and only values within its range.
+	pub fn is_valid(&self) -> bool {
+		fn within<
+			T: Copy + Eq + Default + core::ops::BitAnd<Output = T> + core::ops::Not<Output = T>,
+		>(
+			field: T,
+			all: T,
+		) -> bool {
+			field != T::default() && field & !all == T::default()
+		}
+
+		within(self.minutes, Self::ALL_MINUTES) &&
+			within(self.hours, Self::ALL_HOURS) &&
+			within(self.days_of_month, Self::ALL_DAYS_OF_MONTH) &&
+			within(self.months, Self::ALL_MONTHS) &&
+			within(self.days_of_week, Self::ALL_DAYS_OF_WEEK)
+	}
+
+	/// The first minute matching the expression strictly after `after`, if any.
+	///
+	/// The search is bounded: it gives up after eight years, which only happens when the
+	/// expression can never match, e.g. on February 30th.
+	pub fn next_after(&self, after: Moment) -> Option<Moment> {
+		if !self.is_valid() {
+			return None
+		}
+
+		let start = (after / SLOT_DURATION).checked_add(1)?;
+		let mut day = start / MINUTES_PER_DAY;
+		let mut from_minute = start % MINUTES_PER_DAY;
+		let (first_year, _, _) = civil_from_days(day);
+
+		loop {
+			let (year, month, day_of_month) = civil_from_days(day);
+			if year > first_year + MAX_SEARCH_YEARS {
+				return None
+			}
+
+			if self.months & (1 << month) == 0 {
+				// Skip the rest of the month.
+				let (year, month) = if month == 12 { (year + 1, 1) } else { (year, month + 1) };
+				day = days_from_civil(year, month, 1);
+				from_minute = 0;
+				continue
+			}
+
+			if self.matches_day(day_of_month, ((day + 4) % 7) as u32) {
+				if let Some(minute) = self.first_minute_from(from_minute) {
+					return day
+						.checked_mul(MINUTES_PER_DAY)?
+						.checked_add(minute)?
+						.checked_mul(SLOT_DURATION)
+				}
+			}
+
+			day += 1;
+			from_minute = 0;
+		}
+	}
+
+	fn matches_day(&self, day_of_month: u32, day_of_week: u32) -> bool {
+		let by_month = self.days_of_month & (1 << day_of_month) != 0;
+		let by_week = self.days_of_week & (1 << day_of_week) != 0;
+		match (
+			self.days_of_month == Self::ALL_DAYS_OF_MONTH,
+			self.days_of_week == Self::ALL_DAYS_OF_WEEK,
+		) {
+			(true, true) => true,
+			(true, false) => by_week,
+			(false, true) => by_month,
+			(false, false) => by_month || by_week,
+		}
+	}
+
+	/// The first minute of the day, at or after `from`, matching the hours and minutes.
+	fn first_minute_from(&self, from: u64) -> Option<u64> {
+		let (from_hour, from_minute) = (from / 60, from % 60);
+		(from_hour..24).filter(|hour| self.hours & (1 << hour) != 0).find_map(|hour| {
+			let from_minute = if hour == from_hour { from_minute } else { 0 };
+			let minutes = self.minutes >> from_minute;
+			(minutes != 0).then(|| hour * 60 + from_minute + minutes.trailing_zeros() as u64)
+		})
+	}
+}
+
+/// Parse one field of a cron expression into a bit set of the values from `min` to `max` it
+/// matches.
+fn parse_field(field: &str, min: u32, max: u32) -> Option<u64> {
+	let mut matched = 0u64;
+	for item in field.split(',') {
+		let (range, step) = match item.split_once('/') {
+			Some((range, step)) => (range, step.parse::<u32>().ok().filter(|step| *step > 0)?),
+			None => (item, 1),
+		};
+		let (first, last) = match range.split_once('-') {
+			_ if range == "*" => (min, max),
+			Some((first, last)) => (first.parse().ok()?, last.parse().ok()?),
+			None => {
+				let first = range.parse().ok()?;
+				(first, if step > 1 { max } else { first })
+			},
+		};
+		if first < min || last > max || first > last {
+			return None
+		}
+		matched |= (first..=last).step_by(step as usize).fold(0, |set, value| set | 1 << value);
+	}
+	Some(matched)
+}
+
+/// The year, month and day of the month of `days` since the Unix epoch.
+///
+/// From Howard Hinnant's `civil_from_days`, for the proleptic Gregorian calendar.
+fn civil_from_days(days: u64) -> (u64, u32, u32) {
+	let z = days + 719_468;
+	let era = z / 146_097;
+	let day_of_era = z - era * 146_097;
+	let year_of_era =
+		(day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+	let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+	let shifted_month = (5 * day_of_year + 2) / 153;
+	let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+	let month = if shifted_month < 10 { shifted_month + 3 } else { shifted_month - 9 };
+	let year = year_of_era + era * 400 + (month <= 2) as u64;
+	(year, month as u32, day as u32)
+}
+
+/// The number of days since the Unix epoch of the given date, from 1970 onwards.
+///
+/// The inverse of [`civil_from_days`].
+fn days_from_civil(year: u64, month: u32, day: u32) -> u64 {
+	let year = year - (month <= 2) as u64;
+	let era = year / 400;
+	let year_of_era = year - era * 400;
+	let shifted_month = (if month > 2 { month - 3 } else { month + 9 }) as u64;
+	let day_of_year = (153 * shifted_month + 2) / 5 + day as u64 - 1;
+	let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+	era * 146_097 + day_of_era - 719_468
+}
@@ -89,6 +89,12 @@ pub trait WeightInfo {
 	fn set_retry_named() -> Weight;
 	fn cancel_retry() -> Weight;
 	fn cancel_retry_named() -> Weight;
+	fn service_time_agendas_base() -> Weight;
+	fn next_cron_occurrence() -> Weight;
+	fn schedule_at_time(s: u32, ) -> Weight;
+	fn cancel_at_time(s: u32, ) -> Weight;
+	fn schedule_named_at_time(s: u32, ) -> Weight;
+	fn cancel_named_at_time(s: u32, ) -> Weight;
 }
 
 /// Weights for `pallet_scheduler` using the Substrate node and recommended hardware.
@@ -319,6 +325,70 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(2_u64))
 			.saturating_add(T::DbWeight::get().writes(1_u64))
 	}
+	/// Storage: `Scheduler::TimeIncompleteSince` (r:1 w:1)
+	/// Proof: `Scheduler::TimeIncompleteSince` (`max_values`: Some(1), `max_size`: Some(8), added: 503, mode: `MaxEncodedLen`)
+	/// Storage: `Timestamp::Now` (r:1 w:0)
+	/// Proof: `Timestamp::Now` (`max_values`: Some(1), `max_size`: Some(8), added: 503, mode: `MaxEncodedLen`)
+	fn service_time_agendas_base() -> Weight {
+		// Not benchmarked yet: like `service_agendas_base`, with the larger
+		// `Scheduler::TimeIncompleteSince` and reading the time from `Timestamp::Now`.
+		Self::service_agendas_base()
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(Weight::from_parts(0, 507))
+	}
+	fn next_cron_occurrence() -> Weight {
+		// Not benchmarked yet: no measured weight covers searching the calendar, which walks at
+		// most eight years of days. It is charged like servicing a full agenda, a generous bound
+		// until the benchmark is run.
+		Self::service_agenda_base(512)
+	}
+	/// Storage: `Timestamp::Now` (r:1 w:0)
+	/// Proof: `Timestamp::Now` (`max_values`: Some(1), `max_size`: Some(8), added: 503, mode: `MaxEncodedLen`)
+	/// Storage: `Scheduler::TimeAgenda` (r:1 w:1)
+	/// Proof: `Scheduler::TimeAgenda` (`max_values`: None, `max_size`: Some(116750), added: 119229, mode: `MaxEncodedLen`)
+	/// The range of component `s` is `[0, 511]`.
+	fn schedule_at_time(s: u32, ) -> Weight {
+		// Not benchmarked yet: like `schedule`, with the larger `Scheduler::TimeAgenda` and
+		// reading the time from `Timestamp::Now`.
+		Self::schedule(s)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(Weight::from_parts(0, 10235))
+	}
+	/// Storage: `Scheduler::TimeAgenda` (r:1 w:1)
+	/// Proof: `Scheduler::TimeAgenda` (`max_values`: None, `max_size`: Some(116750), added: 119229, mode: `MaxEncodedLen`)
+	/// Storage: `Scheduler::TimeLookup` (r:0 w:1)
+	/// Proof: `Scheduler::TimeLookup` (`max_values`: None, `max_size`: Some(52), added: 2527, mode: `MaxEncodedLen`)
+	/// The range of component `s` is `[1, 512]`.
+	fn cancel_at_time(s: u32, ) -> Weight {
+		// Not benchmarked yet: like `cancel`, with the larger `Scheduler::TimeAgenda`.
+		Self::cancel(s)
+			.saturating_add(Weight::from_parts(0, 9732))
+	}
+	/// Storage: `Scheduler::TimeLookup` (r:1 w:1)
+	/// Proof: `Scheduler::TimeLookup` (`max_values`: None, `max_size`: Some(52), added: 2527, mode: `MaxEncodedLen`)
+	/// Storage: `Timestamp::Now` (r:1 w:0)
+	/// Proof: `Timestamp::Now` (`max_values`: Some(1), `max_size`: Some(8), added: 503, mode: `MaxEncodedLen`)
+	/// Storage: `Scheduler::TimeAgenda` (r:1 w:1)
+	/// Proof: `Scheduler::TimeAgenda` (`max_values`: None, `max_size`: Some(116750), added: 119229, mode: `MaxEncodedLen`)
+	/// The range of component `s` is `[0, 511]`.
+	fn schedule_named_at_time(s: u32, ) -> Weight {
+		// Not benchmarked yet: like `schedule_named`, with the larger `Scheduler::TimeAgenda` and
+		// `Scheduler::TimeLookup`, and reading the time from `Timestamp::Now`.
+		Self::schedule_named(s)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(Weight::from_parts(0, 10239))
+	}
+	/// Storage: `Scheduler::TimeLookup` (r:1 w:1)
+	/// Proof: `Scheduler::TimeLookup` (`max_values`: None, `max_size`: Some(52), added: 2527, mode: `MaxEncodedLen`)
+	/// Storage: `Scheduler::TimeAgenda` (r:1 w:1)
+	/// Proof: `Scheduler::TimeAgenda` (`max_values`: None, `max_size`: Some(116750), added: 119229, mode: `MaxEncodedLen`)
+	/// The range of component `s` is `[1, 512]`.
+	fn cancel_named_at_time(s: u32, ) -> Weight {
+		// Not benchmarked yet: like `cancel_named`, with the larger `Scheduler::TimeAgenda` and
+		// `Scheduler::TimeLookup`.
+		Self::cancel_named(s)
+			.saturating_add(Weight::from_parts(0, 9736))
+	}
 }
 
 // For backwards compatibility and tests.
@@ -548,4 +618,68 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(2_u64))
 			.saturating_add(RocksDbWeight::get().writes(1_u64))
 	}
+	/// Storage: `Scheduler::TimeIncompleteSince` (r:1 w:1)
+	/// Proof: `Scheduler::TimeIncompleteSince` (`max_values`: Some(1), `max_size`: Some(8), added: 503, mode: `MaxEncodedLen`)
+	/// Storage: `Timestamp::Now` (r:1 w:0)
+	/// Proof: `Timestamp::Now` (`max_values`: Some(1), `max_size`: Some(8), added: 503, mode: `MaxEncodedLen`)
+	fn service_time_agendas_base() -> Weight {
+		// Not benchmarked yet: like `service_agendas_base`, with the larger
+		// `Scheduler::TimeIncompleteSince` and reading the time from `Timestamp::Now`.
+		Self::service_agendas_base()
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(Weight::from_parts(0, 507))
+	}
+	fn next_cron_occurrence() -> Weight {
+		// Not benchmarked yet: no measured weight covers searching the calendar, which walks at
+		// most eight years of days. It is charged like servicing a full agenda, a generous bound
+		// until the benchmark is run.
+		Self::service_agenda_base(512)
+	}
+	/// Storage: `Timestamp::Now` (r:1 w:0)
+	/// Proof: `Timestamp::Now` (`max_values`: Some(1), `max_size`: Some(8), added: 503, mode: `MaxEncodedLen`)
+	/// Storage: `Scheduler::TimeAgenda` (r:1 w:1)
+	/// Proof: `Scheduler::TimeAgenda` (`max_values`: None, `max_size`: Some(116750), added: 119229, mode: `MaxEncodedLen`)
+	/// The range of component `s` is `[0, 511]`.
+	fn schedule_at_time(s: u32, ) -> Weight {
+		// Not benchmarked yet: like `schedule`, with the larger `Scheduler::TimeAgenda` and
+		// reading the time from `Timestamp::Now`.
+		Self::schedule(s)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(Weight::from_parts(0, 10235))
+	}
+	/// Storage: `Scheduler::TimeAgenda` (r:1 w:1)
+	/// Proof: `Scheduler::TimeAgenda` (`max_values`: None, `max_size`: Some(116750), added: 119229, mode: `MaxEncodedLen`)
+	/// Storage: `Scheduler::TimeLookup` (r:0 w:1)
+	/// Proof: `Scheduler::TimeLookup` (`max_values`: None, `max_size`: Some(52), added: 2527, mode: `MaxEncodedLen`)
+	/// The range of component `s` is `[1, 512]`.
+	fn cancel_at_time(s: u32, ) -> Weight {
+		// Not benchmarked yet: like `cancel`, with the larger `Scheduler::TimeAgenda`.
+		Self::cancel(s)
+			.saturating_add(Weight::from_parts(0, 9732))
+	}
+	/// Storage: `Scheduler::TimeLookup` (r:1 w:1)
+	/// Proof: `Scheduler::TimeLookup` (`max_values`: None, `max_size`: Some(52), added: 2527, mode: `MaxEncodedLen`)
+	/// Storage: `Timestamp::Now` (r:1 w:0)
+	/// Proof: `Timestamp::Now` (`max_values`: Some(1), `max_size`: Some(8), added: 503, mode: `MaxEncodedLen`)
+	/// Storage: `Scheduler::TimeAgenda` (r:1 w:1)
+	/// Proof: `Scheduler::TimeAgenda` (`max_values`: None, `max_size`: Some(116750), added: 119229, mode: `MaxEncodedLen`)
+	/// The range of component `s` is `[0, 511]`.
+	fn schedule_named_at_time(s: u32, ) -> Weight {
+		// Not benchmarked yet: like `schedule_named`, with the larger `Scheduler::TimeAgenda` and
+		// `Scheduler::TimeLookup`, and reading the time from `Timestamp::Now`.
+		Self::schedule_named(s)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(Weight::from_parts(0, 10239))
+	}
+	/// Storage: `Scheduler::TimeLookup` (r:1 w:1)
+	/// Proof: `Scheduler::TimeLookup` (`max_values`: None, `max_size`: Some(52), added: 2527, mode: `MaxEncodedLen`)
+	/// Storage: `Scheduler::TimeAgenda` (r:1 w:1)
+	/// Proof: `Scheduler::TimeAgenda` (`max_values`: None, `max_size`: Some(116750), added: 119229, mode: `MaxEncodedLen`)
+	/// The range of component `s` is `[1, 512]`.
+	fn cancel_named_at_time(s: u32, ) -> Weight {
+		// Not benchmarked yet: like `cancel_named`, with the larger `Scheduler::TimeAgenda` and
+		// `Scheduler::TimeLookup`.
+		Self::cancel_named(s)
+			.saturating_add(Weight::from_parts(0, 9736))
+	}
 }
@@ -201,6 +201,7 @@ impl pallet_scheduler::Config for Runtime {
 	type OriginPrivilegeCmp = frame_support::traits::EqualPrivilegeOnly;
 	type Preimages = Preimage;
 	type BlockNumberProvider = RelayChainBlockNumberProvider;
+	type UnixTime = Timestamp;
 }
 
 parameter_types! {
@@ -169,4 +169,48 @@ impl<T: frame_system::Config> pallet_multisig::WeightInfo for WeightInfo<T> {
 			.saturating_add(T::DbWeight::get().reads(1))
 			.saturating_add(T::DbWeight::get().writes(1))
 	}
+	/// Storage: `Multisig::StatefulMultisigs` (r:1 w:1)
+	/// Proof: `Multisig::StatefulMultisigs` (`max_values`: None, `max_size`: Some(3658), added: 6133, mode: `MaxEncodedLen`)
+	/// The range of component `s` is `[1, 100]`.
+	fn create_stateful_multisig(s: u32, ) -> Weight {
+		// Not benchmarked yet: opening an operation of `s` signatories with `approve_as_multi`,
+		// with the configuration in `Multisig::StatefulMultisigs` instead of the operation.
+		<Self as pallet_multisig::WeightInfo>::approve_as_multi_create(s)
+			.saturating_add(Weight::from_parts(0, 312))
+	}
+	/// Storage: `Multisig::StatefulMultisigs` (r:1 w:1)
+	/// Proof: `Multisig::StatefulMultisigs` (`max_values`: None, `max_size`: Some(3658), added: 6133, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `Multisig::Multisigs` (r:101 w:100)
+	/// Proof: `Multisig::Multisigs` (`max_values`: None, `max_size`: Some(3346), added: 5821, mode: `MaxEncodedLen`)
+	/// The range of component `s` is `[1, 100]`.
+	/// The range of component `o` is `[0, 100]`.
+	fn update_stateful_multisig(s: u32, o: u32, ) -> Weight {
+		// Not benchmarked yet: poking the deposit of the configuration in
+		// `Multisig::StatefulMultisigs` like the one of an operation, for a depositor who is not
+		// the caller, then pruning the approvals of `o` operations like approving them, plus
+		// reading past the last operation.
+		<Self as pallet_multisig::WeightInfo>::poke_deposit(s)
+			.saturating_add(Weight::from_parts(0, 312))
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().writes(1))
+			.saturating_add(Weight::from_parts(0, 2603))
+			.saturating_add(<Self as pallet_multisig::WeightInfo>::approve_as_multi_approve(s).saturating_mul(o.into()))
+	}
+	/// Storage: `Multisig::StatefulMultisigs` (r:1 w:1)
+	/// Proof: `Multisig::StatefulMultisigs` (`max_values`: None, `max_size`: Some(3658), added: 6133, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:2 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// The range of component `s` is `[1, 100]`.
+	fn destroy_stateful_multisig(s: u32, ) -> Weight {
+		// Not benchmarked yet: cancelling an operation of `s` signatories, with the configuration
+		// in `Multisig::StatefulMultisigs` instead of the operation, for a depositor who is not
+		// the caller, plus checking that the account of the multisig no longer exists.
+		<Self as pallet_multisig::WeightInfo>::cancel_as_multi(s)
+			.saturating_add(Weight::from_parts(0, 312))
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().writes(1))
+			.saturating_add(Weight::from_parts(0, 5206))
+	}
 }
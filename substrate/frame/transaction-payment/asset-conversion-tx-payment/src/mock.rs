@@ -269,6 +269,7 @@ impl pallet_asset_conversion::Config for Runtime {
 	type LPFee = ConstU32<3>; // means 0.3%
 	type LiquidityWithdrawalFee = LiquidityWithdrawalFee;
 	type MaxSwapPathLength = MaxSwapPathLength;
+	type MaxPriceObservations = ConstU32<10>;
 	type MintMinLiquidity = ConstU64<100>; // 100 is good enough when the main currency has 12 decimals.
 	type WeightInfo = ();
 	pallet_asset_conversion::runtime_benchmarks_enabled! {
@@ -60,7 +60,7 @@ fn add_vesting_schedules<T: Config>(
 		total_locked += locked;
 
 		let schedule = VestingInfo::new(locked, per_block, starting_block.into());
-		assert_ok!(Pallet::<T>::do_vested_transfer(&source, target, schedule));
+		assert_ok!(Pallet::<T>::do_vested_transfer(&source, target, schedule.into()));
 
 		// Top up to guarantee we can always transfer another schedule.
 		T::Currency::make_free_balance_be(&source, BalanceOf::<T>::max_value());
@@ -79,7 +79,7 @@ mod benchmarks {
 		s: Linear<1, T::MAX_VESTING_SCHEDULES>,
 	) -> Result<(), BenchmarkError> {
 		let caller = whitelisted_caller();
-		T::Currency::make_free_balance_be(&caller, T::Currency::minimum_balance());
+		T::Currency::make_free_balance_be(&caller, <T::Currency as Currency<_>>::minimum_balance());
 
 		add_locks::<T>(&caller, l as u8);
 		let expected_balance = add_vesting_schedules::<T>(&caller, s)?;
@@ -111,7 +111,7 @@ mod benchmarks {
 		s: Linear<1, T::MAX_VESTING_SCHEDULES>,
 	) -> Result<(), BenchmarkError> {
 		let caller = whitelisted_caller();
-		T::Currency::make_free_balance_be(&caller, T::Currency::minimum_balance());
+		T::Currency::make_free_balance_be(&caller, <T::Currency as Currency<_>>::minimum_balance());
 
 		add_locks::<T>(&caller, l as u8);
 		add_vesting_schedules::<T>(&caller, s)?;
@@ -141,7 +141,7 @@ mod benchmarks {
 		let other = account::<T::AccountId>("other", 0, SEED);
 		let other_lookup = T::Lookup::unlookup(other.clone());
 
-		T::Currency::make_free_balance_be(&other, T::Currency::minimum_balance());
+		T::Currency::make_free_balance_be(&other, <T::Currency as Currency<_>>::minimum_balance());
 		add_locks::<T>(&other, l as u8);
 		let expected_balance = add_vesting_schedules::<T>(&other, s)?;
 
@@ -176,7 +176,7 @@ mod benchmarks {
 		let other = account::<T::AccountId>("other", 0, SEED);
 		let other_lookup = T::Lookup::unlookup(other.clone());
 
-		T::Currency::make_free_balance_be(&other, T::Currency::minimum_balance());
+		T::Currency::make_free_balance_be(&other, <T::Currency as Currency<_>>::minimum_balance());
 		add_locks::<T>(&other, l as u8);
 		add_vesting_schedules::<T>(&other, s)?;
 		// At block 21 everything is unlocked.
@@ -210,7 +210,7 @@ mod benchmarks {
 		let target = account::<T::AccountId>("target", 0, SEED);
 		let target_lookup = T::Lookup::unlookup(target.clone());
 		// Give target existing locks.
-		T::Currency::make_free_balance_be(&target, T::Currency::minimum_balance());
+		T::Currency::make_free_balance_be(&target, <T::Currency as Currency<_>>::minimum_balance());
 		add_locks::<T>(&target, l as u8);
 		// Add one vesting schedules.
 		let orig_balance = T::Currency::free_balance(&target);
@@ -251,7 +251,7 @@ mod benchmarks {
 		let target = account::<T::AccountId>("target", 0, SEED);
 		let target_lookup = T::Lookup::unlookup(target.clone());
 		// Give target existing locks.
-		T::Currency::make_free_balance_be(&target, T::Currency::minimum_balance());
+		T::Currency::make_free_balance_be(&target, <T::Currency as Currency<_>>::minimum_balance());
 		add_locks::<T>(&target, l as u8);
 		// Add one less than max vesting schedules.
 		let orig_balance = T::Currency::free_balance(&target);
@@ -280,6 +280,51 @@ mod benchmarks {
 		Ok(())
 	}
 
+	#[benchmark]
+	fn force_vested_transfer_with_curve(
+		l: Linear<0, { MaxLocksOf::<T>::get() - 1 }>,
+		s: Linear<0, { T::MAX_VESTING_SCHEDULES - 1 }>,
+	) -> Result<(), BenchmarkError> {
+		let source = account::<T::AccountId>("source", 0, SEED);
+		let source_lookup = T::Lookup::unlookup(source.clone());
+		T::Currency::make_free_balance_be(&source, BalanceOf::<T>::max_value());
+
+		let target = account::<T::AccountId>("target", 0, SEED);
+		let target_lookup = T::Lookup::unlookup(target.clone());
+		// Give target existing locks.
+		T::Currency::make_free_balance_be(&target, <T::Currency as Currency<_>>::minimum_balance());
+		add_locks::<T>(&target, l as u8);
+		// Add one less than max vesting schedules.
+		let orig_balance = T::Currency::free_balance(&target);
+		let expected_balance = add_vesting_schedules::<T>(&target, s)?;
+
+		let transfer_amount = T::MinVestedTransfer::get();
+		let per_block = transfer_amount.checked_div(&20_u32.into()).unwrap();
+
+		// A revocable curve also puts its funds on hold.
+		let vesting_schedule =
+			VestingCurve::new(VestingInfo::new(transfer_amount, per_block, 1_u32.into()))
+				.with_cliff(10_u32.into())
+				.with_period(5_u32.into())
+				.with_revocable(true);
+
+		#[extrinsic_call]
+		_(RawOrigin::Root, source_lookup, target_lookup, vesting_schedule);
+
+		assert_eq!(
+			orig_balance + expected_balance,
+			T::Currency::free_balance(&target),
+			"Transfer didn't happen",
+		);
+		assert_eq!(
+			T::Currency::balance_on_hold(&HoldReason::RevocableSchedule.into(), &target),
+			transfer_amount,
+			"Hold not correctly updated",
+		);
+
+		Ok(())
+	}
+
 	#[benchmark]
 	fn not_unlocking_merge_schedules(
 		l: Linear<0, { MaxLocksOf::<T>::get() - 1 }>,
@@ -287,7 +332,7 @@ mod benchmarks {
 	) -> Result<(), BenchmarkError> {
 		let caller = whitelisted_caller::<T::AccountId>();
 		// Give target existing locks.
-		T::Currency::make_free_balance_be(&caller, T::Currency::minimum_balance());
+		T::Currency::make_free_balance_be(&caller, <T::Currency as Currency<_>>::minimum_balance());
 		add_locks::<T>(&caller, l as u8);
 		// Add max vesting schedules.
 		let expected_balance = add_vesting_schedules::<T>(&caller, s)?;
@@ -314,7 +359,10 @@ mod benchmarks {
 			1_u32.into(),
 		);
 		let expected_index = (s - 2) as usize;
-		assert_eq!(Vesting::<T>::get(&caller).unwrap()[expected_index], expected_schedule);
+		assert_eq!(
+			Vesting::<T>::get(&caller).unwrap()[expected_index],
+			VestingCurve::from(expected_schedule)
+		);
 		assert_eq!(
 			Pallet::<T>::vesting_balance(&caller),
 			Some(expected_balance),
@@ -339,7 +387,7 @@ mod benchmarks {
 
 		let caller = whitelisted_caller::<T::AccountId>();
 		// Give target existing locks.
-		T::Currency::make_free_balance_be(&caller, T::Currency::minimum_balance());
+		T::Currency::make_free_balance_be(&caller, <T::Currency as Currency<_>>::minimum_balance());
 		add_locks::<T>(&caller, l as u8);
 		// Add max vesting schedules.
 		let total_transferred = add_vesting_schedules::<T>(&caller, s)?;
@@ -380,7 +428,7 @@ mod benchmarks {
 		let expected_index = (s - 2) as usize;
 		assert_eq!(
 			Vesting::<T>::get(&caller).unwrap()[expected_index],
-			expected_schedule,
+			VestingCurve::from(expected_schedule),
 			"New schedule is properly created and placed"
 		);
 		assert_eq!(
@@ -414,7 +462,7 @@ mod benchmarks {
 
 		let target = account::<T::AccountId>("target", 0, SEED);
 		let target_lookup = T::Lookup::unlookup(target.clone());
-		T::Currency::make_free_balance_be(&target, T::Currency::minimum_balance());
+		T::Currency::make_free_balance_be(&target, <T::Currency as Currency<_>>::minimum_balance());
 
 		// Give target existing locks.
 		add_locks::<T>(&target, l as u8);
@@ -435,6 +483,46 @@ mod benchmarks {
 		Ok(())
 	}
 
+	#[benchmark]
+	fn revoke_vesting_schedule(
+		l: Linear<0, { MaxLocksOf::<T>::get() - 1 }>,
+		s: Linear<2, { T::MAX_VESTING_SCHEDULES }>,
+	) -> Result<(), BenchmarkError> {
+		let origin =
+			T::RevokeOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+
+		let source = account::<T::AccountId>("source", 0, SEED);
+		let target = account::<T::AccountId>("target", 0, SEED);
+		let target_lookup = T::Lookup::unlookup(target.clone());
+		T::Currency::make_free_balance_be(&target, <T::Currency as Currency<_>>::minimum_balance());
+		let beneficiary = account::<T::AccountId>("beneficiary", 0, SEED);
+		let beneficiary_lookup = T::Lookup::unlookup(beneficiary.clone());
+
+		// Give target existing locks and all but one schedule.
+		add_locks::<T>(&target, l as u8);
+		add_vesting_schedules::<T>(&target, s - 1)?;
+
+		// The last vesting schedule is the revocable one, with nothing vested yet.
+		let min_transfer = T::MinVestedTransfer::get();
+		let locked = min_transfer.checked_mul(&20_u32.into()).unwrap();
+		let schedule = VestingCurve::new(VestingInfo::new(locked, min_transfer, 1_u32.into()))
+			.with_revocable(true);
+		assert_ok!(Pallet::<T>::do_vested_transfer(&source, &target, schedule));
+		let schedule_index = s - 1;
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, target_lookup, schedule_index, beneficiary_lookup);
+
+		assert_eq!(
+			Vesting::<T>::get(&target).unwrap().len(),
+			schedule_index as usize,
+			"Schedule count should reduce by 1"
+		);
+		assert_eq!(T::Currency::free_balance(&beneficiary), locked);
+
+		Ok(())
+	}
+
 	impl_benchmark_test_suite! {
 		Pallet,
 		mock::ExtBuilder::default().existential_deposit(256).build(),
@@ -27,6 +27,12 @@
 //! amount for any reason other than the ones specified in `UnvestedFundsAllowedWithdrawReasons`
 //! configuration value.
 //!
+//! The curve can be shaped further with a [`VestingCurve`]: it may have a cliff, before which
+//! nothing unlocks, and a period, so that the vested funds unlock in tranches rather than every
+//! block. A curve can also be made revocable, letting `RevokeOrigin` cancel it and take back the
+//! unvested funds. The unvested funds of revocable schedules are also put on hold, so that the
+//! owner cannot prevent their revocation by locking them elsewhere.
+//!
 //! As the amount vested increases over time, the amount unvested reduces. However, locks remain in
 //! place and explicit action is needed on behalf of the user to ensure that the amount locked is
 //! equivalent to the amount remaining to be vested. This is done through a dispatchable function,
@@ -42,6 +48,11 @@
 //! - `vest` - Update the lock, reducing it in line with the amount "vested" so far.
 //! - `vest_other` - Update the lock of another account, reducing it in line with the amount
 //!   "vested" so far.
+//! - `vested_transfer_with_curve` - Transfer funds vesting along a cliff or in tranches.
+//! - `force_vested_transfer_with_curve` - Transfer funds vesting along any curve, including
+//!   revocable ones.
+//! - `revoke_vesting_schedule` - Cancel a revocable schedule, transferring its unvested funds to a
+//!   beneficiary.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
@@ -66,6 +77,8 @@ use frame_support::{
 	ensure,
 	storage::bounded_vec::BoundedVec,
 	traits::{
+		fungible::MutateHold,
+		tokens::{Fortitude, Precision, Restriction},
 		Currency, ExistenceRequirement, Get, LockIdentifier, LockableCurrency, VestedTransfer,
 		VestingSchedule, WithdrawReasons,
 	},
@@ -99,6 +112,7 @@ const VESTING_ID: LockIdentifier = *b"vesting ";
 pub enum Releases {
 	V0,
 	V1,
+	V2,
 }
 
 impl Default for Releases {
@@ -131,8 +145,8 @@ impl VestingAction {
 	/// Pick the schedules that this action dictates should continue vesting undisturbed.
 	fn pick_schedules<T: Config>(
 		&self,
-		schedules: Vec<VestingInfo<BalanceOf<T>, BlockNumberFor<T>>>,
-	) -> impl Iterator<Item = VestingInfo<BalanceOf<T>, BlockNumberFor<T>>> + '_ {
+		schedules: Vec<VestingCurve<BalanceOf<T>, BlockNumberFor<T>>>,
+	) -> impl Iterator<Item = VestingCurve<BalanceOf<T>, BlockNumberFor<T>>> + '_ {
 		schedules.into_iter().enumerate().filter_map(move |(index, schedule)| {
 			if self.should_remove(index) {
 				None
@@ -164,7 +178,11 @@ pub mod pallet {
 		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
 
 		/// The currency trait.
-		type Currency: LockableCurrency<Self::AccountId>;
+		type Currency: LockableCurrency<Self::AccountId>
+			+ MutateHold<Self::AccountId, Reason = Self::RuntimeHoldReason, Balance = BalanceOf<Self>>;
+
+		/// The overarching hold reason.
+		type RuntimeHoldReason: From<HoldReason>;
 
 		/// Convert the block number into a balance.
 		type BlockNumberToBalance: Convert<BlockNumberFor<Self>, BalanceOf<Self>>;
@@ -204,6 +222,9 @@ pub mod pallet {
 		///     parachain is lagging its block production to avoid clock skew.
 		type BlockNumberProvider: BlockNumberProvider<BlockNumber = BlockNumberFor<Self>>;
 
+		/// The origin that may revoke revocable vesting schedules.
+		type RevokeOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
 		/// Maximum number of vesting schedules an account may have at a given moment.
 		const MAX_VESTING_SCHEDULES: u32;
 	}
@@ -216,6 +237,14 @@ pub mod pallet {
 		}
 	}
 
+	/// A reason for the pallet placing a hold on funds.
+	#[pallet::composite_enum]
+	pub enum HoldReason {
+		/// The funds of a revocable schedule that have not vested yet.
+		#[codec(index = 0)]
+		RevocableSchedule,
+	}
+
 	#[pallet::hooks]
 	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
 		fn integrity_test() {
@@ -229,7 +258,7 @@ pub mod pallet {
 		_,
 		Blake2_128Concat,
 		T::AccountId,
-		BoundedVec<VestingInfo<BalanceOf<T>, BlockNumberFor<T>>, MaxVestingSchedulesGet<T>>,
+		BoundedVec<VestingCurve<BalanceOf<T>, BlockNumberFor<T>>, MaxVestingSchedulesGet<T>>,
 	>;
 
 	/// Storage version of the pallet.
@@ -253,7 +282,7 @@ pub mod pallet {
 			use sp_runtime::traits::Saturating;
 
 			// Genesis uses the latest storage version.
-			StorageVersion::<T>::put(Releases::V1);
+			StorageVersion::<T>::put(Releases::V2);
 
 			// Generate initial vesting configuration
 			// * who - Account which we are generating vesting configuration for
@@ -272,7 +301,7 @@ pub mod pallet {
 					panic!("Invalid VestingInfo params at genesis")
 				};
 
-				Vesting::<T>::try_append(who, vesting_info)
+				Vesting::<T>::try_append(who, VestingCurve::from(vesting_info))
 					.expect("Too many vesting schedules at genesis.");
 
 				let reasons =
@@ -293,6 +322,13 @@ pub mod pallet {
 		VestingUpdated { account: T::AccountId, unvested: BalanceOf<T> },
 		/// An \[account\] has become fully vested.
 		VestingCompleted { account: T::AccountId },
+		/// A vesting schedule has been revoked and its unvested funds moved to `beneficiary`.
+		VestingRevoked {
+			account: T::AccountId,
+			schedule_index: u32,
+			beneficiary: T::AccountId,
+			unvested: BalanceOf<T>,
+		},
 	}
 
 	/// Error for the vesting pallet.
@@ -309,6 +345,12 @@ pub mod pallet {
 		ScheduleIndexOutOfBounds,
 		/// Failed to create a new schedule because some parameter was invalid.
 		InvalidScheduleParams,
+		/// The vesting schedule is not revocable.
+		NotRevocable,
+		/// Revocable vesting schedules cannot be merged.
+		CannotMergeRevocable,
+		/// Revocable vesting schedules can only be created by `force_vested_transfer_with_curve`.
+		RevocableNotAllowed,
 	}
 
 	#[pallet::call]
@@ -376,7 +418,7 @@ pub mod pallet {
 		) -> DispatchResult {
 			let transactor = ensure_signed(origin)?;
 			let target = T::Lookup::lookup(target)?;
-			Self::do_vested_transfer(&transactor, &target, schedule)
+			Self::do_vested_transfer(&transactor, &target, schedule.into())
 		}
 
 		/// Force a vested transfer.
@@ -406,7 +448,7 @@ pub mod pallet {
 			ensure_root(origin)?;
 			let target = T::Lookup::lookup(target)?;
 			let source = T::Lookup::lookup(source)?;
-			Self::do_vested_transfer(&source, &target, schedule)
+			Self::do_vested_transfer(&source, &target, schedule.into())
 		}
 
 		/// Merge two vesting schedules together, creating a new vesting schedule that unlocks over
@@ -425,6 +467,10 @@ pub mod pallet {
 		///   current_block)`.
 		/// - `ending_block`: `MAX(schedule1.ending_block, schedule2.ending_block)`.
 		/// - `locked`: `schedule1.locked_at(current_block) + schedule2.locked_at(current_block)`.
+		/// - `cliff`: `MAX(schedule1.cliff, schedule2.cliff)`.
+		/// - `period`: `MAX(schedule1.period, schedule2.period)`.
+		///
+		/// Revocable schedules cannot be merged.
 		///
 		/// The dispatch origin for this call must be _Signed_.
 		///
@@ -451,9 +497,10 @@ pub mod pallet {
 			let merge_action =
 				VestingAction::Merge { index1: schedule1_index, index2: schedule2_index };
 
+			let was_holding = Self::has_revocable(&schedules);
 			let (schedules, locked_now) = Self::exec_action(schedules.to_vec(), merge_action)?;
 
-			Self::write_vesting(&who, schedules)?;
+			Self::write_vesting(&who, schedules, was_holding)?;
 			Self::write_lock(&who, locked_now);
 
 			Ok(())
@@ -488,6 +535,135 @@ pub mod pallet {
 			))
 			.into())
 		}
+
+		/// Revoke a revocable vesting schedule, moving the funds it has not vested yet from the
+		/// hold on `target` to `beneficiary`. What has already vested stays with `target`.
+		///
+		/// The dispatch origin for this call must be `RevokeOrigin`.
+		///
+		/// - `target`: The account whose schedule should be revoked.
+		/// - `schedule_index`: The index of the revocable schedule.
+		/// - `beneficiary`: The account receiving the unvested funds.
+		///
+		/// Emits `VestingRevoked`.
+		///
+		/// NOTE: This will unlock all schedules through the current block.
+		#[pallet::call_index(6)]
+		#[pallet::weight(
+			T::WeightInfo::revoke_vesting_schedule(MaxLocksOf::<T>::get(), T::MAX_VESTING_SCHEDULES)
+		)]
+		pub fn revoke_vesting_schedule(
+			origin: OriginFor<T>,
+			target: AccountIdLookupOf<T>,
+			schedule_index: u32,
+			beneficiary: AccountIdLookupOf<T>,
+		) -> DispatchResultWithPostInfo {
+			T::RevokeOrigin::ensure_origin(origin)?;
+			let who = T::Lookup::lookup(target)?;
+			let beneficiary = T::Lookup::lookup(beneficiary)?;
+
+			let schedules = Vesting::<T>::get(&who).ok_or(Error::<T>::NotVesting)?;
+			let schedules_count = schedules.len() as u32;
+			let schedule = *schedules
+				.get(schedule_index as usize)
+				.ok_or(Error::<T>::ScheduleIndexOutOfBounds)?;
+			ensure!(schedule.is_revocable(), Error::<T>::NotRevocable);
+
+			let now = T::BlockNumberProvider::current_block_number();
+			let unvested = schedule.locked_at::<T::BlockNumberToBalance>(now);
+
+			let remove_action = VestingAction::Remove { index: schedule_index as usize };
+			let (schedules, locked_now) = Self::exec_action(schedules.to_vec(), remove_action)?;
+
+			// The unvested funds are held, so `target` cannot keep them from being moved out.
+			let unvested = T::Currency::transfer_on_hold(
+				&HoldReason::RevocableSchedule.into(),
+				&who,
+				&beneficiary,
+				unvested,
+				Precision::BestEffort,
+				Restriction::Free,
+				Fortitude::Force,
+			)?;
+
+			Self::write_vesting(&who, schedules, true)?;
+			Self::write_lock(&who, locked_now);
+
+			Self::deposit_event(Event::<T>::VestingRevoked {
+				account: who,
+				schedule_index,
+				beneficiary,
+				unvested,
+			});
+
+			Ok(Some(T::WeightInfo::revoke_vesting_schedule(
+				MaxLocksOf::<T>::get(),
+				schedules_count,
+			))
+			.into())
+		}
+
+		/// Create a vested transfer vesting along a curve, which may have a cliff and unlock in
+		/// tranches.
+		///
+		/// The dispatch origin for this call must be _Signed_.
+		///
+		/// - `target`: The account receiving the vested funds.
+		/// - `schedule`: The vesting curve attached to the transfer. Must not be revocable.
+		///
+		/// Emits `VestingCreated`.
+		///
+		/// NOTE: This will unlock all schedules through the current block.
+		///
+		/// ## Complexity
+		/// - `O(1)`.
+		#[pallet::call_index(7)]
+		#[pallet::weight(
+			T::WeightInfo::vested_transfer(MaxLocksOf::<T>::get(), T::MAX_VESTING_SCHEDULES)
+		)]
+		pub fn vested_transfer_with_curve(
+			origin: OriginFor<T>,
+			target: AccountIdLookupOf<T>,
+			schedule: VestingCurve<BalanceOf<T>, BlockNumberFor<T>>,
+		) -> DispatchResult {
+			let transactor = ensure_signed(origin)?;
+			// Revocable schedules cannot be merged, so anyone could otherwise fill up the
+			// schedules of `target`.
+			ensure!(!schedule.is_revocable(), Error::<T>::RevocableNotAllowed);
+			let target = T::Lookup::lookup(target)?;
+			Self::do_vested_transfer(&transactor, &target, schedule)
+		}
+
+		/// Force a vested transfer vesting along a curve.
+		///
+		/// The dispatch origin for this call must be _Root_.
+		///
+		/// - `source`: The account whose funds should be transferred.
+		/// - `target`: The account that should be transferred the vested funds.
+		/// - `schedule`: The vesting curve attached to the transfer. If it is revocable, its
+		///   unvested funds are put on hold as well.
+		///
+		/// Emits `VestingCreated`.
+		///
+		/// NOTE: This will unlock all schedules through the current block.
+		///
+		/// ## Complexity
+		/// - `O(1)`.
+		#[pallet::call_index(8)]
+		#[pallet::weight(
+			T::WeightInfo::force_vested_transfer_with_curve(MaxLocksOf::<T>::get(), T::MAX_VESTING_SCHEDULES)
+		)]
+		pub fn force_vested_transfer_with_curve(
+			origin: OriginFor<T>,
+			source: AccountIdLookupOf<T>,
+			target: AccountIdLookupOf<T>,
+			schedule: VestingCurve<BalanceOf<T>, BlockNumberFor<T>>,
+		) -> DispatchResult {
+			ensure_root(origin)?;
+			let target = T::Lookup::lookup(target)?;
+			let source = T::Lookup::lookup(source)?;
+			Self::do_vested_transfer(&source, &target, schedule)
+		}
 	}
 }
 
@@ -495,18 +671,18 @@ impl<T: Config> Pallet<T> {
 	// Public function for accessing vesting storage
 	pub fn vesting(
 		account: T::AccountId,
-	) -> Option<BoundedVec<VestingInfo<BalanceOf<T>, BlockNumberFor<T>>, MaxVestingSchedulesGet<T>>>
+	) -> Option<BoundedVec<VestingCurve<BalanceOf<T>, BlockNumberFor<T>>, MaxVestingSchedulesGet<T>>>
 	{
 		Vesting::<T>::get(account)
 	}
 
-	// Create a new `VestingInfo`, based off of two other `VestingInfo`s.
+	// Create a new `VestingCurve`, based off of two other `VestingCurve`s.
 	// NOTE: We assume both schedules have had funds unlocked up through the current block.
 	fn merge_vesting_info(
 		now: BlockNumberFor<T>,
-		schedule1: VestingInfo<BalanceOf<T>, BlockNumberFor<T>>,
-		schedule2: VestingInfo<BalanceOf<T>, BlockNumberFor<T>>,
-	) -> Option<VestingInfo<BalanceOf<T>, BlockNumberFor<T>>> {
+		schedule1: VestingCurve<BalanceOf<T>, BlockNumberFor<T>>,
+		schedule2: VestingCurve<BalanceOf<T>, BlockNumberFor<T>>,
+	) -> Option<VestingCurve<BalanceOf<T>, BlockNumberFor<T>>> {
 		let schedule1_ending_block = schedule1.ending_block_as_balance::<T::BlockNumberToBalance>();
 		let schedule2_ending_block = schedule2.ending_block_as_balance::<T::BlockNumberToBalance>();
 		let now_as_balance = T::BlockNumberToBalance::convert(now);
@@ -543,7 +719,12 @@ impl<T: Config> Pallet<T> {
 			(locked / duration).max(One::one())
 		};
 
-		let schedule = VestingInfo::new(locked, per_block, starting_block);
+		let cliff = schedule1.cliff().max(schedule2.cliff());
+		let period = schedule1.period().max(schedule2.period());
+
+		let schedule = VestingCurve::new(VestingInfo::new(locked, per_block, starting_block))
+			.with_cliff(cliff)
+			.with_period(period);
 		debug_assert!(schedule.is_valid(), "merge_vesting_info schedule validation check failed");
 
 		Some(schedule)
@@ -553,7 +734,7 @@ impl<T: Config> Pallet<T> {
 	fn do_vested_transfer(
 		source: &T::AccountId,
 		target: &T::AccountId,
-		schedule: VestingInfo<BalanceOf<T>, BlockNumberFor<T>>,
+		schedule: VestingCurve<BalanceOf<T>, BlockNumberFor<T>>,
 	) -> DispatchResult {
 		// Validate user inputs.
 		ensure!(schedule.locked() >= T::MinVestedTransfer::get(), Error::<T>::AmountLow);
@@ -562,25 +743,68 @@ impl<T: Config> Pallet<T> {
 		};
 
 		// Check we can add to this account prior to any storage writes.
-		Self::can_add_vesting_schedule(
-			target,
-			schedule.locked(),
-			schedule.per_block(),
-			schedule.starting_block(),
-		)?;
+		Self::can_add_schedule(target, &schedule)?;
 
 		T::Currency::transfer(source, target, schedule.locked(), ExistenceRequirement::AllowDeath)?;
 
-		// We can't let this fail because the currency transfer has already happened.
-		// Must be successful as it has been checked before.
-		// Better to return error on failure anyway.
-		let res = Self::add_vesting_schedule(
-			target,
-			schedule.locked(),
-			schedule.per_block(),
-			schedule.starting_block(),
+		// Only holding the funds of a revocable schedule may fail here, and our callers roll
+		// back the transfer when it does.
+		let res = Self::add_schedule(target, schedule);
+		debug_assert!(
+			res.is_ok() || schedule.is_revocable(),
+			"Failed to add a schedule when we had to succeed."
+		);
+
+		res
+	}
+
+	/// Add `schedule` to the schedules of `who` and update their lock.
+	fn add_schedule(
+		who: &T::AccountId,
+		schedule: VestingCurve<BalanceOf<T>, BlockNumberFor<T>>,
+	) -> DispatchResult {
+		// Check for `per_block`, `period` or `locked` of 0.
+		if !schedule.is_valid() {
+			return Err(Error::<T>::InvalidScheduleParams.into())
+		};
+
+		let mut schedules = Vesting::<T>::get(who).unwrap_or_default();
+		let was_holding = Self::has_revocable(&schedules);
+
+		// NOTE: we must push the new schedule so that `exec_action`
+		// will give the correct new locked amount.
+		ensure!(schedules.try_push(schedule).is_ok(), Error::<T>::AtMaxVestingSchedules);
+
+		debug_assert!(schedules.len() > 0, "schedules cannot be empty after insertion");
+		let schedule_index = schedules.len() - 1;
+		Self::deposit_event(Event::<T>::VestingCreated {
+			account: who.clone(),
+			schedule_index: schedule_index as u32,
+		});
+
+		let (schedules, locked_now) =
+			Self::exec_action(schedules.to_vec(), VestingAction::Passive)?;
+
+		Self::write_vesting(who, schedules, was_holding || schedule.is_revocable())?;
+		Self::write_lock(who, locked_now);
+
+		Ok(())
+	}
+
+	/// Ensure we can call `add_schedule` without error.
+	fn can_add_schedule(
+		who: &T::AccountId,
+		schedule: &VestingCurve<BalanceOf<T>, BlockNumberFor<T>>,
+	) -> DispatchResult {
+		// Check for `per_block`, `period` or `locked` of 0.
+		if !schedule.is_valid() {
+			return Err(Error::<T>::InvalidScheduleParams.into())
+		}
+
+		ensure!(
+			(Vesting::<T>::decode_len(who).unwrap_or_default() as u32) < T::MAX_VESTING_SCHEDULES,
+			Error::<T>::AtMaxVestingSchedules
 		);
-		debug_assert!(res.is_ok(), "Failed to add a schedule when we had to succeed.");
 
 		Ok(())
 	}
@@ -596,9 +820,9 @@ impl<T: Config> Pallet<T> {
 	///
 	/// NOTE: the amount locked does not include any schedules that are filtered out via `action`.
 	fn report_schedule_updates(
-		schedules: Vec<VestingInfo<BalanceOf<T>, BlockNumberFor<T>>>,
+		schedules: Vec<VestingCurve<BalanceOf<T>, BlockNumberFor<T>>>,
 		action: VestingAction,
-	) -> (Vec<VestingInfo<BalanceOf<T>, BlockNumberFor<T>>>, BalanceOf<T>) {
+	) -> (Vec<VestingCurve<BalanceOf<T>, BlockNumberFor<T>>>, BalanceOf<T>) {
 		let now = T::BlockNumberProvider::current_block_number();
 
 		let mut total_locked_now: BalanceOf<T> = Zero::zero();
@@ -618,6 +842,8 @@ impl<T: Config> Pallet<T> {
 	}
 
 	/// Write an accounts updated vesting lock to storage.
+	///
+	/// The lock also covers the funds held for revocable schedules, as locks overlap holds.
 	fn write_lock(who: &T::AccountId, total_locked_now: BalanceOf<T>) {
 		if total_locked_now.is_zero() {
 			T::Currency::remove_lock(VESTING_ID, who);
@@ -632,13 +858,40 @@ impl<T: Config> Pallet<T> {
 		};
 	}
 
-	/// Write an accounts updated vesting schedules to storage.
+	/// Whether any of `schedules` is revocable, and so has its unvested funds held.
+	fn has_revocable(schedules: &[VestingCurve<BalanceOf<T>, BlockNumberFor<T>>]) -> bool {
+		schedules.iter().any(|schedule| schedule.is_revocable())
+	}
+
+	/// Write an accounts updated vesting schedules to storage, and hold the funds their
+	/// revocable schedules have not vested yet.
+	///
+	/// `was_holding` tells whether the account may have funds on hold from before, so that
+	/// accounts without revocable schedules never touch their holds.
 	fn write_vesting(
 		who: &T::AccountId,
-		schedules: Vec<VestingInfo<BalanceOf<T>, BlockNumberFor<T>>>,
+		schedules: Vec<VestingCurve<BalanceOf<T>, BlockNumberFor<T>>>,
+		was_holding: bool,
 	) -> Result<(), DispatchError> {
+		let now = T::BlockNumberProvider::current_block_number();
+		let held_now = schedules.iter().filter(|schedule| schedule.is_revocable()).fold(
+			Zero::zero(),
+			|total: BalanceOf<T>, schedule| {
+				total.saturating_add(schedule.locked_at::<T::BlockNumberToBalance>(now))
+			},
+		);
+		if was_holding || !held_now.is_zero() {
+			let reason = HoldReason::RevocableSchedule.into();
+			let held = T::Currency::balance_on_hold(&reason, who);
+			if held_now > held {
+				T::Currency::hold(&reason, who, held_now - held)?;
+			} else if held > held_now {
+				T::Currency::release(&reason, who, held - held_now, Precision::BestEffort)?;
+			}
+		}
+
 		let schedules: BoundedVec<
-			VestingInfo<BalanceOf<T>, BlockNumberFor<T>>,
+			VestingCurve<BalanceOf<T>, BlockNumberFor<T>>,
 			MaxVestingSchedulesGet<T>,
 		> = schedules.try_into().map_err(|_| Error::<T>::AtMaxVestingSchedules)?;
 
@@ -654,11 +907,12 @@ impl<T: Config> Pallet<T> {
 	/// Unlock any vested funds of `who`.
 	fn do_vest(who: T::AccountId) -> DispatchResult {
 		let schedules = Vesting::<T>::get(&who).ok_or(Error::<T>::NotVesting)?;
+		let was_holding = Self::has_revocable(&schedules);
 
 		let (schedules, locked_now) =
 			Self::exec_action(schedules.to_vec(), VestingAction::Passive)?;
 
-		Self::write_vesting(&who, schedules)?;
+		Self::write_vesting(&who, schedules, was_holding)?;
 		Self::write_lock(&who, locked_now);
 
 		Ok(())
@@ -667,15 +921,20 @@ impl<T: Config> Pallet<T> {
 	/// Execute a `VestingAction` against the given `schedules`. Returns the updated schedules
 	/// and locked amount.
 	fn exec_action(
-		schedules: Vec<VestingInfo<BalanceOf<T>, BlockNumberFor<T>>>,
+		schedules: Vec<VestingCurve<BalanceOf<T>, BlockNumberFor<T>>>,
 		action: VestingAction,
-	) -> Result<(Vec<VestingInfo<BalanceOf<T>, BlockNumberFor<T>>>, BalanceOf<T>), DispatchError> {
+	) -> Result<(Vec<VestingCurve<BalanceOf<T>, BlockNumberFor<T>>>, BalanceOf<T>), DispatchError>
+	{
 		let (schedules, locked_now) = match action {
 			VestingAction::Merge { index1: idx1, index2: idx2 } => {
 				// The schedule index is based off of the schedule ordering prior to filtering out
 				// any schedules that may be ending at this block.
 				let schedule1 = *schedules.get(idx1).ok_or(Error::<T>::ScheduleIndexOutOfBounds)?;
 				let schedule2 = *schedules.get(idx2).ok_or(Error::<T>::ScheduleIndexOutOfBounds)?;
+				ensure!(
+					!schedule1.is_revocable() && !schedule2.is_revocable(),
+					Error::<T>::CannotMergeRevocable
+				);
 
 				// The length of `schedules` decreases by 2 here since we filter out 2 schedules.
 				// Thus we know below that we can push the new merged schedule without error
@@ -720,9 +979,13 @@ where
 	fn vesting_balance(who: &T::AccountId) -> Option<BalanceOf<T>> {
 		if let Some(v) = Vesting::<T>::get(who) {
 			let now = T::BlockNumberProvider::current_block_number();
-			let total_locked_now = v.iter().fold(Zero::zero(), |total, schedule| {
-				schedule.locked_at::<T::BlockNumberToBalance>(now).saturating_add(total)
-			});
+			// The funds of revocable schedules are held, so they are not part of the free balance.
+			let total_locked_now = v.iter().filter(|schedule| !schedule.is_revocable()).fold(
+				Zero::zero(),
+				|total, schedule| {
+					schedule.locked_at::<T::BlockNumberToBalance>(now).saturating_add(total)
+				},
+			);
 			Some(T::Currency::free_balance(who).min(total_locked_now))
 		} else {
 			None
@@ -751,32 +1014,7 @@ where
 			return Ok(())
 		}
 
-		let vesting_schedule = VestingInfo::new(locked, per_block, starting_block);
-		// Check for `per_block` or `locked` of 0.
-		if !vesting_schedule.is_valid() {
-			return Err(Error::<T>::InvalidScheduleParams.into())
-		};
-
-		let mut schedules = Vesting::<T>::get(who).unwrap_or_default();
-
-		// NOTE: we must push the new schedule so that `exec_action`
-		// will give the correct new locked amount.
-		ensure!(schedules.try_push(vesting_schedule).is_ok(), Error::<T>::AtMaxVestingSchedules);
-
-		debug_assert!(schedules.len() > 0, "schedules cannot be empty after insertion");
-		let schedule_index = schedules.len() - 1;
-		Self::deposit_event(Event::<T>::VestingCreated {
-			account: who.clone(),
-			schedule_index: schedule_index as u32,
-		});
-
-		let (schedules, locked_now) =
-			Self::exec_action(schedules.to_vec(), VestingAction::Passive)?;
-
-		Self::write_vesting(who, schedules)?;
-		Self::write_lock(who, locked_now);
-
-		Ok(())
+		Self::add_schedule(who, VestingInfo::new(locked, per_block, starting_block).into())
 	}
 
 	/// Ensure we can call `add_vesting_schedule` without error. This should always
@@ -787,27 +1025,18 @@ where
 		per_block: BalanceOf<T>,
 		starting_block: BlockNumberFor<T>,
 	) -> DispatchResult {
-		// Check for `per_block` or `locked` of 0.
-		if !VestingInfo::new(locked, per_block, starting_block).is_valid() {
-			return Err(Error::<T>::InvalidScheduleParams.into())
-		}
-
-		ensure!(
-			(Vesting::<T>::decode_len(who).unwrap_or_default() as u32) < T::MAX_VESTING_SCHEDULES,
-			Error::<T>::AtMaxVestingSchedules
-		);
-
-		Ok(())
+		Self::can_add_schedule(who, &VestingInfo::new(locked, per_block, starting_block).into())
 	}
 
 	/// Remove a vesting schedule for a given account.
 	fn remove_vesting_schedule(who: &T::AccountId, schedule_index: u32) -> DispatchResult {
 		let schedules = Vesting::<T>::get(who).ok_or(Error::<T>::NotVesting)?;
+		let was_holding = Self::has_revocable(&schedules);
 		let remove_action = VestingAction::Remove { index: schedule_index as usize };
 
 		let (schedules, locked_now) = Self::exec_action(schedules.to_vec(), remove_action)?;
 
-		Self::write_vesting(who, schedules)?;
+		Self::write_vesting(who, schedules, was_holding)?;
 		Self::write_lock(who, locked_now);
 		Ok(())
 	}
@@ -830,7 +1059,7 @@ where
 		starting_block: BlockNumberFor<T>,
 	) -> DispatchResult {
 		use frame_support::storage::{with_transaction, TransactionOutcome};
-		let schedule = VestingInfo::new(locked, per_block, starting_block);
+		let schedule = VestingInfo::new(locked, per_block, starting_block).into();
 		with_transaction(|| -> TransactionOutcome<DispatchResult> {
 			let result = Self::do_vested_transfer(source, target, schedule);
 
@@ -19,6 +19,16 @@
 
 use super::*;
 use alloc::vec;
+use frame_support::pallet_prelude::{Blake2_128Concat, ConstU32, StorageMap};
+
+/// The `Vesting` storage as of [`Releases::V1`], only able to unlock linearly.
+#[frame_support::storage_alias]
+type VestingV1<T: Config> = StorageMap<
+	Pallet<T>,
+	Blake2_128Concat,
+	<T as frame_system::Config>::AccountId,
+	BoundedVec<VestingInfo<BalanceOf<T>, BlockNumberFor<T>>, MaxVestingSchedulesGet<T>>,
+>;
 
 // Migration from single schedule to multiple schedules.
 pub mod v1 {
@@ -41,7 +51,7 @@ pub mod v1 {
 	pub fn migrate<T: Config>() -> Weight {
 		let mut reads_writes = 0;
 
-		Vesting::<T>::translate::<VestingInfo<BalanceOf<T>, BlockNumberFor<T>>, _>(
+		VestingV1::<T>::translate::<VestingInfo<BalanceOf<T>, BlockNumberFor<T>>, _>(
 			|_key, vesting_info| {
 				reads_writes += 1;
 				let v: Option<
@@ -69,7 +79,7 @@ pub mod v1 {
 	pub fn post_migrate<T: Config>() -> Result<(), &'static str> {
 		assert_eq!(StorageVersion::<T>::get(), Releases::V1);
 
-		for (_key, schedules) in Vesting::<T>::iter() {
+		for (_key, schedules) in VestingV1::<T>::iter() {
 			assert!(
 				schedules.len() >= 1,
 				"A bounded vec with incorrect count of items was created."
@@ -94,3 +104,130 @@ pub mod v1 {
 		Ok(())
 	}
 }
+
+// Migration from linear schedules to curves with a cliff, a period and revocability.
+pub mod v2 {
+	use super::*;
+	use frame_support::{
+		migrations::{MigrationId, SteppedMigration, SteppedMigrationError},
+		traits::StorageInfoTrait,
+		weights::WeightMeter,
+		IterableStorageMap,
+	};
+	#[cfg(feature = "try-runtime")]
+	use sp_runtime::TryRuntimeError;
+
+	/// The identifier of the vesting pallet in multi-block migrations.
+	pub const PALLET_MIGRATIONS_ID: &[u8; 14] = b"pallet-vesting";
+
+	type HashedKey = BoundedVec<u8, ConstU32<256>>;
+
+	/// Migrate the schedules from [`Releases::V1`] to [`Releases::V2`], one account per step.
+	///
+	/// Every schedule becomes a [`VestingCurve`] that keeps unlocking as before: without a cliff,
+	/// every block and irrevocably. The cursor is the raw storage key of the last migrated account.
+	pub struct MigrateV1ToV2<T>(PhantomData<T>);
+
+	impl<T: Config> MigrateV1ToV2<T> {
+		/// The worst case weight of migrating the schedules of one account.
+		pub fn step_weight() -> Weight {
+			let max_size = Vesting::<T>::storage_info()
+				.iter()
+				.filter_map(|info| info.max_size)
+				.fold(0_u64, |total, size| total.saturating_add(size as u64));
+			// Reading a value also proves the trie nodes above it, up to 5 layers of 15
+			// siblings of 33 bytes.
+			let proof_size = max_size.saturating_add(5 * 15 * 33);
+
+			T::DbWeight::get()
+				.reads_writes(1, 1)
+				.saturating_add(Weight::from_parts(0, proof_size))
+		}
+	}
+
+	impl<T: Config> SteppedMigration for MigrateV1ToV2<T> {
+		type Cursor = HashedKey;
+		type Identifier = MigrationId<14>;
+
+		fn id() -> Self::Identifier {
+			MigrationId { pallet_id: *PALLET_MIGRATIONS_ID, version_from: 1, version_to: 2 }
+		}
+
+		fn step(
+			mut cursor: Option<Self::Cursor>,
+			meter: &mut WeightMeter,
+		) -> Result<Option<Self::Cursor>, SteppedMigrationError> {
+			let version_weight = T::DbWeight::get().reads(1);
+			if cursor.is_none() {
+				if !meter.can_consume(version_weight) {
+					return Err(SteppedMigrationError::InsufficientWeight {
+						required: version_weight,
+					})
+				}
+				meter.consume(version_weight);
+				if StorageVersion::<T>::get() != Releases::V1 {
+					log::info!(
+						target: "runtime::vesting",
+						"migration: Vesting storage version v2 migration skipped, not at v1."
+					);
+					return Ok(None)
+				}
+			}
+
+			// Check that we have enough weight for at least the next step. If we don't, then the
+			// migration cannot be complete.
+			let required = Self::step_weight();
+			if meter.remaining().any_lt(required) {
+				return Err(SteppedMigrationError::InsufficientWeight { required })
+			}
+
+			while meter.can_consume(required) {
+				let last_key = Vesting::<T>::translate_next::<
+					BoundedVec<
+						VestingInfo<BalanceOf<T>, BlockNumberFor<T>>,
+						MaxVestingSchedulesGet<T>,
+					>,
+					_,
+				>(cursor.map(|key| key.to_vec()), |_account, schedules| {
+					let schedules =
+						schedules.into_iter().map(VestingCurve::from).collect::<Vec<_>>();
+					// Same bound, so nothing gets truncated.
+					Some(BoundedVec::truncate_from(schedules))
+				});
+				meter.consume(required);
+
+				let Some(last_key) = last_key else {
+					StorageVersion::<T>::put(Releases::V2);
+					log::info!(
+						target: "runtime::vesting",
+						"migration: Vesting storage version v2 migration finished."
+					);
+					return Ok(None)
+				};
+				// Account ids longer than the cursor cannot be migrated.
+				cursor =
+					Some(HashedKey::try_from(last_key).map_err(|_| SteppedMigrationError::Failed)?);
+			}
+
+			Ok(cursor)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<Vec<u8>, TryRuntimeError> {
+			let count = VestingV1::<T>::iter_keys().count() as u32;
+			Ok(count.encode())
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(state: Vec<u8>) -> Result<(), TryRuntimeError> {
+			let count = u32::decode(&mut &state[..])
+				.map_err(|_| TryRuntimeError::Other("failed to decode the vesting count"))?;
+			ensure!(StorageVersion::<T>::get() == Releases::V2, "storage version not updated");
+			ensure!(
+				Vesting::<T>::iter().count() as u32 == count,
+				"vesting accounts lost in the migration"
+			);
+			Ok(())
+		}
+	}
+}
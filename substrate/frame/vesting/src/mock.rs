@@ -16,6 +16,7 @@
 // limitations under the License.
 
 use frame_support::{derive_impl, parameter_types, traits::WithdrawReasons};
+use frame_system::EnsureRoot;
 use sp_runtime::{traits::Identity, BuildStorage};
 
 use super::*;
@@ -58,6 +59,8 @@ impl Config for Test {
 	type WeightInfo = ();
 	type UnvestedFundsAllowedWithdrawReasons = UnvestedFundsAllowedWithdrawReasons;
 	type BlockNumberProvider = System;
+	type RevokeOrigin = EnsureRoot<u64>;
+	type RuntimeHoldReason = RuntimeHoldReason;
 }
 
 pub struct ExtBuilder {
@@ -16,19 +16,35 @@
 // limitations under the License.
 
 use codec::EncodeLike;
-use frame_support::{assert_noop, assert_ok, assert_storage_noop};
+use frame_support::{
+	assert_noop, assert_ok, assert_storage_noop,
+	migrations::{SteppedMigration, SteppedMigrationError},
+	storage::unhashed,
+	traits::fungible::InspectHold,
+	weights::WeightMeter,
+};
 use frame_system::RawOrigin;
 use sp_runtime::{
 	traits::{BadOrigin, Identity},
 	TokenError,
 };
 
-use super::{Vesting as VestingStorage, *};
+use super::{migrations::v2::MigrateV1ToV2, Vesting as VestingStorage, *};
 use crate::mock::{vesting_events_since_last_call, Balances, ExtBuilder, System, Test, Vesting};
 
 /// A default existential deposit.
 const ED: u64 = 256;
 
+/// The curves `schedules` are stored as.
+fn curves(schedules: Vec<VestingInfo<u64, u64>>) -> Vec<VestingCurve<u64, u64>> {
+	schedules.into_iter().map(VestingCurve::from).collect()
+}
+
+/// The funds of `who` held for revocable schedules.
+fn held(who: u64) -> u64 {
+	Balances::balance_on_hold(&HoldReason::RevocableSchedule.into(), &who)
+}
+
 /// Calls vest, and asserts that there is no entry for `account`
 /// in the `Vesting` storage item.
 fn vest_and_assert_no_vesting<T>(account: u64)
@@ -65,9 +81,12 @@ fn check_vesting_status() {
 			64, // Vesting over 20 blocks
 			10,
 		);
-		assert_eq!(VestingStorage::<Test>::get(&1).unwrap(), vec![user1_vesting_schedule]); // Account 1 has a vesting schedule
-		assert_eq!(VestingStorage::<Test>::get(&2).unwrap(), vec![user2_vesting_schedule]); // Account 2 has a vesting schedule
-		assert_eq!(VestingStorage::<Test>::get(&12).unwrap(), vec![user12_vesting_schedule]); // Account 12 has a vesting schedule
+		assert_eq!(VestingStorage::<Test>::get(&1).unwrap(), curves(vec![user1_vesting_schedule])); // Account 1 has a vesting schedule
+		assert_eq!(VestingStorage::<Test>::get(&2).unwrap(), curves(vec![user2_vesting_schedule])); // Account 2 has a vesting schedule
+		assert_eq!(
+			VestingStorage::<Test>::get(&12).unwrap(),
+			curves(vec![user12_vesting_schedule])
+		); // Account 12 has a vesting schedule
 
 		// Account 1 has only 128 units vested from their illiquid ED * 5 units at block 1
 		assert_eq!(Vesting::vesting_balance(&1), Some(128 * 9));
@@ -110,7 +129,7 @@ fn check_vesting_status_for_multi_schedule_account() {
 			10,
 		);
 		// Account 2 already has a vesting schedule.
-		assert_eq!(VestingStorage::<Test>::get(&2).unwrap(), vec![sched0]);
+		assert_eq!(VestingStorage::<Test>::get(&2).unwrap(), curves(vec![sched0]));
 
 		// Account 2's free balance is from sched0.
 		let free_balance = Balances::free_balance(&2);
@@ -128,7 +147,7 @@ fn check_vesting_status_for_multi_schedule_account() {
 		let free_balance = Balances::free_balance(&2);
 		assert_eq!(free_balance, ED * (10 + 20));
 		// The most recently added schedule exists.
-		assert_eq!(VestingStorage::<Test>::get(&2).unwrap(), vec![sched0, sched1]);
+		assert_eq!(VestingStorage::<Test>::get(&2).unwrap(), curves(vec![sched0, sched1]));
 		// sched1 has free funds at block #1, but nothing else.
 		assert_eq!(Vesting::vesting_balance(&2), Some(free_balance - sched1.per_block()));
 
@@ -171,7 +190,7 @@ fn check_vesting_status_for_multi_schedule_account() {
 		assert_eq!(Vesting::vesting_balance(&2), Some(0));
 		// Since we have not called any extrinsics that would unlock funds the schedules
 		// are still in storage,
-		assert_eq!(VestingStorage::<Test>::get(&2).unwrap(), vec![sched0, sched1, sched2]);
+		assert_eq!(VestingStorage::<Test>::get(&2).unwrap(), curves(vec![sched0, sched1, sched2]));
 		// but once we unlock the funds, they are removed from storage.
 		vest_and_assert_no_vesting::<Test>(2);
 	});
@@ -207,7 +226,7 @@ fn vested_balance_should_transfer_with_multi_sched() {
 		let sched0 = VestingInfo::new(5 * ED, 128, 0);
 		assert_ok!(Vesting::vested_transfer(Some(13).into(), 1, sched0));
 		// Total 10*ED locked for all the schedules.
-		assert_eq!(VestingStorage::<Test>::get(&1).unwrap(), vec![sched0, sched0]);
+		assert_eq!(VestingStorage::<Test>::get(&1).unwrap(), curves(vec![sched0, sched0]));
 
 		let user1_free_balance = Balances::free_balance(&1);
 		assert_eq!(user1_free_balance, 3840); // Account 1 has free balance
@@ -245,7 +264,7 @@ fn vested_balance_should_transfer_using_vest_other_with_multi_sched() {
 		let sched0 = VestingInfo::new(5 * ED, 128, 0);
 		assert_ok!(Vesting::vested_transfer(Some(13).into(), 1, sched0));
 		// Total of 10*ED of locked for all the schedules.
-		assert_eq!(VestingStorage::<Test>::get(&1).unwrap(), vec![sched0, sched0]);
+		assert_eq!(VestingStorage::<Test>::get(&1).unwrap(), curves(vec![sched0, sched0]));
 
 		let user1_free_balance = Balances::free_balance(&1);
 		assert_eq!(user1_free_balance, 3840); // Account 1 has free balance
@@ -308,7 +327,10 @@ fn liquid_funds_should_transfer_with_delayed_vesting() {
 			64,
 			10,
 		);
-		assert_eq!(VestingStorage::<Test>::get(&12).unwrap(), vec![user12_vesting_schedule]);
+		assert_eq!(
+			VestingStorage::<Test>::get(&12).unwrap(),
+			curves(vec![user12_vesting_schedule])
+		);
 
 		// Account 12 can still send liquid funds
 		assert_ok!(Balances::transfer_allow_death(Some(12).into(), 3, 256 * 5));
@@ -340,7 +362,7 @@ fn vested_transfer_works() {
 			]
 		);
 		// Now account 4 should have vesting.
-		assert_eq!(VestingStorage::<Test>::get(&4).unwrap(), vec![new_vesting_schedule]);
+		assert_eq!(VestingStorage::<Test>::get(&4).unwrap(), curves(vec![new_vesting_schedule]));
 		// Ensure the transfer happened correctly.
 		let user3_free_balance_updated = Balances::free_balance(&3);
 		assert_eq!(user3_free_balance_updated, 256 * 25);
@@ -379,7 +401,7 @@ fn vested_transfer_correctly_fails() {
 			ED, // Vesting over 20 blocks
 			10,
 		);
-		assert_eq!(VestingStorage::<Test>::get(&2).unwrap(), vec![user2_vesting_schedule]);
+		assert_eq!(VestingStorage::<Test>::get(&2).unwrap(), curves(vec![user2_vesting_schedule]));
 
 		// Fails due to too low transfer amount.
 		let new_vesting_schedule_too_low =
@@ -489,7 +511,10 @@ fn force_vested_transfer_works() {
 			]
 		);
 		// Now account 4 should have vesting.
-		assert_eq!(VestingStorage::<Test>::get(&4).unwrap()[0], new_vesting_schedule);
+		assert_eq!(
+			VestingStorage::<Test>::get(&4).unwrap()[0],
+			VestingCurve::from(new_vesting_schedule)
+		);
 		assert_eq!(VestingStorage::<Test>::get(&4).unwrap().len(), 1);
 		// Ensure the transfer happened correctly.
 		let user3_free_balance_updated = Balances::free_balance(&3);
@@ -528,7 +553,7 @@ fn force_vested_transfer_correctly_fails() {
 			ED, // Vesting over 20 blocks
 			10,
 		);
-		assert_eq!(VestingStorage::<Test>::get(&2).unwrap(), vec![user2_vesting_schedule]);
+		assert_eq!(VestingStorage::<Test>::get(&2).unwrap(), curves(vec![user2_vesting_schedule]));
 
 		// Too low transfer amount.
 		let new_vesting_schedule_too_low =
@@ -614,12 +639,12 @@ fn merge_schedules_that_have_not_started() {
 			ED, // Vest over 20 blocks.
 			10,
 		);
-		assert_eq!(VestingStorage::<Test>::get(&2).unwrap(), vec![sched0]);
+		assert_eq!(VestingStorage::<Test>::get(&2).unwrap(), curves(vec![sched0]));
 		assert_eq!(Balances::usable_balance(&2), 0);
 
 		// Add a schedule that is identical to the one that already exists.
 		assert_ok!(Vesting::vested_transfer(Some(3).into(), 2, sched0));
-		assert_eq!(VestingStorage::<Test>::get(&2).unwrap(), vec![sched0, sched0]);
+		assert_eq!(VestingStorage::<Test>::get(&2).unwrap(), curves(vec![sched0, sched0]));
 		assert_eq!(Balances::usable_balance(&2), 0);
 		assert_ok!(Vesting::merge_schedules(Some(2).into(), 0, 1));
 
@@ -630,7 +655,7 @@ fn merge_schedules_that_have_not_started() {
 			sched0.per_block() * 2,
 			10, // Starts at the block the schedules are merged/
 		);
-		assert_eq!(VestingStorage::<Test>::get(&2).unwrap(), vec![sched1]);
+		assert_eq!(VestingStorage::<Test>::get(&2).unwrap(), curves(vec![sched1]));
 
 		assert_eq!(Balances::usable_balance(&2), 0);
 	});
@@ -646,7 +671,7 @@ fn merge_ongoing_schedules() {
 			ED, // Vest over 20 blocks.
 			10,
 		);
-		assert_eq!(VestingStorage::<Test>::get(&2).unwrap(), vec![sched0]);
+		assert_eq!(VestingStorage::<Test>::get(&2).unwrap(), curves(vec![sched0]));
 
 		let sched1 = VestingInfo::new(
 			ED * 10,
@@ -656,7 +681,7 @@ fn merge_ongoing_schedules() {
 			sched0.starting_block() + 5,
 		);
 		assert_ok!(Vesting::vested_transfer(Some(4).into(), 2, sched1));
-		assert_eq!(VestingStorage::<Test>::get(&2).unwrap(), vec![sched0, sched1]);
+		assert_eq!(VestingStorage::<Test>::get(&2).unwrap(), curves(vec![sched0, sched1]));
 
 		// Got to half way through the second schedule where both schedules are actively vesting.
 		let cur_block = 20;
@@ -688,7 +713,7 @@ fn merge_ongoing_schedules() {
 		let sched2_per_block = sched2_locked / sched2_duration;
 
 		let sched2 = VestingInfo::new(sched2_locked, sched2_per_block, cur_block);
-		assert_eq!(VestingStorage::<Test>::get(&2).unwrap(), vec![sched2]);
+		assert_eq!(VestingStorage::<Test>::get(&2).unwrap(), curves(vec![sched2]));
 
 		// And just to double check, we assert the new merged schedule we be cleaned up as expected.
 		System::set_block_number(30);
@@ -732,7 +757,7 @@ fn merging_shifts_other_schedules_index() {
 		assert_ok!(Vesting::vested_transfer(Some(4).into(), 3, sched2));
 
 		// With no schedules vested or merged they are in the order they are created
-		assert_eq!(VestingStorage::<Test>::get(&3).unwrap(), vec![sched0, sched1, sched2]);
+		assert_eq!(VestingStorage::<Test>::get(&3).unwrap(), curves(vec![sched0, sched1, sched2]));
 		// and the usable balance has not changed.
 		assert_eq!(usable_balance, Balances::usable_balance(&3));
 
@@ -753,7 +778,7 @@ fn merging_shifts_other_schedules_index() {
 		let sched3 = VestingInfo::new(sched3_locked, sched3_per_block, sched3_start);
 
 		// The not touched schedule moves left and the new merged schedule is appended.
-		assert_eq!(VestingStorage::<Test>::get(&3).unwrap(), vec![sched1, sched3]);
+		assert_eq!(VestingStorage::<Test>::get(&3).unwrap(), curves(vec![sched1, sched3]));
 		// The usable balance hasn't changed since none of the schedules have started.
 		assert_eq!(Balances::usable_balance(&3), usable_balance);
 	});
@@ -770,7 +795,7 @@ fn merge_ongoing_and_yet_to_be_started_schedules() {
 			ED, // Vesting over 20 blocks
 			10,
 		);
-		assert_eq!(VestingStorage::<Test>::get(&2).unwrap(), vec![sched0]);
+		assert_eq!(VestingStorage::<Test>::get(&2).unwrap(), curves(vec![sched0]));
 
 		// Fast forward to half way through the life of sched1.
 		let mut cur_block =
@@ -822,7 +847,7 @@ fn merge_ongoing_and_yet_to_be_started_schedules() {
 		let sched2_per_block = sched2_locked / sched2_duration;
 
 		let sched2 = VestingInfo::new(sched2_locked, sched2_per_block, sched2_start);
-		assert_eq!(VestingStorage::<Test>::get(&2).unwrap(), vec![sched2]);
+		assert_eq!(VestingStorage::<Test>::get(&2).unwrap(), curves(vec![sched2]));
 	});
 }
 
@@ -837,7 +862,7 @@ fn merge_finished_and_ongoing_schedules() {
 			ED, // Vesting over 20 blocks.
 			10,
 		);
-		assert_eq!(VestingStorage::<Test>::get(&2).unwrap(), vec![sched0]);
+		assert_eq!(VestingStorage::<Test>::get(&2).unwrap(), curves(vec![sched0]));
 
 		let sched1 = VestingInfo::new(
 			ED * 40,
@@ -856,7 +881,7 @@ fn merge_finished_and_ongoing_schedules() {
 		assert_ok!(Vesting::vested_transfer(Some(3).into(), 2, sched2));
 
 		// The schedules are in expected order prior to merging.
-		assert_eq!(VestingStorage::<Test>::get(&2).unwrap(), vec![sched0, sched1, sched2]);
+		assert_eq!(VestingStorage::<Test>::get(&2).unwrap(), curves(vec![sched0, sched1, sched2]));
 
 		// Fast forward to sched0's end block.
 		let cur_block = sched0.ending_block_as_balance::<Identity>();
@@ -871,7 +896,7 @@ fn merge_finished_and_ongoing_schedules() {
 		// sched2 is now the first, since sched0 & sched1 get filtered out while "merging".
 		// sched1 gets treated like the new merged schedule by getting pushed onto back
 		// of the vesting schedules vec. Note: sched0 finished at the current block.
-		assert_eq!(VestingStorage::<Test>::get(&2).unwrap(), vec![sched2, sched1]);
+		assert_eq!(VestingStorage::<Test>::get(&2).unwrap(), curves(vec![sched2, sched1]));
 
 		// sched0 has finished, so its funds are fully unlocked.
 		let sched0_unlocked_now = sched0.locked();
@@ -899,7 +924,7 @@ fn merge_finishing_schedules_does_not_create_a_new_one() {
 			ED, // 20 block duration.
 			10,
 		);
-		assert_eq!(VestingStorage::<Test>::get(&2).unwrap(), vec![sched0]);
+		assert_eq!(VestingStorage::<Test>::get(&2).unwrap(), curves(vec![sched0]));
 
 		// Create sched1 and transfer it to account 2.
 		let sched1 = VestingInfo::new(
@@ -908,7 +933,7 @@ fn merge_finishing_schedules_does_not_create_a_new_one() {
 			10,
 		);
 		assert_ok!(Vesting::vested_transfer(Some(3).into(), 2, sched1));
-		assert_eq!(VestingStorage::<Test>::get(&2).unwrap(), vec![sched0, sched1]);
+		assert_eq!(VestingStorage::<Test>::get(&2).unwrap(), curves(vec![sched0, sched1]));
 
 		let all_scheds_end = sched0
 			.ending_block_as_balance::<Identity>()
@@ -941,7 +966,7 @@ fn merge_finished_and_yet_to_be_started_schedules() {
 			ED, // 20 block duration.
 			10, // Ends at block 30
 		);
-		assert_eq!(VestingStorage::<Test>::get(&2).unwrap(), vec![sched0]);
+		assert_eq!(VestingStorage::<Test>::get(&2).unwrap(), curves(vec![sched0]));
 
 		let sched1 = VestingInfo::new(
 			ED * 30,
@@ -949,7 +974,7 @@ fn merge_finished_and_yet_to_be_started_schedules() {
 			35,
 		);
 		assert_ok!(Vesting::vested_transfer(Some(13).into(), 2, sched1));
-		assert_eq!(VestingStorage::<Test>::get(&2).unwrap(), vec![sched0, sched1]);
+		assert_eq!(VestingStorage::<Test>::get(&2).unwrap(), curves(vec![sched0, sched1]));
 
 		let sched2 = VestingInfo::new(
 			ED * 40,
@@ -958,7 +983,7 @@ fn merge_finished_and_yet_to_be_started_schedules() {
 		);
 		// Add a 3rd schedule to demonstrate how sched1 shifts.
 		assert_ok!(Vesting::vested_transfer(Some(13).into(), 2, sched2));
-		assert_eq!(VestingStorage::<Test>::get(&2).unwrap(), vec![sched0, sched1, sched2]);
+		assert_eq!(VestingStorage::<Test>::get(&2).unwrap(), curves(vec![sched0, sched1, sched2]));
 
 		System::set_block_number(30);
 
@@ -973,7 +998,7 @@ fn merge_finished_and_yet_to_be_started_schedules() {
 
 		// sched0 is removed since it finished, and sched1 is removed and then pushed on the back
 		// because it is treated as the merged schedule
-		assert_eq!(VestingStorage::<Test>::get(&2).unwrap(), vec![sched2, sched1]);
+		assert_eq!(VestingStorage::<Test>::get(&2).unwrap(), curves(vec![sched2, sched1]));
 
 		// The usable balance is updated because merging fully unlocked sched0.
 		assert_eq!(Balances::usable_balance(&2), sched0.locked());
@@ -989,7 +1014,7 @@ fn merge_schedules_throws_proper_errors() {
 			ED, // 20 block duration.
 			10,
 		);
-		assert_eq!(VestingStorage::<Test>::get(&2).unwrap(), vec![sched0]);
+		assert_eq!(VestingStorage::<Test>::get(&2).unwrap(), curves(vec![sched0]));
 
 		// Account 2 only has 1 vesting schedule.
 		assert_noop!(
@@ -1003,7 +1028,7 @@ fn merge_schedules_throws_proper_errors() {
 
 		// There are enough schedules to merge but an index is non-existent.
 		Vesting::vested_transfer(Some(3).into(), 2, sched0).unwrap();
-		assert_eq!(VestingStorage::<Test>::get(&2).unwrap(), vec![sched0, sched0]);
+		assert_eq!(VestingStorage::<Test>::get(&2).unwrap(), curves(vec![sched0, sched0]));
 		assert_noop!(
 			Vesting::merge_schedules(Some(2).into(), 0, 2),
 			Error::<Test>::ScheduleIndexOutOfBounds
@@ -1036,18 +1061,21 @@ fn generates_multiple_schedules_from_genesis_config() {
 		.build()
 		.execute_with(|| {
 			let user1_sched1 = VestingInfo::new(5 * ED, 128, 0u64);
-			assert_eq!(VestingStorage::<Test>::get(&1).unwrap(), vec![user1_sched1]);
+			assert_eq!(VestingStorage::<Test>::get(&1).unwrap(), curves(vec![user1_sched1]));
 
 			let user2_sched1 = VestingInfo::new(1 * ED, 12, 10u64);
 			let user2_sched2 = VestingInfo::new(2 * ED, 25, 10u64);
-			assert_eq!(VestingStorage::<Test>::get(&2).unwrap(), vec![user2_sched1, user2_sched2]);
+			assert_eq!(
+				VestingStorage::<Test>::get(&2).unwrap(),
+				curves(vec![user2_sched1, user2_sched2])
+			);
 
 			let user12_sched1 = VestingInfo::new(1 * ED, 12, 10u64);
 			let user12_sched2 = VestingInfo::new(2 * ED, 25, 10u64);
 			let user12_sched3 = VestingInfo::new(3 * ED, 38, 10u64);
 			assert_eq!(
 				VestingStorage::<Test>::get(&12).unwrap(),
-				vec![user12_sched1, user12_sched2, user12_sched3]
+				curves(vec![user12_sched1, user12_sched2, user12_sched3])
 			);
 		});
 }
@@ -1066,9 +1094,9 @@ fn multiple_schedules_from_genesis_config_errors() {
 }
 
 #[test]
-fn build_genesis_has_storage_version_v1() {
+fn build_genesis_has_storage_version_v2() {
 	ExtBuilder::default().existential_deposit(ED).build().execute_with(|| {
-		assert_eq!(StorageVersion::<Test>::get(), Releases::V1);
+		assert_eq!(StorageVersion::<Test>::get(), Releases::V2);
 	});
 }
 
@@ -1108,6 +1136,19 @@ fn vesting_info_validate_works() {
 	assert_eq!(VestingInfo::new(min_transfer, 1u64, 10u64).is_valid(), true);
 }
 
+#[test]
+fn vesting_curve_validate_works() {
+	let schedule = VestingInfo::new(ED, 1u64, 10u64);
+
+	// `period` cannot be 0.
+	assert_eq!(VestingCurve::new(schedule).with_period(0).is_valid(), false);
+	// The schedule it is shaped from must be valid.
+	assert_eq!(VestingCurve::new(VestingInfo::new(ED, 0u64, 10u64)).is_valid(), false);
+
+	// With valid inputs it does not error.
+	assert_eq!(VestingCurve::new(schedule).with_cliff(20).with_period(5).is_valid(), true);
+}
+
 #[test]
 fn vesting_info_ending_block_as_balance_works() {
 	// Treats `per_block` 0 as 1.
@@ -1139,6 +1180,25 @@ fn vesting_info_ending_block_as_balance_works() {
 	);
 }
 
+#[test]
+fn vesting_curve_ending_block_as_balance_works() {
+	// The last tranche unlocks at the end of a whole period.
+	let periodic = VestingCurve::new(VestingInfo::new(1000u32, 30u32, 10u32)).with_period(8);
+	assert_eq!(periodic.ending_block_as_balance::<Identity>(), 10 + 40);
+	assert_eq!(periodic.locked_at::<Identity>(49), 1000 - 32 * 30);
+	assert_eq!(periodic.locked_at::<Identity>(50), 0);
+
+	// A cliff after the linear end holds everything back until then.
+	let late_cliff = VestingCurve::new(VestingInfo::new(1000u32, 100u32, 10u32)).with_cliff(30);
+	assert_eq!(late_cliff.ending_block_as_balance::<Identity>(), 30);
+	assert_eq!(late_cliff.locked_at::<Identity>(29), 1000);
+	assert_eq!(late_cliff.locked_at::<Identity>(30), 0);
+
+	// An earlier cliff does not change the end.
+	let early_cliff = VestingCurve::new(VestingInfo::new(1000u32, 100u32, 10u32)).with_cliff(15);
+	assert_eq!(early_cliff.ending_block_as_balance::<Identity>(), 20);
+}
+
 #[test]
 fn per_block_works() {
 	let per_block_0 = VestingInfo::new(256u32, 0u32, 10u32);
@@ -1169,10 +1229,13 @@ fn vested_transfer_less_than_existential_deposit_fails() {
 		);
 
 		// vested_transfer fails.
-		assert_noop!(Vesting::vested_transfer(Some(3).into(), 99, sched), TokenError::BelowMinimum,);
+		assert_noop!(
+			Vesting::vested_transfer_with_curve(Some(3).into(), 99, sched),
+			TokenError::BelowMinimum,
+		);
 		// force_vested_transfer fails.
 		assert_noop!(
-			Vesting::force_vested_transfer(RawOrigin::Root.into(), 3, 99, sched),
+			Vesting::force_vested_transfer_with_curve(RawOrigin::Root.into(), 3, 99, sched),
 			TokenError::BelowMinimum,
 		);
 	});
@@ -1191,7 +1254,7 @@ fn remove_vesting_schedule() {
 			(ED * 5) / 20, // Vesting over 20 blocks
 			10,
 		);
-		assert_ok!(Vesting::vested_transfer(Some(3).into(), 4, new_vesting_schedule));
+		assert_ok!(Vesting::vested_transfer_with_curve(Some(3).into(), 4, new_vesting_schedule));
 		// Verify that the last events are `VestingCreated/VestingUpdated`.
 		assert_eq!(
 			vesting_events_since_last_call(),
@@ -1202,7 +1265,7 @@ fn remove_vesting_schedule() {
 		);
 
 		// Now account 4 should have vesting.
-		assert_eq!(VestingStorage::<Test>::get(&4).unwrap(), vec![new_vesting_schedule]);
+		assert_eq!(VestingStorage::<Test>::get(&4).unwrap(), curves(vec![new_vesting_schedule]));
 		// Account 4 has 5 * 256 locked.
 		assert_eq!(Vesting::vesting_balance(&4), Some(256 * 5));
 		// Verify only root can call.
@@ -1245,7 +1308,7 @@ fn vested_transfer_impl_works() {
 			(ED * 5) / 20, // Vesting over 20 blocks
 			10,
 		);
-		assert_eq!(VestingStorage::<Test>::get(&4).unwrap(), vec![new_vesting_schedule]);
+		assert_eq!(VestingStorage::<Test>::get(&4).unwrap(), curves(vec![new_vesting_schedule]));
 		// Account 4 has 5 * 256 locked.
 		assert_eq!(Vesting::vesting_balance(&4), Some(256 * 5));
 
@@ -1262,3 +1325,390 @@ fn vested_transfer_impl_works() {
 		);
 	});
 }
+
+#[test]
+fn cliff_schedule_works() {
+	ExtBuilder::default().existential_deposit(ED).build().execute_with(|| {
+		let schedule = VestingCurve::new(VestingInfo::new(
+			ED * 5,
+			64, // Vesting over 20 blocks
+			10,
+		))
+		.with_cliff(20);
+		assert_ok!(Vesting::vested_transfer_with_curve(Some(3).into(), 4, schedule));
+		assert_eq!(VestingStorage::<Test>::get(&4).unwrap(), vec![schedule]);
+
+		// Nothing unlocks before the cliff,
+		System::set_block_number(19);
+		assert_eq!(Vesting::vesting_balance(&4), Some(ED * 5));
+
+		// then everything vested since the start unlocks at once,
+		System::set_block_number(20);
+		assert_eq!(Vesting::vesting_balance(&4), Some(ED * 5 - 10 * 64));
+
+		// and the rest keeps unlocking linearly.
+		System::set_block_number(25);
+		assert_eq!(Vesting::vesting_balance(&4), Some(ED * 5 - 15 * 64));
+		System::set_block_number(30);
+		assert_eq!(Vesting::vesting_balance(&4), Some(0));
+		vest_and_assert_no_vesting::<Test>(4);
+
+		// A lump sum is a cliff where everything has vested by then.
+		let lump_sum = VestingCurve::new(VestingInfo::new(ED * 5, ED * 5, 30)).with_cliff(40);
+		assert_ok!(Vesting::vested_transfer_with_curve(Some(3).into(), 4, lump_sum));
+		System::set_block_number(39);
+		assert_eq!(Vesting::vesting_balance(&4), Some(ED * 5));
+		System::set_block_number(40);
+		assert_eq!(Vesting::vesting_balance(&4), Some(0));
+		vest_and_assert_no_vesting::<Test>(4);
+	});
+}
+
+#[test]
+fn periodic_schedule_works() {
+	ExtBuilder::default().existential_deposit(ED).build().execute_with(|| {
+		// 4 tranches of `ED`, every 8 blocks.
+		let schedule = VestingCurve::new(VestingInfo::new(ED * 4, 32, 10)).with_period(8);
+		assert_ok!(Vesting::vested_transfer_with_curve(Some(3).into(), 4, schedule));
+		assert_eq!(schedule.ending_block_as_balance::<Identity>(), 42);
+
+		System::set_block_number(17);
+		assert_eq!(Vesting::vesting_balance(&4), Some(ED * 4));
+		System::set_block_number(18);
+		assert_eq!(Vesting::vesting_balance(&4), Some(ED * 3));
+		System::set_block_number(25);
+		assert_eq!(Vesting::vesting_balance(&4), Some(ED * 3));
+		System::set_block_number(26);
+		assert_eq!(Vesting::vesting_balance(&4), Some(ED * 2));
+
+		// Unlocking only frees whole tranches.
+		assert_ok!(Vesting::vest(Some(4).into()));
+		assert_eq!(
+			vesting_events_since_last_call().last(),
+			Some(&Event::VestingUpdated { account: 4, unvested: ED * 2 })
+		);
+
+		System::set_block_number(42);
+		assert_eq!(Vesting::vesting_balance(&4), Some(0));
+		vest_and_assert_no_vesting::<Test>(4);
+
+		// A period of 0 is rejected.
+		assert_noop!(
+			Vesting::vested_transfer_with_curve(Some(3).into(), 4, schedule.with_period(0)),
+			Error::<Test>::InvalidScheduleParams
+		);
+	});
+}
+
+#[test]
+fn merge_schedules_with_cliff_and_period() {
+	ExtBuilder::default().existential_deposit(ED).build().execute_with(|| {
+		// Account 2 should already have a vesting schedule.
+		let sched0 = VestingInfo::new(
+			ED * 20,
+			ED, // Vesting over 20 blocks
+			10,
+		);
+		assert_eq!(VestingStorage::<Test>::get(&2).unwrap(), curves(vec![sched0]));
+
+		let sched1 = VestingCurve::new(VestingInfo::new(ED * 10, ED, 10))
+			.with_cliff(15)
+			.with_period(5);
+		assert_ok!(Vesting::vested_transfer_with_curve(Some(13).into(), 2, sched1));
+
+		assert_ok!(Vesting::merge_schedules(Some(2).into(), 0, 1));
+
+		// The merged schedule keeps the latest cliff and the longest period.
+		let merged = VestingCurve::new(VestingInfo::new(ED * 30, ED * 30 / 20, 10))
+			.with_cliff(15)
+			.with_period(5);
+		assert_eq!(VestingStorage::<Test>::get(&2).unwrap(), vec![merged]);
+
+		System::set_block_number(14);
+		assert_eq!(Vesting::vesting_balance(&2), Some(ED * 30));
+		System::set_block_number(19);
+		assert_eq!(Vesting::vesting_balance(&2), Some(ED * 30 - 5 * merged.per_block()));
+		System::set_block_number(20);
+		assert_eq!(Vesting::vesting_balance(&2), Some(ED * 30 - 10 * merged.per_block()));
+	});
+}
+
+#[test]
+fn merge_revocable_schedules_fails() {
+	ExtBuilder::default().existential_deposit(ED).build().execute_with(|| {
+		let revocable = VestingCurve::new(VestingInfo::new(ED * 10, ED, 10)).with_revocable(true);
+		assert_ok!(Vesting::force_vested_transfer_with_curve(
+			RawOrigin::Root.into(),
+			13,
+			2,
+			revocable
+		));
+
+		assert_noop!(
+			Vesting::merge_schedules(Some(2).into(), 0, 1),
+			Error::<Test>::CannotMergeRevocable
+		);
+		assert_noop!(
+			Vesting::merge_schedules(Some(2).into(), 1, 0),
+			Error::<Test>::CannotMergeRevocable
+		);
+	});
+}
+
+#[test]
+fn revoke_vesting_schedule_works() {
+	ExtBuilder::default().existential_deposit(ED).build().execute_with(|| {
+		let schedule = VestingCurve::new(VestingInfo::new(
+			ED * 5,
+			64, // Vesting over 20 blocks
+			10,
+		))
+		.with_revocable(true);
+		assert_ok!(Vesting::force_vested_transfer_with_curve(
+			RawOrigin::Root.into(),
+			3,
+			4,
+			schedule
+		));
+		// The unvested funds are put on hold.
+		assert_eq!(Balances::free_balance(&4), ED * 40);
+		assert_eq!(held(4), ED * 5);
+		vesting_events_since_last_call();
+
+		// Half of the schedule has vested by block 20.
+		System::set_block_number(20);
+		assert_ok!(Vesting::revoke_vesting_schedule(RawOrigin::Root.into(), 4, 0, 5));
+		assert_eq!(
+			vesting_events_since_last_call(),
+			vec![
+				Event::VestingCompleted { account: 4 },
+				Event::VestingRevoked {
+					account: 4,
+					schedule_index: 0,
+					beneficiary: 5,
+					unvested: 10 * 64,
+				},
+			]
+		);
+
+		// The unvested half went to the beneficiary and the vested half is free.
+		assert_eq!(VestingStorage::<Test>::get(&4), None);
+		assert_eq!(Vesting::vesting_balance(&4), None);
+		assert_eq!(Balances::free_balance(&4), ED * 45 - 10 * 64);
+		assert_eq!(held(4), 0);
+		assert_eq!(Balances::free_balance(&5), 10 * 64);
+		assert_ok!(Balances::transfer_allow_death(Some(4).into(), 3, ED * 45 - 10 * 64));
+	});
+}
+
+#[test]
+fn revoke_vesting_schedule_keeps_other_schedules() {
+	ExtBuilder::default().existential_deposit(ED).build().execute_with(|| {
+		// Account 2 should already have a vesting schedule.
+		let sched0 = VestingInfo::new(
+			ED * 20,
+			ED, // Vesting over 20 blocks
+			10,
+		);
+		let sched1 = VestingCurve::new(VestingInfo::new(ED * 10, ED, 10)).with_revocable(true);
+		assert_ok!(Vesting::force_vested_transfer_with_curve(
+			RawOrigin::Root.into(),
+			13,
+			2,
+			sched1
+		));
+		assert_eq!(VestingStorage::<Test>::get(&2).unwrap(), vec![sched0.into(), sched1]);
+
+		// Nothing has vested yet, so the whole schedule is taken back.
+		assert_ok!(Vesting::revoke_vesting_schedule(RawOrigin::Root.into(), 2, 1, 5));
+		assert_eq!(VestingStorage::<Test>::get(&2).unwrap(), curves(vec![sched0]));
+		assert_eq!(Vesting::vesting_balance(&2), Some(ED * 20));
+		assert_eq!(Balances::free_balance(&2), ED * 20);
+		assert_eq!(Balances::free_balance(&5), ED * 10);
+	});
+}
+
+#[test]
+fn revoke_vesting_schedule_correctly_fails() {
+	ExtBuilder::default().existential_deposit(ED).build().execute_with(|| {
+		let schedule = VestingCurve::new(VestingInfo::new(ED * 5, 64, 10)).with_revocable(true);
+		assert_ok!(Vesting::force_vested_transfer_with_curve(
+			RawOrigin::Root.into(),
+			3,
+			4,
+			schedule
+		));
+
+		// Only `RevokeOrigin` can revoke.
+		assert_noop!(Vesting::revoke_vesting_schedule(Some(3).into(), 4, 0, 3), BadOrigin);
+		// Account 3 has no schedules.
+		assert_noop!(
+			Vesting::revoke_vesting_schedule(RawOrigin::Root.into(), 3, 0, 5),
+			Error::<Test>::NotVesting
+		);
+		assert_noop!(
+			Vesting::revoke_vesting_schedule(RawOrigin::Root.into(), 4, 1, 5),
+			Error::<Test>::ScheduleIndexOutOfBounds
+		);
+		// The schedule of account 1 from genesis is not revocable.
+		assert_noop!(
+			Vesting::revoke_vesting_schedule(RawOrigin::Root.into(), 1, 0, 5),
+			Error::<Test>::NotRevocable
+		);
+	});
+}
+
+#[test]
+fn vested_transfer_rejects_revocable_schedules() {
+	ExtBuilder::default().existential_deposit(ED).build().execute_with(|| {
+		// Revocable schedules cannot be merged, so they could be used to fill up the schedules of
+		// someone else.
+		let schedule = VestingCurve::new(VestingInfo::new(ED * 5, 64, 10)).with_revocable(true);
+		assert_noop!(
+			Vesting::vested_transfer_with_curve(Some(3).into(), 4, schedule),
+			Error::<Test>::RevocableNotAllowed
+		);
+		assert_noop!(
+			Vesting::force_vested_transfer_with_curve(Some(3).into(), 3, 4, schedule),
+			BadOrigin
+		);
+		assert_ok!(Vesting::force_vested_transfer_with_curve(
+			RawOrigin::Root.into(),
+			3,
+			4,
+			schedule
+		));
+	});
+}
+
+#[test]
+fn revocable_schedule_releases_as_it_vests() {
+	ExtBuilder::default().existential_deposit(ED).build().execute_with(|| {
+		// Account 2 should already have a locking vesting schedule.
+		let schedule = VestingCurve::new(VestingInfo::new(
+			ED * 10,
+			ED, // Vesting over 10 blocks
+			10,
+		))
+		.with_revocable(true);
+		assert_ok!(Vesting::force_vested_transfer_with_curve(
+			RawOrigin::Root.into(),
+			13,
+			2,
+			schedule
+		));
+		assert_eq!(held(2), ED * 10);
+		// Only the non-revocable schedule is part of the free balance.
+		assert_eq!(Vesting::vesting_balance(&2), Some(ED * 20));
+		// Locks overlap holds, so the lock covers both schedules.
+		assert_eq!(Balances::locks(&2)[0].amount, ED * 30);
+		assert_noop!(Balances::transfer_allow_death(Some(2).into(), 3, ED), TokenError::Frozen);
+
+		System::set_block_number(15);
+		assert_ok!(Vesting::vest(Some(2).into()));
+		assert_eq!(held(2), ED * 5);
+		assert_eq!(Balances::locks(&2)[0].amount, ED * 20);
+
+		System::set_block_number(30);
+		assert_ok!(Vesting::vest(Some(2).into()));
+		assert_eq!(held(2), 0);
+		assert!(Balances::locks(&2).is_empty());
+		assert_eq!(VestingStorage::<Test>::get(&2), None);
+		assert_eq!(Balances::free_balance(&2), ED * 30);
+	});
+}
+
+#[test]
+fn revoke_vesting_schedule_works_with_locked_funds() {
+	ExtBuilder::default().existential_deposit(ED).build().execute_with(|| {
+		let schedule = VestingCurve::new(VestingInfo::new(ED * 10, ED, 10)).with_revocable(true);
+		assert_ok!(Vesting::force_vested_transfer_with_curve(
+			RawOrigin::Root.into(),
+			13,
+			4,
+			schedule
+		));
+
+		// The target reserves some funds for another pallet, and stakes everything it can.
+		assert_ok!(Balances::reserve(&4, ED));
+		let free = Balances::free_balance(&4);
+		let total = Balances::total_balance(&4);
+		Balances::set_lock(*b"staking ", &4, total, WithdrawReasons::all());
+		assert_noop!(Balances::transfer_allow_death(Some(4).into(), 3, ED), TokenError::Frozen);
+
+		// That doesn't keep the unvested funds from being taken back.
+		System::set_block_number(15);
+		assert_ok!(Vesting::revoke_vesting_schedule(RawOrigin::Root.into(), 4, 0, 5));
+		assert_eq!(Balances::free_balance(&5), ED * 5);
+		// What vested is released, and the other reserve is left alone.
+		assert_eq!(Balances::free_balance(&4), free + ED * 5);
+		assert_eq!(Balances::reserved_balance(&4), ED);
+		assert_eq!(held(4), 0);
+		assert_eq!(VestingStorage::<Test>::get(&4), None);
+	});
+}
+
+#[test]
+fn migrate_v1_to_v2_works() {
+	ExtBuilder::default()
+		.existential_deposit(ED)
+		.vesting_genesis_config(vec![])
+		.build()
+		.execute_with(|| {
+			StorageVersion::<Test>::put(Releases::V1);
+			let old_schedules = |who: u64| {
+				vec![VestingInfo::new(ED * 5, 64, 10 + who), VestingInfo::new(ED * 2, ED, 20)]
+			};
+			for who in 1..=3 {
+				unhashed::put(&VestingStorage::<Test>::hashed_key_for(&who), &old_schedules(who));
+			}
+
+			// Each step migrates the schedules of one account.
+			let mut meter = WeightMeter::with_limit(
+				<Test as frame_system::Config>::DbWeight::get()
+					.reads(1)
+					.saturating_add(MigrateV1ToV2::<Test>::step_weight()),
+			);
+			let mut cursor = MigrateV1ToV2::<Test>::step(None, &mut meter).unwrap();
+			assert!(cursor.is_some());
+			assert_eq!(StorageVersion::<Test>::get(), Releases::V1);
+			let mut steps = 1;
+			while cursor.is_some() {
+				let mut meter = WeightMeter::with_limit(MigrateV1ToV2::<Test>::step_weight());
+				cursor = MigrateV1ToV2::<Test>::step(cursor, &mut meter).unwrap();
+				steps += 1;
+			}
+			// The last step finds no account left.
+			assert_eq!(steps, 4);
+
+			assert_eq!(StorageVersion::<Test>::get(), Releases::V2);
+			for who in 1..=3 {
+				let schedules = VestingStorage::<Test>::get(&who).unwrap();
+				assert_eq!(schedules, curves(old_schedules(who)));
+				// The migrated schedules keep unlocking linearly.
+				assert!(schedules.iter().all(|s| s.cliff() == 0 && s.period() == 1));
+				assert!(schedules.iter().all(|s| !s.is_revocable()));
+			}
+
+			// Running it again is a no-op.
+			let mut meter = WeightMeter::new();
+			assert_storage_noop!(assert_eq!(
+				MigrateV1ToV2::<Test>::step(None, &mut meter).unwrap(),
+				None
+			));
+		});
+}
+
+#[test]
+fn migrate_v1_to_v2_needs_weight_for_one_step() {
+	ExtBuilder::default().existential_deposit(ED).build().execute_with(|| {
+		StorageVersion::<Test>::put(Releases::V1);
+
+		let limit = MigrateV1ToV2::<Test>::step_weight().saturating_sub(Weight::from_parts(0, 1));
+		let mut meter = WeightMeter::with_limit(limit);
+		assert!(matches!(
+			MigrateV1ToV2::<Test>::step(None, &mut meter),
+			Err(SteppedMigrationError::InsufficientWeight { .. })
+		));
+	});
+}
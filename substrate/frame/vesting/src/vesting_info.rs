@@ -15,7 +15,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-//! Module to enforce private fields on `VestingInfo`.
+//! Module to enforce private fields on `VestingInfo` and `VestingCurve`.
 
 use super::*;
 
@@ -123,3 +123,150 @@ where
 		starting_block.saturating_add(duration)
 	}
 }
+
+/// A vesting schedule whose linear unlock is shaped by a cliff and a period, and which may be
+/// revocable.
+///
+/// A [`VestingInfo`] is the curve without a cliff, unlocking every block and irrevocable.
+#[derive(
+	Encode,
+	Decode,
+	DecodeWithMemTracking,
+	Copy,
+	Clone,
+	PartialEq,
+	Eq,
+	RuntimeDebug,
+	MaxEncodedLen,
+	TypeInfo,
+)]
+pub struct VestingCurve<Balance, BlockNumber> {
+	/// The linear unlock the curve is shaped from.
+	schedule: VestingInfo<Balance, BlockNumber>,
+	/// Block before which nothing unlocks. Whatever has vested by then unlocks at once.
+	cliff: BlockNumber,
+	/// Number of blocks between two unlocks. Funds vested within a period unlock at its end.
+	period: BlockNumber,
+	/// Whether `RevokeOrigin` may cancel the schedule and take back the unvested funds.
+	revocable: bool,
+}
+
+impl<Balance, BlockNumber> From<VestingInfo<Balance, BlockNumber>>
+	for VestingCurve<Balance, BlockNumber>
+where
+	Balance: AtLeast32BitUnsigned + Copy,
+	BlockNumber: AtLeast32BitUnsigned + Copy + Bounded,
+{
+	fn from(schedule: VestingInfo<Balance, BlockNumber>) -> Self {
+		Self::new(schedule)
+	}
+}
+
+impl<Balance, BlockNumber> VestingCurve<Balance, BlockNumber>
+where
+	Balance: AtLeast32BitUnsigned + Copy,
+	BlockNumber: AtLeast32BitUnsigned + Copy + Bounded,
+{
+	/// Instantiate a new `VestingCurve` unlocking as `schedule`.
+	pub fn new(schedule: VestingInfo<Balance, BlockNumber>) -> VestingCurve<Balance, BlockNumber> {
+		VestingCurve { schedule, cliff: Zero::zero(), period: One::one(), revocable: false }
+	}
+
+	/// Keep everything locked until block `cliff`.
+	pub fn with_cliff(mut self, cliff: BlockNumber) -> Self {
+		self.cliff = cliff;
+		self
+	}
+
+	/// Unlock the vested funds in tranches every `period` blocks, instead of every block.
+	pub fn with_period(mut self, period: BlockNumber) -> Self {
+		self.period = period;
+		self
+	}
+
+	/// Set whether `RevokeOrigin` may revoke the schedule.
+	pub fn with_revocable(mut self, revocable: bool) -> Self {
+		self.revocable = revocable;
+		self
+	}
+
+	/// Validate parameters for `VestingCurve`. Note that this does not check
+	/// against `MinVestedTransfer`.
+	pub fn is_valid(&self) -> bool {
+		self.schedule.is_valid() && !self.period.is_zero()
+	}
+
+	/// The linear unlock the curve is shaped from.
+	pub fn schedule(&self) -> &VestingInfo<Balance, BlockNumber> {
+		&self.schedule
+	}
+
+	/// Locked amount at schedule creation.
+	pub fn locked(&self) -> Balance {
+		self.schedule.locked()
+	}
+
+	/// Amount that vests every block after `starting_block`, see [`VestingInfo::per_block`].
+	pub fn per_block(&self) -> Balance {
+		self.schedule.per_block()
+	}
+
+	/// Starting block for vesting.
+	pub fn starting_block(&self) -> BlockNumber {
+		self.schedule.starting_block()
+	}
+
+	/// Block before which nothing unlocks.
+	pub fn cliff(&self) -> BlockNumber {
+		self.cliff
+	}
+
+	/// Number of blocks between two unlocks. Corrects for `period` of 0, which would otherwise
+	/// never unlock anything.
+	pub fn period(&self) -> BlockNumber {
+		self.period.max(One::one())
+	}
+
+	/// Whether `RevokeOrigin` may revoke the schedule.
+	pub fn is_revocable(&self) -> bool {
+		self.revocable
+	}
+
+	/// Amount locked at block `n`.
+	pub fn locked_at<BlockNumberToBalance: Convert<BlockNumber, Balance>>(
+		&self,
+		n: BlockNumber,
+	) -> Balance {
+		// Nothing unlocks before the cliff.
+		if n < self.cliff {
+			return self.locked()
+		}
+		// Only whole periods count, so the funds unlock in tranches.
+		let vested_block_count = n.saturating_sub(self.starting_block());
+		let vested_block_count = vested_block_count - vested_block_count % self.period();
+		self.schedule.locked_at::<BlockNumberToBalance>(
+			self.starting_block().saturating_add(vested_block_count),
+		)
+	}
+
+	/// Block number at which the schedule ends (as type `Balance`).
+	pub fn ending_block_as_balance<BlockNumberToBalance: Convert<BlockNumber, Balance>>(
+		&self,
+	) -> Balance {
+		let starting_block = BlockNumberToBalance::convert(self.starting_block());
+		let duration = self
+			.schedule
+			.ending_block_as_balance::<BlockNumberToBalance>()
+			.saturating_sub(starting_block);
+		// Funds only unlock at the end of a period, so the last tranche may come a bit later.
+		let period = BlockNumberToBalance::convert(self.period());
+		let duration = match duration % period {
+			remainder if remainder.is_zero() => duration,
+			remainder => duration.saturating_add(period - remainder),
+		};
+
+		starting_block
+			.saturating_add(duration)
+			.max(BlockNumberToBalance::convert(self.cliff))
+	}
+}